@@ -0,0 +1,10 @@
+function buildString(rows) {
+  var html = "<table>\n";
+  for (var i = 0; i < rows; i++) {
+    html += "  <tr><td>" + i + "</td><td>row " + i + "</td></tr>\n";
+  }
+  html += "</table>\n";
+
+  // Reading a code unit forces the string to be flattened.
+  return html.charCodeAt(html.length - 1);
+}
@@ -1,7 +1,7 @@
 //! Benchmarks of the whole execution engine in Boa.
 
 use boa::{exec::Executable, realm::Realm, syntax::Parser, Context};
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 
 #[cfg(all(target_arch = "x86_64", target_os = "linux", target_env = "gnu"))]
 #[cfg_attr(
@@ -226,6 +226,33 @@ fn string_concat(c: &mut Criterion) {
     });
 }
 
+static STRING_BUILDING: &str = include_str!("bench_scripts/string_building.js");
+
+fn string_building(c: &mut Criterion) {
+    let mut context = Context::new();
+
+    // Define the `buildString` function.
+    Parser::new(STRING_BUILDING.as_bytes(), false)
+        .parse_all()
+        .unwrap()
+        .run(&mut context)
+        .unwrap();
+
+    // The time per row should stay the same as the number of rows grows.
+    let mut group = c.benchmark_group("String building (Execution)");
+    for rows in [1_000, 10_000, 100_000] {
+        let nodes = Parser::new(format!("buildString({})", rows).as_bytes(), false)
+            .parse_all()
+            .unwrap();
+
+        group.throughput(Throughput::Elements(rows));
+        group.bench_function(BenchmarkId::from_parameter(rows), |b| {
+            b.iter(|| black_box(&nodes).run(&mut context).unwrap())
+        });
+    }
+    group.finish();
+}
+
 static STRING_COMPARE: &str = include_str!("bench_scripts/string_compare.js");
 
 fn string_compare(c: &mut Criterion) {
@@ -347,6 +374,7 @@ criterion_group!(
     regexp_literal,
     regexp,
     string_concat,
+    string_building,
     string_compare,
     string_copy,
     number_object_access,
@@ -68,10 +68,10 @@ fn main() -> Result<(), JsValue> {
 
             // We create a new message from our captured variable.
             let message = JsString::concat_array(&[
-                "message from `",
-                name.to_string(context)?.as_str(),
-                "`: ",
-                captures.greeting.as_str(),
+                &JsString::from("message from `"),
+                &name.to_string(context)?,
+                &JsString::from("`: "),
+                &captures.greeting,
             ]);
 
             // We can also mutate the moved data inside the closure.
@@ -20,7 +20,7 @@ use crate::{
     context::StandardObjects,
     object::{
         internal_methods::get_prototype_from_constructor, ConstructorBuilder, FunctionBuilder,
-        JsObject, ObjectData,
+        JsObject, Object, ObjectData,
     },
     property::{Attribute, PropertyDescriptor, PropertyNameKind},
     symbol::WellKnownSymbols,
@@ -271,6 +271,66 @@ impl Array {
             .into()
     }
 
+    /// Returns `true` if the prototypes of `object` have no elements and ordinary internal
+    /// methods, so that the holes of `object` are absent properties, and adding an element to
+    /// `object` cannot run a setter.
+    fn has_plain_prototypes(object: &Object) -> bool {
+        let mut prototype = object.prototype().clone();
+        while let Some(parent) = prototype {
+            let parent = parent.borrow();
+            if !parent.has_ordinary_get() || parent.properties().has_index_properties() {
+                return false;
+            }
+            prototype = parent.prototype().clone();
+        }
+        true
+    }
+
+    /// Gets the element `index` of `o` without `[[HasProperty]]` and `[[Get]]`, if `o` is an
+    /// array in dense mode with plain prototypes.
+    ///
+    /// Returns `Some(None)` if the element is absent.
+    fn dense_get(o: &JsObject, index: usize) -> Option<Option<JsValue>> {
+        let o = o.borrow();
+        if !o.is_array() || !Self::has_plain_prototypes(&o) {
+            return None;
+        }
+        let elements = o.properties().dense_elements()?;
+        Some(elements.get(index).cloned().flatten())
+    }
+
+    /// Gets the dense elements of `o` and its `length`, if `o` is an extensible array with plain
+    /// prototypes, whose elements below `length` can then be set and deleted in place.
+    fn dense_elements_mut(o: &mut Object) -> Option<(&mut Vec<Option<JsValue>>, usize)> {
+        if !o.is_array() || !o.extensible() || !Self::has_plain_prototypes(o) {
+            return None;
+        }
+        let length = o.properties().get(&"length".into())?;
+        let length = length.value().and_then(JsValue::as_number)? as usize;
+        let elements = o.properties_mut().dense_elements_mut()?;
+        Some((elements, length))
+    }
+
+    /// Defines the element `index` of `o` as a writable, enumerable and configurable data property
+    /// without `[[DefineOwnProperty]]`, if `o` has dense elements that can be set in place and
+    /// `index` is below its `length`.
+    fn dense_define(o: &JsObject, index: usize, value: &JsValue) -> bool {
+        let mut o = o.borrow_mut();
+        match Self::dense_elements_mut(&mut o) {
+            Some((_, length)) if index < length => {}
+            _ => return false,
+        }
+        o.insert(
+            index,
+            PropertyDescriptor::builder()
+                .value(value.clone())
+                .writable(true)
+                .enumerable(true)
+                .configurable(true),
+        );
+        true
+    }
+
     /// Utility function for concatenating array objects.
     ///
     /// Returns a Boolean valued property that if `true` indicates that
@@ -612,6 +672,31 @@ impl Array {
                 "the length + the number of arguments exceed the maximum safe integer limit",
             );
         }
+        // The elements of a dense array without holes at its end are appended in place, since
+        // setting them could not run any code.
+        {
+            let mut object = o.borrow_mut();
+            let writable_length = matches!(
+                object.properties().get(&"length".into()),
+                Some(length) if length.expect_writable()
+            );
+            if let Some((elements, length)) = Self::dense_elements_mut(&mut object) {
+                if writable_length && length == elements.len() && len + arg_count <= u32::MAX as u64
+                {
+                    elements.extend(args.iter().cloned().map(Some));
+                    let len = elements.len();
+                    object.insert(
+                        "length",
+                        PropertyDescriptor::builder()
+                            .value(len)
+                            .writable(true)
+                            .enumerable(false)
+                            .configurable(false),
+                    );
+                    return Ok(len.into());
+                }
+            }
+        }
         // 5. For each element E of items, do
         for element in args.iter().cloned() {
             // a. Perform ? Set(O, ! ToString(𝔽(len)), E, true).
@@ -739,13 +824,13 @@ impl Array {
         };
 
         // 5. Let R be the empty String.
-        let mut r = String::new();
+        let mut r = Vec::new();
         // 6. Let k be 0.
         // 7. Repeat, while k < len,
         for k in 0..len {
             // a. If k > 0, set R to the string-concatenation of R and sep.
             if k > 0 {
-                r.push_str(&separator);
+                r.extend_from_slice(&separator);
             }
             // b. Let element be ? Get(O, ! ToString(𝔽(k))).
             let element = o.get(k, context)?;
@@ -756,7 +841,7 @@ impl Array {
                 element.to_string(context)?
             };
             // d. Set R to the string-concatenation of R and next.
-            r.push_str(&next);
+            r.extend_from_slice(&next);
             // e. Set k to k + 1.
         }
         // 8. Return R.
@@ -1091,16 +1176,21 @@ impl Array {
         for k in 0..len {
             // a. Let Pk be ! ToString(𝔽(k)).
             // b. Let k_present be ? HasProperty(O, Pk).
-            let k_present = o.has_property(k, context)?;
             // c. If k_present is true, then
-            if k_present {
-                // i. Let kValue be ? Get(O, Pk).
-                let k_value = o.get(k, context)?;
+            //     i. Let kValue be ? Get(O, Pk).
+            let k_value = match Self::dense_get(&o, k) {
+                Some(k_value) => k_value,
+                None if o.has_property(k, context)? => Some(o.get(k, context)?),
+                None => None,
+            };
+            if let Some(k_value) = k_value {
                 // ii. Let mappedValue be ? Call(callbackfn, thisArg, « kValue, 𝔽(k), O »).
                 let mapped_value =
                     callback.call(this_arg, &[k_value, k.into(), this.into()], context)?;
                 // iii. Perform ? CreateDataPropertyOrThrow(A, Pk, mappedValue).
-                a.create_data_property_or_throw(k, mapped_value, context)?;
+                if !Self::dense_define(&a, k, &mapped_value) {
+                    a.create_data_property_or_throw(k, mapped_value, context)?;
+                }
             }
             // d. Set k to k + 1.
         }
@@ -2152,23 +2242,46 @@ impl Array {
         // 3. Let len be ? LengthOfArrayLike(obj).
         let length = obj.length_of_array_like(context)?;
 
-        // 4. Let items be a new empty List.
-        let mut items = Vec::with_capacity(length);
+        // The elements of a dense array are read directly, since getting them could not run any
+        // code.
+        let dense_items = {
+            let object = obj.borrow();
+            if object.is_array() && Self::has_plain_prototypes(&object) {
+                object.properties().dense_elements().map(|elements| {
+                    elements
+                        .iter()
+                        .take(length)
+                        .flatten()
+                        .cloned()
+                        .collect::<Vec<_>>()
+                })
+            } else {
+                None
+            }
+        };
 
-        // 5. Let k be 0.
-        // 6. Repeat, while k < len,
-        for k in 0..length {
-            // a. Let Pk be ! ToString(𝔽(k)).
-            // b. Let kPresent be ? HasProperty(obj, Pk).
-            // c. If kPresent is true, then
-            if obj.has_property(k, context)? {
-                // i. Let kValue be ? Get(obj, Pk).
-                let kval = obj.get(k, context)?;
-                // ii. Append kValue to items.
-                items.push(kval);
+        // 4. Let items be a new empty List.
+        let mut items = if let Some(items) = dense_items {
+            items
+        } else {
+            let mut items = Vec::with_capacity(length);
+
+            // 5. Let k be 0.
+            // 6. Repeat, while k < len,
+            for k in 0..length {
+                // a. Let Pk be ! ToString(𝔽(k)).
+                // b. Let kPresent be ? HasProperty(obj, Pk).
+                // c. If kPresent is true, then
+                if obj.has_property(k, context)? {
+                    // i. Let kValue be ? Get(obj, Pk).
+                    let kval = obj.get(k, context)?;
+                    // ii. Append kValue to items.
+                    items.push(kval);
+                }
+                // d. Set k to k + 1.
             }
-            // d. Set k to k + 1.
-        }
+            items
+        };
 
         // 7. Let itemCount be the number of elements in items.
         let item_count = items.len();
@@ -2189,6 +2302,20 @@ impl Array {
         });
         sort_err?;
 
+        // The sorted elements replace the elements of a dense array in place, as long as the
+        // comparison function did not make it sparse or change its length. This also deletes the
+        // elements from `itemCount` to `len`.
+        let sorted_in_place = match Self::dense_elements_mut(&mut obj.borrow_mut()) {
+            Some((elements, len)) if len == length => {
+                *elements = items.drain(..).map(Some).collect();
+                true
+            }
+            _ => false,
+        };
+        if sorted_in_place {
+            return Ok(obj.into());
+        }
+
         // 9. Let j be 0.
         // 10. Repeat, while j < itemCount,
         for (j, item) in items.into_iter().enumerate() {
@@ -1569,3 +1569,57 @@ fn array_sort() {
         "\"1,5,9,40,80,200,700\""
     );
 }
+
+#[test]
+fn dense_array_fast_paths() {
+    let mut context = Context::new();
+    let init = r#"
+        var holes = [3, 2, 1];
+        delete holes[1];
+        holes.length = 5;
+        var pushed = [];
+        for (var i = 0; i < 5; i++) { pushed.push(i, -i); }
+        Array.prototype[1] = 'inherited';
+        var inherited = [3, 2, 1];
+        delete inherited[1];
+        var inheritedSorted = inherited.slice().sort();
+        inherited = inherited.map(v => v + '!');
+        delete Array.prototype[1];
+        var frozen = Object.freeze([1, 2]);
+        var shrunk = [5, 4, 3, 2, 1];
+        shrunk.sort((a, b) => { shrunk.length = 2; return a - b; });
+    "#;
+    forward(&mut context, init);
+
+    assert_eq!(forward(&mut context, "pushed.length"), "10");
+    assert_eq!(
+        forward(&mut context, "pushed.join()"),
+        "\"0,0,1,-1,2,-2,3,-3,4,-4\""
+    );
+    assert_eq!(
+        forward(&mut context, "holes.map(v => v * 2).join()"),
+        "\"6,,2,,\""
+    );
+    assert_eq!(forward(&mut context, "1 in holes.map(v => v)"), "false");
+    assert_eq!(forward(&mut context, "holes.sort().join()"), "\"1,3,,,\"");
+    assert_eq!(forward(&mut context, "holes.length"), "5");
+    assert_eq!(forward(&mut context, "2 in holes"), "false");
+    assert_eq!(
+        forward(&mut context, "inherited.join()"),
+        "\"3!,inherited!,1!\""
+    );
+    assert_eq!(
+        forward(&mut context, "inheritedSorted.join()"),
+        "\"1,3,inherited\""
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "try { frozen.push(3); } catch (e) { e.constructor.name }"
+        ),
+        "\"TypeError\""
+    );
+    assert_eq!(forward(&mut context, "frozen.length"), "2");
+    assert_eq!(forward(&mut context, "shrunk.length"), "5");
+    assert_eq!(forward(&mut context, "shrunk.join()"), "\"1,2,3,4,5\"");
+}
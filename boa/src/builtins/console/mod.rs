@@ -63,6 +63,7 @@ pub fn formatter(data: &[JsValue], context: &mut Context) -> JsResult<String> {
         _ => {
             let mut formatted = String::new();
             let mut arg_index = 1;
+            let target = target.to_std_string_lossy();
             let mut chars = target.chars();
             while let Some(c) = chars.next() {
                 if c == '%' {
@@ -101,7 +102,7 @@ pub fn formatter(data: &[JsValue], context: &mut Context) -> JsResult<String> {
                                 .cloned()
                                 .unwrap_or_default()
                                 .to_string(context)?;
-                            formatted.push_str(&arg);
+                            formatted.push_str(&arg.to_std_string_lossy());
                             arg_index += 1
                         }
                         '%' => formatted.push('%'),
@@ -495,7 +496,7 @@ impl Console {
             None => "default".into(),
         };
 
-        if let Some(t) = context.console_mut().timer_map.remove(label.as_str()) {
+        if let Some(t) = context.console_mut().timer_map.remove(&label) {
             let time = Self::system_time_in_ms();
             logger(
                 LogMessage::Info(format!("{}: {} ms - timer removed", label, time - t)),
@@ -0,0 +1,73 @@
+//! Host hooks that supply the current time and the local time zone to `Date`.
+//!
+//! By default, a [`Context`][crate::Context] uses the clock and time zone of the host system through
+//! [`SystemClock`]. Embedders can install their own [`Clock`] with
+//! [`Context::set_clock`][crate::Context::set_clock], for example to run scripts deterministically in tests, to pin
+//! the time zone of a user, or to reduce the precision of the clock.
+
+use chrono::{prelude::*, LocalResult};
+use std::fmt::Debug;
+
+/// The largest time value, in absolute terms, that `chrono` is able to convert into a date.
+///
+/// Time zone information for dates beyond this range is approximated by the information at the boundary.
+const MAX_CHRONO_TIME_VALUE: f64 = 8.2e15;
+
+/// A source of the current time and of local time zone information.
+///
+/// All times are time values: milliseconds since the epoch, `1970-01-01T00:00:00Z`, ignoring leap seconds.
+pub trait Clock: Debug {
+    /// Returns the current time value.
+    ///
+    /// This is used by `Date.now()` and `new Date()`.
+    fn now(&self) -> f64;
+
+    /// Returns the offset of the local time zone from UTC, in milliseconds, at the time value `t`.
+    ///
+    /// When `is_utc` is `true`, `t` is a UTC time value. Otherwise, `t` is a local time value; if that local time is
+    /// repeated or skipped because of a time zone transition, it must be interpreted using the offset before the
+    /// transition.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-local-time-zone-adjustment
+    fn local_offset(&self, t: f64, is_utc: bool) -> f64;
+
+    /// Returns the name of the local time zone at the UTC time value `t`, if there is one.
+    ///
+    /// The name is appended to the strings returned by `Date.prototype.toString()` and
+    /// `Date.prototype.toTimeString()`, e.g. `Wed Jul 08 2020 09:16:15 GMT+0200 (Central European Summer Time)`.
+    fn time_zone_name(&self, _t: f64) -> Option<String> {
+        None
+    }
+}
+
+/// The default [`Clock`], which uses the clock and time zone of the host system.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> f64 {
+        Utc::now().timestamp_millis() as f64
+    }
+
+    fn local_offset(&self, t: f64, is_utc: bool) -> f64 {
+        let t = t.clamp(-MAX_CHRONO_TIME_VALUE, MAX_CHRONO_TIME_VALUE);
+        let naive = match NaiveDateTime::from_timestamp_opt((t / 1000.0).floor() as i64, 0) {
+            Some(naive) => naive,
+            None => return 0.0,
+        };
+
+        let offset = if is_utc {
+            Local.offset_from_utc_datetime(&naive)
+        } else {
+            match Local.offset_from_local_datetime(&naive) {
+                LocalResult::Single(offset) | LocalResult::Ambiguous(offset, _) => offset,
+                LocalResult::None => Local.offset_from_utc_datetime(&naive),
+            }
+        };
+
+        f64::from(offset.local_minus_utc()) * 1000.0
+    }
+}
@@ -1,6 +1,17 @@
 #[cfg(test)]
 mod tests;
 
+mod clock;
+mod parse;
+mod utils;
+
+pub use self::clock::{Clock, SystemClock};
+
+use self::utils::{
+    date_from_time, hour_from_time, local_time, make_date, make_day, make_full_year, make_time,
+    min_from_time, month_from_time, ms_from_time, sec_from_time, utc_time, week_day,
+    year_from_time, MS_PER_MINUTE,
+};
 use crate::{
     builtins::BuiltIn,
     context::StandardObjects,
@@ -13,42 +24,10 @@ use crate::{
     value::{JsValue, PreferredType},
     BoaProfiler, Context, JsResult, JsString,
 };
-use chrono::{prelude::*, Duration, LocalResult};
-use std::fmt::Display;
+use chrono::prelude::*;
 
 use super::JsArgs;
 
-/// The number of nanoseconds in a millisecond.
-const NANOS_PER_MS: i64 = 1_000_000;
-/// The number of milliseconds in an hour.
-const MILLIS_PER_HOUR: i64 = 3_600_000;
-/// The number of milliseconds in a minute.
-const MILLIS_PER_MINUTE: i64 = 60_000;
-/// The number of milliseconds in a second.
-const MILLIS_PER_SECOND: i64 = 1000;
-
-#[inline]
-fn is_zero_or_normal_opt(value: Option<f64>) -> bool {
-    value
-        .map(|value| value == 0f64 || value.is_normal())
-        .unwrap_or(true)
-}
-
-macro_rules! check_normal_opt {
-    ($($v:expr),+) => {
-        $(is_zero_or_normal_opt($v.into()) &&)+ true
-    };
-}
-
-#[inline]
-fn ignore_ambiguity<T>(result: LocalResult<T>) -> Option<T> {
-    match result {
-        LocalResult::Ambiguous(v, _) => Some(v),
-        LocalResult::Single(v) => Some(v),
-        LocalResult::None => None,
-    }
-}
-
 macro_rules! getter_method {
     ($name:ident) => {{
         fn get_value(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
@@ -56,6 +35,14 @@ macro_rules! getter_method {
         }
         get_value
     }};
+    (local $name:ident) => {{
+        fn get_value(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+            Ok(JsValue::new(
+                this_time_value(this, context)?.$name(context.clock()),
+            ))
+        }
+        get_value
+    }};
     (Self::$name:ident) => {{
         fn get_value(_: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
             Ok(JsValue::new(Date::$name()))
@@ -64,30 +51,18 @@ macro_rules! getter_method {
     }};
 }
 
+/// The internal representation of a `Date` object.
+///
+/// It holds the time value of the date, the number of milliseconds since the epoch, or `None` if the date is invalid
+/// (i.e. NaN). Time values are always integers within ±8.64e15, so they are stored as an `i64`.
 #[derive(Debug, Finalize, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Date(Option<NaiveDateTime>);
-
-impl Display for Date {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self.to_local() {
-            Some(v) => write!(f, "{}", v.format("%a %b %d %Y %H:%M:%S GMT%:z")),
-            _ => write!(f, "Invalid Date"),
-        }
-    }
-}
+pub struct Date(Option<i64>);
 
 unsafe impl Trace for Date {
     // Date is a stack value, it doesn't require tracing.
-    // only safe if `chrono` never implements `Trace` for `NaiveDateTime`
     empty_trace!();
 }
 
-impl Default for Date {
-    fn default() -> Self {
-        Self(Some(Utc::now().naive_utc()))
-    }
-}
-
 impl BuiltIn for Date {
     const NAME: &'static str = "Date";
 
@@ -98,66 +73,70 @@ impl BuiltIn for Date {
     fn init(context: &mut Context) -> JsValue {
         let _timer = BoaProfiler::global().start_event(Self::NAME, "init");
 
-        let date_object = ConstructorBuilder::new(context, Self::constructor)
-            .name(Self::NAME)
-            .length(Self::LENGTH)
-            .method(getter_method!(get_date), "getDate", 0)
-            .method(getter_method!(get_day), "getDay", 0)
-            .method(getter_method!(get_full_year), "getFullYear", 0)
-            .method(getter_method!(get_hours), "getHours", 0)
-            .method(getter_method!(get_milliseconds), "getMilliseconds", 0)
-            .method(getter_method!(get_minutes), "getMinutes", 0)
-            .method(getter_method!(get_month), "getMonth", 0)
-            .method(getter_method!(get_seconds), "getSeconds", 0)
-            .method(getter_method!(get_time), "getTime", 0)
-            .method(getter_method!(get_year), "getYear", 0)
-            .method(Self::get_timezone_offset, "getTimezoneOffset", 0)
-            .method(getter_method!(get_utc_date), "getUTCDate", 0)
-            .method(getter_method!(get_utc_day), "getUTCDay", 0)
-            .method(getter_method!(get_utc_full_year), "getUTCFullYear", 0)
-            .method(getter_method!(get_utc_hours), "getUTCHours", 0)
-            .method(
-                getter_method!(get_utc_milliseconds),
-                "getUTCMilliseconds",
-                0,
-            )
-            .method(getter_method!(get_utc_minutes), "getUTCMinutes", 0)
-            .method(getter_method!(get_utc_month), "getUTCMonth", 0)
-            .method(getter_method!(get_utc_seconds), "getUTCSeconds", 0)
-            .method(Self::set_date, "setDate", 1)
-            .method(Self::set_full_year, "setFullYear", 3)
-            .method(Self::set_hours, "setHours", 4)
-            .method(Self::set_milliseconds, "setMilliseconds", 1)
-            .method(Self::set_minutes, "setMinutes", 3)
-            .method(Self::set_month, "setMonth", 2)
-            .method(Self::set_seconds, "setSeconds", 2)
-            .method(Self::set_year, "setYear", 1)
-            .method(Self::set_time, "setTime", 1)
-            .method(Self::set_utc_date, "setUTCDate", 1)
-            .method(Self::set_utc_full_year, "setUTCFullYear", 3)
-            .method(Self::set_utc_hours, "setUTCHours", 4)
-            .method(Self::set_utc_milliseconds, "setUTCMilliseconds", 1)
-            .method(Self::set_utc_minutes, "setUTCMinutes", 3)
-            .method(Self::set_utc_month, "setUTCMonth", 2)
-            .method(Self::set_utc_seconds, "setUTCSeconds", 2)
-            .method(Self::to_date_string, "toDateString", 0)
-            .method(getter_method!(to_gmt_string), "toGMTString", 0)
-            .method(Self::to_iso_string, "toISOString", 0)
-            .method(Self::to_json, "toJSON", 1)
-            // Locale strings
-            .method(Self::to_string, "toString", 0)
-            .method(Self::to_time_string, "toTimeString", 0)
-            .method(getter_method!(to_utc_string), "toUTCString", 0)
-            .method(getter_method!(value_of), "valueOf", 0)
-            .method(
-                Self::to_primitive,
-                (WellKnownSymbols::to_primitive(), "[Symbol.toPrimitive]"),
-                1,
-            )
-            .static_method(Self::now, "now", 0)
-            .static_method(Self::parse, "parse", 1)
-            .static_method(Self::utc, "UTC", 7)
-            .build();
+        let date_object = ConstructorBuilder::with_standard_object(
+            context,
+            Self::constructor,
+            context.standard_objects().date_object().clone(),
+        )
+        .name(Self::NAME)
+        .length(Self::LENGTH)
+        .method(getter_method!(local get_date), "getDate", 0)
+        .method(getter_method!(local get_day), "getDay", 0)
+        .method(getter_method!(local get_full_year), "getFullYear", 0)
+        .method(getter_method!(local get_hours), "getHours", 0)
+        .method(getter_method!(local get_milliseconds), "getMilliseconds", 0)
+        .method(getter_method!(local get_minutes), "getMinutes", 0)
+        .method(getter_method!(local get_month), "getMonth", 0)
+        .method(getter_method!(local get_seconds), "getSeconds", 0)
+        .method(getter_method!(get_time), "getTime", 0)
+        .method(getter_method!(local get_year), "getYear", 0)
+        .method(Self::get_timezone_offset, "getTimezoneOffset", 0)
+        .method(getter_method!(get_utc_date), "getUTCDate", 0)
+        .method(getter_method!(get_utc_day), "getUTCDay", 0)
+        .method(getter_method!(get_utc_full_year), "getUTCFullYear", 0)
+        .method(getter_method!(get_utc_hours), "getUTCHours", 0)
+        .method(
+            getter_method!(get_utc_milliseconds),
+            "getUTCMilliseconds",
+            0,
+        )
+        .method(getter_method!(get_utc_minutes), "getUTCMinutes", 0)
+        .method(getter_method!(get_utc_month), "getUTCMonth", 0)
+        .method(getter_method!(get_utc_seconds), "getUTCSeconds", 0)
+        .method(Self::set_date, "setDate", 1)
+        .method(Self::set_full_year, "setFullYear", 3)
+        .method(Self::set_hours, "setHours", 4)
+        .method(Self::set_milliseconds, "setMilliseconds", 1)
+        .method(Self::set_minutes, "setMinutes", 3)
+        .method(Self::set_month, "setMonth", 2)
+        .method(Self::set_seconds, "setSeconds", 2)
+        .method(Self::set_year, "setYear", 1)
+        .method(Self::set_time, "setTime", 1)
+        .method(Self::set_utc_date, "setUTCDate", 1)
+        .method(Self::set_utc_full_year, "setUTCFullYear", 3)
+        .method(Self::set_utc_hours, "setUTCHours", 4)
+        .method(Self::set_utc_milliseconds, "setUTCMilliseconds", 1)
+        .method(Self::set_utc_minutes, "setUTCMinutes", 3)
+        .method(Self::set_utc_month, "setUTCMonth", 2)
+        .method(Self::set_utc_seconds, "setUTCSeconds", 2)
+        .method(Self::to_date_string, "toDateString", 0)
+        .method(getter_method!(to_gmt_string), "toGMTString", 0)
+        .method(Self::to_iso_string, "toISOString", 0)
+        .method(Self::to_json, "toJSON", 1)
+        // Locale strings
+        .method(Self::to_string, "toString", 0)
+        .method(Self::to_time_string, "toTimeString", 0)
+        .method(getter_method!(to_utc_string), "toUTCString", 0)
+        .method(getter_method!(value_of), "valueOf", 0)
+        .method(
+            Self::to_primitive,
+            (WellKnownSymbols::to_primitive(), "[Symbol.toPrimitive]"),
+            1,
+        )
+        .static_method(Self::now, "now", 0)
+        .static_method(Self::parse, "parse", 1)
+        .static_method(Self::utc, "UTC", 7)
+        .build();
 
         date_object.into()
     }
@@ -176,28 +155,54 @@ impl Date {
     /// [spec]: https://tc39.es/ecma262/#sec-timeclip
     #[inline]
     pub fn time_clip(time: f64) -> Option<f64> {
-        if time.abs() > 8.64e15 {
-            None
-        } else {
-            Some(time)
+        utils::time_clip(time)
+    }
+
+    /// Creates a `Date` from a time value, clipping it to the valid range.
+    #[inline]
+    pub fn from_time_value(time: f64) -> Self {
+        Self(Self::time_clip(time).map(|time| time as i64))
+    }
+
+    /// Converts the `Date` to the string returned by `Date.prototype.toString()`, in the local time of `clock`.
+    pub fn display(self, clock: &dyn Clock) -> String {
+        match self.utc_time_value() {
+            Some(t) => utils::to_date_string(clock, t),
+            None => "Invalid Date".to_owned(),
         }
     }
 
+    /// Returns the time value of the `Date` in UTC, or `None` if the `Date` is invalid.
+    #[inline]
+    fn utc_time_value(self) -> Option<f64> {
+        self.0.map(|time| time as f64)
+    }
+
+    /// Returns the time value of the `Date` in the local time of `clock`, or `None` if the `Date` is invalid.
+    #[inline]
+    fn local_time_value(self, clock: &dyn Clock) -> Option<f64> {
+        self.utc_time_value().map(|t| local_time(clock, t))
+    }
+
     /// Converts the `Date` to a local `DateTime`.
     ///
-    /// If the `Date` is invalid (i.e. NAN), this function will return `None`.
+    /// If the `Date` is invalid (i.e. NAN) or cannot be represented by `chrono`, this function will return `None`.
     #[inline]
     pub fn to_local(self) -> Option<DateTime<Local>> {
-        self.0
-            .map(|utc| Local::now().timezone().from_utc_datetime(&utc))
+        self.to_utc().map(|utc| utc.with_timezone(&Local))
     }
 
     /// Converts the `Date` to a UTC `DateTime`.
     ///
-    /// If the `Date` is invalid (i.e. NAN), this function will return `None`.
+    /// If the `Date` is invalid (i.e. NAN) or cannot be represented by `chrono`, this function will return `None`.
     pub fn to_utc(self) -> Option<DateTime<Utc>> {
-        self.0
-            .map(|utc| Utc::now().timezone().from_utc_datetime(&utc))
+        self.0.and_then(|time| {
+            let naive = NaiveDateTime::from_timestamp_opt(
+                time.div_euclid(1000),
+                (time.rem_euclid(1000) * 1_000_000) as u32,
+            )?;
+            Some(Utc.from_utc_datetime(&naive))
+        })
     }
 
     /// Optionally sets the individual components of the `Date`.
@@ -215,114 +220,33 @@ impl Date {
         minute: Option<f64>,
         second: Option<f64>,
         millisecond: Option<f64>,
+        clock: &dyn Clock,
     ) {
-        #[inline]
-        fn num_days_in(year: i32, month: u32) -> Option<u32> {
-            let month = month + 1; // zero-based for calculations
-
-            Some(
-                NaiveDate::from_ymd_opt(
-                    match month {
-                        12 => year.checked_add(1)?,
-                        _ => year,
-                    },
-                    match month {
-                        12 => 1,
-                        _ => month + 1,
-                    },
-                    1,
-                )?
-                .signed_duration_since(NaiveDate::from_ymd_opt(year, month, 1)?)
-                .num_days() as u32,
-            )
-        }
-
-        #[inline]
-        fn fix_month(year: i32, month: i32) -> Option<(i32, u32)> {
-            let year = year.checked_add(month / 12)?;
-
-            if month < 0 {
-                let year = year.checked_sub(1)?;
-                let month = (11 + (month + 1) % 12) as u32;
-                Some((year, month))
-            } else {
-                let month = (month % 12) as u32;
-                Some((year, month))
-            }
-        }
-
-        #[inline]
-        fn fix_day(mut year: i32, mut month: i32, mut day: i32) -> Option<(i32, u32, u32)> {
-            loop {
-                if day < 0 {
-                    let (fixed_year, fixed_month) = fix_month(year, month.checked_sub(1)?)?;
-
-                    year = fixed_year;
-                    month = fixed_month as i32;
-                    day += num_days_in(fixed_year, fixed_month)? as i32;
-                } else {
-                    let (fixed_year, fixed_month) = fix_month(year, month)?;
-                    let num_days = num_days_in(fixed_year, fixed_month)? as i32;
-
-                    if day >= num_days {
-                        day -= num_days;
-                        month = month.checked_add(1)?;
-                    } else {
-                        break;
-                    }
-                }
-            }
-
-            let (fixed_year, fixed_month) = fix_month(year, month)?;
-            Some((fixed_year, fixed_month, day as u32))
-        }
-
-        // If any of the args are infinity or NaN, return an invalid date.
-        if !check_normal_opt!(year, month, day, hour, minute, second, millisecond) {
-            self.0 = None;
-            return;
-        }
-
-        let naive = if utc {
-            self.to_utc().map(|dt| dt.naive_utc())
+        let t = if utc {
+            self.utc_time_value()
         } else {
-            self.to_local().map(|dt| dt.naive_local())
+            self.local_time_value(clock)
         };
 
-        self.0 = naive.and_then(|naive| {
-            let year = year.unwrap_or_else(|| naive.year() as f64) as i32;
-            let month = month.unwrap_or_else(|| naive.month0() as f64) as i32;
-            let day = (day.unwrap_or_else(|| naive.day() as f64) as i32).checked_sub(1)?;
-            let hour = hour.unwrap_or_else(|| naive.hour() as f64) as i64;
-            let minute = minute.unwrap_or_else(|| naive.minute() as f64) as i64;
-            let second = second.unwrap_or_else(|| naive.second() as f64) as i64;
-            let millisecond = millisecond
-                .unwrap_or_else(|| naive.nanosecond() as f64 / NANOS_PER_MS as f64)
-                as i64;
-
-            let (year, month, day) = fix_day(year, month, day)?;
-
-            let duration_hour = Duration::milliseconds(hour.checked_mul(MILLIS_PER_HOUR)?);
-            let duration_minute = Duration::milliseconds(minute.checked_mul(MILLIS_PER_MINUTE)?);
-            let duration_second = Duration::milliseconds(second.checked_mul(MILLIS_PER_SECOND)?);
-            let duration_milisecond = Duration::milliseconds(millisecond);
-
-            let duration = duration_hour
-                .checked_add(&duration_minute)?
-                .checked_add(&duration_second)?
-                .checked_add(&duration_milisecond)?;
-
-            NaiveDate::from_ymd_opt(year, month + 1, day + 1)
-                .and_then(|dt| dt.and_hms(0, 0, 0).checked_add_signed(duration))
-                .and_then(|dt| {
-                    if utc {
-                        Some(Utc.from_utc_datetime(&dt).naive_utc())
-                    } else {
-                        ignore_ambiguity(Local.from_local_datetime(&dt)).map(|dt| dt.naive_utc())
-                    }
-                })
-                .filter(|dt| Self::time_clip(dt.timestamp_millis() as f64).is_some())
-        });
+        let t = match t {
+            Some(t) => t,
+            None => return,
+        };
+
+        let year = year.unwrap_or_else(|| year_from_time(t));
+        let month = month.unwrap_or_else(|| month_from_time(t));
+        let day = day.unwrap_or_else(|| date_from_time(t));
+        let hour = hour.unwrap_or_else(|| hour_from_time(t));
+        let minute = minute.unwrap_or_else(|| min_from_time(t));
+        let second = second.unwrap_or_else(|| sec_from_time(t));
+        let millisecond = millisecond.unwrap_or_else(|| ms_from_time(t));
+
+        let date = make_date(
+            make_day(year, month, day),
+            make_time(hour, minute, second, millisecond),
+        );
+
+        *self = Self::from_time_value(if utc { date } else { utc_time(clock, date) });
     }
 
     /// `Date()`
@@ -341,15 +265,12 @@ impl Date {
         context: &mut Context,
     ) -> JsResult<JsValue> {
         if new_target.is_undefined() {
-            Ok(Self::make_date_string())
+            Ok(Self::make_date_string(context))
         } else {
-            let prototype = get_prototype_from_constructor(
-                new_target,
-                StandardObjects::object_object,
-                context,
-            )?;
+            let prototype =
+                get_prototype_from_constructor(new_target, StandardObjects::date_object, context)?;
             Ok(if args.is_empty() {
-                Self::make_date_now(prototype)
+                Self::make_date_now(prototype, context)
             } else if args.len() == 1 {
                 Self::make_date_single(prototype, args, context)?
             } else {
@@ -369,8 +290,9 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date-constructor
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/Date
-    pub(crate) fn make_date_string() -> JsValue {
-        JsValue::new(Local::now().to_rfc3339())
+    pub(crate) fn make_date_string(context: &mut Context) -> JsValue {
+        let now = context.clock().now();
+        JsValue::new(utils::to_date_string(context.clock(), now))
     }
 
     /// `Date()`
@@ -383,8 +305,9 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date-constructor
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/Date
-    pub(crate) fn make_date_now(prototype: JsObject) -> JsObject {
-        JsObject::from_proto_and_data(prototype, ObjectData::date(Date::default()))
+    pub(crate) fn make_date_now(prototype: JsObject, context: &mut Context) -> JsObject {
+        let now = Date::from_time_value(context.clock().now());
+        JsObject::from_proto_and_data(prototype, ObjectData::date(now))
     }
 
     /// `Date(value)`
@@ -404,29 +327,19 @@ impl Date {
     ) -> JsResult<JsObject> {
         let value = &args[0];
         let tv = match this_time_value(value, context) {
-            Ok(dt) => dt.0,
+            Ok(dt) => dt,
             _ => match value.to_primitive(context, PreferredType::Default)? {
-                JsValue::String(ref str) => match chrono::DateTime::parse_from_rfc3339(str) {
-                    Ok(dt) => Some(dt.naive_utc()),
-                    _ => None,
-                },
-                tv => {
-                    let tv = tv.to_number(context)?;
-                    if tv.is_nan() {
-                        None
-                    } else {
-                        let secs = (tv / 1_000f64) as i64;
-                        let nsecs = ((tv % 1_000f64) * 1_000_000f64) as u32;
-                        NaiveDateTime::from_timestamp_opt(secs, nsecs)
-                    }
-                }
+                JsValue::String(ref str) => Date(
+                    parse::parse_date(context.clock(), &str.to_std_string_lossy())
+                        .map(|time| time as i64),
+                ),
+                tv => Date::from_time_value(tv.to_number(context)?),
             },
         };
 
-        let tv = tv.filter(|time| Self::time_clip(time.timestamp_millis() as f64).is_some());
         Ok(JsObject::from_proto_and_data(
             prototype,
-            ObjectData::date(Date(tv)),
+            ObjectData::date(tv),
         ))
     }
 
@@ -445,7 +358,7 @@ impl Date {
         args: &[JsValue],
         context: &mut Context,
     ) -> JsResult<JsObject> {
-        let mut year = args[0].to_number(context)?;
+        let year = args[0].to_number(context)?;
         let month = args[1].to_number(context)?;
         let day = args
             .get(2)
@@ -463,39 +376,14 @@ impl Date {
             .get(6)
             .map_or(Ok(0f64), |value| value.to_number(context))?;
 
-        // If any of the args are infinity or NaN, return an invalid date.
-        if !check_normal_opt!(year, month, day, hour, min, sec, milli) {
-            return Ok(JsObject::from_proto_and_data(
-                prototype,
-                ObjectData::date(Date(None)),
-            ));
-        }
-
-        if (0.0..=99.0).contains(&year) {
-            year += 1900.0
-        }
-
-        let mut date = Self(
-            NaiveDateTime::from_timestamp_opt(0, 0)
-                .and_then(|local| ignore_ambiguity(Local.from_local_datetime(&local)))
-                .map(|local| local.naive_utc())
-                .filter(|time| Self::time_clip(time.timestamp_millis() as f64).is_some()),
-        );
-
-        date.set_components(
-            false,
-            Some(year),
-            Some(month),
-            Some(day),
-            Some(hour),
-            Some(min),
-            Some(sec),
-            Some(milli),
+        let final_date = make_date(
+            make_day(make_full_year(year), month, day),
+            make_time(hour, min, sec, milli),
         );
 
         Ok(JsObject::from_proto_and_data(
             prototype,
-            ObjectData::date(date),
+            ObjectData::date(Date::from_time_value(utc_time(context.clock(), final_date))),
         ))
     }
 
@@ -525,7 +413,11 @@ impl Date {
 
         let hint = args.get_or_undefined(0);
 
-        let try_first = match hint.as_string().map(|s| s.as_str()) {
+        let try_first = match hint
+            .as_string()
+            .map(JsString::to_std_string_lossy)
+            .as_deref()
+        {
             // 3. If hint is "string" or "default", then
             // a. Let tryFirst be string.
             Some("string") | Some("default") => PreferredType::String,
@@ -553,8 +445,9 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getdate
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getDate
-    pub fn get_date(&self) -> f64 {
-        self.to_local().map_or(f64::NAN, |dt| dt.day() as f64)
+    pub fn get_date(&self, clock: &dyn Clock) -> f64 {
+        self.local_time_value(clock)
+            .map_or(f64::NAN, date_from_time)
     }
 
     /// `Date.prototype.getDay()`
@@ -568,12 +461,8 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getday
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getDay
-    pub fn get_day(&self) -> f64 {
-        self.to_local().map_or(f64::NAN, |dt| {
-            let weekday = dt.weekday() as u32;
-            let weekday = (weekday + 1) % 7; // 0 represents Monday in Chrono
-            weekday as f64
-        })
+    pub fn get_day(&self, clock: &dyn Clock) -> f64 {
+        self.local_time_value(clock).map_or(f64::NAN, week_day)
     }
 
     /// `Date.prototype.getFullYear()`
@@ -586,8 +475,9 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getfullyear
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getFullYear
-    pub fn get_full_year(&self) -> f64 {
-        self.to_local().map_or(f64::NAN, |dt| dt.year() as f64)
+    pub fn get_full_year(&self, clock: &dyn Clock) -> f64 {
+        self.local_time_value(clock)
+            .map_or(f64::NAN, year_from_time)
     }
 
     /// `Date.prototype.getHours()`
@@ -600,8 +490,9 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.gethours
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getHours
-    pub fn get_hours(&self) -> f64 {
-        self.to_local().map_or(f64::NAN, |dt| dt.hour() as f64)
+    pub fn get_hours(&self, clock: &dyn Clock) -> f64 {
+        self.local_time_value(clock)
+            .map_or(f64::NAN, hour_from_time)
     }
 
     /// `Date.prototype.getMilliseconds()`
@@ -614,9 +505,8 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getmilliseconds
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getMilliseconds
-    pub fn get_milliseconds(&self) -> f64 {
-        self.to_local()
-            .map_or(f64::NAN, |dt| dt.nanosecond() as f64 / NANOS_PER_MS as f64)
+    pub fn get_milliseconds(&self, clock: &dyn Clock) -> f64 {
+        self.local_time_value(clock).map_or(f64::NAN, ms_from_time)
     }
 
     /// `Date.prototype.getMinutes()`
@@ -629,8 +519,8 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getminutes
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getMinutes
-    pub fn get_minutes(&self) -> f64 {
-        self.to_local().map_or(f64::NAN, |dt| dt.minute() as f64)
+    pub fn get_minutes(&self, clock: &dyn Clock) -> f64 {
+        self.local_time_value(clock).map_or(f64::NAN, min_from_time)
     }
 
     /// `Date.prototype.getMonth()`
@@ -644,8 +534,9 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getmonth
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getMonth
-    pub fn get_month(&self) -> f64 {
-        self.to_local().map_or(f64::NAN, |dt| dt.month0() as f64)
+    pub fn get_month(&self, clock: &dyn Clock) -> f64 {
+        self.local_time_value(clock)
+            .map_or(f64::NAN, month_from_time)
     }
 
     /// `Date.prototype.getSeconds()`
@@ -658,8 +549,8 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getseconds
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getSeconds
-    pub fn get_seconds(&self) -> f64 {
-        self.to_local().map_or(f64::NAN, |dt| dt.second() as f64)
+    pub fn get_seconds(&self, clock: &dyn Clock) -> f64 {
+        self.local_time_value(clock).map_or(f64::NAN, sec_from_time)
     }
 
     /// `Date.prototype.getYear()`
@@ -673,9 +564,9 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getyear
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getYear
-    pub fn get_year(&self) -> f64 {
-        self.to_local()
-            .map_or(f64::NAN, |dt| dt.year() as f64 - 1900f64)
+    pub fn get_year(&self, clock: &dyn Clock) -> f64 {
+        self.local_time_value(clock)
+            .map_or(f64::NAN, |t| year_from_time(t) - 1900f64)
     }
 
     /// `Date.prototype.getTime()`
@@ -689,8 +580,7 @@ impl Date {
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.gettime
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getTime
     pub fn get_time(&self) -> f64 {
-        self.to_utc()
-            .map_or(f64::NAN, |dt| dt.timestamp_millis() as f64)
+        self.utc_time_value().unwrap_or(f64::NAN)
     }
 
     /// `Date.prototype.getTimeZoneOffset()`
@@ -714,14 +604,10 @@ impl Date {
         let t = this_time_value(this, context)?;
 
         // 2. If t is NaN, return NaN.
-        if t.0.is_none() {
-            return Ok(JsValue::nan());
-        }
-
         // 3. Return (t - LocalTime(t)) / msPerMinute.
-        Ok(JsValue::new(
-            -Local::now().offset().local_minus_utc() as f64 / 60f64,
-        ))
+        Ok(JsValue::new(t.utc_time_value().map_or(f64::NAN, |t| {
+            (t - local_time(context.clock(), t)) / MS_PER_MINUTE
+        })))
     }
 
     /// `Date.prototype.getUTCDate()`
@@ -735,7 +621,7 @@ impl Date {
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getutcdate
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getUTCDate
     pub fn get_utc_date(&self) -> f64 {
-        self.to_utc().map_or(f64::NAN, |dt| dt.day() as f64)
+        self.utc_time_value().map_or(f64::NAN, date_from_time)
     }
 
     /// `Date.prototype.getUTCDay()`
@@ -750,11 +636,7 @@ impl Date {
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getutcday
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getUTCDay
     pub fn get_utc_day(&self) -> f64 {
-        self.to_utc().map_or(f64::NAN, |dt| {
-            let weekday = dt.weekday() as u32;
-            let weekday = (weekday + 1) % 7; // 0 represents Monday in Chrono
-            weekday as f64
-        })
+        self.utc_time_value().map_or(f64::NAN, week_day)
     }
 
     /// `Date.prototype.getUTCFullYear()`
@@ -768,7 +650,7 @@ impl Date {
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getutcfullyear
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getUTCFullYear
     pub fn get_utc_full_year(&self) -> f64 {
-        self.to_utc().map_or(f64::NAN, |dt| dt.year() as f64)
+        self.utc_time_value().map_or(f64::NAN, year_from_time)
     }
 
     /// `Date.prototype.getUTCHours()`
@@ -782,7 +664,7 @@ impl Date {
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getutchours
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getUTCHours
     pub fn get_utc_hours(&self) -> f64 {
-        self.to_utc().map_or(f64::NAN, |dt| dt.hour() as f64)
+        self.utc_time_value().map_or(f64::NAN, hour_from_time)
     }
 
     /// `Date.prototype.getUTCMilliseconds()`
@@ -796,8 +678,7 @@ impl Date {
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getutcmilliseconds
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getUTCMilliseconds
     pub fn get_utc_milliseconds(&self) -> f64 {
-        self.to_utc()
-            .map_or(f64::NAN, |dt| dt.nanosecond() as f64 / NANOS_PER_MS as f64)
+        self.utc_time_value().map_or(f64::NAN, ms_from_time)
     }
 
     /// `Date.prototype.getUTCMinutes()`
@@ -811,7 +692,7 @@ impl Date {
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getutcminutes
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getUTCMinutes
     pub fn get_utc_minutes(&self) -> f64 {
-        self.to_utc().map_or(f64::NAN, |dt| dt.minute() as f64)
+        self.utc_time_value().map_or(f64::NAN, min_from_time)
     }
 
     /// `Date.prototype.getUTCMonth()`
@@ -826,7 +707,7 @@ impl Date {
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getutcmonth
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getUTCMonth
     pub fn get_utc_month(&self) -> f64 {
-        self.to_utc().map_or(f64::NAN, |dt| dt.month0() as f64)
+        self.utc_time_value().map_or(f64::NAN, month_from_time)
     }
 
     /// `Date.prototype.getUTCSeconds()`
@@ -840,7 +721,7 @@ impl Date {
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.getutcseconds
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/getUTCSeconds
     pub fn get_utc_seconds(&self) -> f64 {
-        self.to_utc().map_or(f64::NAN, |dt| dt.second() as f64)
+        self.utc_time_value().map_or(f64::NAN, sec_from_time)
     }
 
     /// `Date.prototype.setDate()`
@@ -866,7 +747,17 @@ impl Date {
             .to_number(context)?;
 
         // 3. Let newDate be MakeDate(MakeDay(YearFromTime(t), MonthFromTime(t), dt), TimeWithinDay(t)).
-        t.set_components(false, None, None, Some(dt), None, None, None, None);
+        t.set_components(
+            false,
+            None,
+            None,
+            Some(dt),
+            None,
+            None,
+            None,
+            None,
+            context.clock(),
+        );
 
         // 4. Let u be TimeClip(UTC(newDate)).
         let u = t.get_time();
@@ -899,10 +790,7 @@ impl Date {
 
         // 2. If t is NaN, set t to +0𝔽; otherwise, set t to LocalTime(t).
         if t.0.is_none() {
-            t.0 = NaiveDateTime::from_timestamp_opt(0, 0)
-                .and_then(|local| ignore_ambiguity(Local.from_local_datetime(&local)))
-                .map(|local| local.naive_utc())
-                .filter(|time| Self::time_clip(time.timestamp_millis() as f64).is_some());
+            t = Date::from_time_value(utc_time(context.clock(), 0f64));
         }
 
         // 3. Let y be ? ToNumber(year).
@@ -927,7 +815,17 @@ impl Date {
         };
 
         // 6. Let newDate be MakeDate(MakeDay(y, m, dt), TimeWithinDay(t)).
-        t.set_components(false, Some(y), m, dt, None, None, None, None);
+        t.set_components(
+            false,
+            Some(y),
+            m,
+            dt,
+            None,
+            None,
+            None,
+            None,
+            context.clock(),
+        );
 
         // 7. Let u be TimeClip(UTC(newDate)).
         let u = t.get_time();
@@ -984,7 +882,17 @@ impl Date {
         };
 
         // 6. Let date be MakeDate(Day(t), MakeTime(h, m, s, milli)).
-        t.set_components(false, None, None, None, Some(h), m, sec, milli);
+        t.set_components(
+            false,
+            None,
+            None,
+            None,
+            Some(h),
+            m,
+            sec,
+            milli,
+            context.clock(),
+        );
 
         // 7. Let u be TimeClip(UTC(date)).
         let u = t.get_time();
@@ -1022,7 +930,17 @@ impl Date {
             .to_number(context)?;
 
         // 3. Let time be MakeTime(HourFromTime(t), MinFromTime(t), SecFromTime(t), ms).
-        t.set_components(false, None, None, None, None, None, None, Some(ms));
+        t.set_components(
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(ms),
+            context.clock(),
+        );
 
         // 4. Let u be TimeClip(UTC(MakeDate(Day(t), time))).
         let u = t.get_time();
@@ -1074,7 +992,17 @@ impl Date {
         };
 
         // 5. Let date be MakeDate(Day(t), MakeTime(HourFromTime(t), m, s, milli)).
-        t.set_components(false, None, None, None, None, Some(m), s, milli);
+        t.set_components(
+            false,
+            None,
+            None,
+            None,
+            None,
+            Some(m),
+            s,
+            milli,
+            context.clock(),
+        );
 
         // 6. Let u be TimeClip(UTC(date)).
         let u = t.get_time();
@@ -1115,7 +1043,17 @@ impl Date {
         };
 
         // 4. Let newDate be MakeDate(MakeDay(YearFromTime(t), m, dt), TimeWithinDay(t)).
-        t.set_components(false, None, Some(m), dt, None, None, None, None);
+        t.set_components(
+            false,
+            None,
+            Some(m),
+            dt,
+            None,
+            None,
+            None,
+            None,
+            context.clock(),
+        );
 
         // 5. Let u be TimeClip(UTC(newDate)).
         let u = t.get_time();
@@ -1160,7 +1098,17 @@ impl Date {
         };
 
         // 4. Let date be MakeDate(Day(t), MakeTime(HourFromTime(t), MinFromTime(t), s, milli)).
-        t.set_components(false, None, None, None, None, None, Some(s), milli);
+        t.set_components(
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(s),
+            milli,
+            context.clock(),
+        );
 
         // 5. Let u be TimeClip(UTC(date)).
         let u = t.get_time();
@@ -1188,14 +1136,11 @@ impl Date {
 
         // 2. If t is NaN, set t to +0𝔽; otherwise, set t to LocalTime(t).
         if t.0.is_none() {
-            t.0 = NaiveDateTime::from_timestamp_opt(0, 0)
-                .and_then(|local| ignore_ambiguity(Local.from_local_datetime(&local)))
-                .map(|local| local.naive_utc())
-                .filter(|time| Self::time_clip(time.timestamp_millis() as f64).is_some());
+            t = Date::from_time_value(utc_time(context.clock(), 0f64));
         }
 
         // 3. Let y be ? ToNumber(year).
-        let mut y = args
+        let y = args
             .get(0)
             .cloned()
             .unwrap_or_default()
@@ -1213,13 +1158,21 @@ impl Date {
         // 5. Let yi be ! ToIntegerOrInfinity(y).
         // 6. If 0 ≤ yi ≤ 99, let yyyy be 1900𝔽 + 𝔽(yi).
         // 7. Else, let yyyy be y.
-        if (0f64..=99f64).contains(&y) {
-            y += 1900f64;
-        }
+        let yyyy = make_full_year(y);
 
         // 8. Let d be MakeDay(yyyy, MonthFromTime(t), DateFromTime(t)).
         // 9. Let date be UTC(MakeDate(d, TimeWithinDay(t))).
-        t.set_components(false, Some(y), None, None, None, None, None, None);
+        t.set_components(
+            false,
+            Some(yyyy),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            context.clock(),
+        );
 
         // 10. Set the [[DateValue]] internal slot of this Date object to TimeClip(date).
         this.set_data(ObjectData::date(t));
@@ -1244,19 +1197,10 @@ impl Date {
         this_time_value(this, context)?;
 
         // 2. Let t be ? ToNumber(time).
-        let t = if let Some(t) = args.get(0) {
-            let t = t.to_number(context)?;
-            let seconds = (t / 1_000f64) as i64;
-            let nanoseconds = ((t % 1_000f64) * 1_000_000f64) as u32;
-            Date(
-                ignore_ambiguity(Local.timestamp_opt(seconds, nanoseconds))
-                    .map(|dt| dt.naive_utc()),
-            )
-        } else {
-            Date(None)
-        };
+        let t = args.get_or_undefined(0).to_number(context)?;
 
         // 3. Let v be TimeClip(t).
+        let t = Date::from_time_value(t);
         let v = t.get_time();
 
         // 4. Set the [[DateValue]] internal slot of this Date object to v.
@@ -1292,7 +1236,17 @@ impl Date {
             .to_number(context)?;
 
         // 3. Let newDate be MakeDate(MakeDay(YearFromTime(t), MonthFromTime(t), dt), TimeWithinDay(t)).
-        t.set_components(true, None, None, Some(dt), None, None, None, None);
+        t.set_components(
+            true,
+            None,
+            None,
+            Some(dt),
+            None,
+            None,
+            None,
+            None,
+            context.clock(),
+        );
 
         // 4. Let v be TimeClip(newDate).
         let v = t.get_time();
@@ -1325,10 +1279,7 @@ impl Date {
 
         // 2. If t is NaN, set t to +0𝔽.
         if t.0.is_none() {
-            t.0 = NaiveDateTime::from_timestamp_opt(0, 0)
-                .and_then(|local| ignore_ambiguity(Local.from_local_datetime(&local)))
-                .map(|local| local.naive_utc())
-                .filter(|time| Self::time_clip(time.timestamp_millis() as f64).is_some());
+            t = Date(Some(0));
         }
 
         // 3. Let y be ? ToNumber(year).
@@ -1353,7 +1304,17 @@ impl Date {
         };
 
         // 6. Let newDate be MakeDate(MakeDay(y, m, dt), TimeWithinDay(t)).
-        t.set_components(true, Some(y), m, dt, None, None, None, None);
+        t.set_components(
+            true,
+            Some(y),
+            m,
+            dt,
+            None,
+            None,
+            None,
+            None,
+            context.clock(),
+        );
 
         // 7. Let v be TimeClip(newDate).
         let v = t.get_time();
@@ -1414,7 +1375,7 @@ impl Date {
         };
 
         // 6. Let newDate be MakeDate(Day(t), MakeTime(h, m, s, milli)).
-        t.set_components(true, None, None, None, Some(h), m, sec, ms);
+        t.set_components(true, None, None, None, Some(h), m, sec, ms, context.clock());
 
         // 7. Let v be TimeClip(newDate).
         let v = t.get_time();
@@ -1452,7 +1413,17 @@ impl Date {
             .to_number(context)?;
 
         // 3. Let time be MakeTime(HourFromTime(t), MinFromTime(t), SecFromTime(t), milli).
-        t.set_components(true, None, None, None, None, None, None, Some(ms));
+        t.set_components(
+            true,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(ms),
+            context.clock(),
+        );
 
         // 4. Let v be TimeClip(MakeDate(Day(t), time)).
         let v = t.get_time();
@@ -1508,7 +1479,17 @@ impl Date {
         };
 
         // 7. Let date be MakeDate(Day(t), MakeTime(HourFromTime(t), m, s, milli)).
-        t.set_components(true, None, None, None, None, Some(m), s, milli);
+        t.set_components(
+            true,
+            None,
+            None,
+            None,
+            None,
+            Some(m),
+            s,
+            milli,
+            context.clock(),
+        );
 
         // 8. Let v be TimeClip(date).
         let v = t.get_time();
@@ -1555,7 +1536,17 @@ impl Date {
         };
 
         // 5. Let newDate be MakeDate(MakeDay(YearFromTime(t), m, dt), TimeWithinDay(t)).
-        t.set_components(true, None, Some(m), dt, None, None, None, None);
+        t.set_components(
+            true,
+            None,
+            Some(m),
+            dt,
+            None,
+            None,
+            None,
+            None,
+            context.clock(),
+        );
 
         // 6. Let v be TimeClip(newDate).
         let v = t.get_time();
@@ -1602,7 +1593,17 @@ impl Date {
         };
 
         // 5. Let date be MakeDate(Day(t), MakeTime(HourFromTime(t), MinFromTime(t), s, milli)).
-        t.set_components(true, None, None, None, None, None, Some(s), milli);
+        t.set_components(
+            true,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(s),
+            milli,
+            context.clock(),
+        );
 
         // 6. Let v be TimeClip(date).
         let v = t.get_time();
@@ -1637,8 +1638,8 @@ impl Date {
         // 3. If tv is NaN, return "Invalid Date".
         // 4. Let t be LocalTime(tv).
         // 5. Return DateString(t).
-        if let Some(t) = tv.0 {
-            Ok(t.format("%a %b %d %Y").to_string().into())
+        if let Some(t) = tv.local_time_value(context.clock()) {
+            Ok(utils::date_string(t).into())
         } else {
             Ok(JsString::from("Invalid Date").into())
         }
@@ -1676,13 +1677,8 @@ impl Date {
         _: &[JsValue],
         context: &mut Context,
     ) -> JsResult<JsValue> {
-        if let Some(t) = this_time_value(this, context)?.0 {
-            Ok(Utc::now()
-                .timezone()
-                .from_utc_datetime(&t)
-                .format("%Y-%m-%dT%H:%M:%S.%3fZ")
-                .to_string()
-                .into())
+        if let Some(t) = this_time_value(this, context)?.utc_time_value() {
+            Ok(utils::to_iso_string(t).into())
         } else {
             context.throw_range_error("Invalid time value")
         }
@@ -1734,16 +1730,7 @@ impl Date {
         let tv = this_time_value(this, context)?;
 
         // 2. Return ToDateString(tv).
-        if let Some(t) = tv.0 {
-            Ok(Local::now()
-                .timezone()
-                .from_utc_datetime(&t)
-                .format("%a %b %d %Y %H:%M:%S GMT%z")
-                .to_string()
-                .into())
-        } else {
-            Ok(JsString::from("Invalid Date").into())
-        }
+        Ok(tv.display(context.clock()).into())
     }
 
     /// `Date.prototype.toTimeString()`
@@ -1770,13 +1757,13 @@ impl Date {
         // 3. If tv is NaN, return "Invalid Date".
         // 4. Let t be LocalTime(tv).
         // 5. Return the string-concatenation of TimeString(t) and TimeZoneString(tv).
-        if let Some(t) = tv.0 {
-            Ok(Local::now()
-                .timezone()
-                .from_utc_datetime(&t)
-                .format("%H:%M:%S GMT%z")
-                .to_string()
-                .into())
+        if let Some(t) = tv.utc_time_value() {
+            Ok(format!(
+                "{}{}",
+                utils::time_string(local_time(context.clock(), t)),
+                utils::time_zone_string(context.clock(), t)
+            )
+            .into())
         } else {
             Ok(JsString::from("Invalid Date").into())
         }
@@ -1793,8 +1780,8 @@ impl Date {
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.toutcstring
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/toUTCString
     pub fn to_utc_string(self) -> String {
-        self.to_utc()
-            .map(|date_time| date_time.format("%a, %d %b %Y %H:%M:%S GMT").to_string())
+        self.utc_time_value()
+            .map(utils::to_utc_string)
             .unwrap_or_else(|| "Invalid Date".to_string())
     }
 
@@ -1822,8 +1809,8 @@ impl Date {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.now
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/now
-    pub(crate) fn now(_: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
-        Ok(JsValue::new(Utc::now().timestamp_millis() as f64))
+    pub(crate) fn now(_: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Ok(Self::time_clip(context.clock().now()).map_or_else(JsValue::nan, JsValue::new))
     }
 
     /// `Date.parse()`
@@ -1839,17 +1826,14 @@ impl Date {
     /// [spec]: https://tc39.es/ecma262/#sec-date.parse
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/parse
     pub(crate) fn parse(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
-        // This method is implementation-defined and discouraged, so we just require the same format as the string
+        // This method is implementation-defined and discouraged, so we require the same formats as the string
         // constructor.
+        let string = args.get_or_undefined(0).to_string(context)?;
 
-        if args.is_empty() {
-            return Ok(JsValue::nan());
-        }
-
-        match DateTime::parse_from_rfc3339(&args[0].to_string(context)?) {
-            Ok(v) => Ok(JsValue::new(v.naive_utc().timestamp_millis() as f64)),
-            _ => Ok(JsValue::new(f64::NAN)),
-        }
+        Ok(
+            parse::parse_date(context.clock(), &string.to_std_string_lossy())
+                .map_or_else(JsValue::nan, JsValue::new),
+        )
     }
 
     /// `Date.UTC()`
@@ -1885,28 +1869,12 @@ impl Date {
             .get(6)
             .map_or(Ok(0f64), |value| value.to_number(context))?;
 
-        if !check_normal_opt!(year, month, day, hour, min, sec, milli) {
-            return Ok(JsValue::nan());
-        }
-
-        let year = year as i32;
-        let month = month as u32;
-        let day = day as u32;
-        let hour = hour as u32;
-        let min = min as u32;
-        let sec = sec as u32;
-        let milli = milli as u32;
-
-        let year = if (0..=99).contains(&year) {
-            1900 + year
-        } else {
-            year
-        };
+        let final_date = make_date(
+            make_day(make_full_year(year), month, day),
+            make_time(hour, min, sec, milli),
+        );
 
-        NaiveDate::from_ymd_opt(year, month + 1, day)
-            .and_then(|f| f.and_hms_milli_opt(hour, min, sec, milli))
-            .and_then(|f| Self::time_clip(f.timestamp_millis() as f64))
-            .map_or(Ok(JsValue::nan()), |time| Ok(JsValue::new(time)))
+        Ok(Self::time_clip(final_date).map_or_else(JsValue::nan, JsValue::new))
     }
 }
 
@@ -0,0 +1,455 @@
+//! Parsing of date strings, as used by `Date.parse()` and the `Date(value)` constructor.
+//!
+//! Strings are first parsed using the ECMAScript date time string format. If that fails, they are parsed with a
+//! more lenient parser that accepts the formats produced by `Date.prototype.toString()` and
+//! `Date.prototype.toUTCString()`, RFC 2822 dates, and a few other common formats such as `MM/DD/YYYY`.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!  - [RFC 2822][rfc]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-date.parse
+//! [rfc]: https://datatracker.ietf.org/doc/html/rfc2822#section-3.3
+
+use super::{
+    utils::{
+        make_date, make_day, make_time, time_clip, utc_time, MONTHS, MS_PER_HOUR, MS_PER_MINUTE,
+    },
+    Clock,
+};
+
+/// Parses a date string, returning its time value, or `None` if the string is not recognized or represents a date
+/// out of the valid range.
+///
+/// Dates in local time are converted to UTC using the time zone of `clock`.
+pub(super) fn parse_date(clock: &dyn Clock, string: &str) -> Option<f64> {
+    let string = string.trim();
+    parse_iso(clock, string).or_else(|| parse_legacy(clock, string))
+}
+
+/// The components of a date as read from a date string.
+#[derive(Debug, Clone, Copy, Default)]
+struct DateParts {
+    year: f64,
+    month: f64,
+    day: f64,
+    hour: f64,
+    minute: f64,
+    second: f64,
+    millisecond: f64,
+    /// The offset from UTC of the date, in milliseconds, or `None` if the date is in local time.
+    offset: Option<f64>,
+}
+
+impl DateParts {
+    /// Converts the parts into a time value.
+    fn time_value(self, clock: &dyn Clock) -> Option<f64> {
+        let date = make_date(
+            make_day(self.year, self.month, self.day),
+            make_time(self.hour, self.minute, self.second, self.millisecond),
+        );
+
+        time_clip(match self.offset {
+            Some(offset) => date - offset,
+            None => utc_time(clock, date),
+        })
+    }
+}
+
+/// Returns the number of days in the zero-based `month` of `year`.
+fn days_in_month(year: f64, month: f64) -> f64 {
+    match month as u8 {
+        1 if year % 4.0 == 0.0 && (year % 100.0 != 0.0 || year % 400.0 == 0.0) => 29.0,
+        1 => 28.0,
+        3 | 5 | 8 | 10 => 30.0,
+        _ => 31.0,
+    }
+}
+
+/// A minimal cursor over the bytes of a date string.
+#[derive(Debug)]
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(string: &'a str) -> Self {
+        Self {
+            bytes: string.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn is_done(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    /// Consumes the next byte if it is equal to `byte`.
+    fn next_if(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consumes exactly `count` decimal digits.
+    fn digits(&mut self, count: usize) -> Option<f64> {
+        let digits = self.bytes.get(self.pos..self.pos + count)?;
+        if !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        self.pos += count;
+        Some(
+            digits
+                .iter()
+                .fold(0.0, |acc, digit| acc * 10.0 + f64::from(digit - b'0')),
+        )
+    }
+
+    /// Consumes a run of decimal digits, returning their value and how many there were.
+    fn number(&mut self) -> Option<(f64, usize)> {
+        let start = self.pos;
+        let mut value = 0.0;
+        while let Some(digit) = self.peek().filter(u8::is_ascii_digit) {
+            value = value * 10.0 + f64::from(digit - b'0');
+            self.pos += 1;
+        }
+        let len = self.pos - start;
+        if len == 0 {
+            None
+        } else {
+            Some((value, len))
+        }
+    }
+
+    /// Consumes a run of ASCII letters.
+    fn word(&mut self) -> &'a str {
+        let start = self.pos;
+        while matches!(self.peek(), Some(b) if b.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos]).unwrap_or_default()
+    }
+}
+
+/// Parses a string using the date time string format, `YYYY-MM-DDTHH:mm:ss.sssZ`, and its subsets.
+///
+/// Date-only forms are interpreted as UTC and date-time forms without an offset as local time. A space is accepted
+/// in place of the `T` separator.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-date-time-string-format
+fn parse_iso(clock: &dyn Clock, string: &str) -> Option<f64> {
+    let mut cursor = Cursor::new(string);
+    let mut parts = DateParts {
+        month: 0.0,
+        day: 1.0,
+        ..DateParts::default()
+    };
+
+    // Year: YYYY, or ±YYYYYY for expanded years.
+    parts.year = match cursor.peek()? {
+        sign @ (b'+' | b'-') => {
+            cursor.pos += 1;
+            let year = cursor.digits(6)?;
+            if sign == b'-' {
+                // -000000 is not a valid expanded year.
+                if year == 0.0 {
+                    return None;
+                }
+                -year
+            } else {
+                year
+            }
+        }
+        _ => cursor.digits(4)?,
+    };
+
+    // -MM-DD
+    if cursor.next_if(b'-') {
+        parts.month = cursor.digits(2)? - 1.0;
+        if !(0.0..12.0).contains(&parts.month) {
+            return None;
+        }
+        if cursor.next_if(b'-') {
+            parts.day = cursor.digits(2)?;
+            if parts.day < 1.0 || parts.day > days_in_month(parts.year, parts.month) {
+                return None;
+            }
+        }
+    }
+
+    // Date-only forms are UTC.
+    if cursor.is_done() {
+        parts.offset = Some(0.0);
+        return parts.time_value(clock);
+    }
+
+    // THH:mm:ss.sss
+    if !(cursor.next_if(b'T') || cursor.next_if(b't') || cursor.next_if(b' ')) {
+        return None;
+    }
+    parts.hour = cursor.digits(2)?;
+    if !cursor.next_if(b':') {
+        return None;
+    }
+    parts.minute = cursor.digits(2)?;
+    if cursor.next_if(b':') {
+        parts.second = cursor.digits(2)?;
+        if cursor.next_if(b'.') || cursor.next_if(b',') {
+            // Only the first three digits are significant, any further digits are truncated.
+            let (fraction, len) = cursor.number()?;
+            parts.millisecond = (fraction / 10f64.powi((len as i32) - 3)).trunc();
+        }
+    }
+
+    if parts.hour > 24.0 || parts.minute > 59.0 || parts.second > 59.0 {
+        return None;
+    }
+    // 24:00 is allowed to represent the end of a day, but only if all the other fields are zero.
+    if parts.hour == 24.0
+        && (parts.minute != 0.0 || parts.second != 0.0 || parts.millisecond != 0.0)
+    {
+        return None;
+    }
+
+    // Z or ±HH:mm
+    match cursor.peek() {
+        None => {}
+        Some(b'Z') | Some(b'z') => {
+            cursor.pos += 1;
+            parts.offset = Some(0.0);
+        }
+        Some(sign @ (b'+' | b'-')) => {
+            cursor.pos += 1;
+            let hours = cursor.digits(2)?;
+            cursor.next_if(b':');
+            let minutes = cursor.digits(2)?;
+            if hours > 23.0 || minutes > 59.0 {
+                return None;
+            }
+            let offset = hours * MS_PER_HOUR + minutes * MS_PER_MINUTE;
+            parts.offset = Some(if sign == b'-' { -offset } else { offset });
+        }
+        Some(_) => return None,
+    }
+
+    if cursor.is_done() {
+        parts.time_value(clock)
+    } else {
+        None
+    }
+}
+
+/// Returns the offset from UTC, in hours, of the time zone names accepted by RFC 2822.
+fn named_zone_offset(name: &str) -> Option<f64> {
+    let offset = match name {
+        "z" | "ut" | "utc" | "gmt" => 0.0,
+        "edt" => -4.0,
+        "est" | "cdt" => -5.0,
+        "cst" | "mdt" => -6.0,
+        "mst" | "pdt" => -7.0,
+        "pst" => -8.0,
+        _ => return None,
+    };
+    Some(offset * MS_PER_HOUR)
+}
+
+/// Parses a string using the formats accepted by other engines but not defined by the specification.
+///
+/// This accepts, among others:
+///  - The format of `Date.prototype.toString()`: `Wed Jul 08 2020 09:16:15 GMT+0200 (Central European Summer Time)`
+///  - The format of `Date.prototype.toUTCString()`: `Wed, 08 Jul 2020 09:16:15 GMT`
+///  - RFC 2822 dates: `8 Jul 2020 09:16 -0500`, `Wed, 8 Jul 2020 09:16:15 EST`
+///  - Numeric dates: `07/08/2020`, `2020/07/08 9:16 PM`
+///
+/// Strings without a time zone are interpreted as local time.
+fn parse_legacy(clock: &dyn Clock, string: &str) -> Option<f64> {
+    let mut cursor = Cursor::new(string);
+    let mut year: Option<(f64, usize)> = None;
+    let mut month = None;
+    let mut day = None;
+    let mut time: Option<(f64, f64, f64, f64)> = None;
+    let mut offset = None;
+    let mut pm = None;
+
+    while let Some(byte) = cursor.peek() {
+        match byte {
+            b' ' | b'\t' | b',' | b'.' => cursor.pos += 1,
+            // Comments, usually containing the name of the time zone.
+            b'(' => {
+                let mut depth = 0;
+                while let Some(byte) = cursor.peek() {
+                    cursor.pos += 1;
+                    match byte {
+                        b'(' => depth += 1,
+                        b')' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            // A sign after the time, or after a time zone name, is an offset from UTC.
+            b'+' | b'-' if time.is_some() || offset.is_some() => {
+                cursor.pos += 1;
+                let (value, len) = cursor.number()?;
+                let (hours, minutes) = if cursor.next_if(b':') {
+                    (value, cursor.digits(2)?)
+                } else if len <= 2 {
+                    (value, 0.0)
+                } else if len == 4 {
+                    ((value / 100.0).trunc(), value % 100.0)
+                } else {
+                    return None;
+                };
+                if hours > 23.0 || minutes > 59.0 {
+                    return None;
+                }
+                let value = hours * MS_PER_HOUR + minutes * MS_PER_MINUTE;
+                let value = if byte == b'-' { -value } else { value };
+                offset = Some(offset.unwrap_or(0.0) + value);
+            }
+            // A minus sign before the year, as written by `toString()` for years before 0.
+            b'-' if year.is_none() && month.is_some() && day.is_some() => {
+                cursor.pos += 1;
+                let (value, len) = cursor.number()?;
+                year = Some((-value, len.max(4)));
+            }
+            b'-' | b'/' => cursor.pos += 1,
+            b'0'..=b'9' => {
+                let (value, len) = cursor.number()?;
+                if cursor.next_if(b':') {
+                    // HH:mm[:ss[.sss]]
+                    if time.is_some() {
+                        return None;
+                    }
+                    let minute = cursor.digits(2)?;
+                    let mut second = 0.0;
+                    let mut millisecond = 0.0;
+                    if cursor.next_if(b':') {
+                        second = cursor.digits(2)?;
+                        if cursor.next_if(b'.') {
+                            let (fraction, len) = cursor.number()?;
+                            millisecond = (fraction / 10f64.powi((len as i32) - 3)).trunc();
+                        }
+                    }
+                    // `24:00` is the end of the day, but no later time of hour 24 exists.
+                    if value > 24.0
+                        || minute > 59.0
+                        || second > 59.0
+                        || (value == 24.0 && (minute, second, millisecond) != (0.0, 0.0, 0.0))
+                    {
+                        return None;
+                    }
+                    time = Some((value, minute, second, millisecond));
+                } else if month.is_none()
+                    && (cursor.peek() == Some(b'/') || (cursor.peek() == Some(b'-') && len > 2))
+                {
+                    // MM/DD/YYYY, YYYY/MM/DD or YYYY-MM-DD, with or without leading zeros.
+                    let separator = cursor.peek()?;
+                    cursor.pos += 1;
+                    let (second, _) = cursor.number()?;
+                    if !cursor.next_if(separator) {
+                        return None;
+                    }
+                    let (third, third_len) = cursor.number()?;
+                    if len > 2 {
+                        year = Some((value, len));
+                        month = Some(second - 1.0);
+                        day = Some(third);
+                    } else {
+                        month = Some(value - 1.0);
+                        day = Some(second);
+                        year = Some((third, third_len));
+                    }
+                } else if day.is_none() && len <= 2 && (1.0..=31.0).contains(&value) {
+                    day = Some(value);
+                } else if year.is_none() {
+                    year = Some((value, len));
+                } else {
+                    return None;
+                }
+            }
+            b'a'..=b'z' | b'A'..=b'Z' => {
+                let word = cursor.word().to_ascii_lowercase();
+                if word == "am" || word == "pm" {
+                    pm = Some(word == "pm");
+                } else if let Some(zone) = named_zone_offset(&word) {
+                    if offset.is_some() {
+                        return None;
+                    }
+                    offset = Some(zone);
+                } else if word.len() >= 3 {
+                    let prefix = &word[..3];
+                    if let Some(index) = MONTHS
+                        .iter()
+                        .position(|name| name.eq_ignore_ascii_case(prefix))
+                    {
+                        if month.is_some() {
+                            return None;
+                        }
+                        month = Some(index as f64);
+                    } else if !["sun", "mon", "tue", "wed", "thu", "fri", "sat"].contains(&prefix) {
+                        return None;
+                    }
+                } else {
+                    return None;
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    let (year, year_len) = year?;
+    // Two digit years are interpreted as being in the range 1950..=2049.
+    let year = if year_len <= 2 && year >= 0.0 {
+        if year < 50.0 {
+            2000.0 + year
+        } else {
+            1900.0 + year
+        }
+    } else {
+        year
+    };
+    let month = month?;
+    let day = day?;
+    if !(0.0..12.0).contains(&month) || day < 1.0 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    let (mut hour, minute, second, millisecond) = time.unwrap_or_default();
+    if let Some(pm) = pm {
+        if !(1.0..=12.0).contains(&hour) {
+            return None;
+        }
+        hour %= 12.0;
+        if pm {
+            hour += 12.0;
+        }
+    }
+
+    DateParts {
+        year,
+        month,
+        day,
+        hour,
+        minute,
+        second,
+        millisecond,
+        offset,
+    }
+    .time_value(clock)
+}
@@ -1,7 +1,52 @@
 #![allow(clippy::zero_prefixed_literal)]
 
+use super::Clock;
 use crate::{forward, forward_val, Context, JsValue};
 use chrono::prelude::*;
+use std::{cell::Cell, rc::Rc};
+
+/// A clock controlled by the test, in a time zone `offset` milliseconds ahead of UTC, that switches to summer time
+/// (one more hour) at the time value `dst_start`.
+#[derive(Debug, Clone)]
+struct TestClock {
+    now: Rc<Cell<f64>>,
+    offset: f64,
+    dst_start: f64,
+}
+
+impl TestClock {
+    fn new(now: f64, offset: f64) -> Self {
+        Self {
+            now: Rc::new(Cell::new(now)),
+            offset,
+            dst_start: f64::INFINITY,
+        }
+    }
+}
+
+impl Clock for TestClock {
+    fn now(&self) -> f64 {
+        self.now.get()
+    }
+
+    fn local_offset(&self, t: f64, is_utc: bool) -> f64 {
+        // Local times skipped by the transition are interpreted with the offset before it.
+        let t = if is_utc {
+            t
+        } else {
+            t - self.offset - 3_600_000.0
+        };
+        if t >= self.dst_start {
+            self.offset + 3_600_000.0
+        } else {
+            self.offset
+        }
+    }
+
+    fn time_zone_name(&self, _t: f64) -> Option<String> {
+        Some("Test Time".to_owned())
+    }
+}
 
 // NOTE: Javascript Uses 0-based months, where chrono uses 1-based months. Many of the assertions look wrong because of
 // this.
@@ -15,7 +60,7 @@ fn forward_dt_utc(context: &mut Context, src: &str) -> Option<NaiveDateTime> {
 
     if let JsValue::Object(ref date_time) = date_time {
         if let Some(date_time) = date_time.borrow().as_date() {
-            date_time.0
+            date_time.to_utc().map(|utc| utc.naive_utc())
         } else {
             panic!("expected date")
         }
@@ -38,16 +83,11 @@ fn forward_dt_local(context: &mut Context, src: &str) -> Option<NaiveDateTime> {
 
 #[test]
 fn date_display() {
-    let dt = super::Date(None);
-    assert_eq!("[Invalid Date]", format!("[{}]", dt));
-
-    let cd = super::Date::default();
+    let clock = TestClock::new(0.0, 3_600_000.0);
+    assert_eq!(super::Date(None).display(&clock), "Invalid Date");
     assert_eq!(
-        format!(
-            "[{}]",
-            cd.to_local().unwrap().format("%a %b %d %Y %H:%M:%S GMT%:z")
-        ),
-        format!("[{}]", cd)
+        super::Date::from_time_value(clock.now()).display(&clock),
+        "Thu Jan 01 1970 01:00:00 GMT+0100 (Test Time)"
     );
 }
 
@@ -72,13 +112,16 @@ fn date_this_time_value() {
 #[test]
 fn date_call() -> Result<(), Box<dyn std::error::Error>> {
     let mut context = Context::new();
+    let clock = TestClock::new(1594199775779f64, 7_200_000f64);
+    context.set_clock(clock.clone());
 
     let dt1 = forward(&mut context, "Date()");
 
-    std::thread::sleep(std::time::Duration::from_millis(1));
+    clock.now.set(clock.now.get() + 1000f64);
 
     let dt2 = forward(&mut context, "Date()");
 
+    assert_eq!(dt1, "\"Wed Jul 08 2020 11:16:15 GMT+0200 (Test Time)\"");
     assert_ne!(dt1, dt2);
     Ok(())
 }
@@ -419,8 +462,8 @@ fn date_proto_get_timezone_offset() -> Result<(), Box<dyn std::error::Error>> {
         "new Date('1975-08-19T23:15:30+07:00').getTimezoneOffset()",
     );
 
-    // The value of now().offset() depends on the host machine, so we have to replicate the method code here.
-    let offset_seconds = chrono::Local::now().offset().local_minus_utc() as f64;
+    // The offset depends on the host machine, so we have to replicate the method code here.
+    let offset_seconds = Local.timestamp(177_696_930, 0).offset().local_minus_utc() as f64;
     let offset_minutes = -offset_seconds / 60f64;
     assert_eq!(Ok(JsValue::new(offset_minutes)), actual);
 
@@ -1377,3 +1420,232 @@ fn date_json() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn date_parse_iso_formats() {
+    let mut context = Context::new();
+
+    for (src, expected) in [
+        ("Date.parse('2020')", 1577836800000f64),
+        ("Date.parse('2020-07')", 1593561600000f64),
+        ("Date.parse('2020-07-08')", 1594166400000f64),
+        ("Date.parse('2020-07-08T09:16Z')", 1594199760000f64),
+        ("Date.parse('2020-07-08T09:16:15Z')", 1594199775000f64),
+        ("Date.parse('2020-07-08T09:16:15.779Z')", 1594199775779f64),
+        (
+            "Date.parse('2020-07-08T09:16:15.779+02:00')",
+            1594192575779f64,
+        ),
+        ("Date.parse('2020-07-08T24:00:00Z')", 1594252800000f64),
+        (
+            "Date.parse('+002020-07-08T09:16:15.779Z')",
+            1594199775779f64,
+        ),
+        ("Date.parse('-000001-01-01T00:00:00Z')", -62198755200000f64),
+        ("Date.parse('+275760-09-13T00:00:00.000Z')", 8.64e15),
+        ("Date.parse('-271821-04-20T00:00:00.000Z')", -8.64e15),
+    ] {
+        assert_eq!(
+            forward_val(&mut context, src),
+            Ok(JsValue::new(expected)),
+            "{}",
+            src
+        );
+    }
+
+    for src in [
+        "Date.parse('-000000-01-01T00:00:00Z')",
+        "Date.parse('2020-13-01')",
+        "Date.parse('2019-02-29')",
+        "Date.parse('2020-07-08T24:00:01Z')",
+        "Date.parse('2020-07-08T09:16:15.779+24:00')",
+        "Date.parse('+275760-09-13T00:00:00.001Z')",
+        "Date.parse('2020-07-08T09:16:15.779Zjunk')",
+    ] {
+        assert_eq!(
+            forward_val(&mut context, src),
+            Ok(JsValue::nan()),
+            "{}",
+            src
+        );
+    }
+}
+
+#[test]
+fn date_parse_legacy_formats() {
+    let mut context = Context::new();
+
+    for (src, expected) in [
+        (
+            "Date.parse('Wed, 08 Jul 2020 09:16:15 GMT')",
+            1594199775000f64,
+        ),
+        (
+            "Date.parse('Wed Jul 08 2020 09:16:15 GMT+0200 (Central European Summer Time)')",
+            1594192575000f64,
+        ),
+        ("Date.parse('8 Jul 2020 09:16:15 -0500')", 1594217775000f64),
+        ("Date.parse('Wed, 8 Jul 2020 09:16 EST')", 1594217760000f64),
+        ("Date.parse('July 8, 2020 UTC')", 1594166400000f64),
+        ("Date.parse('07/08/2020 9:16 PM UTC')", 1594242960000f64),
+        ("Date.parse('2020/07/08 UTC')", 1594166400000f64),
+        ("Date.parse('2020-7-8 UTC')", 1594166400000f64),
+        ("Date.parse('2020/7/8 09:16 UTC')", 1594199760000f64),
+        ("Date.parse('2020-7-8 24:00 UTC')", 1594252800000f64),
+    ] {
+        assert_eq!(
+            forward_val(&mut context, src),
+            Ok(JsValue::new(expected)),
+            "{}",
+            src
+        );
+    }
+
+    for src in [
+        "Date.parse('nope')",
+        "Date.parse('Jul 2020')",
+        "Date.parse('Feb 30 2020')",
+        "Date.parse('Jul 08 2020 13:00 PM')",
+        "Date.parse('2020-7-8 24:30 UTC')",
+        "Date.parse('Jul 08 2020 24:00:01')",
+        "Date.parse('2020-7')",
+    ] {
+        assert_eq!(
+            forward_val(&mut context, src),
+            Ok(JsValue::nan()),
+            "{}",
+            src
+        );
+    }
+}
+
+#[test]
+fn date_string_round_trip() {
+    let mut context = Context::new();
+
+    for src in [
+        "let dt = new Date(2020, 06, 08, 09, 16, 15); Date.parse(dt.toString()) === dt.getTime()",
+        "dt = new Date(2020, 06, 08, 09, 16, 15); Date.parse(dt.toUTCString()) === dt.getTime()",
+        "dt = new Date(2020, 06, 08, 09, 16, 15, 779); Date.parse(dt.toISOString()) === dt.getTime()",
+        "dt = new Date(-8.64e15); Date.parse(dt.toISOString()) === dt.getTime()",
+        "dt = new Date(8.64e15); Date.parse(dt.toUTCString()) === dt.getTime()",
+        "dt = new Date(Date.UTC(-1, 0, 1)); Date.parse(dt.toUTCString()) === dt.getTime()",
+    ] {
+        assert_eq!(forward(&mut context, src), "true", "{}", src);
+    }
+}
+
+#[test]
+fn date_extreme_time_values() {
+    let mut context = Context::new();
+
+    assert_eq!(
+        forward(&mut context, "new Date(8.64e15).toISOString()"),
+        "\"+275760-09-13T00:00:00.000Z\""
+    );
+    assert_eq!(
+        forward(&mut context, "new Date(-8.64e15).toISOString()"),
+        "\"-271821-04-20T00:00:00.000Z\""
+    );
+    assert_eq!(
+        forward(&mut context, "new Date(-8.64e15).toUTCString()"),
+        "\"Tue, 20 Apr -271821 00:00:00 GMT\""
+    );
+    assert_eq!(
+        forward(&mut context, "new Date(8.64e15).getUTCFullYear()"),
+        "275760"
+    );
+    assert_eq!(
+        forward(&mut context, "new Date(8.64e15 + 1).getTime()"),
+        "NaN"
+    );
+    assert_eq!(
+        forward(&mut context, "Date.UTC(275760, 8, 13, 0, 0, 0, 1)"),
+        "NaN"
+    );
+    assert_eq!(
+        forward(&mut context, "Date.UTC(-271821, 3, 20)"),
+        "-8640000000000000"
+    );
+}
+
+#[test]
+fn date_clock_now() {
+    let mut context = Context::new();
+    let clock = TestClock::new(1594199775779f64, 0f64);
+    context.set_clock(clock.clone());
+
+    assert_eq!(forward(&mut context, "Date.now()"), "1594199775779");
+    assert_eq!(
+        forward(&mut context, "new Date().getTime()"),
+        "1594199775779"
+    );
+
+    clock.now.set(0f64);
+    assert_eq!(forward(&mut context, "Date.now()"), "0");
+    assert_eq!(
+        forward(&mut context, "new Date().toISOString()"),
+        "\"1970-01-01T00:00:00.000Z\""
+    );
+}
+
+#[test]
+fn date_clock_time_zone() {
+    let mut context = Context::new();
+    context.set_clock(TestClock::new(0f64, -5f64 * 3_600_000f64));
+
+    for (src, expected) in [
+        (
+            "new Date(Date.UTC(2020, 06, 08, 09, 16, 15, 779)).getHours()",
+            "4",
+        ),
+        (
+            "new Date(Date.UTC(2020, 06, 08, 02, 16, 15, 779)).getDate()",
+            "7",
+        ),
+        (
+            "new Date(Date.UTC(2020, 06, 08, 09, 16, 15, 779)).getTimezoneOffset()",
+            "300",
+        ),
+        (
+            "new Date(2020, 06, 08, 04, 16, 15, 779).getTime()",
+            "1594199775779",
+        ),
+        ("Date.parse('2020-07-08T04:16:15.779')", "1594199775779"),
+        ("Date.parse('Jul 8 2020 04:16:15')", "1594199775000"),
+        (
+            "new Date(Date.UTC(2020, 06, 08, 09, 16, 15, 779)).toString()",
+            "\"Wed Jul 08 2020 04:16:15 GMT-0500 (Test Time)\"",
+        ),
+        (
+            "new Date(Date.UTC(2020, 06, 08, 09, 16, 15, 779)).toTimeString()",
+            "\"04:16:15 GMT-0500 (Test Time)\"",
+        ),
+        (
+            "new Date(Date.UTC(2020, 06, 08, 02, 16, 15, 779)).toDateString()",
+            "\"Tue Jul 07 2020\"",
+        ),
+    ] {
+        assert_eq!(forward(&mut context, src), expected, "{}", src);
+    }
+}
+
+#[test]
+fn date_clock_time_zone_rules() {
+    let mut context = Context::new();
+    let mut clock = TestClock::new(0f64, 3_600_000f64);
+    // Summer time starts at 2020-03-29T01:00:00Z.
+    clock.dst_start = 1585443600000f64;
+    context.set_clock(clock);
+
+    for (src, expected) in [
+        ("new Date(2020, 02, 29, 01, 30).getTimezoneOffset()", "-60"),
+        ("new Date(2020, 02, 29, 03, 30).getTimezoneOffset()", "-120"),
+        ("new Date(2020, 02, 29, 01, 30).getHours()", "1"),
+        ("new Date(2020, 02, 29, 03, 30).getHours()", "3"),
+        // 02:30 does not exist in local time, it is interpreted with the offset before the transition.
+        ("new Date(2020, 02, 29, 02, 30).getHours()", "3"),
+    ] {
+        assert_eq!(forward(&mut context, src), expected, "{}", src);
+    }
+}
@@ -0,0 +1,510 @@
+//! Abstract operations on time values used by the `Date` builtin.
+//!
+//! A time value is a number of milliseconds since the epoch, `1970-01-01T00:00:00Z`, ignoring leap seconds. All the
+//! operations in this module work directly on time values, so they cover the whole range of dates representable by a
+//! `Date` object (±8.64e15 milliseconds) instead of being limited to what `chrono` can represent.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-time-values-and-time-range
+
+use super::Clock;
+
+/// The number of milliseconds in a day.
+const MS_PER_DAY: f64 = 86_400_000.0;
+/// The number of milliseconds in an hour.
+pub(super) const MS_PER_HOUR: f64 = 3_600_000.0;
+/// The number of milliseconds in a minute.
+pub(super) const MS_PER_MINUTE: f64 = 60_000.0;
+/// The number of milliseconds in a second.
+pub(super) const MS_PER_SECOND: f64 = 1000.0;
+
+/// The largest time value, in absolute terms, that can be represented by a `Date` object.
+pub(super) const MAX_TIME_VALUE: f64 = 8.64e15;
+
+/// The short names of the days of the week, starting with Sunday.
+const WEEK_DAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+/// The short names of the months, starting with January.
+pub(super) const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// The abstract operation `Day` returns the day number of the day in which `t` falls.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-day
+#[inline]
+pub(super) fn day(t: f64) -> f64 {
+    (t / MS_PER_DAY).floor()
+}
+
+/// The abstract operation `DaysInYear` returns the number of days in the year `y`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-daysinyear
+#[inline]
+fn days_in_year(y: f64) -> f64 {
+    if y % 4.0 != 0.0 {
+        365.0
+    } else if y % 100.0 != 0.0 {
+        366.0
+    } else if y % 400.0 != 0.0 {
+        365.0
+    } else {
+        366.0
+    }
+}
+
+/// The abstract operation `DayFromYear` returns the day number of the first day of the year `y`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-dayfromyear
+#[inline]
+fn day_from_year(y: f64) -> f64 {
+    365.0 * (y - 1970.0) + ((y - 1969.0) / 4.0).floor() - ((y - 1901.0) / 100.0).floor()
+        + ((y - 1601.0) / 400.0).floor()
+}
+
+/// The abstract operation `TimeFromYear` returns the time value of the start of the year `y`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-timefromyear
+#[inline]
+fn time_from_year(y: f64) -> f64 {
+    MS_PER_DAY * day_from_year(y)
+}
+
+/// The abstract operation `YearFromTime` returns the year in which `t` falls.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-yearfromtime
+pub(super) fn year_from_time(t: f64) -> f64 {
+    // Start from an estimate using the average length of a year, and correct it using the exact start of the years
+    // around it. The estimate is never off by more than a year.
+    let mut y = (t / (MS_PER_DAY * 365.2425)).floor() + 1970.0;
+    while time_from_year(y) > t {
+        y -= 1.0;
+    }
+    while time_from_year(y + 1.0) <= t {
+        y += 1.0;
+    }
+    y
+}
+
+/// The abstract operation `InLeapYear` returns `true` if `t` falls within a leap year.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-inleapyear
+#[inline]
+fn in_leap_year(t: f64) -> bool {
+    days_in_year(year_from_time(t)) == 366.0
+}
+
+/// Returns the day number within the year of the first day of `month` (zero-based), accounting for leap years.
+#[inline]
+fn day_from_month(month: f64, leap: bool) -> f64 {
+    const CUMULATIVE_DAYS: [f64; 12] = [
+        0.0, 31.0, 59.0, 90.0, 120.0, 151.0, 181.0, 212.0, 243.0, 273.0, 304.0, 334.0,
+    ];
+
+    let days = CUMULATIVE_DAYS[month as usize];
+    if leap && month >= 2.0 {
+        days + 1.0
+    } else {
+        days
+    }
+}
+
+/// The abstract operation `DayWithinYear` returns the number of days since the start of the year in which `t` falls.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-daywithinyear
+#[inline]
+fn day_within_year(t: f64) -> f64 {
+    day(t) - day_from_year(year_from_time(t))
+}
+
+/// The abstract operation `MonthFromTime` returns the zero-based month in which `t` falls.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-monthfromtime
+pub(super) fn month_from_time(t: f64) -> f64 {
+    let leap = in_leap_year(t);
+    let day = day_within_year(t);
+    (1..12)
+        .map(f64::from)
+        .take_while(|month| day >= day_from_month(*month, leap))
+        .last()
+        .unwrap_or(0.0)
+}
+
+/// The abstract operation `DateFromTime` returns the one-based day of the month in which `t` falls.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-datefromtime
+pub(super) fn date_from_time(t: f64) -> f64 {
+    day_within_year(t) - day_from_month(month_from_time(t), in_leap_year(t)) + 1.0
+}
+
+/// The abstract operation `WeekDay` returns the day of the week in which `t` falls, where `0` represents Sunday.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-week-day
+#[inline]
+pub(super) fn week_day(t: f64) -> f64 {
+    // `rem_euclid` returns `-0` for negative multiples of the divisor, adding `+0` normalizes it to `+0`.
+    (day(t) + 4.0).rem_euclid(7.0) + 0.0
+}
+
+/// The abstract operation `HourFromTime` returns the hour of the day in which `t` falls.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-hours-minutes-second-and-milliseconds
+#[inline]
+pub(super) fn hour_from_time(t: f64) -> f64 {
+    (t / MS_PER_HOUR).floor().rem_euclid(24.0) + 0.0
+}
+
+/// The abstract operation `MinFromTime` returns the minute of the hour in which `t` falls.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-hours-minutes-second-and-milliseconds
+#[inline]
+pub(super) fn min_from_time(t: f64) -> f64 {
+    (t / MS_PER_MINUTE).floor().rem_euclid(60.0) + 0.0
+}
+
+/// The abstract operation `SecFromTime` returns the second of the minute in which `t` falls.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-hours-minutes-second-and-milliseconds
+#[inline]
+pub(super) fn sec_from_time(t: f64) -> f64 {
+    (t / MS_PER_SECOND).floor().rem_euclid(60.0) + 0.0
+}
+
+/// The abstract operation `msFromTime` returns the millisecond of the second in which `t` falls.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-hours-minutes-second-and-milliseconds
+#[inline]
+pub(super) fn ms_from_time(t: f64) -> f64 {
+    t.rem_euclid(MS_PER_SECOND) + 0.0
+}
+
+/// The abstract operation `MakeTime` calculates a number of milliseconds from its arguments.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-maketime
+pub(super) fn make_time(hour: f64, min: f64, sec: f64, ms: f64) -> f64 {
+    // 1. If hour is not finite or min is not finite or sec is not finite or ms is not finite, return NaN.
+    if !hour.is_finite() || !min.is_finite() || !sec.is_finite() || !ms.is_finite() {
+        return f64::NAN;
+    }
+
+    // 2. Let h be 𝔽(! ToIntegerOrInfinity(hour)).
+    // 3. Let m be 𝔽(! ToIntegerOrInfinity(min)).
+    // 4. Let s be 𝔽(! ToIntegerOrInfinity(sec)).
+    // 5. Let milli be 𝔽(! ToIntegerOrInfinity(ms)).
+    // 6. Let t be ((h * msPerHour + m * msPerMinute) + s * msPerSecond) + milli, performing the arithmetic according
+    //    to IEEE 754-2019 rules (that is, as if using the ECMAScript operators * and +).
+    // 7. Return t.
+    hour.trunc() * MS_PER_HOUR
+        + min.trunc() * MS_PER_MINUTE
+        + sec.trunc() * MS_PER_SECOND
+        + ms.trunc()
+}
+
+/// The abstract operation `MakeDay` calculates a number of days from its arguments.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-makeday
+pub(super) fn make_day(year: f64, month: f64, date: f64) -> f64 {
+    // 1. If year is not finite or month is not finite or date is not finite, return NaN.
+    if !year.is_finite() || !month.is_finite() || !date.is_finite() {
+        return f64::NAN;
+    }
+
+    // 2. Let y be 𝔽(! ToIntegerOrInfinity(year)).
+    // 3. Let m be 𝔽(! ToIntegerOrInfinity(month)).
+    // 4. Let dt be 𝔽(! ToIntegerOrInfinity(date)).
+    let (y, m, dt) = (year.trunc(), month.trunc(), date.trunc());
+
+    // 5. Let ym be y + 𝔽(floor(ℝ(m) / 12)).
+    let ym = y + (m / 12.0).floor();
+
+    // 6. If ym is not finite, return NaN.
+    // Years this far away can never produce a valid time value, so they are rejected early to avoid losing precision.
+    if !ym.is_finite() || ym.abs() > 400_000.0 {
+        return f64::NAN;
+    }
+
+    // 7. Let mn be 𝔽(ℝ(m) modulo 12).
+    let mn = m.rem_euclid(12.0);
+
+    // 8. Find a finite time value t such that YearFromTime(t) is ym and MonthFromTime(t) is mn and DateFromTime(t) is
+    //    1𝔽; but if this is not possible (because some argument is out of range), return NaN.
+    let t = time_from_year(ym) + day_from_month(mn, days_in_year(ym) == 366.0) * MS_PER_DAY;
+
+    // 9. Return Day(t) + dt - 1𝔽.
+    day(t) + dt - 1.0
+}
+
+/// The abstract operation `MakeDate` calculates a number of milliseconds from its arguments.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-makedate
+#[inline]
+pub(super) fn make_date(day: f64, time: f64) -> f64 {
+    // 1. If day is not finite or time is not finite, return NaN.
+    if !day.is_finite() || !time.is_finite() {
+        return f64::NAN;
+    }
+
+    // 2. Let tv be day × msPerDay + time.
+    let tv = day * MS_PER_DAY + time;
+
+    // 3. If tv is not finite, return NaN.
+    if !tv.is_finite() {
+        return f64::NAN;
+    }
+
+    // 4. Return tv.
+    tv
+}
+
+/// The abstract operation `MakeFullYear` maps two-digit years to the twentieth century.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-makefullyear
+#[inline]
+pub(super) fn make_full_year(year: f64) -> f64 {
+    // 1. If year is NaN, return NaN.
+    if year.is_nan() {
+        return f64::NAN;
+    }
+
+    // 2. Let truncated be ! ToIntegerOrInfinity(year).
+    let truncated = year.trunc();
+
+    // 3. If truncated is in the inclusive interval from 0 to 99, return 1900𝔽 + 𝔽(truncated).
+    // 4. Return 𝔽(truncated).
+    if (0.0..=99.0).contains(&truncated) {
+        1900.0 + truncated
+    } else {
+        truncated
+    }
+}
+
+/// The abstract operation `TimeClip` calculates a number of milliseconds from its argument, returning `None` if the
+/// time is not in the range that can be represented by a `Date`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-timeclip
+#[inline]
+pub(super) fn time_clip(time: f64) -> Option<f64> {
+    // 1. If time is not finite, return NaN.
+    // 2. If abs(ℝ(time)) > 8.64 × 10^15, return NaN.
+    if !time.is_finite() || time.abs() > MAX_TIME_VALUE {
+        return None;
+    }
+
+    // 3. Return 𝔽(! ToIntegerOrInfinity(time)).
+    Some(time.trunc() + 0.0)
+}
+
+/// The abstract operation `LocalTime` converts the UTC time value `t` to local time, according to `clock`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-localtime
+#[inline]
+pub(super) fn local_time(clock: &dyn Clock, t: f64) -> f64 {
+    t + clock.local_offset(t, true)
+}
+
+/// The abstract operation `UTC` converts the local time value `t` to UTC, according to `clock`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-utc-t
+#[inline]
+pub(super) fn utc_time(clock: &dyn Clock, t: f64) -> f64 {
+    if !t.is_finite() {
+        return f64::NAN;
+    }
+    t - clock.local_offset(t, false)
+}
+
+/// Formats a year as required by the date string formats: padded to at least four digits, with a leading `-` for
+/// negative years.
+fn padded_year(year: f64) -> String {
+    if year < 0.0 {
+        format!("-{:04}", -year)
+    } else {
+        format!("{:04}", year)
+    }
+}
+
+/// The abstract operation `DateString` formats the date part of the time value `tv`, e.g. `Wed Jul 08 2020`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-datestring
+pub(super) fn date_string(tv: f64) -> String {
+    format!(
+        "{} {} {:02} {}",
+        WEEK_DAYS[week_day(tv) as usize],
+        MONTHS[month_from_time(tv) as usize],
+        date_from_time(tv),
+        padded_year(year_from_time(tv))
+    )
+}
+
+/// The abstract operation `TimeString` formats the time part of the time value `tv`, e.g. `09:16:15 GMT`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-timestring
+pub(super) fn time_string(tv: f64) -> String {
+    format!(
+        "{:02}:{:02}:{:02} GMT",
+        hour_from_time(tv),
+        min_from_time(tv),
+        sec_from_time(tv)
+    )
+}
+
+/// The abstract operation `TimeZoneString` formats the offset and the name of the local time zone at the UTC time
+/// value `tv`, e.g. `+0200 (Central European Summer Time)`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-timezoneestring
+pub(super) fn time_zone_string(clock: &dyn Clock, tv: f64) -> String {
+    let offset = clock.local_offset(tv, true);
+    let abs_offset = offset.abs();
+    let offset = format!(
+        "{}{:02}{:02}",
+        if offset >= 0.0 { '+' } else { '-' },
+        (abs_offset / MS_PER_HOUR).floor(),
+        min_from_time(abs_offset)
+    );
+
+    match clock.time_zone_name(tv) {
+        Some(name) => format!("{} ({})", offset, name),
+        None => offset,
+    }
+}
+
+/// The abstract operation `ToDateString` formats the UTC time value `tv` in local time, as returned by
+/// `Date.prototype.toString()`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-todatestring
+pub(super) fn to_date_string(clock: &dyn Clock, tv: f64) -> String {
+    let t = local_time(clock, tv);
+    format!(
+        "{} {}{}",
+        date_string(t),
+        time_string(t),
+        time_zone_string(clock, tv)
+    )
+}
+
+/// Formats the UTC time value `tv` as returned by `Date.prototype.toUTCString()`, e.g.
+/// `Wed, 08 Jul 2020 09:16:15 GMT`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-date.prototype.toutcstring
+pub(super) fn to_utc_string(tv: f64) -> String {
+    format!(
+        "{}, {:02} {} {} {}",
+        WEEK_DAYS[week_day(tv) as usize],
+        date_from_time(tv),
+        MONTHS[month_from_time(tv) as usize],
+        padded_year(year_from_time(tv)),
+        time_string(tv)
+    )
+}
+
+/// Formats the UTC time value `tv` using the date time string format, as returned by
+/// `Date.prototype.toISOString()`.
+///
+/// Years outside of the range `0..=9999` are written using the expanded year representation, e.g.
+/// `+275760-09-13T00:00:00.000Z`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-date-time-string-format
+pub(super) fn to_iso_string(tv: f64) -> String {
+    let year = year_from_time(tv);
+    let year = if (0.0..=9999.0).contains(&year) {
+        format!("{:04}", year)
+    } else if year < 0.0 {
+        format!("-{:06}", -year)
+    } else {
+        format!("+{:06}", year)
+    };
+
+    format!(
+        "{}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month_from_time(tv) + 1.0,
+        date_from_time(tv),
+        hour_from_time(tv),
+        min_from_time(tv),
+        sec_from_time(tv),
+        ms_from_time(tv)
+    )
+}
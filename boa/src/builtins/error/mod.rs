@@ -17,8 +17,10 @@ use crate::{
         internal_methods::get_prototype_from_constructor, ConstructorBuilder, JsObject, ObjectData,
     },
     profiler::BoaProfiler,
-    property::Attribute,
-    Context, JsResult, JsValue,
+    property::{Attribute, PropertyDescriptor},
+    string::utf16,
+    syntax::ast::Position,
+    Context, JsResult, JsString, JsValue,
 };
 
 pub(crate) mod eval;
@@ -112,21 +114,17 @@ impl Error {
             return context.throw_type_error("'this' is not an Object");
         }
         let name = this.get_field("name", context)?;
-        let name_to_string;
         let name = if name.is_undefined() {
-            "Error"
+            JsString::new("Error")
         } else {
-            name_to_string = name.to_string(context)?;
-            name_to_string.as_str()
+            name.to_string(context)?
         };
 
         let message = this.get_field("message", context)?;
-        let message_to_string;
         let message = if message.is_undefined() {
-            ""
+            JsString::empty()
         } else {
-            message_to_string = message.to_string(context)?;
-            message_to_string.as_str()
+            message.to_string(context)?
         };
 
         if name.is_empty() {
@@ -134,7 +132,44 @@ impl Error {
         } else if message.is_empty() {
             Ok(name.into())
         } else {
-            Ok(format!("{}: {}", name, message).into())
+            Ok(JsString::concat_array(&[&name, utf16!(": "), &message]).into())
         }
     }
 }
+
+/// Records the source position at which an error was thrown on the error object, as the
+/// non-standard `lineNumber` and `columnNumber` properties.
+///
+/// The position is only recorded once, so the innermost node that propagates the error
+/// determines it. Displaying the error appends the position to its message. Values which are
+/// not error objects are left untouched, and so are errors which are not extensible, like
+/// frozen errors, or which already have one of the properties.
+pub(crate) fn set_error_position(error: &JsValue, position: Position) {
+    let error = match error.as_object() {
+        Some(error) if error.is_error() => error,
+        _ => return,
+    };
+    {
+        let error = error.borrow();
+        if !error.extensible()
+            || error.properties().contains_key(&"lineNumber".into())
+            || error.properties().contains_key(&"columnNumber".into())
+        {
+            return;
+        }
+    }
+
+    for (key, value) in [
+        ("lineNumber", position.line_number()),
+        ("columnNumber", position.column_number()),
+    ] {
+        error.insert_property(
+            key,
+            PropertyDescriptor::builder()
+                .value(value)
+                .writable(true)
+                .enumerable(false)
+                .configurable(true),
+        );
+    }
+}
@@ -84,3 +84,77 @@ fn uri_error_to_string() {
         "\"URIError\""
     );
 }
+
+#[test]
+fn error_position() {
+    let mut context = Context::new();
+    let init = r#"
+        function thrower() {
+            null.x;
+        }
+        let caught;
+        try {
+            thrower();
+        } catch (e) {
+            caught = e;
+        }
+        let unthrown = new Error();
+    "#;
+    forward(&mut context, init);
+    assert_eq!(forward(&mut context, "caught.lineNumber"), "3");
+    assert_eq!(forward(&mut context, "caught.columnNumber"), "13");
+    assert_eq!(
+        forward(&mut context, "caught.propertyIsEnumerable('lineNumber')"),
+        "false"
+    );
+    assert_eq!(forward(&mut context, "unthrown.lineNumber"), "undefined");
+
+    // The position is the one of the innermost node, not of the statement.
+    assert_eq!(
+        forward(&mut context, "let value = 1 +\n    null.x;"),
+        "Uncaught \"TypeError\": \"cannot convert 'null' or 'undefined' to object\" (at 2:5)"
+    );
+}
+
+#[test]
+fn error_position_in_blocks() {
+    let mut context = Context::new();
+    assert_eq!(
+        forward(&mut context, "{ null.x }"),
+        "Uncaught \"TypeError\": \"cannot convert 'null' or 'undefined' to object\" (at 1:3)"
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "if (true) {\n  while (true) {\n    { null.x; }\n  }\n}"
+        ),
+        "Uncaught \"TypeError\": \"cannot convert 'null' or 'undefined' to object\" (at 3:7)"
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "try {\n  null.x;\n} catch (e) {\n  [e.lineNumber, e.columnNumber].join()\n}"
+        ),
+        "\"2,3\""
+    );
+}
+
+#[test]
+fn error_position_of_frozen_errors() {
+    let mut context = Context::new();
+    let init = r#"
+        let frozen = Object.freeze(new TypeError("frozen"));
+        let caught;
+        try {
+            throw frozen;
+        } catch (e) {
+            caught = e;
+        }
+    "#;
+    forward(&mut context, init);
+    assert_eq!(forward(&mut context, "Object.isFrozen(caught)"), "true");
+    assert_eq!(
+        forward(&mut context, "Object.getOwnPropertyNames(caught).join()"),
+        "\"message\""
+    );
+}
@@ -171,7 +171,7 @@ impl Arguments {
         // a. Let name be parameterNames[index].
         for (index, parameter_name) in formals.iter().map(|fp| fp.name()).enumerate().rev() {
             // b. If name is not an element of mappedNames, then
-            if !mapped_names.contains(parameter_name) {
+            if !mapped_names.contains(&parameter_name) {
                 // i. Add name as an element of the list mappedNames.
                 mapped_names.insert(parameter_name);
                 // ii. If index < len, then
@@ -187,9 +187,9 @@ impl Arguments {
                             // 1. Let getterClosure be a new Abstract Closure with no parameters that captures
                             // name and env and performs the following steps when called:
                             |_, _, captures, context| {
-                                captures.0.get_binding_value(&captures.1, false, context)
+                                captures.0.get_binding_value(captures.1, false, context)
                             },
-                            (env.clone(), parameter_name.to_owned()),
+                            (env.clone(), parameter_name),
                         )
                         .length(0)
                         .name("")
@@ -211,11 +211,11 @@ impl Arguments {
                                 // a. Return env.SetMutableBinding(name, value, false).
                                 captures
                                     .0
-                                    .set_mutable_binding(&captures.1, value, false, context)
+                                    .set_mutable_binding(captures.1, value, false, context)
                                     .map(|_| JsValue::Undefined)
                                 // Ok(JsValue::Undefined)
                             },
-                            (env.clone(), parameter_name.to_owned()),
+                            (env.clone(), parameter_name),
                         )
                         .length(1)
                         .name("")
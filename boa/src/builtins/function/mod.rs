@@ -37,6 +37,7 @@ use crate::{object::Object, symbol::WellKnownSymbols};
 use crate::{
     object::{ConstructorBuilder, FunctionBuilder},
     property::PropertyKey,
+    string::utf16,
     JsString,
 };
 use crate::{
@@ -458,7 +459,7 @@ impl BuiltInFunctionObject {
             .map_or(JsString::new(""), Clone::clone);
 
         // 10. Perform SetFunctionName(F, targetName, "bound").
-        set_function_name(&f, &target_name.into(), Some("bound"), context);
+        set_function_name(&f, &target_name.into(), Some(utf16!("bound")), context);
 
         // 11. Return F.
         Ok(f.into())
@@ -522,7 +523,7 @@ impl BuiltInFunctionObject {
             (Function::Ordinary { body, params, .. }, Some(name)) => {
                 let arguments: String = params
                     .iter()
-                    .map(|param| param.name())
+                    .map(|param| param.name().as_str())
                     .collect::<Vec<&str>>()
                     .join(", ");
 
@@ -632,7 +633,7 @@ impl BuiltIn for BuiltInFunctionObject {
 fn set_function_name(
     function: &JsObject,
     name: &PropertyKey,
-    prefix: Option<&str>,
+    prefix: Option<&[u16]>,
     context: &mut Context,
 ) {
     // 1. Assert: F is an extensible object that does not have a "name" own property.
@@ -642,7 +643,7 @@ fn set_function_name(
             // a. Let description be name's [[Description]] value.
             if let Some(desc) = sym.description() {
                 // c. Else, set name to the string-concatenation of "[", description, and "]".
-                Cow::Owned(JsString::concat_array(&["[", &desc, "]"]))
+                Cow::Owned(JsString::concat_array(&[utf16!("["), &desc, utf16!("]")]))
             } else {
                 // b. If description is undefined, set name to the empty String.
                 Cow::Owned(JsString::new(""))
@@ -662,7 +663,7 @@ fn set_function_name(
 
     // 5. If prefix is present, then
     if let Some(prefix) = prefix {
-        name = Cow::Owned(JsString::concat_array(&[prefix, " ", &name]));
+        name = Cow::Owned(JsString::concat_array(&[prefix, utf16!(" "), &name]));
         // b. If F has an [[InitialName]] internal slot, then
         // i. Optionally, set F.[[InitialName]] to name.
         // todo: implement [[InitialName]] for builtins
@@ -0,0 +1,225 @@
+//! This module implements the generator objects that are returned by generator functions.
+//!
+//! A generator object holds the suspended frame of its generator function, which is resumed by
+//! the `next`, `return` and `throw` methods until the function returns.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!  - [MDN documentation][mdn]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-generator-objects
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Generator
+
+use crate::{
+    builtins::{function::make_builtin_fn, iterable::create_iter_result_object},
+    gc::{custom_trace, Finalize, Trace},
+    object::{JsObject, ObjectData},
+    property::PropertyDescriptor,
+    symbol::WellKnownSymbols,
+    vm::{ResumeKind, SuspendedFrame},
+    BoaProfiler, Context, JsResult, JsValue,
+};
+
+/// The `[[GeneratorState]]` of a generator, with its suspended frame.
+#[derive(Debug, Finalize)]
+pub(crate) enum GeneratorState {
+    /// The generator function has not started running its body.
+    SuspendedStart(SuspendedFrame),
+    /// The generator function is suspended at a `yield`.
+    SuspendedYield(SuspendedFrame),
+    Executing,
+    Completed,
+}
+
+// Safety: the suspended frame is marked. The derived `Trace` would also implement `Drop`, which
+// forbids moving the frame out of the state when the generator is resumed.
+unsafe impl Trace for GeneratorState {
+    custom_trace!(this, {
+        if let Self::SuspendedStart(frame) | Self::SuspendedYield(frame) = this {
+            mark(frame);
+        }
+    });
+}
+
+/// The data of a generator object.
+///
+/// It also holds the suspended frames of async functions, which are resumed by their jobs.
+#[derive(Debug, Trace, Finalize)]
+pub struct Generator {
+    pub(crate) state: GeneratorState,
+}
+
+impl Generator {
+    pub(crate) const NAME: &'static str = "Generator";
+
+    /// Creates the data of a generator whose function is suspended at `frame`.
+    pub(crate) fn new(frame: SuspendedFrame) -> Self {
+        Self {
+            state: GeneratorState::SuspendedStart(frame),
+        }
+    }
+
+    /// Takes the suspended frame of the generator `object`, leaving it executing.
+    ///
+    /// Returns `None` if the generator is not suspended, and leaves its state as it is.
+    pub(crate) fn take_frame(object: &JsObject) -> Option<SuspendedFrame> {
+        let mut object = object.borrow_mut();
+        let generator = object.as_generator_mut()?;
+        match std::mem::replace(&mut generator.state, GeneratorState::Executing) {
+            GeneratorState::SuspendedStart(frame) | GeneratorState::SuspendedYield(frame) => {
+                Some(frame)
+            }
+            state => {
+                generator.state = state;
+                None
+            }
+        }
+    }
+
+    /// Sets the state of the generator `object`, after it ran until a `yield` or until its
+    /// function returned.
+    pub(crate) fn set_frame(object: &JsObject, frame: Option<SuspendedFrame>) {
+        let mut object = object.borrow_mut();
+        let generator = object
+            .as_generator_mut()
+            .expect("the object was checked to be a generator");
+        generator.state = frame.map_or(GeneratorState::Completed, GeneratorState::SuspendedYield);
+    }
+
+    /// %GeneratorPrototype%.next( value )
+    ///
+    /// Resumes the generator with `value` as the result of the `yield` it is suspended at.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-generator.prototype.next
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Generator/next
+    pub(crate) fn next(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let value = args.get(0).cloned().unwrap_or_default();
+        Self::resume(this, value, ResumeKind::Normal, context)
+    }
+
+    /// %GeneratorPrototype%.return( value )
+    ///
+    /// Resumes the generator as if the `yield` it is suspended at was a `return` statement.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-generator.prototype.return
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Generator/return
+    pub(crate) fn r#return(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let value = args.get(0).cloned().unwrap_or_default();
+        Self::resume(this, value, ResumeKind::Return, context)
+    }
+
+    /// %GeneratorPrototype%.throw( exception )
+    ///
+    /// Resumes the generator as if the `yield` it is suspended at threw `exception`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-generator.prototype.throw
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Generator/throw
+    pub(crate) fn throw(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let value = args.get(0).cloned().unwrap_or_default();
+        Self::resume(this, value, ResumeKind::Throw, context)
+    }
+
+    /// Abstract operations `GeneratorResume` and `GeneratorResumeAbrupt`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-generatorresume
+    fn resume(
+        this: &JsValue,
+        value: JsValue,
+        kind: ResumeKind,
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let object = match this {
+            JsValue::Object(object) => object.clone(),
+            _ => return context.throw_type_error("`this` is not a Generator"),
+        };
+        let state = object
+            .borrow_mut()
+            .as_generator_mut()
+            .map(|generator| std::mem::replace(&mut generator.state, GeneratorState::Executing));
+        let state = match state {
+            Some(state) => state,
+            None => return context.throw_type_error("`this` is not a Generator"),
+        };
+
+        let frame = match state {
+            GeneratorState::SuspendedYield(frame) => frame,
+            GeneratorState::SuspendedStart(frame) if kind == ResumeKind::Normal => frame,
+            GeneratorState::Executing => {
+                return context.throw_type_error("Generator is already running")
+            }
+            // A generator that has not started completes without running its body when it is
+            // resumed abruptly.
+            GeneratorState::SuspendedStart(_) | GeneratorState::Completed => {
+                Self::set_frame(&object, None);
+                return match kind {
+                    ResumeKind::Normal => Ok(create_iter_result_object(
+                        JsValue::undefined(),
+                        true,
+                        context,
+                    )),
+                    ResumeKind::Return => Ok(create_iter_result_object(value, true, context)),
+                    ResumeKind::Throw => Err(value),
+                };
+            }
+        };
+
+        let (result, frame) = context.resume_frame(frame, value, kind);
+        let done = frame.is_none();
+        Self::set_frame(&object, frame);
+        Ok(create_iter_result_object(result?, done, context))
+    }
+
+    /// Create the %GeneratorPrototype% object
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-properties-of-generator-prototype
+    pub(crate) fn create_prototype(
+        iterator_prototype: JsObject,
+        context: &mut Context,
+    ) -> JsObject {
+        let _timer = BoaProfiler::global().start_event(Self::NAME, "init");
+
+        let generator = JsObject::from_proto_and_data(iterator_prototype, ObjectData::ordinary());
+        make_builtin_fn(Self::next, "next", &generator, 1, context);
+        make_builtin_fn(Self::r#return, "return", &generator, 1, context);
+        make_builtin_fn(Self::throw, "throw", &generator, 1, context);
+
+        let to_string_tag = WellKnownSymbols::to_string_tag();
+        let to_string_tag_property = PropertyDescriptor::builder()
+            .value(Self::NAME)
+            .writable(false)
+            .enumerable(false)
+            .configurable(true);
+        generator.insert(to_string_tag, to_string_tag_property);
+        generator
+    }
+}
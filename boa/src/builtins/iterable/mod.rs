@@ -9,6 +9,9 @@ use crate::{
     BoaProfiler, Context, JsResult, JsValue,
 };
 
+#[cfg(feature = "vm")]
+use crate::builtins::generator::Generator;
+
 #[derive(Debug, Default)]
 pub struct IteratorPrototypes {
     iterator_prototype: JsObject,
@@ -18,6 +21,8 @@ pub struct IteratorPrototypes {
     regexp_string_iterator: JsObject,
     map_iterator: JsObject,
     for_in_iterator: JsObject,
+    #[cfg(feature = "vm")]
+    generator: JsObject,
 }
 
 impl IteratorPrototypes {
@@ -33,6 +38,8 @@ impl IteratorPrototypes {
             ),
             map_iterator: MapIterator::create_prototype(iterator_prototype.clone(), context),
             for_in_iterator: ForInIterator::create_prototype(iterator_prototype.clone(), context),
+            #[cfg(feature = "vm")]
+            generator: Generator::create_prototype(iterator_prototype.clone(), context),
             iterator_prototype,
         }
     }
@@ -71,6 +78,12 @@ impl IteratorPrototypes {
     pub fn for_in_iterator(&self) -> JsObject {
         self.for_in_iterator.clone()
     }
+
+    #[inline]
+    #[cfg(feature = "vm")]
+    pub fn generator(&self) -> JsObject {
+        self.generator.clone()
+    }
 }
 
 /// `CreateIterResultObject( value, done )`
@@ -195,6 +208,18 @@ impl IteratorRecord {
         }
     }
 
+    /// Get the iterator object of this record.
+    #[cfg(feature = "vm")]
+    pub(crate) fn iterator_object(&self) -> &JsValue {
+        &self.iterator_object
+    }
+
+    /// Get the `next` method of the iterator.
+    #[cfg(feature = "vm")]
+    pub(crate) fn next_function(&self) -> &JsValue {
+        &self.next_function
+    }
+
     /// Get the next value in the iterator
     ///
     /// More information:
@@ -460,3 +460,24 @@ fn json_parse_with_no_args_throws_syntax_error() {
     let result = forward(&mut context, "JSON.parse();");
     assert!(result.contains("SyntaxError"));
 }
+
+#[test]
+fn json_stringify_unpaired_surrogates() {
+    let mut context = Context::new();
+
+    assert_eq!(
+        forward(&mut context, "JSON.stringify('\\uD800')"),
+        r#"""\ud800"""#
+    );
+    assert_eq!(
+        forward(&mut context, "JSON.stringify('a\\uDFFFb')"),
+        r#"""a\udfffb"""#
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "JSON.stringify('\\uD83D\\uDE00') === '\"\\uD83D\\uDE00\"'"
+        ),
+        "true"
+    );
+}
@@ -39,8 +39,13 @@ impl MapIterator {
         context: &mut Context,
     ) -> JsResult<JsValue> {
         if let Some(map_obj) = map.as_object() {
-            if let Some(map) = map_obj.borrow_mut().as_map_mut() {
-                let lock = map.lock(map_obj.clone());
+            // The map must not stay borrowed while the iterator is allocated, since that can trigger a garbage
+            // collection that finalizes other locks on the same map.
+            let lock = map_obj
+                .borrow_mut()
+                .as_map_mut()
+                .map(|map| map.lock(map_obj.clone()));
+            if let Some(lock) = lock {
                 let iter = MapIterator {
                     iterated_map: Some(map_obj.clone()),
                     map_next_index: 0,
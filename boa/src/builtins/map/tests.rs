@@ -404,3 +404,17 @@ fn for_of_delete() {
     assert_eq!(forward(&mut context, "result[3][0]"), "3");
     assert_eq!(forward(&mut context, "result[3][1]"), "\"d\"");
 }
+
+#[test]
+fn unfinished_iterators_are_collected() {
+    let mut context = Context::new();
+    let init = r#"
+        let map = new Map([["1", "one"]]);
+        for (var i = 0; i < 1000; i++) {
+            [map.entries()];
+        }
+        "#;
+    forward(&mut context, init);
+    let result = forward(&mut context, "map.size");
+    assert_eq!(result, "1");
+}
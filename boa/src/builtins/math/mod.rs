@@ -18,6 +18,10 @@ use crate::{
 
 use super::JsArgs;
 
+mod random;
+
+pub use self::random::{RandomSource, Xorshift128Plus};
+
 #[cfg(test)]
 mod tests;
 
@@ -715,9 +719,9 @@ impl Math {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-math.random
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/random
-    pub(crate) fn random(_: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+    pub(crate) fn random(_: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
         // NOTE: Each Math.random function created for distinct realms must produce a distinct sequence of values from successive calls.
-        Ok(rand::random::<f64>().into())
+        Ok(context.random_source_mut().random().into())
     }
 
     /// Round a number to the nearest integer.
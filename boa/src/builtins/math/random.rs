@@ -0,0 +1,72 @@
+//! Sources of random numbers for `Math.random()`.
+//!
+//! Every [`Context`][crate::Context] owns its own source, so the sequence of numbers generated in one context never
+//! depends on what runs in another one. By default, a context uses an [`Xorshift128Plus`] generator seeded from the
+//! entropy of the host system. Embedders can seed it with [`Context::set_random_seed`][crate::Context::set_random_seed]
+//! to get reproducible runs, or supply their own [`RandomSource`] with
+//! [`Context::set_random_source`][crate::Context::set_random_source].
+
+use std::fmt::Debug;
+
+/// A source of random numbers for `Math.random()`.
+pub trait RandomSource: Debug {
+    /// Returns a number in the range `[0, 1)`.
+    ///
+    /// The numbers returned by successive calls should be chosen randomly or pseudo randomly with approximately
+    /// uniform distribution over that range.
+    fn random(&mut self) -> f64;
+}
+
+/// A pseudo random number generator using the xorshift128+ algorithm.
+///
+/// The sequence of numbers generated from a given seed is part of the public API: it is guaranteed to stay the same
+/// across versions of Boa and platforms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Xorshift128Plus {
+    state: [u64; 2],
+}
+
+impl Xorshift128Plus {
+    /// Creates a new generator from a seed.
+    ///
+    /// The seed is expanded into the state of the generator using SplitMix64, so any seed, including `0`, is valid.
+    pub fn new(seed: u64) -> Self {
+        /// Returns the next output of the SplitMix64 generator with the state `x`.
+        fn split_mix64(x: &mut u64) -> u64 {
+            *x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = *x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        }
+
+        let mut seed = seed;
+        Self {
+            state: [split_mix64(&mut seed), split_mix64(&mut seed)],
+        }
+    }
+
+    /// Creates a new generator seeded from the entropy of the host system.
+    pub fn from_entropy() -> Self {
+        Self::new(rand::random())
+    }
+
+    /// Returns the next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        let [mut s1, s0] = self.state;
+        let result = s0.wrapping_add(s1);
+        s1 ^= s1 << 23;
+        s1 ^= s1 >> 17;
+        s1 ^= s0;
+        s1 ^= s0 >> 26;
+        self.state = [s0, s1];
+        result
+    }
+}
+
+impl RandomSource for Xorshift128Plus {
+    fn random(&mut self) -> f64 {
+        // Use the 53 most significant bits, as many as an `f64` can represent exactly in `[0, 1)`.
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
@@ -1,5 +1,6 @@
 #![allow(clippy::float_cmp)]
 
+use super::{RandomSource, Xorshift128Plus};
 use crate::{forward, forward_val, Context};
 use std::f64;
 
@@ -585,6 +586,86 @@ fn pow() {
     assert_eq!(d.to_number(&mut context).unwrap(), 0.020_408_163_265_306_12);
 }
 
+#[test]
+fn random() {
+    let mut context = Context::new();
+    let init = r#"
+        var values = [];
+        for (var i = 0; i < 1000; i++) {
+            values.push(Math.random());
+        }
+        "#;
+
+    eprintln!("{}", forward(&mut context, init));
+
+    assert_eq!(
+        forward(&mut context, "values.every(v => v >= 0 && v < 1)"),
+        "true"
+    );
+    assert_eq!(forward(&mut context, "new Set(values).size > 990"), "true");
+}
+
+#[test]
+fn random_seeded() {
+    let mut first = Context::new();
+    first.set_random_seed(42);
+    let mut second = Context::new();
+    second.set_random_seed(42);
+
+    let src = "[Math.random(), Math.random(), Math.random()].join()";
+    let first_values = forward(&mut first, src);
+    assert_eq!(first_values, forward(&mut second, src));
+
+    // Runs in other contexts don't affect the sequence.
+    let mut third = Context::new();
+    third.set_random_seed(42);
+    forward(&mut first, "Math.random()");
+    assert_eq!(first_values, forward(&mut third, src));
+
+    let mut other = Context::new();
+    other.set_random_seed(43);
+    assert_ne!(first_values, forward(&mut other, src));
+}
+
+#[test]
+fn random_seeded_sequence() {
+    // The sequence generated from a seed must never change.
+    let mut generator = Xorshift128Plus::new(0);
+    let values: Vec<f64> = (0..3).map(|_| generator.random()).collect();
+    assert_eq!(
+        values,
+        [
+            0.314_838_805_262_152_7,
+            0.997_534_173_222_583_9,
+            0.362_113_023_238_824
+        ]
+    );
+}
+
+#[test]
+fn random_custom_source() {
+    #[derive(Debug)]
+    struct Counter(f64);
+
+    impl RandomSource for Counter {
+        fn random(&mut self) -> f64 {
+            self.0 += 0.25;
+            self.0 % 1.0
+        }
+    }
+
+    let mut context = Context::new();
+    context.set_random_source(Counter(0.0));
+
+    assert_eq!(
+        forward(
+            &mut context,
+            "[Math.random(), Math.random(), Math.random(), Math.random()].join()"
+        ),
+        "\"0.25,0.5,0.75,0\""
+    );
+}
+
 #[test]
 fn round() {
     let mut context = Context::new();
@@ -9,6 +9,8 @@ pub mod console;
 pub mod date;
 pub mod error;
 pub mod function;
+#[cfg(feature = "vm")]
+pub mod generator;
 pub mod global_this;
 pub mod infinity;
 pub mod intrinsics;
@@ -23,6 +25,7 @@ pub mod reflect;
 pub mod regexp;
 pub mod set;
 pub mod string;
+pub mod structured_clone;
 pub mod symbol;
 pub mod typed_array;
 pub mod undefined;
@@ -49,6 +52,7 @@ pub(crate) use self::{
     set::set_iterator::SetIterator,
     set::Set,
     string::String,
+    structured_clone::StructuredClone,
     symbol::Symbol,
     typed_array::{
         BigInt64Array, BigUint64Array, Float32Array, Float64Array, Int16Array, Int32Array,
@@ -150,7 +154,8 @@ pub fn init(context: &mut Context) {
         SyntaxError,
         EvalError,
         UriError,
-        Reflect
+        Reflect,
+        StructuredClone
     };
 
     #[cfg(feature = "console")]
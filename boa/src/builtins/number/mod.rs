@@ -733,7 +733,7 @@ impl Number {
     ) -> JsResult<JsValue> {
         if let (Some(val), radix) = (args.get(0), args.get_or_undefined(1)) {
             // 1. Let inputString be ? ToString(string).
-            let input_string = val.to_string(context)?;
+            let input_string = val.to_string(context)?.to_std_string_lossy();
 
             // 2. Let S be ! TrimString(inputString, start).
             let mut var_s = input_string.trim_start_matches(is_trimmable_whitespace);
@@ -858,7 +858,7 @@ impl Number {
         context: &mut Context,
     ) -> JsResult<JsValue> {
         if let Some(val) = args.get(0) {
-            let input_string = val.to_string(context)?;
+            let input_string = val.to_string(context)?.to_std_string_lossy();
             let s = input_string.trim_start_matches(is_trimmable_whitespace);
             let s_prefix_lower = s.chars().take(4).collect::<String>().to_ascii_lowercase();
 
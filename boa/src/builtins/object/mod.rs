@@ -21,9 +21,10 @@ use crate::{
         IntegrityLevel, JsObject, ObjectData, ObjectInitializer, ObjectKind,
     },
     property::{Attribute, DescriptorKind, PropertyDescriptor, PropertyKey, PropertyNameKind},
+    string::utf16,
     symbol::WellKnownSymbols,
     value::JsValue,
-    BoaProfiler, Context, JsResult,
+    BoaProfiler, Context, JsResult, JsString,
 };
 
 use super::Array;
@@ -488,10 +489,13 @@ impl Object {
         let tag = o.get(WellKnownSymbols::to_string_tag(), context)?;
 
         // 16. If Type(tag) is not String, set tag to builtinTag.
-        let tag_str = tag.as_string().map(|s| s.as_str()).unwrap_or(builtin_tag);
+        let tag_str = tag
+            .as_string()
+            .cloned()
+            .unwrap_or_else(|| JsString::new(builtin_tag));
 
         // 17. Return the string-concatenation of "[object ", tag, and "]".
-        Ok(format!("[object {}]", tag_str).into())
+        Ok(JsString::concat_array(&[utf16!("[object "), &tag_str, utf16!("]")]).into())
     }
 
     /// `Object.prototype.hasOwnProperty( property )`
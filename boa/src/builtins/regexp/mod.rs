@@ -20,12 +20,14 @@ use crate::{
         JsObject, ObjectData,
     },
     property::Attribute,
+    string::utf16,
     symbol::WellKnownSymbols,
     value::{IntegerOrInfinity, JsValue},
     BoaProfiler, Context, JsResult, JsString,
 };
 use regexp_string_iterator::RegExpStringIterator;
 use regress::Regex;
+use std::cmp::Ordering;
 
 use super::JsArgs;
 
@@ -182,6 +184,18 @@ impl RegExp {
     /// The amount of arguments this function object takes.
     pub(crate) const LENGTH: usize = 2;
 
+    /// Returns the value of the `[[OriginalSource]]` internal slot.
+    #[inline]
+    pub(crate) fn original_source(&self) -> &JsString {
+        &self.original_source
+    }
+
+    /// Returns the value of the `[[OriginalFlags]]` internal slot.
+    #[inline]
+    pub(crate) fn original_flags(&self) -> &JsString {
+        &self.original_flags
+    }
+
     /// `22.2.3.1 RegExp ( pattern, flags )`
     ///
     /// More information:
@@ -292,7 +306,7 @@ impl RegExp {
         let mut dot_all = false;
         let mut unicode = false;
         let mut sticky = false;
-        for c in f.chars() {
+        for c in f.to_std_string_lossy().chars() {
             match c {
                 'g' if global => {
                     return context.throw_syntax_error("RegExp flags contains multiple 'g'")
@@ -330,13 +344,16 @@ impl RegExp {
         // 12. Set obj.[[OriginalSource]] to P.
         // 13. Set obj.[[OriginalFlags]] to F.
         // 14. Set obj.[[RegExpMatcher]] to the Abstract Closure that evaluates parseResult by applying the semantics provided in 22.2.2 using patternCharacters as the pattern's List of SourceCharacter values and F as the flag parameters.
-        let matcher = match Regex::with_flags(&p, f.as_ref()) {
-            Err(error) => {
-                return Err(context
-                    .construct_syntax_error(format!("failed to create matcher: {}", error.text)));
-            }
-            Ok(val) => val,
-        };
+        let matcher =
+            match Regex::with_flags(&p.to_std_string_lossy(), f.to_std_string_lossy().as_str()) {
+                Err(error) => {
+                    return Err(context.construct_syntax_error(format!(
+                        "failed to create matcher: {}",
+                        error.text
+                    )));
+                }
+                Ok(val) => val,
+            };
 
         let regexp = RegExp {
             matcher,
@@ -657,22 +674,22 @@ impl RegExp {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-escaperegexppattern
-    fn escape_pattern(src: &str, _flags: &str) -> JsResult<JsValue> {
+    fn escape_pattern(src: &JsString, _flags: &JsString) -> JsResult<JsValue> {
         if src.is_empty() {
             Ok(JsValue::new("(?:)"))
         } else {
-            let mut s = String::from("");
-
-            for c in src.chars() {
-                match c {
-                    '/' => s.push_str("\\/"),
-                    '\n' => s.push_str("\\\\n"),
-                    '\r' => s.push_str("\\\\r"),
-                    _ => s.push(c),
+            let mut s = Vec::with_capacity(src.len());
+
+            for &unit in src.iter() {
+                match unit {
+                    0x002F /* / */ => s.extend_from_slice(utf16!("\\/")),
+                    0x000A /* \n */ => s.extend_from_slice(utf16!("\\\\n")),
+                    0x000D /* \r */ => s.extend_from_slice(utf16!("\\\\r")),
+                    _ => s.push(unit),
                 }
             }
 
-            Ok(JsValue::new(s))
+            Ok(JsValue::new(JsString::from(s)))
         }
     }
 
@@ -823,7 +840,7 @@ impl RegExp {
         // 2. Assert: Type(S) is String.
 
         // 3. Let length be the number of code units in S.
-        let length = input.encode_utf16().count();
+        let length = input.len();
 
         // 4. Let lastIndex be ℝ(? ToLength(? Get(R, "lastIndex"))).
         let mut last_index = this.get("lastIndex", context)?.to_length(context)?;
@@ -832,10 +849,10 @@ impl RegExp {
         let flags = &rx.original_flags;
 
         // 6. If flags contains "g", let global be true; else let global be false.
-        let global = flags.contains('g');
+        let global = flags.contains(&u16::from(b'g'));
 
         // 7. If flags contains "y", let sticky be true; else let sticky be false.
-        let sticky = flags.contains('y');
+        let sticky = flags.contains(&u16::from(b'y'));
 
         // 8. If global is false and sticky is false, set lastIndex to 0.
         if !global && !sticky {
@@ -846,7 +863,12 @@ impl RegExp {
         let matcher = &rx.matcher;
 
         // 10. If flags contains "u", let fullUnicode be true; else let fullUnicode be false.
-        let unicode = flags.contains('u');
+        let unicode = flags.contains(&u16::from(b'u'));
+
+        // The matcher operates on UTF-8 text, so it is run against the lossy conversion of S.
+        // Unpaired surrogates are replaced with U+FFFD, which is a single code unit as well,
+        // so code unit indices into S and into the converted string coincide.
+        let lossy_input = input.to_std_string_lossy();
 
         // 11. Let matchSucceeded be false.
         // 12. Repeat, while matchSucceeded is false,
@@ -865,17 +887,15 @@ impl RegExp {
 
             // b. Let r be matcher(S, lastIndex).
             // Check if last_index is a valid utf8 index into input.
-            let last_byte_index = match String::from_utf16(
-                &input.encode_utf16().take(last_index).collect::<Vec<u16>>(),
-            ) {
-                Ok(s) => s.len(),
-                Err(_) => {
+            let last_byte_index = match utf16_to_byte_index(&lossy_input, last_index) {
+                Some(index) => index,
+                None => {
                     return Err(context.construct_type_error(
                         "Failed to get byte index from utf16 encoded string",
                     ))
                 }
             };
-            let r = matcher.find_from(&input, last_byte_index).next();
+            let r = matcher.find_from(&lossy_input, last_byte_index).next();
 
             match r {
                 // c. If r is failure, then
@@ -896,7 +916,7 @@ impl RegExp {
                 Some(m) => {
                     // c. If r is failure, then
                     // d. Else,
-                    if m.start() != last_index {
+                    if byte_to_utf16_index(&lossy_input, m.start()) != last_index {
                         // i. If sticky is true, then
                         if sticky {
                             // 1. Perform ? Set(R, "lastIndex", +0𝔽, true).
@@ -918,16 +938,19 @@ impl RegExp {
         };
 
         // 13. Let e be r's endIndex value.
-        let mut e = match_value.end();
-
         // 14. If fullUnicode is true, then
-        if unicode {
-            // e is an index into the Input character list, derived from S, matched by matcher.
-            // Let eUTF be the smallest index into S that corresponds to the character at element e of Input.
-            // If e is greater than or equal to the number of elements in Input, then eUTF is the number of code units in S.
-            // b. Set e to eUTF.
-            e = input.split_at(e).0.encode_utf16().count();
-        }
+        //     a. e is an index into the Input character list, derived from S, matched by matcher.
+        //        Let eUTF be the smallest index into S that corresponds to the character at element e of Input.
+        //        If e is greater than or equal to the number of elements in Input, then eUTF is the number of code units in S.
+        //     b. Set e to eUTF.
+        // The matcher always reports byte indices into the UTF-8 input, so they are converted unconditionally.
+        let e = byte_to_utf16_index(&lossy_input, match_value.end());
+        let substring = |range: std::ops::Range<usize>| {
+            JsString::from(
+                &input[byte_to_utf16_index(&lossy_input, range.start)
+                    ..byte_to_utf16_index(&lossy_input, range.end)],
+            )
+        };
 
         // 15. If global is true or sticky is true, then
         if global || sticky {
@@ -945,7 +968,7 @@ impl RegExp {
         let a = Array::array_create(n + 1, None, context)?;
 
         // 20. Perform ! CreateDataPropertyOrThrow(A, "index", 𝔽(lastIndex)).
-        a.create_data_property_or_throw("index", match_value.start(), context)
+        a.create_data_property_or_throw("index", last_index, context)
             .unwrap();
 
         // 21. Perform ! CreateDataPropertyOrThrow(A, "input", S).
@@ -953,11 +976,7 @@ impl RegExp {
             .unwrap();
 
         // 22. Let matchedSubstr be the substring of S from lastIndex to e.
-        let matched_substr = if let Some(s) = input.get(match_value.range()) {
-            s
-        } else {
-            ""
-        };
+        let matched_substr = JsString::from(&input[last_index..e]);
 
         // 23. Perform ! CreateDataPropertyOrThrow(A, "0", matchedSubstr).
         a.create_data_property_or_throw(0, matched_substr, context)
@@ -976,11 +995,7 @@ impl RegExp {
             // ii. Perform ! CreateDataPropertyOrThrow(groups, s, capturedValue).
             for (name, range) in named_groups {
                 if let Some(range) = range {
-                    let value = if let Some(s) = input.get(range.clone()) {
-                        s
-                    } else {
-                        ""
-                    };
+                    let value = substring(range.clone());
 
                     groups
                         .to_object(context)?
@@ -1008,13 +1023,7 @@ impl RegExp {
                 None => JsValue::undefined(),
                 // c. Else if fullUnicode is true, then
                 // d. Else,
-                Some(range) => {
-                    if let Some(s) = input.get(range) {
-                        s.into()
-                    } else {
-                        "".into()
-                    }
-                }
+                Some(range) => substring(range).into(),
             };
 
             // e. Perform ! CreateDataPropertyOrThrow(A, ! ToString(𝔽(i)), capturedValue).
@@ -1211,11 +1220,11 @@ impl RegExp {
 
         // 9. If flags contains "g", let global be true.
         // 10. Else, let global be false.
-        let global = flags.contains('g');
+        let global = flags.contains(&u16::from(b'g'));
 
         // 11. If flags contains "u", let fullUnicode be true.
         // 12. Else, let fullUnicode be false.
-        let unicode = flags.contains('u');
+        let unicode = flags.contains(&u16::from(b'u'));
 
         // 13. Return ! CreateRegExpStringIterator(matcher, S, global, fullUnicode).
         RegExpStringIterator::create_regexp_string_iterator(
@@ -1262,7 +1271,7 @@ impl RegExp {
             .to_string(context)?;
 
         // 4. Let lengthS be the number of code unit elements in S.
-        let length_arg_str = arg_str.encode_utf16().count();
+        let length_arg_str = arg_str.len();
 
         // 5. Let functionalReplace be IsCallable(replaceValue).
         let mut replace_value = args.get_or_undefined(1).clone();
@@ -1348,7 +1357,7 @@ impl RegExp {
             let matched = result.get("0", context)?.to_string(context)?;
 
             // d. Let matchLength be the number of code units in matched.
-            let match_length = matched.encode_utf16().count();
+            let match_length = matched.len();
 
             // e. Let position be ? ToIntegerOrInfinity(? Get(result, "index")).
             let position = result
@@ -1430,12 +1439,12 @@ impl RegExp {
 
                 // ii. Let replacement be ? GetSubstitution(matched, S, position, captures, namedCaptures, replaceValue).
                 replacement = string::get_substitution(
-                    matched.to_string(),
-                    arg_str.to_string(),
+                    &matched,
+                    &arg_str,
                     position,
                     captures,
                     named_captures,
-                    replace_value.to_string(context)?,
+                    &replace_value.to_string(context)?,
                     context,
                 )?;
             }
@@ -1448,13 +1457,11 @@ impl RegExp {
                 //    In such cases, the corresponding substitution is ignored.
                 // ii. Set accumulatedResult to the string-concatenation of accumulatedResult,
                 //     the substring of S from nextSourcePosition to position, and replacement.
-                accumulated_result = format!(
-                    "{}{}{}",
-                    accumulated_result,
-                    arg_str.get(next_source_position..position).unwrap(),
-                    replacement
-                )
-                .into();
+                accumulated_result = JsString::concat_array(&[
+                    &accumulated_result,
+                    &arg_str[next_source_position..position],
+                    &replacement,
+                ]);
 
                 // iii. Set nextSourcePosition to position + matchLength.
                 next_source_position = position + match_length;
@@ -1467,12 +1474,7 @@ impl RegExp {
         }
 
         // 16. Return the string-concatenation of accumulatedResult and the substring of S from nextSourcePosition.
-        Ok(format!(
-            "{}{}",
-            accumulated_result,
-            arg_str.get(next_source_position..).unwrap()
-        )
-        .into())
+        Ok(JsString::concat(&accumulated_result, &arg_str[next_source_position..]).into())
     }
 
     /// `RegExp.prototype[ @@search ]( string )`
@@ -1577,14 +1579,14 @@ impl RegExp {
 
         // 6. If flags contains "u", let unicodeMatching be true.
         // 7. Else, let unicodeMatching be false.
-        let unicode = flags.contains('u');
+        let unicode = flags.contains(&u16::from(b'u'));
 
         // 8. If flags contains "y", let newFlags be flags.
         // 9. Else, let newFlags be the string-concatenation of flags and "y".
-        let new_flags = if flags.contains('y') {
-            flags.to_string()
+        let new_flags = if flags.contains(&u16::from(b'y')) {
+            flags
         } else {
-            format!("{}{}", flags, 'y')
+            JsString::concat(&flags, utf16!("y"))
         };
 
         // 10. Let splitter be ? Construct(C, « rx, newFlags »).
@@ -1618,7 +1620,7 @@ impl RegExp {
         }
 
         // 15. Let size be the length of S.
-        let size = arg_str.encode_utf16().count();
+        let size = arg_str.len();
 
         // 16. If size is 0, then
         if size == 0 {
@@ -1666,13 +1668,7 @@ impl RegExp {
                     q = advance_string_index(arg_str.clone(), q, unicode);
                 } else {
                     // 1. Let T be the substring of S from p to q.
-                    let arg_str_substring = String::from_utf16_lossy(
-                        &arg_str
-                            .encode_utf16()
-                            .skip(p)
-                            .take(q - p)
-                            .collect::<Vec<u16>>(),
-                    );
+                    let arg_str_substring = JsString::from(&arg_str[p..q]);
 
                     // 2. Perform ! CreateDataPropertyOrThrow(A, ! ToString(𝔽(lengthA)), T).
                     a.create_data_property_or_throw(length_a, arg_str_substring, context)
@@ -1727,13 +1723,7 @@ impl RegExp {
         }
 
         // 20. Let T be the substring of S from p to size.
-        let arg_str_substring = String::from_utf16_lossy(
-            &arg_str
-                .encode_utf16()
-                .skip(p)
-                .take(size - p)
-                .collect::<Vec<u16>>(),
-        );
+        let arg_str_substring = JsString::from(&arg_str[p..size]);
 
         // 21. Perform ! CreateDataPropertyOrThrow(A, ! ToString(𝔽(lengthA)), T).
         a.create_data_property_or_throw(length_a, arg_str_substring, context)
@@ -1751,8 +1741,6 @@ impl RegExp {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-advancestringindex
 fn advance_string_index(s: JsString, index: usize, unicode: bool) -> usize {
-    // Regress only works with utf8, so this function differs from the spec.
-
     // 1. Assert: index ≤ 2^53 - 1.
 
     // 2. If unicode is false, return index + 1.
@@ -1761,7 +1749,7 @@ fn advance_string_index(s: JsString, index: usize, unicode: bool) -> usize {
     }
 
     // 3. Let length be the number of code units in S.
-    let length = s.encode_utf16().count();
+    let length = s.len();
 
     // 4. If index + 1 ≥ length, return index + 1.
     if index + 1 > length {
@@ -1770,7 +1758,27 @@ fn advance_string_index(s: JsString, index: usize, unicode: bool) -> usize {
 
     // 5. Let cp be ! CodePointAt(S, index).
     let (_, offset, _) =
-        crate::builtins::string::code_point_at(s, index as i32).expect("Failed to get code point");
+        crate::builtins::string::code_point_at(&s, index).expect("Failed to get code point");
 
     index + offset as usize
 }
+
+/// Converts an index into the UTF-16 code units of `s` into the byte index of the same position.
+///
+/// Returns `None` if the index is past the end of the string or falls inside of a surrogate pair.
+fn utf16_to_byte_index(s: &str, index: usize) -> Option<usize> {
+    let mut units = 0;
+    for (byte_index, c) in s.char_indices() {
+        match units.cmp(&index) {
+            Ordering::Equal => return Some(byte_index),
+            Ordering::Greater => return None,
+            Ordering::Less => units += c.len_utf16(),
+        }
+    }
+    (units == index).then_some(s.len())
+}
+
+/// Converts a byte index into `s` into the index of the same position in its UTF-16 code units.
+fn byte_to_utf16_index(s: &str, index: usize) -> usize {
+    s[..index].encode_utf16().count()
+}
@@ -19,13 +19,13 @@ use crate::object::internal_methods::get_prototype_from_constructor;
 use crate::object::JsObject;
 use crate::{
     builtins::{string::string_iterator::StringIterator, Array, BuiltIn, RegExp},
-    object::{ConstructorBuilder, ObjectData},
+    object::{ConstructorBuilder, FunctionBuilder, ObjectData},
     property::{Attribute, PropertyDescriptor},
+    string::{trim_end, trim_start, utf16, CodePoint},
     symbol::WellKnownSymbols,
     BoaProfiler, Context, JsResult, JsString, JsValue,
 };
 use std::{
-    char::{decode_utf16, from_u32},
     cmp::{max, min},
     string::String as StdString,
 };
@@ -33,20 +33,15 @@ use unicode_normalization::UnicodeNormalization;
 
 use super::JsArgs;
 
-pub(crate) fn code_point_at(string: JsString, position: i32) -> Option<(u32, u8, bool)> {
-    let size = string.encode_utf16().count() as i32;
-    if position < 0 || position >= size {
-        return None;
-    }
-    let mut encoded = string.encode_utf16();
-    let first = encoded.nth(position as usize)?;
+pub(crate) fn code_point_at(string: &JsString, position: usize) -> Option<(u32, u8, bool)> {
+    let first = *string.get(position)?;
     if !is_leading_surrogate(first) && !is_trailing_surrogate(first) {
         return Some((first as u32, 1, false));
     }
-    if is_trailing_surrogate(first) || position + 1 == size {
+    if is_trailing_surrogate(first) || position + 1 == string.len() {
         return Some((first as u32, 1, true));
     }
-    let second = encoded.next()?;
+    let second = string[position + 1];
     if !is_trailing_surrogate(second) {
         return Some((first as u32, 1, true));
     }
@@ -99,7 +94,21 @@ impl BuiltIn for String {
 
         let symbol_iterator = WellKnownSymbols::iterator();
 
+        let trim_start = FunctionBuilder::native(context, Self::trim_start)
+            .name("trimStart")
+            .length(0)
+            .constructor(false)
+            .build();
+
+        let trim_end = FunctionBuilder::native(context, Self::trim_end)
+            .name("trimEnd")
+            .length(0)
+            .constructor(false)
+            .build();
+
         let attribute = Attribute::READONLY | Attribute::NON_ENUMERABLE | Attribute::PERMANENT;
+        let method_attribute =
+            Attribute::WRITABLE | Attribute::NON_ENUMERABLE | Attribute::CONFIGURABLE;
         let string_object = ConstructorBuilder::with_standard_object(
             context,
             Self::constructor,
@@ -109,6 +118,8 @@ impl BuiltIn for String {
         .length(Self::LENGTH)
         .property("length", 0, attribute)
         .static_method(Self::from_char_code, "fromCharCode", 1)
+        .static_method(Self::from_code_point, "fromCodePoint", 1)
+        .static_method(Self::raw, "raw", 1)
         .method(Self::char_at, "charAt", 1)
         .method(Self::char_code_at, "charCodeAt", 1)
         .method(Self::code_point_at, "codePointAt", 1)
@@ -126,10 +137,17 @@ impl BuiltIn for String {
         .method(Self::pad_end, "padEnd", 1)
         .method(Self::pad_start, "padStart", 1)
         .method(Self::trim, "trim", 0)
-        .method(Self::trim_start, "trimStart", 0)
-        .method(Self::trim_end, "trimEnd", 0)
+        .property("trimStart", trim_start.clone(), method_attribute)
+        .property("trimEnd", trim_end.clone(), method_attribute)
+        .property("trimLeft", trim_start, method_attribute)
+        .property("trimRight", trim_end, method_attribute)
         .method(Self::to_lowercase, "toLowerCase", 0)
         .method(Self::to_uppercase, "toUpperCase", 0)
+        .method(Self::to_locale_lowercase, "toLocaleLowerCase", 0)
+        .method(Self::to_locale_uppercase, "toLocaleUpperCase", 0)
+        .method(Self::locale_compare, "localeCompare", 1)
+        .method(Self::is_well_formed, "isWellFormed", 0)
+        .method(Self::to_well_formed, "toWellFormed", 0)
         .method(Self::substring, "substring", 2)
         .method(Self::substr, "substr", 2)
         .method(Self::split, "split", 2)
@@ -140,6 +158,19 @@ impl BuiltIn for String {
         .method(Self::iterator, (symbol_iterator, "[Symbol.iterator]"), 0)
         .method(Self::search, "search", 1)
         .method(Self::at, "at", 1)
+        .method(Self::anchor, "anchor", 1)
+        .method(Self::big, "big", 0)
+        .method(Self::blink, "blink", 0)
+        .method(Self::bold, "bold", 0)
+        .method(Self::fixed, "fixed", 0)
+        .method(Self::fontcolor, "fontcolor", 1)
+        .method(Self::fontsize, "fontsize", 1)
+        .method(Self::italics, "italics", 0)
+        .method(Self::link, "link", 1)
+        .method(Self::small, "small", 0)
+        .method(Self::strike, "strike", 0)
+        .method(Self::sub, "sub", 0)
+        .method(Self::sup, "sup", 0)
         .build();
 
         string_object.into()
@@ -197,7 +228,7 @@ impl String {
     /// [spec]: https://tc39.es/ecma262/#sec-stringcreate
     fn string_create(value: JsString, prototype: JsObject, context: &mut Context) -> JsObject {
         // 7. Let length be the number of code unit elements in value.
-        let len = value.encode_utf16().count();
+        let len = value.len();
 
         // 1. Let S be ! MakeBasicObject(« [[Prototype]], [[Extensible]], [[StringData]] »).
         // 2. Set S.[[Prototype]] to prototype.
@@ -256,8 +287,120 @@ impl String {
         // 4. Return the String value whose code units are the elements in the List elements.
         //    If codeUnits is empty, the empty String is returned.
 
-        let s = std::string::String::from_utf16_lossy(elements.as_slice());
-        Ok(JsValue::String(JsString::new(s)))
+        Ok(JsValue::String(JsString::from(elements)))
+    }
+
+    /// `String.fromCodePoint(...codePoints)`
+    ///
+    /// Construct a `String` from one or more code points.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.fromcodepoint
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/fromCodePoint
+    pub(crate) fn from_code_point(
+        _: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let result be the empty String.
+        let mut result = Vec::with_capacity(args.len());
+
+        // 2. For each element next of codePoints, do
+        for next in args {
+            // a. Let nextCP be ? ToNumber(next).
+            let next_cp = next.to_number(context)?;
+
+            // b. If ! IsIntegralNumber(nextCP) is false, throw a RangeError exception.
+            // c. If ℝ(nextCP) < 0 or ℝ(nextCP) > 0x10FFFF, throw a RangeError exception.
+            if next_cp.fract() != 0.0 || !(0.0..=f64::from(0x10FFFF)).contains(&next_cp) {
+                return context.throw_range_error(format!("invalid code point: {}", next_cp));
+            }
+
+            // d. Set result to the string-concatenation of result and ! UTF16EncodeCodePoint(ℝ(nextCP)).
+            let next_cp = next_cp as u32;
+            if next_cp > 0xFFFF {
+                let next_cp = next_cp - 0x10000;
+                result.push(0xD800 + (next_cp >> 10) as u16);
+                result.push(0xDC00 + (next_cp & 0x3FF) as u16);
+            } else {
+                result.push(next_cp as u16);
+            }
+        }
+
+        // 3. Assert: If codePoints is empty, then result is the empty String.
+        // 4. Return result.
+        Ok(JsValue::new(JsString::from(result)))
+    }
+
+    /// `String.raw( template, ...substitutions )`
+    ///
+    /// Returns the raw string form of a template literal, with its substitutions, but without processing its escape
+    /// sequences. It is meant to be used as the tag function of tagged templates, as in ``String.raw`C:\Users\${name}` ``.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.raw
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/raw
+    pub(crate) fn raw(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let substitutions = args.get(1..).unwrap_or_default();
+
+        // 1. Let substitutionCount be the number of elements in substitutions.
+        let substitution_count = substitutions.len();
+
+        // 2. Let cooked be ? ToObject(template).
+        let cooked = args.get_or_undefined(0).to_object(context)?;
+
+        // 3. Let raw be ? ToObject(? Get(cooked, "raw")).
+        let raw = cooked.get("raw", context)?.to_object(context)?;
+
+        // 4. Let literalCount be ? LengthOfArrayLike(raw).
+        let literal_count = raw.length_of_array_like(context)?;
+
+        // 5. If literalCount ≤ 0, return the empty String.
+        if literal_count == 0 {
+            return Ok(JsValue::new(""));
+        }
+
+        // 6. Let R be the empty String.
+        let mut result = Vec::new();
+
+        // 7. Let nextIndex be 0.
+        let mut next_index = 0;
+
+        // 8. Repeat,
+        loop {
+            // a. Let nextLiteralVal be ? Get(raw, ! ToString(𝔽(nextIndex))).
+            let next_literal_val = raw.get(next_index, context)?;
+
+            // b. Let nextLiteral be ? ToString(nextLiteralVal).
+            let next_literal = next_literal_val.to_string(context)?;
+
+            // c. Set R to the string-concatenation of R and nextLiteral.
+            result.extend_from_slice(&next_literal);
+
+            // d. If nextIndex + 1 = literalCount, return R.
+            if next_index + 1 == literal_count {
+                return Ok(JsValue::new(JsString::from(result)));
+            }
+
+            // e. If nextIndex < substitutionCount, then
+            if next_index < substitution_count {
+                // i. Let nextSubVal be substitutions[nextIndex].
+                // ii. Let nextSub be ? ToString(nextSubVal).
+                let next_sub = substitutions[next_index].to_string(context)?;
+
+                // iii. Set R to the string-concatenation of R and nextSub.
+                result.extend_from_slice(&next_sub);
+            }
+
+            // f. Set nextIndex to nextIndex + 1.
+            next_index += 1;
+        }
     }
 
     /// Get the string value to a primitive string
@@ -307,15 +450,9 @@ impl String {
             return Ok("".into());
         }
 
-        // Calling .len() on a string would give the wrong result, as they are bytes not the number of
-        // unicode code points
-        // Note that this is an O(N) operation (because UTF-8 is complex) while getting the number of
-        // bytes is an O(1) operation.
-        if let Some(utf16_val) = primitive_val.encode_utf16().nth(pos as usize) {
-            Ok(JsValue::new(from_u32(utf16_val as u32).unwrap()))
-        } else {
-            Ok("".into())
-        }
+        Ok(JsValue::new(JsString::from(
+            &primitive_val[pos as usize..=pos as usize],
+        )))
     }
 
     /// `String.prototype.at ( index )`
@@ -332,22 +469,20 @@ impl String {
     pub(crate) fn at(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
         let this = this.require_object_coercible(context)?;
         let s = this.to_string(context)?;
-        let len = s.encode_utf16().count();
+        let len = s.len();
         let relative_index = args
             .get(0)
             .cloned()
             .unwrap_or_default()
             .to_integer(context)?;
         let k = if relative_index < 0 as f64 {
-            len - (-relative_index as usize)
+            len.checked_sub(-relative_index as usize)
         } else {
-            relative_index as usize
+            Some(relative_index as usize)
         };
 
-        if let Some(utf16_val) = s.encode_utf16().nth(k) {
-            Ok(JsValue::new(
-                from_u32(u32::from(utf16_val)).expect("invalid utf-16 character"),
-            ))
+        if let Some(k) = k.filter(|&k| k < len) {
+            Ok(JsValue::new(JsString::from(&s[k..=k])))
         } else {
             Ok(JsValue::undefined())
         }
@@ -386,7 +521,7 @@ impl String {
             return Ok(JsValue::undefined());
         }
 
-        if let Some((code_point, _, _)) = code_point_at(primitive_val, pos) {
+        if let Some((code_point, _, _)) = code_point_at(&primitive_val, pos as usize) {
             Ok(JsValue::new(code_point))
         } else {
             Ok(JsValue::undefined())
@@ -426,14 +561,7 @@ impl String {
             return Ok(JsValue::nan());
         }
 
-        // Calling .len() on a string would give the wrong result, as they are bytes not the number of unicode code points
-        // Note that this is an O(N) operation (because UTF-8 is complex) while getting the number of bytes is an O(1) operation.
-        // If there is no element at that index, the result is NaN
-        if let Some(utf16_val) = primitive_val.encode_utf16().nth(pos as usize) {
-            Ok(JsValue::new(f64::from(utf16_val)))
-        } else {
-            Ok(JsValue::nan())
-        }
+        Ok(JsValue::new(f64::from(primitive_val[pos as usize])))
     }
 
     /// `String.prototype.concat( str1[, ...strN] )`
@@ -456,10 +584,10 @@ impl String {
         context: &mut Context,
     ) -> JsResult<JsValue> {
         let object = this.require_object_coercible(context)?;
-        let mut string = object.to_string(context)?.to_string();
+        let mut string = object.to_string(context)?;
 
         for arg in args {
-            string.push_str(&arg.to_string(context)?);
+            string = JsString::concat_rope(&string, &arg.to_string(context)?);
         }
 
         Ok(JsValue::new(string))
@@ -498,7 +626,7 @@ impl String {
                 return context
                     .throw_range_error("repeat count must not overflow maximum string length");
             }
-            Ok(string.repeat(n as usize).into())
+            Ok(JsString::from(string.repeat(n as usize)).into())
         } else {
             Ok("".into())
         }
@@ -523,9 +651,7 @@ impl String {
         // Then we convert it into a Rust String by wrapping it in from_value
         let primitive_val = this.to_string(context)?;
 
-        // Calling .len() on a string would give the wrong result, as they are bytes not the number of unicode code points
-        // Note that this is an O(N) operation (because UTF-8 is complex) while getting the number of bytes is an O(1) operation.
-        let length = primitive_val.chars().count() as i32;
+        let length = primitive_val.len() as i32;
 
         let start = args
             .get(0)
@@ -549,14 +675,13 @@ impl String {
             min(end, length)
         };
 
-        let span = max(to.wrapping_sub(from), 0);
-
-        let new_str: StdString = primitive_val
-            .chars()
-            .skip(from as usize)
-            .take(span as usize)
-            .collect();
-        Ok(JsValue::new(new_str))
+        if from >= to {
+            Ok(JsValue::new(JsString::empty()))
+        } else {
+            Ok(JsValue::new(JsString::from(
+                &primitive_val[from as usize..to as usize],
+            )))
+        }
     }
 
     /// `String.prototype.startWith( searchString[, position] )`
@@ -588,8 +713,8 @@ impl String {
 
         let search_string = arg.to_string(context)?;
 
-        let length = primitive_val.chars().count() as i32;
-        let search_length = search_string.chars().count() as i32;
+        let length = primitive_val.len() as i32;
+        let search_length = search_string.len() as i32;
 
         // If less than 2 args specified, position is 'undefined', defaults to 0
         let position = if let Some(integer) = args.get(1) {
@@ -605,9 +730,8 @@ impl String {
             Ok(JsValue::new(false))
         } else {
             // Only use the part of the string from "start"
-            let this_string: StdString = primitive_val.chars().skip(start as usize).collect();
             Ok(JsValue::new(
-                this_string.starts_with(search_string.as_str()),
+                primitive_val[start as usize..].starts_with(&search_string),
             ))
         }
     }
@@ -641,8 +765,8 @@ impl String {
 
         let search_string = arg.to_string(context)?;
 
-        let length = primitive_val.chars().count() as i32;
-        let search_length = search_string.chars().count() as i32;
+        let length = primitive_val.len() as i32;
+        let search_length = search_string.len() as i32;
 
         // If less than 2 args specified, end_position is 'undefined', defaults to
         // length of this
@@ -659,8 +783,9 @@ impl String {
             Ok(JsValue::new(false))
         } else {
             // Only use the part of the string up to "end"
-            let this_string: StdString = primitive_val.chars().take(end as usize).collect();
-            Ok(JsValue::new(this_string.ends_with(search_string.as_str())))
+            Ok(JsValue::new(
+                primitive_val[..end as usize].ends_with(&search_string),
+            ))
         }
     }
 
@@ -693,7 +818,7 @@ impl String {
 
         let search_string = arg.to_string(context)?;
 
-        let length = primitive_val.chars().count() as i32;
+        let length = primitive_val.len() as i32;
 
         // If less than 2 args specified, position is 'undefined', defaults to 0
 
@@ -706,9 +831,11 @@ impl String {
         let start = min(max(position, 0), length);
 
         // Take the string from "this" and use only the part of it after "start"
-        let this_string: StdString = primitive_val.chars().skip(start as usize).collect();
-
-        Ok(JsValue::new(this_string.contains(search_string.as_str())))
+        Ok(JsValue::new(
+            primitive_val
+                .index_of(&search_string, start as usize)
+                .is_some(),
+        ))
     }
 
     fn is_regexp_object(value: &JsValue) -> bool {
@@ -788,9 +915,7 @@ impl String {
         };
 
         // 10. Let preserved be the substring of string from 0 to position.
-        let preserved = StdString::from_utf16_lossy(
-            &this_str.encode_utf16().take(position).collect::<Vec<u16>>(),
-        );
+        let preserved = &this_str[..position];
 
         // 11. If functionalReplace is true, then
         // 12. Else,
@@ -800,7 +925,11 @@ impl String {
                 .call(
                     replace_value,
                     &JsValue::undefined(),
-                    &[search_str.into(), position.into(), this_str.clone().into()],
+                    &[
+                        search_str.clone().into(),
+                        position.into(),
+                        this_str.clone().into(),
+                    ],
                 )?
                 .to_string(context)?
         } else {
@@ -810,28 +939,22 @@ impl String {
 
             // c. Let replacement be ! GetSubstitution(searchString, string, position, captures, undefined, replaceValue).
             get_substitution(
-                search_str.to_string(),
-                this_str.to_string(),
+                &search_str,
+                &this_str,
                 position,
                 captures,
                 JsValue::undefined(),
-                replace_value.to_string(context)?,
+                &replace_value.to_string(context)?,
                 context,
             )?
         };
 
         // 13. Return the string-concatenation of preserved, replacement, and the substring of string from position + searchLength.
-        Ok(format!(
-            "{}{}{}",
+        Ok(JsString::concat_array(&[
             preserved,
-            replacement,
-            StdString::from_utf16_lossy(
-                &this_str
-                    .encode_utf16()
-                    .skip(position + search_length)
-                    .collect::<Vec<u16>>()
-            )
-        )
+            &replacement,
+            &this_str[position + search_length..],
+        ])
         .into())
     }
 
@@ -873,7 +996,7 @@ impl String {
                     flags.require_object_coercible(context)?;
 
                     // iii. If ? ToString(flags) does not contain "g", throw a TypeError exception.
-                    if !flags.to_string(context)?.contains('g') {
+                    if !flags.to_string(context)?.contains(&u16::from(b'g')) {
                         return context.throw_type_error(
                             "String.prototype.replaceAll called with a non-global RegExp argument",
                         );
@@ -912,7 +1035,7 @@ impl String {
         };
 
         // 7. Let searchLength be the length of searchString.
-        let search_length = search_string.encode_utf16().count();
+        let search_length = search_string.len();
 
         // 8. Let advanceBy be max(1, searchLength).
         let advance_by = max(1, search_length);
@@ -936,19 +1059,12 @@ impl String {
         let mut end_of_last_match = 0;
 
         // 13. Let result be the empty String.
-        let mut result = JsString::new("");
+        let mut result = Vec::with_capacity(string.len());
 
         // 14. For each element p of matchPositions, do
         for p in match_positions {
             // a. Let preserved be the substring of string from endOfLastMatch to p.
-            let preserved = StdString::from_utf16_lossy(
-                &string
-                    .clone()
-                    .encode_utf16()
-                    .skip(end_of_last_match)
-                    .take(p - end_of_last_match)
-                    .collect::<Vec<u16>>(),
-            );
+            let preserved = &string[end_of_last_match..p];
 
             // b. If functionalReplace is true, then
             // c. Else,
@@ -970,40 +1086,32 @@ impl String {
                 // ii. Let captures be a new empty List.
                 // iii. Let replacement be ! GetSubstitution(searchString, string, p, captures, undefined, replaceValue).
                 get_substitution(
-                    search_string.to_string(),
-                    string.to_string(),
+                    &search_string,
+                    &string,
                     p,
                     Vec::new(),
                     JsValue::undefined(),
-                    replace_value_string.clone(),
+                    &replace_value_string,
                     context,
                 )
                 .expect("GetSubstitution should never fail here.")
             };
             // d. Set result to the string-concatenation of result, preserved, and replacement.
-            result = JsString::new(format!("{}{}{}", result.as_str(), &preserved, &replacement));
+            result.extend_from_slice(preserved);
+            result.extend_from_slice(&replacement);
 
             // e. Set endOfLastMatch to p + searchLength.
             end_of_last_match = p + search_length;
         }
 
         // 15. If endOfLastMatch < the length of string, then
-        if end_of_last_match < string.encode_utf16().count() {
+        if end_of_last_match < string.len() {
             // a. Set result to the string-concatenation of result and the substring of string from endOfLastMatch.
-            result = JsString::new(format!(
-                "{}{}",
-                result.as_str(),
-                &StdString::from_utf16_lossy(
-                    &string
-                        .encode_utf16()
-                        .skip(end_of_last_match)
-                        .collect::<Vec<u16>>()
-                )
-            ));
+            result.extend_from_slice(&string[end_of_last_match..]);
         }
 
         // 16. Return result.
-        Ok(result.into())
+        Ok(JsString::from(result).into())
     }
 
     /// `String.prototype.indexOf( searchValue[, fromIndex] )`
@@ -1033,24 +1141,16 @@ impl String {
             .unwrap_or_else(JsValue::undefined)
             .to_string(context)?;
 
-        let length = string.chars().count();
+        let length = string.len();
         let start = args
             .get(1)
             .map(|position| position.to_integer(context))
             .transpose()?
             .map_or(0, |position| position.max(0.0).min(length as f64) as usize);
 
-        if search_string.is_empty() {
-            return Ok(start.min(length).into());
-        }
-
-        if start < length {
-            if let Some(position) = string.find(search_string.as_str()) {
-                return Ok(string[..position].chars().count().into());
-            }
-        }
-
-        Ok(JsValue::new(-1))
+        Ok(string
+            .index_of(&search_string, start)
+            .map_or_else(|| JsValue::new(-1), JsValue::new))
     }
 
     /// `String.prototype.lastIndexOf( searchValue[, fromIndex] )`
@@ -1080,7 +1180,7 @@ impl String {
             .unwrap_or_else(JsValue::undefined)
             .to_string(context)?;
 
-        let length = string.chars().count();
+        let length = string.len();
         let start = args
             .get(1)
             .map(|position| position.to_integer(context))
@@ -1092,8 +1192,11 @@ impl String {
         }
 
         if start < length {
-            if let Some(position) = string.rfind(search_string.as_str()) {
-                return Ok(string[..position].chars().count().into());
+            if let Some(position) = string
+                .windows(search_string.len())
+                .rposition(|window| window == &search_string[..])
+            {
+                return Ok(position.into());
             }
         }
 
@@ -1157,25 +1260,25 @@ impl String {
             return JsValue::new(primitive);
         }
 
-        let filler = fill_string.as_deref().unwrap_or(" ");
+        let filler = fill_string.as_deref().unwrap_or(utf16!(" "));
 
         if filler.is_empty() {
             return JsValue::new(primitive);
         }
 
+        // Repeat the filler and cut it to size max_length
         let fill_len = max_length.wrapping_sub(primitive_length);
-        let mut fill_str = StdString::new();
-
-        while fill_str.len() < fill_len as usize {
-            fill_str.push_str(filler);
-        }
-        // Cut to size max_length
-        let concat_fill_str: StdString = fill_str.chars().take(fill_len as usize).collect();
+        let fill_str = filler
+            .iter()
+            .copied()
+            .cycle()
+            .take(fill_len as usize)
+            .collect::<Vec<_>>();
 
         if at_start {
-            JsValue::new(format!("{}{}", concat_fill_str, &primitive))
+            JsValue::new(JsString::concat(fill_str, primitive))
         } else {
-            JsValue::new(format!("{}{}", primitive, &concat_fill_str))
+            JsValue::new(JsString::concat(primitive, fill_str))
         }
     }
 
@@ -1256,7 +1359,7 @@ impl String {
     pub(crate) fn trim(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
         let this = this.require_object_coercible(context)?;
         let string = this.to_string(context)?;
-        Ok(JsValue::new(string.trim_matches(is_trimmable_whitespace)))
+        Ok(JsValue::new(JsString::from(string.trim())))
     }
 
     /// `String.prototype.trimStart()`
@@ -1277,9 +1380,7 @@ impl String {
         context: &mut Context,
     ) -> JsResult<JsValue> {
         let string = this.to_string(context)?;
-        Ok(JsValue::new(
-            string.trim_start_matches(is_trimmable_whitespace),
-        ))
+        Ok(JsValue::new(JsString::from(trim_start(&string))))
     }
 
     /// String.prototype.trimEnd()
@@ -1301,9 +1402,7 @@ impl String {
     ) -> JsResult<JsValue> {
         let this = this.require_object_coercible(context)?;
         let string = this.to_string(context)?;
-        Ok(JsValue::new(
-            string.trim_end_matches(is_trimmable_whitespace),
-        ))
+        Ok(JsValue::new(JsString::from(trim_end(&string))))
     }
 
     /// `String.prototype.toLowerCase()`
@@ -1327,7 +1426,10 @@ impl String {
         let this_str = this.to_string(context)?;
         // The Rust String is mapped to uppercase using the builtin .to_lowercase().
         // There might be corner cases where it does not behave exactly like Javascript expects
-        Ok(JsValue::new(this_str.to_lowercase()))
+        Ok(JsValue::new(map_unicode_segments(
+            &this_str,
+            str::to_lowercase,
+        )))
     }
 
     /// `String.prototype.toUpperCase()`
@@ -1353,7 +1455,148 @@ impl String {
         let this_str = this.to_string(context)?;
         // The Rust String is mapped to uppercase using the builtin .to_uppercase().
         // There might be corner cases where it does not behave exactly like Javascript expects
-        Ok(JsValue::new(this_str.to_uppercase()))
+        Ok(JsValue::new(map_unicode_segments(
+            &this_str,
+            str::to_uppercase,
+        )))
+    }
+
+    /// `String.prototype.toLocaleLowerCase( [ locales ] )`
+    ///
+    /// The `toLocaleLowerCase()` method returns the calling string value converted to lower case, according to any
+    /// locale-specific case mappings.
+    ///
+    /// Without the ECMAScript Internationalization API, only the Turkish and Azeri mappings of the dotted and dotless
+    /// `i` differ from the ones of `toLowerCase()`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.tolocalelowercase
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/toLocaleLowerCase
+    #[allow(clippy::wrong_self_convention)]
+    pub(crate) fn to_locale_lowercase(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let this = this.require_object_coercible(context)?;
+        let string = this.to_string(context)?;
+
+        if Self::is_turkic_locale(args.get_or_undefined(0), context)? {
+            Ok(JsValue::new(map_unicode_segments(&string, |string| {
+                string
+                    .replace('\u{130}', "i")
+                    .replace("I\u{307}", "i")
+                    .replace('I', "\u{131}")
+                    .to_lowercase()
+            })))
+        } else {
+            Ok(JsValue::new(map_unicode_segments(
+                &string,
+                str::to_lowercase,
+            )))
+        }
+    }
+
+    /// `String.prototype.toLocaleUpperCase( [ locales ] )`
+    ///
+    /// The `toLocaleUpperCase()` method returns the calling string value converted to upper case, according to any
+    /// locale-specific case mappings.
+    ///
+    /// Without the ECMAScript Internationalization API, only the Turkish and Azeri mappings of the dotted and dotless
+    /// `i` differ from the ones of `toUpperCase()`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.tolocaleuppercase
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/toLocaleUpperCase
+    #[allow(clippy::wrong_self_convention)]
+    pub(crate) fn to_locale_uppercase(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let this = this.require_object_coercible(context)?;
+        let string = this.to_string(context)?;
+
+        if Self::is_turkic_locale(args.get_or_undefined(0), context)? {
+            Ok(JsValue::new(map_unicode_segments(&string, |string| {
+                string.replace('i', "\u{130}").to_uppercase()
+            })))
+        } else {
+            Ok(JsValue::new(map_unicode_segments(
+                &string,
+                str::to_uppercase,
+            )))
+        }
+    }
+
+    /// Returns `true` if the first of the requested `locales` is Turkish or Azeri, which have their own case mappings
+    /// for the letter `i`.
+    fn is_turkic_locale(locales: &JsValue, context: &mut Context) -> JsResult<bool> {
+        let locale = match locales {
+            JsValue::Undefined => return Ok(false),
+            JsValue::String(locale) => locale.clone(),
+            locales => {
+                let locales = locales.to_object(context)?;
+                if locales.length_of_array_like(context)? == 0 {
+                    return Ok(false);
+                }
+                locales.get(0, context)?.to_string(context)?
+            }
+        };
+
+        let locale = locale.to_std_string_lossy();
+        let language = locale.split(['-', '_']).next().unwrap_or("");
+        Ok(language.eq_ignore_ascii_case("tr") || language.eq_ignore_ascii_case("az"))
+    }
+
+    /// `String.prototype.localeCompare( that [ , locales [ , options ] ] )`
+    ///
+    /// The `localeCompare()` method returns a number indicating whether the string comes before, after, or is the
+    /// same as the given string in sort order.
+    ///
+    /// Without the ECMAScript Internationalization API, the comparison ignores the `locales` and `options`. Strings
+    /// are compared by code point after being normalized to the NFC form, so that canonically equivalent strings
+    /// compare as equal.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.localecompare
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/localeCompare
+    pub(crate) fn locale_compare(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let O be ? RequireObjectCoercible(this value).
+        let this = this.require_object_coercible(context)?;
+
+        // 2. Let S be ? ToString(O).
+        let s = this.to_string(context)?;
+
+        // 3. Let thatValue be ? ToString(that).
+        let that_value = args.get_or_undefined(0).to_string(context)?;
+
+        // Normalization replaces unpaired surrogates, so they are compared separately.
+        fn unpaired_surrogates(string: &JsString) -> impl Iterator<Item = u16> + '_ {
+            string.code_points().filter_map(|cp| match cp {
+                CodePoint::UnpairedSurrogate(surrogate) => Some(surrogate),
+                CodePoint::Unicode(_) => None,
+            })
+        }
+        let ordering = s
+            .to_std_string_lossy()
+            .nfc()
+            .cmp(that_value.to_std_string_lossy().nfc())
+            .then_with(|| unpaired_surrogates(&s).cmp(unpaired_surrogates(&that_value)));
+        Ok(JsValue::new(ordering as i32))
     }
 
     /// `String.prototype.substring( indexStart[, indexEnd] )`
@@ -1380,7 +1623,7 @@ impl String {
         } else {
             0
         };
-        let length = primitive_val.encode_utf16().count() as i32;
+        let length = primitive_val.len() as i32;
         // If less than 2 args specified, end is the length of the this object converted to a String
         let end = if let Some(integer) = args.get(1) {
             integer.to_integer(context)? as i32
@@ -1396,14 +1639,7 @@ impl String {
         let to = max(final_start, final_end) as usize;
         // Extract the part of the string contained between the start index and the end index
         // where start is guaranteed to be smaller or equals to end
-        let extracted_string: Result<StdString, _> = decode_utf16(
-            primitive_val
-                .encode_utf16()
-                .skip(from)
-                .take(to.wrapping_sub(from)),
-        )
-        .collect();
-        Ok(JsValue::new(extracted_string.expect("Invalid string")))
+        Ok(JsValue::new(JsString::from(&primitive_val[from..to])))
     }
 
     /// `String.prototype.substr( start[, length] )`
@@ -1431,7 +1667,7 @@ impl String {
         } else {
             0
         };
-        let length = primitive_val.chars().count() as i32;
+        let length = primitive_val.len() as i32;
         // If less than 2 args specified, end is +infinity, the maximum number value.
         // Using i32::max_value() should be safe because the final length used is at most
         // the number of code units from start to the end of the string,
@@ -1453,13 +1689,10 @@ impl String {
         if result_length <= 0 {
             Ok(JsValue::new(""))
         } else {
-            let extracted_string: StdString = primitive_val
-                .chars()
-                .skip(start as usize)
-                .take(result_length as usize)
-                .collect();
-
-            Ok(JsValue::new(extracted_string))
+            let start = start as usize;
+            Ok(JsValue::new(JsString::from(
+                &primitive_val[start..start + result_length as usize],
+            )))
         }
     }
 
@@ -1531,7 +1764,7 @@ impl String {
         }
 
         // 10. Let s be the length of S.
-        let this_str_length = this_str.encode_utf16().count();
+        let this_str_length = this_str.len();
 
         // 11. If s = 0, then
         if this_str_length == 0 {
@@ -1568,13 +1801,7 @@ impl String {
                         q += 1;
                     } else {
                         // 1. Let T be the substring of S from p to q.
-                        let this_str_substring = StdString::from_utf16_lossy(
-                            &this_str
-                                .encode_utf16()
-                                .skip(p)
-                                .take(q - p)
-                                .collect::<Vec<u16>>(),
-                        );
+                        let this_str_substring = JsString::from(&this_str[p..q]);
 
                         // 2. Perform ! CreateDataPropertyOrThrow(A, ! ToString(𝔽(lengthA)), T).
                         a.create_data_property_or_throw(length_a, this_str_substring, context)
@@ -1599,13 +1826,7 @@ impl String {
         }
 
         // 15. Let T be the substring of S from p to s.
-        let this_str_substring = StdString::from_utf16_lossy(
-            &this_str
-                .encode_utf16()
-                .skip(p)
-                .take(this_str_length - p)
-                .collect::<Vec<u16>>(),
-        );
+        let this_str_substring = JsString::from(&this_str[p..]);
 
         // 16. Perform ! CreateDataPropertyOrThrow(A, ! ToString(𝔽(lengthA)), T).
         a.create_data_property_or_throw(length_a, this_str_substring, context)
@@ -1667,7 +1888,7 @@ impl String {
                 flags.require_object_coercible(context)?;
 
                 // iii. If ? ToString(flags) does not contain "g", throw a TypeError exception.
-                if !flags.to_string(context)?.contains('g') {
+                if !flags.to_string(context)?.contains(&u16::from(b'g')) {
                     return context.throw_type_error(
                         "String.prototype.matchAll called with a non-global RegExp argument",
                     );
@@ -1710,25 +1931,92 @@ impl String {
         let s = this.to_string(context)?;
         let form = args.get_or_undefined(0);
 
-        let f_str;
-
         let f = if form.is_undefined() {
-            "NFC"
+            JsString::new("NFC")
         } else {
-            f_str = form.to_string(context)?;
-            f_str.as_str()
+            form.to_string(context)?
         };
 
-        match f {
-            "NFC" => Ok(JsValue::new(s.nfc().collect::<StdString>())),
-            "NFD" => Ok(JsValue::new(s.nfd().collect::<StdString>())),
-            "NFKC" => Ok(JsValue::new(s.nfkc().collect::<StdString>())),
-            "NFKD" => Ok(JsValue::new(s.nfkd().collect::<StdString>())),
+        match f.to_std_string_lossy().as_str() {
+            "NFC" => Ok(JsValue::new(map_unicode_segments(&s, |s| {
+                s.nfc().collect()
+            }))),
+            "NFD" => Ok(JsValue::new(map_unicode_segments(&s, |s| {
+                s.nfd().collect()
+            }))),
+            "NFKC" => Ok(JsValue::new(map_unicode_segments(&s, |s| {
+                s.nfkc().collect()
+            }))),
+            "NFKD" => Ok(JsValue::new(map_unicode_segments(&s, |s| {
+                s.nfkd().collect()
+            }))),
             _ => context
                 .throw_range_error("The normalization form should be one of NFC, NFD, NFKC, NFKD."),
         }
     }
 
+    /// `String.prototype.isWellFormed( )`
+    ///
+    /// The `isWellFormed()` method returns `true` if the string does not contain any lone surrogates.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.iswellformed
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/isWellFormed
+    pub(crate) fn is_well_formed(
+        this: &JsValue,
+        _: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let O be ? RequireObjectCoercible(this value).
+        let o = this.require_object_coercible(context)?;
+
+        // 2. Let S be ? ToString(O).
+        let s = o.to_string(context)?;
+
+        // 3. Return IsStringWellFormedUnicode(S).
+        Ok(JsValue::new(s.is_well_formed()))
+    }
+
+    /// `String.prototype.toWellFormed( )`
+    ///
+    /// The `toWellFormed()` method returns a string where all lone surrogates of this string are replaced with the
+    /// Unicode replacement character U+FFFD.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.towellformed
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/toWellFormed
+    #[allow(clippy::wrong_self_convention)]
+    pub(crate) fn to_well_formed(
+        this: &JsValue,
+        _: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let O be ? RequireObjectCoercible(this value).
+        let o = this.require_object_coercible(context)?;
+
+        // 2. Let S be ? ToString(O).
+        let s = o.to_string(context)?;
+
+        // 3. Let strLen be the length of S.
+        // 4. Let k be 0.
+        // 5. Let result be the empty String.
+        // 6. Repeat, while k < strLen,
+        //     a. Let cp be CodePointAt(S, k).
+        //     b. If cp.[[IsUnpairedSurrogate]] is true, then
+        //         i. Set result to the string-concatenation of result and 0xFFFD (REPLACEMENT CHARACTER).
+        //     c. Else,
+        //         i. Set result to the string-concatenation of result and UTF16EncodeCodePoint(cp.[[CodePoint]]).
+        //     d. Set k to k + cp.[[CodeUnitCount]].
+        // 7. Return result.
+        Ok(JsValue::new(s.to_std_string_lossy()))
+    }
+
     /// `String.prototype.search( regexp )`
     ///
     /// The search() method executes a search for a match between a regular expression and this String object.
@@ -1769,6 +2057,238 @@ impl String {
         rx.invoke(WellKnownSymbols::search(), &[JsValue::new(string)], context)
     }
 
+    /// `CreateHTML ( string, tag, attribute, value )`
+    ///
+    /// Wraps the string in an HTML element with the name `tag`, and an optional attribute whose value has its quotes
+    /// escaped. This is used by the legacy HTML methods of `String.prototype`, such as `anchor()` or `bold()`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-createhtml
+    fn create_html(
+        this: &JsValue,
+        tag: &str,
+        attribute: Option<(&str, &JsValue)>,
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let str be ? RequireObjectCoercible(string).
+        let string = this.require_object_coercible(context)?;
+
+        // 2. Let S be ? ToString(str).
+        let s = string.to_string(context)?;
+
+        // 3. Let p1 be the string-concatenation of "<" and tag.
+        let mut p1 = vec![0x003C /* < */];
+        p1.extend(tag.encode_utf16());
+
+        // 4. If attribute is not the empty String, then
+        if let Some((attribute, value)) = attribute {
+            // a. Let V be ? ToString(value).
+            let v = value.to_string(context)?;
+
+            // c. Set p1 to the string-concatenation of:
+            //    - p1
+            //    - the code unit 0x0020 (SPACE)
+            //    - attribute
+            //    - the code unit 0x003D (EQUALS SIGN)
+            //    - the code unit 0x0022 (QUOTATION MARK)
+            //    - escapedV
+            //    - the code unit 0x0022 (QUOTATION MARK)
+            p1.push(0x0020 /* SPACE */);
+            p1.extend(attribute.encode_utf16());
+            p1.extend_from_slice(utf16!("=\""));
+
+            // b. Let escapedV be the String value that is the same as V except that each occurrence of the code unit
+            //    0x0022 (QUOTATION MARK) in V has been replaced with the six code unit sequence "&quot;".
+            for &unit in v.iter() {
+                if unit == 0x0022
+                /* " */
+                {
+                    p1.extend_from_slice(utf16!("&quot;"));
+                } else {
+                    p1.push(unit);
+                }
+            }
+            p1.push(0x0022 /* " */);
+        }
+
+        // 5. Let p2 be the string-concatenation of p1 and ">".
+        // 6. Let p3 be the string-concatenation of p2 and S.
+        // 7. Let p4 be the string-concatenation of p3, "</", tag, and ">".
+        // 8. Return p4.
+        p1.push(0x003E /* > */);
+        p1.extend_from_slice(&s);
+        p1.extend_from_slice(utf16!("</"));
+        p1.extend(tag.encode_utf16());
+        p1.push(0x003E /* > */);
+        Ok(JsValue::new(JsString::from(p1)))
+    }
+
+    /// `String.prototype.anchor( name )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.anchor
+    pub(crate) fn anchor(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        Self::create_html(this, "a", Some(("name", args.get_or_undefined(0))), context)
+    }
+
+    /// `String.prototype.big( )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.big
+    pub(crate) fn big(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::create_html(this, "big", None, context)
+    }
+
+    /// `String.prototype.blink( )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.blink
+    pub(crate) fn blink(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::create_html(this, "blink", None, context)
+    }
+
+    /// `String.prototype.bold( )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.bold
+    pub(crate) fn bold(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::create_html(this, "b", None, context)
+    }
+
+    /// `String.prototype.fixed( )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.fixed
+    pub(crate) fn fixed(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::create_html(this, "tt", None, context)
+    }
+
+    /// `String.prototype.fontcolor( color )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.fontcolor
+    pub(crate) fn fontcolor(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        Self::create_html(
+            this,
+            "font",
+            Some(("color", args.get_or_undefined(0))),
+            context,
+        )
+    }
+
+    /// `String.prototype.fontsize( size )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.fontsize
+    pub(crate) fn fontsize(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        Self::create_html(
+            this,
+            "font",
+            Some(("size", args.get_or_undefined(0))),
+            context,
+        )
+    }
+
+    /// `String.prototype.italics( )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.italics
+    pub(crate) fn italics(
+        this: &JsValue,
+        _: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        Self::create_html(this, "i", None, context)
+    }
+
+    /// `String.prototype.link( url )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.link
+    pub(crate) fn link(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        Self::create_html(this, "a", Some(("href", args.get_or_undefined(0))), context)
+    }
+
+    /// `String.prototype.small( )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.small
+    pub(crate) fn small(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::create_html(this, "small", None, context)
+    }
+
+    /// `String.prototype.strike( )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.strike
+    pub(crate) fn strike(
+        this: &JsValue,
+        _: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        Self::create_html(this, "strike", None, context)
+    }
+
+    /// `String.prototype.sub( )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.sub
+    pub(crate) fn sub(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::create_html(this, "sub", None, context)
+    }
+
+    /// `String.prototype.sup( )`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.sup
+    pub(crate) fn sup(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::create_html(this, "sup", None, context)
+    }
+
     pub(crate) fn iterator(
         this: &JsValue,
         _: &[JsValue],
@@ -1785,23 +2305,30 @@ impl String {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-getsubstitution
 pub(crate) fn get_substitution(
-    matched: StdString,
-    str: StdString,
+    matched: &JsString,
+    str: &JsString,
     position: usize,
     captures: Vec<JsValue>,
     named_captures: JsValue,
-    replacement: JsString,
+    replacement: &JsString,
     context: &mut Context,
 ) -> JsResult<JsString> {
+    /// Gets the value of an ASCII decimal digit code unit.
+    fn to_digit(unit: u16) -> Option<usize> {
+        char::from_u32(u32::from(unit))?
+            .to_digit(10)
+            .map(|digit| digit as usize)
+    }
+
     // 1. Assert: Type(matched) is String.
 
     // 2. Let matchLength be the number of code units in matched.
-    let match_length = matched.encode_utf16().count();
+    let match_length = matched.len();
 
     // 3. Assert: Type(str) is String.
 
     // 4. Let stringLength be the number of code units in str.
-    let str_length = str.encode_utf16().count();
+    let str_length = str.len();
 
     // 5. Assert: position ≤ stringLength.
     // 6. Assert: captures is a possibly empty List of Strings.
@@ -1817,101 +2344,100 @@ pub(crate) fn get_substitution(
     //     from replacement to result while performing replacements as specified in Table 58.
     //     These $ replacements are done left-to-right, and, once such a replacement is performed,
     //     the new replacement text is not subject to further replacements.
-    let mut result = StdString::new();
-    let mut chars = replacement.chars().peekable();
-
-    while let Some(first) = chars.next() {
-        if first == '$' {
-            let second = chars.next();
-            let second_is_digit = second.map_or(false, |ch| ch.is_digit(10));
+    let mut result = Vec::with_capacity(replacement.len());
+    let mut units = replacement.iter().copied().peekable();
+
+    while let Some(first) = units.next() {
+        if first == 0x0024
+        /* $ */
+        {
+            let second = units.next();
+            let second_digit = second.and_then(to_digit);
             // we use peek so that it is still in the iterator if not used
-            let third = if second_is_digit { chars.peek() } else { None };
-            let third_is_digit = third.map_or(false, |ch| ch.is_digit(10));
+            let third = if second_digit.is_some() {
+                units.peek().copied()
+            } else {
+                None
+            };
+            let third_digit = third.and_then(to_digit);
 
             match (second, third) {
                 // $$
-                (Some('$'), _) => {
+                (Some(0x0024 /* $ */), _) => {
                     // $
-                    result.push('$');
+                    result.push(0x0024 /* $ */);
                 }
                 // $&
-                (Some('&'), _) => {
+                (Some(0x0026 /* & */), _) => {
                     // matched
-                    result.push_str(&matched);
+                    result.extend_from_slice(matched);
                 }
                 // $`
-                (Some('`'), _) => {
+                (Some(0x0060 /* ` */), _) => {
                     // The replacement is the substring of str from 0 to position.
-                    result.push_str(&StdString::from_utf16_lossy(
-                        &str.encode_utf16().take(position).collect::<Vec<u16>>(),
-                    ));
+                    result.extend_from_slice(&str[..position]);
                 }
                 // $'
-                (Some('\''), _) => {
+                (Some(0x0027 /* ' */), _) => {
                     // If tailPos ≥ stringLength, the replacement is the empty String.
                     // Otherwise the replacement is the substring of str from tailPos.
                     if tail_pos < str_length {
-                        result.push_str(&StdString::from_utf16_lossy(
-                            &str.encode_utf16().skip(tail_pos).collect::<Vec<u16>>(),
-                        ));
+                        result.extend_from_slice(&str[tail_pos..]);
                     }
                 }
                 // $nn
-                (Some(second), Some(third)) if second_is_digit && third_is_digit => {
+                (Some(second), Some(third)) if second_digit.is_some() && third_digit.is_some() => {
                     // The nnth element of captures, where nn is a two-digit decimal number in the range 01 to 99.
-                    let tens = second.to_digit(10).unwrap() as usize;
-                    let units = third.to_digit(10).unwrap() as usize;
-                    let nn = 10 * tens + units;
+                    let tens = second_digit.expect("checked by the match guard");
+                    let units_digit = third_digit.expect("checked by the match guard");
+                    let nn = 10 * tens + units_digit;
 
                     // If nn ≤ m and the nnth element of captures is undefined, use the empty String instead.
                     // If nn is 00 or nn > m, no replacement is done.
                     if nn == 0 || nn > m {
-                        result.push('$');
-                        result.push(second);
-                        result.push(*third);
+                        result.extend_from_slice(&[0x0024 /* $ */, second, third]);
                     } else if let Some(capture) = captures.get(nn - 1) {
                         if let Some(s) = capture.as_string() {
-                            result.push_str(s);
+                            result.extend_from_slice(s);
                         }
                     }
 
-                    chars.next();
+                    units.next();
                 }
                 // $n
-                (Some(second), _) if second_is_digit => {
+                (Some(second), _) if second_digit.is_some() => {
                     // The nth element of captures, where n is a single digit in the range 1 to 9.
-                    let n = second.to_digit(10).unwrap() as usize;
+                    let n = second_digit.expect("checked by the match guard");
 
                     // If n ≤ m and the nth element of captures is undefined, use the empty String instead.
                     // If n > m, no replacement is done.
                     if n == 0 || n > m {
-                        result.push('$');
-                        result.push(second);
+                        result.extend_from_slice(&[0x0024 /* $ */, second]);
                     } else if let Some(capture) = captures.get(n - 1) {
                         if let Some(s) = capture.as_string() {
-                            result.push_str(s);
+                            result.extend_from_slice(s);
                         }
                     }
                 }
                 // $<
-                (Some('<'), _) => {
+                (Some(0x003C /* < */), _) => {
                     // 1. If namedCaptures is undefined, the replacement text is the String "$<".
                     // 2. Else,
                     if named_captures.is_undefined() {
-                        result.push_str("$<")
+                        result.extend_from_slice(utf16!("$<"));
                     } else {
                         // a. Assert: Type(namedCaptures) is Object.
 
                         // b. Scan until the next > U+003E (GREATER-THAN SIGN).
-                        let mut group_name = StdString::new();
+                        let mut group_name = Vec::new();
                         let mut found = false;
                         loop {
-                            match chars.next() {
-                                Some('>') => {
+                            match units.next() {
+                                Some(0x003E /* > */) => {
                                     found = true;
                                     break;
                                 }
-                                Some(c) => group_name.push(c),
+                                Some(unit) => group_name.push(unit),
                                 None => break,
                             }
                         }
@@ -1919,24 +2445,25 @@ pub(crate) fn get_substitution(
                         // c. If none is found, the replacement text is the String "$<".
                         // d. Else,
                         if !found {
-                            result.push_str("$<");
-                            result.push_str(&group_name);
+                            result.extend_from_slice(utf16!("$<"));
+                            result.extend_from_slice(&group_name);
                         } else {
                             // i. Let groupName be the enclosed substring.
                             // ii. Let capture be ? Get(namedCaptures, groupName).
-                            let capture = named_captures.get_field(group_name, context)?;
+                            let capture =
+                                named_captures.get_field(JsString::from(group_name), context)?;
 
                             // iii. If capture is undefined, replace the text through > with the empty String.
                             // iv. Otherwise, replace the text through > with ? ToString(capture).
                             if !capture.is_undefined() {
-                                result.push_str(capture.to_string(context)?.as_str());
+                                result.extend_from_slice(&capture.to_string(context)?);
                             }
                         }
                     }
                 }
                 // $?, ? is none of the above
                 _ => {
-                    result.push('$');
+                    result.push(0x0024 /* $ */);
                     if let Some(second) = second {
                         result.push(second);
                     }
@@ -1951,37 +2478,45 @@ pub(crate) fn get_substitution(
     Ok(result.into())
 }
 
+/// Applies a mapping of Rust strings to every well formed segment of a JavaScript string, leaving unpaired
+/// surrogates untouched.
+fn map_unicode_segments<F>(string: &JsString, map: F) -> JsString
+where
+    F: Fn(&str) -> StdString,
+{
+    let mut result = Vec::with_capacity(string.len());
+    let mut segment = StdString::new();
+    for code_point in string.code_points() {
+        match code_point {
+            CodePoint::Unicode(c) => segment.push(c),
+            CodePoint::UnpairedSurrogate(surrogate) => {
+                result.extend(map(&segment).encode_utf16());
+                result.push(surrogate);
+                segment.clear();
+            }
+        }
+    }
+    result.extend(map(&segment).encode_utf16());
+    result.into()
+}
+
 /// `22.1.3.21.1 SplitMatch ( S, q, R )`
 ///
 /// More information:
 ///  - [ECMAScript reference][spec]
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-splitmatch
-fn split_match(s_str: &str, q: usize, r_str: &str) -> Option<usize> {
+fn split_match(s: &[u16], q: usize, r: &[u16]) -> Option<usize> {
     // 1. Let r be the number of code units in R.
-    let r = r_str.encode_utf16().count();
-
     // 2. Let s be the number of code units in S.
-    let s = s_str.encode_utf16().count();
-
     // 3. If q + r > s, return not-matched.
-    if q + r > s {
-        return None;
-    }
-
     // 4. If there exists an integer i between 0 (inclusive) and r (exclusive)
     //    such that the code unit at index q + i within S is different from the code unit at index i within R,
     //    return not-matched.
-    for i in 0..r {
-        if let Some(s_char) = s_str.encode_utf16().nth(q + i) {
-            if let Some(r_char) = r_str.encode_utf16().nth(i) {
-                if s_char != r_char {
-                    return None;
-                }
-            }
-        }
+    if s.get(q..q + r.len())? != r {
+        return None;
     }
 
     // 5. Return q + r.
-    Some(q + r)
+    Some(q + r.len())
 }
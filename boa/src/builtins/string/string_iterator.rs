@@ -46,7 +46,7 @@ impl StringIterator {
             ));
         }
         let native_string = string_iterator.string.to_string(context)?;
-        let len = native_string.encode_utf16().count() as i32;
+        let len = native_string.len() as i32;
         let position = string_iterator.next_index;
         if position >= len {
             string_iterator.string = JsValue::undefined();
@@ -57,7 +57,7 @@ impl StringIterator {
             ));
         }
         let (_, code_unit_count, _) =
-            code_point_at(native_string, position).expect("Invalid code point position");
+            code_point_at(&native_string, position as usize).expect("Invalid code point position");
         string_iterator.next_index += code_unit_count as i32;
         let result_string = crate::builtins::string::String::substring(
             &string_iterator.string,
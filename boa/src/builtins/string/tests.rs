@@ -1144,3 +1144,232 @@ fn search() {
     assert_eq!(forward(&mut context, "'aa'.search(/a/g)"), "0");
     assert_eq!(forward(&mut context, "'ba'.search(/a/)"), "1");
 }
+
+#[test]
+fn from_code_point() {
+    let mut context = Context::new();
+
+    assert_eq!(forward(&mut context, "String.fromCodePoint()"), "\"\"");
+    assert_eq!(
+        forward(&mut context, "String.fromCodePoint(65, 0x1F600, 97)"),
+        "\"A😀a\""
+    );
+    assert_eq!(
+        forward(&mut context, "String.fromCodePoint(0x1F600).length"),
+        "2"
+    );
+    assert_eq!(forward(&mut context, "String.fromCodePoint.length"), "1");
+
+    for value in ["-1", "0x110000", "1.5", "NaN", "Infinity", "'a'"] {
+        let source = format!(
+            "try {{ String.fromCodePoint({}) }} catch (e) {{ e.name }}",
+            value
+        );
+        assert_eq!(
+            forward(&mut context, &source),
+            "\"RangeError\"",
+            "{}",
+            value
+        );
+    }
+}
+
+#[test]
+fn raw() {
+    let mut context = Context::new();
+
+    assert_eq!(
+        forward(&mut context, r#"String.raw`a\nb${1 + 1}c\t`"#),
+        r#""a\nb2c\t""#
+    );
+    assert_eq!(forward(&mut context, "String.raw`${1}${2}`"), "\"12\"");
+    assert_eq!(forward(&mut context, "String.raw``"), "\"\"");
+    assert_eq!(
+        forward(
+            &mut context,
+            "String.raw({ raw: ['x', 'y', 'z'] }, 1, 2, 3, 4)"
+        ),
+        "\"x1y2z\""
+    );
+    assert_eq!(
+        forward(&mut context, "String.raw({ raw: 'abc' }, '-', '+')"),
+        "\"a-b+c\""
+    );
+    assert_eq!(
+        forward(&mut context, "String.raw({ raw: { length: 0 } }, 1)"),
+        "\"\""
+    );
+    assert_eq!(
+        forward(&mut context, "try { String.raw({}) } catch (e) { e.name }"),
+        "\"TypeError\""
+    );
+}
+
+#[test]
+fn locale_compare() {
+    let mut context = Context::new();
+
+    assert_eq!(forward(&mut context, "'a'.localeCompare('b')"), "-1");
+    assert_eq!(forward(&mut context, "'b'.localeCompare('a')"), "1");
+    assert_eq!(forward(&mut context, "'a'.localeCompare('a')"), "0");
+    assert_eq!(forward(&mut context, "''.localeCompare('a')"), "-1");
+    assert_eq!(
+        forward(&mut context, "'\\u0065\\u0301'.localeCompare('\\u00e9')"),
+        "0"
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "['c', 'a', 'b'].sort((a, b) => a.localeCompare(b)).join()"
+        ),
+        "\"a,b,c\""
+    );
+}
+
+#[test]
+fn to_locale_case() {
+    let mut context = Context::new();
+
+    assert_eq!(
+        forward(&mut context, "'Hello İ'.toLocaleLowerCase()"),
+        "\"hello i̇\""
+    );
+    assert_eq!(
+        forward(&mut context, "'hello'.toLocaleUpperCase('en-US')"),
+        "\"HELLO\""
+    );
+    assert_eq!(
+        forward(&mut context, "'istanbul'.toLocaleUpperCase('tr')"),
+        "\"İSTANBUL\""
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "'DİYARBAKIR'.toLocaleLowerCase(['tr-TR', 'en'])"
+        ),
+        "\"diyarbakır\""
+    );
+    assert_eq!(
+        forward(&mut context, "'I'.toLocaleLowerCase('az')"),
+        "\"ı\""
+    );
+    assert_eq!(forward(&mut context, "'I'.toLocaleLowerCase([])"), "\"i\"");
+}
+
+#[test]
+fn well_formed() {
+    let mut context = Context::new();
+
+    assert_eq!(forward(&mut context, "'abc😀'.isWellFormed()"), "true");
+    assert_eq!(forward(&mut context, "''.isWellFormed()"), "true");
+    assert_eq!(forward(&mut context, "'abc😀'.toWellFormed()"), "\"abc😀\"");
+    assert_eq!(
+        forward(
+            &mut context,
+            "try { String.prototype.isWellFormed.call(null) } catch (e) { e.name }"
+        ),
+        "\"TypeError\""
+    );
+}
+
+#[test]
+fn trim_aliases() {
+    let mut context = Context::new();
+
+    assert_eq!(
+        forward(
+            &mut context,
+            "String.prototype.trimLeft === String.prototype.trimStart"
+        ),
+        "true"
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "String.prototype.trimRight === String.prototype.trimEnd"
+        ),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut context, "String.prototype.trimLeft.name"),
+        "\"trimStart\""
+    );
+    assert_eq!(forward(&mut context, "' a '.trimRight()"), "\" a\"");
+}
+
+#[test]
+fn html_methods() {
+    let mut context = Context::new();
+
+    assert_eq!(
+        forward(&mut context, "'x'.anchor('a\"b')"),
+        r#""<a name="a&quot;b">x</a>""#
+    );
+    assert_eq!(forward(&mut context, "'x'.bold()"), r#""<b>x</b>""#);
+    assert_eq!(forward(&mut context, "'x'.fixed()"), r#""<tt>x</tt>""#);
+    assert_eq!(
+        forward(&mut context, "'x'.fontsize(7)"),
+        r#""<font size="7">x</font>""#
+    );
+    assert_eq!(
+        forward(&mut context, "'x'.link('https://boa-dev.github.io')"),
+        r#""<a href="https://boa-dev.github.io">x</a>""#
+    );
+    assert_eq!(forward(&mut context, "'x'.sup()"), r#""<sup>x</sup>""#);
+}
+
+#[test]
+fn unpaired_surrogates() {
+    let mut context = Context::new();
+
+    assert_eq!(forward(&mut context, "'\\uD800'.length"), "1");
+    assert_eq!(forward(&mut context, "'\\uD800'.charCodeAt(0)"), "55296");
+    assert_eq!(forward(&mut context, "'a\\uDFFF'.codePointAt(1)"), "57343");
+    assert_eq!(
+        forward(&mut context, "'\\uD83D' + '\\uDE00' === '\\u{1F600}'"),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut context, "'\\uD83D\\uDE00'.slice(1).charCodeAt(0)"),
+        "56832"
+    );
+    assert_eq!(
+        forward(&mut context, "'a\\uD800b'.split('')[1].charCodeAt(0)"),
+        "55296"
+    );
+    assert_eq!(
+        forward(&mut context, "'x\\uD800'.toUpperCase().charCodeAt(1)"),
+        "55296"
+    );
+    assert_eq!(
+        forward(&mut context, "'\\uD800'.toWellFormed().charCodeAt(0)"),
+        "65533"
+    );
+}
+
+#[test]
+fn string_building() {
+    let mut context = Context::new();
+
+    let init = r#"
+        var html = "";
+        for (var i = 0; i < 2000; i++) {
+            html += "<td>" + i + "</td>";
+        }
+        "#;
+    forward(&mut context, init);
+
+    assert_eq!(forward(&mut context, "html.length"), "24890");
+    assert_eq!(
+        forward(&mut context, "html.slice(-13)"),
+        "\"<td>1999</td>\""
+    );
+    assert_eq!(
+        forward(&mut context, "html.indexOf('<td>1000</td>')"),
+        "11890"
+    );
+    assert_eq!(
+        forward(&mut context, "'a'.concat(html, 'b').length"),
+        "24892"
+    );
+}
@@ -0,0 +1,879 @@
+//! This module implements the global `structuredClone` function and the structured serialization of values.
+//!
+//! The structured clone algorithm creates a deep copy of a value, preserving cycles and shared references between
+//! objects, as well as the types of `Map`, `Set`, `Date`, `RegExp`, `ArrayBuffer`, typed array and error objects.
+//!
+//! Besides the `structuredClone` function available to scripts, the algorithm is exposed to Rust through
+//! [`serialize`] and [`deserialize`], which convert values to and from a compact binary format. The serialized bytes
+//! don't depend on the `Context` that produced them, so they can be used to pass values between isolated contexts, or
+//! to persist them.
+//!
+//! More information:
+//!  - [HTML reference][spec]
+//!  - [MDN documentation][mdn]
+//!
+//! [spec]: https://html.spec.whatwg.org/multipage/structured-data.html#safe-passing-of-structured-data
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/structuredClone
+
+use crate::{
+    bigint::RawBigInt,
+    builtins::{
+        array_buffer::ArrayBuffer, map::ordered_map::OrderedMap, set::ordered_set::OrderedSet,
+        typed_array::TypedArrayName, Array, BuiltIn, Date, JsArgs, RegExp,
+    },
+    context::{StandardConstructor, StandardObjects},
+    object::{FunctionBuilder, JsObject, ObjectData, ObjectKind},
+    property::{Attribute, PropertyDescriptor, PropertyNameKind},
+    string::CodePoint,
+    BoaProfiler, Context, JsBigInt, JsResult, JsString, JsValue,
+};
+use std::{collections::HashMap, convert::TryFrom};
+
+#[cfg(test)]
+mod tests;
+
+/// The version of the serialization format, written as the first byte of every serialized value.
+const FORMAT_VERSION: u8 = 1;
+
+/// The maximum number of objects nested in one another in a cloned value.
+///
+/// Both the serialization and the deserialization recurse into nested objects, so deeper values are rejected
+/// instead of overflowing the stack.
+const MAX_DEPTH: usize = 512;
+
+/// Tags identifying the type of each value in the serialization format.
+mod tag {
+    pub(super) const UNDEFINED: u8 = 0;
+    pub(super) const NULL: u8 = 1;
+    pub(super) const FALSE: u8 = 2;
+    pub(super) const TRUE: u8 = 3;
+    pub(super) const INT32: u8 = 4;
+    pub(super) const NUMBER: u8 = 5;
+    pub(super) const BIGINT: u8 = 6;
+    pub(super) const STRING: u8 = 7;
+    pub(super) const OBJECT_REFERENCE: u8 = 8;
+    pub(super) const BOOLEAN_OBJECT: u8 = 9;
+    pub(super) const NUMBER_OBJECT: u8 = 10;
+    pub(super) const BIGINT_OBJECT: u8 = 11;
+    pub(super) const STRING_OBJECT: u8 = 12;
+    pub(super) const DATE: u8 = 13;
+    pub(super) const REGEXP: u8 = 14;
+    pub(super) const ARRAY_BUFFER: u8 = 15;
+    pub(super) const TYPED_ARRAY: u8 = 16;
+    pub(super) const MAP: u8 = 17;
+    pub(super) const SET: u8 = 18;
+    pub(super) const ERROR: u8 = 19;
+    pub(super) const ARRAY: u8 = 20;
+    pub(super) const OBJECT: u8 = 21;
+    /// Marks the end of the properties of an array or an object.
+    pub(super) const END: u8 = 22;
+}
+
+/// The names of the errors that keep their type when cloned, in the order of their serialized representation.
+const ERROR_NAMES: [&str; 7] = [
+    "Error",
+    "EvalError",
+    "RangeError",
+    "ReferenceError",
+    "SyntaxError",
+    "TypeError",
+    "URIError",
+];
+
+/// The typed arrays, in the order of their serialized representation.
+const TYPED_ARRAY_NAMES: [TypedArrayName; 11] = [
+    TypedArrayName::Int8Array,
+    TypedArrayName::Uint8Array,
+    TypedArrayName::Uint8ClampedArray,
+    TypedArrayName::Int16Array,
+    TypedArrayName::Uint16Array,
+    TypedArrayName::Int32Array,
+    TypedArrayName::Uint32Array,
+    TypedArrayName::BigInt64Array,
+    TypedArrayName::BigUint64Array,
+    TypedArrayName::Float32Array,
+    TypedArrayName::Float64Array,
+];
+
+/// JavaScript `structuredClone()` global function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct StructuredClone;
+
+impl BuiltIn for StructuredClone {
+    const NAME: &'static str = "structuredClone";
+
+    const ATTRIBUTE: Attribute = Attribute::WRITABLE
+        .union(Attribute::NON_ENUMERABLE)
+        .union(Attribute::CONFIGURABLE);
+
+    fn init(context: &mut Context) -> JsValue {
+        let _timer = BoaProfiler::global().start_event(Self::NAME, "init");
+
+        FunctionBuilder::native(context, Self::structured_clone)
+            .name(Self::NAME)
+            .length(1)
+            .constructor(false)
+            .build()
+            .into()
+    }
+}
+
+impl StructuredClone {
+    /// `structuredClone( value )`
+    ///
+    /// Creates a deep clone of a value using the structured clone algorithm.
+    ///
+    /// More information:
+    ///  - [HTML reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/structured-data.html#dom-structuredclone
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/structuredClone
+    pub(crate) fn structured_clone(
+        _: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let serialized be ? StructuredSerializeWithTransfer(value, options["transfer"]).
+        let serialized = serialize(args.get_or_undefined(0), context)?;
+
+        // 2. Let deserializeRecord be ? StructuredDeserializeWithTransfer(serialized, this's relevant realm).
+        // 3. Return deserializeRecord.[[Deserialized]].
+        deserialize(&serialized, context)
+    }
+}
+
+/// Serializes a value into bytes using the structured serialization algorithm.
+///
+/// The bytes can be turned back into a value with [`deserialize`], in this or any other `Context`.
+///
+/// # Errors
+///
+/// Returns a `TypeError` if the value is, or contains, a value that cannot be cloned, such as a symbol, a function or
+/// a detached `ArrayBuffer`, or if it has more than 512 nested objects. Errors thrown by getters that run while reading the properties of objects are
+/// propagated.
+///
+/// # Examples
+///
+/// ```
+/// use boa::{
+///     builtins::structured_clone::{deserialize, serialize},
+///     Context,
+/// };
+///
+/// let mut sender = Context::new();
+/// let value = sender.eval("new Map([[1, new Date(0)]])").unwrap();
+/// let bytes = serialize(&value, &mut sender).unwrap();
+///
+/// let mut receiver = Context::new();
+/// let value = deserialize(&bytes, &mut receiver).unwrap();
+/// receiver.register_global_property("message", value, Default::default());
+/// let time = receiver.eval("message.get(1).getTime()").unwrap();
+/// assert_eq!(time.as_number(), Some(0.0));
+/// ```
+///
+/// More information:
+///  - [HTML reference][spec]
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/structured-data.html#structuredserialize
+pub fn serialize(value: &JsValue, context: &mut Context) -> JsResult<Vec<u8>> {
+    let mut serializer = Serializer {
+        output: vec![FORMAT_VERSION],
+        memory: HashMap::new(),
+        objects: Vec::new(),
+        depth: 0,
+    };
+    serializer.serialize_value(value, context)?;
+    Ok(serializer.output)
+}
+
+/// Deserializes a value from bytes created by [`serialize`].
+///
+/// # Errors
+///
+/// Returns a `TypeError` if the bytes are not a valid serialized value, or if they hold more than 512 nested objects.
+///
+/// More information:
+///  - [HTML reference][spec]
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/structured-data.html#structureddeserialize
+pub fn deserialize(bytes: &[u8], context: &mut Context) -> JsResult<JsValue> {
+    let mut deserializer = Deserializer {
+        input: bytes,
+        memory: Vec::new(),
+        depth: 0,
+    };
+    if deserializer.read_u8() != Some(FORMAT_VERSION) {
+        return Err(context.construct_type_error("unsupported structured clone data version"));
+    }
+    let value = deserializer.deserialize_value(context)?;
+    if !deserializer.input.is_empty() {
+        return Err(invalid_data(context));
+    }
+    Ok(value)
+}
+
+/// Creates the error returned when deserializing malformed data.
+fn invalid_data(context: &mut Context) -> JsValue {
+    context.construct_type_error("invalid structured clone data")
+}
+
+/// Creates the error returned when a value has more than [`MAX_DEPTH`] nested objects.
+fn too_deep(context: &mut Context) -> JsValue {
+    context.construct_type_error(format!(
+        "values with more than {} nested objects cannot be cloned",
+        MAX_DEPTH
+    ))
+}
+
+/// The state of an ongoing serialization.
+#[derive(Debug)]
+struct Serializer {
+    /// The serialized bytes.
+    output: Vec<u8>,
+
+    /// Maps the objects that were already serialized, by address, to their index in `objects`.
+    memory: HashMap<usize, usize>,
+
+    /// The objects that were already serialized, in the order they were serialized in.
+    ///
+    /// Keeping them alive guarantees that the addresses in `memory` are not reused during the serialization.
+    objects: Vec<JsObject>,
+
+    /// The number of objects being serialized, each one nested in the previous one.
+    depth: usize,
+}
+
+/// The contents of an object that remain to be serialized once it is no longer borrowed.
+enum Contents {
+    /// The object was completely serialized.
+    Done,
+    Map(Vec<(JsValue, JsValue)>),
+    Set(Vec<JsValue>),
+    Error,
+    Array,
+    Ordinary,
+}
+
+impl Serializer {
+    /// `StructuredSerializeInternal ( value, forStorage [ , memory ] )`
+    ///
+    /// More information:
+    ///  - [HTML reference][spec]
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/structured-data.html#structuredserializeinternal
+    fn serialize_value(&mut self, value: &JsValue, context: &mut Context) -> JsResult<()> {
+        match value {
+            JsValue::Undefined => self.write_u8(tag::UNDEFINED),
+            JsValue::Null => self.write_u8(tag::NULL),
+            JsValue::Boolean(false) => self.write_u8(tag::FALSE),
+            JsValue::Boolean(true) => self.write_u8(tag::TRUE),
+            JsValue::Integer(integer) => {
+                self.write_u8(tag::INT32);
+                self.write_varint(u64::from(((integer << 1) ^ (integer >> 31)) as u32));
+            }
+            JsValue::Rational(number) => {
+                self.write_u8(tag::NUMBER);
+                self.write_f64(*number);
+            }
+            JsValue::BigInt(bigint) => {
+                self.write_u8(tag::BIGINT);
+                self.write_bigint(bigint);
+            }
+            JsValue::String(string) => {
+                self.write_u8(tag::STRING);
+                self.write_string(string);
+            }
+            JsValue::Symbol(_) => {
+                return Err(context.construct_type_error("Symbol values cannot be cloned"))
+            }
+            JsValue::Object(object) => {
+                if self.depth == MAX_DEPTH {
+                    return Err(too_deep(context));
+                }
+                self.depth += 1;
+                let result = self.serialize_object(object, context);
+                self.depth -= 1;
+                result?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Serializes an object, or a reference to it if it was already serialized.
+    fn serialize_object(&mut self, object: &JsObject, context: &mut Context) -> JsResult<()> {
+        let address = object.as_ref() as *const _ as usize;
+        if let Some(&index) = self.memory.get(&address) {
+            self.write_u8(tag::OBJECT_REFERENCE);
+            self.write_varint(index as u64);
+            return Ok(());
+        }
+
+        // The viewed buffer of a typed array is serialized before the typed array itself, so that the typed array can
+        // be created in one step when deserializing. This is fine since buffers can't refer back to typed arrays.
+        let typed_array = object.borrow().as_typed_array().cloned();
+        if let Some(typed_array) = typed_array {
+            if typed_array.is_detached() {
+                return Err(context.construct_type_error("detached typed arrays cannot be cloned"));
+            }
+            let name = typed_array.typed_array_name();
+            let index = TYPED_ARRAY_NAMES
+                .iter()
+                .position(|&n| n == name)
+                .expect("all typed arrays must have a serialized representation");
+            self.write_u8(tag::TYPED_ARRAY);
+            self.write_u8(index as u8);
+            let buffer = typed_array
+                .viewed_array_buffer()
+                .expect("a typed array that is not detached must have a buffer");
+            self.serialize_object(buffer, context)?;
+            self.write_varint(typed_array.byte_offset() as u64);
+            self.write_varint(typed_array.array_length() as u64);
+            self.remember(object);
+            return Ok(());
+        }
+
+        self.remember(object);
+
+        let contents = match object.borrow().kind() {
+            ObjectKind::Boolean(boolean) => {
+                self.write_u8(tag::BOOLEAN_OBJECT);
+                self.write_u8(u8::from(*boolean));
+                Contents::Done
+            }
+            ObjectKind::Number(number) => {
+                self.write_u8(tag::NUMBER_OBJECT);
+                self.write_f64(*number);
+                Contents::Done
+            }
+            ObjectKind::BigInt(bigint) => {
+                self.write_u8(tag::BIGINT_OBJECT);
+                self.write_bigint(bigint);
+                Contents::Done
+            }
+            ObjectKind::String(string) => {
+                self.write_u8(tag::STRING_OBJECT);
+                self.write_string(string);
+                Contents::Done
+            }
+            ObjectKind::Date(date) => {
+                self.write_u8(tag::DATE);
+                self.write_f64(date.get_time());
+                Contents::Done
+            }
+            ObjectKind::RegExp(regexp) => {
+                self.write_u8(tag::REGEXP);
+                self.write_string(regexp.original_source());
+                self.write_string(regexp.original_flags());
+                Contents::Done
+            }
+            ObjectKind::ArrayBuffer(buffer) => {
+                let data = match buffer.array_buffer_data {
+                    Some(ref data) => data,
+                    None => {
+                        return Err(
+                            context.construct_type_error("detached ArrayBuffers cannot be cloned")
+                        )
+                    }
+                };
+                self.write_u8(tag::ARRAY_BUFFER);
+                self.write_varint(data.len() as u64);
+                self.output.extend_from_slice(data);
+                Contents::Done
+            }
+            ObjectKind::Map(map) => Contents::Map(
+                map.iter()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect(),
+            ),
+            ObjectKind::Set(set) => Contents::Set(set.iter().cloned().collect()),
+            ObjectKind::Error => Contents::Error,
+            ObjectKind::Array => Contents::Array,
+            ObjectKind::Ordinary => Contents::Ordinary,
+            kind => {
+                return Err(
+                    context.construct_type_error(format!("{} objects cannot be cloned", kind))
+                )
+            }
+        };
+
+        match contents {
+            Contents::Done => {}
+            Contents::Map(entries) => {
+                self.write_u8(tag::MAP);
+                self.write_varint(entries.len() as u64);
+                for (key, value) in entries {
+                    self.serialize_value(&key, context)?;
+                    self.serialize_value(&value, context)?;
+                }
+            }
+            Contents::Set(values) => {
+                self.write_u8(tag::SET);
+                self.write_varint(values.len() as u64);
+                for value in values {
+                    self.serialize_value(&value, context)?;
+                }
+            }
+            Contents::Error => {
+                // a. Let name be ? Get(value, "name").
+                let name = object.get("name", context)?;
+
+                // b. If name is not one of "Error", "EvalError", "RangeError", "ReferenceError", "SyntaxError",
+                //    "TypeError", or "URIError", then set name to "Error".
+                let name = name
+                    .as_string()
+                    .and_then(|name| ERROR_NAMES.iter().position(|&n| *name == n))
+                    .unwrap_or(0);
+
+                // c. Let valueMessageDesc be ? value.[[GetOwnProperty]]("message").
+                // d. Let message be undefined if IsDataDescriptor(valueMessageDesc) is false, and
+                //    ? ToString(valueMessageDesc.[[Value]]) otherwise.
+                let message = match object.__get_own_property__(&"message".into(), context)? {
+                    Some(desc) if desc.is_data_descriptor() => {
+                        Some(desc.expect_value().to_string(context)?)
+                    }
+                    _ => None,
+                };
+
+                self.write_u8(tag::ERROR);
+                self.write_u8(name as u8);
+                match message {
+                    Some(message) => {
+                        self.write_u8(1);
+                        self.write_string(&message);
+                    }
+                    None => self.write_u8(0),
+                }
+            }
+            Contents::Array => {
+                let length = object.length_of_array_like(context)?;
+                self.write_u8(tag::ARRAY);
+                self.write_varint(length as u64);
+                self.serialize_properties(object, context)?;
+            }
+            Contents::Ordinary => {
+                self.write_u8(tag::OBJECT);
+                self.serialize_properties(object, context)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Serializes the enumerable own properties of an array or an object.
+    fn serialize_properties(&mut self, object: &JsObject, context: &mut Context) -> JsResult<()> {
+        // 1. Let copiedProperties be a new empty List.
+        // 2. Let enumerableKeys be ? EnumerableOwnPropertyNames(value, key).
+        let keys = object.enumerable_own_property_names(PropertyNameKind::Key, context)?;
+
+        // 3. For each key in enumerableKeys:
+        for key in keys {
+            let key = key
+                .as_string()
+                .expect("enumerable own property names must be strings")
+                .clone();
+
+            // a. If ! HasOwnProperty(value, key) is true, then
+            if object.has_own_property(key.clone(), context)? {
+                // i. Let inputValue be ? value.[[Get]](key, value).
+                let value = object.get(key.clone(), context)?;
+
+                // ii. Let outputValue be ? StructuredSerializeInternal(inputValue, forStorage, memory).
+                // iii. Append { [[Key]]: key, [[Value]]: outputValue } to copiedProperties.
+                self.write_u8(tag::STRING);
+                self.write_string(&key);
+                self.serialize_value(&value, context)?;
+            }
+        }
+
+        self.write_u8(tag::END);
+        Ok(())
+    }
+
+    /// Records that `object` was serialized, so that later occurrences are serialized as references.
+    fn remember(&mut self, object: &JsObject) {
+        let address = object.as_ref() as *const _ as usize;
+        self.memory.insert(address, self.objects.len());
+        self.objects.push(object.clone());
+    }
+
+    fn write_u8(&mut self, byte: u8) {
+        self.output.push(byte);
+    }
+
+    /// Writes an unsigned integer in the LEB128 variable length encoding.
+    fn write_varint(&mut self, mut value: u64) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                self.output.push(byte);
+                return;
+            }
+            self.output.push(byte | 0x80);
+        }
+    }
+
+    fn write_f64(&mut self, number: f64) {
+        self.output.extend_from_slice(&number.to_le_bytes());
+    }
+
+    fn write_bigint(&mut self, bigint: &JsBigInt) {
+        let bytes = bigint.as_inner().to_signed_bytes_le();
+        self.write_varint(bytes.len() as u64);
+        self.output.extend_from_slice(&bytes);
+    }
+
+    /// Writes a string in the WTF-8 encoding, which is UTF-8 extended to represent unpaired surrogates.
+    fn write_string(&mut self, string: &JsString) {
+        let mut bytes = Vec::with_capacity(string.len());
+        for code_point in string.code_points() {
+            match code_point {
+                CodePoint::Unicode(c) => {
+                    bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                }
+                CodePoint::UnpairedSurrogate(surrogate) => bytes.extend_from_slice(&[
+                    0xe0 | (surrogate >> 12) as u8,
+                    0x80 | (surrogate >> 6 & 0x3f) as u8,
+                    0x80 | (surrogate & 0x3f) as u8,
+                ]),
+            }
+        }
+        self.write_varint(bytes.len() as u64);
+        self.output.extend_from_slice(&bytes);
+    }
+}
+
+/// The state of an ongoing deserialization.
+#[derive(Debug)]
+struct Deserializer<'a> {
+    /// The bytes that remain to be read.
+    input: &'a [u8],
+
+    /// The objects that were already deserialized, in the order they were serialized in.
+    memory: Vec<JsObject>,
+
+    /// The number of objects being deserialized, each one nested in the previous one.
+    depth: usize,
+}
+
+impl Deserializer<'_> {
+    /// `StructuredDeserialize ( serialized, targetRealm [ , memory ] )`
+    ///
+    /// More information:
+    ///  - [HTML reference][spec]
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/structured-data.html#structureddeserialize
+    fn deserialize_value(&mut self, context: &mut Context) -> JsResult<JsValue> {
+        let tag = self.read_u8().ok_or_else(|| invalid_data(context))?;
+        let value = match tag {
+            tag::UNDEFINED => JsValue::undefined(),
+            tag::NULL => JsValue::null(),
+            tag::FALSE => JsValue::new(false),
+            tag::TRUE => JsValue::new(true),
+            tag::INT32 => {
+                let value = self
+                    .read_varint()
+                    .filter(|&value| value <= u64::from(u32::MAX))
+                    .ok_or_else(|| invalid_data(context))? as u32;
+                JsValue::new((value >> 1) as i32 ^ -((value & 1) as i32))
+            }
+            tag::NUMBER => JsValue::new(self.read_f64().ok_or_else(|| invalid_data(context))?),
+            tag::BIGINT => JsValue::new(self.read_bigint().ok_or_else(|| invalid_data(context))?),
+            tag::STRING => JsValue::new(self.read_string().ok_or_else(|| invalid_data(context))?),
+            tag::OBJECT_REFERENCE => {
+                let object = self
+                    .read_varint()
+                    .and_then(|index| self.memory.get(index as usize))
+                    .ok_or_else(|| invalid_data(context))?;
+                object.clone().into()
+            }
+            _ => {
+                if self.depth == MAX_DEPTH {
+                    return Err(too_deep(context));
+                }
+                self.depth += 1;
+                let object = self.deserialize_object(tag, context);
+                self.depth -= 1;
+                object?.into()
+            }
+        };
+        Ok(value)
+    }
+
+    /// Deserializes an object that was not serialized before.
+    fn deserialize_object(&mut self, tag: u8, context: &mut Context) -> JsResult<JsObject> {
+        let object = match tag {
+            tag::BOOLEAN_OBJECT => {
+                let value = match self.read_u8() {
+                    Some(0) => false,
+                    Some(1) => true,
+                    _ => return Err(invalid_data(context)),
+                };
+                JsValue::new(value).to_object(context)?
+            }
+            tag::NUMBER_OBJECT => {
+                let value = self.read_f64().ok_or_else(|| invalid_data(context))?;
+                JsValue::new(value).to_object(context)?
+            }
+            tag::BIGINT_OBJECT => {
+                let value = self.read_bigint().ok_or_else(|| invalid_data(context))?;
+                JsValue::new(value).to_object(context)?
+            }
+            tag::STRING_OBJECT => {
+                let value = self.read_string().ok_or_else(|| invalid_data(context))?;
+                JsValue::new(value).to_object(context)?
+            }
+            tag::DATE => {
+                let time = self.read_f64().ok_or_else(|| invalid_data(context))?;
+                let prototype = context.standard_objects().date_object().prototype();
+                JsObject::from_proto_and_data(
+                    prototype,
+                    ObjectData::date(Date::from_time_value(time)),
+                )
+            }
+            tag::REGEXP => {
+                let source = self.read_string().ok_or_else(|| invalid_data(context))?;
+                let flags = self.read_string().ok_or_else(|| invalid_data(context))?;
+                RegExp::create(source.into(), flags.into(), context)?
+                    .as_object()
+                    .cloned()
+                    .expect("RegExpCreate must return an object")
+            }
+            tag::ARRAY_BUFFER => {
+                let data = self
+                    .read_varint()
+                    .and_then(|length| self.read_bytes(length))
+                    .ok_or_else(|| invalid_data(context))?;
+                let prototype = context.standard_objects().array_buffer_object().prototype();
+                JsObject::from_proto_and_data(
+                    prototype,
+                    ObjectData::array_buffer(ArrayBuffer {
+                        array_buffer_byte_length: data.len(),
+                        array_buffer_data: Some(data.to_vec()),
+                        array_buffer_detach_key: JsValue::undefined(),
+                    }),
+                )
+            }
+            tag::TYPED_ARRAY => {
+                let name = self
+                    .read_u8()
+                    .and_then(|index| TYPED_ARRAY_NAMES.get(index as usize))
+                    .copied()
+                    .ok_or_else(|| invalid_data(context))?;
+                let buffer = self.deserialize_value(context)?;
+                if !matches!(buffer.as_object(), Some(buffer) if buffer.is_array_buffer()) {
+                    return Err(invalid_data(context));
+                }
+                let byte_offset = self.read_varint().ok_or_else(|| invalid_data(context))?;
+                let length = self.read_varint().ok_or_else(|| invalid_data(context))?;
+
+                let constructor =
+                    typed_array_constructor(name)(context.standard_objects()).constructor();
+                constructor
+                    .construct(
+                        &[
+                            buffer,
+                            JsValue::new(byte_offset as f64),
+                            JsValue::new(length as f64),
+                        ],
+                        &constructor.clone().into(),
+                        context,
+                    )?
+                    .as_object()
+                    .cloned()
+                    .expect("typed array constructors must return an object")
+            }
+            tag::MAP => {
+                let prototype = context.standard_objects().map_object().prototype();
+                let map =
+                    JsObject::from_proto_and_data(prototype, ObjectData::map(OrderedMap::new()));
+                self.memory.push(map.clone());
+                let length = self.read_varint().ok_or_else(|| invalid_data(context))?;
+                for _ in 0..length {
+                    let key = self.deserialize_value(context)?;
+                    let value = self.deserialize_value(context)?;
+                    map.borrow_mut()
+                        .as_map_mut()
+                        .expect("the object must be a map")
+                        .insert(key, value);
+                }
+                return Ok(map);
+            }
+            tag::SET => {
+                let prototype = context.standard_objects().set_object().prototype();
+                let set =
+                    JsObject::from_proto_and_data(prototype, ObjectData::set(OrderedSet::new()));
+                self.memory.push(set.clone());
+                let length = self.read_varint().ok_or_else(|| invalid_data(context))?;
+                for _ in 0..length {
+                    let value = self.deserialize_value(context)?;
+                    set.borrow_mut()
+                        .as_set_mut()
+                        .expect("the object must be a set")
+                        .add(value);
+                }
+                return Ok(set);
+            }
+            tag::ERROR => {
+                let constructor = self
+                    .read_u8()
+                    .and_then(|index| error_constructor(index as usize))
+                    .ok_or_else(|| invalid_data(context))?;
+                let message = match self.read_u8() {
+                    Some(0) => None,
+                    Some(1) => Some(self.read_string().ok_or_else(|| invalid_data(context))?),
+                    _ => return Err(invalid_data(context)),
+                };
+
+                let prototype = constructor(context.standard_objects()).prototype();
+                let error = JsObject::from_proto_and_data(prototype, ObjectData::error());
+                if let Some(message) = message {
+                    error.insert_property(
+                        "message",
+                        PropertyDescriptor::builder()
+                            .value(message)
+                            .writable(true)
+                            .enumerable(false)
+                            .configurable(true),
+                    );
+                }
+                error
+            }
+            tag::ARRAY => {
+                let length = self.read_varint().ok_or_else(|| invalid_data(context))?;
+                let array = Array::array_create(length as usize, None, context)?;
+                self.memory.push(array.clone());
+                self.deserialize_properties(&array, context)?;
+                return Ok(array);
+            }
+            tag::OBJECT => {
+                let object = context.construct_object();
+                self.memory.push(object.clone());
+                self.deserialize_properties(&object, context)?;
+                return Ok(object);
+            }
+            _ => return Err(invalid_data(context)),
+        };
+
+        self.memory.push(object.clone());
+        Ok(object)
+    }
+
+    /// Deserializes the properties of an array or an object, up to the end marker.
+    fn deserialize_properties(&mut self, object: &JsObject, context: &mut Context) -> JsResult<()> {
+        loop {
+            match self.read_u8() {
+                Some(tag::END) => return Ok(()),
+                Some(tag::STRING) => {
+                    let key = self.read_string().ok_or_else(|| invalid_data(context))?;
+                    let value = self.deserialize_value(context)?;
+                    object.create_data_property_or_throw(key, value, context)?;
+                }
+                _ => return Err(invalid_data(context)),
+            }
+        }
+    }
+
+    fn read_u8(&mut self) -> Option<u8> {
+        let (&byte, rest) = self.input.split_first()?;
+        self.input = rest;
+        Some(byte)
+    }
+
+    fn read_bytes(&mut self, length: u64) -> Option<&[u8]> {
+        let length = usize::try_from(length).ok()?;
+        if length > self.input.len() {
+            return None;
+        }
+        let (bytes, rest) = self.input.split_at(length);
+        self.input = rest;
+        Some(bytes)
+    }
+
+    /// Reads an unsigned integer in the LEB128 variable length encoding.
+    fn read_varint(&mut self) -> Option<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_u8()?;
+            value |= u64::from(byte & 0x7f).checked_shl(shift)?;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    fn read_f64(&mut self) -> Option<f64> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.read_bytes(8)?);
+        Some(f64::from_le_bytes(bytes))
+    }
+
+    fn read_bigint(&mut self) -> Option<JsBigInt> {
+        let length = self.read_varint()?;
+        let bytes = self.read_bytes(length)?;
+        Some(RawBigInt::from_signed_bytes_le(bytes).into())
+    }
+
+    /// Reads a string in the WTF-8 encoding.
+    fn read_string(&mut self) -> Option<JsString> {
+        let length = self.read_varint()?;
+        let mut bytes = self.read_bytes(length)?;
+        let mut units = Vec::with_capacity(bytes.len());
+        loop {
+            match std::str::from_utf8(bytes) {
+                Ok(valid) => {
+                    units.extend(valid.encode_utf16());
+                    return Some(units.into());
+                }
+                Err(error) => {
+                    let (valid, invalid) = bytes.split_at(error.valid_up_to());
+                    units.extend(std::str::from_utf8(valid).ok()?.encode_utf16());
+
+                    // The only sequences that are valid WTF-8 but not UTF-8 are encoded surrogates.
+                    match *invalid {
+                        [0xed, second @ 0xa0..=0xbf, third @ 0x80..=0xbf, ..] => {
+                            units.push(
+                                0xd000 | u16::from(second & 0x3f) << 6 | u16::from(third & 0x3f),
+                            );
+                            bytes = &invalid[3..];
+                        }
+                        _ => return None,
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the accessor of the standard constructor of the error with the serialized representation `index`.
+fn error_constructor(index: usize) -> Option<fn(&StandardObjects) -> &StandardConstructor> {
+    Some(match index {
+        0 => StandardObjects::error_object,
+        1 => StandardObjects::eval_error_object,
+        2 => StandardObjects::range_error_object,
+        3 => StandardObjects::reference_error_object,
+        4 => StandardObjects::syntax_error_object,
+        5 => StandardObjects::type_error_object,
+        6 => StandardObjects::uri_error_object,
+        _ => return None,
+    })
+}
+
+/// Returns the accessor of the standard constructor of a typed array.
+fn typed_array_constructor(name: TypedArrayName) -> fn(&StandardObjects) -> &StandardConstructor {
+    match name {
+        TypedArrayName::Int8Array => StandardObjects::typed_int8_array_object,
+        TypedArrayName::Uint8Array => StandardObjects::typed_uint8_array_object,
+        TypedArrayName::Uint8ClampedArray => StandardObjects::typed_uint8clamped_array_object,
+        TypedArrayName::Int16Array => StandardObjects::typed_int16_array_object,
+        TypedArrayName::Uint16Array => StandardObjects::typed_uint16_array_object,
+        TypedArrayName::Int32Array => StandardObjects::typed_int32_array_object,
+        TypedArrayName::Uint32Array => StandardObjects::typed_uint32_array_object,
+        TypedArrayName::BigInt64Array => StandardObjects::typed_bigint64_array_object,
+        TypedArrayName::BigUint64Array => StandardObjects::typed_biguint64_array_object,
+        TypedArrayName::Float32Array => StandardObjects::typed_float32_array_object,
+        TypedArrayName::Float64Array => StandardObjects::typed_float64_array_object,
+    }
+}
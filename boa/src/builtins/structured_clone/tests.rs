@@ -0,0 +1,408 @@
+use super::{deserialize, serialize};
+use crate::{forward, Context, JsValue};
+
+#[test]
+fn primitives() {
+    let mut context = Context::new();
+
+    assert_eq!(
+        forward(&mut context, "structuredClone(undefined)"),
+        "undefined"
+    );
+    assert_eq!(forward(&mut context, "structuredClone(null)"), "null");
+    assert_eq!(forward(&mut context, "structuredClone(true)"), "true");
+    assert_eq!(forward(&mut context, "structuredClone(-42)"), "-42");
+    assert_eq!(
+        forward(&mut context, "structuredClone(2147483647)"),
+        "2147483647"
+    );
+    assert_eq!(
+        forward(&mut context, "structuredClone(-2147483648)"),
+        "-2147483648"
+    );
+    assert_eq!(forward(&mut context, "structuredClone(1.5)"), "1.5");
+    assert_eq!(
+        forward(&mut context, "Object.is(structuredClone(-0), -0)"),
+        "true"
+    );
+    assert_eq!(forward(&mut context, "structuredClone(NaN)"), "NaN");
+    assert_eq!(
+        forward(
+            &mut context,
+            "structuredClone(-123456789012345678901234567890n)"
+        ),
+        "-123456789012345678901234567890n"
+    );
+    assert_eq!(forward(&mut context, "structuredClone(0n)"), "0n");
+    assert_eq!(
+        forward(&mut context, "structuredClone('héllo')"),
+        "\"héllo\""
+    );
+}
+
+#[test]
+fn objects_and_arrays() {
+    let mut context = Context::new();
+    let init = r#"
+        var original = { a: 1, b: { c: [1, 2, 3] }, "0": "zero" };
+        delete original.b.c[1];
+        Object.defineProperty(original, "hidden", { value: 1, enumerable: false });
+        var clone = structuredClone(original);
+        "#;
+    forward(&mut context, init);
+
+    assert_eq!(forward(&mut context, "clone !== original"), "true");
+    assert_eq!(forward(&mut context, "clone.b !== original.b"), "true");
+    assert_eq!(
+        forward(&mut context, "Object.keys(clone).join()"),
+        "\"0,a,b\""
+    );
+    assert_eq!(forward(&mut context, "clone.b.c.length"), "3");
+    assert_eq!(forward(&mut context, "1 in clone.b.c"), "false");
+    assert_eq!(forward(&mut context, "Array.isArray(clone.b.c)"), "true");
+    assert_eq!(forward(&mut context, "clone.hidden"), "undefined");
+    assert_eq!(
+        forward(
+            &mut context,
+            "Object.getPrototypeOf(clone) === Object.prototype"
+        ),
+        "true"
+    );
+}
+
+#[test]
+fn getters_are_invoked() {
+    let mut context = Context::new();
+    let init = r#"
+        var calls = 0;
+        var clone = structuredClone({ get value() { calls++; return 42; } });
+        "#;
+    forward(&mut context, init);
+
+    assert_eq!(forward(&mut context, "calls"), "1");
+    assert_eq!(
+        forward(
+            &mut context,
+            "Object.getOwnPropertyDescriptor(clone, 'value').value"
+        ),
+        "42"
+    );
+}
+
+#[test]
+fn cycles_and_shared_references() {
+    let mut context = Context::new();
+    let init = r#"
+        var shared = { name: "shared" };
+        var original = { first: shared, second: shared, list: [shared] };
+        original.self = original;
+        var clone = structuredClone(original);
+        "#;
+    forward(&mut context, init);
+
+    assert_eq!(forward(&mut context, "clone.self === clone"), "true");
+    assert_eq!(
+        forward(&mut context, "clone.first === clone.second"),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut context, "clone.list[0] === clone.first"),
+        "true"
+    );
+    assert_eq!(forward(&mut context, "clone.first !== shared"), "true");
+}
+
+#[test]
+fn wrapper_objects() {
+    let mut context = Context::new();
+
+    assert_eq!(
+        forward(
+            &mut context,
+            "structuredClone(new Boolean(false)).valueOf()"
+        ),
+        "false"
+    );
+    assert_eq!(
+        forward(&mut context, "typeof structuredClone(new Number(3))"),
+        "\"object\""
+    );
+    assert_eq!(
+        forward(&mut context, "structuredClone(new String('abc')).length"),
+        "3"
+    );
+    assert_eq!(
+        forward(&mut context, "structuredClone(Object(5n)).valueOf()"),
+        "5n"
+    );
+}
+
+#[test]
+fn dates_and_regexps() {
+    let mut context = Context::new();
+    let init = r#"
+        var date = structuredClone(new Date(1234567890123));
+        var invalid = structuredClone(new Date(NaN));
+        var regexp = /a+b/gi;
+        regexp.lastIndex = 3;
+        var regexpClone = structuredClone(regexp);
+        "#;
+    forward(&mut context, init);
+
+    assert_eq!(forward(&mut context, "date instanceof Date"), "true");
+    assert_eq!(forward(&mut context, "date.getTime()"), "1234567890123");
+    assert_eq!(forward(&mut context, "invalid.getTime()"), "NaN");
+    assert_eq!(
+        forward(&mut context, "regexpClone instanceof RegExp"),
+        "true"
+    );
+    assert_eq!(forward(&mut context, "regexpClone.source"), "\"a+b\"");
+    assert_eq!(forward(&mut context, "regexpClone.flags"), "\"gi\"");
+    assert_eq!(forward(&mut context, "regexpClone.lastIndex"), "0");
+}
+
+#[test]
+fn maps_and_sets() {
+    let mut context = Context::new();
+    let init = r#"
+        var key = {};
+        var map = new Map([[key, "object"], ["key", key]]);
+        map.set("self", map);
+        var mapClone = structuredClone(map);
+        var set = new Set([1, "two", key]);
+        var setClone = structuredClone(set);
+        "#;
+    forward(&mut context, init);
+
+    assert_eq!(forward(&mut context, "mapClone instanceof Map"), "true");
+    assert_eq!(forward(&mut context, "mapClone.size"), "3");
+    assert_eq!(
+        forward(&mut context, "mapClone.get('self') === mapClone"),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut context, "mapClone.get(mapClone.get('key'))"),
+        "\"object\""
+    );
+    assert_eq!(forward(&mut context, "mapClone.has(key)"), "false");
+    assert_eq!(forward(&mut context, "setClone instanceof Set"), "true");
+    assert_eq!(forward(&mut context, "[...setClone].length"), "3");
+    assert_eq!(forward(&mut context, "setClone.has('two')"), "true");
+}
+
+#[test]
+fn array_buffers_and_typed_arrays() {
+    let mut context = Context::new();
+    let init = r#"
+        var buffer = new ArrayBuffer(8);
+        var bytes = new Uint8Array(buffer);
+        bytes.set([1, 2, 3, 4, 5, 6, 7, 8]);
+        var view = new Int16Array(buffer, 2, 2);
+        var clone = structuredClone({ buffer, bytes, view });
+        var floats = structuredClone(new Float64Array([0.5, -1]));
+        var bigints = structuredClone(new BigInt64Array([-1n]));
+        "#;
+    forward(&mut context, init);
+
+    assert_eq!(
+        forward(&mut context, "clone.buffer instanceof ArrayBuffer"),
+        "true"
+    );
+    assert_eq!(forward(&mut context, "clone.buffer !== buffer"), "true");
+    assert_eq!(
+        forward(&mut context, "new Uint8Array(clone.buffer).length"),
+        "8"
+    );
+    assert_eq!(
+        forward(&mut context, "clone.bytes.buffer === clone.buffer"),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut context, "clone.view.buffer === clone.buffer"),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut context, "clone.view instanceof Int16Array"),
+        "true"
+    );
+    assert_eq!(forward(&mut context, "clone.view.byteOffset"), "2");
+    assert_eq!(forward(&mut context, "clone.view.length"), "2");
+    assert_eq!(
+        forward(&mut context, "clone.bytes.join()"),
+        "\"1,2,3,4,5,6,7,8\""
+    );
+    assert_eq!(forward(&mut context, "floats.join()"), "\"0.5,-1\"");
+    assert_eq!(forward(&mut context, "bigints[0]"), "-1n");
+}
+
+#[test]
+fn errors() {
+    let mut context = Context::new();
+    let init = r#"
+        var type = structuredClone(new TypeError("bad type"));
+        var custom = new RangeError("out of range");
+        custom.name = "CustomError";
+        var customClone = structuredClone(custom);
+        var empty = structuredClone(new Error());
+        "#;
+    forward(&mut context, init);
+
+    assert_eq!(forward(&mut context, "type instanceof TypeError"), "true");
+    assert_eq!(forward(&mut context, "type.message"), "\"bad type\"");
+    assert_eq!(forward(&mut context, "Object.keys(type).length"), "0");
+    assert_eq!(
+        forward(
+            &mut context,
+            "Object.getPrototypeOf(customClone) === Error.prototype"
+        ),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut context, "customClone.message"),
+        "\"out of range\""
+    );
+    assert_eq!(
+        forward(&mut context, "empty.hasOwnProperty('message')"),
+        "false"
+    );
+}
+
+#[test]
+fn uncloneable_values() {
+    let mut context = Context::new();
+
+    for value in [
+        "Symbol()",
+        "function() {}",
+        "{ f: () => {} }",
+        "new Map([[1, Symbol()]])",
+        "[][Symbol.iterator]()",
+        "globalThis",
+    ] {
+        let source = format!(
+            "try {{ structuredClone({}); 'cloned' }} catch (e) {{ e.name }}",
+            value
+        );
+        assert_eq!(forward(&mut context, &source), "\"TypeError\"", "{}", value);
+    }
+}
+
+#[test]
+fn rust_api_between_contexts() {
+    let mut sender = Context::new();
+    let value = sender
+        .eval(
+            r#"
+            var original = { list: [1, "two", 3n], when: new Date(0), pattern: /x/y };
+            original.list.push(original);
+            original
+            "#,
+        )
+        .unwrap();
+    let bytes = serialize(&value, &mut sender).unwrap();
+
+    let mut receiver = Context::new();
+    let value = deserialize(&bytes, &mut receiver).unwrap();
+    receiver.register_global_property("received", value, Default::default());
+
+    assert_eq!(
+        forward(&mut receiver, "received.list[3] === received"),
+        "true"
+    );
+    assert_eq!(forward(&mut receiver, "received.list[2]"), "3n");
+    assert_eq!(
+        forward(&mut receiver, "received.when instanceof Date"),
+        "true"
+    );
+    assert_eq!(forward(&mut receiver, "received.pattern.sticky"), "true");
+
+    // Serializing again in the other context produces the same bytes.
+    let value = receiver.eval("received").unwrap();
+    assert_eq!(serialize(&value, &mut receiver).unwrap(), bytes);
+}
+
+#[test]
+fn serialized_format() {
+    let mut context = Context::new();
+    let value = context.eval("({ a: [1, 'b'] })").unwrap();
+
+    assert_eq!(
+        serialize(&value, &mut context).unwrap(),
+        [1, 21, 7, 1, b'a', 20, 2, 7, 1, b'0', 4, 2, 7, 1, b'1', 7, 1, b'b', 22, 22]
+    );
+    assert_eq!(
+        serialize(&JsValue::new(-1), &mut context).unwrap(),
+        [1, 4, 1]
+    );
+}
+
+#[test]
+fn invalid_data() {
+    let mut context = Context::new();
+
+    for bytes in [
+        &[][..],
+        &[0],
+        &[1],
+        &[1, 0, 0],
+        &[1, 7, 5, b'a'],
+        &[1, 7, 1, 0xff],
+        &[1, 8, 0],
+        &[1, 21, 7, 1, b'a', 0],
+        &[1, 21, 4, 0, 0, 22],
+        &[1, 16, 1, 21, 22, 0, 0],
+        &[1, 16, 42, 15, 0, 0, 0],
+        &[1, 16, 1, 15, 1, 0, 0, 2],
+        &[1, 19, 7, 0],
+        &[1, 4, 0x80, 0x80, 0x80, 0x80, 0x80],
+        &[1, 255],
+    ] {
+        let error = deserialize(bytes, &mut context).unwrap_err();
+        let error = error.as_object().unwrap();
+        assert!(error.is_error(), "{:?}", bytes);
+    }
+}
+
+#[test]
+fn deeply_nested_values() {
+    let mut context = Context::new();
+    let clone_nested = |depth| {
+        format!(
+            r#"
+            var value = 0;
+            for (var i = 0; i < {}; i++) {{
+                value = [value];
+            }}
+            try {{ structuredClone(value); 'cloned' }} catch (e) {{ e.name }}
+            "#,
+            depth
+        )
+    };
+    assert_eq!(forward(&mut context, &clone_nested(512)), "\"cloned\"");
+    assert_eq!(forward(&mut context, &clone_nested(513)), "\"TypeError\"");
+
+    // Arrays holding one another as their element "0", deeper than the stack could hold.
+    let mut bytes = vec![1];
+    bytes.extend([20, 1, 7, 1, b'0'].repeat(1_000_000));
+    let error = deserialize(&bytes, &mut context).unwrap_err();
+    assert!(error.as_object().unwrap().is_error());
+}
+
+#[test]
+fn unpaired_surrogates() {
+    let mut context = Context::new();
+
+    assert_eq!(
+        forward(
+            &mut context,
+            "var s = structuredClone('a\\uD800b\\uDFFF'); [s.length, s.charCodeAt(1), s.charCodeAt(3)].join()"
+        ),
+        "\"4,55296,57343\""
+    );
+
+    let value = context.eval("'\\uDC00'").unwrap();
+    let bytes = serialize(&value, &mut context).unwrap();
+    assert_eq!(bytes, [1, 7, 3, 0xed, 0xb0, 0x80]);
+    let value = deserialize(&bytes, &mut context).unwrap();
+    assert_eq!(value.as_string().unwrap().as_slice(), [0xDC00]);
+}
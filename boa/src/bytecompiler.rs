@@ -2,15 +2,29 @@ use gc::Gc;
 
 use crate::{
     builtins::function::ThisMode,
+    interner::Sym,
     syntax::ast::{
-        node::{Declaration, GetConstField, GetField, StatementList},
+        node::{
+            declaration::{BindingPatternTypeArray, BindingPatternTypeObject, DeclarationPattern},
+            template::TemplateElement,
+            ArrowFunctionDecl, AsyncFunctionDecl, AsyncFunctionExpr, Block, Call, Declaration,
+            DeclarationList, FormalParameter, FunctionDecl, FunctionExpr, GeneratorDecl,
+            GeneratorExpr, GetConstField, GetField, Identifier, MethodDefinitionKind,
+            PropertyDefinition, PropertyName, StatementList,
+        },
         op::{AssignOp, BinOp, BitOp, CompOp, LogOp, NumOp, UnaryOp},
-        Const, Node,
+        side_table::SideTable,
+        visitor::{walk_call, walk_node, Visitor},
+        Const, Node, Position,
     },
-    vm::{CodeBlock, Opcode},
+    vm::{BindingKind, CodeBlock, Handler, InlineCache, Opcode},
     JsBigInt, JsString, JsValue,
 };
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    mem::size_of,
+    rc::Rc,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 enum Literal {
@@ -24,30 +38,229 @@ struct Label {
     index: u32,
 }
 
+/// The kinds of statements that can be the target of, or have to be left by, a `break` or
+/// `continue` statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JumpControlKind {
+    /// A `while`, `do...while` or `for` loop.
+    Loop,
+    /// A `for...in` loop, which keeps its iterator and `next` method on the stack.
+    ForInLoop,
+    /// A `for...of` loop, which keeps its iterator and `next` method on the stack.
+    ForOfLoop,
+    Switch,
+    /// A labelled block, which is only left by a `break` to its label.
+    LabelledBlock,
+    /// A block protected by an exception handler.
+    Try,
+}
+
+impl JumpControlKind {
+    fn is_loop(self) -> bool {
+        matches!(self, Self::Loop | Self::ForInLoop | Self::ForOfLoop)
+    }
+}
+
 #[derive(Debug, Clone)]
 struct JumpControlInfo {
-    label: Option<Box<str>>,
+    label: Option<Sym>,
+    /// The target of `continue` statements, or `DUMMY_ADDRESS` if it is not known yet.
     start_address: u32,
-    is_loop: bool,
+    kind: JumpControlKind,
     breaks: Vec<Label>,
+    /// The `continue` statements to patch once the target is known.
+    continues: Vec<Label>,
+    /// The number of scopes that were open when the statement was entered.
+    scope_depth: usize,
+    /// The number of values kept on the stack by the enclosing statements when the statement was
+    /// entered.
+    stack_depth: u32,
+    /// The `finally` block of a `try` statement.
+    finally: Option<Block>,
+    /// The start of the code protected by a `try` statement that is not yet in a handler, if any.
+    range_start: Option<u32>,
+    /// The handlers of the code protected by a `try` statement, to patch with the address of the
+    /// handler code.
+    handlers: Vec<usize>,
+}
+
+/// A scope whose bindings are known at compile time.
+///
+/// At runtime, each scope is a declarative environment, and the bindings in it are accessed by
+/// slot instead of by name.
+#[derive(Debug, Default)]
+struct Scope {
+    /// The slot of each binding.
+    slots: HashMap<Sym, u32>,
+    /// Whether a direct `eval` can add bindings to this scope at runtime.
+    dynamic: bool,
+}
+
+/// Collects the names of the `var` and function declarations of a function body, and finds
+/// direct `eval` calls in it.
+///
+/// Nested functions are not entered, since they have their own scope.
+#[derive(Debug, Default)]
+struct VarScopedDeclarations {
+    names: Vec<Sym>,
+    contains_direct_eval: bool,
+}
+
+impl<'ast> Visitor<'ast> for VarScopedDeclarations {
+    fn visit_node(&mut self, node: &'ast Node) {
+        if let Node::VarDeclList(list) = node {
+            self.names
+                .extend(list.as_ref().iter().flat_map(bound_names));
+        }
+        walk_node(self, node)
+    }
+
+    fn visit_call(&mut self, call: &'ast Call) {
+        if matches!(call.expr(), Node::Identifier(ident) if ident.sym() == "eval") {
+            self.contains_direct_eval = true;
+        }
+        walk_call(self, call)
+    }
+
+    fn visit_function_decl(&mut self, function: &'ast FunctionDecl) {
+        self.names.push(function.name());
+    }
+
+    fn visit_async_function_decl(&mut self, function: &'ast AsyncFunctionDecl) {
+        self.names.push(function.name());
+    }
+
+    fn visit_generator_decl(&mut self, generator: &'ast GeneratorDecl) {
+        self.names.push(generator.name());
+    }
+
+    fn visit_function_expr(&mut self, _function: &'ast FunctionExpr) {}
+
+    fn visit_async_function_expr(&mut self, _function: &'ast AsyncFunctionExpr) {}
+
+    fn visit_generator_expr(&mut self, _generator: &'ast GeneratorExpr) {}
+
+    fn visit_arrow_function_decl(&mut self, _function: &'ast ArrowFunctionDecl) {}
+}
+
+/// Finds references to `arguments` in a function, including in its arrow functions, which don't
+/// have their own arguments object.
+#[derive(Debug, Default)]
+struct ArgumentsReferences {
+    found: bool,
+}
+
+impl<'ast> Visitor<'ast> for ArgumentsReferences {
+    fn visit_identifier(&mut self, identifier: &'ast Identifier) {
+        if identifier.sym() == "arguments" {
+            self.found = true;
+        }
+    }
+
+    fn visit_function_decl(&mut self, _function: &'ast FunctionDecl) {}
+
+    fn visit_function_expr(&mut self, _function: &'ast FunctionExpr) {}
+
+    fn visit_async_function_decl(&mut self, _function: &'ast AsyncFunctionDecl) {}
+
+    fn visit_async_function_expr(&mut self, _function: &'ast AsyncFunctionExpr) {}
+
+    fn visit_generator_decl(&mut self, _generator: &'ast GeneratorDecl) {}
+
+    fn visit_generator_expr(&mut self, _generator: &'ast GeneratorExpr) {}
+}
+
+/// Gets the names bound by a declaration.
+fn bound_names(declaration: &Declaration) -> Vec<Sym> {
+    match declaration {
+        Declaration::Identifier { ident, .. } => vec![ident.sym()],
+        Declaration::Pattern(pattern) => pattern.idents(),
+    }
+}
+
+/// Gets the `let` and `const` declarations directly contained in a statement list, which are
+/// scoped to it.
+fn lexically_scoped_declarations<'a, I>(items: I) -> Vec<(Sym, BindingKind)>
+where
+    I: IntoIterator<Item = &'a Node>,
+{
+    let mut bindings = Vec::new();
+    for item in items {
+        let (list, kind): (&DeclarationList, _) = match item {
+            Node::LetDeclList(list) => (list, BindingKind::Let),
+            Node::ConstDeclList(list) => (list, BindingKind::Const),
+            _ => continue,
+        };
+        for declaration in list.as_ref() {
+            bindings.extend(
+                bound_names(declaration)
+                    .into_iter()
+                    .map(|name| (name, kind)),
+            );
+        }
+    }
+    bindings
 }
 
 #[derive(Debug, Clone, Copy)]
 enum Access<'a> {
-    Variable { index: u32 },
+    Variable { name: Sym },
     ByName { node: &'a GetConstField },
     ByValue { node: &'a GetField },
     This,
 }
 
+impl Access<'_> {
+    /// The number of values that [`ByteCompiler::access_reference`] pushes on the stack for this
+    /// access.
+    fn reference_size(&self) -> u8 {
+        match self {
+            Access::Variable { .. } | Access::This => 0,
+            Access::ByName { .. } => 1,
+            Access::ByValue { .. } => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FunctionKind {
+    Declaration,
+    Expression,
+    Arrow,
+}
+
+/// Whether the frames of a function can be suspended, and by what.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FunctionFlavor {
+    Normal,
+    /// A generator function, suspended by `yield`.
+    Generator,
+    /// An async function, suspended by `await`.
+    Async,
+}
+
 #[derive(Debug)]
 pub struct ByteCompiler {
     code_block: CodeBlock,
     literals_map: HashMap<Literal, u32>,
-    names_map: HashMap<JsString, u32>,
+    names_map: HashMap<Sym, u32>,
     functions_map: HashMap<JsString, u32>,
     jump_info: Vec<JumpControlInfo>,
+    /// The scopes enclosing the code being compiled, innermost last, including the scopes of the
+    /// enclosing functions.
+    scopes: Vec<Rc<Scope>>,
+    /// The number of scopes in `scopes` that are not block environments of the code being
+    /// compiled.
+    environment_base: usize,
+    /// The number of values that the enclosing statements keep on the stack, like the iterators
+    /// of `for...in` and `for...of` loops.
+    stack_depth: u32,
     top_level: bool,
+    /// The side tables of the statement lists being compiled, innermost last, which give the
+    /// source positions of the nodes.
+    side_tables: Vec<Rc<SideTable>>,
+    /// The source position of the node being compiled.
+    position: Option<Position>,
 }
 
 impl ByteCompiler {
@@ -62,7 +275,12 @@ impl ByteCompiler {
             names_map: HashMap::new(),
             functions_map: HashMap::new(),
             jump_info: Vec::new(),
+            scopes: Vec::new(),
+            environment_base: 0,
+            stack_depth: 0,
             top_level: true,
+            side_tables: Vec::new(),
+            position: None,
         }
     }
 
@@ -84,24 +302,61 @@ impl ByteCompiler {
     }
 
     #[inline]
-    fn get_or_insert_name(&mut self, name: &str) -> u32 {
-        if let Some(index) = self.names_map.get(name) {
+    fn get_or_insert_name(&mut self, name: Sym) -> u32 {
+        if let Some(index) = self.names_map.get(&name) {
             return *index;
         }
 
-        let name = JsString::new(name);
         let index = self.code_block.variables.len() as u32;
-        self.code_block.variables.push(name.clone());
+        self.code_block.variables.push(name);
         self.names_map.insert(name, index);
         index
     }
 
+    /// Emits `opcode`, which gets or sets the property `name`, with an inline cache of its own.
+    #[inline]
+    fn emit_property_access(&mut self, opcode: Opcode, name: Sym) {
+        let index = self.get_or_insert_name(name);
+        let cache = self.code_block.inline_caches.len() as u32;
+        self.code_block.inline_caches.push(InlineCache::default());
+        self.emit(opcode, &[index, cache]);
+    }
+
     #[inline]
     fn next_opcode_location(&mut self) -> u32 {
         assert!(self.code_block.code.len() < u32::MAX as usize);
         self.code_block.code.len() as u32
     }
 
+    /// Records the current source position for the opcode about to be emitted.
+    fn mark_position(&mut self) {
+        let position = match self.position {
+            Some(position) => position,
+            None => return,
+        };
+        let pc = self.next_opcode_location();
+        match self.code_block.line_table.last_mut() {
+            Some(&mut (_, last)) if last == position => {}
+            Some(last) if last.0 == pc => last.1 = position,
+            _ => self.code_block.line_table.push((pc, position)),
+        }
+    }
+
+    /// Sets the source position of the code emitted from now on to the one of `node`, if it is
+    /// known, and returns the previous position.
+    fn enter(&mut self, node: &Node) -> Option<Position> {
+        let previous = self.position;
+        if let Some(span) = self.side_tables.last().and_then(|table| table.span(node)) {
+            self.position = Some(span.start());
+        }
+        previous
+    }
+
+    /// Restores the source position returned by `enter` once a node is compiled.
+    fn leave(&mut self, previous: Option<Position>) {
+        self.position = previous;
+    }
+
     #[inline]
     fn emit(&mut self, opcode: Opcode, operands: &[u32]) {
         self.emit_opcode(opcode);
@@ -127,6 +382,7 @@ impl ByteCompiler {
 
     #[inline]
     fn emit_opcode(&mut self, opcode: Opcode) {
+        self.mark_position();
         self.emit_u8(opcode as u8)
     }
 
@@ -135,6 +391,12 @@ impl ByteCompiler {
         self.code_block.code.push(value);
     }
 
+    #[inline]
+    fn emit_rotate(&mut self, opcode: Opcode, count: u8) {
+        self.emit_opcode(opcode);
+        self.emit_u8(count);
+    }
+
     #[inline]
     fn emit_push_integer(&mut self, value: i32) {
         match value {
@@ -224,20 +486,52 @@ impl ByteCompiler {
     }
 
     #[inline]
-    fn push_loop_control_info(&mut self, label: Option<Box<str>>, start_address: u32) {
+    fn push_jump_control_info(
+        &mut self,
+        kind: JumpControlKind,
+        label: Option<Sym>,
+        start_address: u32,
+    ) {
         self.jump_info.push(JumpControlInfo {
             label,
             start_address,
-            is_loop: true,
+            kind,
             breaks: Vec::new(),
+            continues: Vec::new(),
+            scope_depth: self.scopes.len(),
+            stack_depth: self.stack_depth,
+            finally: None,
+            range_start: None,
+            handlers: Vec::new(),
         })
     }
 
+    #[inline]
+    fn push_loop_control_info(&mut self, label: Option<Sym>, start_address: u32) {
+        self.push_jump_control_info(JumpControlKind::Loop, label, start_address)
+    }
+
+    /// Sets the target of the `continue` statements of the innermost loop to the next
+    /// instruction, for loops that don't continue at their start.
+    #[inline]
+    fn set_continue_target(&mut self) {
+        let target = self.next_opcode_location();
+        let info = self.jump_info.last_mut().unwrap();
+
+        assert!(info.kind.is_loop());
+
+        info.start_address = target;
+        for label in std::mem::take(&mut info.continues) {
+            self.patch_jump_with_target(label, target);
+        }
+    }
+
     #[inline]
     fn pop_loop_control_info(&mut self) {
         let loop_info = self.jump_info.pop().unwrap();
 
-        assert!(loop_info.is_loop);
+        assert!(loop_info.kind.is_loop());
+        assert!(loop_info.continues.is_empty());
 
         for label in loop_info.breaks {
             self.patch_jump(label);
@@ -245,50 +539,349 @@ impl ByteCompiler {
     }
 
     #[inline]
-    fn push_switch_control_info(&mut self, label: Option<Box<str>>, start_address: u32) {
-        self.jump_info.push(JumpControlInfo {
-            label,
-            start_address,
-            is_loop: false,
-            breaks: Vec::new(),
-        })
+    fn push_switch_control_info(&mut self, label: Option<Sym>, start_address: u32) {
+        self.push_jump_control_info(JumpControlKind::Switch, label, start_address)
     }
 
     #[inline]
     fn pop_switch_control_info(&mut self) {
         let info = self.jump_info.pop().unwrap();
 
-        assert!(!info.is_loop);
+        assert_eq!(info.kind, JumpControlKind::Switch);
 
         for label in info.breaks {
             self.patch_jump(label);
         }
     }
 
+    /// Enters the code protected by a `try` statement, which runs `finally` when it is left.
     #[inline]
-    fn compile_access<'a>(&mut self, node: &'a Node) -> Access<'a> {
-        match node {
-            Node::Identifier(name) => {
-                let index = self.get_or_insert_name(name.as_ref());
-                Access::Variable { index }
+    fn push_try_control_info(&mut self, finally: Option<&Block>) {
+        let start_address = self.next_opcode_location();
+        self.push_jump_control_info(JumpControlKind::Try, None, start_address);
+
+        let info = self.jump_info.last_mut().unwrap();
+        info.finally = finally.cloned();
+        info.range_start = Some(start_address);
+    }
+
+    /// Leaves the code protected by the innermost `try` statement.
+    ///
+    /// Returns its handlers, to patch with [`ByteCompiler::patch_handlers`].
+    #[inline]
+    fn pop_try_control_info(&mut self) -> Vec<usize> {
+        self.close_try_range(self.jump_info.len() - 1);
+        let info = self.jump_info.pop().unwrap();
+
+        assert_eq!(info.kind, JumpControlKind::Try);
+
+        info.handlers
+    }
+
+    /// Adds a handler for the code protected by the `try` statement at `index` since the range
+    /// was last opened, leaving the code that follows unprotected.
+    fn close_try_range(&mut self, index: usize) {
+        let end = self.next_opcode_location();
+        let environment_base = self.environment_base;
+        let info = &mut self.jump_info[index];
+        if let Some(start) = info.range_start.take() {
+            if start < end {
+                info.handlers.push(self.code_block.handlers.len());
+                self.code_block.handlers.push(Handler {
+                    start,
+                    end,
+                    address: Self::DUMMY_ADDRESS,
+                    stack_depth: info.stack_depth,
+                    environment_depth: (info.scope_depth - environment_base) as u32,
+                });
+            }
+        }
+    }
+
+    /// Protects the code that follows by the `try` statements entered after the first `index`
+    /// jump targets.
+    fn open_try_ranges(&mut self, index: usize) {
+        let start = self.next_opcode_location();
+        for info in &mut self.jump_info[index..] {
+            if info.kind == JumpControlKind::Try {
+                info.range_start = Some(start);
+            }
+        }
+    }
+
+    /// Sets the handler code of `handlers` to the next instruction.
+    fn patch_handlers(&mut self, handlers: Vec<usize>) {
+        let address = self.next_opcode_location();
+        for handler in handlers {
+            self.code_block.handlers[handler].address = address;
+        }
+    }
+
+    /// Compiles the `finally` block of the `try` statement at `index`, in the scope of the `try`
+    /// statement, while `values` more values are on the stack.
+    fn compile_finally(&mut self, index: usize, finally: &Block, values: u32) {
+        let jump_info = self.jump_info.split_off(index);
+        let scopes = self.scopes.split_off(jump_info[0].scope_depth);
+        let stack_depth = self.stack_depth;
+        self.stack_depth = jump_info[0].stack_depth + values;
+
+        self.compile_block(finally, false);
+
+        self.stack_depth = stack_depth;
+        self.scopes.extend(scopes);
+        self.jump_info.extend(jump_info);
+    }
+
+    /// Leaves the statements entered after the jump target at `index`, or all of them if there
+    /// is none, before jumping out of them.
+    ///
+    /// The `finally` blocks of the `try` statements that are left run in place, and the jump is
+    /// not protected by them. If `value` is set, the value on top of the stack is kept.
+    fn emit_jump_control_exits(&mut self, index: Option<usize>, value: bool) {
+        let mut scope_depth = self.scopes.len();
+        for i in (index.map_or(0, |index| index + 1)..self.jump_info.len()).rev() {
+            let kind = self.jump_info[i].kind;
+            if value
+                && matches!(
+                    kind,
+                    JumpControlKind::ForInLoop | JumpControlKind::ForOfLoop
+                )
+            {
+                // Move the value below the iterator of the loop.
+                self.emit_rotate(Opcode::RotateRight, 3);
+            }
+            match kind {
+                JumpControlKind::ForInLoop => {
+                    self.emit(Opcode::Pop, &[]);
+                    self.emit(Opcode::Pop, &[]);
+                }
+                JumpControlKind::ForOfLoop => self.emit(Opcode::IteratorClose, &[]),
+                JumpControlKind::Try => {
+                    self.close_try_range(i);
+                    if let Some(finally) = self.jump_info[i].finally.clone() {
+                        let depth = self.jump_info[i].scope_depth;
+                        for _ in depth..scope_depth {
+                            self.emit(Opcode::PopEnvironment, &[]);
+                        }
+                        scope_depth = depth;
+                        self.compile_finally(i, &finally, value as u32);
+                    }
+                }
+                JumpControlKind::Loop
+                | JumpControlKind::Switch
+                | JumpControlKind::LabelledBlock => {}
+            }
+        }
+
+        // Returning leaves the environments of the frame behind.
+        if let Some(index) = index {
+            for _ in self.jump_info[index].scope_depth..scope_depth {
+                self.emit(Opcode::PopEnvironment, &[]);
+            }
+        }
+    }
+
+    /// Returns the value on top of the stack from the function, leaving all the enclosing
+    /// statements.
+    fn emit_return(&mut self) {
+        self.emit_jump_control_exits(None, true);
+        self.emit(Opcode::Return, &[]);
+        self.open_try_ranges(0);
+    }
+
+    /// Suspends the generator with the value on top of the stack, which is replaced by the value
+    /// that it is resumed with.
+    ///
+    /// Resuming it with a return returns from the function as a `return` statement would.
+    fn emit_yield(&mut self) {
+        self.emit(Opcode::Yield, &[]);
+        let generator_return = self.jump_with_custom_opcode(Opcode::GeneratorNext);
+        self.emit_u32(self.stack_depth);
+        let resumed = self.jump();
+
+        self.patch_jump(generator_return);
+        self.emit_return();
+        self.patch_jump(resumed);
+    }
+
+    /// Yields the values of the iterable on top of the stack, for a `yield*` expression, and
+    /// replaces it with the return value of its iterator.
+    fn emit_yield_delegate(&mut self) {
+        self.emit(Opcode::InitIterator, &[]);
+        // The iterator is first resumed normally, with `undefined`.
+        self.emit(Opcode::PushUndefined, &[]);
+        self.emit(Opcode::PushZero, &[]);
+
+        let start_address = self.next_opcode_location();
+        let done = self.jump_with_custom_opcode(Opcode::GeneratorDelegateNext);
+        // The return address is the second operand.
+        let generator_return = Label {
+            index: done.index + size_of::<u32>() as u32,
+        };
+        self.emit_u32(Self::DUMMY_ADDRESS);
+        self.emit_u32(self.stack_depth);
+        self.emit(Opcode::Yield, &[]);
+        self.emit(Opcode::Jump, &[start_address]);
+
+        self.patch_jump(generator_return);
+        self.emit_return();
+
+        self.patch_jump(done);
+        self.emit_rotate(Opcode::RotateRight, 3);
+        self.emit(Opcode::Pop, &[]);
+        self.emit(Opcode::Pop, &[]);
+    }
+
+    /// Enters a scope with the given bindings, which get slots in order.
+    fn push_scope(&mut self, bindings: &[(Sym, BindingKind)], dynamic: bool) {
+        let slots = bindings
+            .iter()
+            .enumerate()
+            .map(|(slot, &(name, _))| (name, slot as u32))
+            .collect();
+        self.scopes.push(Rc::new(Scope { slots, dynamic }));
+    }
+
+    /// Enters the scope of the function being compiled, with its parameters and the declarations
+    /// of its body.
+    fn push_function_scope(
+        &mut self,
+        kind: FunctionKind,
+        name: Option<Sym>,
+        parameters: &[FormalParameter],
+        body: &StatementList,
+    ) {
+        let mut var_declarations = VarScopedDeclarations::default();
+        var_declarations.visit_statement_list(body);
+
+        let mut bindings: Vec<_> = parameters
+            .iter()
+            .map(|parameter| (parameter.name(), BindingKind::Var))
+            .collect();
+        let mut declared: HashSet<_> = bindings.iter().map(|&(name, _)| name).collect();
+        for name in var_declarations.names {
+            if declared.insert(name) {
+                bindings.push((name, BindingKind::Var));
+            }
+        }
+        for (name, kind) in lexically_scoped_declarations(body.items()) {
+            if declared.insert(name) {
+                bindings.push((name, kind));
+            }
+        }
+
+        // <https://tc39.es/ecma262/#sec-functiondeclarationinstantiation>
+        if kind != FunctionKind::Arrow {
+            let mut references = ArgumentsReferences::default();
+            for parameter in parameters {
+                references.visit_formal_parameter(parameter);
+            }
+            references.visit_statement_list(body);
+
+            let arguments = Sym::intern("arguments");
+            if (references.found || var_declarations.contains_direct_eval)
+                && parameters
+                    .iter()
+                    .all(|parameter| parameter.name() != arguments)
+            {
+                self.code_block.arguments_binding =
+                    match bindings.iter().position(|&(name, _)| name == arguments) {
+                        Some(slot) if bindings[slot].1 == BindingKind::Var => Some(slot as u32),
+                        Some(_) => None,
+                        None => {
+                            bindings.push((arguments, BindingKind::Var));
+                            Some(bindings.len() as u32 - 1)
+                        }
+                    };
+            }
+        }
+
+        if let (FunctionKind::Expression, Some(name)) = (kind, name) {
+            if declared.insert(name) {
+                bindings.push((name, BindingKind::Var));
+                self.code_block.function_binding = Some(bindings.len() as u32 - 1);
+            }
+        }
+
+        let dynamic = var_declarations.contains_direct_eval && !self.code_block.strict;
+        self.push_scope(&bindings, dynamic);
+        self.code_block.bindings = bindings;
+    }
+
+    /// Enters a block scope for the `let` and `const` declarations in `items`, if there are any.
+    ///
+    /// Returns whether a scope was entered.
+    fn push_block_scope<'a, I>(&mut self, items: I) -> bool
+    where
+        I: IntoIterator<Item = &'a Node>,
+    {
+        self.push_block_scope_with_bindings(lexically_scoped_declarations(items))
+    }
+
+    /// Enters a block scope with the given bindings, if there are any.
+    ///
+    /// Returns whether a scope was entered.
+    fn push_block_scope_with_bindings(&mut self, bindings: Vec<(Sym, BindingKind)>) -> bool {
+        if bindings.is_empty() {
+            return false;
+        }
+
+        self.push_scope(&bindings, false);
+        let index = self.code_block.scopes.len() as u32;
+        self.code_block.scopes.push(bindings.into_boxed_slice());
+        self.emit(Opcode::PushDeclarativeEnvironment, &[index]);
+        true
+    }
+
+    /// Leaves the innermost block scope.
+    fn pop_block_scope(&mut self) {
+        self.scopes.pop();
+        self.emit(Opcode::PopEnvironment, &[]);
+    }
+
+    /// Resolves `name` to the depth of the environment that holds its binding, and its slot in it.
+    ///
+    /// Returns `None` if the binding has to be looked up by name at runtime. This is the case for
+    /// global bindings, and for names that are not declared in any scope between the reference
+    /// and a scope where a direct `eval` may add bindings.
+    fn resolve(&self, name: Sym) -> Option<(u32, u32)> {
+        for (depth, scope) in self.scopes.iter().rev().enumerate() {
+            if let Some(&slot) = scope.slots.get(&name) {
+                return Some((depth as u32, slot));
+            }
+            if scope.dynamic {
+                return None;
             }
-            Node::GetConstField(node) => Access::ByName { node },
-            Node::GetField(node) => Access::ByValue { node },
-            Node::This => Access::This,
-            _ => unreachable!(),
+        }
+        None
+    }
+
+    /// Gets the access to the reference that `node` evaluates to, if it is one.
+    #[inline]
+    fn compile_access<'a>(&mut self, node: &'a Node) -> Option<Access<'a>> {
+        match node {
+            Node::Identifier(name) => Some(Access::Variable { name: name.sym() }),
+            Node::GetConstField(node) => Some(Access::ByName { node }),
+            Node::GetField(node) => Some(Access::ByValue { node }),
+            Node::This => Some(Access::This),
+            _ => None,
         }
     }
 
     #[inline]
     fn access_get(&mut self, access: Access<'_>, use_expr: bool) {
         match access {
-            Access::Variable { index: name } => {
-                self.emit(Opcode::GetName, &[name]);
+            Access::Variable { name } => {
+                if let Some((depth, slot)) = self.resolve(name) {
+                    self.emit(Opcode::GetLocal, &[depth, slot]);
+                } else {
+                    let index = self.get_or_insert_name(name);
+                    self.emit(Opcode::GetName, &[index]);
+                }
             }
             Access::ByName { node } => {
-                let index = self.get_or_insert_name(node.field());
                 self.compile_expr(node.obj(), true);
-                self.emit(Opcode::GetPropertyByName, &[index]);
+                self.emit_property_access(Opcode::GetPropertyByName, node.field());
             }
             Access::ByValue { node } => {
                 self.compile_expr(node.field(), true);
@@ -316,25 +909,117 @@ impl ByteCompiler {
         }
 
         match access {
-            Access::Variable { index } => {
-                self.emit(Opcode::SetName, &[index]);
+            Access::Variable { name } => {
+                if let Some((depth, slot)) = self.resolve(name) {
+                    self.emit(Opcode::SetLocal, &[depth, slot]);
+                } else {
+                    let index = self.get_or_insert_name(name);
+                    self.emit(Opcode::SetName, &[index]);
+                }
             }
             Access::ByName { node } => {
                 self.compile_expr(node.obj(), true);
-                let index = self.get_or_insert_name(node.field());
-                self.emit(Opcode::SetPropertyByName, &[index]);
+                self.emit_property_access(Opcode::SetPropertyByName, node.field());
             }
             Access::ByValue { node } => {
                 self.compile_expr(node.field(), true);
                 self.compile_expr(node.obj(), true);
                 self.emit(Opcode::SetPropertyByValue, &[]);
             }
-            Access::This => todo!("access_set 'this'"),
+            // Assigning to `this` is an early error, so the parser never lets one through.
+            Access::This => self.emit(Opcode::Pop, &[]),
+        }
+    }
+
+    /// Evaluates the parts of the reference of `access` that have to be evaluated only once when
+    /// the reference is both read and written, and leaves them on the stack.
+    ///
+    /// Stack: **=>** object (for `ByName`), key, object (for `ByValue`)
+    #[inline]
+    fn access_reference(&mut self, access: Access<'_>) {
+        match access {
+            Access::Variable { .. } | Access::This => {}
+            Access::ByName { node } => self.compile_expr(node.obj(), true),
+            Access::ByValue { node } => {
+                self.compile_expr(node.obj(), true);
+                self.compile_expr(node.field(), true);
+                self.emit(Opcode::Swap, &[]);
+            }
+        }
+    }
+
+    /// Gets the value of a reference evaluated by [`Self::access_reference`], keeping the
+    /// reference on the stack.
+    #[inline]
+    fn access_reference_get(&mut self, access: Access<'_>) {
+        match access {
+            Access::Variable { .. } | Access::This => self.access_get(access, true),
+            Access::ByName { node } => {
+                self.emit(Opcode::Dup, &[]);
+                self.emit_property_access(Opcode::GetPropertyByName, node.field());
+            }
+            Access::ByValue { .. } => {
+                self.emit(Opcode::Dup2, &[]);
+                self.emit(Opcode::GetPropertyByValue, &[]);
+            }
+        }
+    }
+
+    /// Sets a reference evaluated by [`Self::access_reference`] to the value on top of the stack,
+    /// consuming the reference and leaving the value if `use_expr` is `true`.
+    #[inline]
+    fn access_reference_set(&mut self, access: Access<'_>, use_expr: bool) {
+        match access {
+            Access::Variable { .. } | Access::This => self.access_set(access, None, use_expr),
+            Access::ByName { node } => {
+                if use_expr {
+                    self.emit(Opcode::Dup, &[]);
+                    self.emit_rotate(Opcode::RotateLeft, 3);
+                } else {
+                    self.emit(Opcode::Swap, &[]);
+                }
+                self.emit_property_access(Opcode::SetPropertyByName, node.field());
+            }
+            Access::ByValue { .. } => {
+                if use_expr {
+                    self.emit(Opcode::Dup, &[]);
+                    self.emit_rotate(Opcode::RotateRight, 4);
+                }
+                self.emit_rotate(Opcode::RotateRight, 3);
+                self.emit(Opcode::SetPropertyByValue, &[]);
+            }
+        }
+    }
+
+    /// Drops a reference evaluated by [`Self::access_reference`] from under the value on top of
+    /// the stack.
+    #[inline]
+    fn access_reference_drop(&mut self, access: Access<'_>) {
+        let size = access.reference_size();
+        if size > 0 {
+            self.emit_rotate(Opcode::RotateRight, size + 1);
+            for _ in 0..size {
+                self.emit(Opcode::Pop, &[]);
+            }
         }
     }
 
     #[inline]
     pub fn compile_statement_list(&mut self, list: &StatementList, use_expr: bool) {
+        if self.top_level {
+            self.top_level = false;
+
+            // Hoist the `var` and function declarations of the script, which are not resolved to
+            // slots.
+            let mut var_declarations = VarScopedDeclarations::default();
+            var_declarations.visit_statement_list(list);
+            for name in var_declarations.names {
+                let index = self.get_or_insert_name(name);
+                self.emit(Opcode::DefVar, &[index]);
+            }
+        }
+
+        self.side_tables.push(Rc::clone(list.side_table()));
         for (i, node) in list.items().iter().enumerate() {
             if i + 1 == list.items().len() {
                 self.compile_stmt(node, use_expr);
@@ -343,14 +1028,104 @@ impl ByteCompiler {
 
             self.compile_stmt(node, false);
         }
+        self.side_tables.pop();
+    }
+
+    /// Compiles the arguments of a call, followed by the `call` opcode if they are pushed on the
+    /// stack, or the `spread` opcode if they have to be collected in an array.
+    fn compile_arguments(&mut self, args: &[Node], call: Opcode, spread: Opcode) {
+        if args.iter().any(|arg| matches!(arg, Node::Spread(_))) {
+            self.compile_array_elements(args);
+            self.emit(spread, &[]);
+        } else {
+            for arg in args {
+                self.compile_expr(arg, true);
+            }
+            self.emit(call, &[args.len() as u32]);
+        }
+    }
+
+    /// Compiles the elements of an array literal, or the arguments of a call with spread
+    /// arguments, into an array.
+    fn compile_array_elements(&mut self, elements: &[Node]) {
+        if !elements
+            .iter()
+            .any(|element| matches!(element, Node::Spread(_)))
+        {
+            for element in elements {
+                self.compile_expr(element, true);
+            }
+            self.emit(Opcode::PushNewArray, &[elements.len() as u32]);
+            return;
+        }
+
+        self.emit(Opcode::PushNewArray, &[0]);
+        for element in elements {
+            if let Node::Spread(spread) = element {
+                self.compile_expr(spread.val(), true);
+                self.emit(Opcode::PushIteratorToArray, &[]);
+            } else {
+                self.compile_expr(element, true);
+                self.emit(Opcode::PushValueToArray, &[]);
+            }
+        }
+    }
+
+    /// Compiles the callee of a call, leaving the `this` value and the function on the stack.
+    fn compile_callee(&mut self, callee: &Node) {
+        match callee {
+            Node::GetConstField(field) => {
+                self.compile_expr(field.obj(), true);
+                self.emit(Opcode::Dup, &[]);
+                self.emit_property_access(Opcode::GetPropertyByName, field.field());
+            }
+            Node::GetField(field) => {
+                self.compile_expr(field.obj(), true);
+                self.emit(Opcode::Dup, &[]);
+                self.compile_expr(field.field(), true);
+                self.emit(Opcode::Swap, &[]);
+                self.emit(Opcode::GetPropertyByValue, &[]);
+            }
+            expr => {
+                self.emit(Opcode::This, &[]);
+                self.compile_expr(expr, true);
+            }
+        }
+    }
+
+    /// Compiles the key of a property definition, and returns the name of the property if it is
+    /// not computed.
+    fn compile_property_name(&mut self, name: &PropertyName) -> Option<u32> {
+        match name {
+            PropertyName::Literal(name) => Some(self.get_or_insert_name(*name)),
+            PropertyName::Computed(node) => {
+                self.compile_expr(node, true);
+                None
+            }
+        }
+    }
+
+    /// Emits the definition of a property of the object literal under the key (if computed) and
+    /// the value on the stack.
+    fn emit_property_definition(&mut self, name: Option<u32>, by_name: Opcode, by_value: Opcode) {
+        match name {
+            Some(index) => self.emit(by_name, &[index]),
+            None => self.emit(by_value, &[]),
+        }
     }
 
     #[inline]
     pub fn compile_expr(&mut self, expr: &Node, use_expr: bool) {
+        let previous = self.enter(expr);
+        self.compile_expr_node(expr, use_expr);
+        self.leave(previous);
+    }
+
+    fn compile_expr_node(&mut self, expr: &Node, use_expr: bool) {
         match expr {
             Node::Const(c) => {
                 match c {
-                    Const::String(v) => self.emit_push_literal(Literal::String(v.as_ref().into())),
+                    Const::String(v) => self.emit_push_literal(Literal::String(v.clone())),
                     Const::Int(v) => self.emit_push_integer(*v),
                     Const::Num(v) => self.emit_push_rational(*v),
                     Const::BigInt(v) => self.emit_push_literal(Literal::BigInt(v.clone())),
@@ -366,10 +1141,44 @@ impl ByteCompiler {
             }
             Node::UnaryOp(unary) => {
                 let opcode = match unary.op() {
-                    UnaryOp::IncrementPre => todo!(),
-                    UnaryOp::DecrementPre => todo!(),
-                    UnaryOp::IncrementPost => todo!(),
-                    UnaryOp::DecrementPost => todo!(),
+                    op @ (UnaryOp::IncrementPre
+                    | UnaryOp::DecrementPre
+                    | UnaryOp::IncrementPost
+                    | UnaryOp::DecrementPost) => {
+                        let opcode = match op {
+                            UnaryOp::IncrementPre | UnaryOp::IncrementPost => Opcode::Inc,
+                            _ => Opcode::Dec,
+                        };
+                        let postfix = matches!(op, UnaryOp::IncrementPost | UnaryOp::DecrementPost);
+
+                        if let Some(access) = self.compile_access(unary.target()) {
+                            self.access_reference(access);
+                            self.access_reference_get(access);
+                            if postfix && use_expr {
+                                // Keep the old value under the reference, as the result.
+                                self.emit(Opcode::ToNumeric, &[]);
+                                self.emit(Opcode::Dup, &[]);
+                                if access.reference_size() > 0 {
+                                    self.emit_rotate(
+                                        Opcode::RotateRight,
+                                        access.reference_size() + 2,
+                                    );
+                                }
+                                self.emit(opcode, &[]);
+                                self.access_reference_set(access, false);
+                            } else {
+                                self.emit(opcode, &[]);
+                                self.access_reference_set(access, use_expr);
+                            }
+                        } else {
+                            self.compile_expr(unary.target(), true);
+                            self.emit(opcode, &[]);
+                            if !use_expr {
+                                self.emit(Opcode::Pop, &[]);
+                            }
+                        }
+                        return;
+                    }
                     UnaryOp::Delete => match unary.target() {
                         Node::GetConstField(ref get_const_field) => {
                             let index = self.get_or_insert_name(get_const_field.field());
@@ -397,7 +1206,18 @@ impl ByteCompiler {
                     UnaryOp::Plus => Some(Opcode::Pos),
                     UnaryOp::Not => Some(Opcode::LogicalNot),
                     UnaryOp::Tilde => Some(Opcode::BitNot),
-                    UnaryOp::TypeOf => Some(Opcode::TypeOf),
+                    UnaryOp::TypeOf => {
+                        // `typeof` does not throw for unresolvable references.
+                        match unary.target() {
+                            Node::Identifier(name) if self.resolve(name.sym()).is_none() => {
+                                let index = self.get_or_insert_name(name.sym());
+                                self.emit(Opcode::GetNameOrUndefined, &[index]);
+                                self.emit(Opcode::TypeOf, &[]);
+                                None
+                            }
+                            _ => Some(Opcode::TypeOf),
+                        }
+                    }
                     UnaryOp::Void => Some(Opcode::Void),
                 };
 
@@ -410,145 +1230,186 @@ impl ByteCompiler {
                     self.emit(Opcode::Pop, &[]);
                 }
             }
-            Node::BinOp(binary) => {
-                self.compile_expr(binary.lhs(), true);
-                match binary.op() {
-                    BinOp::Num(op) => {
-                        self.compile_expr(binary.rhs(), true);
-                        match op {
-                            NumOp::Add => self.emit_opcode(Opcode::Add),
-                            NumOp::Sub => self.emit_opcode(Opcode::Sub),
-                            NumOp::Div => self.emit_opcode(Opcode::Div),
-                            NumOp::Mul => self.emit_opcode(Opcode::Mul),
-                            NumOp::Exp => self.emit_opcode(Opcode::Pow),
-                            NumOp::Mod => self.emit_opcode(Opcode::Mod),
-                        }
+            Node::BinOp(binary) => match binary.op() {
+                BinOp::Num(op) => {
+                    self.compile_expr(binary.lhs(), true);
+                    self.compile_expr(binary.rhs(), true);
+                    match op {
+                        NumOp::Add => self.emit_opcode(Opcode::Add),
+                        NumOp::Sub => self.emit_opcode(Opcode::Sub),
+                        NumOp::Div => self.emit_opcode(Opcode::Div),
+                        NumOp::Mul => self.emit_opcode(Opcode::Mul),
+                        NumOp::Exp => self.emit_opcode(Opcode::Pow),
+                        NumOp::Mod => self.emit_opcode(Opcode::Mod),
+                    }
 
-                        if !use_expr {
-                            self.emit(Opcode::Pop, &[]);
-                        }
+                    if !use_expr {
+                        self.emit(Opcode::Pop, &[]);
                     }
-                    BinOp::Bit(op) => {
-                        self.compile_expr(binary.rhs(), true);
-                        match op {
-                            BitOp::And => self.emit_opcode(Opcode::BitAnd),
-                            BitOp::Or => self.emit_opcode(Opcode::BitOr),
-                            BitOp::Xor => self.emit_opcode(Opcode::BitXor),
-                            BitOp::Shl => self.emit_opcode(Opcode::ShiftLeft),
-                            BitOp::Shr => self.emit_opcode(Opcode::ShiftRight),
-                            BitOp::UShr => self.emit_opcode(Opcode::UnsignedShiftRight),
-                        }
-
-                        if !use_expr {
-                            self.emit(Opcode::Pop, &[]);
-                        }
+                }
+                BinOp::Bit(op) => {
+                    self.compile_expr(binary.lhs(), true);
+                    self.compile_expr(binary.rhs(), true);
+                    match op {
+                        BitOp::And => self.emit_opcode(Opcode::BitAnd),
+                        BitOp::Or => self.emit_opcode(Opcode::BitOr),
+                        BitOp::Xor => self.emit_opcode(Opcode::BitXor),
+                        BitOp::Shl => self.emit_opcode(Opcode::ShiftLeft),
+                        BitOp::Shr => self.emit_opcode(Opcode::ShiftRight),
+                        BitOp::UShr => self.emit_opcode(Opcode::UnsignedShiftRight),
                     }
-                    BinOp::Comp(op) => {
-                        self.compile_expr(binary.rhs(), true);
-                        match op {
-                            CompOp::Equal => self.emit_opcode(Opcode::Eq),
-                            CompOp::NotEqual => self.emit_opcode(Opcode::NotEq),
-                            CompOp::StrictEqual => self.emit_opcode(Opcode::StrictEq),
-                            CompOp::StrictNotEqual => self.emit_opcode(Opcode::StrictNotEq),
-                            CompOp::GreaterThan => self.emit_opcode(Opcode::GreaterThan),
-                            CompOp::GreaterThanOrEqual => self.emit_opcode(Opcode::GreaterThanOrEq),
-                            CompOp::LessThan => self.emit_opcode(Opcode::LessThan),
-                            CompOp::LessThanOrEqual => self.emit_opcode(Opcode::LessThanOrEq),
-                            CompOp::In => self.emit_opcode(Opcode::In),
-                            CompOp::InstanceOf => self.emit_opcode(Opcode::InstanceOf),
-                        }
 
-                        if !use_expr {
-                            self.emit(Opcode::Pop, &[]);
-                        }
+                    if !use_expr {
+                        self.emit(Opcode::Pop, &[]);
                     }
-                    BinOp::Log(op) => {
-                        match op {
-                            LogOp::And => {
-                                let exit = self.jump_with_custom_opcode(Opcode::LogicalAnd);
-                                self.compile_expr(binary.rhs(), true);
-                                self.emit(Opcode::ToBoolean, &[]);
-                                self.patch_jump(exit);
-                            }
-                            LogOp::Or => {
-                                let exit = self.jump_with_custom_opcode(Opcode::LogicalOr);
-                                self.compile_expr(binary.rhs(), true);
-                                self.emit(Opcode::ToBoolean, &[]);
-                                self.patch_jump(exit);
-                            }
-                            LogOp::Coalesce => {
-                                let exit = self.jump_with_custom_opcode(Opcode::Coalesce);
-                                self.compile_expr(binary.rhs(), true);
-                                self.patch_jump(exit);
-                            }
-                        };
-
-                        if !use_expr {
-                            self.emit(Opcode::Pop, &[]);
-                        }
+                }
+                BinOp::Comp(op) => {
+                    self.compile_expr(binary.lhs(), true);
+                    self.compile_expr(binary.rhs(), true);
+                    match op {
+                        CompOp::Equal => self.emit_opcode(Opcode::Eq),
+                        CompOp::NotEqual => self.emit_opcode(Opcode::NotEq),
+                        CompOp::StrictEqual => self.emit_opcode(Opcode::StrictEq),
+                        CompOp::StrictNotEqual => self.emit_opcode(Opcode::StrictNotEq),
+                        CompOp::GreaterThan => self.emit_opcode(Opcode::GreaterThan),
+                        CompOp::GreaterThanOrEqual => self.emit_opcode(Opcode::GreaterThanOrEq),
+                        CompOp::LessThan => self.emit_opcode(Opcode::LessThan),
+                        CompOp::LessThanOrEqual => self.emit_opcode(Opcode::LessThanOrEq),
+                        CompOp::In => self.emit_opcode(Opcode::In),
+                        CompOp::InstanceOf => self.emit_opcode(Opcode::InstanceOf),
                     }
-                    BinOp::Assign(op) => {
-                        let opcode = match op {
-                            AssignOp::Add => Some(Opcode::Add),
-                            AssignOp::Sub => Some(Opcode::Sub),
-                            AssignOp::Mul => Some(Opcode::Mul),
-                            AssignOp::Div => Some(Opcode::Div),
-                            AssignOp::Mod => Some(Opcode::Mod),
-                            AssignOp::Exp => Some(Opcode::Pow),
-                            AssignOp::And => Some(Opcode::BitAnd),
-                            AssignOp::Or => Some(Opcode::BitOr),
-                            AssignOp::Xor => Some(Opcode::BitXor),
-                            AssignOp::Shl => Some(Opcode::ShiftLeft),
-                            AssignOp::Shr => Some(Opcode::ShiftRight),
-                            AssignOp::Ushr => Some(Opcode::UnsignedShiftRight),
-                            AssignOp::BoolAnd => {
-                                let exit = self.jump_with_custom_opcode(Opcode::LogicalAnd);
-                                self.compile_expr(binary.rhs(), true);
-                                self.emit(Opcode::ToBoolean, &[]);
-                                self.patch_jump(exit);
-
-                                None
-                            }
-                            AssignOp::BoolOr => {
-                                let exit = self.jump_with_custom_opcode(Opcode::LogicalOr);
-                                self.compile_expr(binary.rhs(), true);
-                                self.emit(Opcode::ToBoolean, &[]);
-                                self.patch_jump(exit);
-
-                                None
-                            }
-                            AssignOp::Coalesce => {
-                                let exit = self.jump_with_custom_opcode(Opcode::Coalesce);
-                                self.compile_expr(binary.rhs(), true);
-                                self.patch_jump(exit);
-
-                                None
-                            }
-                        };
 
-                        if let Some(opcode) = opcode {
-                            self.compile_expr(binary.rhs(), true);
-                            self.emit(opcode, &[]);
-                        }
-
-                        let access = self.compile_access(binary.lhs());
-                        self.access_set(access, None, use_expr);
+                    if !use_expr {
+                        self.emit(Opcode::Pop, &[]);
                     }
-                    BinOp::Comma => {
+                }
+                BinOp::Log(op) => {
+                    self.compile_expr(binary.lhs(), true);
+                    let opcode = match op {
+                        LogOp::And => Opcode::LogicalAnd,
+                        LogOp::Or => Opcode::LogicalOr,
+                        LogOp::Coalesce => Opcode::Coalesce,
+                    };
+                    let exit = self.jump_with_custom_opcode(opcode);
+                    self.compile_expr(binary.rhs(), true);
+                    self.patch_jump(exit);
+
+                    if !use_expr {
                         self.emit(Opcode::Pop, &[]);
+                    }
+                }
+                BinOp::Assign(op) => {
+                    let opcode = match op {
+                        AssignOp::Add => Opcode::Add,
+                        AssignOp::Sub => Opcode::Sub,
+                        AssignOp::Mul => Opcode::Mul,
+                        AssignOp::Div => Opcode::Div,
+                        AssignOp::Mod => Opcode::Mod,
+                        AssignOp::Exp => Opcode::Pow,
+                        AssignOp::And => Opcode::BitAnd,
+                        AssignOp::Or => Opcode::BitOr,
+                        AssignOp::Xor => Opcode::BitXor,
+                        AssignOp::Shl => Opcode::ShiftLeft,
+                        AssignOp::Shr => Opcode::ShiftRight,
+                        AssignOp::Ushr => Opcode::UnsignedShiftRight,
+                        AssignOp::BoolAnd => Opcode::LogicalAnd,
+                        AssignOp::BoolOr => Opcode::LogicalOr,
+                        AssignOp::Coalesce => Opcode::Coalesce,
+                    };
+
+                    let access = if let Some(access) = self.compile_access(binary.lhs()) {
+                        access
+                    } else {
+                        self.compile_expr(binary.lhs(), false);
+                        self.compile_expr(binary.rhs(), use_expr);
+                        return;
+                    };
+                    self.access_reference(access);
+                    self.access_reference_get(access);
+
+                    if let Opcode::LogicalAnd | Opcode::LogicalOr | Opcode::Coalesce = opcode {
+                        // Only assign if the operator does not short-circuit.
+                        let short_circuit = self.jump_with_custom_opcode(opcode);
                         self.compile_expr(binary.rhs(), true);
+                        self.access_reference_set(access, use_expr);
+                        let exit = self.jump();
 
+                        self.patch_jump(short_circuit);
+                        self.access_reference_drop(access);
                         if !use_expr {
                             self.emit(Opcode::Pop, &[]);
                         }
+                        self.patch_jump(exit);
+                    } else {
+                        self.compile_expr(binary.rhs(), true);
+                        self.emit(opcode, &[]);
+                        self.access_reference_set(access, use_expr);
                     }
                 }
-            }
+                BinOp::Comma => {
+                    self.compile_expr(binary.lhs(), false);
+                    self.compile_expr(binary.rhs(), use_expr);
+                }
+            },
             Node::Object(object) => {
-                if object.properties().is_empty() {
-                    self.emit(Opcode::PushEmptyObject, &[]);
-                } else {
-                    todo!("object literal with properties");
+                self.emit(Opcode::PushEmptyObject, &[]);
+
+                for property in object.properties() {
+                    match property {
+                        PropertyDefinition::IdentifierReference(name) => {
+                            self.emit(Opcode::Dup, &[]);
+                            self.access_get(Access::Variable { name: *name }, true);
+                            let index = self.get_or_insert_name(*name);
+                            self.emit(Opcode::DefineOwnPropertyByName, &[index]);
+                        }
+                        PropertyDefinition::Property(name, value) => {
+                            self.emit(Opcode::Dup, &[]);
+                            let name = self.compile_property_name(name);
+                            self.compile_expr(value, true);
+                            self.emit_property_definition(
+                                name,
+                                Opcode::DefineOwnPropertyByName,
+                                Opcode::DefineOwnPropertyByValue,
+                            );
+                        }
+                        PropertyDefinition::MethodDefinition(kind, name, function) => {
+                            self.emit(Opcode::Dup, &[]);
+                            let name = self.compile_property_name(name);
+                            let (by_name, by_value) = match kind {
+                                MethodDefinitionKind::Get => (
+                                    Opcode::SetPropertyGetterByName,
+                                    Opcode::SetPropertyGetterByValue,
+                                ),
+                                MethodDefinitionKind::Set => (
+                                    Opcode::SetPropertySetterByName,
+                                    Opcode::SetPropertySetterByValue,
+                                ),
+                                MethodDefinitionKind::Ordinary
+                                | MethodDefinitionKind::Generator => (
+                                    Opcode::DefineOwnPropertyByName,
+                                    Opcode::DefineOwnPropertyByValue,
+                                ),
+                            };
+                            let flavor = if *kind == MethodDefinitionKind::Generator {
+                                FunctionFlavor::Generator
+                            } else {
+                                FunctionFlavor::Normal
+                            };
+                            self.compile_function(
+                                FunctionKind::Expression,
+                                flavor,
+                                function.name(),
+                                function.parameters(),
+                                function.body(),
+                                true,
+                            );
+                            self.emit_property_definition(name, by_name, by_value);
+                        }
+                        PropertyDefinition::SpreadObject(source) => {
+                            self.compile_expr(source, true);
+                            self.emit(Opcode::CopyDataProperties, &[0]);
+                        }
+                    }
                 }
 
                 if !use_expr {
@@ -556,14 +1417,13 @@ impl ByteCompiler {
                 }
             }
             Node::Identifier(name) => {
-                let index = self.get_or_insert_name(name.as_ref());
-                let access = Access::Variable { index };
+                let access = Access::Variable { name: name.sym() };
                 self.access_get(access, use_expr);
             }
-            Node::Assign(assign) => {
-                let access = self.compile_access(assign.lhs());
-                self.access_set(access, Some(assign.rhs()), use_expr);
-            }
+            Node::Assign(assign) => match self.compile_access(assign.lhs()) {
+                Some(access) => self.access_set(access, Some(assign.rhs()), use_expr),
+                None => self.compile_expr(assign.rhs(), use_expr),
+            },
             Node::GetConstField(node) => {
                 let access = Access::ByName { node };
                 self.access_get(access, use_expr);
@@ -586,16 +1446,7 @@ impl ByteCompiler {
                 }
             }
             Node::ArrayDecl(array) => {
-                let mut count = 0;
-                for element in array.as_ref().iter().rev() {
-                    if let Node::Spread(_) = element {
-                        todo!("array with spread element");
-                    } else {
-                        self.compile_expr(element, true);
-                    }
-                    count += 1;
-                }
-                self.emit(Opcode::PushNewArray, &[count]);
+                self.compile_array_elements(array.as_ref());
 
                 if !use_expr {
                     self.emit(Opcode::Pop, &[]);
@@ -607,115 +1458,382 @@ impl ByteCompiler {
             Node::FunctionExpr(_function) => self.function(expr, use_expr),
             Node::ArrowFunctionDecl(_function) => self.function(expr, use_expr),
             Node::Call(call) => {
-                for arg in call.args().iter().rev() {
-                    self.compile_expr(arg, true);
-                }
-                match call.expr() {
-                    Node::GetConstField(field) => {
-                        self.compile_expr(field.obj(), true);
-                        self.emit(Opcode::Dup, &[]);
-                        let index = self.get_or_insert_name(field.field());
-                        self.emit(Opcode::GetPropertyByName, &[index]);
-                    }
-                    Node::GetField(field) => {
-                        self.compile_expr(field.obj(), true);
-                        self.emit(Opcode::Dup, &[]);
-                        self.compile_expr(field.field(), true);
-                        self.emit(Opcode::Swap, &[]);
-                        self.emit(Opcode::GetPropertyByValue, &[]);
+                self.compile_callee(call.expr());
+                self.compile_arguments(call.args(), Opcode::Call, Opcode::CallSpread);
+
+                if !use_expr {
+                    self.emit(Opcode::Pop, &[]);
+                }
+            }
+            Node::New(new) => {
+                self.compile_expr(new.expr(), true);
+                self.compile_arguments(new.args(), Opcode::New, Opcode::NewSpread);
+
+                if !use_expr {
+                    self.emit(Opcode::Pop, &[]);
+                }
+            }
+            Node::TemplateLit(template) => {
+                for element in template.elements() {
+                    match element {
+                        TemplateElement::String(s) => {
+                            self.emit_push_literal(Literal::String(s.clone()))
+                        }
+                        TemplateElement::Expr(expr) => self.compile_expr(expr, true),
                     }
-                    expr => {
-                        self.emit(Opcode::This, &[]);
-                        self.compile_expr(expr, true);
+                }
+                self.emit(Opcode::ConcatToString, &[template.elements().len() as u32]);
+
+                if !use_expr {
+                    self.emit(Opcode::Pop, &[]);
+                }
+            }
+            Node::TaggedTemplate(template) => {
+                self.compile_callee(template.tag());
+
+                for cooked in template.cookeds() {
+                    match cooked {
+                        Some(cooked) => self.emit_push_literal(Literal::String(cooked.clone())),
+                        None => self.emit(Opcode::PushUndefined, &[]),
                     }
                 }
-                self.emit(Opcode::Call, &[call.args().len() as u32]);
+                self.emit(Opcode::PushNewArray, &[template.cookeds().len() as u32]);
+
+                self.emit(Opcode::Dup, &[]);
+                for raw in template.raws() {
+                    self.emit_push_literal(Literal::String(raw.as_ref().into()));
+                }
+                self.emit(Opcode::PushNewArray, &[template.raws().len() as u32]);
+                let index = self.get_or_insert_name(Sym::intern("raw"));
+                self.emit(Opcode::DefineOwnPropertyByName, &[index]);
+
+                for expr in template.exprs() {
+                    self.compile_expr(expr, true);
+                }
+                self.emit(Opcode::Call, &[template.exprs().len() as u32 + 1]);
+
+                if !use_expr {
+                    self.emit(Opcode::Pop, &[]);
+                }
+            }
+            Node::Spread(spread) => self.compile_expr(spread.val(), use_expr),
+            Node::AsyncFunctionExpr(_) | Node::GeneratorExpr(_) => self.function(expr, use_expr),
+            Node::Yield(node) => {
+                if let Some(expr) = node.expr() {
+                    self.compile_expr(expr, true);
+                } else {
+                    self.emit(Opcode::PushUndefined, &[]);
+                }
+                if node.delegate() {
+                    self.emit_yield_delegate();
+                } else {
+                    self.emit_yield();
+                }
+
+                if !use_expr {
+                    self.emit(Opcode::Pop, &[]);
+                }
+            }
+            Node::AwaitExpr(expr) => {
+                self.compile_expr(expr.expr(), true);
+                self.emit(Opcode::Await, &[]);
+                // Async functions are never resumed with a return, so there is no return path.
+                let next = self.jump_with_custom_opcode(Opcode::GeneratorNext);
+                self.emit_u32(self.stack_depth);
+                self.patch_jump(next);
 
                 if !use_expr {
                     self.emit(Opcode::Pop, &[]);
                 }
             }
-            expr => todo!("TODO compile: {}", expr),
+            Node::Block(_)
+            | Node::If(_)
+            | Node::WhileLoop(_)
+            | Node::DoWhileLoop(_)
+            | Node::ForLoop(_)
+            | Node::ForInLoop(_)
+            | Node::ForOfLoop(_)
+            | Node::Switch(_)
+            | Node::Try(_)
+            | Node::VarDeclList(_)
+            | Node::LetDeclList(_)
+            | Node::ConstDeclList(_)
+            | Node::Return(_)
+            | Node::Throw(_)
+            | Node::Break(_)
+            | Node::Continue(_)
+            | Node::FunctionDecl(_)
+            | Node::AsyncFunctionDecl(_)
+            | Node::GeneratorDecl(_)
+            | Node::Empty => self.compile_stmt(expr, use_expr),
         }
     }
 
-    #[inline]
-    pub fn compile_stmt(&mut self, node: &Node, use_expr: bool) {
-        match node {
-            Node::VarDeclList(list) => {
-                for decl in list.as_ref() {
-                    match decl {
-                        Declaration::Identifier { ident, .. } => {
-                            let index = self.get_or_insert_name(ident.as_ref());
-                            self.emit(Opcode::DefVar, &[index]);
+    /// Binds `name` to the value on top of the stack, consuming it.
+    fn emit_binding(&mut self, kind: BindingKind, name: Sym) {
+        if let Some((depth, slot)) = self.resolve(name) {
+            match kind {
+                BindingKind::Var => self.emit(Opcode::SetLocal, &[depth, slot]),
+                BindingKind::Let | BindingKind::Const => {
+                    debug_assert_eq!(depth, 0, "lexical bindings belong to the innermost scope");
+                    self.emit(Opcode::InitLocal, &[slot]);
+                }
+            }
+            return;
+        }
 
-                            if let Some(expr) = decl.init() {
-                                self.compile_expr(expr, true);
-                                self.emit(Opcode::InitLexical, &[index]);
-                            };
+        let index = self.get_or_insert_name(name);
+        match kind {
+            BindingKind::Var => self.emit(Opcode::SetName, &[index]),
+            BindingKind::Let | BindingKind::Const => {
+                let opcode = if kind == BindingKind::Let {
+                    Opcode::DefLet
+                } else {
+                    Opcode::DefConst
+                };
+                self.emit(opcode, &[index]);
+                self.emit(Opcode::InitLexical, &[index]);
+            }
+        }
+    }
+
+    /// Replaces the value on top of the stack with the value of `init`, if it is `undefined`.
+    fn emit_default_value(&mut self, init: &Node) {
+        let skip = self.jump_with_custom_opcode(Opcode::JumpIfNotUndefined);
+        self.emit(Opcode::Pop, &[]);
+        self.compile_expr(init, true);
+        self.patch_jump(skip);
+    }
+
+    /// Compiles the declaration of `name`, with an optional initializer.
+    fn compile_declaration(&mut self, kind: BindingKind, name: Sym, init: Option<&Node>) {
+        match init {
+            Some(expr) => self.compile_expr(expr, true),
+            // `var` declarations are hoisted, and initialized to `undefined` when their scope is
+            // entered.
+            None if kind == BindingKind::Var => return,
+            None => self.emit(Opcode::PushUndefined, &[]),
+        }
+        self.emit_binding(kind, name);
+    }
+
+    /// Destructures the value on top of the stack into the bindings of `pattern`, consuming it.
+    fn compile_pattern(&mut self, pattern: &DeclarationPattern, kind: BindingKind) {
+        self.emit(Opcode::ValueNotNullOrUndefined, &[]);
+
+        match pattern {
+            DeclarationPattern::Object(pattern) => {
+                for binding in pattern.bindings() {
+                    match binding {
+                        BindingPatternTypeObject::Empty => {}
+                        BindingPatternTypeObject::SingleName {
+                            ident,
+                            property_name,
+                            default_init,
+                        } => {
+                            self.emit(Opcode::Dup, &[]);
+                            self.emit_property_access(Opcode::GetPropertyByName, *property_name);
+                            if let Some(init) = default_init {
+                                self.emit_default_value(init);
+                            }
+                            self.emit_binding(kind, *ident);
                         }
-                        Declaration::Pattern(pattern) => {
-                            for ident in pattern.idents() {
-                                let index = self.get_or_insert_name(ident);
-                                self.emit(Opcode::DefVar, &[index]);
-
-                                if let Some(expr) = decl.init() {
-                                    self.compile_expr(expr, true);
-                                    self.emit(Opcode::InitLexical, &[index]);
-                                };
+                        BindingPatternTypeObject::RestProperty {
+                            ident,
+                            excluded_keys,
+                        } => {
+                            self.emit(Opcode::Dup, &[]);
+                            self.emit(Opcode::PushEmptyObject, &[]);
+                            self.emit(Opcode::Swap, &[]);
+                            for key in excluded_keys {
+                                self.emit_push_literal(Literal::String((*key).into()));
+                            }
+                            self.emit(Opcode::CopyDataProperties, &[excluded_keys.len() as u32]);
+                            self.emit_binding(kind, *ident);
+                        }
+                        BindingPatternTypeObject::BindingPattern {
+                            ident,
+                            pattern,
+                            default_init,
+                        } => {
+                            self.emit(Opcode::Dup, &[]);
+                            self.emit_property_access(Opcode::GetPropertyByName, *ident);
+                            if let Some(init) = default_init {
+                                self.emit_default_value(init);
                             }
+                            self.compile_pattern(pattern, kind);
                         }
                     }
                 }
-            }
-            Node::LetDeclList(list) => {
-                for decl in list.as_ref() {
-                    match decl {
-                        Declaration::Identifier { ident, .. } => {
-                            let index = self.get_or_insert_name(ident.as_ref());
-                            self.emit(Opcode::DefLet, &[index]);
 
-                            if let Some(expr) = decl.init() {
-                                self.compile_expr(expr, true);
-                                self.emit(Opcode::InitLexical, &[index]);
-                            };
+                self.emit(Opcode::Pop, &[]);
+            }
+            DeclarationPattern::Array(pattern) => {
+                self.emit(Opcode::InitIterator, &[]);
+
+                for binding in pattern.bindings() {
+                    match binding {
+                        BindingPatternTypeArray::Empty => {}
+                        BindingPatternTypeArray::Elision => {
+                            self.emit(Opcode::IteratorNext, &[]);
+                            self.emit(Opcode::Pop, &[]);
                         }
-                        Declaration::Pattern(pattern) => {
-                            for ident in pattern.idents() {
-                                let index = self.get_or_insert_name(ident);
-                                self.emit(Opcode::DefLet, &[index]);
-
-                                if let Some(expr) = decl.init() {
-                                    self.compile_expr(expr, true);
-                                    self.emit(Opcode::InitLexical, &[index]);
-                                };
+                        BindingPatternTypeArray::SingleName {
+                            ident,
+                            default_init,
+                        } => {
+                            self.emit(Opcode::IteratorNext, &[]);
+                            if let Some(init) = default_init {
+                                self.emit_default_value(init);
                             }
+                            self.emit_binding(kind, *ident);
+                        }
+                        BindingPatternTypeArray::BindingPattern { pattern } => {
+                            self.emit(Opcode::IteratorNext, &[]);
+                            self.compile_pattern(pattern, kind);
+                        }
+                        BindingPatternTypeArray::SingleNameRest { ident } => {
+                            self.emit(Opcode::IteratorToArray, &[]);
+                            self.emit_binding(kind, *ident);
+                        }
+                        BindingPatternTypeArray::BindingPatternRest { pattern } => {
+                            self.emit(Opcode::IteratorToArray, &[]);
+                            self.compile_pattern(pattern, kind);
                         }
                     }
                 }
+
+                self.emit(Opcode::Pop, &[]);
+                self.emit(Opcode::Pop, &[]);
             }
-            Node::ConstDeclList(list) => {
+        }
+    }
+
+    /// Compiles a block, in its own scope if it declares lexical bindings.
+    fn compile_block(&mut self, block: &Block, use_expr: bool) {
+        if block.label().is_some() {
+            let start_address = self.next_opcode_location();
+            self.push_jump_control_info(
+                JumpControlKind::LabelledBlock,
+                block.label(),
+                start_address,
+            );
+        }
+
+        let items = block.statement_list().items();
+        let scoped = self.push_block_scope(items);
+        self.compile_statement_list(block.statement_list(), use_expr);
+        if scoped {
+            self.pop_block_scope();
+        }
+
+        if block.label().is_some() {
+            let info = self.jump_info.pop().unwrap();
+
+            assert_eq!(info.kind, JumpControlKind::LabelledBlock);
+
+            for label in info.breaks {
+                self.patch_jump(label);
+            }
+        }
+    }
+
+    /// Binds the variable of a `for...in` or `for...of` loop to the value on top of the stack,
+    /// consuming it.
+    fn compile_for_each_binding(&mut self, variable: &Node) {
+        let (list, kind) = match variable {
+            Node::VarDeclList(list) => (list, BindingKind::Var),
+            Node::LetDeclList(list) => (list, BindingKind::Let),
+            Node::ConstDeclList(list) => (list, BindingKind::Const),
+            node => {
+                match self.compile_access(node) {
+                    Some(access) => self.access_set(access, None, false),
+                    None => self.emit(Opcode::Pop, &[]),
+                }
+                return;
+            }
+        };
+
+        match list.as_ref().first() {
+            Some(Declaration::Identifier { ident, .. }) => self.emit_binding(kind, ident.sym()),
+            Some(Declaration::Pattern(pattern)) => self.compile_pattern(pattern, kind),
+            None => self.emit(Opcode::Pop, &[]),
+        }
+    }
+
+    /// Compiles the loop of a `for...in` or `for...of` statement, with the iterator and its
+    /// `next` method on the stack.
+    fn compile_for_each(
+        &mut self,
+        kind: JumpControlKind,
+        label: Option<Sym>,
+        variable: &Node,
+        body: &Node,
+    ) {
+        // The iterator stays on the stack during the loop.
+        self.stack_depth += 2;
+        let start_address = self.next_opcode_location();
+        self.push_jump_control_info(kind, label, start_address);
+
+        let done = self.jump_with_custom_opcode(Opcode::IteratorStep);
+
+        // Lexical declarations get a fresh binding for each iteration.
+        let scoped = match variable {
+            Node::LetDeclList(_) | Node::ConstDeclList(_) => {
+                self.push_block_scope(std::iter::once(variable))
+            }
+            _ => false,
+        };
+        self.compile_for_each_binding(variable);
+        self.compile_stmt(body, false);
+        if scoped {
+            self.pop_block_scope();
+        }
+        self.emit(Opcode::Jump, &[start_address]);
+
+        self.patch_jump(done);
+        self.emit(Opcode::Pop, &[]);
+        self.emit(Opcode::Pop, &[]);
+        let exit = self.jump();
+
+        // The iterator is still on the stack when breaking out of the loop.
+        self.pop_loop_control_info();
+        self.stack_depth -= 2;
+        if kind == JumpControlKind::ForOfLoop {
+            self.emit(Opcode::IteratorClose, &[]);
+        } else {
+            self.emit(Opcode::Pop, &[]);
+            self.emit(Opcode::Pop, &[]);
+        }
+        self.patch_jump(exit);
+    }
+
+    #[inline]
+    pub fn compile_stmt(&mut self, node: &Node, use_expr: bool) {
+        let previous = self.enter(node);
+        self.compile_stmt_node(node, use_expr);
+        self.leave(previous);
+    }
+
+    fn compile_stmt_node(&mut self, node: &Node, use_expr: bool) {
+        match node {
+            Node::VarDeclList(list) | Node::LetDeclList(list) | Node::ConstDeclList(list) => {
+                let kind = match node {
+                    Node::VarDeclList(_) => BindingKind::Var,
+                    Node::LetDeclList(_) => BindingKind::Let,
+                    _ => BindingKind::Const,
+                };
                 for decl in list.as_ref() {
                     match decl {
-                        Declaration::Identifier { ident, .. } => {
-                            let index = self.get_or_insert_name(ident.as_ref());
-                            self.emit(Opcode::DefConst, &[index]);
-
-                            if let Some(expr) = decl.init() {
-                                self.compile_expr(expr, true);
-                                self.emit(Opcode::InitLexical, &[index]);
-                            };
+                        Declaration::Identifier { ident, init } => {
+                            self.compile_declaration(kind, ident.sym(), init.as_ref())
                         }
                         Declaration::Pattern(pattern) => {
-                            for ident in pattern.idents() {
-                                let index = self.get_or_insert_name(ident);
-                                self.emit(Opcode::DefConst, &[index]);
-
-                                if let Some(expr) = decl.init() {
-                                    self.compile_expr(expr, true);
-                                    self.emit(Opcode::InitLexical, &[index]);
-                                };
+                            match pattern.init() {
+                                Some(init) => self.compile_expr(init, true),
+                                None => self.emit(Opcode::PushUndefined, &[]),
                             }
+                            self.compile_pattern(pattern, kind);
                         }
                     }
                 }
@@ -724,7 +1842,7 @@ impl ByteCompiler {
                 self.compile_expr(node.cond(), true);
                 let jelse = self.jump_if_false();
 
-                self.compile_stmt(node.body(), false);
+                self.compile_stmt(node.body(), use_expr);
 
                 match node.else_node() {
                     None => {
@@ -733,14 +1851,14 @@ impl ByteCompiler {
                     Some(else_body) => {
                         let exit = self.jump();
                         self.patch_jump(jelse);
-                        self.compile_stmt(else_body, false);
+                        self.compile_stmt(else_body, use_expr);
                         self.patch_jump(exit);
                     }
                 }
             }
             Node::WhileLoop(while_) => {
                 let start_address = self.next_opcode_location();
-                self.push_loop_control_info(while_.label().map(Into::into), start_address);
+                self.push_loop_control_info(while_.label(), start_address);
 
                 self.compile_expr(while_.cond(), true);
                 let exit = self.jump_if_false();
@@ -752,55 +1870,214 @@ impl ByteCompiler {
             }
             Node::DoWhileLoop(do_while) => {
                 let start_address = self.next_opcode_location();
-                self.push_loop_control_info(do_while.label().map(Into::into), start_address);
+                self.push_loop_control_info(do_while.label(), Self::DUMMY_ADDRESS);
 
                 self.compile_stmt(do_while.body(), false);
 
+                self.set_continue_target();
                 self.compile_expr(do_while.cond(), true);
                 self.emit(Opcode::JumpIfTrue, &[start_address]);
 
                 self.pop_loop_control_info();
             }
-            Node::Continue(node) => {
-                let label = self.jump();
-                let mut items = self.jump_info.iter_mut().rev().filter(|info| info.is_loop);
-                let target = if node.label().is_none() {
-                    items.next()
-                } else {
-                    items.find(|info| info.label.as_deref() == node.label())
+            Node::ForLoop(for_loop) => {
+                // Lexical declarations in the initializer get a fresh copy of their bindings for
+                // each iteration.
+                let scope = match for_loop.init() {
+                    Some(init @ (Node::LetDeclList(_) | Node::ConstDeclList(_))) => self
+                        .push_block_scope(std::iter::once(init))
+                        .then(|| self.code_block.scopes.len() as u32 - 1),
+                    _ => None,
+                };
+
+                if let Some(init) = for_loop.init() {
+                    self.compile_stmt(init, false);
+                }
+                if let Some(index) = scope {
+                    self.emit(Opcode::CopyDeclarativeEnvironment, &[index]);
                 }
-                .expect("continue target")
-                .start_address;
 
-                self.patch_jump_with_target(label, target);
+                let start_address = self.next_opcode_location();
+                self.push_loop_control_info(for_loop.label(), Self::DUMMY_ADDRESS);
+
+                let exit = for_loop.condition().map(|condition| {
+                    self.compile_expr(condition, true);
+                    self.jump_if_false()
+                });
+                self.compile_stmt(for_loop.body(), false);
+
+                self.set_continue_target();
+                if let Some(index) = scope {
+                    self.emit(Opcode::CopyDeclarativeEnvironment, &[index]);
+                }
+                if let Some(final_expr) = for_loop.final_expr() {
+                    self.compile_expr(final_expr, false);
+                }
+                self.emit(Opcode::Jump, &[start_address]);
+
+                if let Some(exit) = exit {
+                    self.patch_jump(exit);
+                }
+                self.pop_loop_control_info();
+
+                if scope.is_some() {
+                    self.pop_block_scope();
+                }
             }
-            Node::Break(node) => {
+            Node::ForInLoop(for_in) => {
+                self.compile_expr(for_in.expr(), true);
+                let exit = self.jump_with_custom_opcode(Opcode::ForInLoopInitIterator);
+                self.compile_for_each(
+                    JumpControlKind::ForInLoop,
+                    for_in.label(),
+                    for_in.variable(),
+                    for_in.body(),
+                );
+                self.patch_jump(exit);
+            }
+            Node::ForOfLoop(for_of) => {
+                self.compile_expr(for_of.iterable(), true);
+                self.emit(Opcode::InitIterator, &[]);
+                self.compile_for_each(
+                    JumpControlKind::ForOfLoop,
+                    for_of.label(),
+                    for_of.variable(),
+                    for_of.body(),
+                );
+            }
+            Node::Continue(node) => {
+                let index = self
+                    .jump_info
+                    .iter()
+                    .rposition(|info| {
+                        info.kind.is_loop()
+                            && (node.label().is_none() || info.label == node.label())
+                    })
+                    .expect("continue target");
+
+                self.emit_jump_control_exits(Some(index), false);
                 let label = self.jump();
-                if node.label().is_none() {
-                    self.jump_info.last_mut().unwrap().breaks.push(label);
+                self.open_try_ranges(index + 1);
+                let start_address = self.jump_info[index].start_address;
+                if start_address == Self::DUMMY_ADDRESS {
+                    self.jump_info[index].continues.push(label);
                 } else {
-                    for info in self.jump_info.iter_mut().rev() {
-                        if info.label.as_deref() == node.label() {
-                            info.breaks.push(label);
-                            break;
-                        }
-                    }
+                    self.patch_jump_with_target(label, start_address);
                 }
             }
-            Node::Block(block) => {
-                for node in block.items() {
-                    self.compile_stmt(node, false);
+            Node::Break(node) => {
+                let index = if node.label().is_none() {
+                    self.jump_info.iter().rposition(|info| {
+                        !matches!(
+                            info.kind,
+                            JumpControlKind::Try | JumpControlKind::LabelledBlock
+                        )
+                    })
+                } else {
+                    self.jump_info
+                        .iter()
+                        .rposition(|info| info.label == node.label())
                 }
+                .expect("break target");
+
+                self.emit_jump_control_exits(Some(index), false);
+                let label = self.jump();
+                self.open_try_ranges(index + 1);
+                self.jump_info[index].breaks.push(label);
             }
+            Node::Block(block) => self.compile_block(block, use_expr),
             Node::Throw(throw) => {
                 self.compile_expr(throw.expr(), true);
                 self.emit(Opcode::Throw, &[]);
             }
+            Node::Try(try_node) => {
+                let finally = try_node.finally();
+                let mut exits = Vec::new();
+
+                // The `finally` block protects both the `try` and the `catch` blocks.
+                if finally.is_some() {
+                    self.push_try_control_info(finally);
+                }
+                if try_node.catch().is_some() {
+                    self.push_try_control_info(None);
+                }
+
+                self.compile_block(try_node.block(), use_expr);
+
+                let catch_handlers = try_node.catch().map(|_| self.pop_try_control_info());
+                if let Some(finally) = finally {
+                    let index = self.jump_info.len() - 1;
+                    self.close_try_range(index);
+                    self.compile_finally(index, finally, use_expr as u32);
+                }
+
+                if let Some(catch) = try_node.catch() {
+                    exits.push(self.jump());
+
+                    // The exception is on top of the stack when entering the handler.
+                    self.patch_handlers(catch_handlers.unwrap_or_default());
+                    if finally.is_some() {
+                        self.open_try_ranges(self.jump_info.len() - 1);
+                    }
+
+                    let bindings = catch
+                        .parameter()
+                        .map(bound_names)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|name| (name, BindingKind::Let))
+                        .collect();
+                    let scoped = self.push_block_scope_with_bindings(bindings);
+                    match catch.parameter() {
+                        Some(Declaration::Identifier { ident, .. }) => {
+                            self.emit_binding(BindingKind::Let, ident.sym())
+                        }
+                        Some(Declaration::Pattern(pattern)) => {
+                            self.compile_pattern(pattern, BindingKind::Let)
+                        }
+                        None => self.emit(Opcode::Pop, &[]),
+                    }
+                    self.compile_block(catch.block(), use_expr);
+                    if scoped {
+                        self.pop_block_scope();
+                    }
+
+                    if let Some(finally) = finally {
+                        let index = self.jump_info.len() - 1;
+                        self.close_try_range(index);
+                        self.compile_finally(index, finally, use_expr as u32);
+                    }
+                }
+
+                // Run the `finally` block, and rethrow the exception on top of the stack.
+                if let Some(finally) = finally {
+                    exits.push(self.jump());
+
+                    let handlers = self.pop_try_control_info();
+                    self.patch_handlers(handlers);
+                    self.stack_depth += 1;
+                    self.compile_block(finally, false);
+                    self.stack_depth -= 1;
+                    self.emit(Opcode::Throw, &[]);
+                }
+
+                for label in exits {
+                    self.patch_jump(label);
+                }
+            }
             Node::Switch(switch) => {
+                self.compile_expr(switch.val(), true);
+
+                let items = switch
+                    .cases()
+                    .iter()
+                    .flat_map(|case| case.body().items())
+                    .chain(switch.default().into_iter().flatten());
+                let scoped = self.push_block_scope(items);
+
                 let start_address = self.next_opcode_location();
                 self.push_switch_control_info(None, start_address);
 
-                self.compile_expr(switch.val(), true);
                 let mut labels = Vec::with_capacity(switch.cases().len());
                 for case in switch.cases() {
                     self.compile_expr(case.condition(), true);
@@ -815,22 +2092,25 @@ impl ByteCompiler {
                 }
 
                 self.patch_jump(exit);
-                if let Some(body) = switch.default() {
-                    for node in body {
-                        self.compile_stmt(node, false);
-                    }
+                if let Some(body) = switch.default_statement_list() {
+                    self.compile_statement_list(body, false);
                 }
 
                 self.pop_switch_control_info();
+                if scoped {
+                    self.pop_block_scope();
+                }
+            }
+            Node::FunctionDecl(_) | Node::AsyncFunctionDecl(_) | Node::GeneratorDecl(_) => {
+                self.function(node, false)
             }
-            Node::FunctionDecl(_function) => self.function(node, false),
             Node::Return(ret) => {
                 if let Some(expr) = ret.expr() {
                     self.compile_expr(expr, true);
                 } else {
                     self.emit(Opcode::PushUndefined, &[]);
                 }
-                self.emit(Opcode::Return, &[]);
+                self.emit_return();
             }
             Node::Empty => {}
             expr => self.compile_expr(expr, use_expr),
@@ -838,41 +2118,97 @@ impl ByteCompiler {
     }
 
     pub(crate) fn function(&mut self, function: &Node, use_expr: bool) {
-        #[derive(Debug, Clone, Copy, PartialEq)]
-        enum FunctionKind {
-            Declaration,
-            Expression,
-            Arrow,
-        }
-
-        let (kind, name, paramaters, body) = match function {
+        let (kind, flavor, name, paramaters, body) = match function {
             Node::FunctionDecl(function) => (
                 FunctionKind::Declaration,
+                FunctionFlavor::Normal,
                 Some(function.name()),
                 function.parameters(),
                 function.body(),
             ),
             Node::FunctionExpr(function) => (
                 FunctionKind::Expression,
+                FunctionFlavor::Normal,
                 function.name(),
                 function.parameters(),
                 function.body(),
             ),
             Node::ArrowFunctionDecl(function) => (
                 FunctionKind::Arrow,
+                FunctionFlavor::Normal,
                 None,
                 function.params(),
                 function.body(),
             ),
+            Node::GeneratorDecl(generator) => (
+                FunctionKind::Declaration,
+                FunctionFlavor::Generator,
+                Some(generator.name()),
+                generator.parameters(),
+                generator.statement_list(),
+            ),
+            Node::GeneratorExpr(generator) => (
+                FunctionKind::Expression,
+                FunctionFlavor::Generator,
+                generator.name(),
+                generator.parameters(),
+                generator.body(),
+            ),
+            Node::AsyncFunctionDecl(function) => (
+                FunctionKind::Declaration,
+                FunctionFlavor::Async,
+                Some(function.name()),
+                function.parameters(),
+                function.statement_list(),
+            ),
+            Node::AsyncFunctionExpr(function) => (
+                FunctionKind::Expression,
+                FunctionFlavor::Async,
+                function.name(),
+                function.parameters(),
+                function.statement_list(),
+            ),
             _ => unreachable!(),
         };
 
+        self.compile_function(kind, flavor, name, paramaters, body, use_expr);
+    }
+
+    fn compile_function(
+        &mut self,
+        kind: FunctionKind,
+        flavor: FunctionFlavor,
+        name: Option<Sym>,
+        paramaters: &[FormalParameter],
+        body: &StatementList,
+        use_expr: bool,
+    ) {
         let length = paramaters.len() as u32;
-        let mut code = CodeBlock::new(name.unwrap_or("").into(), length, false, true);
+        let strict = self.code_block.strict || body.strict();
+        let mut code = CodeBlock::new(
+            name.map_or_else(JsString::default, JsString::from),
+            length,
+            strict,
+            true,
+        );
 
         if let FunctionKind::Arrow = kind {
             code.constructor = false;
             code.this_mode = ThisMode::Lexical;
+        } else if strict {
+            code.this_mode = ThisMode::Strict;
+        }
+
+        match flavor {
+            FunctionFlavor::Normal => {}
+            FunctionFlavor::Generator => {
+                code.constructor = false;
+                code.generator = true;
+            }
+            FunctionFlavor::Async => {
+                code.constructor = false;
+                code.asynchronous = true;
+            }
         }
 
         let mut compiler = ByteCompiler {
@@ -881,13 +2217,39 @@ impl ByteCompiler {
             names_map: HashMap::new(),
             functions_map: HashMap::new(),
             jump_info: Vec::new(),
+            scopes: self.scopes.clone(),
+            environment_base: 0,
+            stack_depth: 0,
             top_level: false,
+            side_tables: self.side_tables.clone(),
+            position: None,
         };
 
-        for node in body.items() {
-            compiler.compile_stmt(node, false);
+        compiler.push_function_scope(kind, name, paramaters, body);
+        compiler.environment_base = compiler.scopes.len();
+
+        // Default values of parameters are evaluated in order, when the arguments are missing
+        // or `undefined`.
+        for parameter in paramaters {
+            if let Some(init) = parameter.init() {
+                let (depth, slot) = compiler
+                    .resolve(parameter.name())
+                    .expect("parameters are declared in the function scope");
+                compiler.emit(Opcode::GetLocal, &[depth, slot]);
+                compiler.emit_default_value(init);
+                compiler.emit(Opcode::SetLocal, &[depth, slot]);
+            }
+        }
+
+        // A generator is suspended once its parameters are bound, until its first resumption.
+        if flavor == FunctionFlavor::Generator {
+            compiler.emit(Opcode::PushUndefined, &[]);
+            compiler.emit_yield();
+            compiler.emit(Opcode::Pop, &[]);
         }
 
+        compiler.compile_statement_list(body, false);
+
         compiler.code_block.params = paramaters.to_owned().into_boxed_slice();
 
         // TODO These are redundant if a function returns so may need to check if a function returns and adding these if it doesn't
@@ -903,16 +2265,12 @@ impl ByteCompiler {
 
         match kind {
             FunctionKind::Declaration => {
-                let index = self.get_or_insert_name(name.unwrap());
-                let access = Access::Variable { index };
+                let access = Access::Variable {
+                    name: name.unwrap(),
+                };
                 self.access_set(access, None, false);
             }
-            FunctionKind::Expression => {
-                if use_expr {
-                    self.emit(Opcode::Dup, &[]);
-                }
-            }
-            FunctionKind::Arrow => {
+            FunctionKind::Expression | FunctionKind::Arrow => {
                 if !use_expr {
                     self.emit(Opcode::Pop, &[]);
                 }
@@ -3,9 +3,11 @@
 use crate::{
     builtins::{
         self,
+        date::{Clock, SystemClock},
         function::{Function, NativeFunctionSignature, ThisMode},
         intrinsics::IntrinsicObjects,
         iterable::IteratorPrototypes,
+        math::{RandomSource, Xorshift128Plus},
         typed_array::TypedArray,
     },
     class::{Class, ClassBuilder},
@@ -31,7 +33,9 @@ use crate::{
 use crate::builtins::console::Console;
 
 #[cfg(feature = "vm")]
-use crate::vm::Vm;
+use crate::vm::{CodeBlock, OptimizationLevel, Vm};
+#[cfg(feature = "vm")]
+use std::collections::VecDeque;
 
 /// Store a builtin constructor (such as `Object`) and its corresponding prototype.
 #[derive(Debug, Clone)]
@@ -87,6 +91,7 @@ pub struct StandardObjects {
     string: StandardConstructor,
     regexp: StandardConstructor,
     symbol: StandardConstructor,
+    date: StandardConstructor,
     error: StandardConstructor,
     type_error: StandardConstructor,
     reference_error: StandardConstructor,
@@ -132,6 +137,7 @@ impl Default for StandardObjects {
             )),
             regexp: StandardConstructor::default(),
             symbol: StandardConstructor::default(),
+            date: StandardConstructor::default(),
             error: StandardConstructor::default(),
             type_error: StandardConstructor::default(),
             reference_error: StandardConstructor::default(),
@@ -204,6 +210,11 @@ impl StandardObjects {
         &self.symbol
     }
 
+    #[inline]
+    pub fn date_object(&self) -> &StandardConstructor {
+        &self.date
+    }
+
     #[inline]
     pub fn error_object(&self) -> &StandardConstructor {
         &self.error
@@ -391,6 +402,12 @@ pub struct Context {
     /// Whether or not strict mode is active.
     strict: StrictType,
 
+    /// The source of the current time and of the local time zone.
+    clock: Box<dyn Clock>,
+
+    /// The source of random numbers for `Math.random()`.
+    random_source: Box<dyn RandomSource>,
+
     #[cfg(feature = "vm")]
     pub(crate) vm: Vm,
 }
@@ -409,12 +426,17 @@ impl Default for Context {
             standard_objects: Default::default(),
             intrinsic_objects: IntrinsicObjects::default(),
             strict: StrictType::Off,
+            clock: Box::new(SystemClock),
+            random_source: Box::new(Xorshift128Plus::from_entropy()),
             #[cfg(feature = "vm")]
             vm: Vm {
-                frame: None,
+                frames: Vec::new(),
                 stack: Vec::with_capacity(1024),
+                suspended: None,
+                jobs: VecDeque::new(),
                 trace: false,
                 stack_size_limit: 1024,
+                optimization_level: OptimizationLevel::default(),
             },
         };
 
@@ -853,7 +875,7 @@ impl Context {
     pub(crate) fn set_value(&mut self, node: &Node, value: JsValue) -> JsResult<JsValue> {
         match node {
             Node::Identifier(ref name) => {
-                self.set_mutable_binding(name.as_ref(), value.clone(), true)?;
+                self.set_mutable_binding(name.sym(), value.clone(), true)?;
                 Ok(value)
             }
             Node::GetConstField(ref get_const_field_node) => Ok(get_const_field_node
@@ -991,6 +1013,36 @@ impl Context {
         execution_result
     }
 
+    /// Compiles the given script to bytecode, without running it.
+    ///
+    /// The bytecode is optimized at the level set with [`Context::set_optimization_level`], and
+    /// can be inspected with [`CodeBlock::disassemble`].
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::Context;
+    /// let mut context = Context::new();
+    ///
+    /// let code = context.compile("let a = 1; a + 2").unwrap();
+    ///
+    /// assert!(code.disassemble().contains("Add"));
+    /// ```
+    #[cfg(feature = "vm")]
+    pub fn compile<T: AsRef<[u8]>>(&mut self, src: T) -> JsResult<CodeBlock> {
+        let statement_list = Parser::new(src.as_ref(), false)
+            .parse_all()
+            .map_err(|e| self.construct_syntax_error(e.to_string()))?;
+
+        let mut compiler = crate::bytecompiler::ByteCompiler::new(
+            JsString::new("<main>"),
+            statement_list.strict(),
+        );
+        compiler.compile_statement_list(&statement_list, true);
+        let mut code_block = compiler.finish();
+        code_block.optimize(self.vm.optimization_level, self);
+        Ok(code_block)
+    }
+
     /// Evaluates the given code by compiling down to bytecode, then interpreting the bytecode into a value
     ///
     /// # Examples
@@ -1004,43 +1056,54 @@ impl Context {
     /// assert_eq!(value.as_number().unwrap(), 4.0);
     /// ```
     #[cfg(feature = "vm")]
-    #[allow(clippy::unit_arg, clippy::drop_copy)]
     pub fn eval<T: AsRef<[u8]>>(&mut self, src: T) -> JsResult<JsValue> {
+        let code_block = self.compile(src)?;
+        self.execute(code_block)
+    }
+
+    /// Runs a script that was compiled with [`Context::compile`], or loaded from the bytes of
+    /// [`CodeBlock::to_bytes`] with [`CodeBlock::from_bytes`].
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::Context;
+    /// let mut context = Context::new();
+    ///
+    /// let code = context.compile("1 + 3").unwrap();
+    /// let value = context.execute(code).unwrap();
+    ///
+    /// assert_eq!(value.as_number().unwrap(), 4.0);
+    /// ```
+    #[cfg(feature = "vm")]
+    #[allow(clippy::unit_arg, clippy::drop_copy)]
+    pub fn execute(&mut self, code_block: CodeBlock) -> JsResult<JsValue> {
         use gc::Gc;
 
         use crate::vm::CallFrame;
 
         let main_timer = BoaProfiler::global().start_event("Main", "Main");
-        let src_bytes: &[u8] = src.as_ref();
-
-        let parsing_result = Parser::new(src_bytes, false)
-            .parse_all()
-            .map_err(|e| e.to_string());
-
-        let statement_list = match parsing_result {
-            Ok(statement_list) => statement_list,
-            Err(e) => return self.throw_syntax_error(e),
-        };
-
-        let mut compiler = crate::bytecompiler::ByteCompiler::new(JsString::new("<main>"), false);
-        compiler.compile_statement_list(&statement_list, true);
-        let code_block = compiler.finish();
 
         let environment = self.get_current_environment().clone();
         let fp = self.vm.stack.len();
         let global_object = self.global_object().into();
 
         self.vm.push_frame(CallFrame {
-            prev: None,
             code: Gc::new(code_block),
             this: global_object,
             pc: 0,
             fp,
             exit_on_return: true,
             environment,
+            environment_depth: 0,
         });
         let result = self.run();
 
+        // Async functions that are suspended at an `await` continue once the script has run
+        // to completion.
+        if self.vm.frames.is_empty() {
+            self.run_jobs();
+        }
+
         // The main_timer needs to be dropped before the BoaProfiler is.
         drop(main_timer);
         BoaProfiler::global().drop();
@@ -1072,9 +1135,124 @@ impl Context {
         &self.intrinsic_objects
     }
 
+    /// Return the clock used by `Date` to get the current time and the local time zone.
+    #[inline]
+    pub fn clock(&self) -> &dyn Clock {
+        self.clock.as_ref()
+    }
+
+    /// Set the clock used by `Date` to get the current time and the local time zone.
+    ///
+    /// By default, the clock and the time zone of the host system are used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use boa::{builtins::date::Clock, Context};
+    ///
+    /// /// A clock stopped at `2020-07-08T09:16:15.779Z`, in a time zone two hours ahead of UTC.
+    /// #[derive(Debug)]
+    /// struct StoppedClock;
+    ///
+    /// impl Clock for StoppedClock {
+    ///     fn now(&self) -> f64 {
+    ///         1_594_199_775_779.0
+    ///     }
+    ///
+    ///     fn local_offset(&self, _t: f64, _is_utc: bool) -> f64 {
+    ///         2.0 * 3_600_000.0
+    ///     }
+    /// }
+    ///
+    /// let mut context = Context::new();
+    /// context.set_clock(StoppedClock);
+    ///
+    /// let value = context.eval("new Date().getHours()").unwrap();
+    /// assert_eq!(value.as_number(), Some(11.0));
+    /// ```
+    #[inline]
+    pub fn set_clock<C>(&mut self, clock: C)
+    where
+        C: Clock + 'static,
+    {
+        self.clock = Box::new(clock);
+    }
+
+    /// Return the source of random numbers used by `Math.random()`.
+    #[inline]
+    pub(crate) fn random_source_mut(&mut self) -> &mut dyn RandomSource {
+        self.random_source.as_mut()
+    }
+
+    /// Seed the pseudo random number generator used by `Math.random()`.
+    ///
+    /// Two contexts seeded with the same value generate the same sequence of random numbers, which makes it possible
+    /// to replay the execution of scripts that use `Math.random()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use boa::Context;
+    ///
+    /// let mut first = Context::new();
+    /// first.set_random_seed(42);
+    /// let mut second = Context::new();
+    /// second.set_random_seed(42);
+    ///
+    /// assert_eq!(
+    ///     first.eval("Math.random()").unwrap().as_number(),
+    ///     second.eval("Math.random()").unwrap().as_number(),
+    /// );
+    /// ```
+    #[inline]
+    pub fn set_random_seed(&mut self, seed: u64) {
+        self.random_source = Box::new(Xorshift128Plus::new(seed));
+    }
+
+    /// Set the source of random numbers used by `Math.random()`.
+    ///
+    /// By default, each context uses its own pseudo random number generator, seeded from the entropy of the host
+    /// system.
+    #[inline]
+    pub fn set_random_source<R>(&mut self, source: R)
+    where
+        R: RandomSource + 'static,
+    {
+        self.random_source = Box::new(source);
+    }
+
     /// Set the value of trace on the context
     #[cfg(feature = "vm")]
     pub fn set_trace(&mut self, trace: bool) {
         self.vm.trace = trace;
     }
+
+    /// Return how much the scripts compiled by the context are optimized.
+    #[cfg(feature = "vm")]
+    #[inline]
+    pub fn optimization_level(&self) -> OptimizationLevel {
+        self.vm.optimization_level
+    }
+
+    /// Set how much the scripts compiled by the context are optimized.
+    ///
+    /// Scripts are fully optimized by default. Lower levels make the output of
+    /// [`CodeBlock::disassemble`] closer to the source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use boa::{vm::OptimizationLevel, Context};
+    ///
+    /// let mut context = Context::new();
+    /// context.set_optimization_level(OptimizationLevel::None);
+    ///
+    /// let code = context.compile("1 + 2").unwrap();
+    /// assert!(code.disassemble().contains("Add"));
+    /// ```
+    #[cfg(feature = "vm")]
+    #[inline]
+    pub fn set_optimization_level(&mut self, level: OptimizationLevel) {
+        self.vm.optimization_level = level;
+    }
 }
@@ -11,6 +11,7 @@ use crate::{
         lexical_environment::{Environment, EnvironmentType},
     },
     gc::{Finalize, Trace},
+    interner::Sym,
     object::JsObject,
     BoaProfiler, Context, JsResult, JsValue,
 };
@@ -24,6 +25,7 @@ use rustc_hash::FxHashMap;
 /// From this point onwards, a binding is referring to one of these structures.
 #[derive(Trace, Finalize, Debug, Clone)]
 pub struct DeclarativeEnvironmentRecordBinding {
+    pub name: Sym,
     pub value: Option<JsValue>,
     pub can_delete: bool,
     pub mutable: bool,
@@ -32,9 +34,18 @@ pub struct DeclarativeEnvironmentRecordBinding {
 
 /// A declarative Environment Record binds the set of identifiers defined by the
 /// declarations contained within its scope.
+///
+/// The bindings are stored in a flat vector, in creation order, and the index of a binding in it
+/// is its slot. The bytecompiler resolves most identifiers to a slot ahead of time, so that the VM
+/// can access them without looking up their name. The `names` map is only used for accesses by
+/// name.
+///
+/// Deleted bindings leave an empty slot behind, so that the slots of the other bindings do not
+/// change.
 #[derive(Debug, Trace, Finalize, Clone)]
 pub struct DeclarativeEnvironmentRecord {
-    pub env_rec: GcCell<FxHashMap<Box<str>, DeclarativeEnvironmentRecordBinding>>,
+    pub env_rec: GcCell<Vec<DeclarativeEnvironmentRecordBinding>>,
+    pub names: GcCell<FxHashMap<Sym, usize>>,
     pub outer_env: Option<Environment>,
 }
 
@@ -42,10 +53,111 @@ impl DeclarativeEnvironmentRecord {
     pub fn new(env: Option<Environment>) -> DeclarativeEnvironmentRecord {
         let _timer = BoaProfiler::global().start_event("new_declarative_environment", "env");
         DeclarativeEnvironmentRecord {
-            env_rec: GcCell::new(FxHashMap::default()),
+            env_rec: GcCell::new(Vec::new()),
+            names: GcCell::new(FxHashMap::default()),
             outer_env: env,
         }
     }
+
+    /// Gets the slot of the binding for `name`, if there is one.
+    fn slot(&self, name: Sym) -> Option<usize> {
+        self.names.borrow().get(&name).copied()
+    }
+
+    /// Creates a binding for `name` in the next free slot, and returns the slot.
+    #[cfg(feature = "vm")]
+    ///
+    /// If there already is a binding for `name`, accesses by name refer to the new binding from
+    /// now on, but the old one keeps its slot.
+    pub(crate) fn create_binding(
+        &self,
+        name: Sym,
+        mutable: bool,
+        strict: bool,
+        value: Option<JsValue>,
+    ) -> usize {
+        self.insert(DeclarativeEnvironmentRecordBinding {
+            name,
+            value,
+            can_delete: false,
+            mutable,
+            strict,
+        })
+    }
+
+    /// Stores `binding` in the next free slot, and returns the slot.
+    fn insert(&self, binding: DeclarativeEnvironmentRecordBinding) -> usize {
+        let mut env_rec = self.env_rec.borrow_mut();
+        let slot = env_rec.len();
+        self.names.borrow_mut().insert(binding.name, slot);
+        env_rec.push(binding);
+        slot
+    }
+
+    /// Gets the value of the binding in `slot`.
+    ///
+    /// Throws a `ReferenceError` if the binding has not been initialized yet.
+    pub(crate) fn get_binding_value_by_slot(
+        &self,
+        slot: usize,
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let env_rec = self.env_rec.borrow();
+        let binding = &env_rec[slot];
+        if let Some(ref value) = binding.value {
+            Ok(value.clone())
+        } else {
+            let name = binding.name;
+            drop(env_rec);
+            context.throw_reference_error(format!("{} is an uninitialized binding", name))
+        }
+    }
+
+    /// Initializes the binding in `slot` to `value`.
+    #[cfg(feature = "vm")]
+    pub(crate) fn initialize_binding_by_slot(&self, slot: usize, value: JsValue) {
+        self.env_rec.borrow_mut()[slot].value = Some(value);
+    }
+
+    /// Assigns `value` to the binding in `slot`, following the steps of `SetMutableBinding`.
+    #[allow(clippy::else_if_without_else)]
+    pub(crate) fn set_mutable_binding_by_slot(
+        &self,
+        slot: usize,
+        value: JsValue,
+        mut strict: bool,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        let mut env_rec = self.env_rec.borrow_mut();
+        let binding = &mut env_rec[slot];
+        let name = binding.name;
+
+        // 2. If the binding for N in envRec is a strict binding, set S to true.
+        if binding.strict {
+            strict = true;
+        }
+
+        // 3. If the binding for N in envRec has not yet been initialized, throw a ReferenceError exception.
+        if binding.value.is_none() {
+            drop(env_rec);
+            return Err(
+                context.construct_reference_error(format!("{} has not been initialized", name))
+            );
+        // 4. Else if the binding for N in envRec is a mutable binding, change its bound value to V.
+        } else if binding.mutable {
+            binding.value = Some(value);
+        // 5. Else,
+        // a. Assert: This is an attempt to change the value of an immutable binding.
+        // b. If S is true, throw a TypeError exception.
+        } else if strict {
+            drop(env_rec);
+            return Err(context
+                .construct_type_error(format!("Cannot mutate an immutable binding {}", name)));
+        }
+
+        // 6. Return NormalCompletion(empty).
+        Ok(())
+    }
 }
 
 impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
@@ -55,10 +167,10 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-declarative-environment-records-hasbinding-n
-    fn has_binding(&self, name: &str, _context: &mut Context) -> JsResult<bool> {
+    fn has_binding(&self, name: Sym, _context: &mut Context) -> JsResult<bool> {
         // 1. If envRec has a binding for the name that is the value of N, return true.
         // 2. Return false.
-        Ok(self.env_rec.borrow().contains_key(name))
+        Ok(self.names.borrow().contains_key(&name))
     }
 
     /// `9.1.1.1.2 CreateMutableBinding ( N, D )`
@@ -69,7 +181,7 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
     /// [spec]: https://tc39.es/ecma262/#sec-declarative-environment-records-createmutablebinding-n-d
     fn create_mutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         deletion: bool,
         allow_name_reuse: bool,
         _context: &mut Context,
@@ -77,7 +189,7 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
         // 1. Assert: envRec does not already have a binding for N.
         if !allow_name_reuse {
             assert!(
-                !self.env_rec.borrow().contains_key(name),
+                !self.names.borrow().contains_key(&name),
                 "Identifier {} has already been declared",
                 name
             );
@@ -85,15 +197,20 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
 
         // 2. Create a mutable binding in envRec for N and record that it is uninitialized.
         //    If D is true, record that the newly created binding may be deleted by a subsequent DeleteBinding call.
-        self.env_rec.borrow_mut().insert(
-            name.into(),
-            DeclarativeEnvironmentRecordBinding {
-                value: None,
-                can_delete: deletion,
-                mutable: true,
-                strict: false,
-            },
-        );
+        //    A reused name gets its binding replaced in place, so that its slot does not change.
+        let binding = DeclarativeEnvironmentRecordBinding {
+            name,
+            value: None,
+            can_delete: deletion,
+            mutable: true,
+            strict: false,
+        };
+        match self.slot(name) {
+            Some(slot) => self.env_rec.borrow_mut()[slot] = binding,
+            None => {
+                self.insert(binding);
+            }
+        }
 
         // 3. Return NormalCompletion(empty).
         Ok(())
@@ -107,28 +224,26 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
     /// [spec]: https://tc39.es/ecma262/#sec-declarative-environment-records-createimmutablebinding-n-s
     fn create_immutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         strict: bool,
         _context: &mut Context,
     ) -> JsResult<()> {
         // 1. Assert: envRec does not already have a binding for N.
         assert!(
-            !self.env_rec.borrow().contains_key(name),
+            !self.names.borrow().contains_key(&name),
             "Identifier {} has already been declared",
             name
         );
 
         // 2. Create an immutable binding in envRec for N and record that it is uninitialized.
         //    If S is true, record that the newly created binding is a strict binding.
-        self.env_rec.borrow_mut().insert(
-            name.into(),
-            DeclarativeEnvironmentRecordBinding {
-                value: None,
-                can_delete: true,
-                mutable: false,
-                strict,
-            },
-        );
+        self.insert(DeclarativeEnvironmentRecordBinding {
+            name,
+            value: None,
+            can_delete: true,
+            mutable: false,
+            strict,
+        });
 
         // 3. Return NormalCompletion(empty).
         Ok(())
@@ -142,11 +257,12 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
     /// [spec]: https://tc39.es/ecma262/#sec-declarative-environment-records-initializebinding-n-v
     fn initialize_binding(
         &self,
-        name: &str,
+        name: Sym,
         value: JsValue,
         _context: &mut Context,
     ) -> JsResult<()> {
-        if let Some(ref mut record) = self.env_rec.borrow_mut().get_mut(name) {
+        if let Some(slot) = self.slot(name) {
+            let record = &mut self.env_rec.borrow_mut()[slot];
             if record.value.is_none() {
                 // 2. Set the bound value for N in envRec to V.
                 // 3. Record that the binding for N in envRec has been initialized.
@@ -167,60 +283,30 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-declarative-environment-records-setmutablebinding-n-v-s
-    #[allow(clippy::else_if_without_else)]
     fn set_mutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         value: JsValue,
-        mut strict: bool,
+        strict: bool,
         context: &mut Context,
     ) -> JsResult<()> {
-        // 1. If envRec does not have a binding for N, then
-        if self.env_rec.borrow().get(name).is_none() {
-            // a. If S is true, throw a ReferenceError exception.
-            if strict {
-                return Err(context.construct_reference_error(format!("{} not found", name)));
-            }
-
-            // b. Perform envRec.CreateMutableBinding(N, true).
-            self.create_mutable_binding(name, true, false, context)?;
-            // c. Perform envRec.InitializeBinding(N, V).
-            self.initialize_binding(name, value, context)?;
-
-            // d. Return NormalCompletion(empty).
-            return Ok(());
+        // Steps 2 to 6 do not depend on the name.
+        if let Some(slot) = self.slot(name) {
+            return self.set_mutable_binding_by_slot(slot, value, strict, context);
         }
 
-        let (binding_strict, binding_value_is_none, binding_mutable) = {
-            let env_rec = self.env_rec.borrow();
-            let binding = env_rec.get(name).unwrap();
-            (binding.strict, binding.value.is_none(), binding.mutable)
-        };
-
-        // 2. If the binding for N in envRec is a strict binding, set S to true.
-        if binding_strict {
-            strict = true;
+        // 1. If envRec does not have a binding for N, then
+        // a. If S is true, throw a ReferenceError exception.
+        if strict {
+            return Err(context.construct_reference_error(format!("{} not found", name)));
         }
 
-        // 3. If the binding for N in envRec has not yet been initialized, throw a ReferenceError exception.
-        if binding_value_is_none {
-            return Err(
-                context.construct_reference_error(format!("{} has not been initialized", name))
-            );
-        // 4. Else if the binding for N in envRec is a mutable binding, change its bound value to V.
-        } else if binding_mutable {
-            let mut env_rec = self.env_rec.borrow_mut();
-            let binding = env_rec.get_mut(name).unwrap();
-            binding.value = Some(value);
-        // 5. Else,
-        // a. Assert: This is an attempt to change the value of an immutable binding.
-        // b. If S is true, throw a TypeError exception.
-        } else if strict {
-            return Err(context
-                .construct_type_error(format!("Cannot mutate an immutable binding {}", name)));
-        }
+        // b. Perform envRec.CreateMutableBinding(N, true).
+        self.create_mutable_binding(name, true, false, context)?;
+        // c. Perform envRec.InitializeBinding(N, V).
+        self.initialize_binding(name, value, context)?;
 
-        // 6. Return NormalCompletion(empty).
+        // d. Return NormalCompletion(empty).
         Ok(())
     }
 
@@ -232,19 +318,15 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
     /// [spec]: https://tc39.es/ecma262/#sec-declarative-environment-records-getbindingvalue-n-s
     fn get_binding_value(
         &self,
-        name: &str,
+        name: Sym,
         _strict: bool,
         context: &mut Context,
     ) -> JsResult<JsValue> {
         // 1. Assert: envRec has a binding for N.
         // 2. If the binding for N in envRec is an uninitialized binding, throw a ReferenceError exception.
         // 3. Return the value currently bound to N in envRec.
-        if let Some(binding) = self.env_rec.borrow().get(name) {
-            if let Some(ref val) = binding.value {
-                Ok(val.clone())
-            } else {
-                context.throw_reference_error(format!("{} is an uninitialized binding", name))
-            }
+        if let Some(slot) = self.slot(name) {
+            self.get_binding_value_by_slot(slot, context)
         } else {
             panic!("Cannot get binding value for {}", name);
         }
@@ -256,15 +338,18 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-declarative-environment-records-deletebinding-n
-    fn delete_binding(&self, name: &str, _context: &mut Context) -> JsResult<bool> {
+    fn delete_binding(&self, name: Sym, _context: &mut Context) -> JsResult<bool> {
         // 1. Assert: envRec has a binding for the name that is the value of N.
         // 2. If the binding for N in envRec cannot be deleted, return false.
         // 3. Remove the binding for N from envRec.
         // 4. Return true.
-        match self.env_rec.borrow().get(name) {
-            Some(binding) => {
-                if binding.can_delete {
-                    self.env_rec.borrow_mut().remove(name);
+        //    The slot of a removed binding is left empty.
+        match self.slot(name) {
+            Some(slot) => {
+                let mut env_rec = self.env_rec.borrow_mut();
+                if env_rec[slot].can_delete {
+                    env_rec[slot].value = None;
+                    self.names.borrow_mut().remove(&name);
                     Ok(true)
                 } else {
                     Ok(false)
@@ -321,6 +406,10 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
     fn get_environment_type(&self) -> EnvironmentType {
         EnvironmentType::Declarative
     }
+
+    fn as_declarative(&self) -> Option<&DeclarativeEnvironmentRecord> {
+        Some(self)
+    }
 }
 
 impl From<DeclarativeEnvironmentRecord> for Environment {
@@ -9,9 +9,12 @@
 //! There are 5 Environment record kinds. They all have methods in common, these are implemented as a the `EnvironmentRecordTrait`
 //!
 
-use crate::{environment::lexical_environment::VariableScope, object::JsObject};
+use crate::{environment::lexical_environment::VariableScope, interner::Sym, object::JsObject};
 use crate::{
-    environment::lexical_environment::{Environment, EnvironmentType},
+    environment::{
+        declarative_environment_record::DeclarativeEnvironmentRecord,
+        lexical_environment::{Environment, EnvironmentType},
+    },
     gc::{Finalize, Trace},
     Context, JsResult, JsValue,
 };
@@ -24,7 +27,7 @@ use std::fmt::Debug;
 pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
     /// Determine if an Environment Record has a binding for the String value N.
     /// Return true if it does and false if it does not.
-    fn has_binding(&self, name: &str, context: &mut Context) -> JsResult<bool>;
+    fn has_binding(&self, name: Sym, context: &mut Context) -> JsResult<bool>;
 
     /// Create a new but uninitialized mutable binding in an Environment Record. The String value N is the text of the bound name.
     /// If the Boolean argument deletion is true the binding may be subsequently deleted.
@@ -35,7 +38,7 @@ pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
     /// paraments with the same name.
     fn create_mutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         deletion: bool,
         allow_name_reuse: bool,
         context: &mut Context,
@@ -47,7 +50,7 @@ pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
     /// regardless of the strict mode setting of operations that reference that binding.
     fn create_immutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         strict: bool,
         context: &mut Context,
     ) -> JsResult<()>;
@@ -55,8 +58,7 @@ pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
     /// Set the value of an already existing but uninitialized binding in an Environment Record.
     /// The String value N is the text of the bound name.
     /// V is the value for the binding and is a value of any ECMAScript language type.
-    fn initialize_binding(&self, name: &str, value: JsValue, context: &mut Context)
-        -> JsResult<()>;
+    fn initialize_binding(&self, name: Sym, value: JsValue, context: &mut Context) -> JsResult<()>;
 
     /// Set the value of an already existing mutable binding in an Environment Record.
     /// The String value `name` is the text of the bound name.
@@ -64,7 +66,7 @@ pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
     /// If `strict` is true and the binding cannot be set throw a TypeError exception.
     fn set_mutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         value: JsValue,
         strict: bool,
         context: &mut Context,
@@ -76,7 +78,7 @@ pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
     /// otherwise require strict mode reference semantics.
     fn get_binding_value(
         &self,
-        name: &str,
+        name: Sym,
         strict: bool,
         context: &mut Context,
     ) -> JsResult<JsValue>;
@@ -85,7 +87,7 @@ pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
     /// The String value name is the text of the bound name.
     /// If a binding for name exists, remove the binding and return true.
     /// If the binding exists but cannot be removed return false. If the binding does not exist return true.
-    fn delete_binding(&self, name: &str, context: &mut Context) -> JsResult<bool>;
+    fn delete_binding(&self, name: Sym, context: &mut Context) -> JsResult<bool>;
 
     /// Determine if an Environment Record establishes a this binding.
     /// Return true if it does and false if it does not.
@@ -114,6 +116,13 @@ pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
     /// Get the type of environment this is
     fn get_environment_type(&self) -> EnvironmentType;
 
+    /// Get the declarative record holding the bindings of this environment, if it has one.
+    ///
+    /// This gives access to the bindings by slot.
+    fn as_declarative(&self) -> Option<&DeclarativeEnvironmentRecord> {
+        None
+    }
+
     /// Return the `this` binding from the environment or try to get it from outer environments
     fn recursive_get_this_binding(&self, context: &mut Context) -> JsResult<JsValue> {
         if self.has_this_binding() {
@@ -129,7 +138,7 @@ pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
     /// Create mutable binding while handling outer environments
     fn recursive_create_mutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         deletion: bool,
         scope: VariableScope,
         context: &mut Context,
@@ -146,7 +155,7 @@ pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
     /// Create immutable binding while handling outer environments
     fn recursive_create_immutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         deletion: bool,
         scope: VariableScope,
         context: &mut Context,
@@ -163,7 +172,7 @@ pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
     /// Set mutable binding while handling outer environments
     fn recursive_set_mutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         value: JsValue,
         strict: bool,
         context: &mut Context,
@@ -180,7 +189,7 @@ pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
     /// Initialize binding while handling outer environments
     fn recursive_initialize_binding(
         &self,
-        name: &str,
+        name: Sym,
         value: JsValue,
         context: &mut Context,
     ) -> JsResult<()> {
@@ -194,7 +203,7 @@ pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
     }
 
     /// Check if a binding exists in current or any outer environment
-    fn recursive_has_binding(&self, name: &str, context: &mut Context) -> JsResult<bool> {
+    fn recursive_has_binding(&self, name: Sym, context: &mut Context) -> JsResult<bool> {
         Ok(self.has_binding(name, context)?
             || match self.get_outer_environment_ref() {
                 Some(outer) => outer.recursive_has_binding(name, context)?,
@@ -203,7 +212,7 @@ pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
     }
 
     /// Retrieve binding from current or any outer environment
-    fn recursive_get_binding_value(&self, name: &str, context: &mut Context) -> JsResult<JsValue> {
+    fn recursive_get_binding_value(&self, name: Sym, context: &mut Context) -> JsResult<JsValue> {
         if self.has_binding(name, context)? {
             self.get_binding_value(name, false, context)
         } else {
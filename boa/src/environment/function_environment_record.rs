@@ -17,6 +17,7 @@ use crate::{
         lexical_environment::{Environment, EnvironmentType, VariableScope},
     },
     gc::{empty_trace, Finalize, Trace},
+    interner::Sym,
     object::{JsObject, JsPrototype},
     Context, JsResult, JsValue,
 };
@@ -136,13 +137,13 @@ impl FunctionEnvironmentRecord {
 }
 
 impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
-    fn has_binding(&self, name: &str, context: &mut Context) -> JsResult<bool> {
+    fn has_binding(&self, name: Sym, context: &mut Context) -> JsResult<bool> {
         self.declarative_record.has_binding(name, context)
     }
 
     fn create_mutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         deletion: bool,
         allow_name_reuse: bool,
         context: &mut Context,
@@ -153,7 +154,7 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
 
     fn create_immutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         strict: bool,
         context: &mut Context,
     ) -> JsResult<()> {
@@ -161,19 +162,14 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
             .create_immutable_binding(name, strict, context)
     }
 
-    fn initialize_binding(
-        &self,
-        name: &str,
-        value: JsValue,
-        context: &mut Context,
-    ) -> JsResult<()> {
+    fn initialize_binding(&self, name: Sym, value: JsValue, context: &mut Context) -> JsResult<()> {
         self.declarative_record
             .initialize_binding(name, value, context)
     }
 
     fn set_mutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         value: JsValue,
         strict: bool,
         context: &mut Context,
@@ -184,7 +180,7 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
 
     fn get_binding_value(
         &self,
-        name: &str,
+        name: Sym,
         strict: bool,
         context: &mut Context,
     ) -> JsResult<JsValue> {
@@ -192,7 +188,7 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
             .get_binding_value(name, strict, context)
     }
 
-    fn delete_binding(&self, name: &str, context: &mut Context) -> JsResult<bool> {
+    fn delete_binding(&self, name: Sym, context: &mut Context) -> JsResult<bool> {
         self.declarative_record.delete_binding(name, context)
     }
 
@@ -260,9 +256,13 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
         EnvironmentType::Function
     }
 
+    fn as_declarative(&self) -> Option<&DeclarativeEnvironmentRecord> {
+        Some(&self.declarative_record)
+    }
+
     fn recursive_create_mutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         deletion: bool,
         _scope: VariableScope,
         context: &mut Context,
@@ -272,7 +272,7 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
 
     fn recursive_create_immutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         deletion: bool,
         _scope: VariableScope,
         context: &mut Context,
@@ -15,6 +15,7 @@ use crate::{
         object_environment_record::ObjectEnvironmentRecord,
     },
     gc::{Finalize, Trace},
+    interner::Sym,
     object::JsObject,
     property::PropertyDescriptor,
     Context, JsResult, JsValue,
@@ -27,7 +28,7 @@ pub struct GlobalEnvironmentRecord {
     pub object_record: ObjectEnvironmentRecord,
     pub global_this_binding: JsObject,
     pub declarative_record: DeclarativeEnvironmentRecord,
-    pub var_names: GcCell<FxHashSet<Box<str>>>,
+    pub var_names: GcCell<FxHashSet<Sym>>,
 }
 
 impl GlobalEnvironmentRecord {
@@ -59,11 +60,11 @@ impl GlobalEnvironmentRecord {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-hasvardeclaration
-    pub fn has_var_declaration(&self, name: &str) -> bool {
+    pub fn has_var_declaration(&self, name: Sym) -> bool {
         // 1. Let varDeclaredNames be envRec.[[VarNames]].
         // 2. If varDeclaredNames contains N, return true.
         // 3. Return false.
-        self.var_names.borrow().contains(name)
+        self.var_names.borrow().contains(&name)
     }
 
     /// `9.1.1.4.13 HasLexicalDeclaration ( N )`
@@ -72,7 +73,7 @@ impl GlobalEnvironmentRecord {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-haslexicaldeclaration
-    pub fn has_lexical_declaration(&self, name: &str, context: &mut Context) -> JsResult<bool> {
+    pub fn has_lexical_declaration(&self, name: Sym, context: &mut Context) -> JsResult<bool> {
         // 1. Let DclRec be envRec.[[DeclarativeRecord]].
         // 2. Return DclRec.HasBinding(N).
         self.declarative_record.has_binding(name, context)
@@ -86,7 +87,7 @@ impl GlobalEnvironmentRecord {
     /// [spec]: https://tc39.es/ecma262/#sec-hasrestrictedglobalproperty
     pub fn has_restricted_global_property(
         &self,
-        name: &str,
+        name: Sym,
         context: &mut Context,
     ) -> JsResult<bool> {
         // 1. Let ObjRec be envRec.[[ObjectRecord]].
@@ -112,7 +113,7 @@ impl GlobalEnvironmentRecord {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-candeclareglobalvar
-    pub fn can_declare_global_var(&self, name: &str, context: &mut Context) -> JsResult<bool> {
+    pub fn can_declare_global_var(&self, name: Sym, context: &mut Context) -> JsResult<bool> {
         // 1. Let ObjRec be envRec.[[ObjectRecord]].
         // 2. Let globalObject be ObjRec.[[BindingObject]].
         let global_object = &self.object_record.bindings;
@@ -135,7 +136,7 @@ impl GlobalEnvironmentRecord {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-candeclareglobalfunction
-    pub fn can_declare_global_function(&self, name: &str, context: &mut Context) -> JsResult<bool> {
+    pub fn can_declare_global_function(&self, name: Sym, context: &mut Context) -> JsResult<bool> {
         // 1. Let ObjRec be envRec.[[ObjectRecord]].
         // 2. Let globalObject be ObjRec.[[BindingObject]].
         let global_object = &self.object_record.bindings;
@@ -175,7 +176,7 @@ impl GlobalEnvironmentRecord {
     /// [spec]: https://tc39.es/ecma262/#sec-createglobalvarbinding
     pub fn create_global_var_binding(
         &mut self,
-        name: &str,
+        name: Sym,
         deletion: bool,
         context: &mut Context,
     ) -> JsResult<()> {
@@ -201,9 +202,9 @@ impl GlobalEnvironmentRecord {
         // 6. Let varDeclaredNames be envRec.[[VarNames]].
         let mut var_declared_names = self.var_names.borrow_mut();
         // 7. If varDeclaredNames does not contain N, then
-        if !var_declared_names.contains(name) {
+        if !var_declared_names.contains(&name) {
             // a. Append N to varDeclaredNames.
-            var_declared_names.insert(name.into());
+            var_declared_names.insert(name);
         }
 
         // 8. Return NormalCompletion(empty).
@@ -218,7 +219,7 @@ impl GlobalEnvironmentRecord {
     /// [spec]: https://tc39.es/ecma262/#sec-createglobalfunctionbinding
     pub fn create_global_function_binding(
         &mut self,
-        name: &str,
+        name: Sym,
         value: JsValue,
         deletion: bool,
         context: &mut Context,
@@ -255,9 +256,9 @@ impl GlobalEnvironmentRecord {
 
         // 8. Let varDeclaredNames be envRec.[[VarNames]].
         // 9. If varDeclaredNames does not contain N, then
-        if !self.var_names.borrow().contains(name) {
+        if !self.var_names.borrow().contains(&name) {
             // a. Append N to varDeclaredNames.
-            self.var_names.borrow_mut().insert(name.into());
+            self.var_names.borrow_mut().insert(name);
         }
 
         // 10. Return NormalCompletion(empty).
@@ -272,7 +273,7 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-global-environment-records-hasbinding-n
-    fn has_binding(&self, name: &str, context: &mut Context) -> JsResult<bool> {
+    fn has_binding(&self, name: Sym, context: &mut Context) -> JsResult<bool> {
         // 1. Let DclRec be envRec.[[DeclarativeRecord]].
         // 2. If DclRec.HasBinding(N) is true, return true.
         if self.declarative_record.has_binding(name, context)? {
@@ -292,7 +293,7 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
     /// [spec]: https://tc39.es/ecma262/#sec-global-environment-records-createmutablebinding-n-d
     fn create_mutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         deletion: bool,
         allow_name_reuse: bool,
         context: &mut Context,
@@ -318,7 +319,7 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
     /// [spec]: https://tc39.es/ecma262/#sec-global-environment-records-createimmutablebinding-n-s
     fn create_immutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         strict: bool,
         context: &mut Context,
     ) -> JsResult<()> {
@@ -341,12 +342,7 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-global-environment-records-initializebinding-n-v
-    fn initialize_binding(
-        &self,
-        name: &str,
-        value: JsValue,
-        context: &mut Context,
-    ) -> JsResult<()> {
+    fn initialize_binding(&self, name: Sym, value: JsValue, context: &mut Context) -> JsResult<()> {
         // 1. Let DclRec be envRec.[[DeclarativeRecord]].
         // 2. If DclRec.HasBinding(N) is true, then
         if self.declarative_record.has_binding(name, context)? {
@@ -375,7 +371,7 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
     /// [spec]: https://tc39.es/ecma262/#sec-global-environment-records-setmutablebinding-n-v-s
     fn set_mutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         value: JsValue,
         strict: bool,
         context: &mut Context,
@@ -403,7 +399,7 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
     /// [spec]: https://tc39.es/ecma262/#sec-global-environment-records-getbindingvalue-n-s
     fn get_binding_value(
         &self,
-        name: &str,
+        name: Sym,
         strict: bool,
         context: &mut Context,
     ) -> JsResult<JsValue> {
@@ -427,7 +423,7 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-global-environment-records-deletebinding-n
-    fn delete_binding(&self, name: &str, context: &mut Context) -> JsResult<bool> {
+    fn delete_binding(&self, name: Sym, context: &mut Context) -> JsResult<bool> {
         // 1. Let DclRec be envRec.[[DeclarativeRecord]].
         // 2. If DclRec.HasBinding(N) is true, then
         if self.declarative_record.has_binding(name, context)? {
@@ -449,7 +445,7 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
             if status {
                 // i. Let varNames be envRec.[[VarNames]].
                 // ii. If N is an element of varNames, remove that element from the varNames.
-                self.var_names.borrow_mut().remove(name);
+                self.var_names.borrow_mut().remove(&name);
             }
 
             // c. Return status.
@@ -523,7 +519,7 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
 
     fn recursive_create_mutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         deletion: bool,
         _scope: VariableScope,
         context: &mut Context,
@@ -533,7 +529,7 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
 
     fn recursive_create_immutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         deletion: bool,
         _scope: VariableScope,
         context: &mut Context,
@@ -543,7 +539,7 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
 
     fn recursive_set_mutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         value: JsValue,
         strict: bool,
         context: &mut Context,
@@ -553,7 +549,7 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
 
     fn recursive_initialize_binding(
         &self,
-        name: &str,
+        name: Sym,
         value: JsValue,
         context: &mut Context,
     ) -> JsResult<()> {
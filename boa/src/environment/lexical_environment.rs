@@ -7,8 +7,8 @@
 
 use super::global_environment_record::GlobalEnvironmentRecord;
 use crate::{
-    environment::environment_record_trait::EnvironmentRecordTrait, object::JsObject, BoaProfiler,
-    Context, JsResult, JsValue,
+    environment::environment_record_trait::EnvironmentRecordTrait, interner::Sym, object::JsObject,
+    BoaProfiler, Context, JsResult, JsValue,
 };
 use gc::Gc;
 use std::{collections::VecDeque, error, fmt};
@@ -95,7 +95,7 @@ impl Context {
 
     pub(crate) fn create_mutable_binding(
         &mut self,
-        name: &str,
+        name: Sym,
         deletion: bool,
         scope: VariableScope,
     ) -> JsResult<()> {
@@ -105,7 +105,7 @@ impl Context {
 
     pub(crate) fn create_immutable_binding(
         &mut self,
-        name: &str,
+        name: Sym,
         deletion: bool,
         scope: VariableScope,
     ) -> JsResult<()> {
@@ -115,7 +115,7 @@ impl Context {
 
     pub(crate) fn set_mutable_binding(
         &mut self,
-        name: &str,
+        name: Sym,
         value: JsValue,
         strict: bool,
     ) -> JsResult<()> {
@@ -123,7 +123,7 @@ impl Context {
             .recursive_set_mutable_binding(name, value, strict, self)
     }
 
-    pub(crate) fn initialize_binding(&mut self, name: &str, value: JsValue) -> JsResult<()> {
+    pub(crate) fn initialize_binding(&mut self, name: Sym, value: JsValue) -> JsResult<()> {
         self.get_current_environment()
             .recursive_initialize_binding(name, value, self)
     }
@@ -139,12 +139,12 @@ impl Context {
             .clone()
     }
 
-    pub(crate) fn has_binding(&mut self, name: &str) -> JsResult<bool> {
+    pub(crate) fn has_binding(&mut self, name: Sym) -> JsResult<bool> {
         self.get_current_environment()
             .recursive_has_binding(name, self)
     }
 
-    pub(crate) fn get_binding_value(&mut self, name: &str) -> JsResult<JsValue> {
+    pub(crate) fn get_binding_value(&mut self, name: Sym) -> JsResult<JsValue> {
         self.get_current_environment()
             .recursive_get_binding_value(name, self)
     }
@@ -14,6 +14,7 @@ use crate::{
         lexical_environment::{Environment, EnvironmentType},
     },
     gc::{Finalize, Trace},
+    interner::Sym,
     object::JsObject,
     property::PropertyDescriptor,
     symbol::WellKnownSymbols,
@@ -49,7 +50,7 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-object-environment-records-hasbinding-n
-    fn has_binding(&self, name: &str, context: &mut Context) -> JsResult<bool> {
+    fn has_binding(&self, name: Sym, context: &mut Context) -> JsResult<bool> {
         // 1. Let bindingObject be envRec.[[BindingObject]].
         // 2. Let foundBinding be ? HasProperty(bindingObject, N).
         // 3. If foundBinding is false, return false.
@@ -88,7 +89,7 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
     /// [spec]: https://tc39.es/ecma262/#sec-object-environment-records-createmutablebinding-n-d
     fn create_mutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         deletion: bool,
         _allow_name_reuse: bool,
         context: &mut Context,
@@ -115,7 +116,7 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
     /// [spec]: https://tc39.es/ecma262/#sec-object-environment-records-createimmutablebinding-n-s
     fn create_immutable_binding(
         &self,
-        _name: &str,
+        _name: Sym,
         _strict: bool,
         _context: &mut Context,
     ) -> JsResult<()> {
@@ -128,12 +129,7 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-object-environment-records-initializebinding-n-v
-    fn initialize_binding(
-        &self,
-        name: &str,
-        value: JsValue,
-        context: &mut Context,
-    ) -> JsResult<()> {
+    fn initialize_binding(&self, name: Sym, value: JsValue, context: &mut Context) -> JsResult<()> {
         // 1. Return ? envRec.SetMutableBinding(N, V, false).
         self.set_mutable_binding(name, value, false, context)
     }
@@ -146,7 +142,7 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
     /// [spec]: https://tc39.es/ecma262/#sec-object-environment-records-setmutablebinding-n-v-s
     fn set_mutable_binding(
         &self,
-        name: &str,
+        name: Sym,
         value: JsValue,
         strict: bool,
         context: &mut Context,
@@ -173,7 +169,7 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
     /// [spec]: https://tc39.es/ecma262/#sec-object-environment-records-getbindingvalue-n-s
     fn get_binding_value(
         &self,
-        name: &str,
+        name: Sym,
         strict: bool,
         context: &mut Context,
     ) -> JsResult<JsValue> {
@@ -199,7 +195,7 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-object-environment-records-deletebinding-n
-    fn delete_binding(&self, name: &str, context: &mut Context) -> JsResult<bool> {
+    fn delete_binding(&self, name: Sym, context: &mut Context) -> JsResult<bool> {
         // 1. Let bindingObject be envRec.[[BindingObject]].
         // 2. Return ? bindingObject.[[Delete]](N).
         self.bindings.__delete__(&name.into(), context)
@@ -3,7 +3,13 @@
 #[cfg(test)]
 mod tests;
 
-use crate::{Context, JsResult, JsValue};
+use crate::{
+    builtins::error::set_error_position,
+    interner::Sym,
+    syntax::ast::{side_table::SideTable, Node},
+    Context, JsResult, JsValue,
+};
+use std::rc::Rc;
 
 pub trait Executable {
     /// Runs this executable in the given context.
@@ -14,8 +20,8 @@ pub trait Executable {
 pub(crate) enum InterpreterState {
     Executing,
     Return,
-    Break(Option<Box<str>>),
-    Continue(Option<Box<str>>),
+    Break(Option<Sym>),
+    Continue(Option<Sym>),
 }
 
 /// A Javascript intepreter
@@ -23,6 +29,9 @@ pub(crate) enum InterpreterState {
 pub struct Interpreter {
     /// the current state of the interpreter.
     state: InterpreterState,
+
+    /// The side tables of the statement lists being run, which locate the nodes in the source.
+    side_tables: Vec<Rc<SideTable>>,
 }
 
 impl Default for Interpreter {
@@ -36,6 +45,7 @@ impl Interpreter {
     pub fn new() -> Self {
         Self {
             state: InterpreterState::Executing,
+            side_tables: Vec::new(),
         }
     }
 
@@ -48,4 +58,27 @@ impl Interpreter {
     pub(crate) fn get_current_state(&self) -> &InterpreterState {
         &self.state
     }
+
+    /// Enters a statement list with the given side table.
+    #[inline]
+    pub(crate) fn push_side_table(&mut self, side_table: Rc<SideTable>) {
+        self.side_tables.push(side_table);
+    }
+
+    /// Leaves the statement list of the last side table.
+    #[inline]
+    pub(crate) fn pop_side_table(&mut self) {
+        self.side_tables.pop();
+    }
+
+    /// Records the source position of the node which threw an error on the error object.
+    pub(crate) fn locate_error(&self, node: &Node, error: &JsValue) {
+        if let Some(span) = self
+            .side_tables
+            .last()
+            .and_then(|side_table| side_table.span(node))
+        {
+            set_error_position(error, span.start());
+        }
+    }
 }
@@ -774,7 +774,7 @@ mod in_operator {
 
         check_output(&[TestAction::TestEq(
             scenario,
-            "Uncaught \"TypeError\": \"a is not a constructor\"",
+            "Uncaught \"TypeError\": \"a is not a constructor\" (at 3:13)",
         )]);
     }
 
@@ -0,0 +1,236 @@
+//! This module implements the string interner used for identifiers.
+//!
+//! Every identifier in the source code is interned into a process-wide table, and gets a compact
+//! [`Sym`] in return. The lexer, the AST, the environment records and the bytecode refer to names
+//! through these symbols, so comparing or hashing a name is an integer operation, and each
+//! distinct name is only stored once, no matter how many times it appears in the code.
+//!
+//! Interned strings are never freed. This is fine for identifiers, since their number is bounded
+//! by the size of the source code, but the interner should not be used for arbitrary runtime
+//! strings.
+
+use crate::gc::{empty_trace, Finalize, Trace};
+use once_cell::sync::Lazy;
+use rustc_hash::FxHashMap;
+use std::{
+    convert::TryFrom,
+    fmt,
+    num::NonZeroU32,
+    ops::Deref,
+    sync::{PoisonError, RwLock},
+};
+
+#[cfg(feature = "deser")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The process-wide interner.
+static INTERNER: Lazy<RwLock<Interner>> = Lazy::new(|| RwLock::new(Interner::default()));
+
+/// The table of interned strings.
+#[derive(Debug, Default)]
+struct Interner {
+    /// The symbol of each interned string.
+    symbols: FxHashMap<&'static str, Sym>,
+    /// The interned strings, indexed by their symbol minus one.
+    strings: Vec<&'static str>,
+}
+
+impl Interner {
+    /// Gets the symbol of the given string, interning it if needed.
+    fn get_or_intern(&mut self, string: &str) -> Sym {
+        if let Some(&sym) = self.symbols.get(string) {
+            return sym;
+        }
+
+        let index = u32::try_from(self.strings.len() + 1)
+            .ok()
+            .and_then(NonZeroU32::new)
+            .expect("too many interned strings");
+        let sym = Sym(index);
+        let string: &'static str = Box::leak(string.into());
+        self.strings.push(string);
+        self.symbols.insert(string, sym);
+        sym
+    }
+}
+
+/// An interned string.
+///
+/// Symbols are cheap to copy, compare and hash. Two symbols are equal if and only if they were
+/// interned from the same string. The string can be retrieved with [`Sym::as_str`], or through
+/// `Deref`.
+///
+/// # Examples
+/// ```
+/// # use boa::interner::Sym;
+/// let a = Sym::intern("answer");
+/// assert_eq!(a, Sym::intern("answer"));
+/// assert_eq!(a.as_str(), "answer");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Sym(NonZeroU32);
+
+impl Sym {
+    /// Interns the given string, returning its symbol.
+    pub fn intern(string: &str) -> Self {
+        if let Some(&sym) = INTERNER
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .symbols
+            .get(string)
+        {
+            return sym;
+        }
+
+        INTERNER
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_or_intern(string)
+    }
+
+    /// Gets the symbol of the given string, if it has already been interned.
+    pub fn get(string: &str) -> Option<Self> {
+        INTERNER
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .symbols
+            .get(string)
+            .copied()
+    }
+
+    /// Gets the interned string.
+    pub fn as_str(self) -> &'static str {
+        INTERNER
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .strings[self.0.get() as usize - 1]
+    }
+}
+
+impl Deref for Sym {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for Sym {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<&str> for Sym {
+    #[inline]
+    fn from(string: &str) -> Self {
+        Self::intern(string)
+    }
+}
+
+impl From<&String> for Sym {
+    #[inline]
+    fn from(string: &String) -> Self {
+        Self::intern(string)
+    }
+}
+
+impl From<String> for Sym {
+    #[inline]
+    fn from(string: String) -> Self {
+        Self::intern(&string)
+    }
+}
+
+impl From<Box<str>> for Sym {
+    #[inline]
+    fn from(string: Box<str>) -> Self {
+        Self::intern(&string)
+    }
+}
+
+impl PartialEq<str> for Sym {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Sym {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Debug for Sym {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for Sym {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl Finalize for Sym {}
+
+// Safety: `Sym` does not contain any objects which need to be traced.
+unsafe impl Trace for Sym {
+    empty_trace!();
+}
+
+#[cfg(feature = "deser")]
+impl Serialize for Sym {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "deser")]
+impl<'de> Deserialize<'de> for Sym {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let string = String::deserialize(deserializer)?;
+        Ok(Self::intern(&string))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sym;
+
+    #[test]
+    fn intern_is_idempotent() {
+        let a = Sym::intern("interner_test_a");
+        let b = Sym::intern("interner_test_b");
+        assert_ne!(a, b);
+        assert_eq!(a, Sym::intern(&String::from("interner_test_a")));
+        assert_eq!(Sym::get("interner_test_b"), Some(b));
+        assert_eq!(b.as_str(), "interner_test_b");
+        assert_eq!(&*a, "interner_test_a");
+    }
+
+    #[test]
+    fn missing_symbol() {
+        assert_eq!(Sym::get("interner_test_never_interned"), None);
+    }
+
+    #[test]
+    fn interned_across_threads() {
+        let sym = std::thread::spawn(|| Sym::intern("interner_test_thread"))
+            .join()
+            .expect("thread panicked");
+        assert_eq!(Sym::intern("interner_test_thread"), sym);
+        assert_eq!(sym.as_str(), "interner_test_thread");
+    }
+}
@@ -48,6 +48,7 @@ pub mod context;
 pub mod environment;
 pub mod exec;
 pub mod gc;
+pub mod interner;
 pub mod object;
 pub mod profiler;
 pub mod property;
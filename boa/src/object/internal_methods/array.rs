@@ -137,8 +137,7 @@ pub(crate) fn array_exotic_define_own_property(
                     .borrow()
                     .properties
                     .index_property_keys()
-                    .filter(|idx| new_len <= **idx && **idx < u32::MAX)
-                    .copied()
+                    .filter(|idx| new_len <= *idx && *idx < u32::MAX)
                     .collect();
                 keys.sort_unstable_by(|x, y| y.cmp(x));
                 keys
@@ -5,6 +5,7 @@ use crate::{
         lexical_environment::Environment,
     },
     exec::{Executable, InterpreterState},
+    interner::Sym,
     object::{internal_methods::get_prototype_from_constructor, JsObject, ObjectData},
     syntax::ast::node::RcStatementList,
     Context, JsResult, JsValue,
@@ -96,6 +97,8 @@ pub(super) fn call_construct(
             captures: Captures,
         },
         Ordinary(RcStatementList),
+        #[cfg(feature = "vm")]
+        Vm,
     }
 
     let this_function_object = obj.clone();
@@ -165,6 +168,7 @@ pub(super) fn call_construct(
                         context,
                     )?;
 
+                    let arguments = Sym::intern("arguments");
                     let mut arguments_in_parameter_names = false;
                     let mut is_simple_parameter_list = true;
 
@@ -172,7 +176,7 @@ pub(super) fn call_construct(
                         has_parameter_expressions =
                             has_parameter_expressions || param.init().is_some();
                         arguments_in_parameter_names =
-                            arguments_in_parameter_names || param.name() == "arguments";
+                            arguments_in_parameter_names || param.name() == arguments;
                         is_simple_parameter_list = is_simple_parameter_list
                             && !param.is_rest_param()
                             && param.init().is_none()
@@ -190,8 +194,8 @@ pub(super) fn call_construct(
                     if !this_mode.is_lexical()
                         && !arguments_in_parameter_names
                         && (has_parameter_expressions
-                            || (!body.lexically_declared_names().contains("arguments")
-                                && !body.function_declared_names().contains("arguments")))
+                            || (!body.lexically_declared_names().contains(&arguments)
+                                && !body.function_declared_names().contains(&arguments)))
                     {
                         // Add arguments object
                         let arguments_obj =
@@ -202,8 +206,17 @@ pub(super) fn call_construct(
                                     obj, params, args, &local_env, context,
                                 )
                             };
-                        local_env.create_mutable_binding("arguments", false, true, context)?;
-                        local_env.initialize_binding("arguments", arguments_obj.into(), context)?;
+                        local_env.create_mutable_binding(
+                            Sym::intern("arguments"),
+                            false,
+                            true,
+                            context,
+                        )?;
+                        local_env.initialize_binding(
+                            Sym::intern("arguments"),
+                            arguments_obj.into(),
+                            context,
+                        )?;
                     }
 
                     // Push the environment first so that it will be used by default parameters
@@ -257,9 +270,7 @@ pub(super) fn call_construct(
                     FunctionBody::Ordinary(body.clone())
                 }
                 #[cfg(feature = "vm")]
-                Function::VmOrdinary { .. } => {
-                    todo!("vm call")
-                }
+                Function::VmOrdinary { .. } => FunctionBody::Vm,
             }
         }
     } else {
@@ -310,5 +321,9 @@ pub(super) fn call_construct(
                 Ok(JsValue::undefined())
             }
         }
+        #[cfg(feature = "vm")]
+        FunctionBody::Vm if construct => obj.construct_internal(args, this_target, context, true),
+        #[cfg(feature = "vm")]
+        FunctionBody::Vm => obj.call_internal(this_target, args, context, true),
     }
 }
@@ -1,5 +1,7 @@
 //! This module implements the Rust representation of a JavaScript object.
 
+#[cfg(feature = "vm")]
+use crate::builtins::generator::Generator;
 use crate::{
     builtins::{
         array::array_iterator::ArrayIterator,
@@ -50,6 +52,7 @@ pub(crate) mod internal_methods;
 mod jsobject;
 mod operations;
 mod property_map;
+pub(crate) mod shape;
 
 /// Static `prototype`, usually set on constructors as a key to point to their respective prototype object.
 pub static PROTOTYPE: &str = "prototype";
@@ -114,6 +117,8 @@ pub enum ObjectKind {
     ForInIterator(ForInIterator),
     Function(Function),
     BoundFunction(BoundFunction),
+    #[cfg(feature = "vm")]
+    Generator(Generator),
     Set(OrderedSet<JsValue>),
     SetIterator(SetIterator),
     String(JsString),
@@ -170,6 +175,15 @@ impl ObjectData {
         }
     }
 
+    /// Create the `Generator` object data
+    #[cfg(feature = "vm")]
+    pub fn generator(generator: Generator) -> Self {
+        Self {
+            kind: ObjectKind::Generator(generator),
+            internal_methods: &ORDINARY_INTERNAL_METHODS,
+        }
+    }
+
     /// Create the `RegExp` object data
     pub fn reg_exp(reg_exp: Box<RegExp>) -> Self {
         Self {
@@ -352,6 +366,8 @@ impl Display for ObjectKind {
             Self::ForInIterator(_) => "ForInIterator",
             Self::Function(_) => "Function",
             Self::BoundFunction(_) => "BoundFunction",
+            #[cfg(feature = "vm")]
+            Self::Generator(_) => "Generator",
             Self::RegExp(_) => "RegExp",
             Self::RegExpStringIterator(_) => "RegExpStringIterator",
             Self::Map(_) => "Map",
@@ -605,6 +621,18 @@ impl Object {
         }
     }
 
+    #[inline]
+    #[cfg(feature = "vm")]
+    pub fn as_generator_mut(&mut self) -> Option<&mut Generator> {
+        match &mut self.data {
+            ObjectData {
+                kind: ObjectKind::Generator(generator),
+                ..
+            } => Some(generator),
+            _ => None,
+        }
+    }
+
     #[inline]
     pub fn is_set(&self) -> bool {
         matches!(
@@ -1050,6 +1078,50 @@ impl Object {
         &self.properties
     }
 
+    /// Returns `true` if properties can be added to the object.
+    #[inline]
+    pub(crate) fn extensible(&self) -> bool {
+        self.extensible
+    }
+
+    #[inline]
+    pub(crate) fn properties_mut(&mut self) -> &mut PropertyMap {
+        &mut self.properties
+    }
+
+    /// Returns `true` if getting a property of this object only looks at its own properties and
+    /// then at its prototype, that is if its `[[GetPrototypeOf]]`, `[[GetOwnProperty]]` and
+    /// `[[Get]]` internal methods are the ordinary ones.
+    ///
+    /// The internal methods are compared by their tables, since function pointers are not
+    /// guaranteed to be unique.
+    #[inline]
+    pub(crate) fn has_ordinary_get(&self) -> bool {
+        [
+            &ORDINARY_INTERNAL_METHODS,
+            &FUNCTION_INTERNAL_METHODS,
+            &CONSTRUCTOR_INTERNAL_METHODS,
+            &ARRAY_EXOTIC_INTERNAL_METHODS,
+        ]
+        .iter()
+        .any(|methods| std::ptr::eq(*methods, self.data.internal_methods))
+    }
+
+    /// Returns `true` if setting an own writable data property of this object only changes its
+    /// value, that is if its `[[GetOwnProperty]]`, `[[DefineOwnProperty]]` and `[[Set]]` internal
+    /// methods are the ordinary ones.
+    #[cfg(feature = "vm")]
+    #[inline]
+    pub(crate) fn has_ordinary_set(&self) -> bool {
+        [
+            &ORDINARY_INTERNAL_METHODS,
+            &FUNCTION_INTERNAL_METHODS,
+            &CONSTRUCTOR_INTERNAL_METHODS,
+        ]
+        .iter()
+        .any(|methods| std::ptr::eq(*methods, self.data.internal_methods))
+    }
+
     /// Helper function for property insertion.
     #[inline]
     pub(crate) fn insert<K, P>(&mut self, key: K, property: P) -> Option<PropertyDescriptor>
@@ -1229,9 +1301,9 @@ impl<'context> FunctionBuilder<'context> {
     #[inline]
     pub fn name<N>(&mut self, name: N) -> &mut Self
     where
-        N: AsRef<str>,
+        N: Into<JsString>,
     {
-        self.name = name.as_ref().into();
+        self.name = name.into();
         self
     }
 
@@ -1627,9 +1699,9 @@ impl<'context> ConstructorBuilder<'context> {
     #[inline]
     pub fn name<N>(&mut self, name: N) -> &mut Self
     where
-        N: AsRef<str>,
+        N: Into<JsString>,
     {
-        self.name = name.as_ref().into();
+        self.name = name.into();
         self
     }
 
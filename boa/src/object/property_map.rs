@@ -1,11 +1,26 @@
-use super::{PropertyDescriptor, PropertyKey};
+use super::{
+    shape::{Attributes, Shape, MAX_SHAPED_PROPERTIES},
+    PropertyDescriptor, PropertyKey,
+};
 use crate::{
     gc::{custom_trace, Finalize, Trace},
-    JsString, JsSymbol,
+    JsString, JsSymbol, JsValue,
 };
 use indexmap::IndexMap;
 use rustc_hash::{FxHashMap, FxHasher};
-use std::{collections::hash_map, hash::BuildHasherDefault, iter::FusedIterator};
+use std::{
+    collections::hash_map,
+    hash::BuildHasherDefault,
+    iter::{Enumerate, FusedIterator, Zip},
+    slice,
+};
+
+/// The number of holes that can be left before a new element of an object in dense mode, when it
+/// has fewer elements than that.
+///
+/// Past this, an element can be added after at most as many holes as there are elements, so that
+/// an object with a huge index switches to sparse mode instead of allocating its holes.
+const MIN_DENSE_GAP: usize = 64;
 
 /// Wrapper around indexmap::IndexMap for usage in PropertyMap
 #[derive(Debug, Finalize)]
@@ -26,11 +41,353 @@ unsafe impl<K: Trace> Trace for OrderedHashMap<K> {
     });
 }
 
+/// The string keyed properties of a [`PropertyMap`].
+#[derive(Debug)]
+enum StringStorage {
+    /// The layout of the properties is described by a shape of the transition tree, and the
+    /// properties are stored in the order of its slots.
+    Shaped {
+        shape: Shape,
+        slots: Vec<PropertyDescriptor>,
+    },
+    /// Dictionary mode: the properties are stored in a hash map. The map has a shape of its own,
+    /// which it replaces whenever one of its properties is added or removed, or changes its
+    /// attributes.
+    Dictionary {
+        shape: Shape,
+        properties: OrderedHashMap<JsString>,
+    },
+}
+
+impl Finalize for StringStorage {}
+
+unsafe impl Trace for StringStorage {
+    custom_trace!(this, {
+        match this {
+            Self::Shaped { slots, .. } => mark(slots),
+            Self::Dictionary { properties, .. } => mark(properties),
+        }
+    });
+}
+
+impl Default for StringStorage {
+    fn default() -> Self {
+        Self::Shaped {
+            shape: Shape::root(),
+            slots: Vec::new(),
+        }
+    }
+}
+
+impl StringStorage {
+    #[cfg(any(test, feature = "vm"))]
+    fn shape(&self) -> &Shape {
+        match self {
+            Self::Shaped { shape, .. } | Self::Dictionary { shape, .. } => shape,
+        }
+    }
+
+    fn get_full(&self, key: &JsString) -> Option<(usize, &PropertyDescriptor)> {
+        match self {
+            Self::Shaped { shape, slots } => {
+                let (slot, _) = shape.lookup(key)?;
+                Some((slot, &slots[slot]))
+            }
+            Self::Dictionary { properties, .. } => {
+                let (slot, _, property) = properties.0.get_full(key)?;
+                Some((slot, property))
+            }
+        }
+    }
+
+    #[cfg(feature = "vm")]
+    fn get_index(&self, slot: usize) -> Option<&PropertyDescriptor> {
+        match self {
+            Self::Shaped { slots, .. } => slots.get(slot),
+            Self::Dictionary { properties, .. } => {
+                properties.0.get_index(slot).map(|(_, property)| property)
+            }
+        }
+    }
+
+    #[cfg(feature = "vm")]
+    fn get_index_mut(&mut self, slot: usize) -> Option<&mut PropertyDescriptor> {
+        match self {
+            Self::Shaped { slots, .. } => slots.get_mut(slot),
+            Self::Dictionary { properties, .. } => properties
+                .0
+                .get_index_mut(slot)
+                .map(|(_, property)| property),
+        }
+    }
+
+    fn insert(
+        &mut self,
+        key: &JsString,
+        property: PropertyDescriptor,
+    ) -> Option<PropertyDescriptor> {
+        let attributes = Attributes::of(&property);
+        match self {
+            Self::Shaped { shape, slots } => {
+                if let Some((slot, old_attributes)) = shape.lookup(key) {
+                    if old_attributes != attributes {
+                        *shape = shape.change_attributes(slot, attributes);
+                    }
+                    return Some(std::mem::replace(&mut slots[slot], property));
+                }
+                if shape.len() < MAX_SHAPED_PROPERTIES {
+                    *shape = shape.insert(key.clone(), attributes);
+                    slots.push(property);
+                    return None;
+                }
+            }
+            Self::Dictionary { shape, properties } => {
+                let old = properties.0.insert(key.clone(), property);
+                if old.as_ref().map(Attributes::of) != Some(attributes) {
+                    *shape = Shape::unique();
+                }
+                return old;
+            }
+        }
+        self.make_dictionary();
+        self.insert(key, property)
+    }
+
+    fn remove(&mut self, key: &JsString) -> Option<PropertyDescriptor> {
+        match self {
+            Self::Shaped { shape, slots } => {
+                let (slot, _) = shape.lookup(key)?;
+                // Removing the last property added goes back to the previous shape, but any other
+                // removal would need a shape that no other object is likely to share.
+                if slot + 1 == slots.len() {
+                    if let Some(parent) = shape.parent() {
+                        *shape = parent.clone();
+                        return slots.pop();
+                    }
+                }
+            }
+            Self::Dictionary { shape, properties } => {
+                let old = properties.0.shift_remove(key);
+                if old.is_some() {
+                    *shape = Shape::unique();
+                }
+                return old;
+            }
+        }
+        self.make_dictionary();
+        self.remove(key)
+    }
+
+    /// Switches to dictionary mode.
+    fn make_dictionary(&mut self) {
+        if let Self::Shaped { shape, slots } = self {
+            let properties = shape.keys().cloned().zip(slots.drain(..)).collect();
+            *self = Self::Dictionary {
+                shape: Shape::unique(),
+                properties: OrderedHashMap(properties),
+            };
+        }
+    }
+
+    fn iter(&self) -> StringIter<'_> {
+        match self {
+            Self::Shaped { shape, slots } => StringIter::Shaped(shape.keys().zip(slots.iter())),
+            Self::Dictionary { properties, .. } => StringIter::Dictionary(properties.0.iter()),
+        }
+    }
+}
+
+/// An iterator over the string keyed properties of a [`PropertyMap`], in the order they were
+/// added.
+#[derive(Debug, Clone)]
+enum StringIter<'a> {
+    Shaped(Zip<indexmap::map::Keys<'a, JsString, Attributes>, slice::Iter<'a, PropertyDescriptor>>),
+    Dictionary(indexmap::map::Iter<'a, JsString, PropertyDescriptor>),
+}
+
+impl<'a> Iterator for StringIter<'a> {
+    type Item = (&'a JsString, &'a PropertyDescriptor);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Shaped(iter) => iter.next(),
+            Self::Dictionary(iter) => iter.next(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Shaped(iter) => iter.size_hint(),
+            Self::Dictionary(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl ExactSizeIterator for StringIter<'_> {}
+
+impl FusedIterator for StringIter<'_> {}
+
+/// The integer keyed properties, or elements, of a [`PropertyMap`].
+#[derive(Debug, Trace, Finalize)]
+enum IndexedStorage {
+    /// Dense mode: the element at index `i` is stored at `i`, with `None` for holes. All the
+    /// elements are writable, enumerable and configurable data properties, so only their values
+    /// are stored. The last element is never a hole.
+    Dense(Vec<Option<JsValue>>),
+    /// Sparse mode: the elements are stored in a hash map, which is used for the objects that have
+    /// huge indices or elements with other attributes.
+    Sparse(FxHashMap<u32, PropertyDescriptor>),
+}
+
+impl Default for IndexedStorage {
+    fn default() -> Self {
+        Self::Dense(Vec::new())
+    }
+}
+
+/// Gets the descriptor of an element stored in dense mode.
+fn dense_element(value: JsValue) -> PropertyDescriptor {
+    PropertyDescriptor::builder()
+        .value(value)
+        .writable(true)
+        .enumerable(true)
+        .configurable(true)
+        .build()
+}
+
+impl IndexedStorage {
+    fn get(&self, index: u32) -> Option<PropertyDescriptor> {
+        match self {
+            Self::Dense(elements) => elements
+                .get(index as usize)?
+                .as_ref()
+                .map(|value| dense_element(value.clone())),
+            Self::Sparse(elements) => elements.get(&index).cloned(),
+        }
+    }
+
+    fn contains_key(&self, index: u32) -> bool {
+        match self {
+            Self::Dense(elements) => matches!(elements.get(index as usize), Some(Some(_))),
+            Self::Sparse(elements) => elements.contains_key(&index),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Dense(elements) => elements.is_empty(),
+            Self::Sparse(elements) => elements.is_empty(),
+        }
+    }
+
+    fn insert(&mut self, index: u32, property: PropertyDescriptor) -> Option<PropertyDescriptor> {
+        match self {
+            Self::Dense(elements) => {
+                let slot = index as usize;
+                let fits = slot < elements.len()
+                    || slot - elements.len() <= elements.len().max(MIN_DENSE_GAP);
+                let is_default = property.writable() == Some(true)
+                    && property.enumerable() == Some(true)
+                    && property.configurable() == Some(true);
+                match property.value() {
+                    Some(value) if fits && is_default => {
+                        if slot >= elements.len() {
+                            elements.resize(slot + 1, None);
+                        }
+                        return elements[slot].replace(value.clone()).map(dense_element);
+                    }
+                    _ => {}
+                }
+            }
+            Self::Sparse(elements) => return elements.insert(index, property),
+        }
+        self.make_sparse();
+        self.insert(index, property)
+    }
+
+    fn remove(&mut self, index: u32) -> Option<PropertyDescriptor> {
+        match self {
+            Self::Dense(elements) => {
+                let old = elements.get_mut(index as usize)?.take()?;
+                while let Some(None) = elements.last() {
+                    elements.pop();
+                }
+                Some(dense_element(old))
+            }
+            Self::Sparse(elements) => elements.remove(&index),
+        }
+    }
+
+    /// Switches to sparse mode.
+    fn make_sparse(&mut self) {
+        if let Self::Dense(elements) = self {
+            let elements = elements
+                .drain(..)
+                .enumerate()
+                .filter_map(|(index, value)| Some((index as u32, dense_element(value?))))
+                .collect();
+            *self = Self::Sparse(elements);
+        }
+    }
+
+    fn iter(&self) -> IndexedIter<'_> {
+        match self {
+            Self::Dense(elements) => IndexedIter::Dense(elements.iter().enumerate()),
+            Self::Sparse(elements) => IndexedIter::Sparse(elements.iter()),
+        }
+    }
+}
+
+/// An iterator over the integer keyed properties of a [`PropertyMap`].
+#[derive(Debug, Clone)]
+enum IndexedIter<'a> {
+    Dense(Enumerate<slice::Iter<'a, Option<JsValue>>>),
+    Sparse(hash_map::Iter<'a, u32, PropertyDescriptor>),
+}
+
+impl Iterator for IndexedIter<'_> {
+    type Item = (u32, PropertyDescriptor);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Dense(iter) => iter.find_map(|(index, value)| {
+                Some((index as u32, dense_element(value.as_ref()?.clone())))
+            }),
+            Self::Sparse(iter) => iter
+                .next()
+                .map(|(index, property)| (*index, property.clone())),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Dense(iter) => {
+                let len = iter.clone().filter(|(_, value)| value.is_some()).count();
+                (len, Some(len))
+            }
+            Self::Sparse(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl ExactSizeIterator for IndexedIter<'_> {}
+
+impl FusedIterator for IndexedIter<'_> {}
+
+/// The properties of an object.
+///
+/// The string keyed properties, which most objects are made of, are laid out by [`Shape`]s, so
+/// that objects with the same properties share their keys. The elements of arrays are stored in a
+/// vector for as long as they are dense enough.
 #[derive(Default, Debug, Trace, Finalize)]
 pub struct PropertyMap {
-    indexed_properties: FxHashMap<u32, PropertyDescriptor>,
+    indexed_properties: IndexedStorage,
     /// Properties
-    string_properties: OrderedHashMap<JsString>,
+    string_properties: StringStorage,
     /// Symbol Properties
     symbol_properties: OrderedHashMap<JsSymbol>,
 }
@@ -39,11 +396,14 @@ impl PropertyMap {
     pub fn new() -> Self {
         Self::default()
     }
-    pub fn get(&self, key: &PropertyKey) -> Option<&PropertyDescriptor> {
+    pub fn get(&self, key: &PropertyKey) -> Option<PropertyDescriptor> {
         match key {
-            PropertyKey::Index(index) => self.indexed_properties.get(index),
-            PropertyKey::String(string) => self.string_properties.0.get(string),
-            PropertyKey::Symbol(symbol) => self.symbol_properties.0.get(symbol),
+            PropertyKey::Index(index) => self.indexed_properties.get(*index),
+            PropertyKey::String(string) => self
+                .string_properties
+                .get_full(string)
+                .map(|(_, property)| property.clone()),
+            PropertyKey::Symbol(symbol) => self.symbol_properties.0.get(symbol).cloned(),
         }
     }
 
@@ -54,9 +414,7 @@ impl PropertyMap {
     ) -> Option<PropertyDescriptor> {
         match &key {
             PropertyKey::Index(index) => self.indexed_properties.insert(*index, property),
-            PropertyKey::String(string) => {
-                self.string_properties.0.insert(string.clone(), property)
-            }
+            PropertyKey::String(string) => self.string_properties.insert(string, property),
             PropertyKey::Symbol(symbol) => {
                 self.symbol_properties.0.insert(symbol.clone(), property)
             }
@@ -65,20 +423,95 @@ impl PropertyMap {
 
     pub fn remove(&mut self, key: &PropertyKey) -> Option<PropertyDescriptor> {
         match key {
-            PropertyKey::Index(index) => self.indexed_properties.remove(index),
-            PropertyKey::String(string) => self.string_properties.0.shift_remove(string),
+            PropertyKey::Index(index) => self.indexed_properties.remove(*index),
+            PropertyKey::String(string) => self.string_properties.remove(string),
             PropertyKey::Symbol(symbol) => self.symbol_properties.0.shift_remove(symbol),
         }
     }
 
-    /// An iterator visiting all key-value pairs in arbitrary order. The iterator element type is `(PropertyKey, &'a Property)`.
+    /// Gets the shape of the string keyed properties.
+    ///
+    /// A string keyed property stays at the same slot for as long as the map keeps its shape.
+    #[cfg(any(test, feature = "vm"))]
+    #[inline]
+    pub(crate) fn shape(&self) -> &Shape {
+        self.string_properties.shape()
+    }
+
+    /// Returns `true` if the string keyed properties are in dictionary mode.
+    #[cfg(test)]
+    pub(crate) fn is_dictionary(&self) -> bool {
+        matches!(self.string_properties, StringStorage::Dictionary { .. })
+    }
+
+    /// Gets the values of the elements, with `None` for holes, if they are in dense mode.
+    ///
+    /// All the elements of an object in dense mode are writable, enumerable and configurable data
+    /// properties.
+    #[inline]
+    pub(crate) fn dense_elements(&self) -> Option<&[Option<JsValue>]> {
+        match &self.indexed_properties {
+            IndexedStorage::Dense(elements) => Some(elements),
+            IndexedStorage::Sparse(_) => None,
+        }
+    }
+
+    /// Gets the values of the elements if they are in dense mode, to change them in place.
+    ///
+    /// The last element must not be left as a hole.
+    #[inline]
+    pub(crate) fn dense_elements_mut(&mut self) -> Option<&mut Vec<Option<JsValue>>> {
+        match &mut self.indexed_properties {
+            IndexedStorage::Dense(elements) => Some(elements),
+            IndexedStorage::Sparse(_) => None,
+        }
+    }
+
+    /// Returns `true` if there are integer keyed properties.
+    #[inline]
+    pub(crate) fn has_index_properties(&self) -> bool {
+        !self.indexed_properties.is_empty()
+    }
+
+    /// Gets the string keyed property `key` and the slot it is stored at.
+    #[cfg(feature = "vm")]
+    #[inline]
+    pub(crate) fn string_property_slot(
+        &self,
+        key: &JsString,
+    ) -> Option<(usize, &PropertyDescriptor)> {
+        self.string_properties.get_full(key)
+    }
+
+    /// Gets the string keyed property stored at `slot`.
+    #[cfg(feature = "vm")]
+    #[inline]
+    pub(crate) fn string_property_at(&self, slot: usize) -> Option<&PropertyDescriptor> {
+        self.string_properties.get_index(slot)
+    }
+
+    /// Sets the value of the data property stored at `slot`, which keeps the shape of the map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no data property at `slot`.
+    #[cfg(feature = "vm")]
+    #[inline]
+    pub(crate) fn set_string_property_value_at(&mut self, slot: usize, value: JsValue) {
+        self.string_properties
+            .get_index_mut(slot)
+            .expect("the slot must hold a property")
+            .fill_with(PropertyDescriptor::builder().value(value).build());
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order. The iterator element type is `(PropertyKey, Property)`.
     ///
     /// This iterator does not recurse down the prototype chain.
     #[inline]
     pub fn iter(&self) -> Iter<'_> {
         Iter {
             indexed_properties: self.indexed_properties.iter(),
-            string_properties: self.string_properties.0.iter(),
+            string_properties: self.string_properties.iter(),
             symbol_properties: self.symbol_properties.0.iter(),
         }
     }
@@ -91,7 +524,7 @@ impl PropertyMap {
         Keys(self.iter())
     }
 
-    /// An iterator visiting all values in arbitrary order. The iterator element type is `&'a Property`.
+    /// An iterator visiting all values in arbitrary order. The iterator element type is `Property`.
     ///
     /// This iterator does not recurse down the prototype chain.
     #[inline]
@@ -124,7 +557,7 @@ impl PropertyMap {
         SymbolPropertyValues(self.symbol_properties.0.values())
     }
 
-    /// An iterator visiting all indexed key-value pairs in arbitrary order. The iterator element type is `(&'a u32, &'a Property)`.
+    /// An iterator visiting all indexed key-value pairs in arbitrary order. The iterator element type is `(u32, Property)`.
     ///
     /// This iterator does not recurse down the prototype chain.
     #[inline]
@@ -132,20 +565,20 @@ impl PropertyMap {
         IndexProperties(self.indexed_properties.iter())
     }
 
-    /// An iterator visiting all index keys in arbitrary order. The iterator element type is `&'a u32`.
+    /// An iterator visiting all index keys in arbitrary order. The iterator element type is `u32`.
     ///
     /// This iterator does not recurse down the prototype chain.
     #[inline]
     pub fn index_property_keys(&self) -> IndexPropertyKeys<'_> {
-        IndexPropertyKeys(self.indexed_properties.keys())
+        IndexPropertyKeys(self.indexed_properties.iter())
     }
 
-    /// An iterator visiting all index values in arbitrary order. The iterator element type is `&'a Property`.
+    /// An iterator visiting all index values in arbitrary order. The iterator element type is `Property`.
     ///
     /// This iterator does not recurse down the prototype chain.
     #[inline]
     pub fn index_property_values(&self) -> IndexPropertyValues<'_> {
-        IndexPropertyValues(self.indexed_properties.values())
+        IndexPropertyValues(self.indexed_properties.iter())
     }
 
     /// An iterator visiting all string key-value pairs in arbitrary order. The iterator element type is `(&'a RcString, &'a Property)`.
@@ -153,7 +586,7 @@ impl PropertyMap {
     /// This iterator does not recurse down the prototype chain.
     #[inline]
     pub fn string_properties(&self) -> StringProperties<'_> {
-        StringProperties(self.string_properties.0.iter())
+        StringProperties(self.string_properties.iter())
     }
 
     /// An iterator visiting all string keys in arbitrary order. The iterator element type is `&'a RcString`.
@@ -161,7 +594,7 @@ impl PropertyMap {
     /// This iterator does not recurse down the prototype chain.
     #[inline]
     pub fn string_property_keys(&self) -> StringPropertyKeys<'_> {
-        StringPropertyKeys(self.string_properties.0.keys())
+        StringPropertyKeys(self.string_properties.iter())
     }
 
     /// An iterator visiting all string values in arbitrary order. The iterator element type is `&'a Property`.
@@ -169,14 +602,14 @@ impl PropertyMap {
     /// This iterator does not recurse down the prototype chain.
     #[inline]
     pub fn string_property_values(&self) -> StringPropertyValues<'_> {
-        StringPropertyValues(self.string_properties.0.values())
+        StringPropertyValues(self.string_properties.iter())
     }
 
     #[inline]
     pub fn contains_key(&self, key: &PropertyKey) -> bool {
         match key {
-            PropertyKey::Index(index) => self.indexed_properties.contains_key(index),
-            PropertyKey::String(string) => self.string_properties.0.contains_key(string),
+            PropertyKey::Index(index) => self.indexed_properties.contains_key(*index),
+            PropertyKey::String(string) => self.string_properties.get_full(string).is_some(),
             PropertyKey::Symbol(symbol) => self.symbol_properties.0.contains_key(symbol),
         }
     }
@@ -185,21 +618,21 @@ impl PropertyMap {
 /// An iterator over the property entries of an `Object`
 #[derive(Debug, Clone)]
 pub struct Iter<'a> {
-    indexed_properties: hash_map::Iter<'a, u32, PropertyDescriptor>,
-    string_properties: indexmap::map::Iter<'a, JsString, PropertyDescriptor>,
+    indexed_properties: IndexedIter<'a>,
+    string_properties: StringIter<'a>,
     symbol_properties: indexmap::map::Iter<'a, JsSymbol, PropertyDescriptor>,
 }
 
 impl<'a> Iterator for Iter<'a> {
-    type Item = (PropertyKey, &'a PropertyDescriptor);
+    type Item = (PropertyKey, PropertyDescriptor);
     fn next(&mut self) -> Option<Self::Item> {
         if let Some((key, value)) = self.indexed_properties.next() {
-            Some(((*key).into(), value))
+            Some((key.into(), value))
         } else if let Some((key, value)) = self.string_properties.next() {
-            Some((key.clone().into(), value))
+            Some((key.clone().into(), value.clone()))
         } else {
             let (key, value) = self.symbol_properties.next()?;
-            Some((key.clone().into(), value))
+            Some((key.clone().into(), value.clone()))
         }
     }
 }
@@ -239,7 +672,7 @@ impl FusedIterator for Keys<'_> {}
 pub struct Values<'a>(Iter<'a>);
 
 impl<'a> Iterator for Values<'a> {
-    type Item = &'a PropertyDescriptor;
+    type Item = PropertyDescriptor;
     fn next(&mut self) -> Option<Self::Item> {
         let (_, value) = self.0.next()?;
         Some(value)
@@ -338,10 +771,10 @@ impl FusedIterator for SymbolPropertyValues<'_> {}
 
 /// An iterator over the indexed property entries of an `Object`
 #[derive(Debug, Clone)]
-pub struct IndexProperties<'a>(hash_map::Iter<'a, u32, PropertyDescriptor>);
+pub struct IndexProperties<'a>(IndexedIter<'a>);
 
 impl<'a> Iterator for IndexProperties<'a> {
-    type Item = (&'a u32, &'a PropertyDescriptor);
+    type Item = (u32, PropertyDescriptor);
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
@@ -365,14 +798,14 @@ impl FusedIterator for IndexProperties<'_> {}
 
 /// An iterator over the index keys (`u32`) of an `Object`.
 #[derive(Debug, Clone)]
-pub struct IndexPropertyKeys<'a>(hash_map::Keys<'a, u32, PropertyDescriptor>);
+pub struct IndexPropertyKeys<'a>(IndexedIter<'a>);
 
 impl<'a> Iterator for IndexPropertyKeys<'a> {
-    type Item = &'a u32;
+    type Item = u32;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.next()
+        self.0.next().map(|(key, _)| key)
     }
 
     #[inline]
@@ -392,14 +825,14 @@ impl FusedIterator for IndexPropertyKeys<'_> {}
 
 /// An iterator over the index values (`Property`) of an `Object`.
 #[derive(Debug, Clone)]
-pub struct IndexPropertyValues<'a>(hash_map::Values<'a, u32, PropertyDescriptor>);
+pub struct IndexPropertyValues<'a>(IndexedIter<'a>);
 
 impl<'a> Iterator for IndexPropertyValues<'a> {
-    type Item = &'a PropertyDescriptor;
+    type Item = PropertyDescriptor;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.next()
+        self.0.next().map(|(_, value)| value)
     }
 
     #[inline]
@@ -419,7 +852,7 @@ impl FusedIterator for IndexPropertyValues<'_> {}
 
 /// An iterator over the `String` property entries of an `Object`
 #[derive(Debug, Clone)]
-pub struct StringProperties<'a>(indexmap::map::Iter<'a, JsString, PropertyDescriptor>);
+pub struct StringProperties<'a>(StringIter<'a>);
 
 impl<'a> Iterator for StringProperties<'a> {
     type Item = (&'a JsString, &'a PropertyDescriptor);
@@ -446,14 +879,14 @@ impl FusedIterator for StringProperties<'_> {}
 
 /// An iterator over the string keys (`RcString`) of an `Object`.
 #[derive(Debug, Clone)]
-pub struct StringPropertyKeys<'a>(indexmap::map::Keys<'a, JsString, PropertyDescriptor>);
+pub struct StringPropertyKeys<'a>(StringIter<'a>);
 
 impl<'a> Iterator for StringPropertyKeys<'a> {
     type Item = &'a JsString;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.next()
+        self.0.next().map(|(key, _)| key)
     }
 
     #[inline]
@@ -473,14 +906,14 @@ impl FusedIterator for StringPropertyKeys<'_> {}
 
 /// An iterator over the string values (`Property`) of an `Object`.
 #[derive(Debug, Clone)]
-pub struct StringPropertyValues<'a>(indexmap::map::Values<'a, JsString, PropertyDescriptor>);
+pub struct StringPropertyValues<'a>(StringIter<'a>);
 
 impl<'a> Iterator for StringPropertyValues<'a> {
     type Item = &'a PropertyDescriptor;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.next()
+        self.0.next().map(|(_, value)| value)
     }
 
     #[inline]
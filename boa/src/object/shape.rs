@@ -0,0 +1,159 @@
+//! Shapes, which describe the layout of the string keyed properties of objects.
+//!
+//! Objects that get the same properties in the same order, with the same attributes, share a
+//! shape, which maps the keys of the properties to the slots the objects store them at. The
+//! shapes form a transition tree rooted at the empty shape: adding a property to an object moves
+//! it to the child of its shape for the key and the attributes of the property, which is created
+//! the first time an object takes that transition.
+//!
+//! An object whose properties are deleted, or that has many properties, leaves the tree and keeps
+//! its properties in a hash map instead, which is called dictionary mode.
+
+use crate::{
+    gc::{empty_trace, Finalize, Trace},
+    property::PropertyDescriptor,
+    JsString,
+};
+use indexmap::IndexMap;
+use rustc_hash::{FxHashMap, FxHasher};
+use std::{
+    cell::RefCell,
+    hash::BuildHasherDefault,
+    rc::{Rc, Weak},
+};
+
+/// The number of properties after which an object switches to dictionary mode.
+///
+/// Every shape has a table of all its properties, so this bounds the size of the tables of the
+/// shapes of a branch of the tree.
+pub(crate) const MAX_SHAPED_PROPERTIES: usize = 64;
+
+/// The attributes of a property that are part of a shape: whether it is an accessor property,
+/// and its `[[Writable]]`, `[[Enumerable]]` and `[[Configurable]]` attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct Attributes([Option<bool>; 4]);
+
+impl Attributes {
+    /// Gets the attributes of `property`.
+    pub(crate) fn of(property: &PropertyDescriptor) -> Self {
+        Self([
+            Some(property.is_accessor_descriptor()),
+            property.writable(),
+            property.enumerable(),
+            property.configurable(),
+        ])
+    }
+}
+
+/// The layout of the string keyed properties of objects.
+///
+/// Shapes are compared by identity: two objects with equal shapes store the property with a given
+/// key at the same slot, with the same attributes.
+#[derive(Debug, Clone)]
+pub(crate) struct Shape(Rc<Inner>);
+
+#[derive(Debug)]
+struct Inner {
+    /// The shape this one is a transition from, or `None` for a root.
+    parent: Option<Shape>,
+    /// The keys of the properties in slot order, with their attributes.
+    properties: IndexMap<JsString, Attributes, BuildHasherDefault<FxHasher>>,
+    /// The shapes that adding a property to this one leads to. They are kept alive by the objects
+    /// that have them, not by their parent.
+    transitions: RefCell<FxHashMap<(JsString, Attributes), Weak<Inner>>>,
+}
+
+thread_local! {
+    /// The root of the transition tree.
+    static ROOT: Shape = Shape::unique();
+}
+
+impl PartialEq for Shape {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Shape {}
+
+impl Finalize for Shape {}
+
+// Safety: `Shape` does not contain any object that require trace,
+// so this is safe.
+unsafe impl Trace for Shape {
+    empty_trace!();
+}
+
+impl Shape {
+    /// Gets the shape of objects without string keyed properties, the root of the transition tree.
+    pub(crate) fn root() -> Self {
+        ROOT.with(Self::clone)
+    }
+
+    /// Creates an empty shape that is not part of the transition tree, for an object in
+    /// dictionary mode.
+    pub(crate) fn unique() -> Self {
+        Self(Rc::new(Inner {
+            parent: None,
+            properties: IndexMap::default(),
+            transitions: RefCell::default(),
+        }))
+    }
+
+    /// Gets the number of properties of the shape.
+    pub(crate) fn len(&self) -> usize {
+        self.0.properties.len()
+    }
+
+    /// Gets the slot and the attributes of the property `key`.
+    pub(crate) fn lookup(&self, key: &JsString) -> Option<(usize, Attributes)> {
+        let (slot, _, attributes) = self.0.properties.get_full(key)?;
+        Some((slot, *attributes))
+    }
+
+    /// Gets the keys of the properties in slot order.
+    pub(crate) fn keys(&self) -> indexmap::map::Keys<'_, JsString, Attributes> {
+        self.0.properties.keys()
+    }
+
+    /// Gets the shape this one is a transition from.
+    pub(crate) fn parent(&self) -> Option<&Self> {
+        self.0.parent.as_ref()
+    }
+
+    /// Gets the shape that has the properties of this one followed by `key`.
+    pub(crate) fn insert(&self, key: JsString, attributes: Attributes) -> Self {
+        let mut transitions = self.0.transitions.borrow_mut();
+        let transition = (key, attributes);
+        if let Some(shape) = transitions.get(&transition).and_then(Weak::upgrade) {
+            return Self(shape);
+        }
+
+        let mut properties = self.0.properties.clone();
+        properties.insert(transition.0.clone(), attributes);
+        let shape = Rc::new(Inner {
+            parent: Some(self.clone()),
+            properties,
+            transitions: RefCell::default(),
+        });
+        transitions.retain(|_, shape| shape.strong_count() > 0);
+        transitions.insert(transition, Rc::downgrade(&shape));
+        Self(shape)
+    }
+
+    /// Gets the shape that has the properties of this one, with the attributes of the property at
+    /// `slot` replaced by `attributes`.
+    pub(crate) fn change_attributes(&self, slot: usize, attributes: Attributes) -> Self {
+        let mut root = self;
+        while let Some(parent) = root.parent() {
+            root = parent;
+        }
+        self.0
+            .properties
+            .iter()
+            .enumerate()
+            .fold(root.clone(), |shape, (i, (key, &old))| {
+                shape.insert(key.clone(), if i == slot { attributes } else { old })
+            })
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{check_output, exec, TestAction};
+use crate::{check_output, exec, Context, JsValue, TestAction};
 
 #[test]
 fn ordinary_has_instance_nonobject_prototype() {
@@ -44,3 +44,133 @@ fn object_properties_return_order() {
         ),
     ]);
 }
+
+#[test]
+fn objects_share_shapes() {
+    let mut context = Context::default();
+    let objects = context
+        .eval(
+            r#"
+            function point(x, y) { return { x, y }; }
+            var frozen = Object.freeze(point(1, 2));
+            [point(1, 2), point(3, 4), { y: 1, x: 2 }, frozen, Object.freeze(point(5, 6))]
+            "#,
+        )
+        .unwrap();
+    let objects = objects.as_object().unwrap();
+    let mut shape = |index: u32| {
+        let object = objects.get(index, &mut context).unwrap();
+        let object = object.as_object().unwrap().borrow();
+        assert!(!object.properties().is_dictionary());
+        object.properties().shape().clone()
+    };
+
+    assert_eq!(shape(0), shape(1));
+    assert_ne!(shape(0), shape(2));
+    assert_ne!(shape(0), shape(3));
+    assert_eq!(shape(3), shape(4));
+}
+
+#[test]
+fn shapes_fall_back_to_dictionary_mode() {
+    let mut context = Context::default();
+    let shape_of = |value: JsValue| {
+        let object = value.as_object().unwrap().borrow();
+        (
+            object.properties().shape().clone(),
+            object.properties().is_dictionary(),
+        )
+    };
+
+    let (with_x, _) = shape_of(context.eval("({ x: 1 })").unwrap());
+    let (last_deleted, dictionary) = shape_of(
+        context
+            .eval("var o = { x: 1, y: 2 }; delete o.y; o")
+            .unwrap(),
+    );
+    assert_eq!(last_deleted, with_x);
+    assert!(!dictionary);
+
+    let (_, dictionary) = shape_of(
+        context
+            .eval("var o = { x: 1, y: 2, z: 3 }; delete o.y; o")
+            .unwrap(),
+    );
+    assert!(dictionary);
+
+    let (_, dictionary) = shape_of(
+        context
+            .eval("var o = {}; for (var i = 0; i < 100; i++) { o['p' + i] = i; } o")
+            .unwrap(),
+    );
+    assert!(dictionary);
+}
+
+#[test]
+fn dictionary_mode_keeps_property_order() {
+    let scenario = r#"
+        var o = { a: 1, b: 2, c: 3, d: 4 };
+        delete o.b;
+        o.e = 5;
+        o.b = 6;
+        Object.defineProperty(o, 'c', { enumerable: false });
+        var big = {};
+        for (var i = 0; i < 70; i++) { big['p' + i] = i; }
+        delete big.p3;
+        var bigKeys = Object.keys(big);
+        "#;
+
+    check_output(&[
+        TestAction::Execute(scenario),
+        TestAction::TestEq("Object.keys(o)", r#"[ "a", "d", "e", "b" ]"#),
+        TestAction::TestEq(
+            "Object.getOwnPropertyNames(o)",
+            r#"[ "a", "c", "d", "e", "b" ]"#,
+        ),
+        TestAction::TestEq("o.b + o.c + o.e", "14"),
+        TestAction::TestEq("bigKeys.length", "69"),
+        TestAction::TestEq("bigKeys[3] + bigKeys[68]", r#""p4p69""#),
+        TestAction::TestEq("big.p69", "69"),
+    ]);
+}
+
+#[test]
+fn elements_fall_back_to_sparse_mode() {
+    let mut context = Context::default();
+    let mut is_dense = |source: &str| {
+        let value = context.eval(source).unwrap();
+        let object = value.as_object().unwrap().borrow();
+        object.properties().dense_elements().is_some()
+    };
+
+    assert!(is_dense("[1, 2, 3]"));
+    assert!(is_dense("var a = [1, 2, 3]; a[10] = 4; delete a[1]; a"));
+    assert!(is_dense(
+        "var a = []; for (var i = 0; i < 1000; i++) { a.push(i); } a"
+    ));
+    assert!(!is_dense("var a = [1, 2, 3]; a[100000] = 4; a"));
+    assert!(!is_dense("Object.freeze([1, 2, 3])"));
+    assert!(!is_dense(
+        "var a = [1, 2, 3]; Object.defineProperty(a, 1, { get() { return 0; } }); a"
+    ));
+}
+
+#[test]
+fn sparse_elements_keep_their_values() {
+    let scenario = r#"
+        var a = [1, 2, 3];
+        delete a[1];
+        a[100000] = 4;
+        a[2] = 5;
+        Object.defineProperty(a, 0, { enumerable: false });
+        "#;
+
+    check_output(&[
+        TestAction::Execute(scenario),
+        TestAction::TestEq("a.length", "100001"),
+        TestAction::TestEq("Object.keys(a)", r#"[ "2", "100000" ]"#),
+        TestAction::TestEq("a[0] + a[2] + a[100000]", "10"),
+        TestAction::TestEq("1 in a", "false"),
+        TestAction::TestEq("a.length = 3; a[100000]", "undefined"),
+    ]);
+}
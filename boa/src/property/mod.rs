@@ -16,9 +16,11 @@
 
 use crate::{
     gc::{Finalize, Trace},
+    interner::Sym,
     JsString, JsSymbol, JsValue,
 };
-use std::{convert::TryFrom, fmt};
+use rustc_hash::FxHashMap;
+use std::{cell::RefCell, convert::TryFrom, fmt};
 
 mod attribute;
 pub use attribute::Attribute;
@@ -480,10 +482,24 @@ pub enum PropertyKey {
     Index(u32),
 }
 
+/// Parses the code units of a string as a `u32`, accepting the same input as `str::parse`.
+#[inline]
+fn parse_u32_index(string: &[u16]) -> Option<u32> {
+    let digits = string.strip_prefix(&[u16::from(b'+')]).unwrap_or(string);
+    if digits.is_empty() {
+        return None;
+    }
+
+    digits.iter().try_fold(0u32, |acc, &unit| {
+        let digit = char::from_u32(u32::from(unit))?.to_digit(10)?;
+        acc.checked_mul(10)?.checked_add(digit)
+    })
+}
+
 impl From<JsString> for PropertyKey {
     #[inline]
     fn from(string: JsString) -> PropertyKey {
-        if let Ok(index) = string.parse() {
+        if let Some(index) = parse_u32_index(&string) {
             PropertyKey::Index(index)
         } else {
             PropertyKey::String(string)
@@ -524,6 +540,23 @@ impl From<Box<str>> for PropertyKey {
     }
 }
 
+thread_local! {
+    /// The property keys of the interned names, so that they are only allocated once per thread.
+    static SYM_KEYS: RefCell<FxHashMap<Sym, PropertyKey>> = RefCell::default();
+}
+
+impl From<Sym> for PropertyKey {
+    #[inline]
+    fn from(sym: Sym) -> PropertyKey {
+        SYM_KEYS.with(|keys| {
+            keys.borrow_mut()
+                .entry(sym)
+                .or_insert_with(|| PropertyKey::from(sym.as_str()))
+                .clone()
+        })
+    }
+}
+
 impl From<JsSymbol> for PropertyKey {
     #[inline]
     fn from(symbol: JsSymbol) -> PropertyKey {
@@ -1,16 +1,21 @@
 use crate::{
     builtins::string::is_trimmable_whitespace,
     gc::{empty_trace, Finalize, Trace},
+    interner::Sym,
 };
 use rustc_hash::FxHashSet;
 use std::{
     alloc::{alloc, dealloc, Layout},
     borrow::Borrow,
-    cell::Cell,
+    cell::{Cell, UnsafeCell},
+    char::decode_utf16,
+    fmt::Write,
     hash::{Hash, Hasher},
     marker::PhantomData,
+    mem::ManuallyDrop,
     ops::Deref,
-    ptr::{copy_nonoverlapping, NonNull},
+    ptr::{copy_nonoverlapping, drop_in_place, NonNull},
+    string::FromUtf16Error,
 };
 
 const CONSTANTS_ARRAY: [&str; 127] = [
@@ -179,8 +184,9 @@ thread_local! {
         let mut constants = FxHashSet::default();
 
         for s in CONSTANTS_ARRAY.iter() {
+            let units = s.encode_utf16().collect::<Vec<_>>();
             let s = JsString {
-                inner: Inner::new(s),
+                inner: Inner::new(&units),
                 _marker: PhantomData,
             };
             constants.insert(s);
@@ -190,10 +196,101 @@ thread_local! {
     };
 }
 
+/// Creates a `&'static [u16]` from a string literal, encoding it as UTF-16 at compile time.
+macro_rules! utf16 {
+    ($s:literal) => {{
+        const STRING: &str = $s;
+        const UNITS: [u16; $crate::string::utf16_len(STRING)] =
+            $crate::string::encode_utf16(STRING);
+        &UNITS as &[u16]
+    }};
+}
+
+pub(crate) use utf16;
+
+/// Returns the number of UTF-16 code units needed to encode `s`.
+#[doc(hidden)]
+pub(crate) const fn utf16_len(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    let mut len = 0;
+    while i < bytes.len() {
+        let (width, units) = match bytes[i] {
+            0x00..=0x7F => (1, 1),
+            0x80..=0xDF => (2, 1),
+            0xE0..=0xEF => (3, 1),
+            _ => (4, 2),
+        };
+        i += width;
+        len += units;
+    }
+    len
+}
+
+/// Encodes `s` as UTF-16 in a `const` context, `N` must be [`utf16_len`] of `s`.
+#[doc(hidden)]
+pub(crate) const fn encode_utf16<const N: usize>(s: &str) -> [u16; N] {
+    const fn continuation(byte: u8) -> u32 {
+        (byte & 0x3F) as u32
+    }
+
+    let bytes = s.as_bytes();
+    let mut units = [0; N];
+    let mut i = 0;
+    let mut j = 0;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        let (code_point, width) = match byte {
+            0x00..=0x7F => (byte as u32, 1),
+            0x80..=0xDF => (((byte & 0x1F) as u32) << 6 | continuation(bytes[i + 1]), 2),
+            0xE0..=0xEF => (
+                ((byte & 0x0F) as u32) << 12
+                    | continuation(bytes[i + 1]) << 6
+                    | continuation(bytes[i + 2]),
+                3,
+            ),
+            _ => (
+                ((byte & 0x07) as u32) << 18
+                    | continuation(bytes[i + 1]) << 12
+                    | continuation(bytes[i + 2]) << 6
+                    | continuation(bytes[i + 3]),
+                4,
+            ),
+        };
+        if code_point >= 0x10000 {
+            let code_point = code_point - 0x10000;
+            units[j] = (0xD800 | (code_point >> 10)) as u16;
+            units[j + 1] = (0xDC00 | (code_point & 0x3FF)) as u16;
+            j += 2;
+        } else {
+            units[j] = code_point as u16;
+            j += 1;
+        }
+        i += width;
+    }
+    units
+}
+
+/// Concatenations producing fewer code units than this are copied eagerly instead of
+/// creating a rope node, since copying a short string is cheaper than the extra indirection.
+const MIN_ROPE_LENGTH: usize = 256;
+
+/// How the code units of an [`Inner`] are stored.
+enum Repr {
+    /// The code units are stored inline, right after the `Inner` header.
+    Flat,
+
+    /// The string is the concatenation of two strings, and has not been flattened yet.
+    Rope(JsString, JsString),
+
+    /// The string was a rope which has been flattened into a separate buffer.
+    Flattened(Box<[u16]>),
+}
+
 /// The inner representation of a [`JsString`].
 #[repr(C)]
 struct Inner {
-    /// The utf8 length, the number of bytes.
+    /// The utf16 length, the number of code units.
     len: usize,
 
     /// The number of references to the string.
@@ -201,49 +298,26 @@ struct Inner {
     /// When this reaches `0` the string is deallocated.
     refcount: Cell<usize>,
 
+    /// The storage of the code units.
+    ///
+    /// This only ever changes from [`Repr::Rope`] to [`Repr::Flattened`], and no slice of the
+    /// string is handed out before that, so slices stay valid for the lifetime of the `Inner`.
+    repr: UnsafeCell<Repr>,
+
     /// An empty array which is used to get the offset of string data.
-    data: [u8; 0],
+    data: [u16; 0],
 }
 
 impl Inner {
-    /// Create a new `Inner` from `&str`.
+    /// Create a new `Inner` from `&[u16]`.
     #[inline]
-    fn new(s: &str) -> NonNull<Self> {
-        // We get the layout of the `Inner` type and we extend by the size
-        // of the string array.
-        let inner_layout = Layout::new::<Inner>();
-        let (layout, offset) = inner_layout
-            .extend(Layout::array::<u8>(s.len()).unwrap())
-            .unwrap();
-
-        let inner = unsafe {
-            let inner = alloc(layout) as *mut Inner;
-
-            // Write the first part, the Inner.
-            inner.write(Inner {
-                len: s.len(),
-                refcount: Cell::new(1),
-                data: [0; 0],
-            });
-
-            // Get offset into the string data.
-            let data = (*inner).data.as_mut_ptr();
-
-            debug_assert!(std::ptr::eq(inner.cast::<u8>().add(offset), data));
-
-            // Copy string data into data offset.
-            copy_nonoverlapping(s.as_ptr(), data, s.len());
-
-            inner
-        };
-
-        // Safety: We already know it's not null, so this is safe.
-        unsafe { NonNull::new_unchecked(inner) }
+    fn new(s: &[u16]) -> NonNull<Self> {
+        Self::concat_array(&[s])
     }
 
     /// Concatenate array of strings.
     #[inline]
-    fn concat_array(strings: &[&str]) -> NonNull<Inner> {
+    fn concat_array(strings: &[&[u16]]) -> NonNull<Inner> {
         let mut total_string_size = 0;
         for string in strings {
             total_string_size += string.len();
@@ -253,7 +327,7 @@ impl Inner {
         // of the string array.
         let inner_layout = Layout::new::<Inner>();
         let (layout, offset) = inner_layout
-            .extend(Layout::array::<u8>(total_string_size).unwrap())
+            .extend(Layout::array::<u16>(total_string_size).unwrap())
             .unwrap();
 
         let inner = unsafe {
@@ -263,15 +337,16 @@ impl Inner {
             inner.write(Inner {
                 len: total_string_size,
                 refcount: Cell::new(1),
+                repr: UnsafeCell::new(Repr::Flat),
                 data: [0; 0],
             });
 
             // Get offset into the string data.
             let data = (*inner).data.as_mut_ptr();
 
-            debug_assert!(std::ptr::eq(inner.cast::<u8>().add(offset), data));
+            debug_assert!(std::ptr::eq(inner.cast::<u8>().add(offset).cast(), data));
 
-            // Copy the two string data into data offset.
+            // Copy the string data into data offset.
             let mut offset = 0;
             for string in strings {
                 copy_nonoverlapping(string.as_ptr(), data.add(offset), string.len());
@@ -285,30 +360,102 @@ impl Inner {
         unsafe { NonNull::new_unchecked(inner) }
     }
 
+    /// Create a new rope `Inner` from the concatenation of two strings.
+    #[inline]
+    fn rope(x: JsString, y: JsString) -> NonNull<Inner> {
+        let layout = Layout::new::<Inner>();
+
+        unsafe {
+            let inner = alloc(layout) as *mut Inner;
+
+            inner.write(Inner {
+                len: x.len() + y.len(),
+                refcount: Cell::new(1),
+                repr: UnsafeCell::new(Repr::Rope(x, y)),
+                data: [0; 0],
+            });
+
+            // Safety: `alloc` only returns null on allocation failure, which aborts.
+            NonNull::new_unchecked(inner)
+        }
+    }
+
     /// Deallocate inner type with string data.
+    ///
+    /// The caller must make sure that the `Inner` is not a rope anymore, see [`JsString::drop`].
     #[inline]
     unsafe fn dealloc(x: NonNull<Inner>) {
-        let len = (*x.as_ptr()).len;
-
-        let inner_layout = Layout::new::<Inner>();
-        let (layout, _offset) = inner_layout
-            .extend(Layout::array::<u8>(len).unwrap())
-            .unwrap();
+        let layout = match &*(*x.as_ptr()).repr.get() {
+            Repr::Flat => {
+                let len = (*x.as_ptr()).len;
+
+                let inner_layout = Layout::new::<Inner>();
+                let (layout, _offset) = inner_layout
+                    .extend(Layout::array::<u16>(len).unwrap())
+                    .unwrap();
+                layout
+            }
+            Repr::Rope(..) | Repr::Flattened(_) => Layout::new::<Inner>(),
+        };
 
+        drop_in_place(x.as_ptr());
         dealloc(x.as_ptr() as _, layout);
     }
 }
 
+/// A single code point of a [`JsString`], as returned by [`JsString::code_points`].
+///
+/// Since JavaScript strings are arbitrary sequences of UTF-16 code units, a code point
+/// is either a valid Unicode scalar value or an unpaired surrogate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CodePoint {
+    Unicode(char),
+    UnpairedSurrogate(u16),
+}
+
+impl CodePoint {
+    /// Get the number of UTF-16 code units needed to encode this code point.
+    #[inline]
+    pub fn code_unit_count(self) -> usize {
+        match self {
+            Self::Unicode(c) => c.len_utf16(),
+            Self::UnpairedSurrogate(_) => 1,
+        }
+    }
+
+    /// Convert the code point to its [`u32`] representation.
+    #[inline]
+    pub fn as_u32(self) -> u32 {
+        match self {
+            Self::Unicode(c) => u32::from(c),
+            Self::UnpairedSurrogate(surr) => u32::from(surr),
+        }
+    }
+
+    /// If the code point is a valid Unicode scalar value, return it as a [`char`].
+    #[inline]
+    pub fn as_char(self) -> Option<char> {
+        match self {
+            Self::Unicode(c) => Some(c),
+            Self::UnpairedSurrogate(_) => None,
+        }
+    }
+}
+
 /// This represents a JavaScript primitive string.
 ///
-/// This is similar to `Rc<str>`. But unlike `Rc<str>` which stores the length
+/// A JavaScript string is a sequence of UTF-16 code units, which may contain unpaired
+/// surrogates, so the data is stored as `[u16]` rather than as a Rust `str`. Use
+/// [`JsString::to_std_string`] or [`JsString::to_std_string_lossy`] to get a Rust `String`.
+///
+/// This is similar to `Rc<[u16]>`. But unlike `Rc<[u16]>` which stores the length
 /// on the stack and a pointer to the data (this is also known as fat pointers).
 /// The `JsString` length and data is stored on the heap. and just an non-null
 /// pointer is kept, so its size is the size of a pointer.
 #[derive(Finalize)]
 pub struct JsString {
     inner: NonNull<Inner>,
-    _marker: PhantomData<std::rc::Rc<str>>,
+    _marker: PhantomData<std::rc::Rc<[u16]>>,
 }
 
 impl Default for JsString {
@@ -325,19 +472,26 @@ impl JsString {
         JsString::default()
     }
 
-    /// Create a new JavaScript string.
+    /// Create a new JavaScript string from a Rust string.
     #[inline]
     pub fn new<S: AsRef<str>>(s: S) -> Self {
-        let s = s.as_ref();
+        let units = s.as_ref().encode_utf16().collect::<Vec<_>>();
+        Self::from_utf16(&units)
+    }
 
-        if s.len() <= MAX_CONSTANT_STRING_LENGTH {
-            if let Some(constant) = CONSTANTS.with(|c| c.get(s).cloned()) {
+    /// Create a new JavaScript string from a slice of UTF-16 code units.
+    ///
+    /// Unlike [`String::from_utf16`] this never fails, unpaired surrogates are preserved.
+    #[inline]
+    pub fn from_utf16(units: &[u16]) -> Self {
+        if units.len() <= MAX_CONSTANT_STRING_LENGTH {
+            if let Some(constant) = CONSTANTS.with(|c| c.get(units).cloned()) {
                 return constant;
             }
         }
 
         Self {
-            inner: Inner::new(s),
+            inner: Inner::new(units),
             _marker: PhantomData,
         }
     }
@@ -345,28 +499,36 @@ impl JsString {
     /// Concatenate two string.
     pub fn concat<T, U>(x: T, y: U) -> JsString
     where
-        T: AsRef<str>,
-        U: AsRef<str>,
+        T: AsRef<[u16]>,
+        U: AsRef<[u16]>,
     {
-        let x = x.as_ref();
-        let y = y.as_ref();
-
-        let this = Self {
-            inner: Inner::concat_array(&[x, y]),
-            _marker: PhantomData,
-        };
+        Self::concat_array(&[x.as_ref(), y.as_ref()])
+    }
 
-        if this.len() <= MAX_CONSTANT_STRING_LENGTH {
-            if let Some(constant) = CONSTANTS.with(|c| c.get(&this).cloned()) {
-                return constant;
-            }
+    /// Concatenate two strings without copying their code units.
+    ///
+    /// The result is a rope which refers to both strings and is flattened the first time its
+    /// code units are accessed, so building a string by repeatedly appending to it takes linear
+    /// time. Short results are copied eagerly.
+    pub fn concat_rope(x: &JsString, y: &JsString) -> JsString {
+        if x.is_empty() {
+            return y.clone();
+        }
+        if y.is_empty() {
+            return x.clone();
+        }
+        if x.len() + y.len() < MIN_ROPE_LENGTH {
+            return Self::concat(x, y);
         }
 
-        this
+        Self {
+            inner: Inner::rope(x.clone(), y.clone()),
+            _marker: PhantomData,
+        }
     }
 
     /// Concatenate array of string.
-    pub fn concat_array(strings: &[&str]) -> JsString {
+    pub fn concat_array(strings: &[&[u16]]) -> JsString {
         let this = Self {
             inner: Inner::concat_array(strings),
             _marker: PhantomData,
@@ -387,17 +549,117 @@ impl JsString {
         unsafe { self.inner.as_ref() }
     }
 
-    /// Return the JavaScript string as a rust `&str`.
+    /// Get the number of UTF-16 code units in the string.
+    ///
+    /// Unlike going through [`JsString::as_slice`], this does not flatten ropes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner().len
+    }
+
+    /// Returns `true` if the string has no code units.
     #[inline]
-    pub fn as_str(&self) -> &str {
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the JavaScript string as a slice of UTF-16 code units.
+    ///
+    /// If the string is a rope, it is flattened first.
+    #[inline]
+    pub fn as_slice(&self) -> &[u16] {
         let inner = self.inner();
 
+        // Safety: See the documentation of `Inner::repr`.
+        match unsafe { &*inner.repr.get() } {
+            Repr::Flat => unsafe { std::slice::from_raw_parts(inner.data.as_ptr(), inner.len) },
+            Repr::Flattened(units) => units,
+            Repr::Rope(..) => self.flatten(),
+        }
+    }
+
+    /// Flatten a rope into a single buffer of code units.
+    ///
+    /// The rope is traversed iteratively, since ropes built by appending in a loop are as deep
+    /// as the number of iterations.
+    #[cold]
+    fn flatten(&self) -> &[u16] {
+        let inner = self.inner();
+
+        let mut units = Vec::with_capacity(inner.len);
+        let mut pending = vec![self];
+        while let Some(string) = pending.pop() {
+            // Safety: See the documentation of `Inner::repr`.
+            match unsafe { &*string.inner().repr.get() } {
+                Repr::Rope(x, y) => {
+                    pending.push(y);
+                    pending.push(x);
+                }
+                Repr::Flat | Repr::Flattened(_) => units.extend_from_slice(string.as_slice()),
+            }
+        }
+        debug_assert_eq!(units.len(), inner.len);
+
+        // Safety: Ropes never hand out slices, so there are no references into the old
+        // representation. Dropping the children of the rope doesn't touch this string.
         unsafe {
-            let slice = std::slice::from_raw_parts(inner.data.as_ptr(), inner.len);
-            std::str::from_utf8_unchecked(slice)
+            *inner.repr.get() = Repr::Flattened(units.into_boxed_slice());
+            match &*inner.repr.get() {
+                Repr::Flattened(units) => units,
+                Repr::Flat | Repr::Rope(..) => unreachable!(),
+            }
         }
     }
 
+    /// Decode the JavaScript string into a Rust `String`.
+    ///
+    /// Returns an error if the string contains unpaired surrogates, which cannot be
+    /// represented in UTF-8.
+    #[inline]
+    pub fn to_std_string(&self) -> Result<String, FromUtf16Error> {
+        String::from_utf16(self)
+    }
+
+    /// Decode the JavaScript string into a Rust `String`, replacing unpaired surrogates
+    /// with [`char::REPLACEMENT_CHARACTER`].
+    #[inline]
+    pub fn to_std_string_lossy(&self) -> String {
+        String::from_utf16_lossy(self)
+    }
+
+    /// Decode the JavaScript string into a Rust `String`, replacing unpaired surrogates
+    /// with their `\uXXXX` escape sequence.
+    pub fn to_std_string_escaped(&self) -> String {
+        self.code_points()
+            .map(|cp| match cp {
+                CodePoint::Unicode(c) => c.to_string(),
+                CodePoint::UnpairedSurrogate(surr) => format!("\\u{:04X}", surr),
+            })
+            .collect()
+    }
+
+    /// Get an iterator over the code points of the string, as described by
+    /// the `CodePointAt` abstract operation.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-codepointat
+    #[inline]
+    pub fn code_points(&self) -> impl Iterator<Item = CodePoint> + Clone + '_ {
+        decode_utf16(self.iter().copied()).map(|res| match res {
+            Ok(c) => CodePoint::Unicode(c),
+            Err(e) => CodePoint::UnpairedSurrogate(e.unpaired_surrogate()),
+        })
+    }
+
+    /// Returns `true` if the string contains no unpaired surrogates.
+    #[inline]
+    pub fn is_well_formed(&self) -> bool {
+        self.code_points()
+            .all(|cp| matches!(cp, CodePoint::Unicode(_)))
+    }
+
     /// Gets the number of `JsString`s which point to this allocation.
     #[inline]
     pub fn refcount(this: &Self) -> usize {
@@ -421,52 +683,49 @@ impl JsString {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-stringindexof
-    pub(crate) fn index_of(&self, search_value: &Self, from_index: usize) -> Option<usize> {
+    pub(crate) fn index_of(&self, search_value: &[u16], from_index: usize) -> Option<usize> {
         // 1. Assert: Type(string) is String.
         // 2. Assert: Type(searchValue) is String.
         // 3. Assert: fromIndex is a non-negative integer.
 
         // 4. Let len be the length of string.
-        let len = self.encode_utf16().count();
+        let len = self.len();
 
         // 5. If searchValue is the empty String and fromIndex ≤ len, return fromIndex.
-        if search_value.is_empty() && from_index <= len {
-            return Some(from_index);
+        if search_value.is_empty() {
+            return if from_index <= len {
+                Some(from_index)
+            } else {
+                None
+            };
         }
 
         // 6. Let searchLen be the length of searchValue.
-        let search_len = search_value.encode_utf16().count();
-
         // 7. For each integer i starting with fromIndex such that i ≤ len - searchLen, in ascending order, do
-        for i in from_index..=len {
-            if i as isize > (len as isize - search_len as isize) {
-                break;
-            }
-
-            // a. Let candidate be the substring of string from i to i + searchLen.
-            let candidate = String::from_utf16_lossy(
-                &self
-                    .encode_utf16()
-                    .skip(i)
-                    .take(search_len)
-                    .collect::<Vec<u16>>(),
-            );
-
-            // b. If candidate is the same sequence of code units as searchValue, return i.
-            if candidate == search_value.as_str() {
-                return Some(i);
-            }
-        }
-
+        // a. Let candidate be the substring of string from i to i + searchLen.
+        // b. If candidate is the same sequence of code units as searchValue, return i.
         // 8. Return -1.
-        None
+        self.get(from_index..)?
+            .windows(search_value.len())
+            .position(|candidate| candidate == search_value)
+            .map(|i| i + from_index)
+    }
+
+    /// Trims both leading and trailing whitespace, as defined by [`is_trimmable_whitespace`].
+    #[inline]
+    pub(crate) fn trim(&self) -> &[u16] {
+        trim_end(trim_start(self))
     }
 
     pub(crate) fn string_to_number(&self) -> f64 {
-        let string = self.trim_matches(is_trimmable_whitespace);
+        // A string with unpaired surrogates can never be a valid numeric literal.
+        let string = match String::from_utf16(self.trim()) {
+            Ok(string) => string,
+            Err(_) => return f64::NAN,
+        };
 
         // TODO: write our own lexer to match syntax StrDecimalLiteral
-        match string {
+        match string.as_str() {
             "" => 0.0,
             "Infinity" | "+Infinity" => f64::INFINITY,
             "-Infinity" => f64::NEG_INFINITY,
@@ -483,11 +742,39 @@ impl JsString {
                 // Prevent fast_float from parsing "inf", "+inf" as Infinity and "-inf" as -Infinity
                 f64::NAN
             }
-            _ => fast_float::parse(string).unwrap_or(f64::NAN),
+            _ => fast_float::parse(&string).unwrap_or(f64::NAN),
         }
     }
 }
 
+/// Returns `true` if the code unit is a whitespace or line terminator code unit.
+///
+/// All trimmable whitespace is in the Basic Multilingual Plane, so it can be checked per code unit.
+#[inline]
+fn is_trimmable_code_unit(unit: u16) -> bool {
+    char::from_u32(u32::from(unit)).is_some_and(is_trimmable_whitespace)
+}
+
+/// Trims leading whitespace from a slice of UTF-16 code units.
+#[inline]
+pub(crate) fn trim_start(units: &[u16]) -> &[u16] {
+    let start = units
+        .iter()
+        .position(|unit| !is_trimmable_code_unit(*unit))
+        .unwrap_or(units.len());
+    &units[start..]
+}
+
+/// Trims trailing whitespace from a slice of UTF-16 code units.
+#[inline]
+pub(crate) fn trim_end(units: &[u16]) -> &[u16] {
+    let end = units
+        .iter()
+        .rposition(|unit| !is_trimmable_code_unit(*unit))
+        .map_or(0, |i| i + 1);
+    &units[..end]
+}
+
 // Safety: [`JsString`] does not contain any objects which recquire trace,
 // so this is safe.
 unsafe impl Trace for JsString {
@@ -515,7 +802,7 @@ impl Drop for JsString {
             // Safety: If refcount is 1 and we call drop, that means this is the last
             // JsString which points to this memory allocation, so deallocating it is safe.
             unsafe {
-                Inner::dealloc(self.inner);
+                Self::drop_last(self.inner);
             }
         } else {
             inner.refcount.set(inner.refcount.get() - 1);
@@ -523,17 +810,58 @@ impl Drop for JsString {
     }
 }
 
+impl JsString {
+    /// Deallocate a string whose last reference was dropped.
+    ///
+    /// The children of ropes are released iteratively, because dropping them recursively
+    /// could overflow the stack for deep ropes.
+    #[cold]
+    unsafe fn drop_last(inner: NonNull<Inner>) {
+        let mut pending = vec![inner];
+        while let Some(inner) = pending.pop() {
+            let repr = &mut *inner.as_ref().repr.get();
+            if let Repr::Rope(..) = repr {
+                if let Repr::Rope(x, y) = std::mem::replace(repr, Repr::Flattened(Box::default())) {
+                    for child in [ManuallyDrop::new(x), ManuallyDrop::new(y)] {
+                        let refcount = &child.inner().refcount;
+                        if refcount.get() == 1 {
+                            pending.push(child.inner);
+                        } else {
+                            refcount.set(refcount.get() - 1);
+                        }
+                    }
+                }
+            }
+            Inner::dealloc(inner);
+        }
+    }
+}
+
 impl std::fmt::Debug for JsString {
     #[inline]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.as_str().fmt(f)
+        f.write_char('"')?;
+        for cp in self.code_points() {
+            match cp {
+                CodePoint::Unicode(c) => {
+                    for c in c.escape_debug() {
+                        f.write_char(c)?;
+                    }
+                }
+                CodePoint::UnpairedSurrogate(surr) => write!(f, "\\u{{{:x}}}", surr)?,
+            }
+        }
+        f.write_char('"')
     }
 }
 
 impl std::fmt::Display for JsString {
     #[inline]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.as_str().fmt(f)
+        for c in decode_utf16(self.iter().copied()) {
+            f.write_char(c.unwrap_or(char::REPLACEMENT_CHARACTER))?;
+        }
+        Ok(())
     }
 }
 
@@ -544,6 +872,13 @@ impl From<&str> for JsString {
     }
 }
 
+impl From<Sym> for JsString {
+    #[inline]
+    fn from(s: Sym) -> Self {
+        Self::new(s.as_str())
+    }
+}
+
 impl From<Box<str>> for JsString {
     #[inline]
     fn from(s: Box<str>) -> Self {
@@ -558,26 +893,47 @@ impl From<String> for JsString {
     }
 }
 
-impl AsRef<str> for JsString {
+impl From<&[u16]> for JsString {
+    #[inline]
+    fn from(s: &[u16]) -> Self {
+        Self::from_utf16(s)
+    }
+}
+
+impl From<Vec<u16>> for JsString {
     #[inline]
-    fn as_ref(&self) -> &str {
-        self.as_str()
+    fn from(s: Vec<u16>) -> Self {
+        Self::from_utf16(&s)
     }
 }
 
-impl Borrow<str> for JsString {
+impl From<Box<[u16]>> for JsString {
     #[inline]
-    fn borrow(&self) -> &str {
-        self.as_str()
+    fn from(s: Box<[u16]>) -> Self {
+        Self::from_utf16(&s)
+    }
+}
+
+impl AsRef<[u16]> for JsString {
+    #[inline]
+    fn as_ref(&self) -> &[u16] {
+        self.as_slice()
+    }
+}
+
+impl Borrow<[u16]> for JsString {
+    #[inline]
+    fn borrow(&self) -> &[u16] {
+        self.as_slice()
     }
 }
 
 impl Deref for JsString {
-    type Target = str;
+    type Target = [u16];
 
     #[inline]
     fn deref(&self) -> &Self::Target {
-        self.as_str()
+        self.as_slice()
     }
 }
 
@@ -589,7 +945,7 @@ impl PartialEq<JsString> for JsString {
             return true;
         }
 
-        self.as_str() == other.as_str()
+        self.as_slice() == other.as_slice()
     }
 }
 
@@ -598,55 +954,115 @@ impl Eq for JsString {}
 impl Hash for JsString {
     #[inline]
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.as_str().hash(state);
+        self.as_slice().hash(state);
     }
 }
 
 impl PartialOrd for JsString {
     #[inline]
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.as_str().partial_cmp(other.as_str())
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for JsString {
+    /// Strings are ordered lexicographically by their code units, as in `IsLessThan`.
     #[inline]
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.as_str().cmp(other)
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl PartialEq<[u16]> for JsString {
+    #[inline]
+    fn eq(&self, other: &[u16]) -> bool {
+        self.as_slice() == other
     }
 }
 
 impl PartialEq<str> for JsString {
     #[inline]
     fn eq(&self, other: &str) -> bool {
-        self.as_str() == other
+        self.iter().copied().eq(other.encode_utf16())
     }
 }
 
 impl PartialEq<JsString> for str {
     #[inline]
     fn eq(&self, other: &JsString) -> bool {
-        self == other.as_str()
+        other == self
     }
 }
 
 impl PartialEq<&str> for JsString {
     #[inline]
     fn eq(&self, other: &&str) -> bool {
-        self.as_str() == *other
+        self == *other
     }
 }
 
 impl PartialEq<JsString> for &str {
     #[inline]
     fn eq(&self, other: &JsString) -> bool {
-        *self == other.as_str()
+        other == *self
+    }
+}
+
+/// Strings are serialized as Rust strings when they are well formed, and as sequences of code units otherwise.
+#[cfg(feature = "deser")]
+impl serde::Serialize for JsString {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self.to_std_string() {
+            Ok(string) => serializer.serialize_str(&string),
+            Err(_) => serializer.collect_seq(self.iter()),
+        }
+    }
+}
+
+#[cfg(feature = "deser")]
+impl<'de> serde::Deserialize<'de> for JsString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct JsStringVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for JsStringVisitor {
+            type Value = JsString;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("a string or a sequence of UTF-16 code units")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(JsString::new(value))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                let mut units = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(unit) = seq.next_element()? {
+                    units.push(unit);
+                }
+                Ok(units.into())
+            }
+        }
+
+        deserializer.deserialize_any(JsStringVisitor)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::JsString;
+    use super::{CodePoint, JsString, Repr, MIN_ROPE_LENGTH};
     use std::mem::size_of;
 
     #[test]
@@ -697,11 +1113,36 @@ mod tests {
     }
 
     #[test]
-    fn as_str() {
-        let s = "Hello";
+    fn std_string_round_trip() {
+        let s = "Hello, 🌍!";
         let x = JsString::new(s);
 
-        assert_eq!(x.as_str(), s);
+        assert_eq!(x.len(), s.encode_utf16().count());
+        assert_eq!(x.to_std_string().unwrap(), s);
+        assert_eq!(&*x, &s.encode_utf16().collect::<Vec<u16>>()[..]);
+        assert!(x.is_well_formed());
+    }
+
+    #[test]
+    fn unpaired_surrogates() {
+        let units = [0x0061, 0xD800, 0x0062, 0xDFFF];
+        let x = JsString::from_utf16(&units);
+
+        assert_eq!(x.len(), 4);
+        assert_eq!(x.as_slice(), &units);
+        assert!(!x.is_well_formed());
+        assert!(x.to_std_string().is_err());
+        assert_eq!(x.to_std_string_lossy(), "a\u{FFFD}b\u{FFFD}");
+        assert_eq!(x.to_std_string_escaped(), "a\\uD800b\\uDFFF");
+        assert_eq!(
+            x.code_points().collect::<Vec<_>>(),
+            [
+                CodePoint::Unicode('a'),
+                CodePoint::UnpairedSurrogate(0xD800),
+                CodePoint::Unicode('b'),
+                CodePoint::UnpairedSurrogate(0xDFFF),
+            ]
+        );
     }
 
     #[test]
@@ -709,10 +1150,8 @@ mod tests {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
 
-        let s = "Hello, world!";
-        let x = JsString::new(s);
-
-        assert_eq!(x.as_str(), s);
+        let s = utf16!("Hello, world!");
+        let x = JsString::from(s);
 
         let mut hasher = DefaultHasher::new();
         s.hash(&mut hasher);
@@ -727,9 +1166,9 @@ mod tests {
     #[test]
     fn concat() {
         let x = JsString::new("hello");
-        let y = ", ";
+        let y = utf16!(", ");
         let z = JsString::new("world");
-        let w = String::from("!");
+        let w = vec![0x0021];
 
         let xy = JsString::concat(x, y);
         assert_eq!(xy, "hello, ");
@@ -743,4 +1182,57 @@ mod tests {
         assert_eq!(xyzw, "hello, world!");
         assert_eq!(JsString::refcount(&xyzw), 1);
     }
+
+    #[test]
+    fn concat_rope() {
+        let x = JsString::new("a".repeat(200));
+        let y = JsString::new("b".repeat(100));
+
+        let short = JsString::concat_rope(&x, &JsString::new("b"));
+        assert!(matches!(unsafe { &*short.inner().repr.get() }, Repr::Flat));
+
+        let xy = JsString::concat_rope(&x, &y);
+        assert!(matches!(unsafe { &*xy.inner().repr.get() }, Repr::Rope(..)));
+        assert_eq!(xy.len(), 300);
+        assert_eq!(JsString::refcount(&x), 2);
+        assert_eq!(JsString::refcount(&y), 2);
+
+        let expected = format!("{}{}", "a".repeat(200), "b".repeat(100));
+        assert_eq!(xy, &*expected);
+        assert!(matches!(
+            unsafe { &*xy.inner().repr.get() },
+            Repr::Flattened(_)
+        ));
+        assert_eq!(JsString::refcount(&x), 1);
+        assert_eq!(JsString::refcount(&y), 1);
+
+        assert!(JsString::ptr_eq(
+            &JsString::concat_rope(&xy, &JsString::empty()),
+            &xy
+        ));
+    }
+
+    #[test]
+    fn deep_rope() {
+        let piece = JsString::new("x".repeat(MIN_ROPE_LENGTH));
+
+        let mut string = JsString::empty();
+        for _ in 0..100_000 {
+            string = JsString::concat_rope(&string, &piece);
+        }
+        assert_eq!(string.len(), 100_000 * MIN_ROPE_LENGTH);
+
+        // Dropping an unflattened deep rope must not overflow the stack.
+        drop(string.clone());
+        let unflattened = JsString::concat_rope(&string, &piece);
+        drop(string);
+        drop(unflattened);
+
+        let mut string = JsString::empty();
+        for _ in 0..100_000 {
+            string = JsString::concat_rope(&string, &piece);
+        }
+        assert!(string.iter().all(|&unit| unit == u16::from(b'x')));
+        assert_eq!(JsString::refcount(&piece), 1);
+    }
 }
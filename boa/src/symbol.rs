@@ -33,7 +33,7 @@ use std::{
 ///# use boa::symbol::WellKnownSymbols;
 ///
 /// let iterator = WellKnownSymbols::iterator();
-/// assert_eq!(iterator.description().as_deref(), Some("Symbol.iterator"));
+/// assert_eq!(iterator.description(), Some("Symbol.iterator".into()));
 /// ```
 /// This is equivalent to `let iterator = Symbol.iterator` in JavaScript.
 #[derive(Debug, Clone)]
@@ -0,0 +1,123 @@
+//! This module implements the `Comment` structure, which represents a comment in the source code.
+
+use super::Span;
+use std::fmt;
+
+#[cfg(feature = "deser")]
+use serde::{Deserialize, Serialize};
+
+/// The different kinds of comments.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-comments
+#[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CommentKind {
+    /// A comment starting with `//`, up to the end of the line.
+    SingleLine,
+    /// A comment delimited by `/*` and `*/`, which can span multiple lines.
+    MultiLine,
+    /// A `#!` comment on the first line of a script.
+    Hashbang,
+}
+
+/// A comment in the JavaScript source code.
+///
+/// The text of the comment does not include its delimiters, so that the text of `/** doc */` is
+/// `"* doc "`.
+#[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comment {
+    kind: CommentKind,
+    text: Box<str>,
+    span: Span,
+}
+
+impl Comment {
+    /// Creates a new `Comment`.
+    #[inline]
+    pub fn new<T>(kind: CommentKind, text: T, span: Span) -> Self
+    where
+        T: Into<Box<str>>,
+    {
+        Self {
+            kind,
+            text: text.into(),
+            span,
+        }
+    }
+
+    /// Gets the kind of the comment.
+    #[inline]
+    pub fn kind(&self) -> CommentKind {
+        self.kind
+    }
+
+    /// Gets the text of the comment, without its delimiters.
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Gets the span of the comment, including its delimiters.
+    #[inline]
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl fmt::Display for Comment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            CommentKind::SingleLine => write!(f, "//{}", self.text),
+            CommentKind::MultiLine => write!(f, "/*{}*/", self.text),
+            CommentKind::Hashbang => write!(f, "#!{}", self.text),
+        }
+    }
+}
+
+/// The comments attached to a token or to a statement.
+///
+/// Leading comments are the ones found before it, since the end of the previous line which held
+/// code. Trailing comments start after it, on the same line.
+#[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Comments {
+    leading: Box<[Comment]>,
+    trailing: Box<[Comment]>,
+}
+
+impl Comments {
+    /// Creates a new set of attached comments.
+    #[inline]
+    pub fn new<L, T>(leading: L, trailing: T) -> Self
+    where
+        L: Into<Box<[Comment]>>,
+        T: Into<Box<[Comment]>>,
+    {
+        Self {
+            leading: leading.into(),
+            trailing: trailing.into(),
+        }
+    }
+
+    /// Gets the comments found before the token or statement.
+    #[inline]
+    pub fn leading(&self) -> &[Comment] {
+        &self.leading
+    }
+
+    /// Gets the comments found after the token or statement, on the same line.
+    #[inline]
+    pub fn trailing(&self) -> &[Comment] {
+        &self.trailing
+    }
+
+    /// Checks if there are no attached comments.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.leading.is_empty() && self.trailing.is_empty()
+    }
+}
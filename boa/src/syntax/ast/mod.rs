@@ -1,16 +1,22 @@
 //! The Javascript Abstract Syntax Tree.
 
+pub mod comment;
 pub mod constant;
 pub mod keyword;
 pub mod node;
 pub mod op;
 pub mod position;
 pub mod punctuator;
+pub mod side_table;
+pub mod visitor;
 
 pub use self::{
+    comment::{Comment, CommentKind, Comments},
     constant::Const,
     keyword::Keyword,
     node::Node,
     position::{Position, Span},
     punctuator::Punctuator,
+    side_table::SideTable,
+    visitor::{Visitor, VisitorMut},
 };
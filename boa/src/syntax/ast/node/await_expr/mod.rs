@@ -26,6 +26,18 @@ pub struct AwaitExpr {
     expr: Box<Node>,
 }
 
+impl AwaitExpr {
+    /// Gets the expression being awaited.
+    pub fn expr(&self) -> &Node {
+        &self.expr
+    }
+
+    /// Gets a mutable reference to the expression being awaited.
+    pub fn expr_mut(&mut self) -> &mut Node {
+        &mut self.expr
+    }
+}
+
 impl Executable for AwaitExpr {
     fn run(&self, _: &mut Context) -> JsResult<JsValue> {
         let _timer = BoaProfiler::global().start_event("AwaitExpression", "exec");
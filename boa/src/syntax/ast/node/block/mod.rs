@@ -6,9 +6,10 @@ use crate::{
     exec::Executable,
     exec::InterpreterState,
     gc::{Finalize, Trace},
+    interner::Sym,
     BoaProfiler, Context, JsResult, JsValue,
 };
-use std::{collections::HashSet, fmt};
+use std::{collections::HashSet, fmt, rc::Rc};
 
 #[cfg(feature = "deser")]
 use serde::{Deserialize, Serialize};
@@ -32,11 +33,11 @@ mod tests;
 /// [spec]: https://tc39.es/ecma262/#prod-BlockStatement
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/block
 #[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
-#[cfg_attr(feature = "deser", serde(transparent))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct Block {
     #[cfg_attr(feature = "deser", serde(flatten))]
     statements: StatementList,
+    label: Option<Sym>,
 }
 
 impl Block {
@@ -45,16 +46,37 @@ impl Block {
         self.statements.items()
     }
 
-    pub(crate) fn lexically_declared_names(&self) -> HashSet<&str> {
+    /// Gets the statement list of this block.
+    pub fn statement_list(&self) -> &StatementList {
+        &self.statements
+    }
+
+    /// Gets a mutable reference to the statement list of this block.
+    pub fn statement_list_mut(&mut self) -> &mut StatementList {
+        &mut self.statements
+    }
+
+    pub fn label(&self) -> Option<Sym> {
+        self.label
+    }
+
+    pub fn set_label(&mut self, label: Sym) {
+        self.label = Some(label);
+    }
+
+    pub(crate) fn lexically_declared_names(&self) -> HashSet<Sym> {
         self.statements.lexically_declared_names()
     }
 
-    pub(crate) fn var_declared_named(&self) -> HashSet<&str> {
+    pub(crate) fn var_declared_named(&self) -> HashSet<Sym> {
         self.statements.var_declared_names()
     }
 
     /// Implements the display formatting with indentation.
     pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        if let Some(ref label) = self.label {
+            write!(f, "{}: ", label)?;
+        }
         writeln!(f, "{{")?;
         self.statements.display(f, indentation + 1)?;
         write!(f, "{}}}", "    ".repeat(indentation))
@@ -69,11 +91,22 @@ impl Executable for Block {
             context.push_environment(DeclarativeEnvironmentRecord::new(Some(env)));
         }
 
+        // Errors thrown by the statements are located with the spans of the block.
+        let side_table = !self.statements.side_table().is_empty();
+        if side_table {
+            context
+                .executor()
+                .push_side_table(Rc::clone(self.statements.side_table()));
+        }
+
         // https://tc39.es/ecma262/#sec-block-runtime-semantics-evaluation
         // The return value is uninitialized, which means it defaults to Value::Undefined
         let mut obj = JsValue::default();
         for statement in self.items() {
             obj = statement.run(context).map_err(|e| {
+                if side_table {
+                    context.executor().pop_side_table();
+                }
                 // No matter how control leaves the Block the LexicalEnvironment is always
                 // restored to its former state.
                 context.pop_environment();
@@ -85,8 +118,13 @@ impl Executable for Block {
                     // Early return.
                     break;
                 }
-                InterpreterState::Break(_label) => {
-                    // TODO, break to a label.
+                InterpreterState::Break(label) => {
+                    // A `break` to the label of this block ends it normally.
+                    if label.is_some() && *label == self.label {
+                        context
+                            .executor()
+                            .set_current_state(InterpreterState::Executing);
+                    }
 
                     // Early break.
                     break;
@@ -101,6 +139,10 @@ impl Executable for Block {
             }
         }
 
+        if side_table {
+            context.executor().pop_side_table();
+        }
+
         // pop the block env
         let _ = context.pop_environment();
 
@@ -115,6 +157,7 @@ where
     fn from(list: T) -> Self {
         Self {
             statements: list.into(),
+            label: None,
         }
     }
 }
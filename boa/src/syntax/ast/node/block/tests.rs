@@ -1,3 +1,5 @@
+use crate::exec;
+
 #[test]
 fn fmt() {
     super::super::test_formatting(
@@ -9,14 +11,42 @@ fn fmt() {
         another_statement();
         "#,
     );
-    // TODO: Once block labels are implemtned, this should be tested:
-    // super::super::test_formatting(
-    //     r#"
-    //     block_name: {
-    //         let a = function_call();
-    //         console.log("hello");
-    //     }
-    //     another_statement();
-    //     "#,
-    // );
+    super::super::test_formatting(
+        r#"
+        block_name: {
+            let a = function_call();
+            console.log("hello");
+        }
+        another_statement();
+        "#,
+    );
+}
+
+#[test]
+fn break_label() {
+    let scenario = r#"
+        var str = "";
+
+        outer: {
+            str = str + "a";
+            inner: {
+                str = str + "b";
+                break outer;
+            }
+            str = str + "c";
+        }
+        str
+    "#;
+    assert_eq!(&exec(scenario), "\"ab\"");
+
+    let scenario = r#"
+        function f() {
+            a: {
+                break a;
+            }
+            return 1;
+        }
+        f()
+    "#;
+    assert_eq!(&exec(scenario), "1");
 }
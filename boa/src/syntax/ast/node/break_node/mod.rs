@@ -3,6 +3,7 @@ use crate::{
     exec::Executable,
     exec::InterpreterState,
     gc::{Finalize, Trace},
+    interner::Sym,
     Context, JsResult, JsValue,
 };
 use std::fmt;
@@ -30,14 +31,14 @@ mod tests;
 #[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct Break {
-    label: Option<Box<str>>,
+    label: Option<Sym>,
 }
 
 impl Break {
     /// Creates a `Break` AST node.
     pub fn new<OL, L>(label: OL) -> Self
     where
-        L: Into<Box<str>>,
+        L: Into<Sym>,
         OL: Into<Option<L>>,
     {
         Self {
@@ -46,8 +47,8 @@ impl Break {
     }
 
     /// Gets the label of the break statement, if any.
-    pub fn label(&self) -> Option<&str> {
-        self.label.as_ref().map(Box::as_ref)
+    pub fn label(&self) -> Option<Sym> {
+        self.label
     }
 }
 
@@ -55,7 +56,7 @@ impl Executable for Break {
     fn run(&self, context: &mut Context) -> JsResult<JsValue> {
         context
             .executor()
-            .set_current_state(InterpreterState::Break(self.label().map(Box::from)));
+            .set_current_state(InterpreterState::Break(self.label()));
 
         Ok(JsValue::undefined())
     }
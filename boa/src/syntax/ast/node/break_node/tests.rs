@@ -20,23 +20,9 @@ fn check_post_state() {
 
 #[test]
 fn fmt() {
-    // Blocks do not store their label, so we cannot test with
-    // the outer block having a label.
-    //
-    // TODO: Once block labels are implemented, this test should
-    // include them:
-    //
-    // ```
-    // outer: {
-    //     while (true) {
-    //         break outer;
-    //     }
-    //     skipped_call();
-    // }
-    // ```
     super::super::test_formatting(
         r#"
-        {
+        outer: {
             while (true) {
                 break outer;
             }
@@ -52,10 +52,20 @@ impl Call {
         &self.expr
     }
 
+    /// Gets a mutable reference to the name of the function call.
+    pub fn expr_mut(&mut self) -> &mut Node {
+        &mut self.expr
+    }
+
     /// Retrieves the arguments passed to the function.
     pub fn args(&self) -> &[Node] {
         &self.args
     }
+
+    /// Retrieves a mutable reference to the arguments passed to the function.
+    pub fn args_mut(&mut self) -> &mut [Node] {
+        &mut self.args
+    }
 }
 
 impl Executable for Call {
@@ -44,15 +44,25 @@ impl ArrowFunctionDecl {
     }
 
     /// Gets the list of parameters of the arrow function.
-    pub(crate) fn params(&self) -> &[FormalParameter] {
+    pub fn params(&self) -> &[FormalParameter] {
         &self.params
     }
 
+    /// Gets a mutable reference to the list of parameters of the arrow function.
+    pub fn params_mut(&mut self) -> &mut [FormalParameter] {
+        &mut self.params
+    }
+
     /// Gets the body of the arrow function.
-    pub(crate) fn body(&self) -> &StatementList {
+    pub fn body(&self) -> &StatementList {
         &self.body
     }
 
+    /// Gets a mutable reference to the body of the arrow function.
+    pub fn body_mut(&mut self) -> &mut StatementList {
+        &mut self.body
+    }
+
     /// Implements the display formatting with indentation.
     pub(in crate::syntax::ast::node) fn display(
         &self,
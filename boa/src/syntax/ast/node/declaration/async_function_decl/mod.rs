@@ -2,6 +2,7 @@
 
 use crate::{
     exec::Executable,
+    interner::Sym,
     syntax::ast::node::{join_nodes, FormalParameter, Node, StatementList},
     BoaProfiler, Context, JsResult, JsValue,
 };
@@ -22,7 +23,7 @@ use serde::{Deserialize, Serialize};
 #[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct AsyncFunctionDecl {
-    name: Box<str>,
+    name: Sym,
     parameters: Box<[FormalParameter]>,
     body: StatementList,
 }
@@ -31,7 +32,7 @@ impl AsyncFunctionDecl {
     /// Creates a new async function declaration.
     pub(in crate::syntax) fn new<N, P, B>(name: N, parameters: P, body: B) -> Self
     where
-        N: Into<Box<str>>,
+        N: Into<Sym>,
         P: Into<Box<[FormalParameter]>>,
         B: Into<StatementList>,
     {
@@ -43,8 +44,8 @@ impl AsyncFunctionDecl {
     }
 
     /// Gets the name of the async function declaration.
-    pub fn name(&self) -> &str {
-        &self.name
+    pub fn name(&self) -> Sym {
+        self.name
     }
 
     /// Gets the list of parameters of the async function declaration.
@@ -52,11 +53,26 @@ impl AsyncFunctionDecl {
         &self.parameters
     }
 
+    /// Gets a mutable reference to the list of parameters of the async function declaration.
+    pub fn parameters_mut(&mut self) -> &mut [FormalParameter] {
+        &mut self.parameters
+    }
+
     /// Gets the body of the async function declaration.
     pub fn body(&self) -> &[Node] {
         self.body.items()
     }
 
+    /// Gets a mutable reference to the body of the async function declaration.
+    pub fn body_mut(&mut self) -> &mut [Node] {
+        self.body.items_mut()
+    }
+
+    /// Gets the body of the async function declaration as a statement list, with its source information.
+    pub(crate) fn statement_list(&self) -> &StatementList {
+        &self.body
+    }
+
     /// Implements the display formatting with indentation.
     pub(in crate::syntax::ast::node) fn display(
         &self,
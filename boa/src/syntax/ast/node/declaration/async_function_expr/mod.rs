@@ -2,6 +2,7 @@
 
 use crate::{
     exec::Executable,
+    interner::Sym,
     syntax::ast::node::{join_nodes, FormalParameter, Node, StatementList},
     Context, JsResult, JsValue,
 };
@@ -23,7 +24,7 @@ use serde::{Deserialize, Serialize};
 #[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct AsyncFunctionExpr {
-    name: Option<Box<str>>,
+    name: Option<Sym>,
     parameters: Box<[FormalParameter]>,
     body: StatementList,
 }
@@ -32,7 +33,7 @@ impl AsyncFunctionExpr {
     /// Creates a new function expression
     pub(in crate::syntax) fn new<N, P, B>(name: N, parameters: P, body: B) -> Self
     where
-        N: Into<Option<Box<str>>>,
+        N: Into<Option<Sym>>,
         P: Into<Box<[FormalParameter]>>,
         B: Into<StatementList>,
     {
@@ -44,8 +45,8 @@ impl AsyncFunctionExpr {
     }
 
     /// Gets the name of the function declaration.
-    pub fn name(&self) -> Option<&str> {
-        self.name.as_ref().map(Box::as_ref)
+    pub fn name(&self) -> Option<Sym> {
+        self.name
     }
 
     /// Gets the list of parameters of the function declaration.
@@ -53,11 +54,26 @@ impl AsyncFunctionExpr {
         &self.parameters
     }
 
+    /// Gets a mutable reference to the list of parameters of the function declaration.
+    pub fn parameters_mut(&mut self) -> &mut [FormalParameter] {
+        &mut self.parameters
+    }
+
     /// Gets the body of the function declaration.
     pub fn body(&self) -> &[Node] {
         self.body.items()
     }
 
+    /// Gets a mutable reference to the body of the function declaration.
+    pub fn body_mut(&mut self) -> &mut [Node] {
+        self.body.items_mut()
+    }
+
+    /// Gets the body of the async function expression as a statement list, with its source information.
+    pub(crate) fn statement_list(&self) -> &StatementList {
+        &self.body
+    }
+
     /// Implements the display formatting with indentation.
     pub(in crate::syntax::ast::node) fn display(
         &self,
@@ -3,6 +3,7 @@ use crate::{
     environment::lexical_environment::VariableScope,
     exec::Executable,
     gc::{Finalize, Trace},
+    interner::Sym,
     syntax::ast::node::{join_nodes, FormalParameter, Node, StatementList},
     BoaProfiler, Context, JsResult, JsValue,
 };
@@ -32,7 +33,7 @@ use serde::{Deserialize, Serialize};
 #[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct FunctionDecl {
-    name: Box<str>,
+    name: Sym,
     parameters: Box<[FormalParameter]>,
     body: StatementList,
 }
@@ -41,7 +42,7 @@ impl FunctionDecl {
     /// Creates a new function declaration.
     pub(in crate::syntax) fn new<N, P, B>(name: N, parameters: P, body: B) -> Self
     where
-        N: Into<Box<str>>,
+        N: Into<Sym>,
         P: Into<Box<[FormalParameter]>>,
         B: Into<StatementList>,
     {
@@ -53,8 +54,8 @@ impl FunctionDecl {
     }
 
     /// Gets the name of the function declaration.
-    pub fn name(&self) -> &str {
-        &self.name
+    pub fn name(&self) -> Sym {
+        self.name
     }
 
     /// Gets the list of parameters of the function declaration.
@@ -62,11 +63,21 @@ impl FunctionDecl {
         &self.parameters
     }
 
+    /// Gets a mutable reference to the list of parameters of the function declaration.
+    pub fn parameters_mut(&mut self) -> &mut [FormalParameter] {
+        &mut self.parameters
+    }
+
     /// Gets the body of the function declaration.
     pub fn body(&self) -> &StatementList {
         &self.body
     }
 
+    /// Gets a mutable reference to the body of the function declaration.
+    pub fn body_mut(&mut self) -> &mut StatementList {
+        &mut self.body
+    }
+
     /// Implements the display formatting with indentation.
     pub(in crate::syntax::ast::node) fn display(
         &self,
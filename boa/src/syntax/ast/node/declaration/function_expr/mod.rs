@@ -2,8 +2,9 @@ use crate::{
     builtins::function::ThisMode,
     exec::Executable,
     gc::{Finalize, Trace},
+    interner::Sym,
     syntax::ast::node::{join_nodes, FormalParameter, Node, StatementList},
-    Context, JsResult, JsValue,
+    Context, JsResult, JsString, JsValue,
 };
 use std::fmt;
 
@@ -29,7 +30,7 @@ use serde::{Deserialize, Serialize};
 #[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct FunctionExpr {
-    name: Option<Box<str>>,
+    name: Option<Sym>,
     parameters: Box<[FormalParameter]>,
     body: StatementList,
 }
@@ -38,7 +39,7 @@ impl FunctionExpr {
     /// Creates a new function expression
     pub(in crate::syntax) fn new<N, P, B>(name: N, parameters: P, body: B) -> Self
     where
-        N: Into<Option<Box<str>>>,
+        N: Into<Option<Sym>>,
         P: Into<Box<[FormalParameter]>>,
         B: Into<StatementList>,
     {
@@ -50,8 +51,8 @@ impl FunctionExpr {
     }
 
     /// Gets the name of the function declaration.
-    pub fn name(&self) -> Option<&str> {
-        self.name.as_ref().map(Box::as_ref)
+    pub fn name(&self) -> Option<Sym> {
+        self.name
     }
 
     /// Gets the list of parameters of the function declaration.
@@ -59,11 +60,21 @@ impl FunctionExpr {
         &self.parameters
     }
 
+    /// Gets a mutable reference to the list of parameters of the function declaration.
+    pub fn parameters_mut(&mut self) -> &mut [FormalParameter] {
+        &mut self.parameters
+    }
+
     /// Gets the body of the function declaration.
     pub fn body(&self) -> &StatementList {
         &self.body
     }
 
+    /// Gets a mutable reference to the body of the function declaration.
+    pub fn body_mut(&mut self) -> &mut StatementList {
+        &mut self.body
+    }
+
     /// Implements the display formatting with indentation.
     pub(in crate::syntax::ast::node) fn display(
         &self,
@@ -100,7 +111,7 @@ impl FunctionExpr {
 impl Executable for FunctionExpr {
     fn run(&self, context: &mut Context) -> JsResult<JsValue> {
         let val = context.create_function(
-            self.name().unwrap_or(""),
+            self.name().map_or_else(JsString::default, JsString::from),
             self.parameters().to_vec(),
             self.body().clone(),
             true,
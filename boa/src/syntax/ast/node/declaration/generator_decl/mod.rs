@@ -1,6 +1,7 @@
 use crate::{
     exec::Executable,
     gc::{Finalize, Trace},
+    interner::Sym,
     syntax::ast::node::{join_nodes, FormalParameter, Node, StatementList},
     BoaProfiler, Context, JsResult, JsValue,
 };
@@ -21,7 +22,7 @@ use serde::{Deserialize, Serialize};
 #[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct GeneratorDecl {
-    name: Box<str>,
+    name: Sym,
     parameters: Box<[FormalParameter]>,
     body: StatementList,
 }
@@ -30,7 +31,7 @@ impl GeneratorDecl {
     /// Creates a new generator declaration.
     pub(in crate::syntax) fn new<N, P, B>(name: N, parameters: P, body: B) -> Self
     where
-        N: Into<Box<str>>,
+        N: Into<Sym>,
         P: Into<Box<[FormalParameter]>>,
         B: Into<StatementList>,
     {
@@ -42,8 +43,8 @@ impl GeneratorDecl {
     }
 
     /// Gets the name of the generator declaration.
-    pub fn name(&self) -> &str {
-        &self.name
+    pub fn name(&self) -> Sym {
+        self.name
     }
 
     /// Gets the list of parameters of the generator declaration.
@@ -51,11 +52,26 @@ impl GeneratorDecl {
         &self.parameters
     }
 
+    /// Gets a mutable reference to the list of parameters of the generator declaration.
+    pub fn parameters_mut(&mut self) -> &mut [FormalParameter] {
+        &mut self.parameters
+    }
+
     /// Gets the body of the generator declaration.
     pub fn body(&self) -> &[Node] {
         self.body.items()
     }
 
+    /// Gets a mutable reference to the body of the generator declaration.
+    pub fn body_mut(&mut self) -> &mut [Node] {
+        self.body.items_mut()
+    }
+
+    /// Gets the body of the generator declaration as a statement list, with its source information.
+    pub(crate) fn statement_list(&self) -> &StatementList {
+        &self.body
+    }
+
     /// Implements the display formatting with indentation.
     pub(in crate::syntax::ast::node) fn display(
         &self,
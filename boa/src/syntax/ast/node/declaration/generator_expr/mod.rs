@@ -1,6 +1,7 @@
 use crate::{
     exec::Executable,
     gc::{Finalize, Trace},
+    interner::Sym,
     syntax::ast::node::{join_nodes, FormalParameter, Node, StatementList},
     Context, JsResult, JsValue,
 };
@@ -20,7 +21,7 @@ use serde::{Deserialize, Serialize};
 #[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct GeneratorExpr {
-    name: Option<Box<str>>,
+    name: Option<Sym>,
     parameters: Box<[FormalParameter]>,
     body: StatementList,
 }
@@ -29,7 +30,7 @@ impl GeneratorExpr {
     /// Creates a new generator expression
     pub(in crate::syntax) fn new<N, P, B>(name: N, parameters: P, body: B) -> Self
     where
-        N: Into<Option<Box<str>>>,
+        N: Into<Option<Sym>>,
         P: Into<Box<[FormalParameter]>>,
         B: Into<StatementList>,
     {
@@ -41,8 +42,8 @@ impl GeneratorExpr {
     }
 
     /// Gets the name of the generator declaration.
-    pub fn name(&self) -> Option<&str> {
-        self.name.as_ref().map(Box::as_ref)
+    pub fn name(&self) -> Option<Sym> {
+        self.name
     }
 
     /// Gets the list of parameters of the generator declaration.
@@ -50,11 +51,21 @@ impl GeneratorExpr {
         &self.parameters
     }
 
+    /// Gets a mutable reference to the list of parameters of the generator declaration.
+    pub fn parameters_mut(&mut self) -> &mut [FormalParameter] {
+        &mut self.parameters
+    }
+
     /// Gets the body of the generator declaration.
     pub fn body(&self) -> &StatementList {
         &self.body
     }
 
+    /// Gets a mutable reference to the body of the generator declaration.
+    pub fn body_mut(&mut self) -> &mut StatementList {
+        &mut self.body
+    }
+
     /// Implements the display formatting with indentation.
     pub(in crate::syntax::ast::node) fn display(
         &self,
@@ -4,6 +4,7 @@ use crate::{
     environment::lexical_environment::VariableScope,
     exec::Executable,
     gc::{Finalize, Trace},
+    interner::Sym,
     syntax::ast::node::{join_nodes, Identifier, Node},
     Context, JsResult, JsValue,
 };
@@ -106,65 +107,61 @@ impl Executable for DeclarationList {
 
             match &decl {
                 Declaration::Identifier { ident, init } => {
-                    if self.is_var() && context.has_binding(ident.as_ref())? {
+                    if self.is_var() && context.has_binding(ident.sym())? {
                         if init.is_some() {
-                            context.set_mutable_binding(ident.as_ref(), val, context.strict())?;
+                            context.set_mutable_binding(ident.sym(), val, context.strict())?;
                         }
                         continue;
                     }
 
                     match &self {
                         Const(_) => context.create_immutable_binding(
-                            ident.as_ref(),
+                            ident.sym(),
                             false,
                             VariableScope::Block,
                         )?,
                         Let(_) => context.create_mutable_binding(
-                            ident.as_ref(),
+                            ident.sym(),
                             false,
                             VariableScope::Block,
                         )?,
                         Var(_) => context.create_mutable_binding(
-                            ident.as_ref(),
+                            ident.sym(),
                             false,
                             VariableScope::Function,
                         )?,
                     }
 
-                    context.initialize_binding(ident.as_ref(), val)?;
+                    context.initialize_binding(ident.sym(), val)?;
                 }
                 Declaration::Pattern(p) => {
                     for (ident, value) in p.run(None, context)? {
-                        if self.is_var() && context.has_binding(ident.as_ref())? {
+                        if self.is_var() && context.has_binding(ident)? {
                             if !value.is_undefined() {
-                                context.set_mutable_binding(
-                                    ident.as_ref(),
-                                    value,
-                                    context.strict(),
-                                )?;
+                                context.set_mutable_binding(ident, value, context.strict())?;
                             }
                             continue;
                         }
 
                         match &self {
                             Const(_) => context.create_immutable_binding(
-                                ident.as_ref(),
+                                ident,
                                 false,
                                 VariableScope::Block,
                             )?,
                             Let(_) => context.create_mutable_binding(
-                                ident.as_ref(),
+                                ident,
                                 false,
                                 VariableScope::Block,
                             )?,
                             Var(_) => context.create_mutable_binding(
-                                ident.as_ref(),
+                                ident,
                                 false,
                                 VariableScope::Function,
                             )?,
                         }
 
-                        context.initialize_binding(ident.as_ref(), value)?;
+                        context.initialize_binding(ident, value)?;
                     }
                 }
             }
@@ -196,6 +193,15 @@ impl AsRef<[Declaration]> for DeclarationList {
     }
 }
 
+impl AsMut<[Declaration]> for DeclarationList {
+    fn as_mut(&mut self) -> &mut [Declaration] {
+        use DeclarationList::*;
+        match self {
+            Var(list) | Const(list) | Let(list) => list,
+        }
+    }
+}
+
 impl fmt::Display for DeclarationList {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if !self.as_ref().is_empty() {
@@ -361,7 +367,7 @@ impl DeclarationPattern {
         &self,
         init: Option<JsValue>,
         context: &mut Context,
-    ) -> JsResult<Vec<(Box<str>, JsValue)>> {
+    ) -> JsResult<Vec<(Sym, JsValue)>> {
         match &self {
             DeclarationPattern::Object(pattern) => pattern.run(init, context),
             DeclarationPattern::Array(pattern) => pattern.run(init, context),
@@ -372,7 +378,7 @@ impl DeclarationPattern {
     ///
     /// A single binding pattern may declare 0 to n identifiers.
     #[inline]
-    pub fn idents(&self) -> Vec<&str> {
+    pub fn idents(&self) -> Vec<Sym> {
         match &self {
             DeclarationPattern::Object(pattern) => pattern.idents(),
             DeclarationPattern::Array(pattern) => pattern.idents(),
@@ -434,10 +440,28 @@ impl DeclarationPatternObject {
 
     /// Gets the initialization node for the object binding pattern, if any.
     #[inline]
-    pub(in crate::syntax) fn init(&self) -> Option<&Node> {
+    pub fn init(&self) -> Option<&Node> {
         self.init.as_ref()
     }
 
+    /// Gets a mutable reference to the initialization node for the object binding pattern, if any.
+    #[inline]
+    pub fn init_mut(&mut self) -> Option<&mut Node> {
+        self.init.as_mut()
+    }
+
+    /// Gets the bindings of the object binding pattern.
+    #[inline]
+    pub fn bindings(&self) -> &[BindingPatternTypeObject] {
+        &self.bindings
+    }
+
+    /// Gets a mutable reference to the bindings of the object binding pattern.
+    #[inline]
+    pub fn bindings_mut(&mut self) -> &mut [BindingPatternTypeObject] {
+        &mut self.bindings
+    }
+
     /// Initialize the values of an object binding pattern.
     ///
     /// More information:
@@ -452,7 +476,7 @@ impl DeclarationPatternObject {
         &self,
         init: Option<JsValue>,
         context: &mut Context,
-    ) -> JsResult<Vec<(Box<str>, JsValue)>> {
+    ) -> JsResult<Vec<(Sym, JsValue)>> {
         let value = if let Some(value) = init {
             value
         } else if let Some(node) = &self.init {
@@ -509,7 +533,7 @@ impl DeclarationPatternObject {
 
                     // 5. If environment is undefined, return ? PutValue(lhs, v).
                     // 6. Return InitializeReferencedBinding(lhs, v).
-                    results.push((ident.clone(), v));
+                    results.push((*ident, v));
                 }
                 //  BindingRestProperty : ... BindingIdentifier
                 RestProperty {
@@ -526,7 +550,7 @@ impl DeclarationPatternObject {
 
                     // 4. If environment is undefined, return PutValue(lhs, restObj).
                     // 5. Return InitializeReferencedBinding(lhs, restObj).
-                    results.push((ident.clone(), rest_obj.into()));
+                    results.push((*ident, rest_obj.into()));
                 }
                 //  BindingElement : BindingPattern Initializer[opt]
                 BindingPattern {
@@ -557,7 +581,7 @@ impl DeclarationPatternObject {
 
     /// Gets the list of identifiers declared by the object binding pattern.
     #[inline]
-    pub(in crate::syntax) fn idents(&self) -> Vec<&str> {
+    pub(in crate::syntax) fn idents(&self) -> Vec<Sym> {
         let mut idents = Vec::new();
 
         for binding in &self.bindings {
@@ -570,13 +594,13 @@ impl DeclarationPatternObject {
                     property_name: _,
                     default_init: _,
                 } => {
-                    idents.push(ident.as_ref());
+                    idents.push(*ident);
                 }
                 RestProperty {
                     ident: property_name,
                     excluded_keys: _,
                 } => {
-                    idents.push(property_name.as_ref());
+                    idents.push(*property_name);
                 }
                 BindingPattern {
                     ident: _,
@@ -642,10 +666,28 @@ impl DeclarationPatternArray {
 
     /// Gets the initialization node for the array binding pattern, if any.
     #[inline]
-    pub(in crate::syntax) fn init(&self) -> Option<&Node> {
+    pub fn init(&self) -> Option<&Node> {
         self.init.as_ref()
     }
 
+    /// Gets a mutable reference to the initialization node for the array binding pattern, if any.
+    #[inline]
+    pub fn init_mut(&mut self) -> Option<&mut Node> {
+        self.init.as_mut()
+    }
+
+    /// Gets the bindings of the array binding pattern.
+    #[inline]
+    pub fn bindings(&self) -> &[BindingPatternTypeArray] {
+        &self.bindings
+    }
+
+    /// Gets a mutable reference to the bindings of the array binding pattern.
+    #[inline]
+    pub fn bindings_mut(&mut self) -> &mut [BindingPatternTypeArray] {
+        &mut self.bindings
+    }
+
     /// Initialize the values of an array binding pattern.
     ///
     /// More information:
@@ -658,7 +700,7 @@ impl DeclarationPatternArray {
         &self,
         init: Option<JsValue>,
         context: &mut Context,
-    ) -> JsResult<Vec<(Box<str>, JsValue)>> {
+    ) -> JsResult<Vec<(Sym, JsValue)>> {
         let value = if let Some(value) = init {
             value
         } else if let Some(node) = &self.init {
@@ -741,7 +783,7 @@ impl DeclarationPatternArray {
 
                     // 6. If environment is undefined, return ? PutValue(lhs, v).
                     // 7. Return InitializeReferencedBinding(lhs, v).
-                    result.push((ident.clone(), v));
+                    result.push((*ident, v));
                 }
                 // BindingElement : BindingPattern Initializer[opt]
                 BindingPattern { pattern } => {
@@ -791,7 +833,7 @@ impl DeclarationPatternArray {
                         if next.done {
                             // i. If environment is undefined, return ? PutValue(lhs, A).
                             // ii. Return InitializeReferencedBinding(lhs, A).
-                            break result.push((ident.clone(), a.clone().into()));
+                            break result.push((*ident, a.clone().into()));
                         }
 
                         // c. Let nextValue be IteratorValue(next).
@@ -844,7 +886,7 @@ impl DeclarationPatternArray {
 
     /// Gets the list of identifiers declared by the array binding pattern.
     #[inline]
-    pub(in crate::syntax) fn idents(&self) -> Vec<&str> {
+    pub(in crate::syntax) fn idents(&self) -> Vec<Sym> {
         let mut idents = Vec::new();
 
         for binding in &self.bindings {
@@ -857,13 +899,13 @@ impl DeclarationPatternArray {
                     ident,
                     default_init: _,
                 } => {
-                    idents.push(ident.as_ref());
+                    idents.push(*ident);
                 }
                 BindingPattern { pattern } | BindingPatternRest { pattern } => {
                     let mut i = pattern.idents();
                     idents.append(&mut i)
                 }
-                SingleNameRest { ident } => idents.push(ident),
+                SingleNameRest { ident } => idents.push(*ident),
             }
         }
 
@@ -895,8 +937,8 @@ pub enum BindingPatternTypeObject {
     /// [spec1]: https://tc39.es/ecma262/#prod-SingleNameBinding
     /// [spec2]: https://tc39.es/ecma262/#prod-BindingProperty
     SingleName {
-        ident: Box<str>,
-        property_name: Box<str>,
+        ident: Sym,
+        property_name: Sym,
         default_init: Option<Node>,
     },
 
@@ -909,10 +951,7 @@ pub enum BindingPatternTypeObject {
     ///  - [ECMAScript reference: 14.3.3 Destructuring Binding Patterns - BindingRestProperty][spec1]
     ///
     /// [spec1]: https://tc39.es/ecma262/#prod-BindingRestProperty
-    RestProperty {
-        ident: Box<str>,
-        excluded_keys: Vec<Box<str>>,
-    },
+    RestProperty { ident: Sym, excluded_keys: Vec<Sym> },
 
     /// BindingPattern represents a `BindingProperty` with a `BindingPattern` as the `BindingElement`.
     ///
@@ -924,8 +963,8 @@ pub enum BindingPatternTypeObject {
     ///
     /// [spec1]: https://tc39.es/ecma262/#prod-BindingProperty
     BindingPattern {
-        ident: Box<str>,
-        pattern: DeclarationPattern,
+        ident: Sym,
+        pattern: Box<DeclarationPattern>,
         default_init: Option<Node>,
     },
 }
@@ -1007,7 +1046,7 @@ pub enum BindingPatternTypeArray {
     ///
     /// [spec1]: https://tc39.es/ecma262/#prod-SingleNameBinding
     SingleName {
-        ident: Box<str>,
+        ident: Sym,
         default_init: Option<Node>,
     },
 
@@ -1027,7 +1066,7 @@ pub enum BindingPatternTypeArray {
     ///  - [ECMAScript reference: 14.3.3 Destructuring Binding Patterns - BindingRestElement][spec1]
     ///
     /// [spec1]: https://tc39.es/ecma262/#prod-BindingRestElement
-    SingleNameRest { ident: Box<str> },
+    SingleNameRest { ident: Sym },
 
     /// SingleNameRest represents a `BindingPattern` in a `BindingRestElement` of an array binding pattern.
     ///
@@ -1,6 +1,7 @@
 use crate::{
     exec::Executable,
     gc::{Finalize, Trace},
+    interner::Sym,
     syntax::ast::node::Node,
     Context, JsResult, JsValue,
 };
@@ -34,7 +35,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct GetConstField {
     obj: Box<Node>,
-    field: Box<str>,
+    field: Sym,
 }
 
 impl GetConstField {
@@ -42,7 +43,7 @@ impl GetConstField {
     pub fn new<V, L>(value: V, label: L) -> Self
     where
         V: Into<Node>,
-        L: Into<Box<str>>,
+        L: Into<Sym>,
     {
         Self {
             obj: Box::new(value.into()),
@@ -55,9 +56,14 @@ impl GetConstField {
         &self.obj
     }
 
+    /// Gets a mutable reference to the original object from where to get the field from.
+    pub fn obj_mut(&mut self) -> &mut Node {
+        &mut self.obj
+    }
+
     /// Gets the name of the field to retrieve.
-    pub fn field(&self) -> &str {
-        &self.field
+    pub fn field(&self) -> Sym {
+        self.field
     }
 }
 
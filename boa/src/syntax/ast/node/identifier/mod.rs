@@ -3,6 +3,7 @@
 use crate::{
     exec::Executable,
     gc::{Finalize, Trace},
+    interner::Sym,
     syntax::ast::node::Node,
     BoaProfiler, Context, JsResult, JsValue,
 };
@@ -28,15 +29,23 @@ use serde::{Deserialize, Serialize};
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Glossary/Identifier
 #[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "deser", serde(transparent))]
-#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+#[derive(Clone, Debug, Trace, Finalize, PartialEq, Eq, Hash)]
 pub struct Identifier {
-    ident: Box<str>,
+    ident: Sym,
+}
+
+impl Identifier {
+    /// Gets the interned name of the identifier.
+    #[inline]
+    pub fn sym(&self) -> Sym {
+        self.ident
+    }
 }
 
 impl Executable for Identifier {
     fn run(&self, context: &mut Context) -> JsResult<JsValue> {
         let _timer = BoaProfiler::global().start_event("Identifier", "exec");
-        context.get_binding_value(self.as_ref())
+        context.get_binding_value(self.ident)
     }
 }
 
@@ -54,7 +63,7 @@ impl AsRef<str> for Identifier {
 
 impl<T> From<T> for Identifier
 where
-    T: Into<Box<str>>,
+    T: Into<Sym>,
 {
     fn from(stm: T) -> Self {
         Self { ident: stm.into() }
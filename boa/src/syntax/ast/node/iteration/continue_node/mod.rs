@@ -1,6 +1,7 @@
 use crate::{
     exec::{Executable, InterpreterState},
     gc::{Finalize, Trace},
+    interner::Sym,
     syntax::ast::node::Node,
     Context, JsResult, JsValue,
 };
@@ -25,18 +26,18 @@ use serde::{Deserialize, Serialize};
 #[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct Continue {
-    label: Option<Box<str>>,
+    label: Option<Sym>,
 }
 
 impl Continue {
-    pub fn label(&self) -> Option<&str> {
-        self.label.as_ref().map(Box::as_ref)
+    pub fn label(&self) -> Option<Sym> {
+        self.label
     }
 
     /// Creates a `Continue` AST node.
     pub fn new<OL, L>(label: OL) -> Self
     where
-        L: Into<Box<str>>,
+        L: Into<Sym>,
         OL: Into<Option<L>>,
     {
         Self {
@@ -49,7 +50,7 @@ impl Executable for Continue {
     fn run(&self, context: &mut Context) -> JsResult<JsValue> {
         context
             .executor()
-            .set_current_state(InterpreterState::Continue(self.label().map(Box::from)));
+            .set_current_state(InterpreterState::Continue(self.label()));
 
         Ok(JsValue::undefined())
     }
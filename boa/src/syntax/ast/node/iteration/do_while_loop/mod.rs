@@ -1,6 +1,7 @@
 use crate::{
     exec::{Executable, InterpreterState},
     gc::{Finalize, Trace},
+    interner::Sym,
     syntax::ast::node::Node,
     Context, JsResult, JsValue,
 };
@@ -26,7 +27,7 @@ use serde::{Deserialize, Serialize};
 pub struct DoWhileLoop {
     body: Box<Node>,
     cond: Box<Node>,
-    label: Option<Box<str>>,
+    label: Option<Sym>,
 }
 
 impl DoWhileLoop {
@@ -34,15 +35,23 @@ impl DoWhileLoop {
         &self.body
     }
 
+    pub fn body_mut(&mut self) -> &mut Node {
+        &mut self.body
+    }
+
     pub fn cond(&self) -> &Node {
         &self.cond
     }
 
-    pub fn label(&self) -> Option<&str> {
-        self.label.as_ref().map(Box::as_ref)
+    pub fn cond_mut(&mut self) -> &mut Node {
+        &mut self.cond
+    }
+
+    pub fn label(&self) -> Option<Sym> {
+        self.label
     }
 
-    pub fn set_label(&mut self, label: Box<str>) {
+    pub fn set_label(&mut self, label: Sym) {
         self.label = Some(label);
     }
 
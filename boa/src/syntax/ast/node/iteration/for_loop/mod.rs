@@ -2,6 +2,7 @@ use crate::{
     environment::declarative_environment_record::DeclarativeEnvironmentRecord,
     exec::{Executable, InterpreterState},
     gc::{Finalize, Trace},
+    interner::Sym,
     syntax::ast::node::Node,
     BoaProfiler, Context, JsResult, JsValue,
 };
@@ -26,7 +27,7 @@ use serde::{Deserialize, Serialize};
 pub struct ForLoop {
     #[cfg_attr(feature = "deser", serde(flatten))]
     inner: Box<InnerForLoop>,
-    label: Option<Box<str>>,
+    label: Option<Sym>,
 }
 
 impl ForLoop {
@@ -49,21 +50,41 @@ impl ForLoop {
         self.inner.init()
     }
 
+    /// Gets a mutable reference to the initialization node.
+    pub fn init_mut(&mut self) -> Option<&mut Node> {
+        self.inner.init.as_mut()
+    }
+
     /// Gets the loop condition node.
     pub fn condition(&self) -> Option<&Node> {
         self.inner.condition()
     }
 
+    /// Gets a mutable reference to the loop condition node.
+    pub fn condition_mut(&mut self) -> Option<&mut Node> {
+        self.inner.condition.as_mut()
+    }
+
     /// Gets the final expression node.
     pub fn final_expr(&self) -> Option<&Node> {
         self.inner.final_expr()
     }
 
+    /// Gets a mutable reference to the final expression node.
+    pub fn final_expr_mut(&mut self) -> Option<&mut Node> {
+        self.inner.final_expr.as_mut()
+    }
+
     /// Gets the body of the for loop.
     pub fn body(&self) -> &Node {
         self.inner.body()
     }
 
+    /// Gets a mutable reference to the body of the for loop.
+    pub fn body_mut(&mut self) -> &mut Node {
+        &mut self.inner.body
+    }
+
     pub(in crate::syntax::ast::node) fn display(
         &self,
         f: &mut fmt::Formatter<'_>,
@@ -88,11 +109,11 @@ impl ForLoop {
         self.inner.body().display(f, indentation)
     }
 
-    pub fn label(&self) -> Option<&str> {
-        self.label.as_ref().map(Box::as_ref)
+    pub fn label(&self) -> Option<Sym> {
+        self.label
     }
 
-    pub fn set_label(&mut self, label: Box<str>) {
+    pub fn set_label(&mut self, label: Sym) {
         self.label = Some(label);
     }
 }
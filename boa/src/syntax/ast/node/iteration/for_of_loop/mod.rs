@@ -5,6 +5,7 @@ use crate::{
     },
     exec::{Executable, InterpreterState},
     gc::{Finalize, Trace},
+    interner::Sym,
     syntax::ast::node::{Declaration, Node},
     BoaProfiler, Context, JsResult, JsValue,
 };
@@ -19,7 +20,7 @@ pub struct ForOfLoop {
     variable: Box<Node>,
     iterable: Box<Node>,
     body: Box<Node>,
-    label: Option<Box<str>>,
+    label: Option<Sym>,
 }
 
 impl ForOfLoop {
@@ -41,19 +42,31 @@ impl ForOfLoop {
         &self.variable
     }
 
+    pub fn variable_mut(&mut self) -> &mut Node {
+        &mut self.variable
+    }
+
     pub fn iterable(&self) -> &Node {
         &self.iterable
     }
 
+    pub fn iterable_mut(&mut self) -> &mut Node {
+        &mut self.iterable
+    }
+
     pub fn body(&self) -> &Node {
         &self.body
     }
 
-    pub fn label(&self) -> Option<&str> {
-        self.label.as_ref().map(Box::as_ref)
+    pub fn body_mut(&mut self) -> &mut Node {
+        &mut self.body
+    }
+
+    pub fn label(&self) -> Option<Sym> {
+        self.label
     }
 
-    pub fn set_label(&mut self, label: Box<str>) {
+    pub fn set_label(&mut self, label: Sym) {
         self.label = Some(label);
     }
 
@@ -99,20 +112,20 @@ impl Executable for ForOfLoop {
 
             match self.variable() {
                 Node::Identifier(ref name) => {
-                    if context.has_binding(name.as_ref())? {
+                    if context.has_binding(name.sym())? {
                         // Binding already exists
                         context.set_mutable_binding(
-                            name.as_ref(),
+                            name.sym(),
                             next_result.clone(),
                             context.strict(),
                         )?;
                     } else {
                         context.create_mutable_binding(
-                            name.as_ref(),
+                            name.sym(),
                             true,
                             VariableScope::Function,
                         )?;
-                        context.initialize_binding(name.as_ref(), next_result)?;
+                        context.initialize_binding(name.sym(), next_result)?;
                     }
                 }
                 Node::VarDeclList(ref list) => match list.as_ref() {
@@ -123,36 +136,36 @@ impl Executable for ForOfLoop {
 
                         match &var {
                             Declaration::Identifier { ident, .. } => {
-                                if context.has_binding(ident.as_ref())? {
+                                if context.has_binding(ident.sym())? {
                                     context.set_mutable_binding(
-                                        ident.as_ref(),
+                                        ident.sym(),
                                         next_result,
                                         context.strict(),
                                     )?;
                                 } else {
                                     context.create_mutable_binding(
-                                        ident.as_ref(),
+                                        ident.sym(),
                                         false,
                                         VariableScope::Function,
                                     )?;
-                                    context.initialize_binding(ident.as_ref(), next_result)?;
+                                    context.initialize_binding(ident.sym(), next_result)?;
                                 }
                             }
                             Declaration::Pattern(p) => {
                                 for (ident, value) in p.run(Some(next_result), context)? {
-                                    if context.has_binding(ident.as_ref())? {
+                                    if context.has_binding(ident)? {
                                         context.set_mutable_binding(
-                                            ident.as_ref(),
+                                            ident,
                                             value,
                                             context.strict(),
                                         )?;
                                     } else {
                                         context.create_mutable_binding(
-                                            ident.as_ref(),
+                                            ident,
                                             false,
                                             VariableScope::Function,
                                         )?;
-                                        context.initialize_binding(ident.as_ref(), value)?;
+                                        context.initialize_binding(ident, value)?;
                                     }
                                 }
                             }
@@ -173,20 +186,20 @@ impl Executable for ForOfLoop {
                         match &var {
                             Declaration::Identifier { ident, .. } => {
                                 context.create_mutable_binding(
-                                    ident.as_ref(),
+                                    ident.sym(),
                                     false,
                                     VariableScope::Block,
                                 )?;
-                                context.initialize_binding(ident.as_ref(), next_result)?;
+                                context.initialize_binding(ident.sym(), next_result)?;
                             }
                             Declaration::Pattern(p) => {
                                 for (ident, value) in p.run(Some(next_result), context)? {
                                     context.create_mutable_binding(
-                                        ident.as_ref(),
+                                        ident,
                                         false,
                                         VariableScope::Block,
                                     )?;
-                                    context.initialize_binding(ident.as_ref(), value)?;
+                                    context.initialize_binding(ident, value)?;
                                 }
                             }
                         }
@@ -206,20 +219,20 @@ impl Executable for ForOfLoop {
                         match &var {
                             Declaration::Identifier { ident, .. } => {
                                 context.create_immutable_binding(
-                                    ident.as_ref(),
+                                    ident.sym(),
                                     false,
                                     VariableScope::Block,
                                 )?;
-                                context.initialize_binding(ident.as_ref(), next_result)?;
+                                context.initialize_binding(ident.sym(), next_result)?;
                             }
                             Declaration::Pattern(p) => {
                                 for (ident, value) in p.run(Some(next_result), context)? {
                                     context.create_immutable_binding(
-                                        ident.as_ref(),
+                                        ident,
                                         false,
                                         VariableScope::Block,
                                     )?;
-                                    context.initialize_binding(ident.as_ref(), value)?;
+                                    context.initialize_binding(ident, value)?;
                                 }
                             }
                         }
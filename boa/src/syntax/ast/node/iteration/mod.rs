@@ -14,7 +14,7 @@ macro_rules! handle_state_with_labels {
         if let Some(brk_label) = $label {
             if let Some(stmt_label) = $self.label() {
                 // Break from where we are, keeping "continue" set as the state
-                if stmt_label != brk_label.as_ref() {
+                if stmt_label != *brk_label {
                     break;
                 }
             } else {
@@ -1,6 +1,7 @@
 use crate::{
     exec::{Executable, InterpreterState},
     gc::{Finalize, Trace},
+    interner::Sym,
     syntax::ast::node::Node,
     Context, JsResult, JsValue,
 };
@@ -25,7 +26,7 @@ use serde::{Deserialize, Serialize};
 pub struct WhileLoop {
     cond: Box<Node>,
     body: Box<Node>,
-    label: Option<Box<str>>,
+    label: Option<Sym>,
 }
 
 impl WhileLoop {
@@ -33,15 +34,23 @@ impl WhileLoop {
         &self.cond
     }
 
+    pub fn cond_mut(&mut self) -> &mut Node {
+        &mut self.cond
+    }
+
     pub fn body(&self) -> &Node {
         &self.body
     }
 
-    pub fn label(&self) -> Option<&str> {
-        self.label.as_ref().map(Box::as_ref)
+    pub fn body_mut(&mut self) -> &mut Node {
+        &mut self.body
+    }
+
+    pub fn label(&self) -> Option<Sym> {
+        self.label
     }
 
-    pub fn set_label(&mut self, label: Box<str>) {
+    pub fn set_label(&mut self, label: Sym) {
         self.label = Some(label);
     }
 
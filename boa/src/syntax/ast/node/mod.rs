@@ -53,6 +53,7 @@ use super::Const;
 use crate::{
     exec::Executable,
     gc::{empty_trace, Finalize, Trace},
+    interner::Sym,
     BoaProfiler, Context, JsResult, JsValue,
 };
 use std::{
@@ -324,7 +325,7 @@ impl Node {
 impl Executable for Node {
     fn run(&self, context: &mut Context) -> JsResult<JsValue> {
         let _timer = BoaProfiler::global().start_event("Executable", "exec");
-        match *self {
+        let result = match *self {
             Node::AsyncFunctionDecl(ref decl) => decl.run(context),
             Node::AsyncFunctionExpr(ref function_expr) => function_expr.run(context),
             Node::AwaitExpr(ref expr) => expr.run(context),
@@ -337,7 +338,7 @@ impl Executable for Node {
             // we can't move String from Const into value, because const is a garbage collected value
             // Which means Drop() get's called on Const, but str will be gone at that point.
             // Do Const values need to be garbage collected? We no longer need them once we've generated Values
-            Node::Const(Const::String(ref value)) => Ok(JsValue::new(value.to_string())),
+            Node::Const(Const::String(ref value)) => Ok(JsValue::new(value.clone())),
             Node::Const(Const::Bool(value)) => Ok(JsValue::new(value)),
             Node::Block(ref block) => block.run(context),
             Node::Identifier(ref identifier) => identifier.run(context),
@@ -381,7 +382,11 @@ impl Executable for Node {
             Node::Yield(ref y) => y.run(context),
             Node::GeneratorDecl(ref decl) => decl.run(context),
             Node::GeneratorExpr(ref expr) => expr.run(context),
+        };
+        if let Err(ref error) = result {
+            context.executor().locate_error(self, error);
         }
+        result
     }
 }
 
@@ -419,16 +424,16 @@ where
 #[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, PartialEq, Trace, Finalize)]
 pub struct FormalParameter {
-    name: Box<str>,
+    name: Sym,
     init: Option<Node>,
     is_rest_param: bool,
 }
 
 impl FormalParameter {
     /// Creates a new formal parameter.
-    pub(in crate::syntax) fn new<N>(name: N, init: Option<Node>, is_rest_param: bool) -> Self
+    pub(crate) fn new<N>(name: N, init: Option<Node>, is_rest_param: bool) -> Self
     where
-        N: Into<Box<str>>,
+        N: Into<Sym>,
     {
         Self {
             name: name.into(),
@@ -438,8 +443,8 @@ impl FormalParameter {
     }
 
     /// Gets the name of the formal parameter.
-    pub fn name(&self) -> &str {
-        &self.name
+    pub fn name(&self) -> Sym {
+        self.name
     }
 
     /// Gets the initialization node of the formal parameter, if any.
@@ -447,6 +452,11 @@ impl FormalParameter {
         self.init.as_ref()
     }
 
+    /// Gets a mutable reference to the initialization node of the formal parameter, if any.
+    pub fn init_mut(&mut self) -> Option<&mut Node> {
+        self.init.as_mut()
+    }
+
     /// Gets wether the parameter is a rest parameter.
     pub fn is_rest_param(&self) -> bool {
         self.is_rest_param
@@ -490,7 +500,7 @@ pub enum PropertyDefinition {
     ///
     /// [spec]: https://tc39.es/ecma262/#prod-IdentifierReference
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Object_initializer#Property_definitions
-    IdentifierReference(Box<str>),
+    IdentifierReference(Sym),
 
     /// Binds a property name to a JavaScript value.
     ///
@@ -530,7 +540,7 @@ impl PropertyDefinition {
     /// Creates an `IdentifierReference` property definition.
     pub fn identifier_reference<I>(ident: I) -> Self
     where
-        I: Into<Box<str>>,
+        I: Into<Sym>,
     {
         Self::IdentifierReference(ident.into())
     }
@@ -646,7 +656,7 @@ pub enum PropertyName {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#prod-LiteralPropertyName
-    Literal(Box<str>),
+    Literal(Sym),
     /// A `Computed` property name is an expression that gets evaluated and converted into a property name.
     ///
     /// More information:
@@ -667,7 +677,7 @@ impl Display for PropertyName {
 
 impl<T> From<T> for PropertyName
 where
-    T: Into<Box<str>>,
+    T: Into<Sym>,
 {
     fn from(name: T) -> Self {
         Self::Literal(name.into())
@@ -40,10 +40,20 @@ impl New {
         self.call.expr()
     }
 
+    /// Gets a mutable reference to the name of the function call.
+    pub fn expr_mut(&mut self) -> &mut Node {
+        self.call.expr_mut()
+    }
+
     /// Retrieves the arguments passed to the function.
     pub fn args(&self) -> &[Node] {
         self.call.args()
     }
+
+    /// Retrieves a mutable reference to the arguments passed to the function.
+    pub fn args_mut(&mut self) -> &mut [Node] {
+        self.call.args_mut()
+    }
 }
 
 impl Executable for New {
@@ -46,6 +46,10 @@ impl Object {
         &self.properties
     }
 
+    pub fn properties_mut(&mut self) -> &mut [PropertyDefinition] {
+        &mut self.properties
+    }
+
     /// Implements the display formatting with indentation.
     pub(in crate::syntax::ast::node) fn display(
         &self,
@@ -96,7 +100,7 @@ impl Executable for Object {
             match property {
                 PropertyDefinition::Property(name, value) => {
                     let name = match name {
-                        PropertyName::Literal(name) => name.clone().into(),
+                        PropertyName::Literal(name) => (*name).into(),
                         PropertyName::Computed(node) => {
                             node.run(context)?.to_property_key(context)?
                         }
@@ -114,7 +118,7 @@ impl Executable for Object {
                 }
                 PropertyDefinition::MethodDefinition(kind, name, func) => {
                     let name = match name {
-                        PropertyName::Literal(name) => name.clone().into(),
+                        PropertyName::Literal(name) => (*name).into(),
                         PropertyName::Computed(node) => {
                             node.run(context)?.to_property_key(context)?
                         }
@@ -48,7 +48,7 @@ fn spread_getters_in_object() {
         var a = { x: 42 };
         var aWithXGetter = { ...a, ... { get x() { throw new Error('not thrown yet') } } };
     "#;
-    assert_eq!(&exec(scenario), "\"Error\": \"not thrown yet\"");
+    assert_eq!(&exec(scenario), "\"Error\": \"not thrown yet\" (at 3:52)");
 }
 
 #[test]
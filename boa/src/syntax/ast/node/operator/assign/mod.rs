@@ -46,10 +46,20 @@ impl Assign {
         &self.lhs
     }
 
+    /// Gets a mutable reference to the left hand side of the assignment operation.
+    pub fn lhs_mut(&mut self) -> &mut Node {
+        &mut self.lhs
+    }
+
     /// Gets the right hand side of the assignment operation.
     pub fn rhs(&self) -> &Node {
         &self.rhs
     }
+
+    /// Gets a mutable reference to the right hand side of the assignment operation.
+    pub fn rhs_mut(&mut self) -> &mut Node {
+        &mut self.rhs
+    }
 }
 
 impl Executable for Assign {
@@ -58,12 +68,12 @@ impl Executable for Assign {
         let val = self.rhs().run(context)?;
         match self.lhs() {
             Node::Identifier(ref name) => {
-                if context.has_binding(name.as_ref())? {
+                if context.has_binding(name.sym())? {
                     // Binding already exists
-                    context.set_mutable_binding(name.as_ref(), val.clone(), context.strict())?;
+                    context.set_mutable_binding(name.sym(), val.clone(), context.strict())?;
                 } else {
-                    context.create_mutable_binding(name.as_ref(), true, VariableScope::Function)?;
-                    context.initialize_binding(name.as_ref(), val.clone())?;
+                    context.create_mutable_binding(name.sym(), true, VariableScope::Function)?;
+                    context.initialize_binding(name.sym(), val.clone())?;
                 }
             }
             Node::GetConstField(ref get_const_field) => {
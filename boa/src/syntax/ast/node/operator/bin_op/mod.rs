@@ -51,11 +51,21 @@ impl BinOp {
         &self.lhs
     }
 
+    /// Gets a mutable reference to the left hand side of the binary operation.
+    pub fn lhs_mut(&mut self) -> &mut Node {
+        &mut self.lhs
+    }
+
     /// Gets the right hand side of the binary operation.
     pub fn rhs(&self) -> &Node {
         &self.rhs
     }
 
+    /// Gets a mutable reference to the right hand side of the binary operation.
+    pub fn rhs_mut(&mut self) -> &mut Node {
+        &mut self.rhs
+    }
+
     /// Runs the assignment operators.
     fn run_assign(op: AssignOp, x: JsValue, y: &Node, context: &mut Context) -> JsResult<JsValue> {
         match op {
@@ -176,10 +186,10 @@ impl Executable for BinOp {
             }),
             op::BinOp::Assign(op) => match self.lhs() {
                 Node::Identifier(ref name) => {
-                    let v_a = context.get_binding_value(name.as_ref())?;
+                    let v_a = context.get_binding_value(name.sym())?;
 
                     let value = Self::run_assign(op, v_a, self.rhs(), context)?;
-                    context.set_mutable_binding(name.as_ref(), value.clone(), context.strict())?;
+                    context.set_mutable_binding(name.sym(), value.clone(), context.strict())?;
                     Ok(value)
                 }
                 Node::GetConstField(ref get_const_field) => {
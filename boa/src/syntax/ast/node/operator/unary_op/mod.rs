@@ -47,6 +47,11 @@ impl UnaryOp {
     pub fn target(&self) -> &Node {
         self.target.as_ref()
     }
+
+    /// Gets a mutable reference to the target of this unary operator.
+    pub fn target_mut(&mut self) -> &mut Node {
+        &mut self.target
+    }
 }
 
 impl Executable for UnaryOp {
@@ -1,6 +1,7 @@
 use crate::{
     exec::{Executable, InterpreterState},
     gc::{Finalize, Trace},
+    interner::Sym,
     syntax::ast::node::Node,
     Context, JsResult, JsValue,
 };
@@ -34,24 +35,28 @@ mod tests;
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct Return {
     expr: Option<Box<Node>>,
-    label: Option<Box<str>>,
+    label: Option<Sym>,
 }
 
 impl Return {
-    pub fn label(&self) -> Option<&str> {
-        self.label.as_ref().map(Box::as_ref)
+    pub fn label(&self) -> Option<Sym> {
+        self.label
     }
 
     pub fn expr(&self) -> Option<&Node> {
         self.expr.as_ref().map(Box::as_ref)
     }
 
+    pub fn expr_mut(&mut self) -> Option<&mut Node> {
+        self.expr.as_deref_mut()
+    }
+
     /// Creates a `Return` AST node.
     pub fn new<E, OE, L>(expr: OE, label: L) -> Self
     where
         E: Into<Node>,
         OE: Into<Option<E>>,
-        L: Into<Option<Box<str>>>,
+        L: Into<Option<Sym>>,
     {
         Self {
             expr: expr.into().map(E::into).map(Box::new),
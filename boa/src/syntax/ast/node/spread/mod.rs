@@ -40,6 +40,10 @@ impl Spread {
         &self.val
     }
 
+    pub fn val_mut(&mut self) -> &mut Node {
+        &mut self.val
+    }
+
     /// Creates a `Spread` AST node.
     pub fn new<V>(val: V) -> Self
     where
@@ -4,7 +4,12 @@ use crate::{
     context::StrictType,
     exec::{Executable, InterpreterState},
     gc::{empty_trace, Finalize, Trace},
-    syntax::ast::node::{Declaration, Node},
+    interner::Sym,
+    syntax::ast::{
+        node::{Declaration, Node},
+        side_table::{Keyed, SideTable},
+        Comments, Span,
+    },
     BoaProfiler, Context, JsResult, JsValue,
 };
 use std::{collections::HashSet, fmt, ops::Deref, rc::Rc};
@@ -19,15 +24,27 @@ mod tests;
 ///
 /// Similar to `Node::Block` but without the braces.
 ///
+/// When the list was parsed from source code, it also keeps the source span of each of its
+/// items, and a [`SideTable`] with the spans of its nodes and, if the parser preserved comments,
+/// the comments around them. Source information is not taken into account when comparing
+/// statement lists.
+///
 /// More information:
 ///  - [ECMAScript reference][spec]
 ///
 /// [spec]: https://tc39.es/ecma262/#prod-StatementList
 #[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+#[derive(Debug, Trace, Finalize)]
 pub struct StatementList {
     #[cfg_attr(feature = "deser", serde(flatten))]
-    items: Box<[Node]>,
+    #[unsafe_ignore_trace]
+    items: Rc<[Node]>,
+    #[cfg_attr(feature = "deser", serde(default))]
+    #[unsafe_ignore_trace]
+    spans: Rc<[Span]>,
+    #[cfg_attr(feature = "deser", serde(skip))]
+    #[unsafe_ignore_trace]
+    side_table: Rc<SideTable>,
     strict: bool,
 }
 
@@ -38,6 +55,69 @@ impl StatementList {
         &self.items
     }
 
+    /// Gets a mutable reference to the list of items.
+    ///
+    /// Items can be modified in place, but not added or removed, so that the source spans
+    /// still line up with them. A node which is replaced takes over the source information of
+    /// the node it replaces, if they are of the same kind.
+    ///
+    /// Clones of a list share its items until one of them is modified, which copies them along
+    /// with their source information.
+    #[inline]
+    pub fn items_mut(&mut self) -> &mut [Node] {
+        if Rc::get_mut(&mut self.items).is_none() {
+            let items: Rc<[Node]> = self.items.iter().cloned().collect();
+            self.side_table = Rc::new(self.side_table.clone_to(&self.items, &items));
+            self.items = items;
+        }
+        Rc::get_mut(&mut self.items).expect("the items were just copied")
+    }
+
+    /// Gets the source span of the item at the given index, including the semicolons after it.
+    ///
+    /// Returns `None` if the list was not parsed from source code.
+    #[inline]
+    pub fn span(&self, index: usize) -> Option<Span> {
+        self.spans.get(index).copied()
+    }
+
+    /// Gets the source span of a node of the list.
+    ///
+    /// The node has to be part of one of the items of the list, but not of a nested statement
+    /// list, such as the body of a block or of a function. Returns `None` if the list was not
+    /// parsed from source code, or if the node was not written in the source code.
+    #[inline]
+    pub fn span_of<T: Keyed>(&self, node: &T) -> Option<Span> {
+        self.side_table.span(node)
+    }
+
+    /// Gets the table with the source information of the nodes of the list.
+    #[inline]
+    pub(crate) fn side_table(&self) -> &Rc<SideTable> {
+        &self.side_table
+    }
+
+    /// Sets the source spans of the items, and the table with the source information of the
+    /// nodes, which were built for this list.
+    #[inline]
+    pub(crate) fn with_source(mut self, spans: Box<[Span]>, side_table: SideTable) -> Self {
+        debug_assert_eq!(spans.len(), self.items.len(), "one span is needed per item");
+        self.spans = spans.into();
+        self.side_table = Rc::new(side_table);
+        self
+    }
+
+    /// Gets the comments around a node of the list, such as an item, a property definition or a
+    /// function expression.
+    ///
+    /// Like for [`span_of`](Self::span_of), the node has to be part of the list, but not of a
+    /// nested statement list. Returns `None` if the list was not parsed from source code with
+    /// comments preserved, or if there are no comments around the node.
+    #[inline]
+    pub fn comments_of<T: Keyed>(&self, node: &T) -> Option<&Comments> {
+        self.side_table.comments(node)
+    }
+
     /// Get the strict mode.
     #[inline]
     pub fn strict(&self) -> bool {
@@ -70,7 +150,7 @@ impl StatementList {
         Ok(())
     }
 
-    pub fn lexically_declared_names(&self) -> HashSet<&str> {
+    pub fn lexically_declared_names(&self) -> HashSet<Sym> {
         let mut set = HashSet::new();
         for stmt in self.items() {
             if let Node::LetDeclList(decl_list) | Node::ConstDeclList(decl_list) = stmt {
@@ -79,8 +159,8 @@ impl StatementList {
                     // https://tc39.es/ecma262/#sec-block-static-semantics-early-errors
                     match decl {
                         Declaration::Identifier { ident, .. } => {
-                            if !set.insert(ident.as_ref()) {
-                                unreachable!("Redeclaration of {}", ident.as_ref());
+                            if !set.insert(ident.sym()) {
+                                unreachable!("Redeclaration of {}", ident);
                             }
                         }
                         Declaration::Pattern(p) => {
@@ -97,7 +177,7 @@ impl StatementList {
         set
     }
 
-    pub fn function_declared_names(&self) -> HashSet<&str> {
+    pub fn function_declared_names(&self) -> HashSet<Sym> {
         let mut set = HashSet::new();
         for stmt in self.items() {
             if let Node::FunctionDecl(decl) = stmt {
@@ -107,18 +187,18 @@ impl StatementList {
         set
     }
 
-    pub fn var_declared_names(&self) -> HashSet<&str> {
+    pub fn var_declared_names(&self) -> HashSet<Sym> {
         let mut set = HashSet::new();
         for stmt in self.items() {
             if let Node::VarDeclList(decl_list) = stmt {
                 for decl in decl_list.as_ref() {
                     match decl {
                         Declaration::Identifier { ident, .. } => {
-                            set.insert(ident.as_ref());
+                            set.insert(ident.sym());
                         }
                         Declaration::Pattern(p) => {
                             for ident in p.idents() {
-                                set.insert(ident.as_ref());
+                                set.insert(ident);
                             }
                         }
                     }
@@ -148,10 +228,21 @@ impl Executable for StatementList {
             _ => {}
         }
 
+        // Errors thrown by the items are located with the spans of this list.
+        let side_table = !self.side_table.is_empty();
+        if side_table {
+            context
+                .executor()
+                .push_side_table(Rc::clone(&self.side_table));
+        }
+
         for (i, item) in self.items().iter().enumerate() {
             let val = match item.run(context) {
                 Ok(val) => val,
                 Err(e) => {
+                    if side_table {
+                        context.executor().pop_side_table();
+                    }
                     context.set_strict(strict_before);
                     return Err(e);
                 }
@@ -178,6 +269,9 @@ impl Executable for StatementList {
             }
         }
 
+        if side_table {
+            context.executor().pop_side_table();
+        }
         context.set_strict(strict_before);
 
         Ok(obj)
@@ -190,12 +284,32 @@ where
 {
     fn from(stm: T) -> Self {
         Self {
-            items: stm.into(),
+            items: stm.into().into(),
+            spans: Rc::new([]),
+            side_table: Rc::default(),
             strict: false,
         }
     }
 }
 
+impl Clone for StatementList {
+    /// Clones the list, sharing its items and their source information with it.
+    fn clone(&self) -> Self {
+        Self {
+            items: Rc::clone(&self.items),
+            spans: Rc::clone(&self.spans),
+            side_table: Rc::clone(&self.side_table),
+            strict: self.strict,
+        }
+    }
+}
+
+impl PartialEq for StatementList {
+    fn eq(&self, other: &Self) -> bool {
+        self.items == other.items && self.strict == other.strict
+    }
+}
+
 impl fmt::Display for StatementList {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.display(f, 0)
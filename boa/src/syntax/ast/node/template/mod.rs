@@ -1,7 +1,7 @@
 //! Template literal node.
 
 use super::Node;
-use crate::{builtins::Array, exec::Executable, BoaProfiler, Context, JsResult, JsValue};
+use crate::{builtins::Array, exec::Executable, BoaProfiler, Context, JsResult, JsString, JsValue};
 use gc::{Finalize, Trace};
 
 #[cfg(feature = "deser")]
@@ -29,22 +29,32 @@ impl TemplateLit {
     pub fn new(elements: Vec<TemplateElement>) -> Self {
         TemplateLit { elements }
     }
+
+    /// Gets the string and expression elements of the template literal.
+    pub fn elements(&self) -> &[TemplateElement] {
+        &self.elements
+    }
+
+    /// Gets a mutable reference to the string and expression elements of the template literal.
+    pub fn elements_mut(&mut self) -> &mut [TemplateElement] {
+        &mut self.elements
+    }
 }
 
 impl Executable for TemplateLit {
     fn run(&self, context: &mut Context) -> JsResult<JsValue> {
         let _timer = BoaProfiler::global().start_event("TemplateLiteral", "exec");
-        let mut result = String::new();
+        let mut result = Vec::new();
 
         for element in self.elements.iter() {
             match element {
                 TemplateElement::String(s) => {
-                    result.push_str(s);
+                    result.extend_from_slice(s);
                 }
                 TemplateElement::Expr(node) => {
                     let value = node.run(context)?;
                     let s = value.to_string(context)?;
-                    result.push_str(&s);
+                    result.extend_from_slice(&s);
                 }
             }
         }
@@ -69,7 +79,7 @@ impl fmt::Display for TemplateLit {
 pub struct TaggedTemplate {
     tag: Box<Node>,
     raws: Vec<Box<str>>,
-    cookeds: Vec<Option<Box<str>>>,
+    cookeds: Vec<Option<JsString>>,
     exprs: Vec<Node>,
 }
 
@@ -77,7 +87,7 @@ impl TaggedTemplate {
     pub fn new(
         tag: Node,
         raws: Vec<Box<str>>,
-        cookeds: Vec<Option<Box<str>>>,
+        cookeds: Vec<Option<JsString>>,
         exprs: Vec<Node>,
     ) -> Self {
         Self {
@@ -87,6 +97,36 @@ impl TaggedTemplate {
             exprs,
         }
     }
+
+    /// Gets the tag function of the template.
+    pub fn tag(&self) -> &Node {
+        &self.tag
+    }
+
+    /// Gets a mutable reference to the tag function of the template.
+    pub fn tag_mut(&mut self) -> &mut Node {
+        &mut self.tag
+    }
+
+    /// Gets the raw strings of the template.
+    pub fn raws(&self) -> &[Box<str>] {
+        &self.raws
+    }
+
+    /// Gets the cooked strings of the template, which are `None` for invalid escape sequences.
+    pub fn cookeds(&self) -> &[Option<JsString>] {
+        &self.cookeds
+    }
+
+    /// Gets the substitution expressions of the template.
+    pub fn exprs(&self) -> &[Node] {
+        &self.exprs
+    }
+
+    /// Gets a mutable reference to the substitution expressions of the template.
+    pub fn exprs_mut(&mut self) -> &mut [Node] {
+        &mut self.exprs
+    }
 }
 
 impl Executable for TaggedTemplate {
@@ -102,7 +142,7 @@ impl Executable for TaggedTemplate {
 
         for (i, cooked) in self.cookeds.iter().enumerate() {
             if let Some(cooked) = cooked {
-                template_object.set_field(i, JsValue::new(cooked.as_ref()), false, context)?;
+                template_object.set_field(i, JsValue::new(cooked.clone()), false, context)?;
             } else {
                 template_object.set_field(i, JsValue::undefined(), false, context)?;
             }
@@ -159,6 +199,6 @@ impl From<TaggedTemplate> for Node {
 #[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub enum TemplateElement {
-    String(Box<str>),
+    String(JsString),
     Expr(Node),
 }
@@ -37,6 +37,10 @@ impl Throw {
         &self.expr
     }
 
+    pub fn expr_mut(&mut self) -> &mut Node {
+        &mut self.expr
+    }
+
     /// Creates a `Throw` AST node.
     pub fn new<V>(val: V) -> Self
     where
@@ -64,16 +64,31 @@ impl Try {
         &self.block
     }
 
+    /// Gets a mutable reference to the `try` block.
+    pub fn block_mut(&mut self) -> &mut Block {
+        &mut self.block
+    }
+
     /// Gets the `catch` block, if any.
     pub fn catch(&self) -> Option<&Catch> {
         self.catch.as_ref()
     }
 
+    /// Gets a mutable reference to the `catch` block, if any.
+    pub fn catch_mut(&mut self) -> Option<&mut Catch> {
+        self.catch.as_mut()
+    }
+
     /// Gets the `finally` block, if any.
     pub fn finally(&self) -> Option<&Block> {
         self.finally.as_ref().map(Finally::block)
     }
 
+    /// Gets a mutable reference to the `finally` block, if any.
+    pub fn finally_mut(&mut self) -> Option<&mut Block> {
+        self.finally.as_mut().map(|finally| &mut finally.block)
+    }
+
     /// Implements the display formatting with indentation.
     pub(in crate::syntax::ast::node) fn display(
         &self,
@@ -109,22 +124,22 @@ impl Executable for Try {
                                 debug_assert!(init.is_none());
 
                                 context.create_mutable_binding(
-                                    ident.as_ref(),
+                                    ident.sym(),
                                     false,
                                     VariableScope::Block,
                                 )?;
-                                context.initialize_binding(ident.as_ref(), err)?;
+                                context.initialize_binding(ident.sym(), err)?;
                             }
                             Declaration::Pattern(pattern) => {
                                 debug_assert!(pattern.init().is_none());
 
                                 for (ident, value) in pattern.run(Some(err), context)? {
                                     context.create_mutable_binding(
-                                        ident.as_ref(),
+                                        ident,
                                         false,
                                         VariableScope::Block,
                                     )?;
-                                    context.initialize_binding(ident.as_ref(), value)?;
+                                    context.initialize_binding(ident, value)?;
                                 }
                             }
                         }
@@ -190,11 +205,21 @@ impl Catch {
         self.parameter.as_deref()
     }
 
+    /// Gets a mutable reference to the parameter of the catch block.
+    pub fn parameter_mut(&mut self) -> Option<&mut Declaration> {
+        self.parameter.as_deref_mut()
+    }
+
     /// Retrieves the catch execution block.
     pub fn block(&self) -> &Block {
         &self.block
     }
 
+    /// Retrieves a mutable reference to the catch execution block.
+    pub fn block_mut(&mut self) -> &mut Block {
+        &mut self.block
+    }
+
     /// Implements the display formatting with indentation.
     pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
         f.write_str(" catch")?;
@@ -29,6 +29,10 @@ impl Yield {
         self.expr.as_ref().map(Box::as_ref)
     }
 
+    pub fn expr_mut(&mut self) -> Option<&mut Node> {
+        self.expr.as_deref_mut()
+    }
+
     pub fn delegate(&self) -> bool {
         self.delegate
     }
@@ -0,0 +1,402 @@
+//! This module implements the `SideTable` structure, which keeps the source information of the
+//! nodes of a statement list on the side of the abstract syntax tree.
+//!
+//! Nodes do not store their own source spans and comments, so that they can be built and compared
+//! without them. Instead, each statement list parsed from source code has a table which maps the
+//! address of its nodes to their span and comments. The table of a statement list covers the
+//! nodes of its items, down to nested statement lists, which have their own table.
+
+use super::{
+    node::{
+        AsyncFunctionDecl, AsyncFunctionExpr, Case, Declaration, FormalParameter, GeneratorDecl,
+        Node, PropertyDefinition, StatementList, Switch,
+    },
+    visitor::{
+        walk_case, walk_declaration, walk_formal_parameter, walk_node, walk_property_definition,
+        Visitor,
+    },
+    Comment, Comments, Span,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::{collections::VecDeque, mem::Discriminant};
+
+/// A part of the abstract syntax tree which can have source information attached to it.
+///
+/// This is implemented for nodes, and for the parts of nodes which have their own location in
+/// other AST formats: property definitions, formal parameters, declarations and switch cases.
+pub trait Keyed: private::Sealed {}
+
+impl Keyed for Node {}
+impl Keyed for PropertyDefinition {}
+impl Keyed for FormalParameter {}
+impl Keyed for Declaration {}
+impl Keyed for Case {}
+
+mod private {
+    use super::*;
+
+    /// The kind of a keyed part of the AST, so that parts of different types at the same address
+    /// have different keys.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Kind {
+        Node(Discriminant<Node>),
+        Property,
+        Parameter,
+        Declaration,
+        Case,
+    }
+
+    pub trait Sealed {
+        /// Gets the kind of the part.
+        fn kind(&self) -> Kind;
+    }
+
+    impl Sealed for Node {
+        fn kind(&self) -> Kind {
+            Kind::Node(std::mem::discriminant(self))
+        }
+    }
+
+    impl Sealed for PropertyDefinition {
+        fn kind(&self) -> Kind {
+            Kind::Property
+        }
+    }
+
+    impl Sealed for FormalParameter {
+        fn kind(&self) -> Kind {
+            Kind::Parameter
+        }
+    }
+
+    impl Sealed for Declaration {
+        fn kind(&self) -> Kind {
+            Kind::Declaration
+        }
+    }
+
+    impl Sealed for Case {
+        fn kind(&self) -> Kind {
+            Kind::Case
+        }
+    }
+}
+
+use private::Kind;
+
+/// The key of a part of the AST in a side table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Key {
+    address: usize,
+    kind: Kind,
+}
+
+impl Key {
+    fn of<T: Keyed>(part: &T) -> Self {
+        Self {
+            address: part as *const T as usize,
+            kind: part.kind(),
+        }
+    }
+}
+
+/// The source spans and the comments of the nodes of a statement list.
+///
+/// Nodes are identified by their address, so a table is only valid for the statement list it
+/// belongs to. Cloning a statement list moves the entries of its table over to the cloned nodes.
+#[derive(Debug, Default)]
+pub struct SideTable {
+    spans: FxHashMap<Key, Span>,
+    comments: FxHashMap<Key, Comments>,
+}
+
+impl SideTable {
+    /// Gets the source span of a node, if it is known.
+    #[inline]
+    pub fn span<T: Keyed>(&self, node: &T) -> Option<Span> {
+        self.spans.get(&Key::of(node)).copied()
+    }
+
+    /// Gets the comments attached to a node, if it has any.
+    #[inline]
+    pub fn comments<T: Keyed>(&self, node: &T) -> Option<&Comments> {
+        self.comments.get(&Key::of(node))
+    }
+
+    /// Checks if the table has no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty() && self.comments.is_empty()
+    }
+
+    /// Builds the table of the statement list `items` from the spans recorded by the parser.
+    ///
+    /// The parser records the span of each node once it is built, before the node is moved to its
+    /// final place in the tree, so the records are matched back with the nodes: a record whose
+    /// span contains the span of the previous ones is the parent of these, and the children of a
+    /// node are matched in source order with the records of the same kind among the children of
+    /// its record. Nodes that were not recorded, because the parser made them up, get no span.
+    pub(crate) fn from_records(items: &[Node], records: &[Record]) -> Self {
+        let mut children = vec![Vec::new(); records.len()];
+        let mut roots: Vec<usize> = Vec::new();
+        for (index, record) in records.iter().enumerate() {
+            let mut first = roots.len();
+            while first > 0 && record.span.contains(records[roots[first - 1]].span) {
+                first -= 1;
+            }
+            children[index] = roots.split_off(first);
+            roots.push(index);
+        }
+
+        let mut matcher = Matcher {
+            records,
+            children,
+            table: Self::default(),
+        };
+        let items: Vec<_> = items.iter().map(Part::Node).collect();
+        matcher.assign(&items, &mut Candidates::new(records, roots));
+        matcher.table
+    }
+
+    /// Attaches comments to the statement list `items` and to their nodes.
+    ///
+    /// The comments around the items are given by the parser, one set for each item. The other
+    /// comments are taken from the tokens they were found around: `take` takes the comments before
+    /// the token which starts a span, and the ones after the token which ends it. These are
+    /// attached to the outermost node which starts or ends with the token, unless they are already
+    /// attached to an item. Comments which are next to no node are left out.
+    pub(crate) fn attach_comments<F>(
+        &mut self,
+        items: &[Node],
+        comments: Vec<Comments>,
+        mut take: F,
+    ) where
+        F: FnMut(Span) -> (Vec<Comment>, Vec<Comment>),
+    {
+        let attached: FxHashSet<_> = comments
+            .iter()
+            .flat_map(|comments| comments.leading().iter().chain(comments.trailing()))
+            .map(|comment| comment.span().start())
+            .collect();
+        let unattached = |comments: Vec<Comment>| -> Vec<_> {
+            comments
+                .into_iter()
+                .filter(|comment| !attached.contains(&comment.span().start()))
+                .collect()
+        };
+
+        for (item, comments) in items.iter().zip(comments) {
+            if !comments.is_empty() {
+                self.comments.insert(Key::of(item), comments);
+            }
+        }
+        for key in keys(items) {
+            let span = match self.spans.get(&key) {
+                Some(span) => *span,
+                None => continue,
+            };
+            let (leading, trailing) = take(span);
+            let (leading, trailing) = (unattached(leading), unattached(trailing));
+            if leading.is_empty() && trailing.is_empty() {
+                continue;
+            }
+            let comments = self.comments.entry(key).or_default();
+            *comments = Comments::new(
+                [comments.leading(), &leading].concat(),
+                [comments.trailing(), &trailing].concat(),
+            );
+        }
+    }
+
+    /// Copies the table of the statement list `from` to its clone `to`.
+    pub(crate) fn clone_to(&self, from: &[Node], to: &[Node]) -> Self {
+        if self.is_empty() {
+            return Self::default();
+        }
+        let keys = keys(from).into_iter().zip(keys(to));
+        let mut table = Self::default();
+        for (from, to) in keys {
+            if let Some(span) = self.spans.get(&from) {
+                table.spans.insert(to, *span);
+            }
+            if let Some(comments) = self.comments.get(&from) {
+                table.comments.insert(to, comments.clone());
+            }
+        }
+        table
+    }
+}
+
+/// The span of a node, as recorded by the parser.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Record {
+    kind: Kind,
+    span: Span,
+}
+
+impl Record {
+    /// Creates the record of a node which was just parsed.
+    pub(crate) fn new<T: Keyed>(part: &T, span: Span) -> Self {
+        Self {
+            kind: part.kind(),
+            span,
+        }
+    }
+}
+
+/// Matches the nodes of a statement list with the records of the parser.
+#[derive(Debug)]
+struct Matcher<'r> {
+    records: &'r [Record],
+    children: Vec<Vec<usize>>,
+    table: SideTable,
+}
+
+impl Matcher<'_> {
+    /// Matches parts of the AST with the given candidate records, in order.
+    fn assign(&mut self, parts: &[Part<'_>], candidates: &mut Candidates) {
+        for part in parts {
+            match candidates.take(part.kind()) {
+                Some(index) => {
+                    let record = self.records[index];
+                    self.table.spans.insert(part.key(), record.span);
+                    let children = std::mem::take(&mut self.children[index]);
+                    self.assign(
+                        &part.children(),
+                        &mut Candidates::new(self.records, children),
+                    );
+                }
+                // A node made up by the parser, whose children may have been recorded.
+                None => self.assign(&part.children(), candidates),
+            }
+        }
+    }
+}
+
+/// The records which can be matched with the children of a node, by kind.
+#[derive(Debug, Default)]
+struct Candidates(FxHashMap<Kind, VecDeque<usize>>);
+
+impl Candidates {
+    fn new(records: &[Record], indices: Vec<usize>) -> Self {
+        let mut candidates = Self::default();
+        for index in indices {
+            candidates
+                .0
+                .entry(records[index].kind)
+                .or_default()
+                .push_back(index);
+        }
+        candidates
+    }
+
+    /// Takes the first record of the given kind.
+    fn take(&mut self, kind: Kind) -> Option<usize> {
+        self.0.get_mut(&kind)?.pop_front()
+    }
+}
+
+/// A keyed part of the AST.
+#[derive(Debug, Clone, Copy)]
+enum Part<'ast> {
+    Node(&'ast Node),
+    Property(&'ast PropertyDefinition),
+    Parameter(&'ast FormalParameter),
+    Declaration(&'ast Declaration),
+    Case(&'ast Case),
+}
+
+impl<'ast> Part<'ast> {
+    fn key(self) -> Key {
+        match self {
+            Self::Node(node) => Key::of(node),
+            Self::Property(property) => Key::of(property),
+            Self::Parameter(parameter) => Key::of(parameter),
+            Self::Declaration(declaration) => Key::of(declaration),
+            Self::Case(case) => Key::of(case),
+        }
+    }
+
+    fn kind(self) -> Kind {
+        self.key().kind
+    }
+
+    /// Gets the keyed parts directly below this one, without the ones in nested statement lists.
+    fn children(self) -> Vec<Part<'ast>> {
+        let mut children = Children(Vec::new());
+        match self {
+            Self::Node(node) => walk_node(&mut children, node),
+            Self::Property(property) => walk_property_definition(&mut children, property),
+            Self::Parameter(parameter) => walk_formal_parameter(&mut children, parameter),
+            Self::Declaration(declaration) => walk_declaration(&mut children, declaration),
+            Self::Case(case) => walk_case(&mut children, case),
+        }
+        children.0
+    }
+}
+
+/// Collects the keyed parts directly below a part of the AST.
+#[derive(Debug)]
+struct Children<'ast>(Vec<Part<'ast>>);
+
+impl<'ast> Visitor<'ast> for Children<'ast> {
+    fn visit_statement_list(&mut self, _list: &'ast StatementList) {}
+
+    fn visit_node(&mut self, node: &'ast Node) {
+        self.0.push(Part::Node(node));
+    }
+
+    fn visit_property_definition(&mut self, property: &'ast PropertyDefinition) {
+        self.0.push(Part::Property(property));
+    }
+
+    fn visit_formal_parameter(&mut self, parameter: &'ast FormalParameter) {
+        self.0.push(Part::Parameter(parameter));
+    }
+
+    fn visit_declaration(&mut self, declaration: &'ast Declaration) {
+        self.0.push(Part::Declaration(declaration));
+    }
+
+    fn visit_case(&mut self, case: &'ast Case) {
+        self.0.push(Part::Case(case));
+    }
+
+    // The default clause and the bodies of these functions are nested statement lists, although
+    // they are walked as plain nodes.
+    fn visit_switch(&mut self, switch: &'ast Switch) {
+        self.visit_node(switch.val());
+        for case in switch.cases() {
+            self.visit_case(case);
+        }
+    }
+
+    fn visit_async_function_decl(&mut self, function: &'ast AsyncFunctionDecl) {
+        for parameter in function.parameters() {
+            self.visit_formal_parameter(parameter);
+        }
+    }
+
+    fn visit_async_function_expr(&mut self, function: &'ast AsyncFunctionExpr) {
+        for parameter in function.parameters() {
+            self.visit_formal_parameter(parameter);
+        }
+    }
+
+    fn visit_generator_decl(&mut self, generator: &'ast GeneratorDecl) {
+        for parameter in generator.parameters() {
+            self.visit_formal_parameter(parameter);
+        }
+    }
+}
+
+/// Gets the keys of all the parts of a statement list, in a fixed order.
+fn keys(items: &[Node]) -> Vec<Key> {
+    let mut keys = Vec::new();
+    let mut parts: Vec<_> = items.iter().rev().map(Part::Node).collect();
+    while let Some(part) = parts.pop() {
+        keys.push(part.key());
+        parts.extend(part.children().into_iter().rev());
+    }
+    keys
+}
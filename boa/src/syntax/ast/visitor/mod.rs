@@ -0,0 +1,649 @@
+//! Traversal of the abstract syntax tree.
+//!
+//! The [`Visitor`] trait walks the tree by shared reference, and [`VisitorMut`] walks it by
+//! mutable reference. Each `visit_*` method defaults to calling the matching `walk_*` function,
+//! which visits the children of the node in source order. An implementor overrides the methods
+//! for the nodes it is interested in, and calls the `walk_*` function from the override to keep
+//! visiting the children.
+//!
+//! Binding names in declarations, parameters and patterns are not visited as identifiers, only
+//! identifier references are.
+//!
+//! ```
+//! use boa::syntax::{
+//!     ast::{node::Identifier, visitor::Visitor},
+//!     Parser,
+//! };
+//!
+//! #[derive(Default)]
+//! struct References<'ast>(Vec<&'ast str>);
+//!
+//! impl<'ast> Visitor<'ast> for References<'ast> {
+//!     fn visit_identifier(&mut self, identifier: &'ast Identifier) {
+//!         self.0.push(identifier.as_ref());
+//!     }
+//! }
+//!
+//! let statement_list = Parser::new("let a = b + c(d);".as_bytes(), false)
+//!     .parse_all()
+//!     .unwrap();
+//! let mut references = References::default();
+//! references.visit_statement_list(&statement_list);
+//! assert_eq!(references.0, ["b", "c", "d"]);
+//! ```
+
+mod visitor_mut;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::visitor_mut::*;
+
+use crate::syntax::ast::{
+    node::{
+        declaration::{BindingPatternTypeArray, BindingPatternTypeObject, DeclarationPattern},
+        template::TemplateElement,
+        ArrayDecl, ArrowFunctionDecl, Assign, AsyncFunctionDecl, AsyncFunctionExpr, AwaitExpr,
+        BinOp, Block, Break, Call, Case, Catch, ConditionalOp, Continue, Declaration,
+        DeclarationList, DoWhileLoop, ForInLoop, ForLoop, ForOfLoop, FormalParameter, FunctionDecl,
+        FunctionExpr, GeneratorDecl, GeneratorExpr, GetConstField, GetField, Identifier, If, New,
+        Node, Object, PropertyDefinition, PropertyName, Return, Spread, StatementList, Switch,
+        TaggedTemplate, TemplateLit, Throw, Try, UnaryOp, WhileLoop, Yield,
+    },
+    Const,
+};
+
+/// Visits the nodes of the abstract syntax tree by shared reference.
+///
+/// See the [module level documentation](self) for more information.
+pub trait Visitor<'ast>: Sized {
+    fn visit_statement_list(&mut self, list: &'ast StatementList) {
+        walk_statement_list(self, list)
+    }
+
+    fn visit_node(&mut self, node: &'ast Node) {
+        walk_node(self, node)
+    }
+
+    fn visit_array_decl(&mut self, array: &'ast ArrayDecl) {
+        walk_array_decl(self, array)
+    }
+
+    fn visit_arrow_function_decl(&mut self, function: &'ast ArrowFunctionDecl) {
+        walk_arrow_function_decl(self, function)
+    }
+
+    fn visit_assign(&mut self, assign: &'ast Assign) {
+        walk_assign(self, assign)
+    }
+
+    fn visit_async_function_decl(&mut self, function: &'ast AsyncFunctionDecl) {
+        walk_async_function_decl(self, function)
+    }
+
+    fn visit_async_function_expr(&mut self, function: &'ast AsyncFunctionExpr) {
+        walk_async_function_expr(self, function)
+    }
+
+    fn visit_await_expr(&mut self, expr: &'ast AwaitExpr) {
+        walk_await_expr(self, expr)
+    }
+
+    fn visit_bin_op(&mut self, op: &'ast BinOp) {
+        walk_bin_op(self, op)
+    }
+
+    fn visit_block(&mut self, block: &'ast Block) {
+        walk_block(self, block)
+    }
+
+    fn visit_break(&mut self, _break: &'ast Break) {}
+
+    fn visit_call(&mut self, call: &'ast Call) {
+        walk_call(self, call)
+    }
+
+    fn visit_conditional_op(&mut self, op: &'ast ConditionalOp) {
+        walk_conditional_op(self, op)
+    }
+
+    fn visit_const(&mut self, _const: &'ast Const) {}
+
+    fn visit_continue(&mut self, _continue: &'ast Continue) {}
+
+    fn visit_declaration_list(&mut self, list: &'ast DeclarationList) {
+        walk_declaration_list(self, list)
+    }
+
+    fn visit_declaration(&mut self, declaration: &'ast Declaration) {
+        walk_declaration(self, declaration)
+    }
+
+    fn visit_declaration_pattern(&mut self, pattern: &'ast DeclarationPattern) {
+        walk_declaration_pattern(self, pattern)
+    }
+
+    fn visit_do_while_loop(&mut self, do_while: &'ast DoWhileLoop) {
+        walk_do_while_loop(self, do_while)
+    }
+
+    fn visit_formal_parameter(&mut self, parameter: &'ast FormalParameter) {
+        walk_formal_parameter(self, parameter)
+    }
+
+    fn visit_function_decl(&mut self, function: &'ast FunctionDecl) {
+        walk_function_decl(self, function)
+    }
+
+    fn visit_function_expr(&mut self, function: &'ast FunctionExpr) {
+        walk_function_expr(self, function)
+    }
+
+    fn visit_generator_decl(&mut self, generator: &'ast GeneratorDecl) {
+        walk_generator_decl(self, generator)
+    }
+
+    fn visit_generator_expr(&mut self, generator: &'ast GeneratorExpr) {
+        walk_generator_expr(self, generator)
+    }
+
+    fn visit_get_const_field(&mut self, get_const_field: &'ast GetConstField) {
+        walk_get_const_field(self, get_const_field)
+    }
+
+    fn visit_get_field(&mut self, get_field: &'ast GetField) {
+        walk_get_field(self, get_field)
+    }
+
+    fn visit_for_loop(&mut self, for_loop: &'ast ForLoop) {
+        walk_for_loop(self, for_loop)
+    }
+
+    fn visit_for_in_loop(&mut self, for_in_loop: &'ast ForInLoop) {
+        walk_for_in_loop(self, for_in_loop)
+    }
+
+    fn visit_for_of_loop(&mut self, for_of_loop: &'ast ForOfLoop) {
+        walk_for_of_loop(self, for_of_loop)
+    }
+
+    fn visit_identifier(&mut self, _identifier: &'ast Identifier) {}
+
+    fn visit_if(&mut self, if_smt: &'ast If) {
+        walk_if(self, if_smt)
+    }
+
+    fn visit_new(&mut self, new: &'ast New) {
+        walk_new(self, new)
+    }
+
+    fn visit_object(&mut self, object: &'ast Object) {
+        walk_object(self, object)
+    }
+
+    fn visit_property_definition(&mut self, property: &'ast PropertyDefinition) {
+        walk_property_definition(self, property)
+    }
+
+    fn visit_property_name(&mut self, name: &'ast PropertyName) {
+        walk_property_name(self, name)
+    }
+
+    fn visit_return(&mut self, return_smt: &'ast Return) {
+        walk_return(self, return_smt)
+    }
+
+    fn visit_switch(&mut self, switch: &'ast Switch) {
+        walk_switch(self, switch)
+    }
+
+    fn visit_case(&mut self, case: &'ast Case) {
+        walk_case(self, case)
+    }
+
+    fn visit_spread(&mut self, spread: &'ast Spread) {
+        walk_spread(self, spread)
+    }
+
+    fn visit_tagged_template(&mut self, template: &'ast TaggedTemplate) {
+        walk_tagged_template(self, template)
+    }
+
+    fn visit_template_lit(&mut self, template: &'ast TemplateLit) {
+        walk_template_lit(self, template)
+    }
+
+    fn visit_throw(&mut self, throw: &'ast Throw) {
+        walk_throw(self, throw)
+    }
+
+    fn visit_try(&mut self, try_node: &'ast Try) {
+        walk_try(self, try_node)
+    }
+
+    fn visit_catch(&mut self, catch: &'ast Catch) {
+        walk_catch(self, catch)
+    }
+
+    fn visit_unary_op(&mut self, op: &'ast UnaryOp) {
+        walk_unary_op(self, op)
+    }
+
+    fn visit_while_loop(&mut self, while_loop: &'ast WhileLoop) {
+        walk_while_loop(self, while_loop)
+    }
+
+    fn visit_yield(&mut self, yield_expr: &'ast Yield) {
+        walk_yield(self, yield_expr)
+    }
+}
+
+pub fn walk_statement_list<'ast, V: Visitor<'ast>>(visitor: &mut V, list: &'ast StatementList) {
+    for node in list.items() {
+        visitor.visit_node(node);
+    }
+}
+
+pub fn walk_node<'ast, V: Visitor<'ast>>(visitor: &mut V, node: &'ast Node) {
+    match node {
+        Node::ArrayDecl(array) => visitor.visit_array_decl(array),
+        Node::ArrowFunctionDecl(function) => visitor.visit_arrow_function_decl(function),
+        Node::Assign(assign) => visitor.visit_assign(assign),
+        Node::AsyncFunctionDecl(function) => visitor.visit_async_function_decl(function),
+        Node::AsyncFunctionExpr(function) => visitor.visit_async_function_expr(function),
+        Node::AwaitExpr(expr) => visitor.visit_await_expr(expr),
+        Node::BinOp(op) => visitor.visit_bin_op(op),
+        Node::Block(block) => visitor.visit_block(block),
+        Node::Break(break_smt) => visitor.visit_break(break_smt),
+        Node::Call(call) => visitor.visit_call(call),
+        Node::ConditionalOp(op) => visitor.visit_conditional_op(op),
+        Node::Const(constant) => visitor.visit_const(constant),
+        Node::ConstDeclList(list) | Node::LetDeclList(list) | Node::VarDeclList(list) => {
+            visitor.visit_declaration_list(list)
+        }
+        Node::Continue(continue_smt) => visitor.visit_continue(continue_smt),
+        Node::DoWhileLoop(do_while) => visitor.visit_do_while_loop(do_while),
+        Node::FunctionDecl(function) => visitor.visit_function_decl(function),
+        Node::FunctionExpr(function) => visitor.visit_function_expr(function),
+        Node::GetConstField(get_const_field) => visitor.visit_get_const_field(get_const_field),
+        Node::GetField(get_field) => visitor.visit_get_field(get_field),
+        Node::ForLoop(for_loop) => visitor.visit_for_loop(for_loop),
+        Node::ForInLoop(for_in_loop) => visitor.visit_for_in_loop(for_in_loop),
+        Node::ForOfLoop(for_of_loop) => visitor.visit_for_of_loop(for_of_loop),
+        Node::If(if_smt) => visitor.visit_if(if_smt),
+        Node::Identifier(identifier) => visitor.visit_identifier(identifier),
+        Node::New(new) => visitor.visit_new(new),
+        Node::Object(object) => visitor.visit_object(object),
+        Node::Return(return_smt) => visitor.visit_return(return_smt),
+        Node::Switch(switch) => visitor.visit_switch(switch),
+        Node::Spread(spread) => visitor.visit_spread(spread),
+        Node::TaggedTemplate(template) => visitor.visit_tagged_template(template),
+        Node::TemplateLit(template) => visitor.visit_template_lit(template),
+        Node::Throw(throw) => visitor.visit_throw(throw),
+        Node::Try(try_node) => visitor.visit_try(try_node),
+        Node::UnaryOp(op) => visitor.visit_unary_op(op),
+        Node::WhileLoop(while_loop) => visitor.visit_while_loop(while_loop),
+        Node::Yield(yield_expr) => visitor.visit_yield(yield_expr),
+        Node::GeneratorDecl(generator) => visitor.visit_generator_decl(generator),
+        Node::GeneratorExpr(generator) => visitor.visit_generator_expr(generator),
+        Node::This | Node::Empty => {}
+    }
+}
+
+pub fn walk_array_decl<'ast, V: Visitor<'ast>>(visitor: &mut V, array: &'ast ArrayDecl) {
+    for element in array.as_ref() {
+        visitor.visit_node(element);
+    }
+}
+
+pub fn walk_arrow_function_decl<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    function: &'ast ArrowFunctionDecl,
+) {
+    for parameter in function.params() {
+        visitor.visit_formal_parameter(parameter);
+    }
+    visitor.visit_statement_list(function.body());
+}
+
+pub fn walk_assign<'ast, V: Visitor<'ast>>(visitor: &mut V, assign: &'ast Assign) {
+    visitor.visit_node(assign.lhs());
+    visitor.visit_node(assign.rhs());
+}
+
+pub fn walk_async_function_decl<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    function: &'ast AsyncFunctionDecl,
+) {
+    for parameter in function.parameters() {
+        visitor.visit_formal_parameter(parameter);
+    }
+    for node in function.body() {
+        visitor.visit_node(node);
+    }
+}
+
+pub fn walk_async_function_expr<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    function: &'ast AsyncFunctionExpr,
+) {
+    for parameter in function.parameters() {
+        visitor.visit_formal_parameter(parameter);
+    }
+    for node in function.body() {
+        visitor.visit_node(node);
+    }
+}
+
+pub fn walk_await_expr<'ast, V: Visitor<'ast>>(visitor: &mut V, expr: &'ast AwaitExpr) {
+    visitor.visit_node(expr.expr());
+}
+
+pub fn walk_bin_op<'ast, V: Visitor<'ast>>(visitor: &mut V, op: &'ast BinOp) {
+    visitor.visit_node(op.lhs());
+    visitor.visit_node(op.rhs());
+}
+
+pub fn walk_block<'ast, V: Visitor<'ast>>(visitor: &mut V, block: &'ast Block) {
+    visitor.visit_statement_list(block.statement_list());
+}
+
+pub fn walk_call<'ast, V: Visitor<'ast>>(visitor: &mut V, call: &'ast Call) {
+    visitor.visit_node(call.expr());
+    for argument in call.args() {
+        visitor.visit_node(argument);
+    }
+}
+
+pub fn walk_conditional_op<'ast, V: Visitor<'ast>>(visitor: &mut V, op: &'ast ConditionalOp) {
+    visitor.visit_node(op.cond());
+    visitor.visit_node(op.if_true());
+    visitor.visit_node(op.if_false());
+}
+
+pub fn walk_declaration_list<'ast, V: Visitor<'ast>>(visitor: &mut V, list: &'ast DeclarationList) {
+    for declaration in list.as_ref() {
+        visitor.visit_declaration(declaration);
+    }
+}
+
+pub fn walk_declaration<'ast, V: Visitor<'ast>>(visitor: &mut V, declaration: &'ast Declaration) {
+    match declaration {
+        Declaration::Identifier { init, .. } => {
+            if let Some(init) = init {
+                visitor.visit_node(init);
+            }
+        }
+        Declaration::Pattern(pattern) => visitor.visit_declaration_pattern(pattern),
+    }
+}
+
+pub fn walk_declaration_pattern<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    pattern: &'ast DeclarationPattern,
+) {
+    match pattern {
+        DeclarationPattern::Object(object) => {
+            if let Some(init) = object.init() {
+                visitor.visit_node(init);
+            }
+            for binding in object.bindings() {
+                match binding {
+                    BindingPatternTypeObject::SingleName { default_init, .. } => {
+                        if let Some(init) = default_init {
+                            visitor.visit_node(init);
+                        }
+                    }
+                    BindingPatternTypeObject::BindingPattern {
+                        pattern,
+                        default_init,
+                        ..
+                    } => {
+                        visitor.visit_declaration_pattern(pattern);
+                        if let Some(init) = default_init {
+                            visitor.visit_node(init);
+                        }
+                    }
+                    BindingPatternTypeObject::Empty
+                    | BindingPatternTypeObject::RestProperty { .. } => {}
+                }
+            }
+        }
+        DeclarationPattern::Array(array) => {
+            if let Some(init) = array.init() {
+                visitor.visit_node(init);
+            }
+            for binding in array.bindings() {
+                match binding {
+                    BindingPatternTypeArray::SingleName { default_init, .. } => {
+                        if let Some(init) = default_init {
+                            visitor.visit_node(init);
+                        }
+                    }
+                    BindingPatternTypeArray::BindingPattern { pattern }
+                    | BindingPatternTypeArray::BindingPatternRest { pattern } => {
+                        visitor.visit_declaration_pattern(pattern);
+                    }
+                    BindingPatternTypeArray::Empty
+                    | BindingPatternTypeArray::Elision
+                    | BindingPatternTypeArray::SingleNameRest { .. } => {}
+                }
+            }
+        }
+    }
+}
+
+pub fn walk_do_while_loop<'ast, V: Visitor<'ast>>(visitor: &mut V, do_while: &'ast DoWhileLoop) {
+    visitor.visit_node(do_while.body());
+    visitor.visit_node(do_while.cond());
+}
+
+pub fn walk_formal_parameter<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    parameter: &'ast FormalParameter,
+) {
+    if let Some(init) = parameter.init() {
+        visitor.visit_node(init);
+    }
+}
+
+pub fn walk_function_decl<'ast, V: Visitor<'ast>>(visitor: &mut V, function: &'ast FunctionDecl) {
+    for parameter in function.parameters() {
+        visitor.visit_formal_parameter(parameter);
+    }
+    visitor.visit_statement_list(function.body());
+}
+
+pub fn walk_function_expr<'ast, V: Visitor<'ast>>(visitor: &mut V, function: &'ast FunctionExpr) {
+    for parameter in function.parameters() {
+        visitor.visit_formal_parameter(parameter);
+    }
+    visitor.visit_statement_list(function.body());
+}
+
+pub fn walk_generator_decl<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    generator: &'ast GeneratorDecl,
+) {
+    for parameter in generator.parameters() {
+        visitor.visit_formal_parameter(parameter);
+    }
+    for node in generator.body() {
+        visitor.visit_node(node);
+    }
+}
+
+pub fn walk_generator_expr<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    generator: &'ast GeneratorExpr,
+) {
+    for parameter in generator.parameters() {
+        visitor.visit_formal_parameter(parameter);
+    }
+    visitor.visit_statement_list(generator.body());
+}
+
+pub fn walk_get_const_field<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    get_const_field: &'ast GetConstField,
+) {
+    visitor.visit_node(get_const_field.obj());
+}
+
+pub fn walk_get_field<'ast, V: Visitor<'ast>>(visitor: &mut V, get_field: &'ast GetField) {
+    visitor.visit_node(get_field.obj());
+    visitor.visit_node(get_field.field());
+}
+
+pub fn walk_for_loop<'ast, V: Visitor<'ast>>(visitor: &mut V, for_loop: &'ast ForLoop) {
+    if let Some(init) = for_loop.init() {
+        visitor.visit_node(init);
+    }
+    if let Some(condition) = for_loop.condition() {
+        visitor.visit_node(condition);
+    }
+    if let Some(final_expr) = for_loop.final_expr() {
+        visitor.visit_node(final_expr);
+    }
+    visitor.visit_node(for_loop.body());
+}
+
+pub fn walk_for_in_loop<'ast, V: Visitor<'ast>>(visitor: &mut V, for_in_loop: &'ast ForInLoop) {
+    visitor.visit_node(for_in_loop.variable());
+    visitor.visit_node(for_in_loop.expr());
+    visitor.visit_node(for_in_loop.body());
+}
+
+pub fn walk_for_of_loop<'ast, V: Visitor<'ast>>(visitor: &mut V, for_of_loop: &'ast ForOfLoop) {
+    visitor.visit_node(for_of_loop.variable());
+    visitor.visit_node(for_of_loop.iterable());
+    visitor.visit_node(for_of_loop.body());
+}
+
+pub fn walk_if<'ast, V: Visitor<'ast>>(visitor: &mut V, if_smt: &'ast If) {
+    visitor.visit_node(if_smt.cond());
+    visitor.visit_node(if_smt.body());
+    if let Some(else_node) = if_smt.else_node() {
+        visitor.visit_node(else_node);
+    }
+}
+
+pub fn walk_new<'ast, V: Visitor<'ast>>(visitor: &mut V, new: &'ast New) {
+    visitor.visit_node(new.expr());
+    for argument in new.args() {
+        visitor.visit_node(argument);
+    }
+}
+
+pub fn walk_object<'ast, V: Visitor<'ast>>(visitor: &mut V, object: &'ast Object) {
+    for property in object.properties() {
+        visitor.visit_property_definition(property);
+    }
+}
+
+pub fn walk_property_definition<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    property: &'ast PropertyDefinition,
+) {
+    match property {
+        PropertyDefinition::IdentifierReference(_) => {}
+        PropertyDefinition::Property(name, value) => {
+            visitor.visit_property_name(name);
+            visitor.visit_node(value);
+        }
+        PropertyDefinition::MethodDefinition(_, name, function) => {
+            visitor.visit_property_name(name);
+            visitor.visit_function_expr(function);
+        }
+        PropertyDefinition::SpreadObject(object) => visitor.visit_node(object),
+    }
+}
+
+pub fn walk_property_name<'ast, V: Visitor<'ast>>(visitor: &mut V, name: &'ast PropertyName) {
+    match name {
+        PropertyName::Literal(_) => {}
+        PropertyName::Computed(node) => visitor.visit_node(node),
+    }
+}
+
+pub fn walk_return<'ast, V: Visitor<'ast>>(visitor: &mut V, return_smt: &'ast Return) {
+    if let Some(expr) = return_smt.expr() {
+        visitor.visit_node(expr);
+    }
+}
+
+pub fn walk_switch<'ast, V: Visitor<'ast>>(visitor: &mut V, switch: &'ast Switch) {
+    visitor.visit_node(switch.val());
+    for case in switch.cases() {
+        visitor.visit_case(case);
+    }
+    if let Some(default) = switch.default() {
+        for node in default {
+            visitor.visit_node(node);
+        }
+    }
+}
+
+pub fn walk_case<'ast, V: Visitor<'ast>>(visitor: &mut V, case: &'ast Case) {
+    visitor.visit_node(case.condition());
+    visitor.visit_statement_list(case.body());
+}
+
+pub fn walk_spread<'ast, V: Visitor<'ast>>(visitor: &mut V, spread: &'ast Spread) {
+    visitor.visit_node(spread.val());
+}
+
+pub fn walk_tagged_template<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    template: &'ast TaggedTemplate,
+) {
+    visitor.visit_node(template.tag());
+    for expr in template.exprs() {
+        visitor.visit_node(expr);
+    }
+}
+
+pub fn walk_template_lit<'ast, V: Visitor<'ast>>(visitor: &mut V, template: &'ast TemplateLit) {
+    for element in template.elements() {
+        match element {
+            TemplateElement::String(_) => {}
+            TemplateElement::Expr(expr) => visitor.visit_node(expr),
+        }
+    }
+}
+
+pub fn walk_throw<'ast, V: Visitor<'ast>>(visitor: &mut V, throw: &'ast Throw) {
+    visitor.visit_node(throw.expr());
+}
+
+pub fn walk_try<'ast, V: Visitor<'ast>>(visitor: &mut V, try_node: &'ast Try) {
+    visitor.visit_block(try_node.block());
+    if let Some(catch) = try_node.catch() {
+        visitor.visit_catch(catch);
+    }
+    if let Some(finally) = try_node.finally() {
+        visitor.visit_block(finally);
+    }
+}
+
+pub fn walk_catch<'ast, V: Visitor<'ast>>(visitor: &mut V, catch: &'ast Catch) {
+    if let Some(parameter) = catch.parameter() {
+        visitor.visit_declaration(parameter);
+    }
+    visitor.visit_block(catch.block());
+}
+
+pub fn walk_unary_op<'ast, V: Visitor<'ast>>(visitor: &mut V, op: &'ast UnaryOp) {
+    visitor.visit_node(op.target());
+}
+
+pub fn walk_while_loop<'ast, V: Visitor<'ast>>(visitor: &mut V, while_loop: &'ast WhileLoop) {
+    visitor.visit_node(while_loop.cond());
+    visitor.visit_node(while_loop.body());
+}
+
+pub fn walk_yield<'ast, V: Visitor<'ast>>(visitor: &mut V, yield_expr: &'ast Yield) {
+    if let Some(expr) = yield_expr.expr() {
+        visitor.visit_node(expr);
+    }
+}
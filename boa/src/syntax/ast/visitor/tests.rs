@@ -0,0 +1,138 @@
+use super::{walk_function_decl, Visitor, VisitorMut};
+use crate::syntax::{
+    ast::{
+        node::{FunctionDecl, Identifier, Node},
+        Const,
+    },
+    Parser,
+};
+
+/// Collects the names of all identifier references.
+#[derive(Default)]
+struct References<'ast>(Vec<&'ast str>);
+
+impl<'ast> Visitor<'ast> for References<'ast> {
+    fn visit_identifier(&mut self, identifier: &'ast Identifier) {
+        self.0.push(identifier.as_ref());
+    }
+}
+
+#[track_caller]
+fn references(js: &str) -> Vec<String> {
+    let statement_list = Parser::new(js.as_bytes(), false)
+        .parse_all()
+        .expect("failed to parse");
+    let mut references = References::default();
+    references.visit_statement_list(&statement_list);
+    references.0.into_iter().map(str::to_owned).collect()
+}
+
+#[test]
+fn visit_expressions() {
+    assert_eq!(
+        references("a = b ? c[d] : e.f(...g, `${h}`, !i, new j(k));"),
+        ["a", "b", "c", "d", "e", "g", "h", "i", "j", "k"]
+    );
+    assert_eq!(
+        references("let o = { [a]: b, c, ...d, e() { return f; } };"),
+        ["a", "b", "c", "d", "f"]
+    );
+    assert_eq!(references("a`${b}x${c}`;"), ["a", "b", "c"]);
+}
+
+#[test]
+fn visit_statements() {
+    assert_eq!(
+        references(
+            r#"
+            if (a) { b; } else c;
+            for (let i = d; i < e; i++) f;
+            for (const g in h) i;
+            for (const j of k) l;
+            while (m) n;
+            do o; while (p);
+            switch (q) { case r: s; default: t; }
+            try { u; } catch (v) { w; } finally { x; }
+            throw y;
+            "#
+        ),
+        [
+            "a", "b", "c", "d", "i", "e", "i", "f", "h", "i", "k", "l", "m", "n", "o", "p", "q",
+            "r", "s", "t", "u", "w", "x", "y"
+        ]
+    );
+}
+
+#[test]
+fn visit_declarations() {
+    assert_eq!(
+        references(
+            r#"
+            function f(a = b, ...c) { return a + c; }
+            const g = (h) => h + i;
+            var { j = k, l: [m = n] } = o;
+            "#
+        ),
+        ["b", "a", "c", "h", "i", "o", "k", "n"]
+    );
+}
+
+#[test]
+fn override_walk() {
+    /// Counts the function declarations, without looking into nested functions.
+    #[derive(Default)]
+    struct Functions(usize);
+
+    impl<'ast> Visitor<'ast> for Functions {
+        fn visit_function_decl(&mut self, function: &'ast FunctionDecl) {
+            self.0 += 1;
+            if function.name() == "outer" {
+                walk_function_decl(self, function);
+            }
+        }
+    }
+
+    let statement_list = Parser::new(
+        "function outer() { function inner() { function hidden() {} } } function other() {}"
+            .as_bytes(),
+        false,
+    )
+    .parse_all()
+    .expect("failed to parse");
+    let mut functions = Functions::default();
+    functions.visit_statement_list(&statement_list);
+    assert_eq!(functions.0, 3);
+}
+
+#[test]
+fn visit_mut() {
+    /// Replaces references to `x` by the number `1`.
+    struct Inline;
+
+    impl VisitorMut for Inline {
+        fn visit_node_mut(&mut self, node: &mut Node) {
+            match node {
+                Node::Identifier(identifier) if identifier.as_ref() == "x" => {
+                    *node = Const::Int(1).into();
+                }
+                _ => super::walk_node_mut(self, node),
+            }
+        }
+    }
+
+    let mut statement_list = Parser::new(
+        "let y = x + f(x, [x]);\nfunction g(a = x) { return x; }".as_bytes(),
+        false,
+    )
+    .parse_all()
+    .expect("failed to parse");
+    Inline.visit_statement_list_mut(&mut statement_list);
+
+    let expected = Parser::new(
+        "let y = 1 + f(1, [1]);\nfunction g(a = 1) { return 1; }".as_bytes(),
+        false,
+    )
+    .parse_all()
+    .expect("failed to parse");
+    assert_eq!(statement_list, expected);
+}
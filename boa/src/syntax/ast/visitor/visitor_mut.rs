@@ -0,0 +1,598 @@
+//! Traversal of the abstract syntax tree by mutable reference.
+
+use crate::syntax::ast::{
+    node::{
+        declaration::{BindingPatternTypeArray, BindingPatternTypeObject, DeclarationPattern},
+        template::TemplateElement,
+        ArrayDecl, ArrowFunctionDecl, Assign, AsyncFunctionDecl, AsyncFunctionExpr, AwaitExpr,
+        BinOp, Block, Break, Call, Case, Catch, ConditionalOp, Continue, Declaration,
+        DeclarationList, DoWhileLoop, ForInLoop, ForLoop, ForOfLoop, FormalParameter, FunctionDecl,
+        FunctionExpr, GeneratorDecl, GeneratorExpr, GetConstField, GetField, Identifier, If, New,
+        Node, Object, PropertyDefinition, PropertyName, Return, Spread, StatementList, Switch,
+        TaggedTemplate, TemplateLit, Throw, Try, UnaryOp, WhileLoop, Yield,
+    },
+    Const,
+};
+
+/// Visits the nodes of the abstract syntax tree by mutable reference.
+///
+/// See the [module level documentation](super) for more information.
+pub trait VisitorMut: Sized {
+    fn visit_statement_list_mut(&mut self, list: &mut StatementList) {
+        walk_statement_list_mut(self, list)
+    }
+
+    fn visit_node_mut(&mut self, node: &mut Node) {
+        walk_node_mut(self, node)
+    }
+
+    fn visit_array_decl_mut(&mut self, array: &mut ArrayDecl) {
+        walk_array_decl_mut(self, array)
+    }
+
+    fn visit_arrow_function_decl_mut(&mut self, function: &mut ArrowFunctionDecl) {
+        walk_arrow_function_decl_mut(self, function)
+    }
+
+    fn visit_assign_mut(&mut self, assign: &mut Assign) {
+        walk_assign_mut(self, assign)
+    }
+
+    fn visit_async_function_decl_mut(&mut self, function: &mut AsyncFunctionDecl) {
+        walk_async_function_decl_mut(self, function)
+    }
+
+    fn visit_async_function_expr_mut(&mut self, function: &mut AsyncFunctionExpr) {
+        walk_async_function_expr_mut(self, function)
+    }
+
+    fn visit_await_expr_mut(&mut self, expr: &mut AwaitExpr) {
+        walk_await_expr_mut(self, expr)
+    }
+
+    fn visit_bin_op_mut(&mut self, op: &mut BinOp) {
+        walk_bin_op_mut(self, op)
+    }
+
+    fn visit_block_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block)
+    }
+
+    fn visit_break_mut(&mut self, _break: &mut Break) {}
+
+    fn visit_call_mut(&mut self, call: &mut Call) {
+        walk_call_mut(self, call)
+    }
+
+    fn visit_conditional_op_mut(&mut self, op: &mut ConditionalOp) {
+        walk_conditional_op_mut(self, op)
+    }
+
+    fn visit_const_mut(&mut self, _const: &mut Const) {}
+
+    fn visit_continue_mut(&mut self, _continue: &mut Continue) {}
+
+    fn visit_declaration_list_mut(&mut self, list: &mut DeclarationList) {
+        walk_declaration_list_mut(self, list)
+    }
+
+    fn visit_declaration_mut(&mut self, declaration: &mut Declaration) {
+        walk_declaration_mut(self, declaration)
+    }
+
+    fn visit_declaration_pattern_mut(&mut self, pattern: &mut DeclarationPattern) {
+        walk_declaration_pattern_mut(self, pattern)
+    }
+
+    fn visit_do_while_loop_mut(&mut self, do_while: &mut DoWhileLoop) {
+        walk_do_while_loop_mut(self, do_while)
+    }
+
+    fn visit_formal_parameter_mut(&mut self, parameter: &mut FormalParameter) {
+        walk_formal_parameter_mut(self, parameter)
+    }
+
+    fn visit_function_decl_mut(&mut self, function: &mut FunctionDecl) {
+        walk_function_decl_mut(self, function)
+    }
+
+    fn visit_function_expr_mut(&mut self, function: &mut FunctionExpr) {
+        walk_function_expr_mut(self, function)
+    }
+
+    fn visit_generator_decl_mut(&mut self, generator: &mut GeneratorDecl) {
+        walk_generator_decl_mut(self, generator)
+    }
+
+    fn visit_generator_expr_mut(&mut self, generator: &mut GeneratorExpr) {
+        walk_generator_expr_mut(self, generator)
+    }
+
+    fn visit_get_const_field_mut(&mut self, get_const_field: &mut GetConstField) {
+        walk_get_const_field_mut(self, get_const_field)
+    }
+
+    fn visit_get_field_mut(&mut self, get_field: &mut GetField) {
+        walk_get_field_mut(self, get_field)
+    }
+
+    fn visit_for_loop_mut(&mut self, for_loop: &mut ForLoop) {
+        walk_for_loop_mut(self, for_loop)
+    }
+
+    fn visit_for_in_loop_mut(&mut self, for_in_loop: &mut ForInLoop) {
+        walk_for_in_loop_mut(self, for_in_loop)
+    }
+
+    fn visit_for_of_loop_mut(&mut self, for_of_loop: &mut ForOfLoop) {
+        walk_for_of_loop_mut(self, for_of_loop)
+    }
+
+    fn visit_identifier_mut(&mut self, _identifier: &mut Identifier) {}
+
+    fn visit_if_mut(&mut self, if_smt: &mut If) {
+        walk_if_mut(self, if_smt)
+    }
+
+    fn visit_new_mut(&mut self, new: &mut New) {
+        walk_new_mut(self, new)
+    }
+
+    fn visit_object_mut(&mut self, object: &mut Object) {
+        walk_object_mut(self, object)
+    }
+
+    fn visit_property_definition_mut(&mut self, property: &mut PropertyDefinition) {
+        walk_property_definition_mut(self, property)
+    }
+
+    fn visit_property_name_mut(&mut self, name: &mut PropertyName) {
+        walk_property_name_mut(self, name)
+    }
+
+    fn visit_return_mut(&mut self, return_smt: &mut Return) {
+        walk_return_mut(self, return_smt)
+    }
+
+    fn visit_switch_mut(&mut self, switch: &mut Switch) {
+        walk_switch_mut(self, switch)
+    }
+
+    fn visit_case_mut(&mut self, case: &mut Case) {
+        walk_case_mut(self, case)
+    }
+
+    fn visit_spread_mut(&mut self, spread: &mut Spread) {
+        walk_spread_mut(self, spread)
+    }
+
+    fn visit_tagged_template_mut(&mut self, template: &mut TaggedTemplate) {
+        walk_tagged_template_mut(self, template)
+    }
+
+    fn visit_template_lit_mut(&mut self, template: &mut TemplateLit) {
+        walk_template_lit_mut(self, template)
+    }
+
+    fn visit_throw_mut(&mut self, throw: &mut Throw) {
+        walk_throw_mut(self, throw)
+    }
+
+    fn visit_try_mut(&mut self, try_node: &mut Try) {
+        walk_try_mut(self, try_node)
+    }
+
+    fn visit_catch_mut(&mut self, catch: &mut Catch) {
+        walk_catch_mut(self, catch)
+    }
+
+    fn visit_unary_op_mut(&mut self, op: &mut UnaryOp) {
+        walk_unary_op_mut(self, op)
+    }
+
+    fn visit_while_loop_mut(&mut self, while_loop: &mut WhileLoop) {
+        walk_while_loop_mut(self, while_loop)
+    }
+
+    fn visit_yield_mut(&mut self, yield_expr: &mut Yield) {
+        walk_yield_mut(self, yield_expr)
+    }
+}
+
+pub fn walk_statement_list_mut<V: VisitorMut>(visitor: &mut V, list: &mut StatementList) {
+    for node in list.items_mut() {
+        visitor.visit_node_mut(node);
+    }
+}
+
+pub fn walk_node_mut<V: VisitorMut>(visitor: &mut V, node: &mut Node) {
+    match node {
+        Node::ArrayDecl(array) => visitor.visit_array_decl_mut(array),
+        Node::ArrowFunctionDecl(function) => visitor.visit_arrow_function_decl_mut(function),
+        Node::Assign(assign) => visitor.visit_assign_mut(assign),
+        Node::AsyncFunctionDecl(function) => visitor.visit_async_function_decl_mut(function),
+        Node::AsyncFunctionExpr(function) => visitor.visit_async_function_expr_mut(function),
+        Node::AwaitExpr(expr) => visitor.visit_await_expr_mut(expr),
+        Node::BinOp(op) => visitor.visit_bin_op_mut(op),
+        Node::Block(block) => visitor.visit_block_mut(block),
+        Node::Break(break_smt) => visitor.visit_break_mut(break_smt),
+        Node::Call(call) => visitor.visit_call_mut(call),
+        Node::ConditionalOp(op) => visitor.visit_conditional_op_mut(op),
+        Node::Const(constant) => visitor.visit_const_mut(constant),
+        Node::ConstDeclList(list) | Node::LetDeclList(list) | Node::VarDeclList(list) => {
+            visitor.visit_declaration_list_mut(list)
+        }
+        Node::Continue(continue_smt) => visitor.visit_continue_mut(continue_smt),
+        Node::DoWhileLoop(do_while) => visitor.visit_do_while_loop_mut(do_while),
+        Node::FunctionDecl(function) => visitor.visit_function_decl_mut(function),
+        Node::FunctionExpr(function) => visitor.visit_function_expr_mut(function),
+        Node::GetConstField(get_const_field) => visitor.visit_get_const_field_mut(get_const_field),
+        Node::GetField(get_field) => visitor.visit_get_field_mut(get_field),
+        Node::ForLoop(for_loop) => visitor.visit_for_loop_mut(for_loop),
+        Node::ForInLoop(for_in_loop) => visitor.visit_for_in_loop_mut(for_in_loop),
+        Node::ForOfLoop(for_of_loop) => visitor.visit_for_of_loop_mut(for_of_loop),
+        Node::If(if_smt) => visitor.visit_if_mut(if_smt),
+        Node::Identifier(identifier) => visitor.visit_identifier_mut(identifier),
+        Node::New(new) => visitor.visit_new_mut(new),
+        Node::Object(object) => visitor.visit_object_mut(object),
+        Node::Return(return_smt) => visitor.visit_return_mut(return_smt),
+        Node::Switch(switch) => visitor.visit_switch_mut(switch),
+        Node::Spread(spread) => visitor.visit_spread_mut(spread),
+        Node::TaggedTemplate(template) => visitor.visit_tagged_template_mut(template),
+        Node::TemplateLit(template) => visitor.visit_template_lit_mut(template),
+        Node::Throw(throw) => visitor.visit_throw_mut(throw),
+        Node::Try(try_node) => visitor.visit_try_mut(try_node),
+        Node::UnaryOp(op) => visitor.visit_unary_op_mut(op),
+        Node::WhileLoop(while_loop) => visitor.visit_while_loop_mut(while_loop),
+        Node::Yield(yield_expr) => visitor.visit_yield_mut(yield_expr),
+        Node::GeneratorDecl(generator) => visitor.visit_generator_decl_mut(generator),
+        Node::GeneratorExpr(generator) => visitor.visit_generator_expr_mut(generator),
+        Node::This | Node::Empty => {}
+    }
+}
+
+pub fn walk_array_decl_mut<V: VisitorMut>(visitor: &mut V, array: &mut ArrayDecl) {
+    for element in array.as_mut() {
+        visitor.visit_node_mut(element);
+    }
+}
+
+pub fn walk_arrow_function_decl_mut<V: VisitorMut>(
+    visitor: &mut V,
+    function: &mut ArrowFunctionDecl,
+) {
+    for parameter in function.params_mut() {
+        visitor.visit_formal_parameter_mut(parameter);
+    }
+    visitor.visit_statement_list_mut(function.body_mut());
+}
+
+pub fn walk_assign_mut<V: VisitorMut>(visitor: &mut V, assign: &mut Assign) {
+    visitor.visit_node_mut(assign.lhs_mut());
+    visitor.visit_node_mut(assign.rhs_mut());
+}
+
+pub fn walk_async_function_decl_mut<V: VisitorMut>(
+    visitor: &mut V,
+    function: &mut AsyncFunctionDecl,
+) {
+    for parameter in function.parameters_mut() {
+        visitor.visit_formal_parameter_mut(parameter);
+    }
+    for node in function.body_mut() {
+        visitor.visit_node_mut(node);
+    }
+}
+
+pub fn walk_async_function_expr_mut<V: VisitorMut>(
+    visitor: &mut V,
+    function: &mut AsyncFunctionExpr,
+) {
+    for parameter in function.parameters_mut() {
+        visitor.visit_formal_parameter_mut(parameter);
+    }
+    for node in function.body_mut() {
+        visitor.visit_node_mut(node);
+    }
+}
+
+pub fn walk_await_expr_mut<V: VisitorMut>(visitor: &mut V, expr: &mut AwaitExpr) {
+    visitor.visit_node_mut(expr.expr_mut());
+}
+
+pub fn walk_bin_op_mut<V: VisitorMut>(visitor: &mut V, op: &mut BinOp) {
+    visitor.visit_node_mut(op.lhs_mut());
+    visitor.visit_node_mut(op.rhs_mut());
+}
+
+pub fn walk_block_mut<V: VisitorMut>(visitor: &mut V, block: &mut Block) {
+    visitor.visit_statement_list_mut(block.statement_list_mut());
+}
+
+pub fn walk_call_mut<V: VisitorMut>(visitor: &mut V, call: &mut Call) {
+    visitor.visit_node_mut(call.expr_mut());
+    for argument in call.args_mut() {
+        visitor.visit_node_mut(argument);
+    }
+}
+
+pub fn walk_conditional_op_mut<V: VisitorMut>(visitor: &mut V, op: &mut ConditionalOp) {
+    visitor.visit_node_mut(op.cond_mut());
+    visitor.visit_node_mut(op.if_true_mut());
+    visitor.visit_node_mut(op.if_false_mut());
+}
+
+pub fn walk_declaration_list_mut<V: VisitorMut>(visitor: &mut V, list: &mut DeclarationList) {
+    for declaration in list.as_mut() {
+        visitor.visit_declaration_mut(declaration);
+    }
+}
+
+pub fn walk_declaration_mut<V: VisitorMut>(visitor: &mut V, declaration: &mut Declaration) {
+    match declaration {
+        Declaration::Identifier { init, .. } => {
+            if let Some(init) = init {
+                visitor.visit_node_mut(init);
+            }
+        }
+        Declaration::Pattern(pattern) => visitor.visit_declaration_pattern_mut(pattern),
+    }
+}
+
+pub fn walk_declaration_pattern_mut<V: VisitorMut>(
+    visitor: &mut V,
+    pattern: &mut DeclarationPattern,
+) {
+    match pattern {
+        DeclarationPattern::Object(object) => {
+            if let Some(init) = object.init_mut() {
+                visitor.visit_node_mut(init);
+            }
+            for binding in object.bindings_mut() {
+                match binding {
+                    BindingPatternTypeObject::SingleName { default_init, .. } => {
+                        if let Some(init) = default_init {
+                            visitor.visit_node_mut(init);
+                        }
+                    }
+                    BindingPatternTypeObject::BindingPattern {
+                        pattern,
+                        default_init,
+                        ..
+                    } => {
+                        visitor.visit_declaration_pattern_mut(pattern);
+                        if let Some(init) = default_init {
+                            visitor.visit_node_mut(init);
+                        }
+                    }
+                    BindingPatternTypeObject::Empty
+                    | BindingPatternTypeObject::RestProperty { .. } => {}
+                }
+            }
+        }
+        DeclarationPattern::Array(array) => {
+            if let Some(init) = array.init_mut() {
+                visitor.visit_node_mut(init);
+            }
+            for binding in array.bindings_mut() {
+                match binding {
+                    BindingPatternTypeArray::SingleName { default_init, .. } => {
+                        if let Some(init) = default_init {
+                            visitor.visit_node_mut(init);
+                        }
+                    }
+                    BindingPatternTypeArray::BindingPattern { pattern }
+                    | BindingPatternTypeArray::BindingPatternRest { pattern } => {
+                        visitor.visit_declaration_pattern_mut(pattern);
+                    }
+                    BindingPatternTypeArray::Empty
+                    | BindingPatternTypeArray::Elision
+                    | BindingPatternTypeArray::SingleNameRest { .. } => {}
+                }
+            }
+        }
+    }
+}
+
+pub fn walk_do_while_loop_mut<V: VisitorMut>(visitor: &mut V, do_while: &mut DoWhileLoop) {
+    visitor.visit_node_mut(do_while.body_mut());
+    visitor.visit_node_mut(do_while.cond_mut());
+}
+
+pub fn walk_formal_parameter_mut<V: VisitorMut>(visitor: &mut V, parameter: &mut FormalParameter) {
+    if let Some(init) = parameter.init_mut() {
+        visitor.visit_node_mut(init);
+    }
+}
+
+pub fn walk_function_decl_mut<V: VisitorMut>(visitor: &mut V, function: &mut FunctionDecl) {
+    for parameter in function.parameters_mut() {
+        visitor.visit_formal_parameter_mut(parameter);
+    }
+    visitor.visit_statement_list_mut(function.body_mut());
+}
+
+pub fn walk_function_expr_mut<V: VisitorMut>(visitor: &mut V, function: &mut FunctionExpr) {
+    for parameter in function.parameters_mut() {
+        visitor.visit_formal_parameter_mut(parameter);
+    }
+    visitor.visit_statement_list_mut(function.body_mut());
+}
+
+pub fn walk_generator_decl_mut<V: VisitorMut>(visitor: &mut V, generator: &mut GeneratorDecl) {
+    for parameter in generator.parameters_mut() {
+        visitor.visit_formal_parameter_mut(parameter);
+    }
+    for node in generator.body_mut() {
+        visitor.visit_node_mut(node);
+    }
+}
+
+pub fn walk_generator_expr_mut<V: VisitorMut>(visitor: &mut V, generator: &mut GeneratorExpr) {
+    for parameter in generator.parameters_mut() {
+        visitor.visit_formal_parameter_mut(parameter);
+    }
+    visitor.visit_statement_list_mut(generator.body_mut());
+}
+
+pub fn walk_get_const_field_mut<V: VisitorMut>(
+    visitor: &mut V,
+    get_const_field: &mut GetConstField,
+) {
+    visitor.visit_node_mut(get_const_field.obj_mut());
+}
+
+pub fn walk_get_field_mut<V: VisitorMut>(visitor: &mut V, get_field: &mut GetField) {
+    visitor.visit_node_mut(get_field.obj_mut());
+    visitor.visit_node_mut(get_field.field_mut());
+}
+
+pub fn walk_for_loop_mut<V: VisitorMut>(visitor: &mut V, for_loop: &mut ForLoop) {
+    if let Some(init) = for_loop.init_mut() {
+        visitor.visit_node_mut(init);
+    }
+    if let Some(condition) = for_loop.condition_mut() {
+        visitor.visit_node_mut(condition);
+    }
+    if let Some(final_expr) = for_loop.final_expr_mut() {
+        visitor.visit_node_mut(final_expr);
+    }
+    visitor.visit_node_mut(for_loop.body_mut());
+}
+
+pub fn walk_for_in_loop_mut<V: VisitorMut>(visitor: &mut V, for_in_loop: &mut ForInLoop) {
+    visitor.visit_node_mut(for_in_loop.variable_mut());
+    visitor.visit_node_mut(for_in_loop.expr_mut());
+    visitor.visit_node_mut(for_in_loop.body_mut());
+}
+
+pub fn walk_for_of_loop_mut<V: VisitorMut>(visitor: &mut V, for_of_loop: &mut ForOfLoop) {
+    visitor.visit_node_mut(for_of_loop.variable_mut());
+    visitor.visit_node_mut(for_of_loop.iterable_mut());
+    visitor.visit_node_mut(for_of_loop.body_mut());
+}
+
+pub fn walk_if_mut<V: VisitorMut>(visitor: &mut V, if_smt: &mut If) {
+    visitor.visit_node_mut(if_smt.cond_mut());
+    visitor.visit_node_mut(if_smt.body_mut());
+    if let Some(else_node) = if_smt.else_node_mut() {
+        visitor.visit_node_mut(else_node);
+    }
+}
+
+pub fn walk_new_mut<V: VisitorMut>(visitor: &mut V, new: &mut New) {
+    visitor.visit_node_mut(new.expr_mut());
+    for argument in new.args_mut() {
+        visitor.visit_node_mut(argument);
+    }
+}
+
+pub fn walk_object_mut<V: VisitorMut>(visitor: &mut V, object: &mut Object) {
+    for property in object.properties_mut() {
+        visitor.visit_property_definition_mut(property);
+    }
+}
+
+pub fn walk_property_definition_mut<V: VisitorMut>(
+    visitor: &mut V,
+    property: &mut PropertyDefinition,
+) {
+    match property {
+        PropertyDefinition::IdentifierReference(_) => {}
+        PropertyDefinition::Property(name, value) => {
+            visitor.visit_property_name_mut(name);
+            visitor.visit_node_mut(value);
+        }
+        PropertyDefinition::MethodDefinition(_, name, function) => {
+            visitor.visit_property_name_mut(name);
+            visitor.visit_function_expr_mut(function);
+        }
+        PropertyDefinition::SpreadObject(object) => visitor.visit_node_mut(object),
+    }
+}
+
+pub fn walk_property_name_mut<V: VisitorMut>(visitor: &mut V, name: &mut PropertyName) {
+    match name {
+        PropertyName::Literal(_) => {}
+        PropertyName::Computed(node) => visitor.visit_node_mut(node),
+    }
+}
+
+pub fn walk_return_mut<V: VisitorMut>(visitor: &mut V, return_smt: &mut Return) {
+    if let Some(expr) = return_smt.expr_mut() {
+        visitor.visit_node_mut(expr);
+    }
+}
+
+pub fn walk_switch_mut<V: VisitorMut>(visitor: &mut V, switch: &mut Switch) {
+    visitor.visit_node_mut(switch.val_mut());
+    for case in switch.cases_mut() {
+        visitor.visit_case_mut(case);
+    }
+    if let Some(default) = switch.default_mut() {
+        for node in default {
+            visitor.visit_node_mut(node);
+        }
+    }
+}
+
+pub fn walk_case_mut<V: VisitorMut>(visitor: &mut V, case: &mut Case) {
+    visitor.visit_node_mut(case.condition_mut());
+    visitor.visit_statement_list_mut(case.body_mut());
+}
+
+pub fn walk_spread_mut<V: VisitorMut>(visitor: &mut V, spread: &mut Spread) {
+    visitor.visit_node_mut(spread.val_mut());
+}
+
+pub fn walk_tagged_template_mut<V: VisitorMut>(visitor: &mut V, template: &mut TaggedTemplate) {
+    visitor.visit_node_mut(template.tag_mut());
+    for expr in template.exprs_mut() {
+        visitor.visit_node_mut(expr);
+    }
+}
+
+pub fn walk_template_lit_mut<V: VisitorMut>(visitor: &mut V, template: &mut TemplateLit) {
+    for element in template.elements_mut() {
+        match element {
+            TemplateElement::String(_) => {}
+            TemplateElement::Expr(expr) => visitor.visit_node_mut(expr),
+        }
+    }
+}
+
+pub fn walk_throw_mut<V: VisitorMut>(visitor: &mut V, throw: &mut Throw) {
+    visitor.visit_node_mut(throw.expr_mut());
+}
+
+pub fn walk_try_mut<V: VisitorMut>(visitor: &mut V, try_node: &mut Try) {
+    visitor.visit_block_mut(try_node.block_mut());
+    if let Some(catch) = try_node.catch_mut() {
+        visitor.visit_catch_mut(catch);
+    }
+    if let Some(finally) = try_node.finally_mut() {
+        visitor.visit_block_mut(finally);
+    }
+}
+
+pub fn walk_catch_mut<V: VisitorMut>(visitor: &mut V, catch: &mut Catch) {
+    if let Some(parameter) = catch.parameter_mut() {
+        visitor.visit_declaration_mut(parameter);
+    }
+    visitor.visit_block_mut(catch.block_mut());
+}
+
+pub fn walk_unary_op_mut<V: VisitorMut>(visitor: &mut V, op: &mut UnaryOp) {
+    visitor.visit_node_mut(op.target_mut());
+}
+
+pub fn walk_while_loop_mut<V: VisitorMut>(visitor: &mut V, while_loop: &mut WhileLoop) {
+    visitor.visit_node_mut(while_loop.cond_mut());
+    visitor.visit_node_mut(while_loop.body_mut());
+}
+
+pub fn walk_yield_mut<V: VisitorMut>(visitor: &mut V, yield_expr: &mut Yield) {
+    if let Some(expr) = yield_expr.expr_mut() {
+        visitor.visit_node_mut(expr);
+    }
+}
@@ -0,0 +1,1291 @@
+//! JavaScript code generation from the AST.
+//!
+//! The [`Generator`] turns a [`StatementList`] back into source code, either laid out for humans
+//! ([`Style::Pretty`]) or with all optional whitespace removed ([`Style::Minified`]).
+//!
+//! Parentheses are only emitted where the grammar requires them, following the same precedence
+//! and associativity rules as the [`Parser`](super::Parser). Because of this, parsing the
+//! generated code gives back the original AST for every statement list produced by the parser:
+//!
+//! ```
+//! use boa::syntax::{codegen::{generate, Style}, Parser};
+//!
+//! let source = "let a = (b + c) * d;";
+//! let ast = Parser::new(source.as_bytes(), false).parse_all().unwrap();
+//!
+//! let minified = generate(&ast, Style::Minified);
+//! assert_eq!(minified, "let a=(b+c)*d;");
+//! assert_eq!(Parser::new(minified.as_bytes(), false).parse_all().unwrap(), ast);
+//! ```
+
+use crate::{
+    builtins::Number,
+    interner::Sym,
+    string::CodePoint,
+    syntax::ast::{
+        node::{
+            declaration::{BindingPatternTypeArray, BindingPatternTypeObject, DeclarationPattern},
+            template::TemplateElement,
+            ArrowFunctionDecl, BinOp, Case, Declaration, DeclarationList, FormalParameter,
+            MethodDefinitionKind, Node, PropertyDefinition, PropertyName, StatementList,
+        },
+        op::{self, BitOp, CompOp, LogOp, NumOp, UnaryOp},
+        visitor::{walk_bin_op, Visitor},
+        Const, Keyword,
+    },
+    JsString,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The layout of the generated code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Style {
+    /// One statement per line, indented with four spaces, with spaces around operators.
+    Pretty,
+    /// No whitespace, except where it is needed to separate two tokens.
+    Minified,
+}
+
+/// Generates the source code of a statement list.
+#[inline]
+pub fn generate(statement_list: &StatementList, style: Style) -> String {
+    let mut generator = Generator::new(style);
+    generator.statement_list(statement_list);
+    generator.finish()
+}
+
+/// The binding power of an expression, from the loosest to the tightest.
+///
+/// An operand is wrapped in parentheses when its precedence is lower than the one required by the
+/// position where it appears.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Precedence {
+    Sequence,
+    Assignment,
+    Conditional,
+    LogicalOr,
+    LogicalAnd,
+    BitwiseOr,
+    BitwiseXor,
+    BitwiseAnd,
+    Equality,
+    Relational,
+    Shift,
+    Additive,
+    Multiplicative,
+    Exponentiation,
+    Unary,
+    Update,
+    LeftHandSide,
+    Member,
+    Primary,
+}
+
+impl Precedence {
+    /// Gets the precedence of the operand at the right of a left-associative operator.
+    fn next(self) -> Self {
+        match self {
+            Self::Sequence => Self::Assignment,
+            Self::Assignment => Self::Conditional,
+            Self::Conditional => Self::LogicalOr,
+            Self::LogicalOr => Self::LogicalAnd,
+            Self::LogicalAnd => Self::BitwiseOr,
+            Self::BitwiseOr => Self::BitwiseXor,
+            Self::BitwiseXor => Self::BitwiseAnd,
+            Self::BitwiseAnd => Self::Equality,
+            Self::Equality => Self::Relational,
+            Self::Relational => Self::Shift,
+            Self::Shift => Self::Additive,
+            Self::Additive => Self::Multiplicative,
+            Self::Multiplicative => Self::Exponentiation,
+            Self::Exponentiation => Self::Unary,
+            Self::Unary => Self::Update,
+            Self::Update => Self::LeftHandSide,
+            Self::LeftHandSide => Self::Member,
+            Self::Member | Self::Primary => Self::Primary,
+        }
+    }
+
+    /// Gets the precedence of a binary operator.
+    fn of_bin_op(op: op::BinOp) -> Self {
+        match op {
+            op::BinOp::Num(NumOp::Add) | op::BinOp::Num(NumOp::Sub) => Self::Additive,
+            op::BinOp::Num(NumOp::Mul)
+            | op::BinOp::Num(NumOp::Div)
+            | op::BinOp::Num(NumOp::Mod) => Self::Multiplicative,
+            op::BinOp::Num(NumOp::Exp) => Self::Exponentiation,
+            op::BinOp::Bit(BitOp::And) => Self::BitwiseAnd,
+            op::BinOp::Bit(BitOp::Or) => Self::BitwiseOr,
+            op::BinOp::Bit(BitOp::Xor) => Self::BitwiseXor,
+            op::BinOp::Bit(_) => Self::Shift,
+            op::BinOp::Comp(CompOp::Equal)
+            | op::BinOp::Comp(CompOp::NotEqual)
+            | op::BinOp::Comp(CompOp::StrictEqual)
+            | op::BinOp::Comp(CompOp::StrictNotEqual) => Self::Equality,
+            op::BinOp::Comp(_) => Self::Relational,
+            op::BinOp::Log(LogOp::And) => Self::LogicalAnd,
+            op::BinOp::Log(_) => Self::LogicalOr,
+            op::BinOp::Assign(_) => Self::Assignment,
+            op::BinOp::Comma => Self::Sequence,
+        }
+    }
+
+    /// Gets the precedence of an expression node.
+    fn of(node: &Node) -> Self {
+        match node {
+            Node::BinOp(op) => Self::of_bin_op(op.op()),
+            Node::Assign(_) | Node::ArrowFunctionDecl(_) | Node::Yield(_) => Self::Assignment,
+            Node::ConditionalOp(_) => Self::Conditional,
+            Node::UnaryOp(op) => match op.op() {
+                UnaryOp::IncrementPost
+                | UnaryOp::IncrementPre
+                | UnaryOp::DecrementPost
+                | UnaryOp::DecrementPre => Self::Update,
+                _ => Self::Unary,
+            },
+            Node::AwaitExpr(_) => Self::Unary,
+            Node::Const(Const::Int(num)) if *num < 0 => Self::Unary,
+            Node::Const(Const::Num(num)) if num.is_sign_negative() && !num.is_nan() => Self::Unary,
+            Node::Call(_) => Self::LeftHandSide,
+            Node::New(_) | Node::GetConstField(_) | Node::GetField(_) | Node::TaggedTemplate(_) => {
+                Self::Member
+            }
+            _ => Self::Primary,
+        }
+    }
+}
+
+/// A JavaScript code generator.
+///
+/// Most users will want to use the [`generate`] function instead, but a generator can be reused
+/// to concatenate several statement lists into the same output.
+#[derive(Debug)]
+pub struct Generator {
+    style: Style,
+    output: String,
+    indentation: usize,
+    /// Whether the last token written is a `yield` without an argument, which consumes the
+    /// following semicolon by itself.
+    bare_yield: bool,
+}
+
+impl Generator {
+    /// Creates a new generator with the given style.
+    #[inline]
+    pub fn new(style: Style) -> Self {
+        Self {
+            style,
+            output: String::new(),
+            indentation: 0,
+            bare_yield: false,
+        }
+    }
+
+    /// Consumes the generator, returning the generated code.
+    #[inline]
+    pub fn finish(self) -> String {
+        self.output
+    }
+
+    /// Generates the code of the statements of a statement list.
+    pub fn statement_list(&mut self, statement_list: &StatementList) {
+        for node in directive_first(statement_list) {
+            if !self.output.is_empty() {
+                self.newline();
+            }
+            self.statement(node);
+        }
+        if self.style == Style::Pretty && !self.output.is_empty() {
+            self.output.push('\n');
+        }
+    }
+
+    /// Generates the code of a single expression.
+    #[inline]
+    pub fn expression(&mut self, node: &Node) {
+        self.expr(node, Precedence::Sequence);
+    }
+
+    /// Writes a token, separating it from the previous one if they would otherwise merge.
+    fn token(&mut self, token: &str) {
+        if let (Some(last), Some(first)) = (self.output.chars().last(), token.chars().next()) {
+            let separate = (is_word_char(last) && is_word_char(first))
+                || (last == first && matches!(last, '+' | '-'))
+                || (last == '/' && matches!(first, '/' | '*'))
+                || (last == '<' && first == '!');
+            if separate {
+                self.output.push(' ');
+            }
+        }
+        self.output.push_str(token);
+        self.bare_yield = false;
+    }
+
+    /// Writes a space, in the pretty style only.
+    fn space(&mut self) {
+        if self.style == Style::Pretty {
+            self.output.push(' ');
+        }
+    }
+
+    /// Starts a new indented line, in the pretty style only.
+    fn newline(&mut self) {
+        if self.style == Style::Pretty {
+            self.output.push('\n');
+            for _ in 0..self.indentation {
+                self.output.push_str("    ");
+            }
+        }
+    }
+
+    /// Writes the semicolon terminating a statement.
+    fn semicolon(&mut self) {
+        // A `yield` without an argument consumes the semicolon following it, and the statement
+        // then needs a second one unless a new line starts right after.
+        if self.bare_yield && self.style == Style::Minified {
+            self.output.push(';');
+        }
+        self.token(";");
+    }
+
+    /// Writes a separator between list items.
+    fn comma(&mut self) {
+        self.token(",");
+        self.space();
+    }
+
+    /// Writes a binary operator.
+    fn operator(&mut self, operator: &str) {
+        self.space();
+        self.token(operator);
+        self.space();
+    }
+
+    /// Writes a statement.
+    fn statement(&mut self, node: &Node) {
+        match node {
+            Node::Block(block) => {
+                self.label(block.label());
+                self.block(block.statement_list().items());
+            }
+            Node::Empty => self.token(";"),
+            Node::VarDeclList(list) | Node::LetDeclList(list) | Node::ConstDeclList(list) => {
+                self.declaration_list(list, false);
+                self.semicolon();
+            }
+            Node::If(if_smt) => {
+                self.token("if");
+                self.space();
+                self.token("(");
+                self.expression(if_smt.cond());
+                self.token(")");
+                match if_smt.else_node() {
+                    Some(else_node) => {
+                        // A nested `if` without `else` would take our `else` for itself.
+                        if ends_with_open_if(if_smt.body()) {
+                            self.space();
+                            self.block(std::slice::from_ref(if_smt.body()));
+                        } else {
+                            self.sub_statement(if_smt.body());
+                        }
+                        if matches!(if_smt.body(), Node::Block(_)) {
+                            self.space();
+                        } else {
+                            self.newline();
+                        }
+                        self.token("else");
+                        if matches!(else_node, Node::If(_)) {
+                            self.space();
+                            self.statement(else_node);
+                        } else {
+                            self.sub_statement(else_node);
+                        }
+                    }
+                    None => self.sub_statement(if_smt.body()),
+                }
+            }
+            Node::WhileLoop(while_loop) => {
+                self.label(while_loop.label());
+                self.token("while");
+                self.space();
+                self.token("(");
+                self.expression(while_loop.cond());
+                self.token(")");
+                self.sub_statement(while_loop.body());
+            }
+            Node::DoWhileLoop(do_while) => {
+                self.label(do_while.label());
+                self.token("do");
+                self.sub_statement(do_while.body());
+                if matches!(do_while.body(), Node::Block(_)) {
+                    self.space();
+                } else {
+                    self.newline();
+                }
+                self.token("while");
+                self.space();
+                self.token("(");
+                self.expression(do_while.cond());
+                self.token(")");
+                self.token(";");
+            }
+            Node::ForLoop(for_loop) => {
+                self.label(for_loop.label());
+                self.token("for");
+                self.space();
+                self.token("(");
+                if let Some(init) = for_loop.init() {
+                    self.for_initializer(init);
+                }
+                self.token(";");
+                if let Some(condition) = for_loop.condition() {
+                    self.space();
+                    self.expression(condition);
+                }
+                self.token(";");
+                if let Some(final_expr) = for_loop.final_expr() {
+                    self.space();
+                    self.expression(final_expr);
+                }
+                self.token(")");
+                self.sub_statement(for_loop.body());
+            }
+            Node::ForInLoop(for_in_loop) => {
+                self.label(for_in_loop.label());
+                self.iterable_loop(
+                    for_in_loop.variable(),
+                    "in",
+                    for_in_loop.expr(),
+                    for_in_loop.body(),
+                );
+            }
+            Node::ForOfLoop(for_of_loop) => {
+                self.label(for_of_loop.label());
+                self.iterable_loop(
+                    for_of_loop.variable(),
+                    "of",
+                    for_of_loop.iterable(),
+                    for_of_loop.body(),
+                );
+            }
+            Node::Switch(switch) => {
+                self.token("switch");
+                self.space();
+                self.token("(");
+                self.expression(switch.val());
+                self.token(")");
+                self.space();
+                self.token("{");
+                self.indentation += 1;
+                for case in switch.cases() {
+                    self.case(case);
+                }
+                if let Some(default) = switch.default() {
+                    self.newline();
+                    self.token("default");
+                    self.token(":");
+                    self.case_body(default);
+                }
+                self.indentation -= 1;
+                self.newline();
+                self.token("}");
+            }
+            Node::Try(try_node) => {
+                self.token("try");
+                self.space();
+                self.block(try_node.block().statement_list().items());
+                if let Some(catch) = try_node.catch() {
+                    self.space();
+                    self.token("catch");
+                    if let Some(parameter) = catch.parameter() {
+                        self.space();
+                        self.token("(");
+                        self.declaration(parameter, false);
+                        self.token(")");
+                    }
+                    self.space();
+                    self.block(catch.block().statement_list().items());
+                }
+                if let Some(finally) = try_node.finally() {
+                    self.space();
+                    self.token("finally");
+                    self.space();
+                    self.block(finally.statement_list().items());
+                }
+            }
+            Node::Return(return_smt) => {
+                self.token("return");
+                if let Some(expr) = return_smt.expr() {
+                    self.space();
+                    self.expression(expr);
+                }
+                self.semicolon();
+            }
+            Node::Throw(throw) => {
+                self.token("throw");
+                self.space();
+                self.expression(throw.expr());
+                self.semicolon();
+            }
+            Node::Break(break_smt) => {
+                self.token("break");
+                if let Some(label) = break_smt.label() {
+                    self.space();
+                    self.token(&label);
+                }
+                self.semicolon();
+            }
+            Node::Continue(continue_smt) => {
+                self.token("continue");
+                if let Some(label) = continue_smt.label() {
+                    self.space();
+                    self.token(&label);
+                }
+                self.semicolon();
+            }
+            Node::FunctionDecl(function) => self.function(
+                "function",
+                Some(function.name()),
+                function.parameters(),
+                directive_first(function.body()),
+            ),
+            Node::GeneratorDecl(generator) => self.function(
+                "function*",
+                Some(generator.name()),
+                generator.parameters(),
+                generator.body(),
+            ),
+            Node::AsyncFunctionDecl(function) => self.function(
+                "async function",
+                Some(function.name()),
+                function.parameters(),
+                function.body(),
+            ),
+            _ => {
+                let start = self.output.len();
+                self.expression(node);
+                // Expression statements cannot start with tokens which would make them parse as
+                // a block or a declaration.
+                if starts_declaration(&self.output[start..]) {
+                    self.output.insert(start, '(');
+                    self.token(")");
+                }
+                self.semicolon();
+            }
+        }
+    }
+
+    /// Writes the body of a control flow statement.
+    fn sub_statement(&mut self, node: &Node) {
+        match node {
+            Node::Block(_) => {
+                self.space();
+                self.statement(node);
+            }
+            Node::Empty => self.statement(node),
+            _ => {
+                self.indentation += 1;
+                self.newline();
+                self.statement(node);
+                self.indentation -= 1;
+            }
+        }
+    }
+
+    /// Writes a block of statements.
+    fn block<'a>(&mut self, items: impl IntoIterator<Item = &'a Node>) {
+        self.token("{");
+        let mut items = items.into_iter().peekable();
+        if items.peek().is_some() {
+            self.indentation += 1;
+            for node in items {
+                self.newline();
+                self.statement(node);
+            }
+            self.indentation -= 1;
+            self.newline();
+        }
+        self.token("}");
+    }
+
+    /// Writes the label of a loop or a block, if any.
+    fn label(&mut self, label: Option<Sym>) {
+        if let Some(label) = label {
+            self.token(&label);
+            self.token(":");
+            self.space();
+        }
+    }
+
+    /// Writes a `case` clause of a `switch` statement.
+    fn case(&mut self, case: &Case) {
+        self.newline();
+        self.token("case");
+        self.space();
+        self.expression(case.condition());
+        self.token(":");
+        self.case_body(case.body().items());
+    }
+
+    /// Writes the statements of a `case` or `default` clause.
+    fn case_body(&mut self, items: &[Node]) {
+        self.indentation += 1;
+        for node in items {
+            self.newline();
+            self.statement(node);
+        }
+        self.indentation -= 1;
+    }
+
+    /// Writes the initializer of a `for` loop, in which `in` expressions must be parenthesized.
+    fn for_initializer(&mut self, init: &Node) {
+        match init {
+            Node::VarDeclList(list) | Node::LetDeclList(list) | Node::ConstDeclList(list) => {
+                self.declaration_list(list, true)
+            }
+            _ if contains_in(init) => {
+                self.token("(");
+                self.expression(init);
+                self.token(")");
+            }
+            _ => self.expression(init),
+        }
+    }
+
+    /// Writes a `for...in` or `for...of` loop.
+    fn iterable_loop(&mut self, variable: &Node, keyword: &str, expr: &Node, body: &Node) {
+        self.token("for");
+        self.space();
+        self.token("(");
+        match variable {
+            Node::VarDeclList(list) | Node::LetDeclList(list) | Node::ConstDeclList(list) => {
+                self.declaration_list(list, true)
+            }
+            _ => self.expr(variable, Precedence::LeftHandSide),
+        }
+        self.space();
+        self.token(keyword);
+        self.space();
+        if keyword == "of" {
+            self.expr(expr, Precedence::Assignment);
+        } else {
+            self.expression(expr);
+        }
+        self.token(")");
+        self.sub_statement(body);
+    }
+
+    /// Writes a `var`, `let` or `const` declaration list, without the semicolon.
+    fn declaration_list(&mut self, list: &DeclarationList, no_in: bool) {
+        self.token(match list {
+            DeclarationList::Var(_) => "var",
+            DeclarationList::Let(_) => "let",
+            DeclarationList::Const(_) => "const",
+        });
+        self.space();
+        for (i, declaration) in list.as_ref().iter().enumerate() {
+            if i > 0 {
+                self.comma();
+            }
+            self.declaration(declaration, no_in);
+        }
+    }
+
+    /// Writes a single declaration, with its initializer.
+    fn declaration(&mut self, declaration: &Declaration, no_in: bool) {
+        match declaration {
+            Declaration::Identifier { ident, init } => {
+                self.token(ident.as_ref());
+                if let Some(init) = init {
+                    self.initializer(init, no_in);
+                }
+            }
+            Declaration::Pattern(pattern) => self.declaration_pattern(pattern, no_in),
+        }
+    }
+
+    /// Writes ` = init`.
+    fn initializer(&mut self, init: &Node, no_in: bool) {
+        self.operator("=");
+        if no_in && contains_in(init) {
+            self.token("(");
+            self.expression(init);
+            self.token(")");
+        } else {
+            self.expr(init, Precedence::Assignment);
+        }
+    }
+
+    /// Writes a binding pattern, with its initializer.
+    fn declaration_pattern(&mut self, pattern: &DeclarationPattern, no_in: bool) {
+        match pattern {
+            DeclarationPattern::Object(object) => {
+                let bindings: Vec<_> = object
+                    .bindings()
+                    .iter()
+                    .filter(|binding| !matches!(binding, BindingPatternTypeObject::Empty))
+                    .collect();
+                self.token("{");
+                for (i, binding) in bindings.iter().copied().enumerate() {
+                    if i > 0 {
+                        self.comma();
+                    } else {
+                        self.space();
+                    }
+                    match binding {
+                        BindingPatternTypeObject::Empty => {}
+                        BindingPatternTypeObject::SingleName {
+                            ident,
+                            property_name,
+                            default_init,
+                        } => {
+                            if ident != property_name {
+                                self.property_key(property_name);
+                                self.token(":");
+                                self.space();
+                            }
+                            self.token(ident);
+                            if let Some(init) = default_init {
+                                self.initializer(init, no_in);
+                            }
+                        }
+                        BindingPatternTypeObject::RestProperty { ident, .. } => {
+                            self.token("...");
+                            self.token(ident);
+                        }
+                        BindingPatternTypeObject::BindingPattern {
+                            ident,
+                            pattern,
+                            default_init,
+                        } => {
+                            self.property_key(ident);
+                            self.token(":");
+                            self.space();
+                            self.declaration_pattern(pattern, no_in);
+                            if let Some(init) = default_init {
+                                self.initializer(init, no_in);
+                            }
+                        }
+                    }
+                }
+                if !bindings.is_empty() {
+                    self.space();
+                }
+                self.token("}");
+            }
+            DeclarationPattern::Array(array) => {
+                self.token("[");
+                let bindings = array.bindings();
+                for (i, binding) in bindings.iter().enumerate() {
+                    let last = i + 1 == bindings.len();
+                    match binding {
+                        BindingPatternTypeArray::Empty => continue,
+                        BindingPatternTypeArray::Elision => {
+                            self.token(",");
+                            if !last {
+                                self.space();
+                            }
+                            continue;
+                        }
+                        BindingPatternTypeArray::SingleName {
+                            ident,
+                            default_init,
+                        } => {
+                            self.token(ident);
+                            if let Some(init) = default_init {
+                                self.initializer(init, no_in);
+                            }
+                        }
+                        BindingPatternTypeArray::BindingPattern { pattern } => {
+                            self.declaration_pattern(pattern, no_in)
+                        }
+                        BindingPatternTypeArray::SingleNameRest { ident } => {
+                            self.token("...");
+                            self.token(ident);
+                        }
+                        BindingPatternTypeArray::BindingPatternRest { pattern } => {
+                            self.token("...");
+                            self.declaration_pattern(pattern, no_in);
+                        }
+                    }
+                    if !last {
+                        self.comma();
+                    }
+                }
+                self.token("]");
+            }
+        }
+        if let Some(init) = pattern.init() {
+            self.initializer(init, no_in);
+        }
+    }
+
+    /// Writes a function declaration or expression.
+    fn function<'a>(
+        &mut self,
+        keyword: &str,
+        name: Option<Sym>,
+        parameters: &[FormalParameter],
+        body: impl IntoIterator<Item = &'a Node>,
+    ) {
+        for keyword in keyword.split(' ') {
+            self.token(keyword);
+        }
+        if let Some(name) = name {
+            if keyword.ends_with('*') {
+                self.space();
+            }
+            self.token(&name);
+        }
+        self.parameters(parameters);
+        self.space();
+        self.block(body);
+    }
+
+    /// Writes a parenthesized list of formal parameters.
+    fn parameters(&mut self, parameters: &[FormalParameter]) {
+        self.token("(");
+        for (i, parameter) in parameters.iter().enumerate() {
+            if i > 0 {
+                self.comma();
+            }
+            if parameter.is_rest_param() {
+                self.token("...");
+            }
+            self.token(&parameter.name());
+            if let Some(init) = parameter.init() {
+                self.initializer(init, false);
+            }
+        }
+        self.token(")");
+    }
+
+    /// Writes an arrow function.
+    fn arrow_function(&mut self, function: &ArrowFunctionDecl) {
+        match function.params() {
+            [parameter] if parameter.init().is_none() && !parameter.is_rest_param() => {
+                self.token(&parameter.name())
+            }
+            parameters => self.parameters(parameters),
+        }
+        self.operator("=>");
+        match function.body().items() {
+            // A body made of a single return statement is written as a concise body.
+            [Node::Return(return_smt)] if return_smt.expr().is_some() => {
+                let expr = return_smt.expr().expect("checked above");
+                if matches!(expr, Node::Object(_)) {
+                    self.token("(");
+                    self.expression(expr);
+                    self.token(")");
+                } else {
+                    self.expr(expr, Precedence::Assignment);
+                }
+            }
+            _ => self.block(directive_first(function.body())),
+        }
+    }
+
+    /// Writes an expression, parenthesizing it if its precedence is lower than `precedence`.
+    fn expr(&mut self, node: &Node, precedence: Precedence) {
+        if Precedence::of(node) < precedence {
+            self.token("(");
+            self.expr(node, Precedence::Sequence);
+            self.token(")");
+            return;
+        }
+
+        match node {
+            Node::Const(constant) => self.constant(constant),
+            Node::Identifier(identifier) => self.token(identifier.as_ref()),
+            Node::This => self.token("this"),
+            Node::ArrayDecl(array) => {
+                self.token("[");
+                let elements = array.as_ref();
+                for (i, element) in elements.iter().enumerate() {
+                    let last = i + 1 == elements.len();
+                    // Holes are parsed as `undefined`.
+                    if let Node::Const(Const::Undefined) = element {
+                        self.token(",");
+                        if !last {
+                            self.space();
+                        }
+                        continue;
+                    }
+                    self.expr(element, Precedence::Assignment);
+                    if !last {
+                        self.comma();
+                    }
+                }
+                self.token("]");
+            }
+            Node::Object(object) => {
+                if object.properties().is_empty() {
+                    self.token("{");
+                    self.token("}");
+                    return;
+                }
+                self.token("{");
+                self.indentation += 1;
+                for (i, property) in object.properties().iter().enumerate() {
+                    if i > 0 {
+                        self.token(",");
+                    }
+                    self.newline();
+                    self.property(property);
+                }
+                self.indentation -= 1;
+                self.newline();
+                self.token("}");
+            }
+            Node::TemplateLit(template) => {
+                self.token("`");
+                for element in template.elements() {
+                    match element {
+                        TemplateElement::String(string) => {
+                            self.output.push_str(&escape_template(string))
+                        }
+                        TemplateElement::Expr(expr) => {
+                            self.output.push_str("${");
+                            self.expression(expr);
+                            self.output.push('}');
+                        }
+                    }
+                }
+                self.output.push('`');
+            }
+            Node::TaggedTemplate(template) => {
+                self.expr(template.tag(), Precedence::LeftHandSide);
+                self.token("`");
+                for (i, raw) in template.raws().iter().enumerate() {
+                    if i > 0 {
+                        self.output.push_str("${");
+                        if let Some(expr) = template.exprs().get(i - 1) {
+                            self.expression(expr);
+                        }
+                        self.output.push('}');
+                    }
+                    self.output.push_str(raw);
+                }
+                self.output.push('`');
+            }
+            Node::FunctionExpr(function) => self.function(
+                "function",
+                function.name(),
+                function.parameters(),
+                directive_first(function.body()),
+            ),
+            Node::GeneratorExpr(generator) => self.function(
+                "function*",
+                generator.name(),
+                generator.parameters(),
+                directive_first(generator.body()),
+            ),
+            Node::AsyncFunctionExpr(function) => self.function(
+                "async function",
+                function.name(),
+                function.parameters(),
+                function.body(),
+            ),
+            Node::ArrowFunctionDecl(function) => self.arrow_function(function),
+            Node::GetConstField(get_const_field) => {
+                self.member_object(get_const_field.obj());
+                if is_identifier_name(&get_const_field.field()) {
+                    self.token(".");
+                    self.token(&get_const_field.field());
+                } else {
+                    self.token("[");
+                    self.string(&JsString::new(get_const_field.field()));
+                    self.token("]");
+                }
+            }
+            Node::GetField(get_field) => {
+                self.member_object(get_field.obj());
+                self.token("[");
+                self.expression(get_field.field());
+                self.token("]");
+            }
+            Node::Call(call) => {
+                self.expr(call.expr(), Precedence::LeftHandSide);
+                self.arguments(call.args());
+            }
+            Node::New(new) => {
+                self.token("new");
+                self.space();
+                // The callee cannot contain a call, which would take the arguments of `new`.
+                if contains_call(new.expr()) {
+                    self.token("(");
+                    self.expression(new.expr());
+                    self.token(")");
+                } else {
+                    self.expr(new.expr(), Precedence::Member);
+                }
+                self.arguments(new.args());
+            }
+            Node::Spread(spread) => {
+                self.token("...");
+                self.expr(spread.val(), Precedence::Assignment);
+            }
+            Node::UnaryOp(unary_op) => {
+                let operator = match unary_op.op() {
+                    UnaryOp::IncrementPost | UnaryOp::IncrementPre => "++",
+                    UnaryOp::DecrementPost | UnaryOp::DecrementPre => "--",
+                    UnaryOp::Minus => "-",
+                    UnaryOp::Plus => "+",
+                    UnaryOp::Not => "!",
+                    UnaryOp::Tilde => "~",
+                    UnaryOp::TypeOf => "typeof",
+                    UnaryOp::Delete => "delete",
+                    UnaryOp::Void => "void",
+                };
+                match unary_op.op() {
+                    UnaryOp::IncrementPost | UnaryOp::DecrementPost => {
+                        self.expr(unary_op.target(), Precedence::LeftHandSide);
+                        self.token(operator);
+                    }
+                    UnaryOp::TypeOf | UnaryOp::Delete | UnaryOp::Void => {
+                        self.token(operator);
+                        self.space();
+                        self.expr(unary_op.target(), Precedence::Unary);
+                    }
+                    _ => {
+                        self.token(operator);
+                        self.expr(unary_op.target(), Precedence::Unary);
+                    }
+                }
+            }
+            Node::AwaitExpr(await_expr) => {
+                self.token("await");
+                self.space();
+                self.expr(await_expr.expr(), Precedence::Unary);
+            }
+            Node::BinOp(bin_op) => self.bin_op(bin_op.op(), bin_op.lhs(), bin_op.rhs()),
+            Node::Assign(assign) => {
+                self.expr(assign.lhs(), Precedence::LeftHandSide);
+                self.operator("=");
+                self.expr(assign.rhs(), Precedence::Assignment);
+            }
+            Node::ConditionalOp(conditional) => {
+                self.expr(conditional.cond(), Precedence::LogicalOr);
+                self.operator("?");
+                self.expr(conditional.if_true(), Precedence::Assignment);
+                self.operator(":");
+                self.expr(conditional.if_false(), Precedence::Assignment);
+            }
+            Node::Yield(yield_expr) => {
+                self.token(if yield_expr.delegate() {
+                    "yield*"
+                } else {
+                    "yield"
+                });
+                match yield_expr.expr() {
+                    Some(expr) => {
+                        self.space();
+                        self.expr(expr, Precedence::Assignment);
+                    }
+                    None => self.bare_yield = true,
+                }
+            }
+            // Statements cannot appear in expressions, but are still written out as is.
+            _ => self.statement(node),
+        }
+    }
+
+    /// Writes a binary operation.
+    fn bin_op(&mut self, op: op::BinOp, lhs: &Node, rhs: &Node) {
+        let precedence = Precedence::of_bin_op(op);
+        let (lhs_precedence, rhs_precedence) = match op {
+            op::BinOp::Comma => (Precedence::Sequence, Precedence::Assignment),
+            op::BinOp::Assign(_) => (Precedence::LeftHandSide, Precedence::Assignment),
+            op::BinOp::Num(NumOp::Exp) => (Precedence::Update, Precedence::Exponentiation),
+            // `||` is parsed as right-associative, and cannot be mixed with `??`.
+            op::BinOp::Log(LogOp::Or) => (
+                Precedence::LogicalAnd,
+                if is_coalesce(rhs) {
+                    Precedence::Primary
+                } else {
+                    Precedence::LogicalOr
+                },
+            ),
+            op::BinOp::Log(LogOp::Coalesce) => (
+                if is_coalesce(lhs) {
+                    Precedence::LogicalOr
+                } else {
+                    Precedence::BitwiseOr
+                },
+                Precedence::BitwiseOr,
+            ),
+            _ => (precedence, precedence.next()),
+        };
+
+        self.expr(lhs, lhs_precedence);
+        match op {
+            op::BinOp::Comma => self.comma(),
+            _ => self.operator(&op.to_string()),
+        }
+        self.expr(rhs, rhs_precedence);
+    }
+
+    /// Writes the object of a member expression.
+    fn member_object(&mut self, obj: &Node) {
+        // A dot right after an integer would be parsed as a decimal point.
+        if let Node::Const(Const::Int(_)) | Node::Const(Const::Num(_)) = obj {
+            self.token("(");
+            self.expression(obj);
+            self.token(")");
+        } else {
+            self.expr(obj, Precedence::LeftHandSide);
+        }
+    }
+
+    /// Writes a parenthesized list of arguments.
+    fn arguments(&mut self, args: &[Node]) {
+        self.token("(");
+        for (i, arg) in args.iter().enumerate() {
+            if i > 0 {
+                self.comma();
+            }
+            self.expr(arg, Precedence::Assignment);
+        }
+        self.token(")");
+    }
+
+    /// Writes a property of an object literal.
+    fn property(&mut self, property: &PropertyDefinition) {
+        match property {
+            PropertyDefinition::IdentifierReference(ident) => self.token(ident),
+            PropertyDefinition::Property(name, value) => {
+                if let (PropertyName::Literal(name), Node::Identifier(ident)) = (name, value) {
+                    if name.as_ref() == ident.as_ref()
+                        && is_identifier_name(name)
+                        && name.parse::<Keyword>().is_err()
+                    {
+                        self.token(name);
+                        return;
+                    }
+                }
+                self.property_name(name);
+                self.token(":");
+                self.space();
+                self.expr(value, Precedence::Assignment);
+            }
+            PropertyDefinition::MethodDefinition(kind, name, function) => {
+                match kind {
+                    MethodDefinitionKind::Get => self.token("get"),
+                    MethodDefinitionKind::Set => self.token("set"),
+                    MethodDefinitionKind::Generator => self.token("*"),
+                    MethodDefinitionKind::Ordinary => {}
+                }
+                if matches!(kind, MethodDefinitionKind::Get | MethodDefinitionKind::Set) {
+                    self.space();
+                }
+                self.property_name(name);
+                self.parameters(function.parameters());
+                self.space();
+                self.block(directive_first(function.body()));
+            }
+            PropertyDefinition::SpreadObject(obj) => {
+                self.token("...");
+                self.expr(obj, Precedence::Assignment);
+            }
+        }
+    }
+
+    /// Writes the name of a property in an object literal.
+    fn property_name(&mut self, name: &PropertyName) {
+        match name {
+            PropertyName::Literal(name) => self.property_key(name),
+            PropertyName::Computed(node) => {
+                self.token("[");
+                if contains_in(node) {
+                    self.token("(");
+                    self.expression(node);
+                    self.token(")");
+                } else {
+                    self.expr(node, Precedence::Assignment);
+                }
+                self.token("]");
+            }
+        }
+    }
+
+    /// Writes a literal property key, quoting it if it is neither a valid identifier name nor an
+    /// integer which reads back the same.
+    fn property_key(&mut self, key: &str) {
+        let is_integer = matches!(key.parse::<i32>(), Ok(n) if n >= 0 && n.to_string() == key);
+        if is_identifier_name(key) || is_integer {
+            self.token(key);
+        } else {
+            self.string(&JsString::new(key));
+        }
+    }
+
+    /// Writes a constant.
+    fn constant(&mut self, constant: &Const) {
+        match constant {
+            Const::String(string) => self.string(string),
+            Const::Int(num) => self.token(&num.to_string()),
+            Const::Num(num) => self.number(*num),
+            Const::BigInt(num) => self.token(&format!("{}n", num)),
+            Const::Bool(true) => self.token("true"),
+            Const::Bool(false) => self.token("false"),
+            Const::Null => self.token("null"),
+            Const::Undefined => self.token("undefined"),
+        }
+    }
+
+    /// Writes a number.
+    fn number(&mut self, num: f64) {
+        if num.is_sign_negative() && !num.is_nan() {
+            self.token("-");
+            self.number(-num);
+        } else if num.is_infinite() {
+            self.token("Infinity");
+        } else {
+            self.token(&Number::to_native_string(num));
+        }
+    }
+
+    /// Writes a double quoted string literal.
+    fn string(&mut self, string: &JsString) {
+        let mut literal = String::with_capacity(string.len() + 2);
+        literal.push('"');
+        for code_point in string.code_points() {
+            match code_point {
+                CodePoint::Unicode('"') => literal.push_str("\\\""),
+                CodePoint::Unicode('\\') => literal.push_str("\\\\"),
+                CodePoint::Unicode('\n') => literal.push_str("\\n"),
+                CodePoint::Unicode('\r') => literal.push_str("\\r"),
+                CodePoint::Unicode('\t') => literal.push_str("\\t"),
+                CodePoint::Unicode(c)
+                    if (c as u32) < 0x20 || matches!(c, '\u{2028}' | '\u{2029}') =>
+                {
+                    literal.push_str(&format!("\\u{:04X}", c as u32))
+                }
+                CodePoint::Unicode(c) => literal.push(c),
+                CodePoint::UnpairedSurrogate(unit) => literal.push_str(&format!("\\u{:04X}", unit)),
+            }
+        }
+        literal.push('"');
+        self.token(&literal);
+    }
+}
+
+/// Escapes the cooked value of a template element.
+pub(super) fn escape_template(string: &JsString) -> String {
+    let mut escaped = String::with_capacity(string.len());
+    let mut code_points = string.code_points().peekable();
+    while let Some(code_point) = code_points.next() {
+        match code_point {
+            CodePoint::Unicode('`') => escaped.push_str("\\`"),
+            CodePoint::Unicode('\\') => escaped.push_str("\\\\"),
+            // Carriage returns are normalized to line feeds in templates.
+            CodePoint::Unicode('\r') => escaped.push_str("\\r"),
+            CodePoint::Unicode('$')
+                if matches!(code_points.peek(), Some(CodePoint::Unicode('{'))) =>
+            {
+                escaped.push_str("\\$")
+            }
+            CodePoint::Unicode(c) => escaped.push(c),
+            CodePoint::UnpairedSurrogate(unit) => escaped.push_str(&format!("\\u{:04X}", unit)),
+        }
+    }
+    escaped
+}
+
+/// Checks if a character can be part of an identifier, keyword or number.
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '$' | '\\') || !c.is_ascii()
+}
+
+/// Checks if a string can be written as is as a property name.
+pub(super) fn is_identifier_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Checks if the code of an expression statement starts like a block or a declaration.
+fn starts_declaration(code: &str) -> bool {
+    if code.starts_with('{') {
+        return true;
+    }
+    ["function", "async", "class", "let"].iter().any(|keyword| {
+        matches!(code.strip_prefix(keyword), Some(rest) if !rest.starts_with(is_word_char))
+    })
+}
+
+/// Checks if a node is a `??` operation.
+fn is_coalesce(node: &Node) -> bool {
+    matches!(node, Node::BinOp(op) if op.op() == op::BinOp::Log(LogOp::Coalesce))
+}
+
+/// Checks if a member expression contains a call, which cannot appear in the callee of `new`
+/// without parentheses.
+fn contains_call(node: &Node) -> bool {
+    match node {
+        Node::Call(_) => true,
+        Node::GetConstField(get_const_field) => contains_call(get_const_field.obj()),
+        Node::GetField(get_field) => contains_call(get_field.obj()),
+        Node::TaggedTemplate(template) => contains_call(template.tag()),
+        _ => false,
+    }
+}
+
+/// Iterates over the statements of a script or function body, with the `"use strict"` directive
+/// first.
+///
+/// The parser hoists function declarations above the directive, which would no longer be
+/// recognized if written back in that order.
+fn directive_first(list: &StatementList) -> impl Iterator<Item = &Node> {
+    let directive = if list.strict() {
+        list.items()
+            .iter()
+            .position(|node| matches!(node, Node::Const(Const::String(s)) if s == "use strict"))
+    } else {
+        None
+    };
+    directive.map(|i| &list.items()[i]).into_iter().chain(
+        list.items()
+            .iter()
+            .enumerate()
+            .filter(move |(i, _)| Some(*i) != directive)
+            .map(|(_, node)| node),
+    )
+}
+
+/// Checks if the body of an `if` statement ends with an `if` statement without `else`.
+fn ends_with_open_if(node: &Node) -> bool {
+    match node {
+        Node::If(if_smt) => match if_smt.else_node() {
+            Some(else_node) => ends_with_open_if(else_node),
+            None => true,
+        },
+        Node::WhileLoop(while_loop) => ends_with_open_if(while_loop.body()),
+        Node::ForLoop(for_loop) => ends_with_open_if(for_loop.body()),
+        Node::ForInLoop(for_in_loop) => ends_with_open_if(for_in_loop.body()),
+        Node::ForOfLoop(for_of_loop) => ends_with_open_if(for_of_loop.body()),
+        _ => false,
+    }
+}
+
+/// Checks if an expression contains an `in` operator, which is not allowed unparenthesized in
+/// the initializer of a `for` loop.
+fn contains_in(node: &Node) -> bool {
+    struct ContainsIn(bool);
+
+    impl<'ast> Visitor<'ast> for ContainsIn {
+        fn visit_bin_op(&mut self, op: &'ast BinOp) {
+            if op.op() == op::BinOp::Comp(CompOp::In) {
+                self.0 = true;
+            } else {
+                walk_bin_op(self, op);
+            }
+        }
+    }
+
+    let mut visitor = ContainsIn(false);
+    visitor.visit_node(node);
+    visitor.0
+}
@@ -0,0 +1,184 @@
+//! Tests for the code generator.
+
+use super::{generate, Style};
+use crate::syntax::{ast::node::StatementList, Parser};
+
+/// Parses the given source code.
+fn parse(src: &str) -> StatementList {
+    Parser::new(src.as_bytes(), false)
+        .parse_all()
+        .unwrap_or_else(|e| panic!("failed to parse `{}`: {}", src, e))
+}
+
+/// Checks that the source code is minified to the expected output, and that the code generated in
+/// both styles parses back to the same tree.
+#[track_caller]
+fn check_minified(src: &str, expected: &str) {
+    let ast = parse(src);
+    let minified = generate(&ast, Style::Minified);
+    assert_eq!(minified, expected);
+    assert_eq!(parse(&minified), ast, "minified code: `{}`", minified);
+    let pretty = generate(&ast, Style::Pretty);
+    assert_eq!(parse(&pretty), ast, "pretty code: `{}`", pretty);
+}
+
+#[test]
+fn pretty_layout() {
+    let src = r#"
+        function f(a, b = 1, ...c) { if (a) { return b; } else return c; }
+        const o = { a: 1, get b() { return 2; } };
+        switch (o.a) { case 1: f(); break; default: }
+        "#;
+    let expected = r#"function f(a, b = 1, ...c) {
+    if (a) {
+        return b;
+    } else
+        return c;
+}
+const o = {
+    a: 1,
+    get b() {
+        return 2;
+    }
+};
+switch (o.a) {
+    case 1:
+        f();
+        break;
+    default:
+}
+"#;
+    let ast = parse(src);
+    let pretty = generate(&ast, Style::Pretty);
+    assert_eq!(pretty, expected);
+    assert_eq!(parse(&pretty), ast);
+}
+
+#[test]
+fn redundant_parentheses() {
+    check_minified("((a + b)) * (c);", "(a+b)*c;");
+    check_minified("a + (b * c);", "a+b*c;");
+    check_minified("a - (b - c);", "a-(b-c);");
+    check_minified("x = (a ? b : c) ? d : (e ? f : g);", "x=(a?b:c)?d:e?f:g;");
+}
+
+#[test]
+fn logical_operators() {
+    check_minified("x = a || b || c;", "x=a||b||c;");
+    check_minified("x = (a || b) || c;", "x=(a||b)||c;");
+    check_minified("x = a || b && c;", "x=a||b&&c;");
+    check_minified("x = (a ?? b) ?? c;", "x=a??b??c;");
+    check_minified("x = (a || b) ?? c;", "x=(a||b)??c;");
+}
+
+#[test]
+fn exponentiation() {
+    check_minified("2 ** 3 ** 4;", "2**3**4;");
+    check_minified("(2 ** 3) ** 4;", "(2**3)**4;");
+    check_minified("(-2) ** 2;", "(-2)**2;");
+    check_minified("a ** -b;", "a**-b;");
+}
+
+#[test]
+fn unary_operators() {
+    check_minified("- (-a); + (+a); - (--a);", "- -a;+ +a;- --a;");
+    check_minified("a++ + ++b;", "a++ + ++b;");
+    check_minified("typeof typeof a;", "typeof typeof a;");
+    check_minified("!(a instanceof B);", "!(a instanceof B);");
+    check_minified("delete a[b], void 0;", "delete a[b],void 0;");
+}
+
+#[test]
+fn member_expressions() {
+    check_minified("new (f())().g;", "new(f())().g;");
+    check_minified("new a.b.c(1)[2];", "new a.b.c(1)[2];");
+    check_minified("(1).toString();", "(1).toString();");
+    check_minified("(1.5).toFixed();", "(1.5).toFixed();");
+    check_minified("(a + b).c;", "(a+b).c;");
+}
+
+#[test]
+fn statement_start() {
+    check_minified("({ a: 1 });", "({a:1});");
+    check_minified("(function () {})();", "(function(){}());");
+    check_minified("(a, b) => ({});", "(a,b)=>({});");
+}
+
+#[test]
+fn dangling_else() {
+    check_minified(
+        "if (a) { if (b) c(); } else d();",
+        "if(a){if(b)c();}else d();",
+    );
+    check_minified("if (a) if (b) c(); else d();", "if(a)if(b)c();else d();");
+    check_minified(
+        "if (a) {} else if (b) {} else {}",
+        "if(a){}else if(b){}else{}",
+    );
+}
+
+#[test]
+fn literals() {
+    check_minified(
+        "a = \"quote\\\" \\\\ \\n \u{2028} é\";",
+        r#"a="quote\" \\ \n \u2028 é";"#,
+    );
+    check_minified("a = 'single';", r#"a="single";"#);
+    check_minified("a = 1e21 + 0.1 + 0x10 + 10n;", "a=1e+21+0.1+16+10n;");
+    check_minified("a = [1, , 2, ...b, ,];", "a=[1,,2,...b,,];");
+    check_minified("a = /re+/gi;", r#"a=new RegExp("re+","gi");"#);
+}
+
+#[test]
+fn templates() {
+    check_minified(
+        r"x = `a${b}c\`${`nested ${d}`}`;",
+        r"x=`a${b}c\`${`nested ${d}`}`;",
+    );
+    check_minified(r"tag`raw\n${x}`;", r"tag`raw\n${x}`;");
+}
+
+#[test]
+fn property_keys() {
+    check_minified(
+        r#"({ a, b: b, "c": 1, "x y": 2, 3: 4, "07": 5, [k]: 6 });"#,
+        r#"({a,b,c:1,"x y":2,3:4,"07":5,[k]:6});"#,
+    );
+}
+
+#[test]
+fn for_loop_initializer() {
+    check_minified(
+        "for (let i = 0, j = (a in b); i < 10; i++) ;",
+        "for(let i=0,j=(a in b);i<10;i++);",
+    );
+    check_minified("for ((a in b); ;) ;", "for((a in b);;);");
+    check_minified(
+        "for (const k in obj) continue;",
+        "for(const k in obj)continue;",
+    );
+    check_minified(
+        "outer: for (k of [1, 2]) break outer;",
+        "outer:for(k of[1,2])break outer;",
+    );
+}
+
+#[test]
+fn strict_directive() {
+    check_minified(
+        r#""use strict"; function f() {} f();"#,
+        r#""use strict";function f(){}f();"#,
+    );
+    check_minified(
+        r#"function g() { "use strict"; return 1; }"#,
+        r#"function g(){"use strict";return 1;}"#,
+    );
+}
+
+#[test]
+fn bare_yield() {
+    check_minified(
+        "function* g() {\n yield;\n yield* h;\n}",
+        "function*g(){yield;;yield*h;}",
+    );
+}
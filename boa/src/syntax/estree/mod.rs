@@ -0,0 +1,1425 @@
+//! Export of the AST to the [ESTree][spec] JSON format.
+//!
+//! ESTree is the de facto standard AST format of JavaScript tools, used among others by Acorn,
+//! Babel and ESLint. The exported tree follows the ES2022 version of the specification.
+//!
+//! The nodes written in the source code carry `start`, `end`, `range` and `loc` fields, taken from
+//! the side tables of their statement lists. The parts of the AST which have no span, such as
+//! binding identifiers, property keys or the block bodies of functions, are located by scanning
+//! the tokens of the source code around the located nodes. Offsets and columns are counted in
+//! UTF-16 code units, like Acorn does.
+//!
+//! The `default` clause of a `switch` statement is always exported as the last one, since the
+//! parser does not keep its place among the other clauses.
+//!
+//! [spec]: https://github.com/estree/estree
+
+use super::{
+    ast::{
+        node::{
+            declaration::{BindingPatternTypeArray, BindingPatternTypeObject, DeclarationPattern},
+            template::TemplateElement,
+            Declaration, DeclarationList, FormalParameter, MethodDefinitionKind, New, Node,
+            PropertyDefinition, PropertyName, StatementList,
+        },
+        op::{self, UnaryOp},
+        side_table::{Keyed, SideTable},
+        Const, Position, Span,
+    },
+    codegen::{escape_template, is_identifier_name, Generator, Style},
+};
+use crate::{interner::Sym, JsString};
+use serde_json::{json, Map, Value};
+use std::{cell::RefCell, rc::Rc};
+
+#[cfg(test)]
+mod tests;
+
+/// Converts a statement list parsed from `source` to an ESTree `Program` node.
+///
+/// The source code is needed to compute the `range` of the nodes.
+pub fn to_estree(statement_list: &StatementList, source: &str) -> Value {
+    let exporter = Exporter::new(source);
+    let program = json!({
+        "type": "Program",
+        "sourceType": "script",
+        "body": exporter.statement_list(statement_list),
+    });
+    let start = json!({ "line": 1, "column": 0 });
+    located(program, 0, exporter.end, start, exporter.end_position())
+}
+
+/// Maps the positions of the lexer to UTF-16 offsets, and the nodes to their positions.
+#[derive(Debug)]
+struct Exporter {
+    /// The characters of the source code.
+    chars: Vec<char>,
+    /// The index of the first character of each line.
+    line_starts: Vec<usize>,
+    /// The UTF-16 offset of each character, followed by the length of the source.
+    offsets: Vec<usize>,
+    /// The UTF-16 length of the source.
+    end: usize,
+    /// The side tables of the statement lists being converted, the innermost one last.
+    side_tables: RefCell<Vec<Rc<SideTable>>>,
+}
+
+impl Exporter {
+    /// Indexes the lines of the source code, which end where the lexer starts a new line.
+    fn new(source: &str) -> Self {
+        let mut line_starts = vec![0];
+        let mut offsets = Vec::with_capacity(source.len() + 1);
+        let mut end = 0;
+        let mut chars = source.chars().enumerate().peekable();
+        while let Some((i, c)) = chars.next() {
+            offsets.push(end);
+            end += c.len_utf16();
+            let line_break = match c {
+                '\r' => !matches!(chars.peek(), Some((_, '\n'))),
+                '\n' | '\u{2028}' | '\u{2029}' => true,
+                _ => false,
+            };
+            if line_break {
+                line_starts.push(i + 1);
+            }
+        }
+        offsets.push(end);
+        Self {
+            chars: source.chars().collect(),
+            line_starts,
+            offsets,
+            end,
+            side_tables: RefCell::default(),
+        }
+    }
+
+    /// Gets the index of the character at a position, and of the first character of its line.
+    fn index(&self, position: Position) -> (usize, usize) {
+        let last = self.offsets.len() - 1;
+        let line_start = self
+            .line_starts
+            .get(position.line_number() as usize - 1)
+            .copied()
+            .unwrap_or(last);
+        let index = (line_start + position.column_number() as usize - 1).min(last);
+        (index, line_start.min(last))
+    }
+
+    /// Gets the source code of a span.
+    fn source(&self, span: Span) -> String {
+        let (start, _) = self.index(span.start());
+        let (end, _) = self.index(span.end());
+        self.chars[start..end.max(start)].iter().collect()
+    }
+
+    /// Gets the span of a part of the AST of the innermost statement list being converted.
+    fn span<T: Keyed>(&self, part: &T) -> Option<Span> {
+        self.side_tables.borrow().last()?.span(part)
+    }
+
+    /// Gets the indices of the first character of a span, and of the character following it.
+    fn range(&self, span: Span) -> (usize, usize) {
+        (self.index(span.start()).0, self.index(span.end()).0)
+    }
+
+    /// Gets the range of characters of a part of the AST, if its span is known.
+    fn range_of<T: Keyed>(&self, part: &T) -> Option<(usize, usize)> {
+        self.span(part).map(|span| self.range(span))
+    }
+
+    /// Gets the range of characters of a converted node, if it has a location.
+    fn bounds(&self, node: &Value) -> Option<(usize, usize)> {
+        let index = |key| {
+            let offset = node.get(key)?.as_u64()? as usize;
+            self.offsets.binary_search(&offset).ok()
+        };
+        Some((index("start")?, index("end")?))
+    }
+
+    /// Adds the location fields of a part of the AST to its converted node, if its span is known.
+    fn located<T: Keyed>(&self, part: &T, node: Value) -> Value {
+        self.with_range(node, self.range_of(part))
+    }
+
+    /// Skips the whitespace and the comments following a character.
+    fn skip_trivia(&self, mut at: usize) -> usize {
+        let chars = &self.chars;
+        loop {
+            match (chars.get(at), chars.get(at + 1)) {
+                (Some(c), _) if c.is_whitespace() || *c == '\u{feff}' => at += 1,
+                (Some('/'), Some('/')) => {
+                    while at < chars.len()
+                        && !matches!(chars[at], '\r' | '\n' | '\u{2028}' | '\u{2029}')
+                    {
+                        at += 1;
+                    }
+                }
+                (Some('/'), Some('*')) => {
+                    at += 2;
+                    while at < chars.len() && !(chars[at] == '*' && chars.get(at + 1) == Some(&'/'))
+                    {
+                        at += 1;
+                    }
+                    at = (at + 2).min(chars.len());
+                }
+                _ => return at,
+            }
+        }
+    }
+
+    /// Gets the index of the character following the token which starts at a character.
+    ///
+    /// Only the tokens which can surround the nodes without span are told apart: names, numbers,
+    /// strings and a few punctuators.
+    fn token_end(&self, at: usize) -> usize {
+        let chars = &self.chars;
+        let is_word =
+            |c: char| c.is_alphanumeric() || matches!(c, '_' | '$' | '\u{200c}' | '\u{200d}');
+        match chars.get(at) {
+            None => at,
+            Some(&quote) if quote == '\'' || quote == '"' => {
+                let mut end = at + 1;
+                while end < chars.len() && chars[end] != quote {
+                    end += if chars[end] == '\\' { 2 } else { 1 };
+                }
+                (end + 1).min(chars.len())
+            }
+            Some(&c) if is_word(c) || c == '\\' => {
+                let number = c.is_ascii_digit();
+                let mut end = at;
+                while let Some(&c) = chars.get(end) {
+                    if c == '\\'
+                        && chars.get(end + 1) == Some(&'u')
+                        && chars.get(end + 2) == Some(&'{')
+                    {
+                        while end < chars.len() && chars[end] != '}' {
+                            end += 1;
+                        }
+                        end += 1;
+                    } else if is_word(c) || c == '\\' || (number && c == '.') {
+                        end += 1;
+                    } else {
+                        break;
+                    }
+                }
+                end.min(chars.len())
+            }
+            Some('.') if chars.get(at + 1) == Some(&'.') && chars.get(at + 2) == Some(&'.') => {
+                at + 3
+            }
+            Some('=') if chars.get(at + 1) == Some(&'>') => at + 2,
+            Some(_) => at + 1,
+        }
+    }
+
+    /// Gets the range of the first token following a character.
+    fn token(&self, at: usize) -> (usize, usize) {
+        let start = self.skip_trivia(at);
+        (start, self.token_end(start))
+    }
+
+    /// Checks if a range of characters is a given text.
+    fn is(&self, (start, end): (usize, usize), text: &str) -> bool {
+        self.chars[start..end].iter().copied().eq(text.chars())
+    }
+
+    /// Skips the first token following a character if it is a given text.
+    fn skip(&self, at: usize, text: &str) -> usize {
+        let token = self.token(at);
+        if self.is(token, text) {
+            token.1
+        } else {
+            at
+        }
+    }
+
+    /// Finds the first token which is a given text, between a character and the end of a range.
+    fn find(&self, mut at: usize, end: usize, text: &str) -> Option<(usize, usize)> {
+        loop {
+            let token = self.token(at);
+            if token.0 == token.1 || token.1 > end {
+                return None;
+            }
+            if self.is(token, text) {
+                return Some(token);
+            }
+            at = token.1;
+        }
+    }
+
+    /// Gets the range of the label which precedes a labelled statement, if it is followed by a
+    /// colon.
+    fn label_before(&self, statement: usize) -> Option<(usize, usize)> {
+        let chars = &self.chars;
+        let skip_whitespace = |mut at: usize| {
+            while at > 0 && chars[at - 1].is_whitespace() {
+                at -= 1;
+            }
+            at
+        };
+        let colon = skip_whitespace(statement).checked_sub(1)?;
+        if chars[colon] != ':' {
+            return None;
+        }
+        let end = skip_whitespace(colon);
+        let mut start = end;
+        while start > 0
+            && (chars[start - 1].is_alphanumeric() || matches!(chars[start - 1], '_' | '$'))
+        {
+            start -= 1;
+        }
+        Some((start, end)).filter(|(start, end)| start < end)
+    }
+
+    /// Converts the parts of a statement list, with the side table of the list.
+    fn in_list<R>(&self, statement_list: &StatementList, f: impl FnOnce(&Self) -> R) -> R {
+        self.side_tables
+            .borrow_mut()
+            .push(Rc::clone(statement_list.side_table()));
+        let result = f(self);
+        self.side_tables.borrow_mut().pop();
+        result
+    }
+
+    /// Gets the line and UTF-16 column at the end of the source.
+    fn end_position(&self) -> Value {
+        let line_start = self.line_starts[self.line_starts.len() - 1];
+        json!({
+            "line": self.line_starts.len(),
+            "column": self.end - self.offsets[line_start],
+        })
+    }
+
+    /// Adds the location fields to a node, if its range of characters is known.
+    fn with_range(&self, node: Value, range: Option<(usize, usize)>) -> Value {
+        let (start, end) = match range {
+            Some(range) => range,
+            None => return node,
+        };
+        let position = |index: usize| {
+            let line = self
+                .line_starts
+                .partition_point(|&line_start| line_start <= index);
+            json!({
+                "line": line,
+                "column": self.offsets[index] - self.offsets[self.line_starts[line - 1]],
+            })
+        };
+        located(
+            node,
+            self.offsets[start],
+            self.offsets[end],
+            position(start),
+            position(end),
+        )
+    }
+
+    /// Adds the location of a block statement whose opening brace is the first token following a
+    /// character.
+    ///
+    /// The closing brace follows the statement of the block which ends last, since the function
+    /// declarations are hoisted to the front of the list.
+    fn block_at(&self, block: Value, at: usize) -> Value {
+        let open = self.token(at);
+        if !self.is(open, "{") {
+            return block;
+        }
+        let last = block["body"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|statement| self.bounds(statement))
+            .map(|(_, end)| end)
+            .max()
+            .unwrap_or(open.1);
+        let close = self.token(last);
+        if !self.is(close, "}") {
+            return block;
+        }
+        self.with_range(block, Some((open.0, close.1)))
+    }
+
+    /// Converts the statements of a list, with their locations.
+    ///
+    /// The span of an expression statement includes its semicolon, unlike the one of its
+    /// expression.
+    fn statement_list(&self, statement_list: &StatementList) -> Vec<Value> {
+        self.in_list(statement_list, |exporter| {
+            statement_list
+                .items()
+                .iter()
+                .enumerate()
+                .map(|(i, node)| {
+                    let mut statement = exporter.statement(node);
+                    if statement_list.strict() && is_use_strict(node) {
+                        insert(&mut statement, "directive", json!("use strict"));
+                    }
+                    let range = statement_list.span(i).map(|span| exporter.range(span));
+                    exporter.with_range(statement, range)
+                })
+                .collect()
+        })
+    }
+
+    /// Converts a statement.
+    fn statement(&self, node: &Node) -> Value {
+        let range = self.range_of(node);
+        let statement = match node {
+            Node::Block(block) => self.block(block.statement_list()),
+            Node::Empty => json!({ "type": "EmptyStatement" }),
+            Node::VarDeclList(list) | Node::LetDeclList(list) | Node::ConstDeclList(list) => {
+                self.declaration_list(list)
+            }
+            Node::If(if_smt) => json!({
+                "type": "IfStatement",
+                "test": self.expression(if_smt.cond()),
+                "consequent": self.statement(if_smt.body()),
+                "alternate": if_smt.else_node().map(|node| self.statement(node)),
+            }),
+            Node::WhileLoop(while_loop) => json!({
+                "type": "WhileStatement",
+                "test": self.expression(while_loop.cond()),
+                "body": self.statement(while_loop.body()),
+            }),
+            Node::DoWhileLoop(do_while) => json!({
+                "type": "DoWhileStatement",
+                "body": self.statement(do_while.body()),
+                "test": self.expression(do_while.cond()),
+            }),
+            Node::ForLoop(for_loop) => json!({
+                "type": "ForStatement",
+                "init": for_loop.init().map(|init| self.for_target(init, range)),
+                "test": for_loop.condition().map(|test| self.expression(test)),
+                "update": for_loop.final_expr().map(|update| self.expression(update)),
+                "body": self.statement(for_loop.body()),
+            }),
+            Node::ForInLoop(for_in_loop) => json!({
+                "type": "ForInStatement",
+                "left": self.for_target(for_in_loop.variable(), range),
+                "right": self.expression(for_in_loop.expr()),
+                "body": self.statement(for_in_loop.body()),
+            }),
+            Node::ForOfLoop(for_of_loop) => json!({
+                "type": "ForOfStatement",
+                "await": false,
+                "left": self.for_target(for_of_loop.variable(), range),
+                "right": self.expression(for_of_loop.iterable()),
+                "body": self.statement(for_of_loop.body()),
+            }),
+            Node::Switch(switch) => {
+                let mut cases: Vec<_> = switch
+                    .cases()
+                    .iter()
+                    .map(|case| {
+                        self.located(
+                            case,
+                            json!({
+                                "type": "SwitchCase",
+                                "test": self.expression(case.condition()),
+                                "consequent": self.statement_list(case.body()),
+                            }),
+                        )
+                    })
+                    .collect();
+                let discriminant = self.expression(switch.val());
+                if let Some(default) = switch.default_statement_list() {
+                    let default = json!({
+                        "type": "SwitchCase",
+                        "test": null,
+                        "consequent": self.statement_list(default),
+                    });
+                    cases.push(self.default_case(default, &discriminant, &cases));
+                }
+                json!({
+                    "type": "SwitchStatement",
+                    "discriminant": discriminant,
+                    "cases": cases,
+                })
+            }
+            Node::Try(try_node) => {
+                let at = range.map(|(start, _)| self.skip(start, "try"));
+                let block = self.block_after(try_node.block().statement_list(), at);
+                let mut at = self.bounds(&block).map(|(_, end)| end);
+                let handler = try_node.catch().map(|catch| {
+                    let catch_keyword = at.map(|at| self.token(at));
+                    let param = catch.parameter().map(|parameter| {
+                        let binding = self.binding(parameter, self.range_of(parameter));
+                        self.located(parameter, binding)
+                    });
+                    let body_at = match self.bounds(param.as_ref().unwrap_or(&Value::Null)) {
+                        Some((_, end)) => Some(self.skip(end, ")")),
+                        None => catch_keyword.map(|(_, end)| self.skip(self.skip(end, "("), ")")),
+                    };
+                    let body = self.block_after(catch.block().statement_list(), body_at);
+                    let range = catch_keyword.zip(self.bounds(&body));
+                    at = range.map(|(_, (_, end))| end);
+                    self.with_range(
+                        json!({
+                            "type": "CatchClause",
+                            "param": param,
+                            "body": body,
+                        }),
+                        range.map(|((start, _), (_, end))| (start, end)),
+                    )
+                });
+                let finalizer = try_node.finally().map(|finally| {
+                    let at = at.map(|at| self.skip(at, "finally"));
+                    self.block_after(finally.statement_list(), at)
+                });
+                json!({
+                    "type": "TryStatement",
+                    "block": block,
+                    "handler": handler,
+                    "finalizer": finalizer,
+                })
+            }
+            Node::Return(return_smt) => json!({
+                "type": "ReturnStatement",
+                "argument": return_smt.expr().map(|expr| self.expression(expr)),
+            }),
+            Node::Throw(throw) => json!({
+                "type": "ThrowStatement",
+                "argument": self.expression(throw.expr()),
+            }),
+            Node::Break(break_smt) => json!({
+                "type": "BreakStatement",
+                "label": break_smt.label().map(|label| self.jump_label(&label, range, "break")),
+            }),
+            Node::Continue(continue_smt) => json!({
+                "type": "ContinueStatement",
+                "label": continue_smt
+                    .label()
+                    .map(|label| self.jump_label(&label, range, "continue")),
+            }),
+            Node::FunctionDecl(function) => self.function(
+                "FunctionDeclaration",
+                Some(function.name()),
+                function.parameters(),
+                self.statement_list(function.body()),
+                (false, false),
+                range,
+            ),
+            Node::GeneratorDecl(generator) => self.function(
+                "FunctionDeclaration",
+                Some(generator.name()),
+                generator.parameters(),
+                self.statement_list(generator.statement_list()),
+                (true, false),
+                range,
+            ),
+            Node::AsyncFunctionDecl(function) => self.function(
+                "FunctionDeclaration",
+                Some(function.name()),
+                function.parameters(),
+                self.statement_list(function.statement_list()),
+                (false, true),
+                range,
+            ),
+            _ => json!({
+                "type": "ExpressionStatement",
+                "expression": self.expression(node),
+            }),
+        };
+        self.labeled(node_label(node), self.with_range(statement, range))
+    }
+
+    /// Converts a block statement.
+    fn block(&self, statement_list: &StatementList) -> Value {
+        json!({
+            "type": "BlockStatement",
+            "body": self.statement_list(statement_list),
+        })
+    }
+
+    /// Converts a block statement which is not a node of the AST, and locates it if its opening
+    /// brace follows a known character.
+    fn block_after(&self, statement_list: &StatementList, at: Option<usize>) -> Value {
+        let block = self.block(statement_list);
+        match at {
+            Some(at) => self.block_at(block, at),
+            None => block,
+        }
+    }
+
+    /// Locates the `default` clause of a `switch` statement, which starts after the opening brace
+    /// or after one of the other clauses.
+    fn default_case(&self, default: Value, discriminant: &Value, cases: &[Value]) -> Value {
+        let open = self
+            .bounds(discriminant)
+            .map(|(_, end)| self.skip(self.skip(end, ")"), "{"));
+        let keyword = open
+            .into_iter()
+            .chain(cases.iter().filter_map(|case| Some(self.bounds(case)?.1)))
+            .map(|at| self.token(at))
+            .find(|&token| self.is(token, "default"));
+        let range = keyword.map(|(start, end)| {
+            let colon = self.skip(end, ":");
+            let end = default["consequent"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|statement| self.bounds(statement))
+                .map(|(_, end)| end)
+                .max()
+                .unwrap_or(colon);
+            (start, end)
+        });
+        self.with_range(default, range)
+    }
+
+    /// Converts the label of a `break` or `continue` statement, which follows its keyword.
+    fn jump_label(&self, label: &str, statement: Option<(usize, usize)>, keyword: &str) -> Value {
+        let range = statement.map(|(start, _)| self.token(self.skip(start, keyword)));
+        self.with_range(identifier(label), range)
+    }
+
+    /// Wraps a loop or a block in a `LabeledStatement` if it has a label, which precedes it in
+    /// the source code.
+    fn labeled(&self, label: Option<Sym>, body: Value) -> Value {
+        let label = match label {
+            Some(label) => label,
+            None => return body,
+        };
+        let body_range = self.bounds(&body);
+        let label_range = body_range.and_then(|(start, _)| self.label_before(start));
+        let range = label_range
+            .zip(body_range)
+            .map(|((start, _), (_, end))| (start, end));
+        let statement = json!({
+            "type": "LabeledStatement",
+            "label": self.with_range(identifier(&label), label_range),
+            "body": body,
+        });
+        self.with_range(statement, range)
+    }
+
+    /// Converts a `var`, `let` or `const` declaration list.
+    fn declaration_list(&self, list: &DeclarationList) -> Value {
+        let kind = match list {
+            DeclarationList::Var(_) => "var",
+            DeclarationList::Let(_) => "let",
+            DeclarationList::Const(_) => "const",
+        };
+        let declarations: Vec<_> = list
+            .as_ref()
+            .iter()
+            .map(|declaration| {
+                let init = match declaration {
+                    Declaration::Identifier { init, .. } => init.as_ref(),
+                    Declaration::Pattern(pattern) => pattern.init(),
+                };
+                let range = self.range_of(declaration);
+                self.with_range(
+                    json!({
+                        "type": "VariableDeclarator",
+                        "id": self.binding(declaration, range),
+                        "init": init.map(|init| self.expression(init)),
+                    }),
+                    range,
+                )
+            })
+            .collect();
+        json!({
+            "type": "VariableDeclaration",
+            "kind": kind,
+            "declarations": declarations,
+        })
+    }
+
+    /// Converts the initializer of a `for` loop, or the left side of a `for...in/of` loop.
+    ///
+    /// A declaration list in this place is not a statement, so its range goes from its keyword,
+    /// after the opening parenthesis of the loop, to the end of its last declarator.
+    fn for_target(&self, node: &Node, statement: Option<(usize, usize)>) -> Value {
+        match node {
+            Node::VarDeclList(list) | Node::LetDeclList(list) | Node::ConstDeclList(list) => {
+                let declaration = self.declaration_list(list);
+                let keyword = statement
+                    .and_then(|(start, end)| self.find(start, end, "for"))
+                    .map(|(_, end)| self.token(self.skip(end, "(")));
+                let last = declaration["declarations"]
+                    .as_array()
+                    .and_then(|declarations| self.bounds(declarations.last()?));
+                let range = keyword.zip(last).map(|((start, _), (_, end))| (start, end));
+                self.with_range(declaration, range)
+            }
+            _ => self.expression(node),
+        }
+    }
+
+    /// Converts the target of a declaration, without its initializer, and locates it from the
+    /// start of the declaration if its range is known.
+    fn binding(&self, declaration: &Declaration, range: Option<(usize, usize)>) -> Value {
+        let mut target = match declaration {
+            Declaration::Identifier { ident, .. } => identifier(ident.as_ref()),
+            Declaration::Pattern(pattern) => self.pattern(pattern),
+        };
+        if let Some((start, _)) = range {
+            self.locate_binding(&mut target, start);
+        }
+        target
+    }
+
+    /// Adds the locations to a converted binding target whose first token follows a character,
+    /// and returns the end of the target.
+    ///
+    /// The default values are expressions, which are already located.
+    fn locate_binding(&self, target: &mut Value, at: usize) -> Option<usize> {
+        let start = self.skip_trivia(at);
+        let kind = target.get("type")?.as_str()?.to_owned();
+        let end = match kind.as_str() {
+            "Identifier" => self.token_end(start),
+            "AssignmentPattern" => {
+                self.locate_binding(&mut target["left"], start)?;
+                self.bounds(&target["right"])?.1
+            }
+            "RestElement" => {
+                self.locate_binding(&mut target["argument"], self.skip(start, "..."))?
+            }
+            "Property" => {
+                let key = self.token(start);
+                let end = if target["shorthand"] == json!(true) {
+                    self.locate_binding(&mut target["value"], start)?
+                } else {
+                    self.locate_binding(&mut target["value"], self.skip(key.1, ":"))?
+                };
+                let key_node = target["key"].take();
+                target["key"] = self.with_range(key_node, Some(key));
+                end
+            }
+            "ObjectPattern" | "ArrayPattern" => {
+                let (elements, close) = if kind == "ObjectPattern" {
+                    ("properties", "}")
+                } else {
+                    ("elements", "]")
+                };
+                let mut at = start + 1;
+                for element in target[elements].as_array_mut()? {
+                    // An elision of an array pattern is only a comma.
+                    if !element.is_null() {
+                        at = self.locate_binding(element, at)?;
+                    }
+                    at = self.skip(at, ",");
+                }
+                let close_token = self.token(at);
+                if !self.is(close_token, close) {
+                    return None;
+                }
+                close_token.1
+            }
+            _ => return None,
+        };
+        *target = self.with_range(target.take(), Some((start, end)));
+        Some(end)
+    }
+
+    /// Converts a binding pattern, without its initializer.
+    fn pattern(&self, pattern: &DeclarationPattern) -> Value {
+        match pattern {
+            DeclarationPattern::Object(object) => {
+                let properties: Vec<_> = object
+                    .bindings()
+                    .iter()
+                    .filter_map(|binding| match binding {
+                        BindingPatternTypeObject::Empty => None,
+                        BindingPatternTypeObject::SingleName {
+                            ident,
+                            property_name,
+                            default_init,
+                        } => Some(pattern_property(
+                            property_name,
+                            self.with_default(identifier(ident), default_init.as_ref()),
+                            ident == property_name,
+                        )),
+                        BindingPatternTypeObject::RestProperty { ident, .. } => Some(json!({
+                            "type": "RestElement",
+                            "argument": identifier(ident),
+                        })),
+                        BindingPatternTypeObject::BindingPattern {
+                            ident,
+                            pattern,
+                            default_init,
+                        } => Some(pattern_property(
+                            ident,
+                            self.with_default(self.pattern(pattern), default_init.as_ref()),
+                            false,
+                        )),
+                    })
+                    .collect();
+                json!({
+                    "type": "ObjectPattern",
+                    "properties": properties,
+                })
+            }
+            DeclarationPattern::Array(array) => {
+                let elements: Vec<_> = array
+                    .bindings()
+                    .iter()
+                    .filter_map(|binding| match binding {
+                        BindingPatternTypeArray::Empty => None,
+                        BindingPatternTypeArray::Elision => Some(Value::Null),
+                        BindingPatternTypeArray::SingleName {
+                            ident,
+                            default_init,
+                        } => Some(self.with_default(identifier(ident), default_init.as_ref())),
+                        BindingPatternTypeArray::BindingPattern { pattern } => {
+                            Some(self.with_default(self.pattern(pattern), pattern.init()))
+                        }
+                        BindingPatternTypeArray::SingleNameRest { ident } => Some(json!({
+                            "type": "RestElement",
+                            "argument": identifier(ident),
+                        })),
+                        BindingPatternTypeArray::BindingPatternRest { pattern } => Some(json!({
+                            "type": "RestElement",
+                            "argument": self.pattern(pattern),
+                        })),
+                    })
+                    .collect();
+                json!({
+                    "type": "ArrayPattern",
+                    "elements": elements,
+                })
+            }
+        }
+    }
+
+    /// Wraps a binding target in an `AssignmentPattern` if it has a default value.
+    fn with_default(&self, target: Value, default: Option<&Node>) -> Value {
+        match default {
+            Some(default) => json!({
+                "type": "AssignmentPattern",
+                "left": target,
+                "right": self.expression(default),
+            }),
+            None => target,
+        }
+    }
+
+    /// Converts a function.
+    ///
+    /// The flags are whether the function is a generator and whether it is asynchronous. The name
+    /// and the block body are located from the range of the function, if it is known.
+    fn function(
+        &self,
+        kind: &str,
+        name: Option<Sym>,
+        parameters: &[FormalParameter],
+        body: Vec<Value>,
+        (generator, is_async): (bool, bool),
+        range: Option<(usize, usize)>,
+    ) -> Value {
+        let params = self.parameters(parameters);
+        let mut id = name.map(|name| identifier(&name));
+        let mut body = json!({
+            "type": "BlockStatement",
+            "body": body,
+        });
+        if let Some((start, end)) = range {
+            if let Some(name) = id.take() {
+                let at = ["async", "function", "*"]
+                    .iter()
+                    .fold(start, |at, keyword| self.skip(at, keyword));
+                id = Some(self.with_range(name, Some(self.token(at))));
+            }
+            let params_end = match params.last().and_then(|param| self.bounds(param)) {
+                Some((_, end)) => Some(self.skip(end, ",")),
+                None => self.find(start, end, "(").map(|(_, end)| end),
+            };
+            if let Some(params_end) = params_end {
+                body = self.block_at(body, self.skip(self.skip(params_end, ")"), "=>"));
+            }
+        }
+        json!({
+            "type": kind,
+            "id": id,
+            "params": params,
+            "body": body,
+            "generator": generator,
+            "async": is_async,
+            "expression": false,
+        })
+    }
+
+    /// Converts a list of formal parameters.
+    fn parameters(&self, parameters: &[FormalParameter]) -> Vec<Value> {
+        parameters
+            .iter()
+            .map(|parameter| {
+                let range = self.range_of(parameter);
+                let name_range = range.map(|(start, _)| self.token(self.skip(start, "...")));
+                let name = self.with_range(identifier(&parameter.name()), name_range);
+                let parameter_node = if parameter.is_rest_param() {
+                    json!({
+                        "type": "RestElement",
+                        "argument": name,
+                    })
+                } else {
+                    self.with_default(name, parameter.init())
+                };
+                self.located(parameter, parameter_node)
+            })
+            .collect()
+    }
+
+    /// Converts a list of expressions.
+    fn expressions(&self, nodes: &[Node]) -> Vec<Value> {
+        nodes.iter().map(|node| self.expression(node)).collect()
+    }
+
+    /// Converts an expression.
+    fn expression(&self, node: &Node) -> Value {
+        let range = self.range_of(node);
+        let expression = match node {
+            Node::Const(Const::Undefined) => identifier("undefined"),
+            Node::Const(constant) => literal(constant),
+            Node::Identifier(ident) => identifier(ident.as_ref()),
+            Node::This => json!({ "type": "ThisExpression" }),
+            Node::ArrayDecl(array) => {
+                let elements: Vec<_> = array
+                    .as_ref()
+                    .iter()
+                    .map(|element| match element {
+                        // Holes are parsed as `undefined`.
+                        Node::Const(Const::Undefined) => Value::Null,
+                        _ => self.expression(element),
+                    })
+                    .collect();
+                json!({
+                    "type": "ArrayExpression",
+                    "elements": elements,
+                })
+            }
+            Node::Object(object) => {
+                let properties: Vec<_> = object
+                    .properties()
+                    .iter()
+                    .map(|property| self.property(property))
+                    .collect();
+                json!({
+                    "type": "ObjectExpression",
+                    "properties": properties,
+                })
+            }
+            Node::TemplateLit(template) => {
+                let mut quasis = Vec::new();
+                let mut expressions = Vec::new();
+                let mut cooked = None;
+                for element in template.elements() {
+                    match element {
+                        TemplateElement::String(string) => cooked = Some(string),
+                        TemplateElement::Expr(expr) => {
+                            quasis.push(template_element(cooked.take(), None, false));
+                            expressions.push(self.expression(expr));
+                        }
+                    }
+                }
+                quasis.push(template_element(cooked, None, true));
+                let mut template = json!({
+                    "type": "TemplateLiteral",
+                    "quasis": quasis,
+                    "expressions": expressions,
+                });
+                if let Some((start, _)) = range {
+                    self.locate_quasis(&mut template, start);
+                }
+                template
+            }
+            Node::TaggedTemplate(template) => {
+                let raws = template.raws();
+                let quasis: Vec<_> = raws
+                    .iter()
+                    .zip(template.cookeds())
+                    .enumerate()
+                    .map(|(i, (raw, cooked))| {
+                        template_element(cooked.as_ref(), Some(raw), i + 1 == raws.len())
+                    })
+                    .collect();
+                let tag = self.expression(template.tag());
+                let mut quasi = json!({
+                    "type": "TemplateLiteral",
+                    "quasis": quasis,
+                    "expressions": self.expressions(template.exprs()),
+                });
+                if let Some(((_, tag_end), (_, end))) = self.bounds(&tag).zip(range) {
+                    let (start, _) = self.token(tag_end);
+                    self.locate_quasis(&mut quasi, start);
+                    quasi = self.with_range(quasi, Some((start, end)));
+                }
+                json!({
+                    "type": "TaggedTemplateExpression",
+                    "tag": tag,
+                    "quasi": quasi,
+                })
+            }
+            Node::FunctionExpr(function) => self.function(
+                "FunctionExpression",
+                function.name(),
+                function.parameters(),
+                self.statement_list(function.body()),
+                (false, false),
+                range,
+            ),
+            Node::GeneratorExpr(generator) => self.function(
+                "FunctionExpression",
+                generator.name(),
+                generator.parameters(),
+                self.statement_list(generator.body()),
+                (true, false),
+                range,
+            ),
+            Node::AsyncFunctionExpr(function) => self.function(
+                "FunctionExpression",
+                function.name(),
+                function.parameters(),
+                self.statement_list(function.statement_list()),
+                (false, true),
+                range,
+            ),
+            Node::ArrowFunctionDecl(function) => {
+                let body = function.body();
+                let expression_body = concise_body(body);
+                let mut arrow = self.function(
+                    "ArrowFunctionExpression",
+                    None,
+                    function.params(),
+                    match expression_body {
+                        Some(_) => Vec::new(),
+                        None => self.statement_list(body),
+                    },
+                    (false, false),
+                    range,
+                );
+                insert(&mut arrow, "id", Value::Null);
+                if let Some(expression) = expression_body {
+                    let expression = self.in_list(body, |exporter| exporter.expression(expression));
+                    insert(&mut arrow, "body", expression);
+                    insert(&mut arrow, "expression", json!(true));
+                }
+                arrow
+            }
+            Node::GetConstField(get_const_field) => {
+                let field = get_const_field.field();
+                let (property, computed) = if is_identifier_name(&field) {
+                    (identifier(&field), false)
+                } else {
+                    (literal(&Const::String(JsString::new(field))), true)
+                };
+                let object = self.expression(get_const_field.obj());
+                let property_range = self.bounds(&object).map(|(_, end)| {
+                    let accessor = if computed { "[" } else { "." };
+                    self.token(self.skip(end, accessor))
+                });
+                member(object, self.with_range(property, property_range), computed)
+            }
+            Node::GetField(get_field) => member(
+                self.expression(get_field.obj()),
+                self.expression(get_field.field()),
+                true,
+            ),
+            Node::Call(call) => json!({
+                "type": "CallExpression",
+                "callee": self.expression(call.expr()),
+                "arguments": self.expressions(call.args()),
+                "optional": false,
+            }),
+            Node::New(new) => match self.regex_literal(node, new) {
+                Some(literal) => literal,
+                None => json!({
+                    "type": "NewExpression",
+                    "callee": self.expression(new.expr()),
+                    "arguments": self.expressions(new.args()),
+                }),
+            },
+            Node::Spread(spread) => json!({
+                "type": "SpreadElement",
+                "argument": self.expression(spread.val()),
+            }),
+            Node::UnaryOp(unary_op) => {
+                let argument = self.expression(unary_op.target());
+                match unary_op.op() {
+                    UnaryOp::IncrementPost
+                    | UnaryOp::IncrementPre
+                    | UnaryOp::DecrementPost
+                    | UnaryOp::DecrementPre => json!({
+                        "type": "UpdateExpression",
+                        "operator": unary_op.op().to_string(),
+                        "prefix": matches!(
+                            unary_op.op(),
+                            UnaryOp::IncrementPre | UnaryOp::DecrementPre
+                        ),
+                        "argument": argument,
+                    }),
+                    op => json!({
+                        "type": "UnaryExpression",
+                        "operator": op.to_string(),
+                        "prefix": true,
+                        "argument": argument,
+                    }),
+                }
+            }
+            Node::AwaitExpr(await_expr) => json!({
+                "type": "AwaitExpression",
+                "argument": self.expression(await_expr.expr()),
+            }),
+            Node::BinOp(bin_op) => {
+                let left = self.expression(bin_op.lhs());
+                let right = self.expression(bin_op.rhs());
+                let kind = match bin_op.op() {
+                    op::BinOp::Comma => {
+                        // Sequences are parsed as left-nested comma operations.
+                        let mut expressions = match left {
+                            Value::Object(ref map)
+                                if map.get("type") == Some(&json!("SequenceExpression")) =>
+                            {
+                                map["expressions"].as_array().cloned().unwrap_or_default()
+                            }
+                            left => vec![left],
+                        };
+                        expressions.push(right);
+                        return self.located(
+                            node,
+                            json!({
+                                "type": "SequenceExpression",
+                                "expressions": expressions,
+                            }),
+                        );
+                    }
+                    op::BinOp::Assign(_) => "AssignmentExpression",
+                    op::BinOp::Log(_) => "LogicalExpression",
+                    _ => "BinaryExpression",
+                };
+                json!({
+                    "type": kind,
+                    "operator": bin_op.op().to_string(),
+                    "left": left,
+                    "right": right,
+                })
+            }
+            Node::Assign(assign) => json!({
+                "type": "AssignmentExpression",
+                "operator": "=",
+                "left": self.expression(assign.lhs()),
+                "right": self.expression(assign.rhs()),
+            }),
+            Node::ConditionalOp(conditional) => json!({
+                "type": "ConditionalExpression",
+                "test": self.expression(conditional.cond()),
+                "consequent": self.expression(conditional.if_true()),
+                "alternate": self.expression(conditional.if_false()),
+            }),
+            Node::Yield(yield_expr) => json!({
+                "type": "YieldExpression",
+                "argument": yield_expr.expr().map(|expr| self.expression(expr)),
+                "delegate": yield_expr.delegate(),
+            }),
+            // Statements cannot appear in expressions, but are still converted as is.
+            _ => return self.statement(node),
+        };
+        self.located(node, expression)
+    }
+
+    /// Adds the locations to the elements of a converted template literal whose opening backtick
+    /// is at a character.
+    ///
+    /// An element goes from the backtick or the brace closing a substitution to the next
+    /// substitution or the closing backtick, none of them included.
+    fn locate_quasis(&self, template: &mut Value, open: usize) {
+        let chars = &self.chars;
+        let substitution_ends: Vec<_> = template["expressions"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|expression| Some(self.skip(self.bounds(expression)?.1, "}")))
+            .collect();
+        let mut at = Some(open + 1);
+        let quasis = template["quasis"].as_array_mut().into_iter().flatten();
+        for (i, quasi) in quasis.enumerate() {
+            let start = match at {
+                Some(start) => start,
+                None => return,
+            };
+            let mut end = start;
+            while end < chars.len()
+                && chars[end] != '`'
+                && !(chars[end] == '$' && chars.get(end + 1) == Some(&'{'))
+            {
+                end += if chars[end] == '\\' { 2 } else { 1 };
+            }
+            *quasi = self.with_range(quasi.take(), Some((start, end.min(chars.len()))));
+            at = substitution_ends.get(i).copied().flatten();
+        }
+    }
+
+    /// Converts a `new RegExp(pattern, flags)` expression which was written as a regular
+    /// expression literal in the source code to a `Literal` node.
+    fn regex_literal(&self, node: &Node, new: &New) -> Option<Value> {
+        let raw = self.source(self.span(node)?);
+        if !raw.starts_with('/') {
+            return None;
+        }
+        match (new.expr(), new.args()) {
+            (
+                Node::Identifier(ident),
+                [Node::Const(Const::String(pattern)), Node::Const(Const::String(flags))],
+            ) if ident.as_ref() == "RegExp" => Some(json!({
+                "type": "Literal",
+                "value": null,
+                "raw": raw,
+                "regex": {
+                    "pattern": pattern.to_std_string_lossy(),
+                    "flags": flags.to_std_string_lossy(),
+                },
+            })),
+            _ => None,
+        }
+    }
+
+    /// Converts a property of an object literal.
+    fn property(&self, property: &PropertyDefinition) -> Value {
+        let range = self.range_of(property);
+        let start = range.map(|(start, _)| start);
+        let converted = match property {
+            PropertyDefinition::IdentifierReference(ident) => {
+                let ident_range = start.map(|start| self.token(start));
+                json!({
+                    "type": "Property",
+                    "key": self.with_range(identifier(ident), ident_range),
+                    "value": self.with_range(identifier(ident), ident_range),
+                    "kind": "init",
+                    "method": false,
+                    "shorthand": true,
+                    "computed": false,
+                })
+            }
+            PropertyDefinition::Property(name, value) => {
+                let shorthand = matches!(
+                    (name, value),
+                    (PropertyName::Literal(name), Node::Identifier(ident))
+                        if name.as_ref() == ident.as_ref()
+                );
+                let (key, computed) = self.property_name(name, start);
+                let mut value = self.expression(value);
+                if shorthand && self.bounds(&value).is_none() {
+                    // The value of a shorthand property is its key.
+                    value = self.with_range(value, self.bounds(&key));
+                }
+                json!({
+                    "type": "Property",
+                    "key": key,
+                    "value": value,
+                    "kind": "init",
+                    "method": false,
+                    "shorthand": shorthand,
+                    "computed": computed,
+                })
+            }
+            PropertyDefinition::MethodDefinition(kind, name, function) => {
+                let key_start = start.map(|start| match kind {
+                    MethodDefinitionKind::Get => self.skip(start, "get"),
+                    MethodDefinitionKind::Set => self.skip(start, "set"),
+                    MethodDefinitionKind::Generator => self.skip(start, "*"),
+                    MethodDefinitionKind::Ordinary => start,
+                });
+                let (key, computed) = self.property_name(name, key_start);
+                // The function of a method starts at its parameters.
+                let function_range =
+                    self.bounds(&key)
+                        .zip(range)
+                        .map(|((_, key_end), (_, end))| {
+                            let key_end = if computed {
+                                self.skip(key_end, "]")
+                            } else {
+                                key_end
+                            };
+                            (self.skip_trivia(key_end), end)
+                        });
+                let value = self.function(
+                    "FunctionExpression",
+                    None,
+                    function.parameters(),
+                    self.statement_list(function.body()),
+                    (matches!(kind, MethodDefinitionKind::Generator), false),
+                    function_range,
+                );
+                let value = self.with_range(value, function_range);
+                json!({
+                    "type": "Property",
+                    "key": key,
+                    "value": value,
+                    "kind": match kind {
+                        MethodDefinitionKind::Get => "get",
+                        MethodDefinitionKind::Set => "set",
+                        _ => "init",
+                    },
+                    "method": matches!(
+                        kind,
+                        MethodDefinitionKind::Ordinary | MethodDefinitionKind::Generator
+                    ),
+                    "shorthand": false,
+                    "computed": computed,
+                })
+            }
+            PropertyDefinition::SpreadObject(obj) => json!({
+                "type": "SpreadElement",
+                "argument": self.expression(obj),
+            }),
+        };
+        self.located(property, converted)
+    }
+
+    /// Converts the name of a property, and returns whether it is computed.
+    ///
+    /// A literal name is the first token following the start of the key, if it is known.
+    fn property_name(&self, name: &PropertyName, start: Option<usize>) -> (Value, bool) {
+        match name {
+            PropertyName::Literal(name) => {
+                let range = start.map(|start| self.token(start));
+                (self.with_range(property_key(name), range), false)
+            }
+            PropertyName::Computed(node) => (self.expression(node), true),
+        }
+    }
+}
+
+/// Adds the `start`, `end`, `range` and `loc` fields to a node.
+fn located(
+    mut node: Value,
+    start: usize,
+    end: usize,
+    start_position: Value,
+    end_position: Value,
+) -> Value {
+    insert(&mut node, "start", json!(start));
+    insert(&mut node, "end", json!(end));
+    insert(&mut node, "range", json!([start, end]));
+    insert(
+        &mut node,
+        "loc",
+        json!({ "start": start_position, "end": end_position }),
+    );
+    node
+}
+
+/// Sets a field of a node.
+fn insert(node: &mut Value, key: &str, value: Value) {
+    if let Value::Object(map) = node {
+        map.insert(key.to_owned(), value);
+    }
+}
+
+/// Gets the label of a loop or a block.
+fn node_label(node: &Node) -> Option<Sym> {
+    match node {
+        Node::Block(block) => block.label(),
+        Node::WhileLoop(while_loop) => while_loop.label(),
+        Node::DoWhileLoop(do_while) => do_while.label(),
+        Node::ForLoop(for_loop) => for_loop.label(),
+        Node::ForInLoop(for_in_loop) => for_in_loop.label(),
+        Node::ForOfLoop(for_of_loop) => for_of_loop.label(),
+        _ => None,
+    }
+}
+
+/// Gets the expression of the body of an arrow function, if it was written without braces.
+///
+/// Such a body is parsed as a single return statement, with the span of its expression.
+fn concise_body(body: &StatementList) -> Option<&Node> {
+    match body.items() {
+        [Node::Return(return_smt)] => {
+            let expression = return_smt.expr()?;
+            let span = body.span(0)?;
+            if body.span_of(expression) == Some(span) {
+                Some(expression)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Creates an `Identifier` node.
+fn identifier(name: &str) -> Value {
+    json!({
+        "type": "Identifier",
+        "name": name,
+    })
+}
+
+/// Creates a `MemberExpression` node.
+fn member(object: Value, property: Value, computed: bool) -> Value {
+    json!({
+        "type": "MemberExpression",
+        "object": object,
+        "property": property,
+        "computed": computed,
+        "optional": false,
+    })
+}
+
+/// Creates a `Literal` node, with the generated code of the constant as raw value.
+fn literal(constant: &Const) -> Value {
+    let mut generator = Generator::new(Style::Minified);
+    generator.expression(&Node::Const(constant.clone()));
+    let raw = generator.finish();
+    let mut literal = Map::new();
+    literal.insert("type".to_owned(), json!("Literal"));
+    let value = match constant {
+        Const::String(string) => json!(string.to_std_string_lossy()),
+        Const::Num(number) => json!(number),
+        Const::Int(number) => json!(number),
+        Const::BigInt(bigint) => {
+            literal.insert("bigint".to_owned(), json!(bigint.to_string()));
+            Value::Null
+        }
+        Const::Bool(boolean) => json!(boolean),
+        Const::Null | Const::Undefined => Value::Null,
+    };
+    literal.insert("value".to_owned(), value);
+    literal.insert("raw".to_owned(), json!(raw));
+    Value::Object(literal)
+}
+
+/// Creates a `TemplateElement` node.
+fn template_element(cooked: Option<&JsString>, raw: Option<&str>, tail: bool) -> Value {
+    let raw = match (raw, cooked) {
+        (Some(raw), _) => raw.to_owned(),
+        (None, Some(cooked)) => escape_template(cooked),
+        (None, None) => String::new(),
+    };
+    json!({
+        "type": "TemplateElement",
+        "value": {
+            "raw": raw,
+            "cooked": cooked.map(JsString::to_std_string_lossy),
+        },
+        "tail": tail,
+    })
+}
+
+/// Creates the key of a non computed property.
+fn property_key(name: &str) -> Value {
+    if is_identifier_name(name) {
+        identifier(name)
+    } else {
+        literal(&Const::String(JsString::new(name)))
+    }
+}
+
+/// Creates a `Property` node of an object pattern.
+fn pattern_property(key: &str, value: Value, shorthand: bool) -> Value {
+    json!({
+        "type": "Property",
+        "key": property_key(key),
+        "value": value,
+        "kind": "init",
+        "method": false,
+        "shorthand": shorthand,
+        "computed": false,
+    })
+}
+
+/// Checks if a statement is the `"use strict"` directive.
+fn is_use_strict(node: &Node) -> bool {
+    matches!(node, Node::Const(Const::String(string)) if string == "use strict")
+}
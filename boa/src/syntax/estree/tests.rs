@@ -0,0 +1,428 @@
+//! Tests for the ESTree export.
+
+use super::to_estree;
+use crate::syntax::Parser;
+use serde_json::{json, Value};
+
+/// Parses the source code and exports it to ESTree.
+fn export(src: &str) -> Value {
+    let ast = Parser::new(src.as_bytes(), false)
+        .parse_all()
+        .expect("failed to parse");
+    to_estree(&ast, src)
+}
+
+/// Exports the expression of the first statement of the source code, without locations.
+fn export_expression(src: &str) -> Value {
+    without_locations(export(src)["body"][0]["expression"].clone())
+}
+
+/// Removes the location fields of a node and of its children.
+fn without_locations(node: Value) -> Value {
+    match node {
+        Value::Object(map) => map
+            .into_iter()
+            .filter(|(key, _)| !matches!(key.as_str(), "start" | "end" | "range" | "loc"))
+            .map(|(key, value)| (key, without_locations(value)))
+            .collect(),
+        Value::Array(array) => array.into_iter().map(without_locations).collect(),
+        node => node,
+    }
+}
+
+#[test]
+fn program_location() {
+    let program = export("foo(a);\n\"😀\"; x;");
+    assert_eq!(program["range"], json!([0, 16]));
+    assert_eq!(
+        program["loc"],
+        json!({ "start": { "line": 1, "column": 0 }, "end": { "line": 2, "column": 8 } })
+    );
+
+    // Offsets and columns are counted in UTF-16 code units.
+    let statement = &program["body"][2];
+    assert_eq!(statement["start"], json!(14));
+    assert_eq!(statement["end"], json!(16));
+    assert_eq!(statement["range"], json!([14, 16]));
+    assert_eq!(
+        statement["loc"],
+        json!({ "start": { "line": 2, "column": 6 }, "end": { "line": 2, "column": 8 } })
+    );
+}
+
+#[test]
+fn nested_statement_location() {
+    let program = export("function f() {\r\n  return 1;\r\n}");
+    let statement = &program["body"][0]["body"]["body"][0];
+    assert_eq!(statement["type"], json!("ReturnStatement"));
+    assert_eq!(statement["loc"]["start"], json!({ "line": 2, "column": 2 }));
+    assert_eq!(statement["start"], json!(18));
+}
+
+#[test]
+fn node_location() {
+    let declaration = &export("let a = b === c;\nf(x => x);")["body"][0]["declarations"][0];
+    assert_eq!(declaration["range"], json!([4, 15]));
+    let init = &declaration["init"];
+    assert_eq!(init["type"], json!("BinaryExpression"));
+    assert_eq!(init["range"], json!([8, 15]));
+    assert_eq!(init["right"]["range"], json!([14, 15]));
+
+    let call = &export("let a = b === c;\nf(x => x);")["body"][1]["expression"];
+    assert_eq!(call["range"], json!([17, 26]));
+    let arrow = &call["arguments"][0];
+    assert_eq!(arrow["range"], json!([19, 25]));
+    assert_eq!(arrow["params"][0]["range"], json!([19, 20]));
+    assert_eq!(
+        arrow["body"]["loc"],
+        json!({ "start": { "line": 2, "column": 7 }, "end": { "line": 2, "column": 8 } })
+    );
+}
+
+#[test]
+fn directive() {
+    let program = export("\"use strict\"; a;");
+    assert_eq!(program["body"][0]["directive"], json!("use strict"));
+    assert_eq!(program["body"][1].get("directive"), None);
+}
+
+#[test]
+fn variable_declaration() {
+    let program = export("const { a, b: [c = 1, ...d] } = e;");
+    let declaration = &without_locations(program["body"][0].clone());
+    assert_eq!(declaration["type"], json!("VariableDeclaration"));
+    assert_eq!(declaration["kind"], json!("const"));
+    assert_eq!(
+        declaration["declarations"],
+        json!([{
+            "type": "VariableDeclarator",
+            "id": {
+                "type": "ObjectPattern",
+                "properties": [
+                    {
+                        "type": "Property",
+                        "key": { "type": "Identifier", "name": "a" },
+                        "value": { "type": "Identifier", "name": "a" },
+                        "kind": "init",
+                        "method": false,
+                        "shorthand": true,
+                        "computed": false,
+                    },
+                    {
+                        "type": "Property",
+                        "key": { "type": "Identifier", "name": "b" },
+                        "value": {
+                            "type": "ArrayPattern",
+                            "elements": [
+                                {
+                                    "type": "AssignmentPattern",
+                                    "left": { "type": "Identifier", "name": "c" },
+                                    "right": { "type": "Literal", "value": 1, "raw": "1" },
+                                },
+                                {
+                                    "type": "RestElement",
+                                    "argument": { "type": "Identifier", "name": "d" },
+                                },
+                            ],
+                        },
+                        "kind": "init",
+                        "method": false,
+                        "shorthand": false,
+                        "computed": false,
+                    },
+                ],
+            },
+            "init": { "type": "Identifier", "name": "e" },
+        }])
+    );
+}
+
+#[test]
+fn operators() {
+    assert_eq!(
+        export_expression("a || b + c;"),
+        json!({
+            "type": "LogicalExpression",
+            "operator": "||",
+            "left": { "type": "Identifier", "name": "a" },
+            "right": {
+                "type": "BinaryExpression",
+                "operator": "+",
+                "left": { "type": "Identifier", "name": "b" },
+                "right": { "type": "Identifier", "name": "c" },
+            },
+        })
+    );
+    assert_eq!(
+        export_expression("a += b++;"),
+        json!({
+            "type": "AssignmentExpression",
+            "operator": "+=",
+            "left": { "type": "Identifier", "name": "a" },
+            "right": {
+                "type": "UpdateExpression",
+                "operator": "++",
+                "prefix": false,
+                "argument": { "type": "Identifier", "name": "b" },
+            },
+        })
+    );
+    assert_eq!(
+        export_expression("a, b, c;"),
+        json!({
+            "type": "SequenceExpression",
+            "expressions": [
+                { "type": "Identifier", "name": "a" },
+                { "type": "Identifier", "name": "b" },
+                { "type": "Identifier", "name": "c" },
+            ],
+        })
+    );
+}
+
+#[test]
+fn member_and_call() {
+    assert_eq!(
+        export_expression("a.b[\"c d\"](...e);"),
+        json!({
+            "type": "CallExpression",
+            "callee": {
+                "type": "MemberExpression",
+                "object": {
+                    "type": "MemberExpression",
+                    "object": { "type": "Identifier", "name": "a" },
+                    "property": { "type": "Identifier", "name": "b" },
+                    "computed": false,
+                    "optional": false,
+                },
+                "property": { "type": "Literal", "value": "c d", "raw": "\"c d\"" },
+                "computed": true,
+                "optional": false,
+            },
+            "arguments": [{
+                "type": "SpreadElement",
+                "argument": { "type": "Identifier", "name": "e" },
+            }],
+            "optional": false,
+        })
+    );
+}
+
+#[test]
+fn literals() {
+    assert_eq!(
+        export_expression("[1.5, , 'a', 10n, null, true];")["elements"],
+        json!([
+            { "type": "Literal", "value": 1.5, "raw": "1.5" },
+            null,
+            { "type": "Literal", "value": "a", "raw": "\"a\"" },
+            { "type": "Literal", "value": null, "raw": "10n", "bigint": "10" },
+            { "type": "Literal", "value": null, "raw": "null" },
+            { "type": "Literal", "value": true, "raw": "true" },
+        ])
+    );
+    assert_eq!(
+        export_expression("`a${b}c`;"),
+        json!({
+            "type": "TemplateLiteral",
+            "quasis": [
+                {
+                    "type": "TemplateElement",
+                    "value": { "raw": "a", "cooked": "a" },
+                    "tail": false,
+                },
+                {
+                    "type": "TemplateElement",
+                    "value": { "raw": "c", "cooked": "c" },
+                    "tail": true,
+                },
+            ],
+            "expressions": [{ "type": "Identifier", "name": "b" }],
+        })
+    );
+}
+
+#[test]
+fn regex_literal() {
+    assert_eq!(
+        export_expression("/re+/gi;"),
+        json!({
+            "type": "Literal",
+            "value": null,
+            "raw": "/re+/gi",
+            "regex": { "pattern": "re+", "flags": "gi" },
+        })
+    );
+    assert_eq!(
+        export_expression("new RegExp('re+', 'gi');")["type"],
+        json!("NewExpression")
+    );
+}
+
+#[test]
+fn arrow_function_body() {
+    let arrow = export_expression("(a) => a + 1;");
+    assert_eq!(arrow["expression"], json!(true));
+    assert_eq!(arrow["body"]["type"], json!("BinaryExpression"));
+
+    let arrow = export_expression("(a) => { return a + 1; };");
+    assert_eq!(arrow["expression"], json!(false));
+    assert_eq!(arrow["body"]["type"], json!("BlockStatement"));
+    assert_eq!(arrow["body"]["body"][0]["type"], json!("ReturnStatement"));
+}
+
+#[test]
+fn object_methods() {
+    let object = export_expression("({ get a() {}, *b() {} });");
+    let properties = &object["properties"];
+    assert_eq!(properties[0]["kind"], json!("get"));
+    assert_eq!(properties[0]["method"], json!(false));
+    assert_eq!(properties[1]["kind"], json!("init"));
+    assert_eq!(properties[1]["method"], json!(true));
+    assert_eq!(properties[1]["value"]["generator"], json!(true));
+}
+
+#[test]
+fn labeled_loop() {
+    let statement = &export("outer: for (;;) break outer;")["body"][0];
+    assert_eq!(statement["type"], json!("LabeledStatement"));
+    assert_eq!(statement["range"], json!([0, 28]));
+    assert_eq!(
+        without_locations(statement["label"].clone()),
+        json!({ "type": "Identifier", "name": "outer" })
+    );
+    assert_eq!(statement["label"]["range"], json!([0, 5]));
+    assert_eq!(statement["body"]["type"], json!("ForStatement"));
+    assert_eq!(statement["body"]["test"], Value::Null);
+    assert_eq!(
+        without_locations(statement["body"]["body"].clone()),
+        json!({
+            "type": "BreakStatement",
+            "label": { "type": "Identifier", "name": "outer" },
+        })
+    );
+}
+
+/// Collects the paths of the nodes which have no location, or a location outside the one of
+/// their parent.
+fn unlocated(node: &Value, path: &str, parent: (u64, u64), paths: &mut Vec<String>) {
+    match node {
+        Value::Object(map) => {
+            let mut range = parent;
+            if map.contains_key("type") {
+                match map
+                    .get("range")
+                    .and_then(Value::as_array)
+                    .map(Vec::as_slice)
+                {
+                    Some([start, end]) => {
+                        range = (start.as_u64().unwrap(), end.as_u64().unwrap());
+                        if range.0 < parent.0 || range.1 > parent.1 || range.0 > range.1 {
+                            paths.push(format!("{} {:?} outside {:?}", path, range, parent));
+                        }
+                    }
+                    _ => paths.push(path.to_owned()),
+                }
+            }
+            for (key, value) in map {
+                if !matches!(key.as_str(), "loc" | "range") {
+                    unlocated(value, &format!("{}.{}", path, key), range, paths);
+                }
+            }
+        }
+        Value::Array(array) => {
+            for (i, value) in array.iter().enumerate() {
+                unlocated(value, &format!("{}[{}]", path, i), parent, paths);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[test]
+fn every_node_has_a_location() {
+    let src = r#""use strict";
+var v = 1, w;
+let l = 2;
+const { a, b: [c = 1, , ...d], e: f = 2, ...g } = h;
+function fn(p, q = 1, ...r) { return p; }
+function* gen() { yield 1; }
+async function af() { await x; }
+let fe = function named() {}, ge = function* () {}, ae = async function () {};
+let arrow = (x, y) => { return x; }, concise = z => z * 2, none = () => {};
+let obj = { k: 1, 'str': 2, 3: 3, [comp]: 4, short, get gt() { return 1; }, set st(v) {}, m() {}, *g2() {}, ...spread };
+let arr = [1, , 2, ...rest];
+let holes = [, a, , ];
+for (var i = 0, j; i < 1; i++) {}
+for (let k in obj) {}
+for (const [x1, { y1 }] of arr) {}
+for (;;) { break; }
+outer: while (true) { break outer; }
+lbl: { break lbl; }
+do { continue; } while (false);
+switch (v) { case 1: break; default: w = 2; case 2: }
+switch (v) { default: }
+try { t(); } catch (err) { e2(); } finally { f2(); }
+try {} catch {}
+if (a) b(); else { c(); }
+throw new Error(`x${y}z`);
+tag`a${b}c`;
+o.p.q; o[p]; new C(1); typeof a; -a; a ? b : c; a = b; a += 1; a++; a, b;
+/re/g;
+this;
+"#;
+    let program = export(src);
+    let mut paths = Vec::new();
+    unlocated(
+        &program,
+        "program",
+        (0, src.encode_utf16().count() as u64),
+        &mut paths,
+    );
+    assert!(paths.is_empty(), "{:#?}", paths);
+}
+/// Gets the source code of a located node.
+fn source_of<'a>(src: &'a str, node: &Value) -> &'a str {
+    let offset = |key: &str| node[key].as_u64().expect("node without location") as usize;
+    &src[offset("start")..offset("end")]
+}
+
+#[test]
+fn scanned_locations() {
+    let src = "let { a, b: [c = 1, , ...d] } = h;\n\
+               function f(p, ...r) { return `x${p}y`; }\n\
+               let o = { get g() {}, m() {} };\n\
+               switch (v) { case 1: default: w; }\n\
+               try { t(); } catch (e) {} finally {}\n";
+    let program = export(src);
+    let located = |node: &Value| source_of(src, node);
+
+    let function = &program["body"][0];
+    assert_eq!(located(&function["id"]), "f");
+    assert_eq!(located(&function["params"][1]["argument"]), "r");
+    assert_eq!(located(&function["body"]), "{ return `x${p}y`; }");
+    let template = &function["body"]["body"][0]["argument"];
+    assert_eq!(located(&template["quasis"][0]), "x");
+    assert_eq!(located(&template["quasis"][1]), "y");
+
+    let declaration = &program["body"][1];
+    assert_eq!(located(declaration), "let { a, b: [c = 1, , ...d] } = h;");
+    let pattern = &declaration["declarations"][0]["id"];
+    assert_eq!(located(pattern), "{ a, b: [c = 1, , ...d] }");
+    assert_eq!(located(&pattern["properties"][0]["value"]), "a");
+    assert_eq!(located(&pattern["properties"][1]["key"]), "b");
+    let elements = &pattern["properties"][1]["value"]["elements"];
+    assert_eq!(located(&elements[0]["left"]), "c");
+    assert_eq!(located(&elements[2]), "...d");
+
+    let method = &program["body"][2]["declarations"][0]["init"]["properties"][0];
+    assert_eq!(located(&method["key"]), "g");
+    assert_eq!(located(&method["value"]), "() {}");
+
+    assert_eq!(located(&program["body"][3]["cases"][1]), "default: w;");
+
+    let try_statement = &program["body"][4];
+    assert_eq!(located(&try_statement["block"]), "{ t(); }");
+    assert_eq!(located(&try_statement["handler"]), "catch (e) {}");
+    assert_eq!(located(&try_statement["finalizer"]), "{}");
+}
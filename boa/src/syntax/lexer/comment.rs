@@ -21,7 +21,24 @@ use std::io::Read;
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-comments
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Lexical_grammar
-pub(super) struct SingleLineComment;
+pub(super) struct SingleLineComment {
+    text: Option<String>,
+}
+
+impl SingleLineComment {
+    /// Creates a new single line comment lexer, which keeps the text of the comment if `preserve`
+    /// is `true`.
+    pub(super) fn new(preserve: bool) -> Self {
+        Self {
+            text: preserve.then(String::new),
+        }
+    }
+
+    /// Takes the text of the lexed comment, if it was preserved.
+    pub(super) fn take_text(&mut self) -> Option<String> {
+        self.text.take()
+    }
+}
 
 impl<R> Tokenizer<R> for SingleLineComment {
     fn lex(&mut self, cursor: &mut Cursor<R>, start_pos: Position) -> Result<Token, Error>
@@ -31,14 +48,21 @@ impl<R> Tokenizer<R> for SingleLineComment {
         let _timer = BoaProfiler::global().start_event("SingleLineComment", "Lexing");
 
         // Skip either to the end of the line or to the end of the input
+        let mut bytes = Vec::new();
         while let Some(ch) = cursor.peek()? {
             if ch == b'\n' || ch == b'\r' {
                 break;
             } else {
                 // Consume char.
-                cursor.next_byte()?.expect("Comment character vanished");
+                let byte = cursor.next_byte()?.expect("Comment character vanished");
+                if self.text.is_some() {
+                    bytes.push(byte);
+                }
             }
         }
+        if let Some(ref mut text) = self.text {
+            text.push_str(&String::from_utf8_lossy(&bytes));
+        }
         Ok(Token::new(
             TokenKind::Comment,
             Span::new(start_pos, cursor.pos()),
@@ -56,7 +80,24 @@ impl<R> Tokenizer<R> for SingleLineComment {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-comments
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Lexical_grammar
-pub(super) struct MultiLineComment;
+pub(super) struct MultiLineComment {
+    text: Option<String>,
+}
+
+impl MultiLineComment {
+    /// Creates a new multi-line comment lexer, which keeps the text of the comment if `preserve`
+    /// is `true`.
+    pub(super) fn new(preserve: bool) -> Self {
+        Self {
+            text: preserve.then(String::new),
+        }
+    }
+
+    /// Takes the text of the lexed comment, if it was preserved.
+    pub(super) fn take_text(&mut self) -> Option<String> {
+        self.text.take()
+    }
+}
 
 impl<R> Tokenizer<R> for MultiLineComment {
     fn lex(&mut self, cursor: &mut Cursor<R>, start_pos: Position) -> Result<Token, Error>
@@ -84,6 +125,9 @@ impl<R> Tokenizer<R> for MultiLineComment {
                 }
                 _ => {}
             };
+            if let Some(ref mut text) = self.text {
+                text.push(tried_ch.unwrap_or(char::REPLACEMENT_CHARACTER));
+            }
         }
 
         Err(Error::syntax(
@@ -100,7 +144,24 @@ impl<R> Tokenizer<R> for MultiLineComment {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-ecmascript-language-lexical-grammar
 
-pub(super) struct HashbangComment;
+pub(super) struct HashbangComment {
+    text: Option<String>,
+}
+
+impl HashbangComment {
+    /// Creates a new hashbang comment lexer, which keeps the text of the comment if `preserve` is
+    /// `true`.
+    pub(super) fn new(preserve: bool) -> Self {
+        Self {
+            text: preserve.then(String::new),
+        }
+    }
+
+    /// Takes the text of the lexed comment, if it was preserved.
+    pub(super) fn take_text(&mut self) -> Option<String> {
+        self.text.take()
+    }
+}
 
 impl<R> Tokenizer<R> for HashbangComment {
     fn lex(&mut self, cursor: &mut Cursor<R>, start_pos: Position) -> Result<Token, Error>
@@ -115,6 +176,13 @@ impl<R> Tokenizer<R> for HashbangComment {
                 Ok(c) if c == '\r' || c == '\n' || c == '\u{2028}' || c == '\u{2029}' => break,
                 _ => {}
             };
+            if let Some(ref mut text) = self.text {
+                text.push(tried_ch.unwrap_or(char::REPLACEMENT_CHARACTER));
+            }
+        }
+        // The `!` is part of the delimiter.
+        if let Some(ref mut text) = self.text {
+            text.remove(0);
         }
 
         Ok(Token::new(
@@ -3,8 +3,8 @@
 //! This module contains the Boa lexer or tokenizer implementation.
 //!
 //! The Lexer splits its input source code into a sequence of input elements called tokens,
-//! represented by the [Token] structure. It also removes
-//! whitespace and comments and attaches them to the next token.
+//! represented by the [Token] structure. It also removes whitespace and comments. Comments can
+//! optionally be preserved, in which case they are attached to the surrounding tokens.
 //!
 //! This is tightly coupled with the parser due to the javascript goal-symbol requirements
 //! as documented by the spec.
@@ -40,7 +40,7 @@ use self::{
     string::StringLiteral,
     template::TemplateLiteral,
 };
-use crate::syntax::ast::{Punctuator, Span};
+use crate::syntax::ast::{Comment, CommentKind, Comments, Punctuator, Span};
 pub use crate::{profiler::BoaProfiler, syntax::ast::Position};
 use core::convert::TryFrom;
 pub use error::Error;
@@ -59,6 +59,18 @@ trait Tokenizer<R> {
 pub struct Lexer<R> {
     cursor: Cursor<R>,
     goal_symbol: InputElement,
+    comments: Option<CommentState>,
+}
+
+/// The state of a lexer which preserves comments.
+#[derive(Debug, Default)]
+struct CommentState {
+    /// The comments lexed since the last token, which will lead the next one.
+    pending: Vec<Comment>,
+    /// A line terminator found in a trailing comment, to be returned as the next token.
+    line_terminator: Option<Span>,
+    /// The number of open braces in each of the template substitutions being lexed.
+    template_braces: Vec<usize>,
 }
 
 impl<R> Lexer<R> {
@@ -110,7 +122,140 @@ impl<R> Lexer<R> {
         Self {
             cursor: Cursor::new(reader),
             goal_symbol: Default::default(),
+            comments: None,
+        }
+    }
+
+    /// Sets whether the lexer preserves comments.
+    ///
+    /// When it does, the comments are attached to the tokens: a token gets as trailing comments
+    /// the ones starting on the same line after it, and as leading comments all the other ones
+    /// found since the previous token which is not a line terminator. Comments after the last
+    /// token can be taken with [`Lexer::take_pending_comments`].
+    #[inline]
+    pub fn set_preserve_comments(&mut self, preserve: bool) {
+        self.comments = if preserve {
+            Some(CommentState::default())
+        } else {
+            None
+        };
+    }
+
+    /// Checks if the lexer preserves comments.
+    #[inline]
+    pub fn preserves_comments(&self) -> bool {
+        self.comments.is_some()
+    }
+
+    /// Takes the comments lexed since the last token, which have not been attached to any token.
+    #[inline]
+    pub fn take_pending_comments(&mut self) -> Vec<Comment> {
+        self.comments
+            .as_mut()
+            .map(|comments| std::mem::take(&mut comments.pending))
+            .unwrap_or_default()
+    }
+
+    /// Records a lexed comment, if comments are preserved.
+    fn record_comment(&mut self, kind: CommentKind, text: Option<String>, span: Span) {
+        if let (Some(comments), Some(text)) = (self.comments.as_mut(), text) {
+            comments.pending.push(Comment::new(kind, text, span));
+        }
+    }
+
+    /// Attaches the pending comments to a token, as well as the comments following it on the same
+    /// line, if comments are preserved.
+    fn attach_comments(&mut self, token: Token) -> Result<Token, Error>
+    where
+        R: Read,
+    {
+        let state = match self.comments.as_mut() {
+            Some(state) => state,
+            None => return Ok(token),
+        };
+        if token.kind() == &TokenKind::LineTerminator {
+            return Ok(token);
+        }
+
+        // A `}` closing a template substitution is followed by the rest of the template, and the
+        // start of a regular expression might be lexed as a division first, so what follows them
+        // cannot be lexed yet.
+        let mut lex_trailing = !matches!(
+            token.kind(),
+            TokenKind::Punctuator(Punctuator::Div) | TokenKind::Punctuator(Punctuator::AssignDiv)
+        );
+        match token.kind() {
+            TokenKind::TemplateMiddle(_) => state.template_braces.push(0),
+            TokenKind::Punctuator(Punctuator::OpenBlock) => {
+                if let Some(braces) = state.template_braces.last_mut() {
+                    *braces += 1;
+                }
+            }
+            TokenKind::Punctuator(Punctuator::CloseBlock) => {
+                match state.template_braces.last_mut() {
+                    Some(0) => {
+                        state.template_braces.pop();
+                        lex_trailing = false;
+                    }
+                    Some(braces) => *braces -= 1,
+                    None => {}
+                }
+            }
+            _ => {}
+        }
+
+        let leading = std::mem::take(&mut state.pending);
+        let trailing = if lex_trailing {
+            self.lex_trailing_comments()?
+        } else {
+            Vec::new()
+        };
+        Ok(token.with_comments(Comments::new(leading, trailing)))
+    }
+
+    /// Lexes the comments following a token on the same line.
+    fn lex_trailing_comments(&mut self) -> Result<Vec<Comment>, Error>
+    where
+        R: Read,
+    {
+        let mut trailing = Vec::new();
+        loop {
+            while matches!(self.cursor.peek()?, Some(b' ') | Some(b'\t')) {
+                self.cursor.next_byte()?;
+            }
+            let start = self.cursor.pos();
+            match self.cursor.peek_n(2)? {
+                // `//`
+                0x2F2F => {
+                    self.cursor.next_byte()?;
+                    self.cursor.next_byte()?;
+                    let mut comment = SingleLineComment::new(true);
+                    let token = comment.lex(&mut self.cursor, start)?;
+                    if let Some(text) = comment.take_text() {
+                        trailing.push(Comment::new(CommentKind::SingleLine, text, token.span()));
+                    }
+                    break;
+                }
+                // `/*`
+                0x2A2F => {
+                    self.cursor.next_byte()?;
+                    self.cursor.next_byte()?;
+                    let mut comment = MultiLineComment::new(true);
+                    let token = comment.lex(&mut self.cursor, start)?;
+                    if let Some(text) = comment.take_text() {
+                        trailing.push(Comment::new(CommentKind::MultiLine, text, token.span()));
+                    }
+                    if token.kind() == &TokenKind::LineTerminator {
+                        if let Some(state) = self.comments.as_mut() {
+                            state.line_terminator = Some(token.span());
+                        }
+                        break;
+                    }
+                }
+                _ => break,
+            }
         }
+        Ok(trailing)
     }
 
     // Handles lexing of a token starting '/' with the '/' already being consumed.
@@ -130,11 +275,17 @@ impl<R> Lexer<R> {
             match c {
                 b'/' => {
                     self.cursor.next_byte()?.expect("/ token vanished"); // Consume the '/'
-                    SingleLineComment.lex(&mut self.cursor, start)
+                    let mut comment = SingleLineComment::new(self.preserves_comments());
+                    let token = comment.lex(&mut self.cursor, start)?;
+                    self.record_comment(CommentKind::SingleLine, comment.take_text(), token.span());
+                    Ok(token)
                 }
                 b'*' => {
                     self.cursor.next_byte()?.expect("* token vanished"); // Consume the '*'
-                    MultiLineComment.lex(&mut self.cursor, start)
+                    let mut comment = MultiLineComment::new(self.preserves_comments());
+                    let token = comment.lex(&mut self.cursor, start)?;
+                    self.record_comment(CommentKind::MultiLine, comment.take_text(), token.span());
+                    Ok(token)
                 }
                 ch => {
                     match self.get_goal() {
@@ -179,6 +330,14 @@ impl<R> Lexer<R> {
     {
         let _timer = BoaProfiler::global().start_event("next()", "Lexing");
 
+        if let Some(span) = self
+            .comments
+            .as_mut()
+            .and_then(|comments| comments.line_terminator.take())
+        {
+            return Ok(Some(Token::new(TokenKind::LineTerminator, span)));
+        }
+
         let (start, next_ch) = loop {
             let start = self.cursor.pos();
             if let Some(next_ch) = self.cursor.next_char()? {
@@ -196,7 +355,14 @@ impl<R> Lexer<R> {
         if start.column_number() == 1 && start.line_number() == 1 && next_ch == 0x23 {
             if let Some(hashbang_peek) = self.cursor.peek()? {
                 if hashbang_peek == 0x21 {
-                    let _token = HashbangComment.lex(&mut self.cursor, start);
+                    let mut comment = HashbangComment::new(self.preserves_comments());
+                    if let Ok(token) = comment.lex(&mut self.cursor, start) {
+                        self.record_comment(
+                            CommentKind::Hashbang,
+                            comment.take_text(),
+                            token.span(),
+                        );
+                    }
                     return self.next();
                 }
             }
@@ -281,7 +447,7 @@ impl<R> Lexer<R> {
                 // Skip comment
                 self.next()
             } else {
-                Ok(Some(token))
+                self.attach_comments(token).map(Some)
             }
         } else {
             Err(Error::syntax(
@@ -300,7 +466,8 @@ impl<R> Lexer<R> {
     where
         R: Read,
     {
-        TemplateLiteral.lex(&mut self.cursor, start)
+        let token = TemplateLiteral.lex(&mut self.cursor, start)?;
+        self.attach_comments(token)
     }
 }
 
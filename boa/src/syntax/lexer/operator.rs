@@ -20,7 +20,6 @@ macro_rules! vop {
             None => Err(Error::syntax("abrupt end - could not preview next value as part of the operator", $cursor.pos())),
             Some(b'=') => {
                 $cursor.next_byte()?.expect("= token vanished");
-                $cursor.next_column();
                 $assign_op
             }
             Some(_) => $op,
@@ -31,12 +30,10 @@ macro_rules! vop {
             None => Err(Error::syntax("abrupt end - could not preview next value as part of the operator", $cursor.pos())),
             Some(b'=') => {
                 $cursor.next_byte()?.expect("= token vanished");
-                $cursor.next_column();
                 $assign_op
             },
             $($case => {
                 $cursor.next_byte()?.expect("Token vanished");
-                $cursor.next_column();
                 $block
             })+,
             _ => $op,
@@ -46,7 +43,6 @@ macro_rules! vop {
         match $cursor.peek().ok_or_else(|| Error::syntax("could not preview next value", $cursor.pos()))? {
             $($case => {
                 $cursor.next_byte()?;
-                $cursor.next_column();
                 $block
             })+,
             _ => $op
@@ -7,6 +7,7 @@ use crate::{
         ast::{Position, Span},
         lexer::{Token, TokenKind},
     },
+    JsString,
 };
 use std::{
     io::{self, ErrorKind, Read},
@@ -111,7 +112,7 @@ impl StringLiteral {
         start_pos: Position,
         terminator: StringTerminator,
         is_strict_mode: bool,
-    ) -> Result<(String, Span), Error>
+    ) -> Result<(JsString, Span), Error>
     where
         R: Read,
     {
@@ -150,7 +151,7 @@ impl StringLiteral {
             }
         }
 
-        Ok((buf.to_string_lossy(), Span::new(start_pos, cursor.pos())))
+        Ok((buf.into(), Span::new(start_pos, cursor.pos())))
     }
 
     #[inline]
@@ -8,6 +8,7 @@ use crate::{
         ast::{Position, Span},
         lexer::{Token, TokenKind},
     },
+    JsString,
 };
 use std::io::{self, ErrorKind, Read};
 
@@ -50,7 +51,7 @@ impl TemplateString {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-static-semantics-templatestrings
-    pub fn to_owned_cooked(&self) -> Result<Box<str>, Error> {
+    pub fn to_owned_cooked(&self) -> Result<JsString, Error> {
         let mut cursor = Cursor::with_position(self.raw.as_bytes(), self.start_pos);
         let mut buf: Vec<u16> = Vec::new();
 
@@ -81,7 +82,7 @@ impl TemplateString {
             }
         }
 
-        Ok(buf.to_string_lossy().into())
+        Ok(buf.into())
     }
 }
 
@@ -5,7 +5,7 @@ use super::regex::RegExpFlags;
 use super::token::Numeric;
 use super::*;
 use super::{Error, Position};
-use crate::syntax::ast::Keyword;
+use crate::syntax::ast::{Comment, CommentKind, Keyword};
 use crate::syntax::lexer::template::TemplateString;
 use std::str;
 
@@ -329,6 +329,23 @@ fn check_positions() {
     );
 }
 
+#[test]
+fn check_positions_operators() {
+    let s = "a === b => c";
+    let mut lexer = Lexer::new(s.as_bytes());
+
+    assert_eq!(lexer.next().unwrap().unwrap().span(), span((1, 1), (1, 2)));
+
+    // Multi-character operators advance the column once per character.
+    assert_eq!(lexer.next().unwrap().unwrap().span(), span((1, 3), (1, 6)));
+    assert_eq!(lexer.next().unwrap().unwrap().span(), span((1, 7), (1, 8)));
+    assert_eq!(lexer.next().unwrap().unwrap().span(), span((1, 9), (1, 11)));
+    assert_eq!(
+        lexer.next().unwrap().unwrap().span(),
+        span((1, 12), (1, 13))
+    );
+}
+
 #[test]
 fn check_positions_codepoint() {
     let s = r#"console.log("hello world\u{2764}"); // Test"#;
@@ -1016,3 +1033,88 @@ mod carriage_return {
         expect_tokens_with_lines(3, "-\r\n\n\r3");
     }
 }
+
+#[test]
+fn comments_discarded_by_default() {
+    let mut lexer = Lexer::new(&b"/* a */ x // b"[..]);
+
+    let token = lexer.next().unwrap().unwrap();
+    assert!(token.leading_comments().is_empty());
+    assert!(token.trailing_comments().is_empty());
+    assert!(lexer.take_pending_comments().is_empty());
+}
+
+#[test]
+fn preserved_comments() {
+    let s =
+        "#!/usr/bin/env boa\n/** doc */\nfunction f() {} // trailing\n/* a */ /* b\n */ x\n// end";
+    let mut lexer = Lexer::new(s.as_bytes());
+    lexer.set_preserve_comments(true);
+
+    let mut function = lexer.next().unwrap().unwrap();
+    while function.kind() == &TokenKind::LineTerminator {
+        function = lexer.next().unwrap().unwrap();
+    }
+    assert_eq!(function.kind(), &TokenKind::Keyword(Keyword::Function));
+    assert_eq!(
+        function.leading_comments(),
+        [
+            Comment::new(
+                CommentKind::Hashbang,
+                "/usr/bin/env boa",
+                span((1, 1), (2, 1))
+            ),
+            Comment::new(CommentKind::MultiLine, "* doc ", span((2, 1), (2, 11))),
+        ]
+    );
+    assert!(function.trailing_comments().is_empty());
+
+    let mut close_block = lexer.next().unwrap().unwrap();
+    while close_block.kind() != &TokenKind::Punctuator(Punctuator::CloseBlock) {
+        assert!(close_block.leading_comments().is_empty());
+        close_block = lexer.next().unwrap().unwrap();
+    }
+    assert_eq!(
+        close_block.trailing_comments(),
+        [Comment::new(
+            CommentKind::SingleLine,
+            " trailing",
+            span((3, 17), (3, 28))
+        )]
+    );
+
+    assert_eq!(
+        lexer.next().unwrap().unwrap().kind(),
+        &TokenKind::LineTerminator
+    );
+    assert_eq!(
+        lexer.next().unwrap().unwrap().kind(),
+        &TokenKind::LineTerminator
+    );
+    let x = lexer.next().unwrap().unwrap();
+    assert_eq!(x.kind(), &TokenKind::identifier("x"));
+    let texts: Vec<_> = x.leading_comments().iter().map(|c| c.text()).collect();
+    assert_eq!(texts, [" a ", " b\n "]);
+
+    assert_eq!(
+        lexer.next().unwrap().unwrap().kind(),
+        &TokenKind::LineTerminator
+    );
+    assert!(lexer.next().unwrap().is_none());
+    let pending = lexer.take_pending_comments();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].to_string(), "// end");
+}
+
+#[test]
+fn trailing_comment_line_terminator() {
+    let mut lexer = Lexer::new(&b"a /* x\n */ b"[..]);
+    lexer.set_preserve_comments(true);
+
+    let a = lexer.next().unwrap().unwrap();
+    assert_eq!(a.trailing_comments()[0].text(), " x\n ");
+    expect_tokens(
+        &mut lexer,
+        &[TokenKind::LineTerminator, TokenKind::identifier("b")],
+    );
+}
@@ -8,9 +8,10 @@
 use super::regex::RegExpFlags;
 
 use crate::{
-    syntax::ast::{Keyword, Punctuator, Span},
+    interner::Sym,
+    syntax::ast::{Comment, Comments, Keyword, Punctuator, Span},
     syntax::lexer::template::TemplateString,
-    JsBigInt,
+    JsBigInt, JsString,
 };
 use std::fmt::{self, Debug, Display, Formatter};
 
@@ -30,13 +31,30 @@ pub struct Token {
     kind: TokenKind,
     /// The token position in the original source code.
     span: Span,
+    /// The comments around the token, if the lexer preserves them.
+    comments: Option<Box<Comments>>,
 }
 
 impl Token {
     /// Create a new detailed token from the token data, line number and column number
     #[inline]
     pub fn new(kind: TokenKind, span: Span) -> Self {
-        Self { kind, span }
+        Self {
+            kind,
+            span,
+            comments: None,
+        }
+    }
+
+    /// Attaches comments to the token.
+    #[inline]
+    pub fn with_comments(mut self, comments: Comments) -> Self {
+        self.comments = if comments.is_empty() {
+            None
+        } else {
+            Some(Box::new(comments))
+        };
+        self
     }
 
     /// Gets the kind of the token.
@@ -50,6 +68,27 @@ impl Token {
     pub fn span(&self) -> Span {
         self.span
     }
+
+    /// Gets the comments found before the token, since the end of the previous line which held
+    /// code.
+    ///
+    /// This is always empty unless the lexer preserves comments.
+    #[inline]
+    pub fn leading_comments(&self) -> &[Comment] {
+        self.comments
+            .as_ref()
+            .map_or(&[], |comments| comments.leading())
+    }
+
+    /// Gets the comments found after the token, on the same line.
+    ///
+    /// This is always empty unless the lexer preserves comments.
+    #[inline]
+    pub fn trailing_comments(&self) -> &[Comment] {
+        self.comments
+            .as_ref()
+            .map_or(&[], |comments| comments.trailing())
+    }
 }
 
 impl Display for Token {
@@ -104,7 +143,7 @@ pub enum TokenKind {
     EOF,
 
     /// An identifier.
-    Identifier(Box<str>),
+    Identifier(Sym),
 
     /// A keyword.
     Keyword(Keyword),
@@ -119,7 +158,7 @@ pub enum TokenKind {
     Punctuator(Punctuator),
 
     /// A string literal.
-    StringLiteral(Box<str>),
+    StringLiteral(JsString),
 
     /// A part of a template literal without substitution.
     TemplateNoSubstitution(TemplateString),
@@ -175,7 +214,7 @@ impl TokenKind {
     /// Creates an `Identifier` token type.
     pub fn identifier<I>(ident: I) -> Self
     where
-        I: Into<Box<str>>,
+        I: Into<Sym>,
     {
         Self::Identifier(ident.into())
     }
@@ -201,7 +240,7 @@ impl TokenKind {
     /// Creates a `StringLiteral` token type.
     pub fn string_literal<S>(lit: S) -> Self
     where
-        S: Into<Box<str>>,
+        S: Into<JsString>,
     {
         Self::StringLiteral(lit.into())
     }
@@ -3,6 +3,8 @@
 #![allow(clippy::upper_case_acronyms)]
 
 pub mod ast;
+pub mod codegen;
+pub mod estree;
 pub mod lexer;
 pub mod parser;
 
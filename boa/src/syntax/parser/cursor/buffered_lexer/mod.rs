@@ -1,10 +1,12 @@
 use crate::{
     profiler::BoaProfiler,
     syntax::{
+        ast::{Comment, Punctuator},
         lexer::{InputElement, Lexer, Position, Token, TokenKind},
         parser::error::ParseError,
     },
 };
+use rustc_hash::FxHashMap;
 use std::io::Read;
 
 #[cfg(test)]
@@ -29,6 +31,13 @@ pub(super) struct BufferedLexer<R> {
     peeked: [Option<Token>; PEEK_BUF_SIZE],
     read_index: usize,
     write_index: usize,
+    last_token_end: Position,
+    last_token_closes_statement: bool,
+    last_token_trailing_comments: Vec<Comment>,
+    /// The leading comments of the consumed tokens, by start position of the token.
+    leading_comments: FxHashMap<Position, Vec<Comment>>,
+    /// The trailing comments of the consumed tokens, by end position of the token.
+    trailing_comments: FxHashMap<Position, Vec<Comment>>,
 }
 
 impl<R> From<Lexer<R>> for BufferedLexer<R>
@@ -51,6 +60,11 @@ where
             ],
             read_index: 0,
             write_index: 0,
+            last_token_end: Position::new(1, 1),
+            last_token_closes_statement: false,
+            last_token_trailing_comments: Vec::new(),
+            leading_comments: FxHashMap::default(),
+            trailing_comments: FxHashMap::default(),
         }
     }
 }
@@ -81,13 +95,117 @@ where
     pub(super) fn lex_regex(&mut self, start: Position) -> Result<Token, ParseError> {
         let _timer = BoaProfiler::global().start_event("cursor::lex_regex()", "Parsing");
         self.set_goal(InputElement::RegExp);
-        self.lexer.lex_slash_token(start).map_err(|e| e.into())
+        let token = self.lexer.lex_slash_token(start)?;
+        self.consumed(&token);
+        Ok(token)
     }
 
     /// Lexes the next tokens as template middle or template tail assuming that the starting
     /// '}' has already been consumed.
     pub(super) fn lex_template(&mut self, start: Position) -> Result<Token, ParseError> {
-        self.lexer.lex_template(start).map_err(ParseError::from)
+        let token = self.lexer.lex_template(start)?;
+        self.consumed(&token);
+        Ok(token)
+    }
+
+    /// Keeps track of the last consumed token which is not a line terminator, and of the comments
+    /// around the consumed tokens.
+    fn consumed(&mut self, token: &Token) {
+        if matches!(
+            token.kind(),
+            TokenKind::Punctuator(Punctuator::Comma | Punctuator::Semicolon)
+        ) {
+            // The comments around a separator belong to the token before it.
+            let comments = [token.leading_comments(), token.trailing_comments()].concat();
+            if !comments.is_empty() {
+                self.trailing_comments
+                    .entry(self.last_token_end)
+                    .or_default()
+                    .extend(comments);
+            }
+        } else if token.kind() != &TokenKind::LineTerminator {
+            let mut leading = token.leading_comments().to_vec();
+            // The comments between two tokens on the same line belong to the second one.
+            let inline = matches!(
+                self.trailing_comments
+                    .get(&self.last_token_end)
+                    .and_then(|comments| comments.last()),
+                Some(comment)
+                    if comment.span().end().line_number() == token.span().start().line_number()
+            );
+            if inline {
+                let mut comments = self
+                    .trailing_comments
+                    .remove(&self.last_token_end)
+                    .unwrap_or_default();
+                comments.append(&mut leading);
+                leading = comments;
+            }
+            if !leading.is_empty() {
+                self.leading_comments.insert(token.span().start(), leading);
+            }
+            if !token.trailing_comments().is_empty() {
+                self.trailing_comments
+                    .insert(token.span().end(), token.trailing_comments().to_vec());
+            }
+        }
+        if token.kind() != &TokenKind::LineTerminator {
+            self.last_token_end = token.span().end();
+            self.last_token_closes_statement = matches!(
+                token.kind(),
+                TokenKind::Punctuator(Punctuator::Semicolon | Punctuator::CloseBlock)
+            );
+            self.last_token_trailing_comments = token.trailing_comments().to_vec();
+        }
+    }
+
+    /// Gets the end position of the last consumed token, ignoring line terminators.
+    #[inline]
+    pub(super) fn last_token_end(&self) -> Position {
+        self.last_token_end
+    }
+
+    /// Returns `true` if the last consumed token, ignoring line terminators, is a `;` or a `}`,
+    /// which can end a statement.
+    #[inline]
+    pub(super) fn last_token_closes_statement(&self) -> bool {
+        self.last_token_closes_statement
+    }
+
+    /// Takes the trailing comments of the last consumed token, ignoring line terminators.
+    #[inline]
+    pub(super) fn take_trailing_comments(&mut self) -> Vec<Comment> {
+        std::mem::take(&mut self.last_token_trailing_comments)
+    }
+
+    /// Takes the leading comments of the consumed token which starts at `position`.
+    #[inline]
+    pub(super) fn take_leading_comments_at(&mut self, position: Position) -> Vec<Comment> {
+        self.leading_comments.remove(&position).unwrap_or_default()
+    }
+
+    /// Takes the trailing comments of the consumed token which ends at `position`.
+    #[inline]
+    pub(super) fn take_trailing_comments_at(&mut self, position: Position) -> Vec<Comment> {
+        self.trailing_comments.remove(&position).unwrap_or_default()
+    }
+
+    /// Sets whether the lexer preserves comments.
+    #[inline]
+    pub(super) fn set_preserve_comments(&mut self, preserve: bool) {
+        self.lexer.set_preserve_comments(preserve)
+    }
+
+    /// Checks if the lexer preserves comments.
+    #[inline]
+    pub(super) fn preserves_comments(&self) -> bool {
+        self.lexer.preserves_comments()
+    }
+
+    /// Takes the comments after the last token of the source, which are not attached to any token.
+    #[inline]
+    pub(super) fn take_pending_comments(&mut self) -> Vec<Comment> {
+        self.lexer.take_pending_comments()
     }
 
     #[inline]
@@ -174,6 +292,10 @@ where
             let tok = self.peeked[self.read_index].take();
             self.read_index = (self.read_index + 1) % PEEK_BUF_SIZE;
 
+            if let Some(ref token) = tok {
+                self.consumed(token);
+            }
+
             Ok(tok)
         } else {
             // We do not update the read index, since we should always return `None` from now on.
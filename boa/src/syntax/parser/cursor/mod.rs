@@ -1,13 +1,44 @@
 //! Cursor implementation for the parser.
 mod buffered_lexer;
 
-use super::ParseError;
-use crate::syntax::{
-    ast::Punctuator,
-    lexer::{InputElement, Lexer, Position, Token, TokenKind},
+use super::{Diagnostic, ParseError};
+use crate::{
+    interner::Sym,
+    syntax::{
+        ast::{
+            node::Node,
+            side_table::{Keyed, Record, SideTable},
+            Comment, Comments, Keyword, Punctuator, Span,
+        },
+        lexer::{InputElement, Lexer, Position, Token, TokenKind},
+    },
 };
 use buffered_lexer::BufferedLexer;
-use std::io::Read;
+use std::{io::Read, mem};
+
+/// Checks if the token can only appear at the start of a statement.
+fn starts_statement(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::Keyword(
+            Keyword::Break
+                | Keyword::Class
+                | Keyword::Const
+                | Keyword::Continue
+                | Keyword::Do
+                | Keyword::For
+                | Keyword::Function
+                | Keyword::If
+                | Keyword::Let
+                | Keyword::Return
+                | Keyword::Switch
+                | Keyword::Throw
+                | Keyword::Try
+                | Keyword::Var
+                | Keyword::While
+        )
+    )
+}
 
 /// The result of a peek for a semicolon.
 #[derive(Debug)]
@@ -22,6 +53,16 @@ pub(super) enum SemicolonResult<'s> {
 #[derive(Debug)]
 pub(super) struct Cursor<R> {
     buffered_lexer: BufferedLexer<R>,
+
+    /// The diagnostics collected so far, if the parser recovers from errors.
+    diagnostics: Option<Vec<Diagnostic>>,
+
+    /// The spans of the parsed nodes which are not part of a statement list yet.
+    records: Vec<Record>,
+
+    /// The statements of the current function that a `break` can leave, innermost last: the
+    /// labels of labelled statements, and `None` for loops and `switch` statements.
+    break_targets: Vec<Option<Sym>>,
 }
 
 impl<R> Cursor<R>
@@ -33,6 +74,127 @@ where
     pub(super) fn new(reader: R) -> Self {
         Self {
             buffered_lexer: Lexer::new(reader).into(),
+            diagnostics: None,
+            records: Vec::new(),
+            break_targets: Vec::new(),
+        }
+    }
+
+    /// Enters a statement that a `break` with `label` can leave: a labelled statement, or a
+    /// loop or `switch` statement if `label` is `None`.
+    #[inline]
+    pub(super) fn push_break_target(&mut self, label: Option<Sym>) {
+        self.break_targets.push(label);
+    }
+
+    /// Leaves the innermost statement entered with [`Cursor::push_break_target`].
+    #[inline]
+    pub(super) fn pop_break_target(&mut self) {
+        self.break_targets.pop();
+    }
+
+    /// Checks if a `break` with `label` has an enclosing statement to leave.
+    #[inline]
+    pub(super) fn has_break_target(&self, label: Option<Sym>) -> bool {
+        self.break_targets.contains(&label)
+    }
+
+    /// Takes the break targets of the enclosing statements, which a function body cannot leave.
+    #[inline]
+    pub(super) fn take_break_targets(&mut self) -> Vec<Option<Sym>> {
+        mem::take(&mut self.break_targets)
+    }
+
+    /// Restores the break targets taken by [`Cursor::take_break_targets`].
+    #[inline]
+    pub(super) fn set_break_targets(&mut self, break_targets: Vec<Option<Sym>>) {
+        self.break_targets = break_targets;
+    }
+
+    /// Makes the parser collect errors as diagnostics instead of stopping at the first one.
+    #[inline]
+    pub(super) fn enable_recovery(&mut self) {
+        self.diagnostics.get_or_insert_with(Vec::new);
+    }
+
+    /// Takes the diagnostics collected so far.
+    #[inline]
+    pub(super) fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        self.diagnostics.as_mut().map(mem::take).unwrap_or_default()
+    }
+
+    /// Records the error as a diagnostic if the parser recovers from errors, or returns it
+    /// otherwise.
+    pub(super) fn recover(&mut self, error: ParseError) -> Result<(), ParseError> {
+        let diagnostics = match &mut self.diagnostics {
+            Some(diagnostics) => diagnostics,
+            None => return Err(error),
+        };
+
+        // Nested statement lists may see the same lexing error while unwinding.
+        let diagnostic = Diagnostic::from(error);
+        if diagnostics.last() != Some(&diagnostic) {
+            diagnostics.push(diagnostic);
+        }
+        Ok(())
+    }
+
+    /// Skips tokens until the start of the next statement, after the statement starting at
+    /// `start` failed to parse.
+    ///
+    /// This stops after a `;` or a complete `{ ... }` block, before a line terminator or a
+    /// keyword that starts a statement, or before one of the `break_nodes` that end the
+    /// enclosing statement list. If the failed statement did not consume any token, at least
+    /// one token is skipped, unless the next token is a break node. If the failed statement
+    /// already consumed the `;` or `}` that ends it, nothing is skipped.
+    ///
+    /// Returns `false` if no more tokens can be read.
+    pub(super) fn synchronize(&mut self, start: Position, break_nodes: &[TokenKind]) -> bool {
+        let mut depth = 0_usize;
+        let mut skipped = self.last_token_end() > start;
+        if skipped && self.buffered_lexer.last_token_closes_statement() {
+            return true;
+        }
+        loop {
+            if depth == 0 && skipped {
+                match self.buffered_lexer.peek(0, false) {
+                    Ok(Some(token)) if token.kind() == &TokenKind::LineTerminator => return true,
+                    _ => {}
+                }
+            }
+
+            let token = match self.peek(0) {
+                Ok(Some(token)) => token,
+                Ok(None) => return false,
+                Err(e) => {
+                    let _ = self.recover(e);
+                    return false;
+                }
+            };
+
+            if depth == 0 {
+                if break_nodes.contains(token.kind()) {
+                    return true;
+                }
+                if skipped && starts_statement(token.kind()) {
+                    return true;
+                }
+            }
+
+            let kind = token.kind().clone();
+            if let Err(e) = self.next() {
+                let _ = self.recover(e);
+                return false;
+            }
+            skipped = true;
+
+            match kind {
+                TokenKind::Punctuator(Punctuator::OpenBlock) => depth += 1,
+                TokenKind::Punctuator(Punctuator::CloseBlock) if depth > 1 => depth -= 1,
+                TokenKind::Punctuator(Punctuator::CloseBlock) => return true,
+                TokenKind::Punctuator(Punctuator::Semicolon) if depth == 0 => return true,
+                _ => {}
+            }
         }
     }
 
@@ -71,6 +233,103 @@ where
         self.buffered_lexer.set_strict_mode(strict_mode)
     }
 
+    /// Gets the end position of the last consumed token.
+    #[inline]
+    pub(super) fn last_token_end(&self) -> Position {
+        self.buffered_lexer.last_token_end()
+    }
+
+    /// Gets the start position of the next token, where the node about to be parsed starts.
+    #[inline]
+    pub(super) fn node_start(&mut self) -> Result<Position, ParseError> {
+        Ok(self.peek(0)?.ok_or(ParseError::AbruptEnd)?.span().start())
+    }
+
+    /// Records the span of a node which was just parsed, from `start` to the end of the last
+    /// consumed token.
+    #[inline]
+    pub(super) fn record<T: Keyed>(&mut self, node: &T, start: Position) {
+        let end = self.last_token_end();
+        if start > end {
+            return;
+        }
+        let record = Record::new(node, Span::new(start, end));
+        // Parsers which pass a node through may record it again.
+        if self.records.last() != Some(&record) {
+            self.records.push(record);
+        }
+    }
+
+    /// Records the span of a node which was just parsed, and returns the node.
+    #[inline]
+    pub(super) fn spanned<T: Keyed>(&mut self, node: T, start: Position) -> T {
+        self.record(&node, start);
+        node
+    }
+
+    /// Gets the number of node spans recorded so far, which is where the records of the nodes
+    /// parsed from now on start.
+    #[inline]
+    pub(super) fn records_start(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Drops the spans recorded since `start`, for nodes which were left out of the AST.
+    #[inline]
+    pub(super) fn drop_records(&mut self, start: usize) {
+        self.records.truncate(start);
+    }
+
+    /// Builds the side table of a statement list with the spans recorded since `start`, and the
+    /// comments around its items, one set for each item.
+    ///
+    /// The comments found around the nodes of the list are attached to them too, if the lexer
+    /// preserves comments.
+    #[inline]
+    pub(super) fn side_table(
+        &mut self,
+        items: &[Node],
+        start: usize,
+        comments: Vec<Comments>,
+    ) -> SideTable {
+        let records = self.records.split_off(start);
+        let mut side_table = SideTable::from_records(items, &records);
+        if self.preserves_comments() {
+            let lexer = &mut self.buffered_lexer;
+            side_table.attach_comments(items, comments, |span| {
+                (
+                    lexer.take_leading_comments_at(span.start()),
+                    lexer.take_trailing_comments_at(span.end()),
+                )
+            });
+        }
+        side_table
+    }
+
+    /// Takes the trailing comments of the last consumed token.
+    #[inline]
+    pub(super) fn take_trailing_comments(&mut self) -> Vec<Comment> {
+        self.buffered_lexer.take_trailing_comments()
+    }
+
+    /// Makes the lexer preserve comments, so that they get attached to the parsed statements.
+    #[inline]
+    pub(super) fn preserve_comments(&mut self) {
+        self.buffered_lexer.set_preserve_comments(true)
+    }
+
+    /// Checks if the lexer preserves comments.
+    #[inline]
+    pub(super) fn preserves_comments(&self) -> bool {
+        self.buffered_lexer.preserves_comments()
+    }
+
+    /// Takes the comments after the last token of the source.
+    #[inline]
+    pub(super) fn take_pending_comments(&mut self) -> Vec<Comment> {
+        self.buffered_lexer.take_pending_comments()
+    }
+
     /// Returns an error if the next token is not of kind `kind`.
     #[inline]
     pub(super) fn expect<K>(&mut self, kind: K, context: &'static str) -> Result<Token, ParseError>
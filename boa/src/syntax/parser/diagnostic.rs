@@ -0,0 +1,160 @@
+//! Diagnostics reported by the parser when recovering from errors.
+
+use super::ParseError;
+use crate::syntax::{
+    ast::{Position, Span},
+    lexer::TokenKind,
+};
+use std::fmt;
+
+/// The severity of a [`Diagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// The source code is invalid, and the affected statement was left out of the AST.
+    Error,
+    /// The source code is valid, but probably does not do what was intended.
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        })
+    }
+}
+
+/// A suggested edit which would fix the problem reported by a [`Diagnostic`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    message: Box<str>,
+    span: Span,
+    replacement: Box<str>,
+}
+
+impl Suggestion {
+    /// Creates a suggestion to replace the source code in `span` with `replacement`.
+    pub fn new<M, T>(message: M, span: Span, replacement: T) -> Self
+    where
+        M: Into<Box<str>>,
+        T: Into<Box<str>>,
+    {
+        Self {
+            message: message.into(),
+            span,
+            replacement: replacement.into(),
+        }
+    }
+
+    /// Creates a suggestion to insert `text` at `position`.
+    pub fn insert<T>(text: T, position: Position) -> Self
+    where
+        T: Into<Box<str>>,
+    {
+        let text = text.into();
+        Self {
+            message: format!("insert `{}`", text).into(),
+            span: Span::new(position, position),
+            replacement: text,
+        }
+    }
+
+    /// Gets the human readable description of the edit.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Gets the span of source code which should be replaced.
+    ///
+    /// An empty span means the replacement should be inserted at that position.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Gets the text which should replace the source code in the span.
+    pub fn replacement(&self) -> &str {
+        &self.replacement
+    }
+}
+
+/// A problem found in the source code while parsing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    severity: Severity,
+    message: Box<str>,
+    span: Option<Span>,
+    suggestion: Option<Suggestion>,
+}
+
+impl Diagnostic {
+    /// Creates a new diagnostic.
+    pub fn new<M>(severity: Severity, message: M, span: Option<Span>) -> Self
+    where
+        M: Into<Box<str>>,
+    {
+        Self {
+            severity,
+            message: message.into(),
+            span,
+            suggestion: None,
+        }
+    }
+
+    /// Attaches a suggested fix to the diagnostic.
+    pub fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        self.suggestion = Some(suggestion);
+        self
+    }
+
+    /// Gets the severity of the diagnostic.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Gets the message of the diagnostic.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Gets the span of source code the diagnostic refers to, if known.
+    ///
+    /// Diagnostics without a span refer to the end of the input.
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+
+    /// Gets the suggested fix for the diagnostic, if there is one.
+    pub fn suggestion(&self) -> Option<&Suggestion> {
+        self.suggestion.as_ref()
+    }
+}
+
+impl From<ParseError> for Diagnostic {
+    fn from(error: ParseError) -> Self {
+        let diagnostic = Self::new(Severity::Error, error.to_string(), error.span());
+
+        // A single missing punctuator can be fixed by inserting it before the unexpected token.
+        match error {
+            ParseError::Expected {
+                expected, found, ..
+            } => match &*expected {
+                [TokenKind::Punctuator(punctuator)] => diagnostic.with_suggestion(
+                    Suggestion::insert(punctuator.to_string(), found.span().start()),
+                ),
+                _ => diagnostic,
+            },
+            _ => diagnostic,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity, self.message)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " (help: {})", suggestion.message)?;
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 //! Error and result implementation for the parser.
 
-use crate::syntax::ast::{position::Position, Node};
+use crate::syntax::ast::{
+    position::{Position, Span},
+    Node,
+};
 use crate::syntax::lexer::{Error as LexError, Token, TokenKind};
 use std::fmt;
 
@@ -110,6 +113,24 @@ impl ParseError {
     pub(super) fn unimplemented(message: &'static str, position: Position) -> Self {
         Self::Unimplemented { message, position }
     }
+
+    /// Gets the location in the source code where the error happened, if known.
+    ///
+    /// Errors reported at a single position are returned as an empty span at that position.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Self::Expected { found, .. } | Self::Unexpected { found, .. } => Some(found.span()),
+            Self::General { position, .. }
+            | Self::Unimplemented { position, .. }
+            | Self::Lex {
+                err: LexError::Syntax(_, position),
+            } => Some(Span::new(*position, *position)),
+            Self::AbruptEnd
+            | Self::Lex {
+                err: LexError::IO(_),
+            } => None,
+        }
+    }
 }
 
 impl fmt::Display for ParseError {
@@ -12,7 +12,7 @@ use crate::{
     syntax::{
         ast::{
             node::{ArrowFunctionDecl, FormalParameter, Node, Return, StatementList},
-            Punctuator,
+            Punctuator, Span,
         },
         lexer::{Error as LexError, Position, TokenKind},
         parser::{
@@ -89,9 +89,10 @@ where
             let param = BindingIdentifier::new(self.allow_yield, self.allow_await)
                 .parse(cursor)
                 .context("arrow function")?;
+            let param = FormalParameter::new(param, None, false);
             (
                 FormalParameterList {
-                    parameters: Box::new([FormalParameter::new(param, None, false)]),
+                    parameters: Box::new([cursor.spanned(param, params_start_position)]),
                     is_simple: true,
                     has_duplicates: false,
                 },
@@ -127,7 +128,7 @@ where
         {
             let lexically_declared_names = body.lexically_declared_names();
             for param in params.parameters.as_ref() {
-                if lexically_declared_names.contains(param.name()) {
+                if lexically_declared_names.contains(&param.name()) {
                     return Err(ParseError::lex(LexError::Syntax(
                         format!("Redeclaration of formal parameter `{}`", param.name()).into(),
                         match cursor.peek(0)? {
@@ -175,11 +176,19 @@ where
                 cursor.expect(Punctuator::CloseBlock, "arrow function")?;
                 Ok(body)
             }
-            _ => Ok(StatementList::from(vec![Return::new(
-                ExpressionBody::new(self.allow_in, false).parse(cursor)?,
-                None,
-            )
-            .into()])),
+            _ => {
+                let start = cursor.node_start()?;
+                let records = cursor.records_start();
+                let expr = ExpressionBody::new(self.allow_in, false).parse(cursor)?;
+                // The body returns the expression, which is where the return statement is.
+                let body =
+                    StatementList::from(
+                        vec![cursor.spanned(Return::new(expr, None).into(), start)],
+                    );
+                let span = Span::new(start, cursor.last_token_end());
+                let side_table = cursor.side_table(body.items(), records, Vec::new());
+                Ok(body.with_source(Box::new([span]), side_table))
+            }
         }
     }
 }
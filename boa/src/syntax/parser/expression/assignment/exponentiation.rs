@@ -88,11 +88,13 @@ where
             return UnaryExpression::new(self.allow_yield, self.allow_await).parse(cursor);
         }
 
+        let start = cursor.node_start()?;
         let lhs = UpdateExpression::new(self.allow_yield, self.allow_await).parse(cursor)?;
         if let Some(tok) = cursor.peek(0)? {
             if let TokenKind::Punctuator(Punctuator::Exp) = tok.kind() {
                 cursor.next()?.expect("** token vanished"); // Consume the token.
-                return Ok(BinOp::new(NumOp::Exp, lhs, self.parse(cursor)?).into());
+                let node = BinOp::new(NumOp::Exp, lhs, self.parse(cursor)?).into();
+                return Ok(cursor.spanned(node, start));
             }
         }
         Ok(lhs)
@@ -83,11 +83,14 @@ where
     fn parse(self, cursor: &mut Cursor<R>) -> ParseResult {
         let _timer = BoaProfiler::global().start_event("AssignmentExpression", "Parsing");
         cursor.set_goal(InputElement::Div);
+        let start = cursor.node_start()?;
 
         match cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.kind() {
             // [+Yield]YieldExpression[?In, ?Await]
             TokenKind::Keyword(Keyword::Yield) if self.allow_yield.0 => {
-                return YieldExpression::new(self.allow_in, self.allow_await).parse(cursor)
+                return YieldExpression::new(self.allow_in, self.allow_await)
+                    .parse(cursor)
+                    .map(|node| cursor.spanned(node, start));
             }
             // ArrowFunction[?In, ?Yield, ?Await] -> ArrowParameters[?Yield, ?Await] -> BindingIdentifier[?Yield, ?Await]
             TokenKind::Identifier(_)
@@ -101,7 +104,7 @@ where
                             self.allow_await,
                         )
                         .parse(cursor)
-                        .map(Node::ArrowFunctionDecl);
+                        .map(|arrow| cursor.spanned(Node::ArrowFunctionDecl(arrow), start));
                     }
                 }
             }
@@ -120,7 +123,9 @@ where
                                         self.allow_await,
                                     )
                                     .parse(cursor)
-                                    .map(Node::ArrowFunctionDecl);
+                                    .map(|arrow| {
+                                        cursor.spanned(Node::ArrowFunctionDecl(arrow), start)
+                                    });
                                 }
                             }
                         }
@@ -131,7 +136,7 @@ where
                                 self.allow_await,
                             )
                             .parse(cursor)
-                            .map(Node::ArrowFunctionDecl);
+                            .map(|arrow| cursor.spanned(Node::ArrowFunctionDecl(arrow), start));
                         }
                         TokenKind::Identifier(_) => {
                             if let Some(t) = cursor.peek(2)? {
@@ -144,7 +149,9 @@ where
                                             self.allow_await,
                                         )
                                         .parse(cursor)
-                                        .map(Node::ArrowFunctionDecl);
+                                        .map(|arrow| {
+                                            cursor.spanned(Node::ArrowFunctionDecl(arrow), start)
+                                        });
                                     }
                                     TokenKind::Punctuator(Punctuator::CloseParen) => {
                                         // Need to check if the token after the close paren is an arrow, if so then this is an ArrowFunction
@@ -158,7 +165,12 @@ where
                                                     self.allow_await,
                                                 )
                                                 .parse(cursor)
-                                                .map(Node::ArrowFunctionDecl);
+                                                .map(|arrow| {
+                                                    cursor.spanned(
+                                                        Node::ArrowFunctionDecl(arrow),
+                                                        start,
+                                                    )
+                                                });
                                             }
                                         }
                                     }
@@ -187,6 +199,7 @@ where
                     cursor.next()?.expect("= token vanished"); // Consume the token.
                     if is_assignable(&lhs) {
                         lhs = Assign::new(lhs, self.parse(cursor)?).into();
+                        cursor.record(&lhs, start);
                     } else {
                         return Err(ParseError::lex(LexError::Syntax(
                             "Invalid left-hand side in assignment".into(),
@@ -201,6 +214,7 @@ where
                         let expr = self.parse(cursor)?;
 
                         lhs = BinOp::new(binop, lhs, expr).into();
+                        cursor.record(&lhs, start);
                     } else {
                         return Err(ParseError::lex(LexError::Syntax(
                             "Invalid left-hand side in assignment".into(),
@@ -94,14 +94,14 @@ where
                 }
             }
 
+            let start = cursor.node_start()?;
             if cursor.next_if(Punctuator::Spread)?.is_some() {
-                args.push(
-                    Spread::new(
-                        AssignmentExpression::new(true, self.allow_yield, self.allow_await)
-                            .parse(cursor)?,
-                    )
-                    .into(),
-                );
+                let spread = Spread::new(
+                    AssignmentExpression::new(true, self.allow_yield, self.allow_await)
+                        .parse(cursor)?,
+                )
+                .into();
+                args.push(cursor.spanned(spread, start));
             } else {
                 cursor.set_goal(InputElement::RegExp);
                 args.push(
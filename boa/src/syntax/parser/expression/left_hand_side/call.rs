@@ -17,7 +17,7 @@ use crate::{
             },
             Punctuator,
         },
-        lexer::TokenKind,
+        lexer::{Position, TokenKind},
         parser::{
             expression::{left_hand_side::template::TaggedTemplateLiteral, Expression},
             AllowAwait, AllowYield, Cursor, ParseError, ParseResult, TokenParser,
@@ -38,12 +38,18 @@ use std::io::Read;
 pub(super) struct CallExpression {
     allow_yield: AllowYield,
     allow_await: AllowAwait,
+    start: Position,
     first_member_expr: Node,
 }
 
 impl CallExpression {
     /// Creates a new `CallExpression` parser.
-    pub(super) fn new<Y, A>(allow_yield: Y, allow_await: A, first_member_expr: Node) -> Self
+    pub(super) fn new<Y, A>(
+        allow_yield: Y,
+        allow_await: A,
+        start: Position,
+        first_member_expr: Node,
+    ) -> Self
     where
         Y: Into<AllowYield>,
         A: Into<AllowAwait>,
@@ -51,6 +57,7 @@ impl CallExpression {
         Self {
             allow_yield: allow_yield.into(),
             allow_await: allow_await.into(),
+            start,
             first_member_expr,
         }
     }
@@ -69,7 +76,8 @@ where
 
         let mut lhs = if token.kind() == &TokenKind::Punctuator(Punctuator::OpenParen) {
             let args = Arguments::new(self.allow_yield, self.allow_await).parse(cursor)?;
-            Node::from(Call::new(self.first_member_expr, args))
+            let call = Node::from(Call::new(self.first_member_expr, args));
+            cursor.spanned(call, self.start)
         } else {
             let next_token = cursor.next()?.expect("token vanished");
             return Err(ParseError::expected(
@@ -91,7 +99,7 @@ where
 
                     match &cursor.next()?.ok_or(ParseError::AbruptEnd)?.kind() {
                         TokenKind::Identifier(name) => {
-                            lhs = GetConstField::new(lhs, name.clone()).into();
+                            lhs = GetConstField::new(lhs, *name).into();
                         }
                         TokenKind::Keyword(kw) => {
                             lhs = GetConstField::new(lhs, kw.to_string()).into();
@@ -123,6 +131,7 @@ where
                 }
                 _ => break,
             }
+            cursor.record(&lhs, self.start);
         }
         Ok(lhs)
     }
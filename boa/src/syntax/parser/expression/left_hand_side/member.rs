@@ -64,6 +64,7 @@ where
     fn parse(self, cursor: &mut Cursor<R>) -> ParseResult {
         let _timer = BoaProfiler::global().start_event("MemberExpression", "Parsing");
 
+        let start = cursor.node_start()?;
         let mut lhs = if cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.kind()
             == &TokenKind::Keyword(Keyword::New)
         {
@@ -77,7 +78,7 @@ where
             };
             let call_node = Call::new(lhs, args);
 
-            Node::from(New::from(call_node))
+            cursor.spanned(Node::from(New::from(call_node)), start)
         } else {
             PrimaryExpression::new(self.allow_yield, self.allow_await).parse(cursor)?
         };
@@ -89,9 +90,7 @@ where
                     let token = cursor.next()?.ok_or(ParseError::AbruptEnd)?;
 
                     match token.kind() {
-                        TokenKind::Identifier(name) => {
-                            lhs = GetConstField::new(lhs, name.clone()).into()
-                        }
+                        TokenKind::Identifier(name) => lhs = GetConstField::new(lhs, *name).into(),
                         TokenKind::Keyword(kw) => {
                             lhs = GetConstField::new(lhs, kw.to_string()).into()
                         }
@@ -124,6 +123,7 @@ where
                 }
                 _ => break,
             }
+            cursor.record(&lhs, start);
         }
 
         Ok(lhs)
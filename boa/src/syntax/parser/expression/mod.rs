@@ -79,6 +79,7 @@ macro_rules! expression { ($name:ident, $lower:ident, [$( $op:path ),*], [$( $lo
                 cursor.set_goal($goal.unwrap());
             }
 
+            let start = cursor.node_start()?;
             let mut lhs = $lower::new($( self.$low_param ),*).parse(cursor)?;
             while let Some(tok) = cursor.peek(0)? {
                 match *tok.kind() {
@@ -89,6 +90,7 @@ macro_rules! expression { ($name:ident, $lower:ident, [$( $op:path ),*], [$( $lo
                             lhs,
                             $lower::new($( self.$low_param ),*).parse(cursor)?
                         ).into();
+                        cursor.record(&lhs, start);
                     }
                     TokenKind::Keyword(op) if $( op == $op )||* => {
                         let _ = cursor.next().expect("token disappeared");
@@ -97,6 +99,7 @@ macro_rules! expression { ($name:ident, $lower:ident, [$( $op:path ),*], [$( $lo
                             lhs,
                             $lower::new($( self.$low_param ),*).parse(cursor)?
                         ).into();
+                        cursor.record(&lhs, start);
                     }
                     _ => break
                 }
@@ -214,6 +217,7 @@ where
     fn parse(self, cursor: &mut Cursor<R>) -> ParseResult {
         let _timer = BoaProfiler::global().start_event("ShortCircuitExpression", "Parsing");
 
+        let start = cursor.node_start()?;
         let mut current_node =
             BitwiseORExpression::new(self.allow_in, self.allow_yield, self.allow_await)
                 .parse(cursor)?;
@@ -236,6 +240,7 @@ where
                             .parse(cursor)?;
 
                     current_node = BinOp::new(LogOp::And, current_node, rhs).into();
+                    cursor.record(&current_node, start);
                 }
                 TokenKind::Punctuator(Punctuator::BoolOr) => {
                     if previous == PreviousExpr::Coalesce {
@@ -255,6 +260,7 @@ where
                     )
                     .parse(cursor)?;
                     current_node = BinOp::new(LogOp::Or, current_node, rhs).into();
+                    cursor.record(&current_node, start);
                 }
                 TokenKind::Punctuator(Punctuator::Coalesce) => {
                     if previous == PreviousExpr::Logical {
@@ -273,6 +279,7 @@ where
                         BitwiseORExpression::new(self.allow_in, self.allow_yield, self.allow_await)
                             .parse(cursor)?;
                     current_node = BinOp::new(LogOp::Coalesce, current_node, rhs).into();
+                    cursor.record(&current_node, start);
                 }
                 _ => break,
             }
@@ -486,6 +493,7 @@ where
             cursor.set_goal(None::<InputElement>.unwrap());
         }
 
+        let start = cursor.node_start()?;
         let mut lhs = ShiftExpression::new(self.allow_yield, self.allow_await).parse(cursor)?;
         while let Some(tok) = cursor.peek(0)? {
             match *tok.kind() {
@@ -502,6 +510,7 @@ where
                         ShiftExpression::new(self.allow_yield, self.allow_await).parse(cursor)?,
                     )
                     .into();
+                    cursor.record(&lhs, start);
                 }
                 TokenKind::Keyword(op)
                     if op == Keyword::InstanceOf
@@ -514,6 +523,7 @@ where
                         ShiftExpression::new(self.allow_yield, self.allow_await).parse(cursor)?,
                     )
                     .into();
+                    cursor.record(&lhs, start);
                 }
                 _ => break,
             }
@@ -66,20 +66,23 @@ where
 
         loop {
             // TODO: Support all features.
-            while cursor.next_if(Punctuator::Comma)?.is_some() {
-                elements.push(Node::Const(Const::Undefined));
+            // Holes are recorded with the span of their comma, so that the spans of the
+            // elements after them are matched with the right nodes.
+            while let Some(comma) = cursor.next_if(Punctuator::Comma)? {
+                let start = comma.span().start();
+                elements.push(cursor.spanned(Node::Const(Const::Undefined), start));
             }
 
             if cursor.next_if(Punctuator::CloseBracket)?.is_some() {
                 break;
             }
 
-            let _ = cursor.peek(0)?.ok_or(ParseError::AbruptEnd); // Check that there are more tokens to read.
+            let start = cursor.node_start()?; // Check that there are more tokens to read.
 
             if cursor.next_if(Punctuator::Spread)?.is_some() {
                 let node = AssignmentExpression::new(true, self.allow_yield, self.allow_await)
                     .parse(cursor)?;
-                elements.push(Spread::new(node).into());
+                elements.push(cursor.spanned(Spread::new(node).into(), start));
             } else {
                 elements.push(
                     AssignmentExpression::new(true, self.allow_yield, self.allow_await)
@@ -1,3 +1,4 @@
+use crate::interner::Sym;
 use crate::syntax::{
     ast::{
         node::{AsyncFunctionExpr, Declaration, DeclarationList, Return, StatementList},
@@ -18,11 +19,10 @@ fn check_async_expression() {
             vec![Declaration::new_with_identifier(
                 "add",
                 Some(
-                    AsyncFunctionExpr::new::<Option<Box<str>>, _, StatementList>(
+                    AsyncFunctionExpr::new::<Option<Sym>, _, StatementList>(
                         None,
                         [],
-                        vec![Return::new::<_, _, Option<Box<str>>>(Const::from(1), None).into()]
-                            .into(),
+                        vec![Return::new::<_, _, Option<Sym>>(Const::from(1), None).into()].into(),
                     )
                     .into(),
                 ),
@@ -46,17 +46,17 @@ fn check_nested_async_expression() {
             vec![Declaration::new_with_identifier(
                 "a",
                 Some(
-                    AsyncFunctionExpr::new::<Option<Box<str>>, _, StatementList>(
+                    AsyncFunctionExpr::new::<Option<Sym>, _, StatementList>(
                         None,
                         [],
                         vec![DeclarationList::Const(
                             vec![Declaration::new_with_identifier(
                                 "b",
                                 Some(
-                                    AsyncFunctionExpr::new::<Option<Box<str>>, _, StatementList>(
+                                    AsyncFunctionExpr::new::<Option<Sym>, _, StatementList>(
                                         None,
                                         [],
-                                        vec![Return::new::<_, _, Option<Box<str>>>(
+                                        vec![Return::new::<_, _, Option<Sym>>(
                                             Const::from(1),
                                             None,
                                         )
@@ -1,3 +1,4 @@
+use crate::interner::Sym;
 use crate::syntax::{
     ast::{
         node::{Declaration, DeclarationList, GeneratorExpr, StatementList, Yield},
@@ -17,7 +18,7 @@ fn check_generator_function_expression() {
             vec![Declaration::new_with_identifier(
                 "gen",
                 Some(
-                    GeneratorExpr::new::<Option<Box<str>>, _, StatementList>(
+                    GeneratorExpr::new::<Option<Sym>, _, StatementList>(
                         None,
                         [],
                         vec![Yield::new(Const::from(1), false).into()].into(),
@@ -42,7 +43,7 @@ fn check_generator_function_delegate_yield_expression() {
             vec![Declaration::new_with_identifier(
                 "gen",
                 Some(
-                    GeneratorExpr::new::<Option<Box<str>>, _, StatementList>(
+                    GeneratorExpr::new::<Option<Sym>, _, StatementList>(
                         None,
                         [],
                         vec![Yield::new(Const::from(1), true).into()].into(),
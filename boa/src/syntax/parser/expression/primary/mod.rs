@@ -78,8 +78,9 @@ where
         let _timer = BoaProfiler::global().start_event("PrimaryExpression", "Parsing");
 
         let tok = cursor.next()?.ok_or(ParseError::AbruptEnd)?;
+        let start = tok.span().start();
 
-        match tok.kind() {
+        let node = match tok.kind() {
             TokenKind::Keyword(Keyword::This) => Ok(Node::This),
             TokenKind::Keyword(Keyword::Function) => {
                 let next_token = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?;
@@ -97,7 +98,7 @@ where
                 let expr =
                     Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
                 cursor.expect(Punctuator::CloseParen, "primary expression")?;
-                Ok(expr)
+                return Ok(expr);
             }
             TokenKind::Punctuator(Punctuator::OpenBracket) => {
                 cursor.set_goal(InputElement::RegExp);
@@ -146,7 +147,7 @@ where
                 }
                 Ok(Identifier::from("await").into())
             }
-            TokenKind::StringLiteral(s) => Ok(Const::from(s.as_ref()).into()),
+            TokenKind::StringLiteral(s) => Ok(Const::from(s.clone()).into()),
             TokenKind::TemplateNoSubstitution(template_string) => {
                 Ok(Const::from(template_string.to_owned_cooked().map_err(ParseError::lex)?).into())
             }
@@ -182,14 +183,12 @@ where
                 self.allow_yield,
                 self.allow_await,
                 tok.span().start(),
-                template_string
-                    .to_owned_cooked()
-                    .map_err(ParseError::lex)?
-                    .as_ref(),
+                template_string.to_owned_cooked().map_err(ParseError::lex)?,
             )
             .parse(cursor)
             .map(Node::TemplateLit),
             _ => Err(ParseError::unexpected(tok.clone(), "primary expression")),
-        }
+        }?;
+        Ok(cursor.spanned(node, start))
     }
 }
@@ -69,8 +69,10 @@ where
                 break;
             }
 
-            elements
-                .push(PropertyDefinition::new(self.allow_yield, self.allow_await).parse(cursor)?);
+            let start = cursor.node_start()?;
+            let property =
+                PropertyDefinition::new(self.allow_yield, self.allow_await).parse(cursor)?;
+            elements.push(cursor.spanned(property, start));
 
             if cursor.next_if(Punctuator::CloseBlock)?.is_some() {
                 break;
@@ -238,7 +240,7 @@ where
             {
                 let lexically_declared_names = body.lexically_declared_names();
                 for param in params.parameters.as_ref() {
-                    if lexically_declared_names.contains(param.name()) {
+                    if lexically_declared_names.contains(&param.name()) {
                         return Err(ParseError::lex(LexError::Syntax(
                             format!("Redeclaration of formal parameter `{}`", param.name()).into(),
                             match cursor.peek(0)? {
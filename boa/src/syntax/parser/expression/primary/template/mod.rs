@@ -18,6 +18,7 @@ use crate::{
         parser::expression::Expression,
         parser::{AllowAwait, AllowYield, ParseError, TokenParser},
     },
+    JsString,
 };
 use std::io::Read;
 
@@ -34,12 +35,17 @@ pub(super) struct TemplateLiteral {
     allow_yield: AllowYield,
     allow_await: AllowAwait,
     start: Position,
-    first: String,
+    first: JsString,
 }
 
 impl TemplateLiteral {
     /// Creates a new `TemplateLiteral` parser.
-    pub(super) fn new<Y, A>(allow_yield: Y, allow_await: A, start: Position, first: &str) -> Self
+    pub(super) fn new<Y, A>(
+        allow_yield: Y,
+        allow_await: A,
+        start: Position,
+        first: JsString,
+    ) -> Self
     where
         Y: Into<AllowYield>,
         A: Into<AllowAwait>,
@@ -48,7 +54,7 @@ impl TemplateLiteral {
             allow_yield: allow_yield.into(),
             allow_await: allow_await.into(),
             start,
-            first: first.to_owned(),
+            first,
         }
     }
 }
@@ -63,7 +69,7 @@ where
         let _timer = BoaProfiler::global().start_event("TemplateLiteral", "Parsing");
 
         let mut elements = vec![
-            TemplateElement::String(self.first.into_boxed_str()),
+            TemplateElement::String(self.first),
             TemplateElement::Expr(
                 Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?,
             ),
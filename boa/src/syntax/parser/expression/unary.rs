@@ -63,7 +63,7 @@ where
 
         let tok = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?;
         let token_start = tok.span().start();
-        match tok.kind() {
+        let node = match tok.kind() {
             TokenKind::Keyword(Keyword::Delete) => {
                 cursor.next()?.expect("Delete keyword vanished"); // Consume the token.
                 let val = self.parse(cursor)?;
@@ -77,33 +77,34 @@ where
                     }
                 }
 
-                Ok(node::UnaryOp::new(UnaryOp::Delete, val).into())
+                node::UnaryOp::new(UnaryOp::Delete, val).into()
             }
             TokenKind::Keyword(Keyword::Void) => {
                 cursor.next()?.expect("Void keyword vanished"); // Consume the token.
-                Ok(node::UnaryOp::new(UnaryOp::Void, self.parse(cursor)?).into())
+                node::UnaryOp::new(UnaryOp::Void, self.parse(cursor)?).into()
             }
             TokenKind::Keyword(Keyword::TypeOf) => {
                 cursor.next()?.expect("TypeOf keyword vanished"); // Consume the token.
-                Ok(node::UnaryOp::new(UnaryOp::TypeOf, self.parse(cursor)?).into())
+                node::UnaryOp::new(UnaryOp::TypeOf, self.parse(cursor)?).into()
             }
             TokenKind::Punctuator(Punctuator::Add) => {
                 cursor.next()?.expect("+ token vanished"); // Consume the token.
-                Ok(node::UnaryOp::new(UnaryOp::Plus, self.parse(cursor)?).into())
+                node::UnaryOp::new(UnaryOp::Plus, self.parse(cursor)?).into()
             }
             TokenKind::Punctuator(Punctuator::Sub) => {
                 cursor.next()?.expect("- token vanished"); // Consume the token.
-                Ok(node::UnaryOp::new(UnaryOp::Minus, self.parse(cursor)?).into())
+                node::UnaryOp::new(UnaryOp::Minus, self.parse(cursor)?).into()
             }
             TokenKind::Punctuator(Punctuator::Neg) => {
                 cursor.next()?.expect("~ token vanished"); // Consume the token.
-                Ok(node::UnaryOp::new(UnaryOp::Tilde, self.parse(cursor)?).into())
+                node::UnaryOp::new(UnaryOp::Tilde, self.parse(cursor)?).into()
             }
             TokenKind::Punctuator(Punctuator::Not) => {
                 cursor.next()?.expect("! token vanished"); // Consume the token.
-                Ok(node::UnaryOp::new(UnaryOp::Not, self.parse(cursor)?).into())
+                node::UnaryOp::new(UnaryOp::Not, self.parse(cursor)?).into()
             }
-            _ => UpdateExpression::new(self.allow_yield, self.allow_await).parse(cursor),
-        }
+            _ => return UpdateExpression::new(self.allow_yield, self.allow_await).parse(cursor),
+        };
+        Ok(cursor.spanned(node, token_start))
     }
 }
@@ -56,22 +56,25 @@ where
         let _timer = BoaProfiler::global().start_event("UpdateExpression", "Parsing");
 
         let tok = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?;
+        let start = tok.span().start();
         match tok.kind() {
             TokenKind::Punctuator(Punctuator::Inc) => {
                 cursor.next()?.expect("Punctuator::Inc token disappeared");
-                return Ok(node::UnaryOp::new(
+                let node = node::UnaryOp::new(
                     UnaryOp::IncrementPre,
                     UnaryExpression::new(self.allow_yield, self.allow_await).parse(cursor)?,
                 )
-                .into());
+                .into();
+                return Ok(cursor.spanned(node, start));
             }
             TokenKind::Punctuator(Punctuator::Dec) => {
                 cursor.next()?.expect("Punctuator::Dec token disappeared");
-                return Ok(node::UnaryOp::new(
+                let node = node::UnaryOp::new(
                     UnaryOp::DecrementPre,
                     UnaryExpression::new(self.allow_yield, self.allow_await).parse(cursor)?,
                 )
-                .into());
+                .into();
+                return Ok(cursor.spanned(node, start));
             }
             _ => {}
         }
@@ -81,11 +84,17 @@ where
             match tok.kind() {
                 TokenKind::Punctuator(Punctuator::Inc) => {
                     cursor.next()?.expect("Punctuator::Inc token disappeared");
-                    return Ok(node::UnaryOp::new(UnaryOp::IncrementPost, lhs).into());
+                    return Ok(cursor.spanned(
+                        node::UnaryOp::new(UnaryOp::IncrementPost, lhs).into(),
+                        start,
+                    ));
                 }
                 TokenKind::Punctuator(Punctuator::Dec) => {
                     cursor.next()?.expect("Punctuator::Dec token disappeared");
-                    return Ok(node::UnaryOp::new(UnaryOp::DecrementPost, lhs).into());
+                    return Ok(cursor.spanned(
+                        node::UnaryOp::new(UnaryOp::DecrementPost, lhs).into(),
+                        start,
+                    ));
                 }
                 _ => {}
             }
@@ -89,6 +89,7 @@ where
         loop {
             let mut rest_param = false;
 
+            let start = cursor.node_start()?;
             let next_param = match cursor.peek(0)? {
                 Some(tok) if tok.kind() == &TokenKind::Punctuator(Punctuator::Spread) => {
                     rest_param = true;
@@ -100,12 +101,12 @@ where
             if next_param.is_rest_param() || next_param.init().is_some() {
                 is_simple = false;
             }
-            if parameter_names.contains(next_param.name()) {
+            if parameter_names.contains(&next_param.name()) {
                 has_duplicates = true;
             }
-            parameter_names.insert(Box::from(next_param.name()));
+            parameter_names.insert(next_param.name());
 
-            params.push(next_param);
+            params.push(cursor.spanned(next_param, start));
 
             if cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.kind()
                 == &TokenKind::Punctuator(Punctuator::CloseParen)
@@ -305,7 +306,7 @@ where
                 TokenKind::Punctuator(Punctuator::CloseBlock) => {
                     return Ok(Vec::new().into());
                 }
-                TokenKind::StringLiteral(string) if string.as_ref() == "use strict" => {
+                TokenKind::StringLiteral(string) if *string == "use strict" => {
                     cursor.set_strict_mode(true);
                     strict = true;
                 }
@@ -313,6 +314,7 @@ where
             }
         }
 
+        let break_targets = cursor.take_break_targets();
         let statement_list = StatementList::new(
             self.allow_yield,
             self.allow_await,
@@ -324,6 +326,7 @@ where
 
         // Reset strict mode back to the global scope.
         cursor.set_strict_mode(global_strict_mode);
+        cursor.set_break_targets(break_targets);
 
         let mut statement_list = statement_list?;
         statement_list.set_strict(strict);
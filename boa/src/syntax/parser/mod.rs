@@ -1,6 +1,7 @@
 //! Boa parser implementation.
 
 mod cursor;
+pub mod diagnostic;
 pub mod error;
 mod expression;
 mod function;
@@ -8,7 +9,10 @@ mod statement;
 #[cfg(test)]
 mod tests;
 
-pub use self::error::{ParseError, ParseResult};
+pub use self::{
+    diagnostic::{Diagnostic, Severity, Suggestion},
+    error::{ParseError, ParseResult},
+};
 use crate::syntax::{ast::node::StatementList, lexer::TokenKind};
 
 use cursor::Cursor;
@@ -104,6 +108,42 @@ impl<R> Parser<R> {
     {
         Script.parse(&mut self.cursor)
     }
+
+    /// Parses the full input, preserving the comments of the source code.
+    ///
+    /// The comments are attached to the nodes of the statement lists of the AST, see
+    /// [`StatementList::comments_of`]. The leading comments of a statement are the ones before it,
+    /// and its trailing comments the ones after it on the same line. Comments after the last
+    /// statement of a list are trailing comments of that statement. Within a statement, comments
+    /// are attached to the outermost node which starts right after them, such as a property
+    /// definition or a function expression, or which ends right before them on the same line.
+    pub fn parse_all_with_comments(&mut self) -> Result<StatementList, ParseError>
+    where
+        R: Read,
+    {
+        self.cursor.preserve_comments();
+        Script.parse(&mut self.cursor)
+    }
+
+    /// Parses the full input, recovering from errors at statement boundaries.
+    ///
+    /// Statements that fail to parse are left out of the returned AST, and a diagnostic is
+    /// reported for each of them. If no diagnostics are returned, the AST is the same that
+    /// [`Parser::parse_all`] would return.
+    pub fn parse_all_with_diagnostics(&mut self) -> (StatementList, Vec<Diagnostic>)
+    where
+        R: Read,
+    {
+        self.cursor.enable_recovery();
+        let statement_list = match Script.parse(&mut self.cursor) {
+            Ok(statement_list) => statement_list,
+            Err(e) => {
+                let _ = self.cursor.recover(e);
+                StatementList::from(Vec::new())
+            }
+        };
+        (statement_list, self.cursor.take_diagnostics())
+    }
 }
 
 /// Parses a full script.
@@ -126,7 +166,7 @@ where
             Some(tok) => {
                 let mut strict = false;
                 match tok.kind() {
-                    TokenKind::StringLiteral(string) if string.as_ref() == "use strict" => {
+                    TokenKind::StringLiteral(string) if *string == "use strict" => {
                         cursor.set_strict_mode(true);
                         strict = true;
                     }
@@ -14,6 +14,7 @@ use super::LabelIdentifier;
 
 use crate::syntax::lexer::TokenKind;
 use crate::{
+    interner::Sym,
     syntax::{
         ast::{node::Break, Keyword, Punctuator},
         parser::{
@@ -62,7 +63,10 @@ where
 
     fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("BreakStatement", "Parsing");
-        cursor.expect(Keyword::Break, "break statement")?;
+        let position = cursor
+            .expect(Keyword::Break, "break statement")?
+            .span()
+            .start();
 
         let label = if let SemicolonResult::Found(tok) = cursor.peek_semicolon()? {
             match tok {
@@ -72,14 +76,27 @@ where
                 _ => {}
             }
 
+            // Early Error: It is a Syntax Error if the statement is not nested in a loop or a
+            // `switch` statement.
+            // https://tc39.es/ecma262/#sec-break-statement-static-semantics-early-errors
+            if !cursor.has_break_target(None) {
+                return Err(ParseError::general("illegal break statement", position));
+            }
+
             None
         } else {
             let label = LabelIdentifier::new(self.allow_yield, self.allow_await).parse(cursor)?;
+            // Early Error: It is a Syntax Error if the label is not the one of an enclosing
+            // statement.
+            // https://tc39.es/ecma262/#sec-static-semantics-containsundefinedbreaktarget
+            if !cursor.has_break_target(Some(label)) {
+                return Err(ParseError::general("undefined break target", position));
+            }
             cursor.expect_semicolon("break statement")?;
 
             Some(label)
         };
 
-        Ok(Break::new::<_, Box<str>>(label))
+        Ok(Break::new::<_, Sym>(label))
     }
 }
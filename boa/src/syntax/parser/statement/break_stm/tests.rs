@@ -1,20 +1,24 @@
+use crate::interner::Sym;
 use crate::syntax::{
     ast::{
         node::{Block, Break, Node, WhileLoop},
         Const,
     },
-    parser::tests::check_parser,
+    parser::tests::{check_invalid, check_parser},
 };
 
+/// Creates a `while (true)` loop with the given label and body.
+fn labelled_loop(label: &str, body: Block) -> Node {
+    let mut while_loop = WhileLoop::new(Const::from(true), body);
+    while_loop.set_label(label.into());
+    while_loop.into()
+}
+
 #[test]
 fn inline() {
     check_parser(
         "while (true) break;",
-        vec![WhileLoop::new(
-            Const::from(true),
-            Node::Break(Break::new::<_, Box<str>>(None)),
-        )
-        .into()],
+        vec![WhileLoop::new(Const::from(true), Node::Break(Break::new::<_, Sym>(None))).into()],
     );
 }
 
@@ -23,7 +27,7 @@ fn new_line() {
     check_parser(
         "while (true)
             break;",
-        vec![WhileLoop::new(Const::from(true), Break::new::<_, Box<str>>(None)).into()],
+        vec![WhileLoop::new(Const::from(true), Break::new::<_, Sym>(None)).into()],
     );
 }
 
@@ -33,7 +37,7 @@ fn inline_block_semicolon_insertion() {
         "while (true) {break}",
         vec![WhileLoop::new(
             Const::from(true),
-            Block::from(vec![Break::new::<_, Box<str>>(None).into()]),
+            Block::from(vec![Break::new::<_, Sym>(None).into()]),
         )
         .into()],
     );
@@ -42,14 +46,13 @@ fn inline_block_semicolon_insertion() {
 #[test]
 fn new_line_semicolon_insertion() {
     check_parser(
-        "while (true) {
+        "test: while (true) {
             break test
         }",
-        vec![WhileLoop::new(
-            Const::from(true),
+        vec![labelled_loop(
+            "test",
             Block::from(vec![Break::new("test").into()]),
-        )
-        .into()],
+        )],
     );
 }
 
@@ -59,7 +62,7 @@ fn inline_block() {
         "while (true) {break;}",
         vec![WhileLoop::new(
             Const::from(true),
-            Block::from(vec![Break::new::<_, Box<str>>(None).into()]),
+            Block::from(vec![Break::new::<_, Sym>(None).into()]),
         )
         .into()],
     );
@@ -68,39 +71,36 @@ fn inline_block() {
 #[test]
 fn new_line_block() {
     check_parser(
-        "while (true) {
+        "test: while (true) {
             break test;
         }",
-        vec![WhileLoop::new(
-            Const::from(true),
+        vec![labelled_loop(
+            "test",
             Block::from(vec![Break::new("test").into()]),
-        )
-        .into()],
+        )],
     );
 }
 
 #[test]
 fn reserved_label() {
     check_parser(
-        "while (true) {
+        "await: while (true) {
             break await;
         }",
-        vec![WhileLoop::new(
-            Const::from(true),
+        vec![labelled_loop(
+            "await",
             Block::from(vec![Break::new("await").into()]),
-        )
-        .into()],
+        )],
     );
 
     check_parser(
-        "while (true) {
+        "yield: while (true) {
             break yield;
         }",
-        vec![WhileLoop::new(
-            Const::from(true),
+        vec![labelled_loop(
+            "yield",
             Block::from(vec![Break::new("yield").into()]),
-        )
-        .into()],
+        )],
     );
 }
 
@@ -112,7 +112,7 @@ fn new_line_block_empty() {
         }",
         vec![WhileLoop::new(
             Const::from(true),
-            Block::from(vec![Break::new::<_, Box<str>>(None).into()]),
+            Block::from(vec![Break::new::<_, Sym>(None).into()]),
         )
         .into()],
     );
@@ -126,8 +126,29 @@ fn new_line_block_empty_semicolon_insertion() {
         }",
         vec![WhileLoop::new(
             Const::from(true),
-            Block::from(vec![Break::new::<_, Box<str>>(None).into()]),
+            Block::from(vec![Break::new::<_, Sym>(None).into()]),
         )
         .into()],
     );
 }
+
+#[test]
+fn labelled_block() {
+    let mut block = Block::from(vec![Break::new("test").into()]);
+    block.set_label("test".into());
+    check_parser("test: { break test; }", vec![block.into()]);
+}
+
+#[test]
+fn illegal_break() {
+    check_invalid("break;");
+    check_invalid("if (true) { break; }");
+    check_invalid("while (true) { (function () { break; })(); }");
+}
+
+#[test]
+fn undefined_label() {
+    check_invalid("while (true) { break test; }");
+    check_invalid("test: function f() { break test; }");
+    check_invalid("test: while (true) { (function () { break test; })(); }");
+}
@@ -12,6 +12,7 @@ mod tests;
 
 use crate::syntax::lexer::TokenKind;
 use crate::{
+    interner::Sym,
     syntax::{
         ast::{node::Continue, Keyword, Punctuator},
         parser::{
@@ -79,6 +80,6 @@ where
             Some(label)
         };
 
-        Ok(Continue::new::<_, Box<str>>(label))
+        Ok(Continue::new::<_, Sym>(label))
     }
 }
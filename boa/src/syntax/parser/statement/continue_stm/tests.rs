@@ -1,3 +1,4 @@
+use crate::interner::Sym;
 use crate::syntax::{
     ast::{
         node::{Block, Continue, WhileLoop},
@@ -10,7 +11,7 @@ use crate::syntax::{
 fn inline() {
     check_parser(
         "while (true) continue;",
-        vec![WhileLoop::new(Const::from(true), Continue::new::<_, Box<str>>(None)).into()],
+        vec![WhileLoop::new(Const::from(true), Continue::new::<_, Sym>(None)).into()],
     );
 }
 
@@ -19,7 +20,7 @@ fn new_line() {
     check_parser(
         "while (true)
             continue;",
-        vec![WhileLoop::new(Const::from(true), Continue::new::<_, Box<str>>(None)).into()],
+        vec![WhileLoop::new(Const::from(true), Continue::new::<_, Sym>(None)).into()],
     );
 }
 
@@ -29,7 +30,7 @@ fn inline_block_semicolon_insertion() {
         "while (true) {continue}",
         vec![WhileLoop::new(
             Const::from(true),
-            Block::from(vec![Continue::new::<_, Box<str>>(None).into()]),
+            Block::from(vec![Continue::new::<_, Sym>(None).into()]),
         )
         .into()],
     );
@@ -55,7 +56,7 @@ fn inline_block() {
         "while (true) {continue;}",
         vec![WhileLoop::new(
             Const::from(true),
-            Block::from(vec![Continue::new::<_, Box<str>>(None).into()]),
+            Block::from(vec![Continue::new::<_, Sym>(None).into()]),
         )
         .into()],
     );
@@ -108,7 +109,7 @@ fn new_line_block_empty() {
         }",
         vec![WhileLoop::new(
             Const::from(true),
-            Block::from(vec![Continue::new::<_, Box<str>>(None).into()]),
+            Block::from(vec![Continue::new::<_, Sym>(None).into()]),
         )
         .into()],
     );
@@ -122,7 +123,7 @@ fn new_line_block_empty_semicolon_insertion() {
         }",
         vec![WhileLoop::new(
             Const::from(true),
-            Block::from(vec![Continue::new::<_, Box<str>>(None).into()]),
+            Block::from(vec![Continue::new::<_, Sym>(None).into()]),
         )
         .into()],
     );
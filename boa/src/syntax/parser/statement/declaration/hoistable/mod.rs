@@ -17,6 +17,7 @@ pub(in crate::syntax::parser) use function_decl::FunctionDeclaration;
 use generator_decl::GeneratorDeclaration;
 
 use crate::{
+    interner::Sym,
     syntax::{
         ast::node::{FormalParameter, StatementList},
         ast::{Keyword, Node, Punctuator},
@@ -110,7 +111,7 @@ trait CallableDeclaration {
 fn parse_callable_declaration<R: Read, C: CallableDeclaration>(
     c: &C,
     cursor: &mut Cursor<R>,
-) -> Result<(Box<str>, Box<[FormalParameter]>, StatementList), ParseError> {
+) -> Result<(Sym, Box<[FormalParameter]>, StatementList), ParseError> {
     let next_token = cursor.peek(0)?;
     let name = if let Some(token) = next_token {
         match token.kind() {
@@ -179,7 +180,7 @@ fn parse_callable_declaration<R: Read, C: CallableDeclaration>(
     {
         let lexically_declared_names = body.lexically_declared_names();
         for param in params.parameters.as_ref() {
-            if lexically_declared_names.contains(param.name()) {
+            if lexically_declared_names.contains(&param.name()) {
                 return Err(ParseError::lex(LexError::Syntax(
                     format!("Redeclaration of formal parameter `{}`", param.name()).into(),
                     match cursor.peek(0)? {
@@ -154,8 +154,10 @@ where
         let mut const_decls = Vec::new();
 
         loop {
+            let start = cursor.node_start()?;
             let decl = LexicalBinding::new(self.allow_in, self.allow_yield, self.allow_await)
                 .parse(cursor)?;
+            let decl = cursor.spanned(decl, start);
 
             if self.is_const {
                 if self.const_init_required {
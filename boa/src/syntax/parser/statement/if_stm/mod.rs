@@ -73,9 +73,11 @@ where
         {
             // FunctionDeclarations in IfStatement Statement Clauses
             // https://tc39.es/ecma262/#sec-functiondeclarations-in-ifstatement-statement-clauses
-            FunctionDeclaration::new(self.allow_yield, self.allow_await, false)
+            let start = cursor.node_start()?;
+            let function = FunctionDeclaration::new(self.allow_yield, self.allow_await, false)
                 .parse(cursor)?
-                .into()
+                .into();
+            cursor.spanned(function, start)
         } else {
             let node = Statement::new(self.allow_yield, self.allow_await, self.allow_return)
                 .parse(cursor)?;
@@ -97,11 +99,10 @@ where
             {
                 // FunctionDeclarations in IfStatement Statement Clauses
                 // https://tc39.es/ecma262/#sec-functiondeclarations-in-ifstatement-statement-clauses
-                Some(
-                    FunctionDeclaration::new(self.allow_yield, self.allow_await, false)
-                        .parse(cursor)?
-                        .into(),
-                )
+                let function = FunctionDeclaration::new(self.allow_yield, self.allow_await, false)
+                    .parse(cursor)?
+                    .into();
+                Some(cursor.spanned(function, position))
             } else {
                 let node = Statement::new(self.allow_yield, self.allow_await, self.allow_return)
                     .parse(cursor)?;
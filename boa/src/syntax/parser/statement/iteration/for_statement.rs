@@ -12,7 +12,7 @@ use crate::{
     syntax::{
         ast::{
             node::{ForInLoop, ForLoop, ForOfLoop, Node},
-            Const, Keyword, Punctuator,
+            Keyword, Punctuator,
         },
         parser::{
             expression::Expression,
@@ -72,17 +72,20 @@ where
         cursor.expect(Keyword::For, "for statement")?;
         cursor.expect(Punctuator::OpenParen, "for statement")?;
 
+        let start = cursor.node_start()?;
         let init = match cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.kind() {
             TokenKind::Keyword(Keyword::Var) => {
                 let _ = cursor.next()?;
-                Some(
+                let declarations =
                     VariableDeclarationList::new(false, self.allow_yield, self.allow_await)
                         .parse(cursor)
-                        .map(Node::from)?,
-                )
+                        .map(Node::from)?;
+                Some(cursor.spanned(declarations, start))
             }
             TokenKind::Keyword(Keyword::Let) | TokenKind::Keyword(Keyword::Const) => {
-                Some(Declaration::new(self.allow_yield, self.allow_await, false).parse(cursor)?)
+                let declarations =
+                    Declaration::new(self.allow_yield, self.allow_await, false).parse(cursor)?;
+                Some(cursor.spanned(declarations, start))
             }
             TokenKind::Punctuator(Punctuator::Semicolon) => None,
             _ => Some(Expression::new(false, self.allow_yield, self.allow_await).parse(cursor)?),
@@ -99,8 +102,11 @@ where
                     .span()
                     .end();
 
+                cursor.push_break_target(None);
                 let body = Statement::new(self.allow_yield, self.allow_await, self.allow_return)
-                    .parse(cursor)?;
+                    .parse(cursor);
+                cursor.pop_break_target();
+                let body = body?;
 
                 // Early Error: It is a Syntax Error if IsLabelledFunction(the first Statement) is true.
                 if let Node::FunctionDecl(_) = body {
@@ -119,8 +125,11 @@ where
                     .span()
                     .end();
 
+                cursor.push_break_target(None);
                 let body = Statement::new(self.allow_yield, self.allow_await, self.allow_return)
-                    .parse(cursor)?;
+                    .parse(cursor);
+                cursor.pop_break_target();
+                let body = body?;
 
                 // Early Error: It is a Syntax Error if IsLabelledFunction(the first Statement) is true.
                 if let Node::FunctionDecl(_) = body {
@@ -135,11 +144,11 @@ where
         cursor.expect(Punctuator::Semicolon, "for statement")?;
 
         let cond = if cursor.next_if(Punctuator::Semicolon)?.is_some() {
-            Const::from(true).into()
+            None
         } else {
             let step = Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
             cursor.expect(Punctuator::Semicolon, "for statement")?;
-            step
+            Some(step)
         };
 
         let step = if cursor.next_if(Punctuator::CloseParen)?.is_some() {
@@ -155,8 +164,11 @@ where
 
         let position = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.span().start();
 
+        cursor.push_break_target(None);
         let body =
-            Statement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)?;
+            Statement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor);
+        cursor.pop_break_target();
+        let body = body?;
 
         // Early Error: It is a Syntax Error if IsLabelledFunction(the first Statement) is true.
         if let Node::FunctionDecl(_) = body {
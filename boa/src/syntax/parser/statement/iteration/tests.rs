@@ -1,3 +1,4 @@
+use crate::interner::Sym;
 use crate::syntax::{
     ast::{
         node::{
@@ -122,7 +123,7 @@ fn while_spaces() {
         break;
 
         "#,
-        vec![WhileLoop::new(Const::from(true), Break::new::<_, Box<str>>(None)).into()],
+        vec![WhileLoop::new(Const::from(true), Break::new::<_, Sym>(None)).into()],
     );
 }
 
@@ -144,7 +145,7 @@ fn do_while_spaces() {
 
         "#,
         vec![DoWhileLoop::new(
-            Block::from(vec![Break::new::<Option<Box<str>>, Box<str>>(None).into()]),
+            Block::from(vec![Break::new::<Option<Sym>, Sym>(None).into()]),
             Const::Bool(true),
         )
         .into()],
@@ -65,8 +65,11 @@ where
             .span()
             .end();
 
+        cursor.push_break_target(None);
         let body =
-            Statement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)?;
+            Statement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor);
+        cursor.pop_break_target();
+        let body = body?;
 
         // Early Error: It is a Syntax Error if IsLabelledFunction(Statement) is true.
         if let Node::FunctionDecl(_) = body {
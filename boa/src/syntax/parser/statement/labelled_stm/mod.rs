@@ -1,6 +1,7 @@
 use std::io::Read;
 
 use crate::{
+    interner::Sym,
     syntax::{
         ast::{Keyword, Node, Punctuator},
         lexer::TokenKind,
@@ -72,11 +73,18 @@ where
                 ))
             }
             TokenKind::Keyword(Keyword::Function) => {
-                FunctionDeclaration::new(self.allow_yield, self.allow_await, false)
-                .parse(cursor)?
-                .into()
+                let start = next_token.span().start();
+                let function = FunctionDeclaration::new(self.allow_yield, self.allow_await, false)
+                    .parse(cursor)?
+                    .into();
+                cursor.spanned(function, start)
+            }
+            _ => {
+                cursor.push_break_target(Some(name));
+                let statement = Statement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor);
+                cursor.pop_break_target();
+                statement?
             }
-            _ => Statement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)?
         };
 
         set_label_for_node(&mut node, name);
@@ -84,13 +92,14 @@ where
     }
 }
 
-fn set_label_for_node(node: &mut Node, name: Box<str>) {
+fn set_label_for_node(node: &mut Node, name: Sym) {
     match node {
         Node::ForLoop(ref mut for_loop) => for_loop.set_label(name),
         Node::ForOfLoop(ref mut for_of_loop) => for_of_loop.set_label(name),
         Node::ForInLoop(ref mut for_in_loop) => for_in_loop.set_label(name),
         Node::DoWhileLoop(ref mut do_while_loop) => do_while_loop.set_label(name),
         Node::WhileLoop(ref mut while_loop) => while_loop.set_label(name),
+        Node::Block(ref mut block) => block.set_label(name),
         _ => (),
     }
 }
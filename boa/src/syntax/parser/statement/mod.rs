@@ -45,15 +45,16 @@ use crate::syntax::{
 use super::{AllowAwait, AllowIn, AllowReturn, AllowYield, Cursor, ParseError, TokenParser};
 
 use crate::{
+    interner::Sym,
     syntax::{
         ast::{
             node::{
                 self,
                 declaration::{BindingPatternTypeArray, BindingPatternTypeObject},
             },
-            Keyword, Node, Punctuator,
+            Comments, Keyword, Node, Punctuator, Span,
         },
-        lexer::{Error as LexError, InputElement, Position, TokenKind},
+        lexer::{Error as LexError, InputElement, Token, TokenKind},
         parser::expression::await_expr::AwaitExpression,
     },
     BoaProfiler,
@@ -121,12 +122,30 @@ where
     fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("Statement", "Parsing");
         // TODO: add BreakableStatement and divide Whiles, fors and so on to another place.
+
+        // `yield` and `await` are label identifiers where they are not keywords, which
+        // `LabelledStatement` checks.
+        if matches!(
+            cursor.peek(0)?.map(Token::kind),
+            Some(TokenKind::Keyword(Keyword::Yield | Keyword::Await))
+        ) && matches!(
+            cursor.peek(1)?.map(Token::kind),
+            Some(TokenKind::Punctuator(Punctuator::Colon))
+        ) {
+            return LabelledStatement::new(self.allow_yield, self.allow_await, self.allow_return)
+                .parse(cursor);
+        }
+
         let tok = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?;
+        let start = tok.span().start();
 
-        match tok.kind() {
+        let statement = match tok.kind() {
             TokenKind::Keyword(Keyword::Await) => AwaitExpression::new(self.allow_yield)
                 .parse(cursor)
-                .map(Node::from),
+                .and_then(|await_expr| {
+                    cursor.expect_semicolon("await expression")?;
+                    Ok(Node::from(await_expr))
+                }),
             TokenKind::Keyword(Keyword::If) => {
                 IfStatement::new(self.allow_yield, self.allow_await, self.allow_return)
                     .parse(cursor)
@@ -206,6 +225,7 @@ where
                         TokenKind::Punctuator(Punctuator::Colon)
                     )
                 {
+                    // The labelled statement has its own span already, without the label.
                     return LabelledStatement::new(
                         self.allow_yield,
                         self.allow_await,
@@ -215,11 +235,13 @@ where
                     .map(Node::from);
                 }
 
-                ExpressionStatement::new(self.allow_yield, self.allow_await).parse(cursor)
+                // The expression is the statement, and it has its own span already.
+                return ExpressionStatement::new(self.allow_yield, self.allow_await).parse(cursor);
             }
 
-            _ => ExpressionStatement::new(self.allow_yield, self.allow_await).parse(cursor),
-        }
+            _ => return ExpressionStatement::new(self.allow_yield, self.allow_await).parse(cursor),
+        }?;
+        Ok(cursor.spanned(statement, start))
     }
 }
 
@@ -281,127 +303,167 @@ where
     fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("StatementList", "Parsing");
         let mut items = Vec::new();
+        let mut spans = Vec::new();
+        let mut comments = Vec::new();
+        let records = cursor.records_start();
 
+        // The comments after the last item, before the end of the list.
+        let dangling;
         loop {
-            match cursor.peek(0)? {
-                Some(token) if self.break_nodes.contains(token.kind()) => break,
-                None => break,
-                _ => {}
-            }
+            let (start, leading) = match cursor.peek(0) {
+                Ok(Some(token)) if self.break_nodes.contains(token.kind()) => {
+                    dangling = token.leading_comments().to_vec();
+                    break;
+                }
+                Ok(None) => {
+                    dangling = cursor.take_pending_comments();
+                    break;
+                }
+                Ok(Some(token)) => (token.span().start(), token.leading_comments().to_vec()),
+                Err(e) => {
+                    cursor.recover(e)?;
+                    dangling = Vec::new();
+                    break;
+                }
+            };
 
+            let item_records = cursor.records_start();
             let item = StatementListItem::new(
                 self.allow_yield,
                 self.allow_await,
                 self.allow_return,
                 self.in_block,
             )
-            .parse(cursor)?;
-            items.push(item);
+            .parse(cursor);
+            match item {
+                Ok(item) => {
+                    items.push(item);
+                    spans.push(Span::new(start, cursor.last_token_end()));
+                }
+                Err(e) => {
+                    // Leave the statement out, and continue with the next one.
+                    cursor.recover(e)?;
+                    cursor.drop_records(item_records);
+                    if !cursor.synchronize(start, self.break_nodes) {
+                        dangling = Vec::new();
+                        break;
+                    }
+                    continue;
+                }
+            }
 
-            // move the cursor forward for any consecutive semicolon.
-            while cursor.next_if(Punctuator::Semicolon)?.is_some() {}
+            // move the cursor forward for any consecutive semicolon, keeping their comments.
+            let mut trailing = cursor.take_trailing_comments();
+            while let Some(semicolon) = cursor.next_if(Punctuator::Semicolon)? {
+                trailing.extend_from_slice(semicolon.leading_comments());
+                trailing.append(&mut cursor.take_trailing_comments());
+            }
+            comments.push(Comments::new(leading, trailing));
         }
 
-        // Handle any redeclarations
-        // https://tc39.es/ecma262/#sec-block-static-semantics-early-errors
-        {
-            let mut lexically_declared_names: HashSet<&str> = HashSet::new();
-            let mut var_declared_names: HashSet<&str> = HashSet::new();
-
-            // TODO: Use more helpful positions in errors when spans are added to Nodes
-            for item in &items {
-                match item {
-                    Node::LetDeclList(decl_list) | Node::ConstDeclList(decl_list) => {
-                        for decl in decl_list.as_ref() {
-                            // if name in VarDeclaredNames or can't be added to
-                            // LexicallyDeclaredNames, raise an error
-                            match decl {
-                                node::Declaration::Identifier { ident, .. } => {
-                                    if var_declared_names.contains(ident.as_ref())
-                                        || !lexically_declared_names.insert(ident.as_ref())
-                                    {
-                                        return Err(ParseError::lex(LexError::Syntax(
-                                            format!(
-                                                "Redeclaration of variable `{}`",
-                                                ident.as_ref()
-                                            )
-                                            .into(),
-                                            match cursor.peek(0)? {
-                                                Some(token) => token.span().end(),
-                                                None => Position::new(1, 1),
-                                            },
-                                        )));
-                                    }
-                                }
-                                node::Declaration::Pattern(p) => {
-                                    for ident in p.idents() {
-                                        if var_declared_names.contains(ident)
-                                            || !lexically_declared_names.insert(ident.as_ref())
-                                        {
-                                            return Err(ParseError::lex(LexError::Syntax(
-                                                format!("Redeclaration of variable `{}`", ident)
-                                                    .into(),
-                                                match cursor.peek(0)? {
-                                                    Some(token) => token.span().end(),
-                                                    None => Position::new(1, 1),
-                                                },
-                                            )));
-                                        }
-                                    }
+        if let Some(last) = comments.last_mut() {
+            if !dangling.is_empty() {
+                let trailing = [last.trailing(), &dangling].concat();
+                *last = Comments::new(last.leading(), trailing);
+            }
+        }
+
+        if let Err(e) = check_redeclarations(&items, &spans) {
+            cursor.recover(e)?;
+        }
+
+        // Hoisting reorders the items, so their spans and comments have to be reordered along with
+        // them.
+        let mut items: Vec<_> = items
+            .into_iter()
+            .zip(spans.into_iter().zip(comments))
+            .collect();
+        items.sort_by(|(a, _), (b, _)| Node::hoistable_order(a, b));
+        let (items, (spans, comments)): (Vec<_>, (Vec<_>, Vec<_>)) = items.into_iter().unzip();
+
+        let statement_list = node::StatementList::from(items);
+        let side_table = cursor.side_table(statement_list.items(), records, comments);
+        Ok(statement_list.with_source(spans.into(), side_table))
+    }
+}
+
+/// Checks the statement list items for redeclarations of lexically declared names.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-block-static-semantics-early-errors
+fn check_redeclarations(items: &[Node], spans: &[Span]) -> Result<(), ParseError> {
+    let mut lexically_declared_names: HashSet<Sym> = HashSet::new();
+    let mut var_declared_names: HashSet<Sym> = HashSet::new();
+
+    for (item, span) in items.iter().zip(spans) {
+        match item {
+            Node::LetDeclList(decl_list) | Node::ConstDeclList(decl_list) => {
+                for decl in decl_list.as_ref() {
+                    // if name in VarDeclaredNames or can't be added to
+                    // LexicallyDeclaredNames, raise an error
+                    match decl {
+                        node::Declaration::Identifier { ident, .. } => {
+                            if var_declared_names.contains(&ident.sym())
+                                || !lexically_declared_names.insert(ident.sym())
+                            {
+                                return Err(ParseError::lex(LexError::Syntax(
+                                    format!("Redeclaration of variable `{}`", ident).into(),
+                                    span.start(),
+                                )));
+                            }
+                        }
+                        node::Declaration::Pattern(p) => {
+                            for ident in p.idents() {
+                                if var_declared_names.contains(&ident)
+                                    || !lexically_declared_names.insert(ident)
+                                {
+                                    return Err(ParseError::lex(LexError::Syntax(
+                                        format!("Redeclaration of variable `{}`", ident).into(),
+                                        span.start(),
+                                    )));
                                 }
                             }
                         }
                     }
-                    Node::VarDeclList(decl_list) => {
-                        for decl in decl_list.as_ref() {
-                            match decl {
-                                node::Declaration::Identifier { ident, .. } => {
-                                    // if name in LexicallyDeclaredNames, raise an error
-                                    if lexically_declared_names.contains(ident.as_ref()) {
-                                        return Err(ParseError::lex(LexError::Syntax(
-                                            format!(
-                                                "Redeclaration of variable `{}`",
-                                                ident.as_ref()
-                                            )
-                                            .into(),
-                                            match cursor.peek(0)? {
-                                                Some(token) => token.span().end(),
-                                                None => Position::new(1, 1),
-                                            },
-                                        )));
-                                    }
-                                    // otherwise, add to VarDeclaredNames
-                                    var_declared_names.insert(ident.as_ref());
-                                }
-                                node::Declaration::Pattern(p) => {
-                                    for ident in p.idents() {
-                                        // if name in LexicallyDeclaredNames, raise an error
-                                        if lexically_declared_names.contains(ident) {
-                                            return Err(ParseError::lex(LexError::Syntax(
-                                                format!("Redeclaration of variable `{}`", ident)
-                                                    .into(),
-                                                match cursor.peek(0)? {
-                                                    Some(token) => token.span().end(),
-                                                    None => Position::new(1, 1),
-                                                },
-                                            )));
-                                        }
-                                        // otherwise, add to VarDeclaredNames
-                                        var_declared_names.insert(ident.as_ref());
-                                    }
+                }
+            }
+            Node::VarDeclList(decl_list) => {
+                for decl in decl_list.as_ref() {
+                    match decl {
+                        node::Declaration::Identifier { ident, .. } => {
+                            // if name in LexicallyDeclaredNames, raise an error
+                            if lexically_declared_names.contains(&ident.sym()) {
+                                return Err(ParseError::lex(LexError::Syntax(
+                                    format!("Redeclaration of variable `{}`", ident).into(),
+                                    span.start(),
+                                )));
+                            }
+                            // otherwise, add to VarDeclaredNames
+                            var_declared_names.insert(ident.sym());
+                        }
+                        node::Declaration::Pattern(p) => {
+                            for ident in p.idents() {
+                                // if name in LexicallyDeclaredNames, raise an error
+                                if lexically_declared_names.contains(&ident) {
+                                    return Err(ParseError::lex(LexError::Syntax(
+                                        format!("Redeclaration of variable `{}`", ident).into(),
+                                        span.start(),
+                                    )));
                                 }
+                                // otherwise, add to VarDeclaredNames
+                                var_declared_names.insert(ident);
                             }
                         }
                     }
-                    _ => (),
                 }
             }
+            _ => (),
         }
-
-        items.sort_by(Node::hoistable_order);
-
-        Ok(items.into())
     }
+
+    Ok(())
 }
 
 /// Statement list item parsing
@@ -449,8 +511,9 @@ where
         let _timer = BoaProfiler::global().start_event("StatementListItem", "Parsing");
         let strict_mode = cursor.strict_mode();
         let tok = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?;
+        let start = tok.span().start();
 
-        match *tok.kind() {
+        let declaration = match *tok.kind() {
             TokenKind::Keyword(Keyword::Function) | TokenKind::Keyword(Keyword::Async) => {
                 if strict_mode && self.in_block {
                     return Err(ParseError::lex(LexError::Syntax(
@@ -461,12 +524,20 @@ where
                 Declaration::new(self.allow_yield, self.allow_await, true).parse(cursor)
             }
             TokenKind::Keyword(Keyword::Const) | TokenKind::Keyword(Keyword::Let) => {
-                Declaration::new(self.allow_yield, self.allow_await, true).parse(cursor)
+                // The semicolon is part of the declaration, like for a `var` statement.
+                Declaration::new(self.allow_yield, self.allow_await, true)
+                    .parse(cursor)
+                    .and_then(|declaration| {
+                        cursor.expect_semicolon("lexical declaration")?;
+                        Ok(declaration)
+                    })
             }
             _ => {
-                Statement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)
+                return Statement::new(self.allow_yield, self.allow_await, self.allow_return)
+                    .parse(cursor)
             }
-        }
+        }?;
+        Ok(cursor.spanned(declaration, start))
     }
 }
 
@@ -510,7 +581,7 @@ impl<R> TokenParser<R> for BindingIdentifier
 where
     R: Read,
 {
-    type Output = Box<str>;
+    type Output = Sym;
 
     /// Strict mode parsing as per <https://tc39.es/ecma262/#sec-identifiers-static-semantics-early-errors>.
     fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
@@ -519,7 +590,7 @@ where
         let next_token = cursor.next()?.ok_or(ParseError::AbruptEnd)?;
 
         match next_token.kind() {
-            TokenKind::Identifier(ref s) => Ok(s.clone()),
+            TokenKind::Identifier(ref s) => Ok(*s),
             TokenKind::Keyword(Keyword::Yield) if self.allow_yield.0 => {
                 // Early Error: It is a Syntax Error if this production has a [Yield] parameter and StringValue of Identifier is "yield".
                 Err(ParseError::general(
@@ -636,7 +707,7 @@ where
                 _ => BindingIdentifier::new(self.allow_yield, self.allow_await).parse(cursor)?,
             };
 
-            property_names.push(property_name.clone());
+            property_names.push(property_name);
 
             if let Some(peek_token) = cursor.peek(0)? {
                 match peek_token.kind() {
@@ -645,7 +716,7 @@ where
                             Initializer::new(self.allow_in, self.allow_yield, self.allow_await)
                                 .parse(cursor)?;
                         patterns.push(BindingPatternTypeObject::SingleName {
-                            ident: property_name.clone(),
+                            ident: property_name,
                             property_name,
                             default_init: Some(init),
                         });
@@ -678,9 +749,11 @@ where
                                                 patterns.push(
                                                     BindingPatternTypeObject::BindingPattern {
                                                         ident: property_name,
-                                                        pattern: DeclarationPattern::Object(
-                                                            DeclarationPatternObject::new(
-                                                                bindings, None,
+                                                        pattern: Box::new(
+                                                            DeclarationPattern::Object(
+                                                                DeclarationPatternObject::new(
+                                                                    bindings, None,
+                                                                ),
                                                             ),
                                                         ),
                                                         default_init: Some(init),
@@ -691,9 +764,11 @@ where
                                                 patterns.push(
                                                     BindingPatternTypeObject::BindingPattern {
                                                         ident: property_name,
-                                                        pattern: DeclarationPattern::Object(
-                                                            DeclarationPatternObject::new(
-                                                                bindings, None,
+                                                        pattern: Box::new(
+                                                            DeclarationPattern::Object(
+                                                                DeclarationPatternObject::new(
+                                                                    bindings, None,
+                                                                ),
                                                             ),
                                                         ),
                                                         default_init: None,
@@ -723,9 +798,11 @@ where
                                                 patterns.push(
                                                     BindingPatternTypeObject::BindingPattern {
                                                         ident: property_name,
-                                                        pattern: DeclarationPattern::Array(
-                                                            DeclarationPatternArray::new(
-                                                                bindings, None,
+                                                        pattern: Box::new(
+                                                            DeclarationPattern::Array(
+                                                                DeclarationPatternArray::new(
+                                                                    bindings, None,
+                                                                ),
                                                             ),
                                                         ),
                                                         default_init: Some(init),
@@ -736,9 +813,11 @@ where
                                                 patterns.push(
                                                     BindingPatternTypeObject::BindingPattern {
                                                         ident: property_name,
-                                                        pattern: DeclarationPattern::Array(
-                                                            DeclarationPatternArray::new(
-                                                                bindings, None,
+                                                        pattern: Box::new(
+                                                            DeclarationPattern::Array(
+                                                                DeclarationPatternArray::new(
+                                                                    bindings, None,
+                                                                ),
                                                             ),
                                                         ),
                                                         default_init: None,
@@ -789,7 +868,7 @@ where
                     }
                     _ => {
                         patterns.push(BindingPatternTypeObject::SingleName {
-                            ident: property_name.clone(),
+                            ident: property_name,
                             property_name,
                             default_init: None,
                         });
@@ -68,8 +68,11 @@ where
 
         cursor.expect(Punctuator::CloseParen, "switch statement")?;
 
-        let (cases, default) =
-            CaseBlock::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)?;
+        cursor.push_break_target(None);
+        let case_block =
+            CaseBlock::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor);
+        cursor.pop_break_target();
+        let (cases, default) = case_block?;
 
         Ok(Switch::new(condition, cases, default))
     }
@@ -134,7 +137,8 @@ where
                     )
                     .parse(cursor)?;
 
-                    cases.push(node::Case::new(cond, statement_list));
+                    let case = node::Case::new(cond, statement_list);
+                    cases.push(cursor.spanned(case, token.span().start()));
                 }
                 Some(token) if token.kind() == &TokenKind::Keyword(Keyword::Default) => {
                     if default.is_some() {
@@ -1,3 +1,4 @@
+use crate::interner::Sym;
 use crate::syntax::{
     ast::{
         node::{
@@ -170,7 +171,7 @@ fn check_seperated_switch() {
                                 vec![Node::from(Const::from(5))],
                             )
                             .into(),
-                            Break::new::<_, Box<str>>(None).into(),
+                            Break::new::<_, Sym>(None).into(),
                         ],
                     ),
                     Case::new(
@@ -181,7 +182,7 @@ fn check_seperated_switch() {
                                 vec![Node::from(Const::from(10))],
                             )
                             .into(),
-                            Break::new::<_, Box<str>>(None).into(),
+                            Break::new::<_, Sym>(None).into(),
                         ],
                     ),
                 ],
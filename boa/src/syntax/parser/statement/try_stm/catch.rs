@@ -59,8 +59,10 @@ where
         let _timer = BoaProfiler::global().start_event("Catch", "Parsing");
         cursor.expect(Keyword::Catch, "try statement")?;
         let catch_param = if cursor.next_if(Punctuator::OpenParen)?.is_some() {
+            let start = cursor.node_start()?;
             let catch_param =
                 CatchParameter::new(self.allow_yield, self.allow_await).parse(cursor)?;
+            let catch_param = cursor.spanned(catch_param, start);
 
             cursor.expect(Punctuator::CloseParen, "catch in try statement")?;
             Some(catch_param)
@@ -70,7 +72,7 @@ where
 
         let mut set = FxHashSet::default();
         let idents = match &catch_param {
-            Some(node::Declaration::Identifier { ident, .. }) => vec![ident.as_ref()],
+            Some(node::Declaration::Identifier { ident, .. }) => vec![ident.sym()],
             Some(node::Declaration::Pattern(p)) => p.idents(),
             _ => vec![],
         };
@@ -102,13 +104,13 @@ where
 
         for ident in set {
             // FIXME: pass correct position once #1295 lands
-            if lexically_declared_names.contains(ident) {
+            if lexically_declared_names.contains(&ident) {
                 return Err(ParseError::general(
                     "identifier redeclared",
                     Position::new(1, 1),
                 ));
             }
-            if var_declared_names.contains(ident) {
+            if var_declared_names.contains(&ident) {
                 return Err(ParseError::general(
                     "identifier redeclared",
                     Position::new(1, 1),
@@ -113,10 +113,11 @@ where
         let mut list = Vec::new();
 
         loop {
-            list.push(
+            let start = cursor.node_start()?;
+            let declaration =
                 VariableDeclaration::new(self.allow_in, self.allow_yield, self.allow_await)
-                    .parse(cursor)?,
-            );
+                    .parse(cursor)?;
+            list.push(cursor.spanned(declaration, start));
 
             match cursor.peek_semicolon()? {
                 SemicolonResult::NotFound(tk)
@@ -1,14 +1,18 @@
 //! Tests for the parser.
 
-use super::Parser;
-use crate::syntax::ast::{
-    node::{
-        field::GetConstField, ArrowFunctionDecl, Assign, BinOp, Call, Declaration, DeclarationList,
-        FormalParameter, FunctionDecl, Identifier, If, New, Node, Object, PropertyDefinition,
-        Return, StatementList, UnaryOp,
+use super::{Diagnostic, Parser, Severity};
+use crate::syntax::{
+    ast::{
+        node::{
+            field::GetConstField, ArrowFunctionDecl, Assign, BinOp, Call, Declaration,
+            DeclarationList, FormalParameter, FunctionDecl, Identifier, If, New, Node, Object,
+            PropertyDefinition, Return, StatementList, UnaryOp,
+        },
+        op::{self, CompOp, LogOp, NumOp},
+        side_table::Keyed,
+        Const, Position, Span,
     },
-    op::{self, CompOp, LogOp, NumOp},
-    Const,
+    codegen::{generate, Style},
 };
 
 /// Checks that the given JavaScript string gives the expected expression.
@@ -18,12 +22,23 @@ pub(super) fn check_parser<L>(js: &str, expr: L)
 where
     L: Into<Box<[Node]>>,
 {
-    assert_eq!(
-        Parser::new(js.as_bytes(), false)
+    let statement_list = Parser::new(js.as_bytes(), false)
+        .parse_all()
+        .expect("failed to parse");
+    assert_eq!(statement_list, StatementList::from(expr));
+    check_round_trip(&statement_list);
+}
+
+/// Checks that the code generated from the given statement list parses back to it, in all styles.
+#[track_caller]
+pub(super) fn check_round_trip(statement_list: &StatementList) {
+    for style in [Style::Pretty, Style::Minified] {
+        let code = generate(statement_list, style);
+        let reparsed = Parser::new(code.as_bytes(), false)
             .parse_all()
-            .expect("failed to parse"),
-        StatementList::from(expr)
-    );
+            .unwrap_or_else(|e| panic!("failed to parse generated code `{}`: {}", code, e));
+        assert_eq!(&reparsed, statement_list, "generated code: `{}`", code);
+    }
 }
 
 /// Checks that the given javascript string creates a parse error.
@@ -398,3 +413,380 @@ fn hashbang_use_strict_with_with_statement() {
 fn hashbang_comment() {
     check_parser(r"#!Comment Here", vec![]);
 }
+
+#[test]
+fn statement_spans() {
+    let statement_list = Parser::new("let a = 1;\nfoo(a)\n\nfunction foo(x) {}".as_bytes(), false)
+        .parse_all()
+        .expect("failed to parse");
+
+    // The function declaration is hoisted, and its span moves along with it.
+    assert!(matches!(statement_list.items()[0], Node::FunctionDecl(_)));
+    assert_eq!(
+        statement_list.span(0),
+        Some(Span::new(Position::new(4, 1), Position::new(4, 19)))
+    );
+    assert_eq!(
+        statement_list.span(1),
+        Some(Span::new(Position::new(1, 1), Position::new(1, 11)))
+    );
+    assert_eq!(
+        statement_list.span(2),
+        Some(Span::new(Position::new(2, 1), Position::new(2, 7)))
+    );
+    assert_eq!(statement_list.span(3), None);
+}
+
+#[test]
+fn node_spans() {
+    let statement_list = Parser::new(
+        "let a = b + c * d;\nfoo(a, b.c);\nif (a) {\n  b = a;\n}\nbar(x => x);".as_bytes(),
+        false,
+    )
+    .parse_all()
+    .expect("failed to parse");
+    let span = |start: (u32, u32), end: (u32, u32)| {
+        Some(Span::new(
+            Position::new(start.0, start.1),
+            Position::new(end.0, end.1),
+        ))
+    };
+
+    let declaration = match &statement_list.items()[0] {
+        Node::LetDeclList(list) => &list.as_ref()[0],
+        node => panic!("unexpected node: {:?}", node),
+    };
+    assert_eq!(statement_list.span_of(declaration), span((1, 5), (1, 18)));
+    let sum = declaration.init().expect("no initializer");
+    assert_eq!(statement_list.span_of(sum), span((1, 9), (1, 18)));
+    let product = match sum {
+        Node::BinOp(sum) => sum.rhs(),
+        node => panic!("unexpected node: {:?}", node),
+    };
+    assert_eq!(statement_list.span_of(product), span((1, 13), (1, 18)));
+
+    let call = match &statement_list.items()[1] {
+        Node::Call(call) => call,
+        node => panic!("unexpected node: {:?}", node),
+    };
+    assert_eq!(
+        statement_list.span_of(&statement_list.items()[1]),
+        span((2, 1), (2, 12))
+    );
+    assert_eq!(statement_list.span_of(call.expr()), span((2, 1), (2, 4)));
+    assert_eq!(
+        statement_list.span_of(&call.args()[0]),
+        span((2, 5), (2, 6))
+    );
+    assert_eq!(
+        statement_list.span_of(&call.args()[1]),
+        span((2, 8), (2, 11))
+    );
+
+    // Nodes in a block are in the statement list of the block.
+    let block = match &statement_list.items()[2] {
+        Node::If(if_node) => {
+            assert_eq!(statement_list.span_of(if_node.cond()), span((3, 5), (3, 6)));
+            match if_node.body() {
+                Node::Block(block) => block,
+                node => panic!("unexpected node: {:?}", node),
+            }
+        }
+        node => panic!("unexpected node: {:?}", node),
+    };
+    assert_eq!(
+        statement_list.span_of(&statement_list.items()[2]),
+        span((3, 1), (5, 2))
+    );
+    let assign = &block.items()[0];
+    assert_eq!(statement_list.span_of(assign), None);
+    assert_eq!(block.statement_list().span_of(assign), span((4, 3), (4, 8)));
+
+    // The expression body of an arrow function is a statement list of its own, whose statement
+    // is where the expression is.
+    let arrow = match &statement_list.items()[3] {
+        Node::Call(call) => match &call.args()[0] {
+            Node::ArrowFunctionDecl(arrow) => arrow,
+            node => panic!("unexpected node: {:?}", node),
+        },
+        node => panic!("unexpected node: {:?}", node),
+    };
+    let body = arrow.body();
+    let expr = match &body.items()[0] {
+        Node::Return(ret) => ret.expr().expect("no expression"),
+        node => panic!("unexpected node: {:?}", node),
+    };
+    assert!(body.span(0).is_some());
+    assert_eq!(body.span_of(&body.items()[0]), body.span(0));
+    assert_eq!(body.span_of(expr), body.span(0));
+}
+
+#[test]
+fn cloned_lists_keep_node_spans() {
+    let statement_list = Parser::new("foo(a, b);\nbar();".as_bytes(), false)
+        .parse_all()
+        .expect("failed to parse");
+    let span = |start: (u32, u32), end: (u32, u32)| {
+        Some(Span::new(
+            Position::new(start.0, start.1),
+            Position::new(end.0, end.1),
+        ))
+    };
+
+    // A clone shares the items of the list.
+    let mut clone = statement_list.clone();
+    assert_eq!(clone.items().as_ptr(), statement_list.items().as_ptr());
+    match &clone.items()[0] {
+        Node::Call(call) => assert_eq!(clone.span_of(&call.args()[1]), span((1, 8), (1, 9))),
+        node => panic!("unexpected node: {:?}", node),
+    }
+
+    // Modifying the clone copies the items along with their spans.
+    clone.items_mut();
+    assert_ne!(clone.items().as_ptr(), statement_list.items().as_ptr());
+    assert_eq!(clone, statement_list);
+    for list in [&statement_list, &clone] {
+        match &list.items()[0] {
+            Node::Call(call) => {
+                assert_eq!(list.span_of(&call.args()[1]), span((1, 8), (1, 9)))
+            }
+            node => panic!("unexpected node: {:?}", node),
+        }
+        assert_eq!(list.span_of(&list.items()[1]), span((2, 1), (2, 6)));
+    }
+}
+
+#[test]
+fn parse_error_span() {
+    let error = Parser::new("let a = 1;\nlet a = 2;".as_bytes(), false)
+        .parse_all()
+        .expect_err("redeclaration should fail");
+    assert_eq!(
+        error.span(),
+        Some(Span::new(Position::new(2, 1), Position::new(2, 1)))
+    );
+
+    let error = Parser::new("let a = (;".as_bytes(), false)
+        .parse_all()
+        .expect_err("parsing should fail");
+    assert_eq!(
+        error.span(),
+        Some(Span::new(Position::new(1, 10), Position::new(1, 11)))
+    );
+
+    let error = Parser::new("let a = (".as_bytes(), false)
+        .parse_all()
+        .expect_err("parsing should fail");
+    assert_eq!(error.span(), None);
+}
+
+/// Parses the given JavaScript string recovering from errors, returning the reparsed source of
+/// the partial AST and the spans of the reported diagnostics.
+#[track_caller]
+fn parse_with_diagnostics(js: &str) -> (String, Vec<Option<Span>>) {
+    let (statement_list, diagnostics) =
+        Parser::new(js.as_bytes(), false).parse_all_with_diagnostics();
+    assert!(diagnostics
+        .iter()
+        .all(|diagnostic| diagnostic.severity() == Severity::Error));
+    (
+        statement_list.to_string(),
+        diagnostics.iter().map(Diagnostic::span).collect(),
+    )
+}
+
+#[test]
+fn recover_at_statement_boundaries() {
+    let (source, spans) = parse_with_diagnostics("let a = ;\nlet b = 2;\nfoo(;\nb;");
+    assert_eq!(source, "let b = 2;\nb;\n");
+    assert_eq!(
+        spans,
+        vec![
+            Some(Span::new(Position::new(1, 9), Position::new(1, 10))),
+            Some(Span::new(Position::new(3, 5), Position::new(3, 6))),
+        ]
+    );
+
+    let (source, spans) = parse_with_diagnostics("}\nlet a = @; b;");
+    assert_eq!(source, "b;\n");
+    assert_eq!(spans.len(), 2);
+}
+
+#[test]
+fn recover_after_a_consumed_semicolon() {
+    let (source, spans) = parse_with_diagnostics("x = 1 +; y = 2; z = 3;");
+    assert_eq!(source, "y = 2;\nz = 3;\n");
+    assert_eq!(spans.len(), 1);
+
+    let (source, spans) = parse_with_diagnostics("let a = ; y = 2;");
+    assert_eq!(source, "y = 2;\n");
+    assert_eq!(spans.len(), 1);
+
+    let (source, spans) = parse_with_diagnostics("let a = ; let b = ; c = 3;");
+    assert_eq!(source, "c = 3;\n");
+    assert_eq!(spans.len(), 2);
+}
+
+#[test]
+fn recover_in_nested_statement_lists() {
+    let (source, spans) = parse_with_diagnostics(
+        "function f() { let x = ; return 1; }\nswitch (a) { case 1: let = ; case 2: b(); }",
+    );
+    assert_eq!(
+        source,
+        "function f() {\n    return 1;\n};\nswitch (a) {\n    case 1:\n    case 2:\n        b();\n}\n"
+    );
+    assert_eq!(spans.len(), 2);
+}
+
+#[test]
+fn recover_keeps_valid_input() {
+    let js = "let a = 1;\nfunction f() { return a; }\nf();";
+    let (statement_list, diagnostics) =
+        Parser::new(js.as_bytes(), false).parse_all_with_diagnostics();
+    assert!(diagnostics.is_empty());
+    assert_eq!(
+        statement_list,
+        Parser::new(js.as_bytes(), false)
+            .parse_all()
+            .expect("failed to parse")
+    );
+}
+
+#[test]
+fn diagnostic_suggestion() {
+    let (_, diagnostics) =
+        Parser::new("if (a { b() }\nc();".as_bytes(), false).parse_all_with_diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    let suggestion = diagnostics[0]
+        .suggestion()
+        .expect("missing parenthesis should have a suggestion");
+    assert_eq!(suggestion.replacement(), ")");
+    assert_eq!(
+        suggestion.span(),
+        Span::new(Position::new(1, 7), Position::new(1, 7))
+    );
+}
+
+/// Parses the given JavaScript string, preserving its comments.
+fn parse_with_comments(js: &str) -> StatementList {
+    Parser::new(js.as_bytes(), false)
+        .parse_all_with_comments()
+        .expect("failed to parse")
+}
+
+/// Gets the text of the leading and trailing comments of a node of the statement list.
+fn node_comment_texts<'a, T: Keyed>(
+    statement_list: &'a StatementList,
+    node: &T,
+) -> (Vec<&'a str>, Vec<&'a str>) {
+    statement_list
+        .comments_of(node)
+        .map_or((Vec::new(), Vec::new()), |comments| {
+            (
+                comments.leading().iter().map(|c| c.text()).collect(),
+                comments.trailing().iter().map(|c| c.text()).collect(),
+            )
+        })
+}
+
+/// Gets the text of the leading and trailing comments of each item in the statement list.
+fn comment_texts(statement_list: &StatementList) -> Vec<(Vec<&str>, Vec<&str>)> {
+    statement_list
+        .items()
+        .iter()
+        .map(|item| node_comment_texts(statement_list, item))
+        .collect()
+}
+
+#[test]
+fn attach_comments() {
+    let js = "/** Adds one. */\nfunction inc(x) {\n    return x + 1; // c\n    // dangling\n}\n\n// a\nlet a = inc(1); /* b */\n// end\n";
+    let statement_list = parse_with_comments(js);
+    assert_eq!(
+        comment_texts(&statement_list),
+        [
+            (vec!["* Adds one. "], vec![]),
+            (vec![" a"], vec![" b ", " end"]),
+        ]
+    );
+
+    let body = match &statement_list.items()[0] {
+        Node::FunctionDecl(decl) => decl.body(),
+        node => panic!("expected a function declaration, got {:?}", node),
+    };
+    assert_eq!(comment_texts(body), [(vec![], vec![" c", " dangling"])]);
+
+    let statement_list = Parser::new(js.as_bytes(), false)
+        .parse_all()
+        .expect("failed to parse");
+    assert!(statement_list
+        .comments_of(&statement_list.items()[0])
+        .is_none());
+}
+
+#[test]
+fn attach_comments_to_nodes() {
+    let js = "module.exports = {\n  /** Runs the plugin. */\n  run() { return 1; },\n  stop: /* now */ function () {}, // unused\n};\nf(/** @param {number} x */ (x) => x);";
+    let statement_list = parse_with_comments(js);
+    let items = statement_list.items();
+    assert_eq!(
+        comment_texts(&statement_list),
+        [(vec![], vec![]), (vec![], vec![])]
+    );
+
+    let object = match &items[0] {
+        Node::Assign(assign) => match assign.rhs() {
+            Node::Object(object) => object,
+            node => panic!("expected an object literal, got {:?}", node),
+        },
+        node => panic!("expected an assignment, got {:?}", node),
+    };
+    let properties = object.properties();
+    assert_eq!(
+        node_comment_texts(&statement_list, &properties[0]),
+        (vec!["* Runs the plugin. "], vec![])
+    );
+    assert_eq!(
+        node_comment_texts(&statement_list, &properties[1]),
+        (vec![], vec![" unused"])
+    );
+    let function = match &properties[1] {
+        PropertyDefinition::Property(_, function) => function,
+        property => panic!("expected a property, got {:?}", property),
+    };
+    assert_eq!(
+        node_comment_texts(&statement_list, function),
+        (vec![" now "], vec![])
+    );
+
+    let arrow = match &items[1] {
+        Node::Call(call) => &call.args()[0],
+        node => panic!("expected a call, got {:?}", node),
+    };
+    assert_eq!(
+        node_comment_texts(&statement_list, arrow),
+        (vec!["* @param {number} x "], vec![])
+    );
+}
+
+#[test]
+fn comments_do_not_change_the_tree() {
+    for js in [
+        "x = `a${b} // c`; // d",
+        "x = `a${ /* b */ {} /* c */ }d`;",
+        "a = / b/; // c",
+        "a = b / c; /* d */",
+        "a /* \n */ b",
+        "if (a) { b() } // c\nelse { d() }",
+    ] {
+        assert_eq!(
+            parse_with_comments(js),
+            Parser::new(js.as_bytes(), false)
+                .parse_all()
+                .expect("failed to parse"),
+            "{}",
+            js
+        );
+    }
+}
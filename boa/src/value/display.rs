@@ -122,12 +122,12 @@ pub(crate) fn log_string_from(x: &JsValue, print_internals: bool, print_children
                                 // Introduce recursive call to stringify any objects
                                 // which are part of the Array
                                 log_string_from(
-                                    v.borrow()
+                                    &v.borrow()
                                         .properties()
                                         .get(&i.into())
                                         // FIXME: handle accessor descriptors
-                                        .and_then(|p| p.value())
-                                        .unwrap_or(&JsValue::Undefined),
+                                        .and_then(|p| p.value().cloned())
+                                        .unwrap_or_default(),
                                     print_internals,
                                     false,
                                 )
@@ -216,7 +216,19 @@ pub(crate) fn display_obj(v: &JsValue, print_internals: bool) -> String {
                 .unwrap_or(&JsValue::Undefined)
                 .display()
                 .to_string();
-            return format!("{}: {}", name, message);
+            let position = |key: &str| {
+                v.get_property(key)
+                    .as_ref()
+                    .and_then(|d| d.value())
+                    .filter(|value| value.is_number())
+                    .map(|value| value.display().to_string())
+            };
+            return match (position("lineNumber"), position("columnNumber")) {
+                (Some(line), Some(column)) => {
+                    format!("{}: {} (at {}:{})", name, message, line, column)
+                }
+                _ => format!("{}: {}", name, message),
+            };
         }
     }
 
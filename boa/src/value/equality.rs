@@ -68,16 +68,20 @@ impl JsValue {
             //    a. Let n be ! StringToBigInt(y).
             //    b. If n is NaN, return false.
             //    c. Return the result of the comparison x == n.
-            (Self::BigInt(ref a), Self::String(ref b)) => match JsBigInt::from_string(b) {
-                Some(ref b) => a == b,
-                None => false,
-            },
+            (Self::BigInt(ref a), Self::String(ref b)) => {
+                match JsBigInt::from_string(&b.to_std_string_lossy()) {
+                    Some(ref b) => a == b,
+                    None => false,
+                }
+            }
 
             // 7. If Type(x) is String and Type(y) is BigInt, return the result of the comparison y == x.
-            (Self::String(ref a), Self::BigInt(ref b)) => match JsBigInt::from_string(a) {
-                Some(ref a) => a == b,
-                None => false,
-            },
+            (Self::String(ref a), Self::BigInt(ref b)) => {
+                match JsBigInt::from_string(&a.to_std_string_lossy()) {
+                    Some(ref a) => a == b,
+                    None => false,
+                }
+            }
 
             // 8. If Type(x) is Boolean, return the result of the comparison ! ToNumber(x) == y.
             (Self::Boolean(x), _) => return other.equals(&JsValue::new(*x as i32), context),
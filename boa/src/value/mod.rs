@@ -306,7 +306,7 @@ impl JsValue {
         match self {
             Self::Object(ref object) => {
                 // TODO: had to skip `__get_own_properties__` since we don't have context here
-                let property = object.borrow().properties().get(&key).cloned();
+                let property = object.borrow().properties().get(&key);
                 if property.is_some() {
                     return property;
                 }
@@ -470,7 +470,7 @@ impl JsValue {
                 Err(context.construct_type_error("cannot convert undefined to a BigInt"))
             }
             JsValue::String(ref string) => {
-                if let Some(value) = JsBigInt::from_string(string) {
+                if let Some(value) = JsBigInt::from_string(&string.to_std_string_lossy()) {
                     Ok(value)
                 } else {
                     Err(context.construct_syntax_error(format!(
@@ -573,7 +573,7 @@ impl JsValue {
                 object.insert_property(
                     "length",
                     PropertyDescriptor::builder()
-                        .value(string.encode_utf16().count())
+                        .value(string.len())
                         .writable(false)
                         .enumerable(false)
                         .configurable(false),
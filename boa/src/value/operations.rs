@@ -11,9 +11,13 @@ impl JsValue {
             (Self::Integer(x), Self::Rational(y)) => Self::new(f64::from(*x) + y),
             (Self::Rational(x), Self::Integer(y)) => Self::new(x + f64::from(*y)),
 
-            (Self::String(ref x), Self::String(ref y)) => Self::from(JsString::concat(x, y)),
-            (Self::String(ref x), y) => Self::from(JsString::concat(x, y.to_string(context)?)),
-            (x, Self::String(ref y)) => Self::from(JsString::concat(x.to_string(context)?, y)),
+            (Self::String(ref x), Self::String(ref y)) => Self::from(JsString::concat_rope(x, y)),
+            (Self::String(ref x), y) => {
+                Self::from(JsString::concat_rope(x, &y.to_string(context)?))
+            }
+            (x, Self::String(ref y)) => {
+                Self::from(JsString::concat_rope(&x.to_string(context)?, y))
+            }
             (Self::BigInt(ref x), Self::BigInt(ref y)) => Self::new(JsBigInt::add(x, y)),
 
             // Slow path:
@@ -22,10 +26,10 @@ impl JsValue {
                 other.to_primitive(context, PreferredType::Default)?,
             ) {
                 (Self::String(ref x), ref y) => {
-                    Self::from(JsString::concat(x, y.to_string(context)?))
+                    Self::from(JsString::concat_rope(x, &y.to_string(context)?))
                 }
                 (ref x, Self::String(ref y)) => {
-                    Self::from(JsString::concat(x.to_string(context)?, y))
+                    Self::from(JsString::concat_rope(&x.to_string(context)?, y))
                 }
                 (x, y) => match (x.to_numeric(context)?, y.to_numeric(context)?) {
                     (Numeric::Number(x), Numeric::Number(y)) => Self::new(x + y),
@@ -432,7 +436,7 @@ impl JsValue {
                 Ok(num) => -num,
                 Err(_) => f64::NAN,
             }),
-            Self::String(ref str) => Self::new(match f64::from_str(str) {
+            Self::String(ref str) => Self::new(match f64::from_str(&str.to_std_string_lossy()) {
                 Ok(num) => -num,
                 Err(_) => f64::NAN,
             }),
@@ -495,29 +499,17 @@ impl JsValue {
                 };
 
                 match (px, py) {
-                    (Self::String(ref x), Self::String(ref y)) => {
-                        if x.starts_with(y.as_str()) {
-                            return Ok(AbstractRelation::False);
-                        }
-                        if y.starts_with(x.as_str()) {
-                            return Ok(AbstractRelation::True);
-                        }
-                        for (x, y) in x.chars().zip(y.chars()) {
-                            if x != y {
-                                return Ok((x < y).into());
-                            }
-                        }
-                        unreachable!()
-                    }
+                    // Strings are compared by their sequence of code units.
+                    (Self::String(ref x), Self::String(ref y)) => (x < y).into(),
                     (Self::BigInt(ref x), Self::String(ref y)) => {
-                        if let Some(y) = JsBigInt::from_string(y) {
+                        if let Some(y) = JsBigInt::from_string(&y.to_std_string_lossy()) {
                             (*x < y).into()
                         } else {
                             AbstractRelation::Undefined
                         }
                     }
                     (Self::String(ref x), Self::BigInt(ref y)) => {
-                        if let Some(x) = JsBigInt::from_string(x) {
+                        if let Some(x) = JsBigInt::from_string(&x.to_std_string_lossy()) {
                             (x < *y).into()
                         } else {
                             AbstractRelation::Undefined
@@ -626,7 +626,7 @@ mod cyclic_conversions {
 
         assert_eq!(
             forward(&mut context, src),
-            r#"Uncaught "TypeError": "cyclic object value""#,
+            r#"Uncaught "TypeError": "cyclic object value" (at 4:13)"#,
         );
     }
 
@@ -2,16 +2,86 @@
 //! This module will provides everything needed to implement the CallFrame
 
 use super::CodeBlock;
-use crate::{environment::lexical_environment::Environment, JsValue};
+use crate::{
+    environment::lexical_environment::Environment,
+    gc::{custom_trace, empty_trace, Finalize, Trace},
+    object::JsObject,
+    JsValue,
+};
 use gc::Gc;
 
-#[derive(Debug)]
+#[derive(Debug, Trace, Finalize)]
 pub struct CallFrame {
-    pub(crate) prev: Option<Box<Self>>,
     pub(crate) code: Gc<CodeBlock>,
     pub(crate) pc: usize,
     pub(crate) fp: usize,
     pub(crate) exit_on_return: bool,
     pub(crate) this: JsValue,
     pub(crate) environment: Environment,
+    /// The number of block environments entered on top of the environment of the function.
+    pub(crate) environment_depth: u32,
+}
+
+/// The call frame of a generator or an async function that is not running, together with its
+/// values on the stack.
+#[derive(Debug, Finalize)]
+pub(crate) struct SuspendedFrame {
+    pub(crate) frame: CallFrame,
+    pub(crate) stack: Vec<JsValue>,
+}
+
+// Safety: the frame and the stack are marked. The derived `Trace` would also implement `Drop`,
+// which forbids moving the frame out of a suspended frame when it is resumed.
+unsafe impl Trace for SuspendedFrame {
+    custom_trace!(this, {
+        mark(&this.frame);
+        mark(&this.stack);
+    });
+}
+
+/// The completion a suspended frame is resumed with.
+///
+/// It is pushed on the stack as an integer, above the value the frame is resumed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ResumeKind {
+    Normal = 0,
+    Throw = 1,
+    Return = 2,
+}
+
+impl Finalize for ResumeKind {}
+
+// Safety: `ResumeKind` does not contain any object that require trace,
+// so this is safe.
+unsafe impl Trace for ResumeKind {
+    empty_trace!();
+}
+
+impl ResumeKind {
+    /// Gets the kind of a resumption from the integer pushed on the stack.
+    pub(crate) fn from_value(value: &JsValue) -> Self {
+        match value {
+            JsValue::Integer(1) => Self::Throw,
+            JsValue::Integer(2) => Self::Return,
+            _ => Self::Normal,
+        }
+    }
+}
+
+/// A job that runs once the current script has run to completion.
+#[derive(Debug)]
+pub(crate) enum Job {
+    /// Resumes the async function whose suspended frame is held by `generator`.
+    Resume {
+        generator: JsObject,
+        value: JsValue,
+        kind: ResumeKind,
+    },
+    /// Resumes the async function whose suspended frame is held by `generator` once `thenable`
+    /// is settled, by calling its `then` method.
+    AwaitThenable {
+        generator: JsObject,
+        thenable: JsValue,
+        then: JsValue,
+    },
 }
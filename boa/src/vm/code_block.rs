@@ -1,16 +1,23 @@
 use crate::{
-    builtins::function::{
-        Captures, ClosureFunctionSignature, Function, NativeFunctionSignature, ThisMode,
+    builtins::{
+        function::{
+            arguments::Arguments, Captures, ClosureFunctionSignature, Function,
+            NativeFunctionSignature, ThisMode,
+        },
+        generator::Generator,
+        Array,
     },
     context::StandardObjects,
     environment::{
+        declarative_environment_record::DeclarativeEnvironmentRecord,
         function_environment_record::{BindingStatus, FunctionEnvironmentRecord},
         lexical_environment::Environment,
     },
     gc::{Finalize, Trace},
-    object::{internal_methods::get_prototype_from_constructor, JsObject, ObjectData},
+    interner::Sym,
+    object::{internal_methods::get_prototype_from_constructor, JsObject, ObjectData, PROTOTYPE},
     property::PropertyDescriptor,
-    syntax::ast::node::FormalParameter,
+    syntax::ast::{node::FormalParameter, Position},
     vm::Opcode,
     Context, JsResult, JsString, JsValue,
 };
@@ -18,7 +25,7 @@ use gc::Gc;
 
 use std::{convert::TryInto, fmt::Write, mem::size_of};
 
-use super::CallFrame;
+use super::{CallFrame, InlineCache};
 
 /// This represents wether a value can be read from [`CodeBlock`] code.
 pub unsafe trait Readable {}
@@ -34,7 +41,30 @@ unsafe impl Readable for i64 {}
 unsafe impl Readable for f32 {}
 unsafe impl Readable for f64 {}
 
-#[derive(Debug, Trace, Finalize)]
+/// How a binding is created when the VM enters its scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BindingKind {
+    /// A parameter, a `var` or a function declaration, initialized to `undefined`.
+    Var,
+    /// A `let` declaration, uninitialized until the declaration is evaluated.
+    Let,
+    /// A `const` declaration, uninitialized until the declaration is evaluated.
+    Const,
+}
+
+impl BindingKind {
+    /// Creates a binding of this kind for `name` in the next free slot of `env`, and returns the
+    /// slot.
+    pub(crate) fn create(self, name: Sym, env: &DeclarativeEnvironmentRecord) -> usize {
+        match self {
+            Self::Var => env.create_binding(name, true, false, Some(JsValue::undefined())),
+            Self::Let => env.create_binding(name, true, false, None),
+            Self::Const => env.create_binding(name, false, true, None),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Trace, Finalize)]
 pub struct CodeBlock {
     /// Name of this function
     pub(crate) name: JsString,
@@ -48,6 +78,12 @@ pub struct CodeBlock {
     /// Is this function a constructor.
     pub(crate) constructor: bool,
 
+    /// Is this function a generator.
+    pub(crate) generator: bool,
+
+    /// Is this function an async function.
+    pub(crate) asynchronous: bool,
+
     /// [[ThisMode]]
     pub(crate) this_mode: ThisMode,
 
@@ -60,10 +96,55 @@ pub struct CodeBlock {
     pub(crate) literals: Vec<JsValue>,
 
     /// Variables names
-    pub(crate) variables: Vec<JsString>,
+    pub(crate) variables: Vec<Sym>,
+
+    /// Bindings of the function scope, in slot order. The parameters come first.
+    #[unsafe_ignore_trace]
+    pub(crate) bindings: Vec<(Sym, BindingKind)>,
+
+    /// The slot of the `arguments` binding, if the function has an arguments object.
+    pub(crate) arguments_binding: Option<u32>,
+
+    /// The slot of the binding of a named function expression to the function itself.
+    pub(crate) function_binding: Option<u32>,
+
+    /// Bindings of each block scope, in slot order, indexed by the operand of
+    /// `PushDeclarativeEnvironment`.
+    #[unsafe_ignore_trace]
+    pub(crate) scopes: Vec<Box<[(Sym, BindingKind)]>>,
 
     // Functions inside this function
     pub(crate) functions: Vec<Gc<CodeBlock>>,
+
+    /// The inline caches of the property accesses, indexed by the operand of
+    /// `GetPropertyByName` and `SetPropertyByName`.
+    pub(crate) inline_caches: Vec<InlineCache>,
+
+    /// Maps the start of each statement's bytecode to its source position, sorted by pc.
+    #[unsafe_ignore_trace]
+    pub(crate) line_table: Vec<(u32, Position)>,
+
+    /// The exception handlers of the `try` statements, innermost first.
+    #[unsafe_ignore_trace]
+    pub(crate) handlers: Vec<Handler>,
+}
+
+/// An exception handler, which protects a range of the code of a [`CodeBlock`].
+///
+/// A `try` statement can have several handlers for the same code, since its protected code is
+/// split around the `finally` blocks inlined at `break`, `continue` and `return` statements.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Handler {
+    /// The address of the first protected instruction.
+    pub(crate) start: u32,
+    /// The address after the last protected instruction.
+    pub(crate) end: u32,
+    /// The address of the code that handles the exception, which is on top of the stack.
+    pub(crate) address: u32,
+    /// The number of values on the stack of the frame when the protected code is entered.
+    pub(crate) stack_depth: u32,
+    /// The number of block environments of the frame when the protected code is entered.
+    pub(crate) environment_depth: u32,
 }
 
 impl CodeBlock {
@@ -72,11 +153,20 @@ impl CodeBlock {
             code: Vec::new(),
             literals: Vec::new(),
             variables: Vec::new(),
+            bindings: Vec::new(),
+            arguments_binding: None,
+            function_binding: None,
+            scopes: Vec::new(),
             functions: Vec::new(),
+            inline_caches: Vec::new(),
+            line_table: Vec::new(),
+            handlers: Vec::new(),
             name,
             length,
             strict,
             constructor,
+            generator: false,
+            asynchronous: false,
             this_mode: ThisMode::Global,
             params: Vec::new().into_boxed_slice(),
         }
@@ -103,6 +193,75 @@ impl CodeBlock {
         unsafe { self.read_unchecked(offset) }
     }
 
+    /// Gets the innermost exception handler that protects the instruction at `pc`.
+    pub(crate) fn handler(&self, pc: usize) -> Option<&Handler> {
+        let pc = pc as u32;
+        self.handlers
+            .iter()
+            .find(|handler| handler.start <= pc && pc < handler.end)
+    }
+
+    /// Gets the source position of the statement which the instruction at `pc` belongs to.
+    pub(crate) fn position(&self, pc: usize) -> Option<Position> {
+        let index = self
+            .line_table
+            .partition_point(|&(start, _)| start as usize <= pc);
+        index.checked_sub(1).map(|index| self.line_table[index].1)
+    }
+
+    /// Creates the bindings of the function scope in `env`, and binds the parameters to `args`.
+    fn instantiate_bindings(
+        &self,
+        function: &JsObject,
+        env: &Environment,
+        args: &[JsValue],
+        context: &mut Context,
+    ) {
+        let declarative = env
+            .as_declarative()
+            .expect("function environments are declarative");
+
+        for (i, &(name, kind)) in self.bindings.iter().enumerate() {
+            let slot = kind.create(name, declarative);
+
+            if let Some(param) = self.params.get(i) {
+                let value = if param.is_rest_param() {
+                    let rest = args.get(i..).unwrap_or_default().to_vec();
+                    Array::create_array_from_list(rest, context).into()
+                } else if let Some(value) = args.get(i) {
+                    value.clone()
+                } else {
+                    continue;
+                };
+                declarative.initialize_binding_by_slot(slot, value);
+            }
+        }
+
+        // <https://tc39.es/ecma262/#sec-functiondeclarationinstantiation>
+        if let Some(slot) = self.arguments_binding {
+            let is_simple_parameter_list = self
+                .params
+                .iter()
+                .all(|param| !param.is_rest_param() && param.init().is_none());
+            let arguments = if self.strict || !is_simple_parameter_list {
+                Arguments::create_unmapped_arguments_object(args, context)
+            } else {
+                Arguments::create_mapped_arguments_object(
+                    function,
+                    &self.params,
+                    args,
+                    env,
+                    context,
+                )
+            };
+            declarative.initialize_binding_by_slot(slot as usize, arguments.into());
+        }
+
+        if let Some(slot) = self.function_binding {
+            declarative.initialize_binding_by_slot(slot as usize, function.clone().into());
+        }
+    }
+
     pub(crate) fn instruction_operands(&self, pc: &mut usize) -> String {
         let opcode: Opcode = self.code[*pc].try_into().unwrap();
         *pc += size_of::<Opcode>();
@@ -122,6 +281,11 @@ impl CodeBlock {
                 *pc += size_of::<i32>();
                 result
             }
+            Opcode::RotateLeft | Opcode::RotateRight => {
+                let result = self.read::<u8>(*pc).to_string();
+                *pc += size_of::<u8>();
+                result
+            }
             Opcode::PushRational => {
                 let operand = self.read::<f64>(*pc);
                 *pc += size_of::<f64>();
@@ -137,11 +301,48 @@ impl CodeBlock {
             | Opcode::LogicalAnd
             | Opcode::LogicalOr
             | Opcode::Coalesce
-            | Opcode::Call => {
+            | Opcode::JumpIfNotUndefined
+            | Opcode::JumpIfNotLessThan
+            | Opcode::JumpIfNotLessThanOrEq
+            | Opcode::Call
+            | Opcode::New
+            | Opcode::CopyDataProperties
+            | Opcode::ForInLoopInitIterator
+            | Opcode::IteratorStep
+            | Opcode::ConcatToString
+            | Opcode::InitLocal
+            | Opcode::PushDeclarativeEnvironment
+            | Opcode::CopyDeclarativeEnvironment => {
                 let result = self.read::<u32>(*pc).to_string();
                 *pc += size_of::<u32>();
                 result
             }
+            Opcode::GetLocal | Opcode::SetLocal | Opcode::IncLocal | Opcode::DecLocal => {
+                let depth = self.read::<u32>(*pc);
+                *pc += size_of::<u32>();
+                let slot = self.read::<u32>(*pc);
+                *pc += size_of::<u32>();
+                format!("depth: {}, slot: {}", depth, slot)
+            }
+            Opcode::GeneratorNext => {
+                let address = self.read::<u32>(*pc);
+                *pc += size_of::<u32>();
+                let stack_depth = self.read::<u32>(*pc);
+                *pc += size_of::<u32>();
+                format!("return: {}, stack depth: {}", address, stack_depth)
+            }
+            Opcode::GeneratorDelegateNext => {
+                let done_address = self.read::<u32>(*pc);
+                *pc += size_of::<u32>();
+                let address = self.read::<u32>(*pc);
+                *pc += size_of::<u32>();
+                let stack_depth = self.read::<u32>(*pc);
+                *pc += size_of::<u32>();
+                format!(
+                    "done: {}, return: {}, stack depth: {}",
+                    done_address, address, stack_depth
+                )
+            }
             Opcode::GetFunction => {
                 let operand = self.read::<u32>(*pc);
                 *pc += size_of::<u32>();
@@ -157,17 +358,29 @@ impl CodeBlock {
             | Opcode::DefConst
             | Opcode::InitLexical
             | Opcode::GetName
+            | Opcode::GetNameOrUndefined
             | Opcode::SetName
-            | Opcode::GetPropertyByName
-            | Opcode::SetPropertyByName
+            | Opcode::DefineOwnPropertyByName
+            | Opcode::SetPropertyGetterByName
+            | Opcode::SetPropertySetterByName
             | Opcode::DeletePropertyByName => {
                 let operand = self.read::<u32>(*pc);
                 *pc += size_of::<u32>();
                 format!("{:04}: '{}'", operand, self.variables[operand as usize])
             }
+            Opcode::GetPropertyByName | Opcode::SetPropertyByName => {
+                let operand = self.read::<u32>(*pc);
+                let cache = self.read::<u32>(*pc + size_of::<u32>());
+                *pc += size_of::<u32>() * 2;
+                format!(
+                    "{:04}: '{}' (cache: {})",
+                    operand, self.variables[operand as usize], cache
+                )
+            }
             Opcode::Pop
             | Opcode::Dup
             | Opcode::Swap
+            | Opcode::Dup2
             | Opcode::PushZero
             | Opcode::PushOne
             | Opcode::PushNaN
@@ -178,6 +391,8 @@ impl CodeBlock {
             | Opcode::PushFalse
             | Opcode::PushUndefined
             | Opcode::PushEmptyObject
+            | Opcode::PushValueToArray
+            | Opcode::PushIteratorToArray
             | Opcode::Add
             | Opcode::Sub
             | Opcode::Div
@@ -191,6 +406,9 @@ impl CodeBlock {
             | Opcode::BitAnd
             | Opcode::BitXor
             | Opcode::BitNot
+            | Opcode::Inc
+            | Opcode::Dec
+            | Opcode::ToNumeric
             | Opcode::In
             | Opcode::Eq
             | Opcode::StrictEq
@@ -208,11 +426,24 @@ impl CodeBlock {
             | Opcode::Neg
             | Opcode::GetPropertyByValue
             | Opcode::SetPropertyByValue
+            | Opcode::DefineOwnPropertyByValue
+            | Opcode::SetPropertyGetterByValue
+            | Opcode::SetPropertySetterByValue
             | Opcode::DeletePropertyByValue
+            | Opcode::ValueNotNullOrUndefined
             | Opcode::ToBoolean
             | Opcode::Throw
             | Opcode::This
+            | Opcode::CallSpread
+            | Opcode::NewSpread
             | Opcode::Return
+            | Opcode::InitIterator
+            | Opcode::IteratorNext
+            | Opcode::IteratorToArray
+            | Opcode::IteratorClose
+            | Opcode::PopEnvironment
+            | Opcode::Yield
+            | Opcode::Await
             | Opcode::Nop => String::new(),
         }
     }
@@ -266,6 +497,36 @@ impl std::fmt::Display for CodeBlock {
 
         f.write_char('\n')?;
 
+        f.write_str("Lines:\n")?;
+        if !self.line_table.is_empty() {
+            for (pc, position) in &self.line_table {
+                writeln!(f, "    {:06}: {}", pc, position)?;
+            }
+        } else {
+            writeln!(f, "    <empty>")?;
+        }
+
+        f.write_char('\n')?;
+
+        f.write_str("Handlers:\n")?;
+        if !self.handlers.is_empty() {
+            for handler in &self.handlers {
+                writeln!(
+                    f,
+                    "    {:06}..{:06} -> {:06} (stack: {}, environments: {})",
+                    handler.start,
+                    handler.end,
+                    handler.address,
+                    handler.stack_depth,
+                    handler.environment_depth
+                )?;
+            }
+        } else {
+            writeln!(f, "    <empty>")?;
+        }
+
+        f.write_char('\n')?;
+
         f.write_str("Functions:\n")?;
         if !self.functions.is_empty() {
             for (i, code) in self.functions.iter().enumerate() {
@@ -294,8 +555,6 @@ impl JsVmFunction {
     pub fn new(code: Gc<CodeBlock>, environment: Environment, context: &mut Context) -> JsObject {
         let function_prototype = context.standard_objects().function_object().prototype();
 
-        let prototype = context.construct_object();
-
         let name_property = PropertyDescriptor::builder()
             .value(code.name.clone())
             .writable(true)
@@ -310,32 +569,49 @@ impl JsVmFunction {
             .configurable(true)
             .build();
 
+        let generator = code.generator;
+        let asynchronous = code.asynchronous;
         let function = Function::VmOrdinary { code, environment };
 
         let constructor =
             JsObject::from_proto_and_data(function_prototype, ObjectData::function(function));
 
-        let constructor_property = PropertyDescriptor::builder()
-            .value(constructor.clone())
-            .writable(true)
-            .enumerable(false)
-            .configurable(true)
-            .build();
-
-        prototype
-            .define_property_or_throw("constructor", constructor_property, context)
-            .unwrap();
-
-        let prototype_property = PropertyDescriptor::builder()
-            .value(prototype)
-            .writable(false)
-            .enumerable(false)
-            .configurable(true)
-            .build();
+        // The prototype of a generator function is the prototype of its generators, and it
+        // does not refer back to the function. Async functions have no prototype.
+        let prototype = if generator {
+            Some(JsObject::from_proto_and_data(
+                context.iterator_prototypes().generator(),
+                ObjectData::ordinary(),
+            ))
+        } else if asynchronous {
+            None
+        } else {
+            let prototype = context.construct_object();
+            let constructor_property = PropertyDescriptor::builder()
+                .value(constructor.clone())
+                .writable(true)
+                .enumerable(false)
+                .configurable(true)
+                .build();
+
+            prototype
+                .define_property_or_throw("constructor", constructor_property, context)
+                .unwrap();
+            Some(prototype)
+        };
 
-        constructor
-            .define_property_or_throw("prototype", prototype_property, context)
-            .unwrap();
+        if let Some(prototype) = prototype {
+            let prototype_property = PropertyDescriptor::builder()
+                .value(prototype)
+                .writable(true)
+                .enumerable(false)
+                .configurable(false)
+                .build();
+
+            constructor
+                .define_property_or_throw("prototype", prototype_property, context)
+                .unwrap();
+        }
         constructor
             .define_property_or_throw("name", name_property, context)
             .unwrap();
@@ -354,11 +630,15 @@ pub(crate) enum FunctionBody {
     },
     Native {
         function: NativeFunctionSignature,
+        constructor: bool,
     },
     Closure {
         function: Box<dyn ClosureFunctionSignature>,
         captures: Captures,
     },
+    /// A function of the tree-walking interpreter, executed through its `[[Call]]` and
+    /// `[[Construct]]` internal methods.
+    Interpreted,
 }
 
 // TODO: this should be modified to not take `exit_on_return` and then moved to `internal_methods`
@@ -382,8 +662,12 @@ impl JsObject {
             let function = object.as_function().unwrap();
 
             match function {
-                Function::Native { function, .. } => FunctionBody::Native {
+                Function::Native {
+                    function,
+                    constructor,
+                } => FunctionBody::Native {
                     function: *function,
+                    constructor: *constructor,
                 },
                 Function::Closure {
                     function, captures, ..
@@ -395,22 +679,39 @@ impl JsObject {
                     code: code.clone(),
                     environment: environment.clone(),
                 },
-                Function::Ordinary { .. } => unreachable!(),
+                Function::Ordinary { .. } => FunctionBody::Interpreted,
             }
         };
 
         match body {
-            FunctionBody::Native { function } => function(this, args, context),
+            // Native constructors tell calls from constructions apart through an undefined `this`.
+            FunctionBody::Native {
+                function,
+                constructor: true,
+            } => function(&JsValue::undefined(), args, context),
+            FunctionBody::Native { function, .. } => function(this, args, context),
             FunctionBody::Closure { function, captures } => {
                 (function)(this, args, captures, context)
             }
+            FunctionBody::Interpreted => self.__call__(this, args, context),
             FunctionBody::Ordinary { code, environment } => {
                 let lexical_this_mode = code.this_mode == ThisMode::Lexical;
 
+                // <https://tc39.es/ecma262/#sec-ordinarycallbindthis>
+                let this = if code.this_mode == ThisMode::Global {
+                    if this.is_null_or_undefined() {
+                        context.global_object().into()
+                    } else {
+                        this.to_object(context)?.into()
+                    }
+                } else {
+                    this.clone()
+                };
+
                 // Create a new Function environment whose parent is set to the scope of the function declaration (self.environment)
                 // <https://tc39.es/ecma262/#sec-prepareforordinarycall>
                 let local_env = FunctionEnvironmentRecord::new(
-                    this_function_object,
+                    this_function_object.clone(),
                     if !lexical_this_mode {
                         Some(this.clone())
                     } else {
@@ -430,39 +731,61 @@ impl JsObject {
                 // Turn local_env into Environment so it can be cloned
                 let local_env: Environment = local_env.into();
 
+                // Add the bindings of the function scope, including the arguments
+                code.instantiate_bindings(&this_function_object, &local_env, args, context);
+
                 // Push the environment first so that it will be used by default parameters
                 context.push_environment(local_env.clone());
 
-                // Add argument bindings to the function environment
-                for (i, param) in code.params.iter().enumerate() {
-                    // Rest Parameters
-                    if param.is_rest_param() {
-                        todo!("Rest parameter");
-                    }
-
-                    let value = match args.get(i).cloned() {
-                        None => JsValue::undefined(),
-                        Some(value) => value,
-                    };
-
-                    Function::add_arguments_to_environment(param, value, &local_env, context);
-                }
+                let generator = code.generator;
+                let asynchronous = code.asynchronous;
 
                 context.vm.push_frame(CallFrame {
-                    prev: None,
                     code,
-                    this: this.clone(),
+                    this,
                     pc: 0,
                     fp: context.vm.stack.len(),
                     exit_on_return,
                     environment: local_env,
+                    environment_depth: 0,
                 });
 
                 let result = context.run();
 
                 context.pop_environment();
 
-                result
+                if generator {
+                    // The body of a generator function is suspended right after the parameters
+                    // are bound, until the first call of `next`.
+                    result?;
+                    let frame = context
+                        .vm
+                        .suspended
+                        .take()
+                        .expect("generator functions are suspended before their body");
+                    let prototype = this_function_object.get(PROTOTYPE, context)?;
+                    let prototype = prototype
+                        .as_object()
+                        .cloned()
+                        .unwrap_or_else(|| context.iterator_prototypes().generator());
+                    Ok(JsObject::from_proto_and_data(
+                        prototype,
+                        ObjectData::generator(Generator::new(frame)),
+                    )
+                    .into())
+                } else if asynchronous {
+                    // TODO: return a promise once there is a `Promise` builtin.
+                    if let (Ok(awaited), Some(frame)) = (result, context.vm.suspended.take()) {
+                        let generator = JsObject::from_proto_and_data(
+                            None,
+                            ObjectData::generator(Generator::new(frame)),
+                        );
+                        context.await_value(generator, awaited);
+                    }
+                    Ok(JsValue::undefined())
+                } else {
+                    result
+                }
             }
         }
     }
@@ -495,8 +818,12 @@ impl JsObject {
             let function = object.as_function().unwrap();
 
             match function {
-                Function::Native { function, .. } => FunctionBody::Native {
+                Function::Native {
+                    function,
+                    constructor,
+                } => FunctionBody::Native {
                     function: *function,
+                    constructor: *constructor,
                 },
                 Function::Closure {
                     function, captures, ..
@@ -508,7 +835,7 @@ impl JsObject {
                     code: code.clone(),
                     environment: environment.clone(),
                 },
-                Function::Ordinary { .. } => unreachable!(),
+                Function::Ordinary { .. } => FunctionBody::Interpreted,
             }
         };
 
@@ -517,6 +844,7 @@ impl JsObject {
             FunctionBody::Closure { function, captures } => {
                 (function)(this_target, args, captures, context)
             }
+            FunctionBody::Interpreted => self.__construct__(args, this_target, context),
             FunctionBody::Ordinary { code, environment } => {
                 let this: JsValue = {
                     // If the prototype of the constructor is not an object, then use the default object
@@ -535,7 +863,7 @@ impl JsObject {
                 // Create a new Function environment whose parent is set to the scope of the function declaration (self.environment)
                 // <https://tc39.es/ecma262/#sec-prepareforordinarycall>
                 let local_env = FunctionEnvironmentRecord::new(
-                    this_function_object,
+                    this_function_object.clone(),
                     Some(this.clone()),
                     Some(environment),
                     // Arrow functions do not have a this binding https://tc39.es/ecma262/#sec-function-environment-records
@@ -544,46 +872,40 @@ impl JsObject {
                     } else {
                         BindingStatus::Uninitialized
                     },
-                    JsValue::undefined(),
+                    this_target.clone(),
                     context,
                 )?;
 
                 // Turn local_env into Environment so it can be cloned
                 let local_env: Environment = local_env.into();
 
+                // Add the bindings of the function scope, including the arguments
+                code.instantiate_bindings(&this_function_object, &local_env, args, context);
+
                 // Push the environment first so that it will be used by default parameters
                 context.push_environment(local_env.clone());
 
-                // Add argument bindings to the function environment
-                for (i, param) in code.params.iter().enumerate() {
-                    // Rest Parameters
-                    if param.is_rest_param() {
-                        todo!("Rest parameter");
-                    }
-
-                    let value = match args.get(i).cloned() {
-                        None => JsValue::undefined(),
-                        Some(value) => value,
-                    };
-
-                    Function::add_arguments_to_environment(param, value, &local_env, context);
-                }
-
                 context.vm.push_frame(CallFrame {
-                    prev: None,
                     code,
-                    this,
+                    this: this.clone(),
                     pc: 0,
                     fp: context.vm.stack.len(),
                     exit_on_return,
                     environment: local_env,
+                    environment_depth: 0,
                 });
 
-                let _result = context.run();
+                let result = context.run();
 
                 context.pop_environment();
 
-                context.get_this_binding()
+                // <https://tc39.es/ecma262/#sec-ecmascript-function-objects-construct-argumentslist-newtarget>
+                let result = result?;
+                if result.is_object() {
+                    Ok(result)
+                } else {
+                    Ok(this)
+                }
             }
         }
     }
@@ -0,0 +1,199 @@
+//! A textual disassembly of the bytecode of a [`CodeBlock`] and of its nested functions.
+//!
+//! Unlike the [`Display`][std::fmt::Display] implementation of [`CodeBlock`], which is meant
+//! for tracing, the disassembly is meant to be read: jump targets and the ranges of exception
+//! handlers are shown as labels, and the code of each statement is annotated with its position
+//! in the source.
+
+use super::{CodeBlock, Opcode};
+use std::{collections::BTreeMap, convert::TryInto, fmt::Write, mem::size_of};
+
+/// The width of the opcode column.
+const OPCODE_WIDTH: usize = 24;
+
+impl CodeBlock {
+    /// Disassembles the bytecode of this code block and of all the functions nested in it.
+    ///
+    /// Each function starts with a header that gives its path from this code block, like
+    /// `<main>/0/1` for the second function of the first function, and its attributes. It is
+    /// followed by its instructions, its exception handlers, its literals, the names it looks up
+    /// at runtime and its nested functions. The start of the code of each statement is annotated
+    /// with its `line:column` position in the source.
+    pub fn disassemble(&self) -> String {
+        let mut output = String::new();
+        self.disassemble_into(&mut output, &self.name.to_string());
+        output
+    }
+
+    fn disassemble_into(&self, output: &mut String, path: &str) {
+        let labels = self.labels();
+        let label = |address: u32| format!("L{}", labels[&address]);
+
+        let mut attributes = vec![format!("length: {}", self.length)];
+        for (set, attribute) in [
+            (self.strict, "strict"),
+            (self.constructor, "constructor"),
+            (self.generator, "generator"),
+            (self.asynchronous, "async"),
+        ] {
+            if set {
+                attributes.push(attribute.to_owned());
+            }
+        }
+        writeln!(
+            output,
+            "function {} '{}' ({})",
+            path,
+            self.name,
+            attributes.join(", ")
+        )
+        .unwrap();
+
+        output.push_str("  Code:\n");
+        let mut lines = self.line_table.iter().peekable();
+        let mut pc = 0;
+        while pc < self.code.len() {
+            if let Some(index) = labels.get(&(pc as u32)) {
+                writeln!(output, "  L{}:", index).unwrap();
+            }
+            // Statements that emit no code share the address of the next one.
+            let mut position = None;
+            while let Some((_, next)) = lines.next_if(|(start, _)| *start as usize <= pc) {
+                position = Some(next);
+            }
+            if let Some(position) = position {
+                writeln!(output, "    ; {}", position).unwrap();
+            }
+
+            let address = pc;
+            let opcode: Opcode = self.code[pc].try_into().unwrap();
+            let operands = match self.jump_operands(pc) {
+                Some(targets) => {
+                    self.instruction_operands(&mut pc);
+                    match opcode {
+                        Opcode::GeneratorNext => format!(
+                            "return: {}, stack depth: {}",
+                            label(targets[0]),
+                            self.read::<u32>(address + 1 + size_of::<u32>())
+                        ),
+                        Opcode::GeneratorDelegateNext => format!(
+                            "done: {}, return: {}, stack depth: {}",
+                            label(targets[0]),
+                            label(targets[1]),
+                            self.read::<u32>(address + 1 + 2 * size_of::<u32>())
+                        ),
+                        _ => label(targets[0]),
+                    }
+                }
+                None => self.instruction_operands(&mut pc),
+            };
+            let line = format!(
+                "    {:04}  {:<width$} {}",
+                address,
+                opcode.as_str(),
+                operands,
+                width = OPCODE_WIDTH
+            );
+            writeln!(output, "{}", line.trim_end()).unwrap();
+        }
+        if let Some(index) = labels.get(&(self.code.len() as u32)) {
+            writeln!(output, "  L{}:", index).unwrap();
+        }
+
+        if !self.handlers.is_empty() {
+            output.push_str("  Handlers:\n");
+            for handler in &self.handlers {
+                writeln!(
+                    output,
+                    "    {}..{} -> {} (stack depth: {}, environment depth: {})",
+                    label(handler.start),
+                    label(handler.end),
+                    label(handler.address),
+                    handler.stack_depth,
+                    handler.environment_depth
+                )
+                .unwrap();
+            }
+        }
+
+        if !self.literals.is_empty() {
+            output.push_str("  Literals:\n");
+            for (i, value) in self.literals.iter().enumerate() {
+                writeln!(
+                    output,
+                    "    {:04}: <{}> {}",
+                    i,
+                    value.type_of(),
+                    value.display()
+                )
+                .unwrap();
+            }
+        }
+
+        if !self.variables.is_empty() {
+            output.push_str("  Names:\n");
+            for (i, name) in self.variables.iter().enumerate() {
+                writeln!(output, "    {:04}: {}", i, name).unwrap();
+            }
+        }
+
+        if !self.functions.is_empty() {
+            output.push_str("  Functions:\n");
+            for (i, code) in self.functions.iter().enumerate() {
+                writeln!(output, "    {:04}: {}/{} '{}'", i, path, i, code.name).unwrap();
+            }
+        }
+
+        for (i, code) in self.functions.iter().enumerate() {
+            output.push('\n');
+            code.disassemble_into(output, &format!("{}/{}", path, i));
+        }
+    }
+
+    /// Numbers the jump targets and the bounds of the exception handlers in address order.
+    fn labels(&self) -> BTreeMap<u32, usize> {
+        let mut labels = BTreeMap::new();
+        let mut pc = 0;
+        while pc < self.code.len() {
+            if let Some(targets) = self.jump_operands(pc) {
+                labels.extend(targets.into_iter().map(|target| (target, 0)));
+            }
+            self.instruction_operands(&mut pc);
+        }
+        for handler in &self.handlers {
+            labels.extend([(handler.start, 0), (handler.end, 0), (handler.address, 0)]);
+        }
+        for (index, label) in labels.values_mut().enumerate() {
+            *label = index;
+        }
+        labels
+    }
+
+    /// Gets the addresses that the instruction at `pc` can jump to, if it is a jump.
+    fn jump_operands(&self, pc: usize) -> Option<Vec<u32>> {
+        let opcode: Opcode = self.code[pc].try_into().unwrap();
+        let count = match opcode {
+            Opcode::Jump
+            | Opcode::JumpIfFalse
+            | Opcode::JumpIfTrue
+            | Opcode::JumpIfNotUndefined
+            | Opcode::JumpIfNotLessThan
+            | Opcode::JumpIfNotLessThanOrEq
+            | Opcode::LogicalAnd
+            | Opcode::LogicalOr
+            | Opcode::Coalesce
+            | Opcode::Case
+            | Opcode::Default
+            | Opcode::ForInLoopInitIterator
+            | Opcode::IteratorStep
+            | Opcode::GeneratorNext => 1,
+            Opcode::GeneratorDelegateNext => 2,
+            _ => return None,
+        };
+        Some(
+            (0..count)
+                .map(|i| self.read::<u32>(pc + 1 + i * size_of::<u32>()))
+                .collect(),
+        )
+    }
+}
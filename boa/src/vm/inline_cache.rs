@@ -0,0 +1,238 @@
+//! Inline caches of the instructions that get and set properties by name.
+//!
+//! Each `GetPropertyByName` and `SetPropertyByName` instruction has a cache of its own, which
+//! remembers the shapes of the objects it saw and the slots their properties were found at. As
+//! long as an object has one of these shapes, the property is read or written at its slot without
+//! hashing its key. An object whose properties are added, removed or reconfigured gets a new
+//! shape, which invalidates the entries for its old one.
+//!
+//! A cache starts empty, is monomorphic while it has one entry and polymorphic while it has up to
+//! [`MAX_ENTRIES`]. An instruction that misses its cache too often becomes megamorphic, and from
+//! then on always takes the generic path.
+
+use crate::{
+    gc::{Finalize, Trace},
+    interner::Sym,
+    object::{shape::Shape, JsObject},
+    property::PropertyKey,
+    JsString, JsValue,
+};
+use gc::GcCell;
+
+/// The number of entries after which a cache becomes megamorphic.
+const MAX_ENTRIES: usize = 4;
+
+/// The number of misses after which a cache becomes megamorphic, which bounds the work spent on
+/// properties that cannot be cached, like accessors or the properties of exotic objects.
+const MAX_MISSES: u8 = 8;
+
+/// The inline cache of an instruction.
+#[derive(Debug, Clone, Default, Trace, Finalize)]
+pub(crate) struct InlineCache {
+    state: GcCell<State>,
+}
+
+#[derive(Debug, Clone, Trace, Finalize)]
+enum State {
+    Entries { entries: Vec<Entry>, misses: u8 },
+    Megamorphic,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self::Entries {
+            entries: Vec::new(),
+            misses: 0,
+        }
+    }
+}
+
+/// Where a data property was found, for objects of a given shape.
+#[derive(Debug, Clone, Trace, Finalize)]
+struct Entry {
+    /// The shape of the object the property is looked up on.
+    shape: Shape,
+    /// The prototypes that the lookup went through, up to the one that has the property, with
+    /// their shapes. It is empty for an own property.
+    prototypes: Box<[(JsObject, Shape)]>,
+    /// The slot of the property in the object that has it.
+    slot: usize,
+}
+
+impl InlineCache {
+    /// Gets the value of the data property `name` of `object`.
+    ///
+    /// Returns `None` if the property is not a data property of `object` or of an ordinary
+    /// prototype, in which case it must be got with `[[Get]]`.
+    pub(crate) fn get(&self, object: &JsObject, name: Sym) -> Option<JsValue> {
+        if let Some(value) = self.load(object) {
+            return Some(value);
+        }
+        let key = self.key_on_miss(name)?;
+        let (entry, value) = lookup(object, &key)?;
+        self.insert(entry);
+        Some(value)
+    }
+
+    /// Sets the value of the own writable data property `name` of `object` to `value`.
+    ///
+    /// Returns `false` if `object` has no such property, in which case it must be set with
+    /// `[[Set]]`.
+    pub(crate) fn set(&self, object: &JsObject, name: Sym, value: &JsValue) -> bool {
+        if self.store(object, value) {
+            return true;
+        }
+        let key = match self.key_on_miss(name) {
+            Some(key) => key,
+            None => return false,
+        };
+        let entry = {
+            let object = object.borrow();
+            if !object.has_ordinary_set() {
+                return false;
+            }
+            match object.properties().string_property_slot(&key) {
+                Some((slot, property)) if property.writable() == Some(true) => Entry {
+                    shape: object.properties().shape().clone(),
+                    prototypes: Box::default(),
+                    slot,
+                },
+                _ => return false,
+            }
+        };
+        object
+            .borrow_mut()
+            .properties_mut()
+            .set_string_property_value_at(entry.slot, value.clone());
+        self.insert(entry);
+        true
+    }
+
+    /// Returns `true` if the cache is megamorphic.
+    #[cfg(test)]
+    pub(crate) fn is_megamorphic(&self) -> bool {
+        matches!(*self.state.borrow(), State::Megamorphic)
+    }
+
+    /// Gets the number of entries of the cache.
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        match &*self.state.borrow() {
+            State::Entries { entries, .. } => entries.len(),
+            State::Megamorphic => 0,
+        }
+    }
+
+    /// Reads the property of `object` if an entry matches its shape and its prototypes.
+    fn load(&self, object: &JsObject) -> Option<JsValue> {
+        let state = self.state.borrow();
+        let entries = match &*state {
+            State::Entries { entries, .. } => entries,
+            State::Megamorphic => return None,
+        };
+
+        let mut holder = object.borrow();
+        if !holder.has_ordinary_get() {
+            return None;
+        }
+        let shape = holder.properties().shape();
+        let entry = entries.iter().find(|entry| entry.shape == *shape)?;
+        for (prototype, shape) in entry.prototypes.iter() {
+            if !matches!(holder.prototype(), Some(parent) if JsObject::equals(parent, prototype)) {
+                return None;
+            }
+            holder = prototype.borrow();
+            if !holder.has_ordinary_get() || holder.properties().shape() != shape {
+                return None;
+            }
+        }
+        holder
+            .properties()
+            .string_property_at(entry.slot)?
+            .value()
+            .cloned()
+    }
+
+    /// Writes the own property of `object` if an entry matches its shape.
+    fn store(&self, object: &JsObject, value: &JsValue) -> bool {
+        let state = self.state.borrow();
+        let entries = match &*state {
+            State::Entries { entries, .. } => entries,
+            State::Megamorphic => return false,
+        };
+
+        let mut object = object.borrow_mut();
+        if !object.has_ordinary_set() {
+            return false;
+        }
+        let shape = object.properties().shape();
+        match entries.iter().find(|entry| entry.shape == *shape) {
+            Some(entry) => {
+                object
+                    .properties_mut()
+                    .set_string_property_value_at(entry.slot, value.clone());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Counts a miss, and gets the key to look up if the cache is not megamorphic.
+    fn key_on_miss(&self, name: Sym) -> Option<JsString> {
+        let mut state = self.state.borrow_mut();
+        match &mut *state {
+            State::Entries { misses, .. } if *misses < MAX_MISSES => *misses += 1,
+            _ => {
+                *state = State::Megamorphic;
+                return None;
+            }
+        }
+        match &PropertyKey::from(name) {
+            PropertyKey::String(key) => Some(key.clone()),
+            _ => None,
+        }
+    }
+
+    /// Adds `entry` to the cache, replacing the entry for the same shape, whose prototypes are out
+    /// of date.
+    fn insert(&self, entry: Entry) {
+        let mut state = self.state.borrow_mut();
+        if let State::Entries { entries, .. } = &mut *state {
+            entries.retain(|old| old.shape != entry.shape);
+            if entries.len() == MAX_ENTRIES {
+                *state = State::Megamorphic;
+            } else {
+                entries.push(entry);
+            }
+        }
+    }
+}
+
+/// Looks up the data property `key` of `object` and of its prototypes, as long as they are all
+/// ordinary for `[[Get]]`.
+fn lookup(object: &JsObject, key: &JsString) -> Option<(Entry, JsValue)> {
+    let shape = object.borrow().properties().shape().clone();
+    let mut prototypes = Vec::new();
+    let mut holder = object.clone();
+    loop {
+        let parent = {
+            let holder = holder.borrow();
+            if !holder.has_ordinary_get() {
+                return None;
+            }
+            if let Some((slot, property)) = holder.properties().string_property_slot(key) {
+                let value = property.value()?.clone();
+                let entry = Entry {
+                    shape,
+                    prototypes: prototypes.into_boxed_slice(),
+                    slot,
+                };
+                return Some((entry, value));
+            }
+            holder.prototype().clone()?
+        };
+        let parent_shape = parent.borrow().properties().shape().clone();
+        prototypes.push((parent.clone(), parent_shape));
+        holder = parent;
+    }
+}
@@ -3,20 +3,42 @@
 //! plus an interpreter to execute those instructions
 
 use crate::{
-    builtins::Array, environment::lexical_environment::VariableScope, BoaProfiler, Context,
-    JsResult, JsValue,
+    builtins::{
+        error::set_error_position,
+        generator::Generator,
+        iterable::{iterable_to_list, IteratorRecord},
+        Array, ForInIterator,
+    },
+    environment::{
+        declarative_environment_record::DeclarativeEnvironmentRecord,
+        lexical_environment::{Environment, VariableScope},
+    },
+    object::{FunctionBuilder, JsObject},
+    property::{PropertyDescriptor, PropertyKey},
+    value::Numeric,
+    BoaProfiler, Context, JsBigInt, JsResult, JsString, JsValue,
 };
 
 mod call_frame;
 mod code_block;
+mod disassembler;
+mod inline_cache;
 mod opcode;
+mod optimizer;
+mod serialization;
 
 pub use call_frame::CallFrame;
+pub(crate) use call_frame::{Job, ResumeKind, SuspendedFrame};
 pub use code_block::CodeBlock;
 pub use code_block::JsVmFunction;
+pub(crate) use code_block::{BindingKind, Handler};
+pub(crate) use inline_cache::InlineCache;
 pub use opcode::Opcode;
+pub use optimizer::OptimizationLevel;
+pub use serialization::{BytecodeError, BYTECODE_VERSION};
 
-use std::{convert::TryInto, mem::size_of, time::Instant};
+use gc::{Gc, GcCell};
+use std::{collections::VecDeque, convert::TryInto, mem::size_of, time::Instant};
 
 use self::code_block::Readable;
 
@@ -25,10 +47,17 @@ mod tests;
 /// Virtual Machine.
 #[derive(Debug)]
 pub struct Vm {
-    pub(crate) frame: Option<Box<CallFrame>>,
+    /// The frames of the functions being executed, innermost last.
+    pub(crate) frames: Vec<CallFrame>,
     pub(crate) stack: Vec<JsValue>,
+    /// The frame that the last run was suspended in, by `yield` or `await`.
+    pub(crate) suspended: Option<SuspendedFrame>,
+    /// The jobs to run once the current script has run to completion.
+    pub(crate) jobs: VecDeque<Job>,
     pub(crate) trace: bool,
     pub(crate) stack_size_limit: usize,
+    /// How much the scripts compiled by the context are optimized.
+    pub(crate) optimization_level: OptimizationLevel,
 }
 
 impl Vm {
@@ -62,26 +91,42 @@ impl Vm {
 
     #[inline]
     pub(crate) fn frame(&self) -> &CallFrame {
-        self.frame.as_ref().unwrap()
+        self.frames.last().unwrap()
     }
 
     #[inline]
     pub(crate) fn frame_mut(&mut self) -> &mut CallFrame {
-        self.frame.as_mut().unwrap()
+        self.frames.last_mut().unwrap()
     }
 
     #[inline]
-    pub(crate) fn push_frame(&mut self, mut frame: CallFrame) {
-        let prev = self.frame.take();
-        frame.prev = prev;
-        self.frame = Some(Box::new(frame));
+    pub(crate) fn push_frame(&mut self, frame: CallFrame) {
+        self.frames.push(frame);
     }
 
+    /// Gets the environment `depth` levels above the current one.
     #[inline]
-    pub(crate) fn pop_frame(&mut self) -> Option<Box<CallFrame>> {
-        let mut current = self.frame.take()?;
-        self.frame = current.prev.take();
-        Some(current)
+    pub(crate) fn environment(&self, depth: u32) -> Environment {
+        let mut environment = &self.frame().environment;
+        for _ in 0..depth {
+            environment = environment
+                .get_outer_environment_ref()
+                .expect("environment depth out of range");
+        }
+        environment.clone()
+    }
+
+    /// Gets the iterator and the `next` method in the top two values of the stack, without
+    /// popping them.
+    #[inline]
+    fn iterator_record(&self) -> IteratorRecord {
+        let len = self.stack.len();
+        IteratorRecord::new(self.stack[len - 2].clone(), self.stack[len - 1].clone())
+    }
+
+    #[inline]
+    pub(crate) fn pop_frame(&mut self) -> Option<CallFrame> {
+        self.frames.pop()
     }
 }
 
@@ -120,6 +165,25 @@ impl Context {
                 self.vm.push(first);
                 self.vm.push(second);
             }
+            Opcode::Dup2 => {
+                let second = self.vm.pop();
+                let first = self.vm.pop();
+
+                self.vm.push(first.clone());
+                self.vm.push(second.clone());
+                self.vm.push(first);
+                self.vm.push(second);
+            }
+            Opcode::RotateLeft => {
+                let n = self.vm.read::<u8>() as usize;
+                let len = self.vm.stack.len();
+                self.vm.stack[len - n..].rotate_left(1);
+            }
+            Opcode::RotateRight => {
+                let n = self.vm.read::<u8>() as usize;
+                let len = self.vm.stack.len();
+                self.vm.stack[len - n..].rotate_right(1);
+            }
             Opcode::PushUndefined => self.vm.push(JsValue::undefined()),
             Opcode::PushNull => self.vm.push(JsValue::null()),
             Opcode::PushTrue => self.vm.push(true),
@@ -153,13 +217,26 @@ impl Context {
             Opcode::PushEmptyObject => self.vm.push(self.construct_object()),
             Opcode::PushNewArray => {
                 let count = self.vm.read::<u32>();
-                let mut elements = Vec::with_capacity(count as usize);
-                for _ in 0..count {
-                    elements.push(self.vm.pop());
-                }
+                let elements = self
+                    .vm
+                    .stack
+                    .split_off(self.vm.stack.len() - count as usize);
                 let array = Array::create_array_from_list(elements, self);
                 self.vm.push(array);
             }
+            Opcode::PushValueToArray => {
+                let value = self.vm.pop();
+                let array = self.vm.pop();
+                Array::add_to_array_object(&array, &[value], self)?;
+                self.vm.push(array);
+            }
+            Opcode::PushIteratorToArray => {
+                let iterable = self.vm.pop();
+                let array = self.vm.pop();
+                let values = iterable_to_list(self, iterable, None)?;
+                Array::add_to_array_object(&array, &values, self)?;
+                self.vm.push(array);
+            }
             Opcode::Add => bin_op!(add),
             Opcode::Sub => bin_op!(sub),
             Opcode::Mul => bin_op!(mul),
@@ -251,50 +328,166 @@ impl Context {
                 };
                 self.vm.push(value);
             }
+            Opcode::Inc => {
+                let value = self.vm.pop();
+                let value = self.increment(&value, true)?;
+                self.vm.push(value);
+            }
+            Opcode::Dec => {
+                let value = self.vm.pop();
+                let value = self.increment(&value, false)?;
+                self.vm.push(value);
+            }
+            Opcode::ToNumeric => {
+                let value = self.vm.pop().to_numeric(self)?;
+                self.vm.push(value);
+            }
             Opcode::DefVar => {
                 let index = self.vm.read::<u32>();
-                let name = self.vm.frame().code.variables[index as usize].clone();
+                let name = self.vm.frame().code.variables[index as usize];
 
-                self.create_mutable_binding(name.as_ref(), false, VariableScope::Function)?;
+                if !self.has_binding(name)? {
+                    self.create_mutable_binding(name, false, VariableScope::Function)?;
+                    self.initialize_binding(name, JsValue::undefined())?;
+                }
             }
             Opcode::DefLet => {
                 let index = self.vm.read::<u32>();
-                let name = self.vm.frame().code.variables[index as usize].clone();
+                let name = self.vm.frame().code.variables[index as usize];
 
-                self.create_mutable_binding(name.as_ref(), false, VariableScope::Block)?;
+                self.create_mutable_binding(name, false, VariableScope::Block)?;
             }
             Opcode::DefConst => {
                 let index = self.vm.read::<u32>();
-                let name = self.vm.frame().code.variables[index as usize].clone();
+                let name = self.vm.frame().code.variables[index as usize];
 
-                self.create_immutable_binding(name.as_ref(), false, VariableScope::Block)?;
+                self.create_immutable_binding(name, false, VariableScope::Block)?;
             }
             Opcode::InitLexical => {
                 let index = self.vm.read::<u32>();
                 let value = self.vm.pop();
-                let name = self.vm.frame().code.variables[index as usize].clone();
+                let name = self.vm.frame().code.variables[index as usize];
 
-                self.initialize_binding(&name, value)?;
+                self.initialize_binding(name, value)?;
             }
             Opcode::GetName => {
                 let index = self.vm.read::<u32>();
-                let name = self.vm.frame().code.variables[index as usize].clone();
+                let name = self.vm.frame().code.variables[index as usize];
 
-                let value = self.get_binding_value(&name)?;
+                let value = self.get_binding_value(name)?;
+                self.vm.push(value);
+            }
+            Opcode::GetNameOrUndefined => {
+                let index = self.vm.read::<u32>();
+                let name = self.vm.frame().code.variables[index as usize];
+
+                let value = if self.has_binding(name)? {
+                    self.get_binding_value(name)?
+                } else {
+                    JsValue::undefined()
+                };
                 self.vm.push(value);
             }
             Opcode::SetName => {
                 let index = self.vm.read::<u32>();
                 let value = self.vm.pop();
-                let name = self.vm.frame().code.variables[index as usize].clone();
+                let name = self.vm.frame().code.variables[index as usize];
 
-                if self.has_binding(name.as_ref())? {
+                if self.has_binding(name)? {
                     // Binding already exists
-                    self.set_mutable_binding(name.as_ref(), value, self.strict())?;
+                    let strict = self.vm.frame().code.strict;
+                    self.set_mutable_binding(name, value, strict)?;
                 } else {
-                    self.create_mutable_binding(name.as_ref(), true, VariableScope::Function)?;
-                    self.initialize_binding(name.as_ref(), value)?;
+                    self.create_mutable_binding(name, true, VariableScope::Function)?;
+                    self.initialize_binding(name, value)?;
+                }
+            }
+            Opcode::GetLocal => {
+                let depth = self.vm.read::<u32>();
+                let slot = self.vm.read::<u32>();
+
+                let environment = self.vm.environment(depth);
+                let value = environment
+                    .as_declarative()
+                    .expect("slots are only resolved to declarative environments")
+                    .get_binding_value_by_slot(slot as usize, self)?;
+                self.vm.push(value);
+            }
+            Opcode::SetLocal => {
+                let depth = self.vm.read::<u32>();
+                let slot = self.vm.read::<u32>();
+                let value = self.vm.pop();
+
+                let environment = self.vm.environment(depth);
+                let strict = self.vm.frame().code.strict;
+                environment
+                    .as_declarative()
+                    .expect("slots are only resolved to declarative environments")
+                    .set_mutable_binding_by_slot(slot as usize, value, strict, self)?;
+            }
+            Opcode::IncLocal | Opcode::DecLocal => {
+                let depth = self.vm.read::<u32>();
+                let slot = self.vm.read::<u32>() as usize;
+
+                let environment = self.vm.environment(depth);
+                let environment = environment
+                    .as_declarative()
+                    .expect("slots are only resolved to declarative environments");
+                let value = environment.get_binding_value_by_slot(slot, self)?;
+                let value = self.increment(&value, opcode == Opcode::IncLocal)?;
+                let strict = self.vm.frame().code.strict;
+                environment.set_mutable_binding_by_slot(slot, value, strict, self)?;
+            }
+            Opcode::InitLocal => {
+                let slot = self.vm.read::<u32>();
+                let value = self.vm.pop();
+
+                self.vm
+                    .frame()
+                    .environment
+                    .as_declarative()
+                    .expect("slots are only resolved to declarative environments")
+                    .initialize_binding_by_slot(slot as usize, value);
+            }
+            Opcode::PushDeclarativeEnvironment => {
+                let index = self.vm.read::<u32>();
+
+                let outer = self.vm.frame().environment.clone();
+                let environment = DeclarativeEnvironmentRecord::new(Some(outer));
+                for &(name, kind) in self.vm.frame().code.scopes[index as usize].iter() {
+                    kind.create(name, &environment);
                 }
+                let frame = self.vm.frame_mut();
+                frame.environment = environment.into();
+                frame.environment_depth += 1;
+            }
+            Opcode::CopyDeclarativeEnvironment => {
+                let index = self.vm.read::<u32>();
+
+                let code = self.vm.frame().code.clone();
+                let current = self.vm.frame().environment.clone();
+                let declarative = current
+                    .as_declarative()
+                    .expect("block scopes are declarative environments");
+                let environment =
+                    DeclarativeEnvironmentRecord::new(current.get_outer_environment());
+                for (slot, &(name, kind)) in code.scopes[index as usize].iter().enumerate() {
+                    kind.create(name, &environment);
+                    let value = declarative.get_binding_value_by_slot(slot, self)?;
+                    environment.initialize_binding_by_slot(slot, value);
+                }
+                self.vm.frame_mut().environment = environment.into();
+            }
+            Opcode::PopEnvironment => {
+                let outer = self
+                    .vm
+                    .frame()
+                    .environment
+                    .get_outer_environment()
+                    .expect("cannot pop the outermost environment");
+                let frame = self.vm.frame_mut();
+                frame.environment = outer;
+                frame.environment_depth -= 1;
             }
             Opcode::Jump => {
                 let address = self.vm.read::<u32>();
@@ -306,18 +499,46 @@ impl Context {
                     self.vm.frame_mut().pc = address as usize;
                 }
             }
+            Opcode::JumpIfNotLessThan | Opcode::JumpIfNotLessThanOrEq => {
+                let address = self.vm.read::<u32>();
+                let rhs = self.vm.pop();
+                let lhs = self.vm.pop();
+                let condition = if opcode == Opcode::JumpIfNotLessThan {
+                    lhs.lt(&rhs, self)?
+                } else {
+                    lhs.le(&rhs, self)?
+                };
+                if !condition {
+                    self.vm.frame_mut().pc = address as usize;
+                }
+            }
             Opcode::JumpIfTrue => {
                 let address = self.vm.read::<u32>();
                 if self.vm.pop().to_boolean() {
                     self.vm.frame_mut().pc = address as usize;
                 }
             }
+            Opcode::JumpIfNotUndefined => {
+                let address = self.vm.read::<u32>();
+                if !self.vm.stack.last().expect("stack is empty").is_undefined() {
+                    self.vm.frame_mut().pc = address as usize;
+                }
+            }
+            Opcode::ValueNotNullOrUndefined => {
+                let value = self.vm.stack.last().expect("stack is empty");
+                if value.is_null() {
+                    return Err(self.construct_type_error("Cannot destructure 'null' value"));
+                }
+                if value.is_undefined() {
+                    return Err(self.construct_type_error("Cannot destructure 'undefined' value"));
+                }
+            }
             Opcode::LogicalAnd => {
                 let exit = self.vm.read::<u32>();
                 let lhs = self.vm.pop();
                 if !lhs.to_boolean() {
                     self.vm.frame_mut().pc = exit as usize;
-                    self.vm.push(false);
+                    self.vm.push(lhs);
                 }
             }
             Opcode::LogicalOr => {
@@ -325,7 +546,7 @@ impl Context {
                 let lhs = self.vm.pop();
                 if lhs.to_boolean() {
                     self.vm.frame_mut().pc = exit as usize;
-                    self.vm.push(true);
+                    self.vm.push(lhs);
                 }
             }
             Opcode::Coalesce => {
@@ -342,6 +563,7 @@ impl Context {
             }
             Opcode::GetPropertyByName => {
                 let index = self.vm.read::<u32>();
+                let cache = self.vm.read::<u32>();
 
                 let value = self.vm.pop();
                 let object = if let Some(object) = value.as_object() {
@@ -350,8 +572,12 @@ impl Context {
                     value.to_object(self)?
                 };
 
-                let name = self.vm.frame().code.variables[index as usize].clone();
-                let result = object.get(name, self)?;
+                let code = &self.vm.frame().code;
+                let name = code.variables[index as usize];
+                let result = match code.inline_caches[cache as usize].get(&object, name) {
+                    Some(result) => result,
+                    None => object.get(name, self)?,
+                };
 
                 self.vm.push(result)
             }
@@ -371,6 +597,7 @@ impl Context {
             }
             Opcode::SetPropertyByName => {
                 let index = self.vm.read::<u32>();
+                let cache = self.vm.read::<u32>();
 
                 let object = self.vm.pop();
                 let value = self.vm.pop();
@@ -380,9 +607,11 @@ impl Context {
                     object.to_object(self)?
                 };
 
-                let name = self.vm.frame().code.variables[index as usize].clone();
-
-                object.set(name, value, true, self)?;
+                let code = &self.vm.frame().code;
+                let name = code.variables[index as usize];
+                if !code.inline_caches[cache as usize].set(&object, name, &value) {
+                    object.set(name, value, true, self)?;
+                }
             }
             Opcode::SetPropertyByValue => {
                 let object = self.vm.pop();
@@ -397,11 +626,90 @@ impl Context {
                 let key = key.to_property_key(self)?;
                 object.set(key, value, true, self)?;
             }
+            Opcode::DefineOwnPropertyByName | Opcode::DefineOwnPropertyByValue => {
+                let value = self.vm.pop();
+                let key = self.property_key_operand(opcode == Opcode::DefineOwnPropertyByName)?;
+                let object = self.vm.pop().to_object(self)?;
+
+                object.__define_own_property__(
+                    key,
+                    PropertyDescriptor::builder()
+                        .value(value)
+                        .writable(true)
+                        .enumerable(true)
+                        .configurable(true)
+                        .build(),
+                    self,
+                )?;
+            }
+            Opcode::SetPropertyGetterByName | Opcode::SetPropertyGetterByValue => {
+                let getter = self.vm.pop();
+                let key = self.property_key_operand(opcode == Opcode::SetPropertyGetterByName)?;
+                let object = self.vm.pop().to_object(self)?;
+
+                let set = object
+                    .__get_own_property__(&key, self)?
+                    .as_ref()
+                    .and_then(PropertyDescriptor::set)
+                    .cloned();
+                object.__define_own_property__(
+                    key,
+                    PropertyDescriptor::builder()
+                        .maybe_get(getter.as_object().cloned())
+                        .maybe_set(set)
+                        .enumerable(true)
+                        .configurable(true)
+                        .build(),
+                    self,
+                )?;
+            }
+            Opcode::SetPropertySetterByName | Opcode::SetPropertySetterByValue => {
+                let setter = self.vm.pop();
+                let key = self.property_key_operand(opcode == Opcode::SetPropertySetterByName)?;
+                let object = self.vm.pop().to_object(self)?;
+
+                let get = object
+                    .__get_own_property__(&key, self)?
+                    .as_ref()
+                    .and_then(PropertyDescriptor::get)
+                    .cloned();
+                object.__define_own_property__(
+                    key,
+                    PropertyDescriptor::builder()
+                        .maybe_get(get)
+                        .maybe_set(setter.as_object().cloned())
+                        .enumerable(true)
+                        .configurable(true)
+                        .build(),
+                    self,
+                )?;
+            }
+            Opcode::CopyDataProperties => {
+                let count = self.vm.read::<u32>();
+                let excluded_keys = self
+                    .vm
+                    .stack
+                    .split_off(self.vm.stack.len() - count as usize);
+                let excluded_keys = excluded_keys
+                    .iter()
+                    .map(|key| key.to_property_key(self))
+                    .collect::<JsResult<Vec<_>>>()?;
+                let source = self.vm.pop();
+                let object = self.vm.pop();
+
+                object
+                    .to_object(self)?
+                    .copy_data_properties(&source, excluded_keys, self)?;
+                self.vm.push(object);
+            }
             Opcode::DeletePropertyByName => {
                 let index = self.vm.read::<u32>();
-                let key = self.vm.frame().code.variables[index as usize].clone();
+                let key = self.vm.frame().code.variables[index as usize];
                 let object = self.vm.pop();
                 let result = object.to_object(self)?.__delete__(&key.into(), self)?;
+                if !result && self.vm.frame().code.strict {
+                    return Err(self.construct_type_error("Cannot delete property"));
+                }
                 self.vm.push(result);
             }
             Opcode::DeletePropertyByValue => {
@@ -410,6 +718,9 @@ impl Context {
                 let result = object
                     .to_object(self)?
                     .__delete__(&key.to_property_key(self)?, self)?;
+                if !result && self.vm.frame().code.strict {
+                    return Err(self.construct_type_error("Cannot delete property"));
+                }
                 self.vm.push(result);
             }
             Opcode::Throw => {
@@ -443,54 +754,272 @@ impl Context {
                 let function = JsVmFunction::new(code, environment, self);
                 self.vm.push(function);
             }
-            Opcode::Call => {
+            Opcode::Call | Opcode::CallSpread => {
                 if self.vm.stack_size_limit <= self.vm.stack.len() {
                     return Err(self.construct_range_error("Maximum call stack size exceeded"));
                 }
-                let argc = self.vm.read::<u32>();
+                let args = self.arguments_operand(opcode == Opcode::Call)?;
                 let func = self.vm.pop();
                 let this = self.vm.pop();
-                let mut args = Vec::with_capacity(argc as usize);
-                for _ in 0..argc {
-                    args.push(self.vm.pop());
-                }
 
                 let object = match func {
                     JsValue::Object(ref object) if object.is_callable() => object.clone(),
                     _ => return Err(self.construct_type_error("not a callable function")),
                 };
 
-                let result = object.call_internal(&this, &args, self, false)?;
+                let result = object.call_internal(&this, &args, self, true)?;
 
                 self.vm.push(result);
             }
-            Opcode::Return => {
-                let exit = self.vm.frame().exit_on_return;
+            Opcode::New | Opcode::NewSpread => {
+                if self.vm.stack_size_limit <= self.vm.stack.len() {
+                    return Err(self.construct_range_error("Maximum call stack size exceeded"));
+                }
+                let args = self.arguments_operand(opcode == Opcode::New)?;
+                let func = self.vm.pop();
+
+                let object = match func {
+                    JsValue::Object(ref object) if object.is_constructor() => object.clone(),
+                    _ => return Err(self.construct_type_error("not a constructor")),
+                };
 
-                let _ = self.vm.pop_frame();
+                let result = object.construct_internal(&args, &func, self, true)?;
 
-                if exit {
+                self.vm.push(result);
+            }
+            Opcode::Return => {
+                let value = self.vm.pop();
+                let frame = self.vm.pop_frame().expect("no frame to return from");
+                self.vm.stack.truncate(frame.fp);
+                self.vm.push(value);
+
+                if frame.exit_on_return {
                     return Ok(true);
                 }
             }
+            Opcode::InitIterator => {
+                let iterable = self.vm.pop();
+                let iterator = iterable.get_iterator(self, None, None)?;
+                self.vm.push(iterator.iterator_object().clone());
+                self.vm.push(iterator.next_function().clone());
+            }
+            Opcode::ForInLoopInitIterator => {
+                let address = self.vm.read::<u32>();
+                let object = self.vm.pop();
+                if object.is_null_or_undefined() {
+                    self.vm.frame_mut().pc = address as usize;
+                } else {
+                    let object = object.to_object(self)?;
+                    let iterator =
+                        ForInIterator::create_for_in_iterator(JsValue::new(object), self);
+                    let next_function = iterator
+                        .get_property("next")
+                        .as_ref()
+                        .map(PropertyDescriptor::expect_value)
+                        .cloned()
+                        .ok_or_else(|| {
+                            self.construct_type_error("Could not find property `next`")
+                        })?;
+                    self.vm.push(iterator);
+                    self.vm.push(next_function);
+                }
+            }
+            Opcode::IteratorNext => {
+                let next = self.vm.iterator_record().next(self)?;
+                if next.done {
+                    self.vm.push(JsValue::undefined());
+                } else {
+                    self.vm.push(next.value);
+                }
+            }
+            Opcode::IteratorStep => {
+                let address = self.vm.read::<u32>();
+                let next = self.vm.iterator_record().next(self)?;
+                if next.done {
+                    self.vm.frame_mut().pc = address as usize;
+                } else {
+                    self.vm.push(next.value);
+                }
+            }
+            Opcode::IteratorToArray => {
+                let iterator = self.vm.iterator_record();
+                let mut values = Vec::new();
+                loop {
+                    let next = iterator.next(self)?;
+                    if next.done {
+                        break;
+                    }
+                    values.push(next.value);
+                }
+                let array = Array::create_array_from_list(values, self);
+                self.vm.push(array);
+            }
+            Opcode::IteratorClose => {
+                let iterator = self.vm.iterator_record();
+                self.vm.pop();
+                self.vm.pop();
+                iterator.close(Ok(JsValue::undefined()), self)?;
+            }
+            Opcode::ConcatToString => {
+                let count = self.vm.read::<u32>();
+                let values = self
+                    .vm
+                    .stack
+                    .split_off(self.vm.stack.len() - count as usize);
+                let strings = values
+                    .iter()
+                    .map(|value| value.to_string(self))
+                    .collect::<JsResult<Vec<_>>>()?;
+                let strings: Vec<&[u16]> = strings.iter().map(|string| &**string).collect();
+                self.vm.push(JsString::concat_array(&strings));
+            }
+            Opcode::Yield | Opcode::Await => {
+                let value = self.vm.pop();
+                let frame = self.vm.pop_frame().expect("no frame to suspend");
+                let stack = self.vm.stack.split_off(frame.fp);
+                self.vm.suspended = Some(SuspendedFrame { frame, stack });
+                self.vm.push(value);
+                return Ok(true);
+            }
+            Opcode::GeneratorNext => {
+                let address = self.vm.read::<u32>();
+                let stack_depth = self.vm.read::<u32>();
+                let kind = ResumeKind::from_value(&self.vm.pop());
+                let received = self.vm.pop();
+                match kind {
+                    ResumeKind::Normal => self.vm.push(received),
+                    ResumeKind::Throw => return Err(received),
+                    ResumeKind::Return => self.generator_return(received, address, stack_depth),
+                }
+            }
+            Opcode::GeneratorDelegateNext => {
+                let done_address = self.vm.read::<u32>();
+                let address = self.vm.read::<u32>();
+                let stack_depth = self.vm.read::<u32>();
+                let kind = ResumeKind::from_value(&self.vm.pop());
+                let received = self.vm.pop();
+                let iterator = self.vm.iterator_record();
+                let iterator_object = iterator.iterator_object().clone();
+
+                let result = match kind {
+                    ResumeKind::Normal => {
+                        self.call(iterator.next_function(), &iterator_object, &[received])?
+                    }
+                    ResumeKind::Throw => {
+                        let throw = iterator_object.get_method("throw", self)?;
+                        if let Some(throw) = throw {
+                            throw.call(&iterator_object, &[received], self)?
+                        } else {
+                            iterator.close(Ok(JsValue::undefined()), self)?;
+                            return Err(self.construct_type_error(
+                                "the iterator of yield* does not have a throw method",
+                            ));
+                        }
+                    }
+                    ResumeKind::Return => {
+                        let r#return = iterator_object.get_method("return", self)?;
+                        if let Some(r#return) = r#return {
+                            r#return.call(&iterator_object, &[received], self)?
+                        } else {
+                            self.generator_return(received, address, stack_depth);
+                            return Ok(false);
+                        }
+                    }
+                };
+
+                if !result.is_object() {
+                    return Err(self.construct_type_error("iterator result is not an object"));
+                }
+                let done = result.get_field("done", self)?.to_boolean();
+                let value = result.get_field("value", self)?;
+                if !done {
+                    self.vm.push(value);
+                } else if kind == ResumeKind::Return {
+                    self.generator_return(value, address, stack_depth);
+                } else {
+                    self.vm.push(value);
+                    self.vm.frame_mut().pc = done_address as usize;
+                }
+            }
         }
 
         Ok(false)
     }
 
-    /// Unwind the stack.
-    fn unwind(&mut self) -> bool {
-        let mut fp = 0;
-        while let Some(mut frame) = self.vm.frame.take() {
-            fp = frame.fp;
+    /// Adds one to `value`, or subtracts one if `increment` is `false`, after converting it with
+    /// `ToNumeric`.
+    fn increment(&mut self, value: &JsValue, increment: bool) -> JsResult<JsValue> {
+        Ok(match value.to_numeric(self)? {
+            Numeric::Number(number) if increment => JsValue::new(number + 1.0),
+            Numeric::Number(number) => JsValue::new(number - 1.0),
+            Numeric::BigInt(bigint) if increment => {
+                JsValue::new(JsBigInt::add(&bigint, &JsBigInt::one()))
+            }
+            Numeric::BigInt(bigint) => JsValue::new(JsBigInt::sub(&bigint, &JsBigInt::one())),
+        })
+    }
+
+    /// Drops the values on the stack of the current frame above `stack_depth`, and jumps to
+    /// `address` to return `value` from a resumed generator.
+    fn generator_return(&mut self, value: JsValue, address: u32, stack_depth: u32) {
+        let frame = self.vm.frame_mut();
+        frame.pc = address as usize;
+        let stack_len = frame.fp + stack_depth as usize;
+        self.vm.stack.truncate(stack_len);
+        self.vm.push(value);
+    }
+
+    /// Reads the key operand of a property instruction: the name operand if `by_name` is `true`,
+    /// otherwise the key on the stack.
+    fn property_key_operand(&mut self, by_name: bool) -> JsResult<PropertyKey> {
+        if by_name {
+            let index = self.vm.read::<u32>();
+            Ok(self.vm.frame().code.variables[index as usize].into())
+        } else {
+            self.vm.pop().to_property_key(self)
+        }
+    }
+
+    /// Pops the arguments of a call instruction: `argc` values if `from_stack` is `true`,
+    /// otherwise the elements of the array on the stack.
+    fn arguments_operand(&mut self, from_stack: bool) -> JsResult<Vec<JsValue>> {
+        if from_stack {
+            let argc = self.vm.read::<u32>();
+            Ok(self.vm.stack.split_off(self.vm.stack.len() - argc as usize))
+        } else {
+            self.vm.pop().create_list_from_array_like(&[], self)
+        }
+    }
+
+    /// Unwind the stack to the innermost exception handler of this run that protects the
+    /// instruction at `pc` of the current frame.
+    ///
+    /// Returns `true` if there is no such handler, and the exception has to be propagated to the
+    /// caller of the run.
+    fn unwind(&mut self, mut pc: usize) -> bool {
+        while let Some(frame) = self.vm.frames.last_mut() {
+            if let Some(handler) = frame.code.handler(pc).copied() {
+                for _ in handler.environment_depth..frame.environment_depth {
+                    frame.environment = frame
+                        .environment
+                        .get_outer_environment()
+                        .expect("handlers do not outlive the environments of their frame");
+                }
+                frame.environment_depth = handler.environment_depth;
+                frame.pc = handler.address as usize;
+                let stack_len = frame.fp + handler.stack_depth as usize;
+                self.vm.stack.truncate(stack_len);
+                return false;
+            }
+
+            let frame = self.vm.pop_frame().expect("frame was just checked");
+            self.vm.stack.truncate(frame.fp);
             if frame.exit_on_return {
                 break;
             }
 
-            self.vm.frame = frame.prev.take();
-        }
-        while self.vm.stack.len() > fp {
-            let _ = self.vm.pop();
+            // The caller is suspended right after its call instruction.
+            pc = self.vm.frame().pc - 1;
         }
         true
     }
@@ -528,8 +1057,8 @@ impl Context {
             );
         }
 
-        self.vm.frame_mut().pc = 0;
         while self.vm.frame().pc < self.vm.frame().code.code.len() {
+            let start_pc = self.vm.frame().pc;
             let result = if self.vm.trace {
                 let mut pc = self.vm.frame().pc;
                 let opcode: Opcode = self.vm.frame().code.read::<u8>(pc).try_into().unwrap();
@@ -574,7 +1103,11 @@ impl Context {
                     }
                 }
                 Err(e) => {
-                    let should_exit = self.unwind();
+                    if let Some(position) = self.vm.frame().code.position(start_pc) {
+                        set_error_position(&e, position);
+                    }
+
+                    let should_exit = self.unwind(start_pc);
                     if should_exit {
                         return Err(e);
                     } else {
@@ -608,10 +1141,165 @@ impl Context {
             println!("\n");
         }
 
-        if self.vm.stack.is_empty() {
-            return Ok(JsValue::undefined());
+        let frame = self.vm.pop_frame().expect("no frame to finish");
+        let result = if self.vm.stack.len() > frame.fp {
+            self.vm.pop()
+        } else {
+            JsValue::undefined()
+        };
+        self.vm.stack.truncate(frame.fp);
+
+        Ok(result)
+    }
+
+    /// Resumes the suspended `frame` with `value` and the kind of the resumption, and runs it
+    /// until it returns or is suspended again.
+    ///
+    /// Returns the result of the run, and the frame if it was suspended again.
+    pub(crate) fn resume_frame(
+        &mut self,
+        suspended: SuspendedFrame,
+        value: JsValue,
+        kind: ResumeKind,
+    ) -> (JsResult<JsValue>, Option<SuspendedFrame>) {
+        let SuspendedFrame { mut frame, stack } = suspended;
+        frame.fp = self.vm.stack.len();
+        self.vm.stack.extend(stack);
+        self.vm.push(value);
+        self.vm.push(kind as i32);
+
+        // Names that are not resolved to slots are looked up from the function environment.
+        let mut environment = frame.environment.clone();
+        for _ in 0..frame.environment_depth {
+            environment = environment
+                .get_outer_environment()
+                .expect("block environments are inside the function environment");
+        }
+        self.push_environment(environment);
+        self.vm.push_frame(frame);
+
+        let result = self.run();
+
+        self.pop_environment();
+
+        (result, self.vm.suspended.take())
+    }
+
+    /// Continues an async function after it ran until an `await` or until it returned.
+    ///
+    /// If it is suspended, its frame is stored back in `generator` until the awaited value is
+    /// settled.
+    // TODO: settle the promise of the async function once there is a `Promise` builtin.
+    fn continue_async(
+        &mut self,
+        generator: JsObject,
+        result: JsResult<JsValue>,
+        frame: Option<SuspendedFrame>,
+    ) {
+        match (result, frame) {
+            (Ok(awaited), Some(frame)) => {
+                Generator::set_frame(&generator, Some(frame));
+                self.await_value(generator, awaited);
+            }
+            _ => Generator::set_frame(&generator, None),
         }
+    }
 
-        Ok(self.vm.pop())
+    /// Queues a job to resume the async function suspended in `generator` once `awaited` is
+    /// settled.
+    pub(crate) fn await_value(&mut self, generator: JsObject, awaited: JsValue) {
+        let then = match &awaited {
+            JsValue::Object(object) => object.get("then", self),
+            _ => Ok(JsValue::undefined()),
+        };
+        let job = match then {
+            Ok(then) if then.is_callable() => Job::AwaitThenable {
+                generator,
+                thenable: awaited,
+                then,
+            },
+            Ok(_) => Job::Resume {
+                generator,
+                value: awaited,
+                kind: ResumeKind::Normal,
+            },
+            Err(error) => Job::Resume {
+                generator,
+                value: error,
+                kind: ResumeKind::Throw,
+            },
+        };
+        self.vm.jobs.push_back(job);
+    }
+
+    /// Runs the queued jobs of async functions, until there are none left.
+    pub(crate) fn run_jobs(&mut self) {
+        while let Some(job) = self.vm.jobs.pop_front() {
+            match job {
+                Job::Resume {
+                    generator,
+                    value,
+                    kind,
+                } => {
+                    if let Some(frame) = Generator::take_frame(&generator) {
+                        let (result, frame) = self.resume_frame(frame, value, kind);
+                        self.continue_async(generator, result, frame);
+                    }
+                }
+                Job::AwaitThenable {
+                    generator,
+                    thenable,
+                    then,
+                } => {
+                    // The function is resumed at most once, by the first of the callbacks
+                    // that is called.
+                    let settled = Gc::new(GcCell::new(false));
+                    let on_fulfilled = self.settle_callback(
+                        generator.clone(),
+                        settled.clone(),
+                        ResumeKind::Normal,
+                    );
+                    let on_rejected =
+                        self.settle_callback(generator.clone(), settled.clone(), ResumeKind::Throw);
+                    if let Err(error) =
+                        self.call(&then, &thenable, &[on_fulfilled.into(), on_rejected.into()])
+                    {
+                        if !std::mem::replace(&mut *settled.borrow_mut(), true) {
+                            self.vm.jobs.push_back(Job::Resume {
+                                generator,
+                                value: error,
+                                kind: ResumeKind::Throw,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Creates a callback for the `then` method of an awaited thenable, which queues a job to
+    /// resume the async function of `generator` with the value it is called with.
+    fn settle_callback(
+        &mut self,
+        generator: JsObject,
+        settled: Gc<GcCell<bool>>,
+        kind: ResumeKind,
+    ) -> JsObject {
+        FunctionBuilder::closure_with_captures(
+            self,
+            |_, args, (generator, settled, kind), context| {
+                if !std::mem::replace(&mut *settled.borrow_mut(), true) {
+                    context.vm.jobs.push_back(Job::Resume {
+                        generator: generator.clone(),
+                        value: args.get(0).cloned().unwrap_or_default(),
+                        kind: *kind,
+                    });
+                }
+                Ok(JsValue::undefined())
+            },
+            (generator, settled, kind),
+        )
+        .length(1)
+        .build()
     }
 }
@@ -25,6 +25,27 @@ pub enum Opcode {
     /// Stack: v1, v2 **=>** v2, v1
     Swap,
 
+    /// Push a copy of the top two values on the stack.
+    ///
+    /// Operands:
+    ///
+    /// Stack: v1, v2 **=>** v1, v2, v1, v2
+    Dup2,
+
+    /// Move the `n`th value from the top of the stack to the top.
+    ///
+    /// Operands: n: `u8`
+    ///
+    /// Stack: v1, v2, ..., vn **=>** v2, ..., vn, v1
+    RotateLeft,
+
+    /// Move the top value of the stack below the next `n - 1` values.
+    ///
+    /// Operands: n: `u8`
+    ///
+    /// Stack: v1, ..., vn-1, vn **=>** vn, v1, ..., vn-1
+    RotateRight,
+
     /// Push integer `0` on the stack.
     ///
     /// Operands:
@@ -137,9 +158,25 @@ pub enum Opcode {
     ///
     /// Operands: n: `u32`
     ///
-    /// Stack: v1, v1, ... vn **=>** [v1, v2, ..., vn]
+    /// Stack: v1, v2, ... vn **=>** [v1, v2, ..., vn]
     PushNewArray,
 
+    /// Append a value to the end of an array.
+    ///
+    /// Operands:
+    ///
+    /// Stack: array, value **=>** array
+    PushValueToArray,
+
+    /// Append all the values produced by an iterable to the end of an array.
+    ///
+    /// Like `[...iterable]`
+    ///
+    /// Operands:
+    ///
+    /// Stack: array, iterable **=>** array
+    PushIteratorToArray,
+
     /// Binary `+` operator.
     ///
     /// Operands:
@@ -231,6 +268,27 @@ pub enum Opcode {
     /// Stack: value **=>** ~value
     BitNot,
 
+    /// Unary `++` operator, applied to a value converted with `ToNumeric`.
+    ///
+    /// Operands:
+    ///
+    /// Stack: value **=>** (ToNumeric(value) + 1)
+    Inc,
+
+    /// Unary `--` operator, applied to a value converted with `ToNumeric`.
+    ///
+    /// Operands:
+    ///
+    /// Stack: value **=>** (ToNumeric(value) - 1)
+    Dec,
+
+    /// Convert the value on top of the stack to a number or a bigint.
+    ///
+    /// Operands:
+    ///
+    /// Stack: value **=>** ToNumeric(value)
+    ToNumeric,
+
     /// Binary `in` operator.
     ///
     /// Operands:
@@ -303,7 +361,8 @@ pub enum Opcode {
 
     /// Binary logical `&&` operator.
     ///
-    /// This is a short-circit operator, if the `lhs` value is `false`, then it jumps to `exit` address.
+    /// This is a short-circit operator, if the `lhs` value is falsy, then it jumps to `exit`
+    /// address, leaving `lhs` on the stack as the result.
     ///
     /// Operands: exit: `u32`
     ///
@@ -312,7 +371,8 @@ pub enum Opcode {
 
     /// Binary logical `||` operator.
     ///
-    /// This is a short-circit operator, if the `lhs` value is `true`, then it jumps to `exit` address.
+    /// This is a short-circit operator, if the `lhs` value is truthy, then it jumps to `exit`
+    /// address, leaving `lhs` on the stack as the result.
     ///
     /// Operands: exit: `u32`
     ///
@@ -366,6 +426,8 @@ pub enum Opcode {
 
     /// Declate `var` type variable.
     ///
+    /// The variable is initialized to `undefined`, unless a binding with the same name exists.
+    ///
     /// Operands: name_index: `u32`
     ///
     /// Stack: **=>**
@@ -399,6 +461,16 @@ pub enum Opcode {
     /// Stack: **=>** value
     GetName,
 
+    /// Find a binding on the environment chain and push its value, or push `undefined` if the
+    /// binding does not exist.
+    ///
+    /// Like `typeof name`
+    ///
+    /// Operands: name_index: `u32`
+    ///
+    /// Stack: **=>** value
+    GetNameOrUndefined,
+
     /// Find a binding on the environment chain and assign its value.
     ///
     /// Operands: name_index: `u32`
@@ -406,11 +478,60 @@ pub enum Opcode {
     /// Stack: value **=>**
     SetName,
 
+    /// Push the value of a binding that was resolved at compile time.
+    ///
+    /// The binding is in the environment `depth` levels above the current one.
+    ///
+    /// Operands: depth: `u32`, slot: `u32`
+    ///
+    /// Stack: **=>** value
+    GetLocal,
+
+    /// Assign to a binding that was resolved at compile time.
+    ///
+    /// The binding is in the environment `depth` levels above the current one.
+    ///
+    /// Operands: depth: `u32`, slot: `u32`
+    ///
+    /// Stack: value **=>**
+    SetLocal,
+
+    /// Initialize a lexical binding of the current environment that was resolved at compile time.
+    ///
+    /// Operands: slot: `u32`
+    ///
+    /// Stack: value **=>**
+    InitLocal,
+
+    /// Enter a block scope, by pushing a new declarative environment with its bindings.
+    ///
+    /// Operands: scope_index: `u32`
+    ///
+    /// Stack: **=>**
+    PushDeclarativeEnvironment,
+
+    /// Replace the current block scope with a copy of it, for the next iteration of a `for` loop.
+    ///
+    /// The bindings of the copy have the current values of the bindings of the scope, so that
+    /// closures created in an iteration keep seeing the values of that iteration.
+    ///
+    /// Operands: scope_index: `u32`
+    ///
+    /// Stack: **=>**
+    CopyDeclarativeEnvironment,
+
+    /// Leave a block scope, by popping the current environment.
+    ///
+    /// Operands:
+    ///
+    /// Stack: **=>**
+    PopEnvironment,
+
     /// Get a property by name from an object an push it on the stack.
     ///
     /// Like `object.name`
     ///
-    /// Operands: name_index: `u32`
+    /// Operands: name_index: `u32`, cache_index: `u32`
     ///
     /// Stack: object **=>** value
     GetPropertyByName,
@@ -428,7 +549,7 @@ pub enum Opcode {
     ///
     /// Like `object.name = value`
     ///
-    /// Operands: name_index: `u32`
+    /// Operands: name_index: `u32`, cache_index: `u32`
     ///
     /// Stack: value, object **=>**
     SetPropertyByName,
@@ -442,6 +563,69 @@ pub enum Opcode {
     /// Stack: value, key, object **=>**
     SetPropertyByValue,
 
+    /// Defines an own data property by name of an object.
+    ///
+    /// Like `{ name: value }`
+    ///
+    /// Operands: name_index: `u32`
+    ///
+    /// Stack: object, value **=>**
+    DefineOwnPropertyByName,
+
+    /// Defines an own data property by value of an object.
+    ///
+    /// Like `{ [key]: value }`
+    ///
+    /// Operands:
+    ///
+    /// Stack: object, key, value **=>**
+    DefineOwnPropertyByValue,
+
+    /// Defines the getter of an accessor property by name of an object.
+    ///
+    /// Like `{ get name() {} }`
+    ///
+    /// Operands: name_index: `u32`
+    ///
+    /// Stack: object, getter **=>**
+    SetPropertyGetterByName,
+
+    /// Defines the getter of an accessor property by value of an object.
+    ///
+    /// Like `{ get [key]() {} }`
+    ///
+    /// Operands:
+    ///
+    /// Stack: object, key, getter **=>**
+    SetPropertyGetterByValue,
+
+    /// Defines the setter of an accessor property by name of an object.
+    ///
+    /// Like `{ set name(value) {} }`
+    ///
+    /// Operands: name_index: `u32`
+    ///
+    /// Stack: object, setter **=>**
+    SetPropertySetterByName,
+
+    /// Defines the setter of an accessor property by value of an object.
+    ///
+    /// Like `{ set [key](value) {} }`
+    ///
+    /// Operands:
+    ///
+    /// Stack: object, key, setter **=>**
+    SetPropertySetterByValue,
+
+    /// Copies the own enumerable properties of `source` to `object`, except the excluded keys.
+    ///
+    /// Like `{ ...source }`
+    ///
+    /// Operands: excluded_key_count: `u32`
+    ///
+    /// Stack: object, source, excluded_key_1, ... excluded_key_n **=>** object
+    CopyDataProperties,
+
     /// Deletes a property by name of an object.
     ///
     /// Like `delete object.key.`
@@ -488,6 +672,25 @@ pub enum Opcode {
     /// [truthy]: https://developer.mozilla.org/en-US/docs/Glossary/Truthy
     JumpIfTrue,
 
+    /// Conditional jump to address.
+    ///
+    /// If the value on top of the stack is not `undefined` then jump to `address`. The value is
+    /// not popped.
+    ///
+    /// Operands: address: `u32`
+    ///
+    /// Stack: value **=>** value
+    JumpIfNotUndefined,
+
+    /// Throw a `TypeError` if the value on top of the stack is `null` or `undefined`.
+    ///
+    /// Like the check that precedes destructuring.
+    ///
+    /// Operands:
+    ///
+    /// Stack: value **=>** value
+    ValueNotNullOrUndefined,
+
     /// Throw exception
     ///
     /// Operands:
@@ -535,12 +738,167 @@ pub enum Opcode {
     ///
     /// Operands: argc: `u32`
     ///
-    /// Stack: `func`, `this`, `arg1`, `arg2`,...`argn` **=>**
+    /// Stack: `this`, `func`, `arg1`, `arg2`,...`argn` **=>** result
     Call,
 
+    /// Call a function with the arguments in an array.
+    ///
+    /// Like `func(...args)`
+    ///
+    /// Operands:
+    ///
+    /// Stack: `this`, `func`, `arguments_array` **=>** result
+    CallSpread,
+
+    /// Call a constructor.
+    ///
+    /// Operands: argc: `u32`
+    ///
+    /// Stack: `func`, `arg1`, `arg2`,...`argn` **=>** result
+    New,
+
+    /// Call a constructor with the arguments in an array.
+    ///
+    /// Like `new func(...args)`
+    ///
+    /// Operands:
+    ///
+    /// Stack: `func`, `arguments_array` **=>** result
+    NewSpread,
+
     /// Return from a function.
     Return,
 
+    /// Get the iterator of an iterable, and its `next` method.
+    ///
+    /// Operands:
+    ///
+    /// Stack: iterable **=>** iterator, next_method
+    InitIterator,
+
+    /// Get the iterator for the keys of a `for...in` loop, and its `next` method.
+    ///
+    /// If the object is `null` or `undefined`, there are no keys, and it jumps to `address`.
+    ///
+    /// Operands: address: `u32`
+    ///
+    /// Stack: object **=>** iterator, next_method
+    ForInLoopInitIterator,
+
+    /// Push the next value of an iterator, or `undefined` if the iterator is done.
+    ///
+    /// Operands:
+    ///
+    /// Stack: iterator, next_method **=>** iterator, next_method, value
+    IteratorNext,
+
+    /// Push the next value of an iterator, or jump to `address` if the iterator is done.
+    ///
+    /// Operands: address: `u32`
+    ///
+    /// Stack: iterator, next_method **=>** iterator, next_method, value
+    IteratorStep,
+
+    /// Push an array with the remaining values of an iterator.
+    ///
+    /// Operands:
+    ///
+    /// Stack: iterator, next_method **=>** iterator, next_method, array
+    IteratorToArray,
+
+    /// Close an iterator that is not done, by calling its `return` method.
+    ///
+    /// Operands:
+    ///
+    /// Stack: iterator, next_method **=>**
+    IteratorClose,
+
+    /// Concatenate the string conversions of the top `count` values.
+    ///
+    /// Like template literals.
+    ///
+    /// Operands: count: `u32`
+    ///
+    /// Stack: v1, v2, ..., vn **=>** string
+    ConcatToString,
+
+    /// Suspend the frame of a generator, which yields `value`.
+    ///
+    /// The frame is resumed with a value and the kind of the resumption on the stack.
+    ///
+    /// Operands:
+    ///
+    /// Stack: value **=>** received, resume_kind
+    Yield,
+
+    /// Suspend the frame of an async function until `value` is settled.
+    ///
+    /// The frame is resumed with a value and the kind of the resumption on the stack.
+    ///
+    /// Operands:
+    ///
+    /// Stack: value **=>** received, resume_kind
+    Await,
+
+    /// Continue a resumed frame according to the kind of the resumption.
+    ///
+    /// A normal resumption pushes `received`, and a throw resumption throws it. A return
+    /// resumption drops the values on the stack above `stack_depth` and jumps to `address`
+    /// with `received` on top, to return it.
+    ///
+    /// Operands: address: `u32`, stack_depth: `u32`
+    ///
+    /// Stack: received, resume_kind **=>** received
+    GeneratorNext,
+
+    /// Forward a resumption of a generator to the iterator of a `yield*` expression.
+    ///
+    /// The `next`, `throw` or `return` method of the iterator is called with `received`. If the
+    /// iterator is not done, the value of its result is pushed, to be yielded. If it is done,
+    /// the value of its result is pushed and it jumps to `done_address`, or if it was a return
+    /// resumption, to `address` as for `GeneratorNext`.
+    ///
+    /// Operands: done_address: `u32`, address: `u32`, stack_depth: `u32`
+    ///
+    /// Stack: iterator, next_method, received, resume_kind **=>** iterator, next_method, value
+    GeneratorDelegateNext,
+
+    /// Increment a binding that was resolved at compile time.
+    ///
+    /// It is emitted by the optimizer for `GetLocal`, `Inc`, `SetLocal` on the same binding.
+    ///
+    /// Operands: depth: `u32`, slot: `u32`
+    ///
+    /// Stack: **=>**
+    IncLocal,
+
+    /// Decrement a binding that was resolved at compile time.
+    ///
+    /// It is emitted by the optimizer for `GetLocal`, `Dec`, `SetLocal` on the same binding.
+    ///
+    /// Operands: depth: `u32`, slot: `u32`
+    ///
+    /// Stack: **=>**
+    DecLocal,
+
+    /// Jump to `address` if `lhs < rhs` is false.
+    ///
+    /// It is emitted by the optimizer for `LessThan`, `JumpIfFalse`.
+    ///
+    /// Operands: address: `u32`
+    ///
+    /// Stack: lhs, rhs **=>**
+    JumpIfNotLessThan,
+
+    /// Jump to `address` if `lhs <= rhs` is false.
+    ///
+    /// It is emitted by the optimizer for `LessThanOrEq`, `JumpIfFalse`.
+    ///
+    /// Operands: address: `u32`
+    ///
+    /// Stack: lhs, rhs **=>**
+    JumpIfNotLessThanOrEq,
+
     /// No-operation instruction, does nothing.
     ///
     /// Operands:
@@ -566,6 +924,9 @@ impl Opcode {
             Opcode::Pop => "Pop",
             Opcode::Dup => "Dup",
             Opcode::Swap => "Swap",
+            Opcode::Dup2 => "Dup2",
+            Opcode::RotateLeft => "RotateLeft",
+            Opcode::RotateRight => "RotateRight",
             Opcode::PushZero => "PushZero",
             Opcode::PushOne => "PushOne",
             Opcode::PushInt8 => "PushInt8",
@@ -582,6 +943,8 @@ impl Opcode {
             Opcode::PushLiteral => "PushLiteral",
             Opcode::PushEmptyObject => "PushEmptyObject",
             Opcode::PushNewArray => "PushNewArray",
+            Opcode::PushValueToArray => "PushValueToArray",
+            Opcode::PushIteratorToArray => "PushIteratorToArray",
             Opcode::Add => "Add",
             Opcode::Sub => "Sub",
             Opcode::Div => "Div",
@@ -595,6 +958,9 @@ impl Opcode {
             Opcode::BitAnd => "BitAnd",
             Opcode::BitXor => "BitXor",
             Opcode::BitNot => "BitNot",
+            Opcode::Inc => "Inc",
+            Opcode::Dec => "Dec",
+            Opcode::ToNumeric => "ToNumeric",
             Opcode::In => "In",
             Opcode::Eq => "Eq",
             Opcode::StrictEq => "StrictEq",
@@ -618,16 +984,32 @@ impl Opcode {
             Opcode::DefConst => "DefConst",
             Opcode::InitLexical => "InitLexical",
             Opcode::GetName => "GetName",
+            Opcode::GetNameOrUndefined => "GetNameOrUndefined",
             Opcode::SetName => "SetName",
+            Opcode::GetLocal => "GetLocal",
+            Opcode::SetLocal => "SetLocal",
+            Opcode::InitLocal => "InitLocal",
+            Opcode::PushDeclarativeEnvironment => "PushDeclarativeEnvironment",
+            Opcode::CopyDeclarativeEnvironment => "CopyDeclarativeEnvironment",
+            Opcode::PopEnvironment => "PopEnvironment",
             Opcode::GetPropertyByName => "GetPropertyByName",
             Opcode::GetPropertyByValue => "GetPropertyByValue",
             Opcode::SetPropertyByName => "SetPropertyByName",
             Opcode::SetPropertyByValue => "SetPropertyByValue",
+            Opcode::DefineOwnPropertyByName => "DefineOwnPropertyByName",
+            Opcode::DefineOwnPropertyByValue => "DefineOwnPropertyByValue",
+            Opcode::SetPropertyGetterByName => "SetPropertyGetterByName",
+            Opcode::SetPropertyGetterByValue => "SetPropertyGetterByValue",
+            Opcode::SetPropertySetterByName => "SetPropertySetterByName",
+            Opcode::SetPropertySetterByValue => "SetPropertySetterByValue",
+            Opcode::CopyDataProperties => "CopyDataProperties",
             Opcode::DeletePropertyByName => "DeletePropertyByName",
             Opcode::DeletePropertyByValue => "DeletePropertyByValue",
             Opcode::Jump => "Jump",
             Opcode::JumpIfFalse => "JumpIfFalse",
             Opcode::JumpIfTrue => "JumpIfTrue",
+            Opcode::JumpIfNotUndefined => "JumpIfNotUndefined",
+            Opcode::ValueNotNullOrUndefined => "ValueNotNullOrUndefined",
             Opcode::Throw => "Throw",
             Opcode::ToBoolean => "ToBoolean",
             Opcode::This => "This",
@@ -635,7 +1017,25 @@ impl Opcode {
             Opcode::Default => "Default",
             Opcode::GetFunction => "GetFunction",
             Opcode::Call => "Call",
+            Opcode::CallSpread => "CallSpread",
+            Opcode::New => "New",
+            Opcode::NewSpread => "NewSpread",
             Opcode::Return => "Return",
+            Opcode::InitIterator => "InitIterator",
+            Opcode::ForInLoopInitIterator => "ForInLoopInitIterator",
+            Opcode::IteratorNext => "IteratorNext",
+            Opcode::IteratorStep => "IteratorStep",
+            Opcode::IteratorToArray => "IteratorToArray",
+            Opcode::IteratorClose => "IteratorClose",
+            Opcode::ConcatToString => "ConcatToString",
+            Opcode::Yield => "Yield",
+            Opcode::Await => "Await",
+            Opcode::GeneratorNext => "GeneratorNext",
+            Opcode::GeneratorDelegateNext => "GeneratorDelegateNext",
+            Opcode::IncLocal => "IncLocal",
+            Opcode::DecLocal => "DecLocal",
+            Opcode::JumpIfNotLessThan => "JumpIfNotLessThan",
+            Opcode::JumpIfNotLessThanOrEq => "JumpIfNotLessThanOrEq",
             Opcode::Nop => "Nop",
         }
     }
@@ -0,0 +1,618 @@
+//! An optimizer that rewrites the bytecode of a [`CodeBlock`] after it is compiled.
+//!
+//! The code is decoded into a list of instructions whose jump targets are indices in the list, so
+//! that the passes can remove and replace instructions without keeping track of addresses. A
+//! removed instruction stands for the next instruction that is kept: a jump to it, a bound of an
+//! exception handler or an entry of the line table moves to that instruction when the code is
+//! encoded again.
+//!
+//! The passes only rewrite sequences of instructions that no jump lands in the middle of, so the
+//! code behaves the same for every path that reaches it.
+
+use super::{
+    serialization::{operands, Operand},
+    CodeBlock, Opcode,
+};
+use crate::{Context, JsValue};
+use gc::Gc;
+use std::convert::TryFrom;
+
+/// How much the bytecode of a script is optimized after it is compiled.
+///
+/// Each level runs the passes of the levels below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum OptimizationLevel {
+    /// The code is run as the compiler emits it.
+    None,
+    /// Folds operations on literals, removes values that are pushed only to be popped, threads
+    /// jumps to jumps and removes unreachable code.
+    Basic,
+    /// Also replaces common sequences of instructions by superinstructions.
+    #[default]
+    Full,
+}
+
+/// An instruction of the code being optimized.
+#[derive(Debug, Clone)]
+struct Instruction {
+    opcode: Opcode,
+    /// The operands, in the order they follow the opcode. Addresses are indices of instructions.
+    operands: Vec<u64>,
+    /// Whether a pass removed the instruction.
+    removed: bool,
+}
+
+impl Instruction {
+    fn new(opcode: Opcode, operands: Vec<u64>) -> Self {
+        Self {
+            opcode,
+            operands,
+            removed: false,
+        }
+    }
+
+    /// Gets the indices of the operands that are jump targets.
+    fn targets(&self) -> impl Iterator<Item = usize> {
+        operands(self.opcode)
+            .iter()
+            .enumerate()
+            .filter(|(_, operand)| matches!(operand, Operand::Address))
+            .map(|(index, _)| index)
+    }
+}
+
+impl CodeBlock {
+    /// Optimizes the code of this code block and of all the functions nested in it.
+    ///
+    /// Operations are folded with `context`, so that they give the same results as in the vm.
+    pub(crate) fn optimize(&mut self, level: OptimizationLevel, context: &mut Context) {
+        if level == OptimizationLevel::None {
+            return;
+        }
+
+        // Code with an address that is not the one of an instruction is left as it is.
+        if let Some(mut optimizer) = Optimizer::new(self) {
+            optimizer.fold_constants(context);
+            optimizer.remove_unused_values();
+            optimizer.thread_jumps();
+            optimizer.remove_dead_code();
+            optimizer.remove_jumps_to_next();
+            if level >= OptimizationLevel::Full {
+                optimizer.fuse_instructions();
+            }
+            optimizer.finish();
+        }
+
+        self.functions = self
+            .functions
+            .iter()
+            .map(|function| {
+                let mut function = CodeBlock::clone(function);
+                function.optimize(level, context);
+                Gc::new(function)
+            })
+            .collect();
+    }
+}
+
+/// The state of the optimization of a code block.
+#[derive(Debug)]
+struct Optimizer<'a> {
+    code_block: &'a mut CodeBlock,
+    instructions: Vec<Instruction>,
+    /// The start, end and handler address of each exception handler, as instruction indices.
+    handlers: Vec<[usize; 3]>,
+    /// The instruction index of each entry of the line table.
+    line_table: Vec<usize>,
+    /// Whether a jump or an exception handler can enter the code at each instruction.
+    labels: Vec<bool>,
+}
+
+impl<'a> Optimizer<'a> {
+    /// Decodes the code of `code_block`.
+    ///
+    /// Returns `None` if the code cannot be decoded, or if a jump, an exception handler or the
+    /// line table refers to an address which is not the start of an instruction or the end of
+    /// the code.
+    fn new(code_block: &'a mut CodeBlock) -> Option<Self> {
+        let mut indices = vec![usize::MAX; code_block.code.len() + 1];
+        let mut instructions = Vec::new();
+        let mut pc = 0;
+        while pc < code_block.code.len() {
+            indices[pc] = instructions.len();
+            let opcode = Opcode::try_from(code_block.code[pc]).ok()?;
+            pc += 1;
+            let size: usize = operands(opcode).iter().map(|operand| operand.size()).sum();
+            if pc + size > code_block.code.len() {
+                return None;
+            }
+            let operands = operands(opcode)
+                .iter()
+                .map(|operand| {
+                    let value = match operand.size() {
+                        1 => u64::from(code_block.read::<u8>(pc)),
+                        2 => u64::from(code_block.read::<u16>(pc)),
+                        4 => u64::from(code_block.read::<u32>(pc)),
+                        _ => code_block.read::<u64>(pc),
+                    };
+                    pc += operand.size();
+                    value
+                })
+                .collect();
+            instructions.push(Instruction::new(opcode, operands));
+        }
+        indices[code_block.code.len()] = instructions.len();
+        let index = |address: u64| {
+            usize::try_from(address)
+                .ok()
+                .and_then(|address| indices.get(address))
+                .copied()
+                .filter(|&index| index != usize::MAX)
+        };
+
+        for instruction in &mut instructions {
+            for operand in instruction.targets().collect::<Vec<_>>() {
+                instruction.operands[operand] = index(instruction.operands[operand])? as u64;
+            }
+        }
+        let mut handlers = Vec::with_capacity(code_block.handlers.len());
+        for handler in &code_block.handlers {
+            handlers.push([
+                index(handler.start.into())?,
+                index(handler.end.into())?,
+                index(handler.address.into())?,
+            ]);
+        }
+        let line_table = code_block
+            .line_table
+            .iter()
+            .map(|&(pc, _)| index(pc.into()))
+            .collect::<Option<_>>()?;
+
+        let labels = vec![false; instructions.len() + 1];
+        Some(Self {
+            code_block,
+            instructions,
+            handlers,
+            line_table,
+            labels,
+        })
+    }
+
+    /// Gets the first instruction at or after `index` that is kept.
+    fn next(&self, index: usize) -> usize {
+        (index..self.instructions.len())
+            .find(|&index| !self.instructions[index].removed)
+            .unwrap_or(self.instructions.len())
+    }
+
+    /// Marks the instructions that jumps and exception handlers can enter the code at.
+    fn compute_labels(&mut self) {
+        let mut labels = vec![false; self.instructions.len() + 1];
+        for instruction in self.instructions.iter().filter(|i| !i.removed) {
+            for operand in instruction.targets() {
+                labels[self.next(instruction.operands[operand] as usize)] = true;
+            }
+        }
+        for &index in self.handlers.iter().flatten() {
+            labels[self.next(index)] = true;
+        }
+        self.labels = labels;
+    }
+
+    /// Removes the instruction at `index`, which is replaced by the instruction at `into`.
+    fn remove(&mut self, index: usize, into: usize) {
+        self.instructions[index].removed = true;
+        if self.labels[index] {
+            self.labels[into] = true;
+        }
+    }
+
+    /// Gets the value that the instruction at `index` pushes, if it pushes a number, a boolean,
+    /// a string, `null` or `undefined`.
+    fn constant(&self, index: usize) -> Option<JsValue> {
+        let instruction = &self.instructions[index];
+        let operand = instruction.operands.first().copied().unwrap_or_default();
+        Some(match instruction.opcode {
+            Opcode::PushZero => JsValue::new(0),
+            Opcode::PushOne => JsValue::new(1),
+            Opcode::PushInt8 => JsValue::new(i32::from(operand as u8 as i8)),
+            Opcode::PushInt16 => JsValue::new(i32::from(operand as u16 as i16)),
+            Opcode::PushInt32 => JsValue::new(operand as u32 as i32),
+            Opcode::PushRational => JsValue::new(f64::from_bits(operand)),
+            Opcode::PushNaN => JsValue::nan(),
+            Opcode::PushPositiveInfinity => JsValue::positive_infinity(),
+            Opcode::PushNegativeInfinity => JsValue::negative_infinity(),
+            Opcode::PushTrue => JsValue::new(true),
+            Opcode::PushFalse => JsValue::new(false),
+            Opcode::PushNull => JsValue::null(),
+            Opcode::PushUndefined => JsValue::undefined(),
+            // Operations on bigints can take arbitrarily long, so they are left to the vm.
+            Opcode::PushLiteral => Some(self.code_block.literals[operand as usize].clone())
+                .filter(JsValue::is_string)?,
+            _ => return None,
+        })
+    }
+
+    /// Creates the instruction that pushes `value`.
+    fn push_constant(&mut self, value: JsValue) -> Instruction {
+        let integer = |value: i32| match value {
+            0 => Instruction::new(Opcode::PushZero, Vec::new()),
+            1 => Instruction::new(Opcode::PushOne, Vec::new()),
+            x if x as i8 as i32 == x => {
+                Instruction::new(Opcode::PushInt8, vec![u64::from(x as i8 as u8)])
+            }
+            x if x as i16 as i32 == x => {
+                Instruction::new(Opcode::PushInt16, vec![u64::from(x as i16 as u16)])
+            }
+            x => Instruction::new(Opcode::PushInt32, vec![u64::from(x as u32)]),
+        };
+
+        match value {
+            JsValue::Integer(value) => integer(value),
+            JsValue::Rational(value) if value.is_nan() => {
+                Instruction::new(Opcode::PushNaN, Vec::new())
+            }
+            JsValue::Rational(value) if value == f64::INFINITY => {
+                Instruction::new(Opcode::PushPositiveInfinity, Vec::new())
+            }
+            JsValue::Rational(value) if value == f64::NEG_INFINITY => {
+                Instruction::new(Opcode::PushNegativeInfinity, Vec::new())
+            }
+            #[allow(clippy::float_cmp)]
+            JsValue::Rational(value)
+                if value as i32 as f64 == value && !(value == 0.0 && value.is_sign_negative()) =>
+            {
+                integer(value as i32)
+            }
+            JsValue::Rational(value) => {
+                Instruction::new(Opcode::PushRational, vec![value.to_bits()])
+            }
+            JsValue::Boolean(true) => Instruction::new(Opcode::PushTrue, Vec::new()),
+            JsValue::Boolean(false) => Instruction::new(Opcode::PushFalse, Vec::new()),
+            JsValue::Null => Instruction::new(Opcode::PushNull, Vec::new()),
+            JsValue::Undefined => Instruction::new(Opcode::PushUndefined, Vec::new()),
+            value => {
+                let literals = &mut self.code_block.literals;
+                let index = literals
+                    .iter()
+                    .position(|literal| literal.strict_equals(&value))
+                    .unwrap_or_else(|| {
+                        literals.push(value);
+                        literals.len() - 1
+                    });
+                Instruction::new(Opcode::PushLiteral, vec![index as u64])
+            }
+        }
+    }
+
+    /// Replaces operations on constants by the constant they evaluate to, and conditional jumps
+    /// on constants by unconditional jumps.
+    ///
+    /// An operation is only folded if it succeeds, so that errors are still thrown at runtime.
+    fn fold_constants(&mut self, context: &mut Context) {
+        self.compute_labels();
+        let mut kept: Vec<usize> = Vec::new();
+        for index in 0..self.instructions.len() {
+            if self.instructions[index].removed {
+                continue;
+            }
+            let opcode = self.instructions[index].opcode;
+            let folded = match kept[..] {
+                [.., lhs, rhs] if !self.labels[rhs] && !self.labels[index] => {
+                    match (self.constant(lhs), self.constant(rhs)) {
+                        (Some(x), Some(y)) => fold_binary(opcode, &x, &y, context)
+                            .map(|value| (value, vec![lhs, rhs])),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            }
+            .or_else(|| match kept[..] {
+                [.., operand] if !self.labels[index] => self
+                    .constant(operand)
+                    .and_then(|x| fold_unary(opcode, &x, context))
+                    .map(|value| (value, vec![operand])),
+                _ => None,
+            });
+
+            if let Some((value, operands)) = folded {
+                for operand in operands {
+                    kept.pop();
+                    self.remove(operand, index);
+                }
+                self.instructions[index] = self.push_constant(value);
+            }
+
+            // A conditional jump on a constant either always or never jumps.
+            if let (Opcode::JumpIfFalse | Opcode::JumpIfTrue, Some(&condition)) =
+                (opcode, kept.last())
+            {
+                if let Some(condition_value) =
+                    self.constant(condition).filter(|_| !self.labels[index])
+                {
+                    kept.pop();
+                    if condition_value.to_boolean() == (opcode == Opcode::JumpIfTrue) {
+                        self.remove(condition, index);
+                        self.instructions[index].opcode = Opcode::Jump;
+                    } else {
+                        let next = self.next(index + 1);
+                        self.remove(condition, next);
+                        self.remove(index, next);
+                        continue;
+                    }
+                }
+            }
+            kept.push(index);
+        }
+    }
+
+    /// Removes the values that are pushed by `Dup` or a constant, and popped right away.
+    fn remove_unused_values(&mut self) {
+        self.compute_labels();
+        let mut kept: Vec<usize> = Vec::new();
+        for index in 0..self.instructions.len() {
+            if self.instructions[index].removed {
+                continue;
+            }
+            if let (Opcode::Pop, Some(&push)) = (self.instructions[index].opcode, kept.last()) {
+                if !self.labels[index]
+                    && (self.instructions[push].opcode == Opcode::Dup
+                        || self.constant(push).is_some())
+                {
+                    kept.pop();
+                    let next = self.next(index + 1);
+                    self.remove(push, next);
+                    self.remove(index, next);
+                    continue;
+                }
+            }
+            kept.push(index);
+        }
+    }
+
+    /// Makes the jumps to an unconditional jump go to its target instead.
+    fn thread_jumps(&mut self) {
+        let len = self.instructions.len();
+        for index in 0..len {
+            if self.instructions[index].removed {
+                continue;
+            }
+            for operand in self.instructions[index].targets().collect::<Vec<_>>() {
+                let mut target = self.next(self.instructions[index].operands[operand] as usize);
+                // Jumps can form a cycle, like the code of `while (true) {}`.
+                for _ in 0..len {
+                    match self.instructions.get(target) {
+                        Some(jump) if jump.opcode == Opcode::Jump => {
+                            target = self.next(jump.operands[0] as usize);
+                        }
+                        _ => break,
+                    }
+                }
+                self.instructions[index].operands[operand] = target as u64;
+            }
+        }
+    }
+
+    /// Removes the jumps to the next instruction, once the code between them is removed.
+    ///
+    /// Removing a jump can make the jumps before it jump to the next instruction, so this is
+    /// repeated until no jump is removed.
+    fn remove_jumps_to_next(&mut self) {
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for index in (0..self.instructions.len()).rev() {
+                let instruction = &self.instructions[index];
+                if instruction.removed
+                    || !matches!(
+                        instruction.opcode,
+                        Opcode::Jump | Opcode::JumpIfFalse | Opcode::JumpIfTrue
+                    )
+                    || self.next(instruction.operands[0] as usize) != self.next(index + 1)
+                {
+                    continue;
+                }
+                if instruction.opcode == Opcode::Jump {
+                    self.instructions[index].removed = true;
+                    changed = true;
+                } else {
+                    self.instructions[index] = Instruction::new(Opcode::Pop, Vec::new());
+                }
+            }
+        }
+    }
+
+    /// Removes the instructions that no path from the start of the code or from an exception
+    /// handler reaches.
+    fn remove_dead_code(&mut self) {
+        let len = self.instructions.len();
+        let mut reachable = vec![false; len];
+        let mut pending: Vec<usize> = self.handlers.iter().map(|handler| handler[2]).collect();
+        pending.push(0);
+        while let Some(index) = pending.pop() {
+            let index = self.next(index);
+            if index == len || reachable[index] {
+                continue;
+            }
+            reachable[index] = true;
+
+            let instruction = &self.instructions[index];
+            pending.extend(
+                instruction
+                    .targets()
+                    .map(|operand| instruction.operands[operand] as usize),
+            );
+            if !matches!(
+                instruction.opcode,
+                Opcode::Jump | Opcode::Default | Opcode::Return | Opcode::Throw
+            ) {
+                pending.push(index + 1);
+            }
+        }
+
+        for (instruction, reachable) in self.instructions.iter_mut().zip(reachable) {
+            instruction.removed |= !reachable;
+        }
+    }
+
+    /// Replaces common sequences of instructions by superinstructions.
+    fn fuse_instructions(&mut self) {
+        self.compute_labels();
+        let mut kept: Vec<usize> = Vec::new();
+        for index in 0..self.instructions.len() {
+            if self.instructions[index].removed {
+                continue;
+            }
+            let instruction = &self.instructions[index];
+            let fused = match (instruction.opcode, &kept[..]) {
+                // `i++` and `i--` as statements.
+                (Opcode::SetLocal, &[.., get, step])
+                    if self.instructions[get].opcode == Opcode::GetLocal
+                        && self.instructions[get].operands == instruction.operands
+                        && !self.labels[step] =>
+                {
+                    match self.instructions[step].opcode {
+                        Opcode::Inc => Some((Opcode::IncLocal, vec![get, step])),
+                        Opcode::Dec => Some((Opcode::DecLocal, vec![get, step])),
+                        _ => None,
+                    }
+                }
+                // The conditions of loops.
+                (Opcode::JumpIfFalse, &[.., compare]) => match self.instructions[compare].opcode {
+                    Opcode::LessThan => Some((Opcode::JumpIfNotLessThan, vec![compare])),
+                    Opcode::LessThanOrEq => Some((Opcode::JumpIfNotLessThanOrEq, vec![compare])),
+                    _ => None,
+                },
+                _ => None,
+            };
+
+            if let Some((opcode, sequence)) = fused {
+                if !self.labels[index] {
+                    for removed in sequence {
+                        kept.pop();
+                        self.remove(removed, index);
+                    }
+                    self.instructions[index].opcode = opcode;
+                }
+            }
+            kept.push(index);
+        }
+    }
+
+    /// Encodes the instructions that are kept back into the code block.
+    fn finish(mut self) {
+        let mut addresses = Vec::with_capacity(self.instructions.len() + 1);
+        let mut address = 0;
+        for instruction in &self.instructions {
+            addresses.push(address);
+            if !instruction.removed {
+                let size = operands(instruction.opcode)
+                    .iter()
+                    .map(|operand| operand.size())
+                    .sum::<usize>();
+                address += 1 + size as u32;
+            }
+        }
+        addresses.push(address);
+
+        // Folding leaves the literals of the operands it removed unused.
+        let old_literals = std::mem::take(&mut self.code_block.literals);
+        let mut literals = Vec::new();
+        let mut literal_indices = vec![None; old_literals.len()];
+        for instruction in self.instructions.iter_mut().filter(|i| !i.removed) {
+            if instruction.opcode == Opcode::PushLiteral {
+                let index = instruction.operands[0] as usize;
+                let new_index = *literal_indices[index].get_or_insert_with(|| {
+                    literals.push(old_literals[index].clone());
+                    literals.len() - 1
+                });
+                instruction.operands[0] = new_index as u64;
+            }
+        }
+        self.code_block.literals = literals;
+
+        let mut code = Vec::with_capacity(address as usize);
+        for instruction in self.instructions.iter().filter(|i| !i.removed) {
+            code.push(instruction.opcode as u8);
+            for (&value, &operand) in instruction
+                .operands
+                .iter()
+                .zip(operands(instruction.opcode))
+            {
+                let value = match operand {
+                    Operand::Address => u64::from(addresses[value as usize]),
+                    _ => value,
+                };
+                match operand.size() {
+                    1 => code.push(value as u8),
+                    2 => code.extend(&(value as u16).to_ne_bytes()),
+                    4 => code.extend(&(value as u32).to_ne_bytes()),
+                    _ => code.extend(&value.to_ne_bytes()),
+                }
+            }
+        }
+        self.code_block.code = code;
+
+        for (handler, [start, end, address]) in
+            self.code_block.handlers.iter_mut().zip(self.handlers)
+        {
+            handler.start = addresses[start];
+            handler.end = addresses[end];
+            handler.address = addresses[address];
+        }
+
+        // The entries of statements whose code was removed share the address of the next
+        // statement, which the code at that address belongs to.
+        let mut line_table: Vec<(u32, _)> = Vec::with_capacity(self.line_table.len());
+        for (&(_, position), index) in self.code_block.line_table.iter().zip(self.line_table) {
+            let pc = addresses[index];
+            match line_table.last_mut() {
+                Some(last) if last.0 == pc => last.1 = position,
+                _ => line_table.push((pc, position)),
+            }
+        }
+        self.code_block.line_table = line_table;
+    }
+}
+
+/// Evaluates the binary operation `opcode` on constants, as the vm would.
+fn fold_binary(opcode: Opcode, x: &JsValue, y: &JsValue, context: &mut Context) -> Option<JsValue> {
+    match opcode {
+        Opcode::Add => x.add(y, context),
+        Opcode::Sub => x.sub(y, context),
+        Opcode::Mul => x.mul(y, context),
+        Opcode::Div => x.div(y, context),
+        Opcode::Mod => x.rem(y, context),
+        Opcode::Pow => x.pow(y, context),
+        Opcode::BitAnd => x.bitand(y, context),
+        Opcode::BitOr => x.bitor(y, context),
+        Opcode::BitXor => x.bitxor(y, context),
+        Opcode::ShiftLeft => x.shl(y, context),
+        Opcode::ShiftRight => x.shr(y, context),
+        Opcode::UnsignedShiftRight => x.ushr(y, context),
+        Opcode::Eq => x.equals(y, context).map(JsValue::new),
+        Opcode::NotEq => x.equals(y, context).map(|equal| JsValue::new(!equal)),
+        Opcode::StrictEq => Ok(JsValue::new(x.strict_equals(y))),
+        Opcode::StrictNotEq => Ok(JsValue::new(!x.strict_equals(y))),
+        Opcode::GreaterThan => x.gt(y, context).map(JsValue::new),
+        Opcode::GreaterThanOrEq => x.ge(y, context).map(JsValue::new),
+        Opcode::LessThan => x.lt(y, context).map(JsValue::new),
+        Opcode::LessThanOrEq => x.le(y, context).map(JsValue::new),
+        _ => return None,
+    }
+    .ok()
+}
+
+/// Evaluates the unary operation `opcode` on a constant, as the vm would.
+fn fold_unary(opcode: Opcode, x: &JsValue, context: &mut Context) -> Option<JsValue> {
+    match opcode {
+        Opcode::Neg => x.neg(context),
+        Opcode::Pos => x.to_number(context).map(JsValue::new),
+        Opcode::LogicalNot => Ok(JsValue::new(!x.to_boolean())),
+        Opcode::TypeOf => Ok(JsValue::new(x.type_of())),
+        Opcode::Void => Ok(JsValue::undefined()),
+        _ => return None,
+    }
+    .ok()
+}
@@ -0,0 +1,1067 @@
+//! A versioned binary format for [`CodeBlock`]s, so that compiled scripts can be cached and run
+//! again without being parsed and compiled.
+//!
+//! A serialized script starts with a header, which holds the magic bytes of the format, its
+//! version and the number of opcodes of the vm that wrote it, followed by the code block of the
+//! script, whose nested functions are written after their parent. All integers are little endian,
+//! and strings are written as their length followed by their UTF-16 code units, or by their UTF-8
+//! bytes for interned names.
+//!
+//! Loading a code block checks that the bytes are well formed before the vm gets to see them:
+//! every instruction must be a known opcode with all of its operands, every index must point
+//! into the table it refers to, and every jump target and exception handler bound must be the
+//! address of an instruction. The control flow is then followed to check that every path to an
+//! instruction leaves it in the same environments, that no instruction pops more values than
+//! there are on the stack, and that every binding slot is in the environment it is looked up in.
+
+use super::{
+    code_block::{BindingKind, Handler},
+    CodeBlock, InlineCache, Opcode,
+};
+use crate::{
+    builtins::function::ThisMode,
+    interner::Sym,
+    syntax::ast::{node::FormalParameter, Node, Position},
+    JsBigInt, JsString, JsValue,
+};
+use gc::Gc;
+use std::{
+    convert::{TryFrom, TryInto},
+    error::Error,
+    fmt,
+    mem::size_of,
+};
+
+/// The version of the bytecode format.
+///
+/// It must be bumped whenever the layout of a code block or the meaning of an opcode changes.
+pub const BYTECODE_VERSION: u32 = 3;
+
+/// The magic bytes that start every serialized script.
+const MAGIC: &[u8; 4] = b"BOAB";
+
+/// The number of opcodes of the vm, which is written in the header to catch opcode tables that
+/// changed without a version bump.
+const OPCODE_COUNT: u8 = Opcode::Nop as u8 + 1;
+
+const LITERAL_UNDEFINED: u8 = 0;
+const LITERAL_NULL: u8 = 1;
+const LITERAL_FALSE: u8 = 2;
+const LITERAL_TRUE: u8 = 3;
+const LITERAL_INTEGER: u8 = 4;
+const LITERAL_RATIONAL: u8 = 5;
+const LITERAL_STRING: u8 = 6;
+const LITERAL_BIGINT: u8 = 7;
+
+const FLAG_STRICT: u8 = 1 << 0;
+const FLAG_CONSTRUCTOR: u8 = 1 << 1;
+const FLAG_GENERATOR: u8 = 1 << 2;
+const FLAG_ASYNC: u8 = 1 << 3;
+
+const PARAMETER_REST: u8 = 1 << 0;
+const PARAMETER_INIT: u8 = 1 << 1;
+
+/// An error that occurs when loading a serialized [`CodeBlock`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BytecodeError {
+    /// The bytes are not a serialized script.
+    InvalidMagic,
+    /// The script was serialized by an incompatible version of the vm.
+    UnsupportedVersion { version: u32 },
+    /// The bytes end in the middle of the script.
+    UnexpectedEnd,
+    /// There are bytes left after the end of the script.
+    TrailingBytes,
+    /// A field of a code block has a value that is not valid for it.
+    InvalidField { field: &'static str },
+    /// The bytecode of a function is not well formed.
+    InvalidCode {
+        function: String,
+        pc: usize,
+        message: &'static str,
+    },
+}
+
+impl fmt::Display for BytecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMagic => f.write_str("not a serialized script"),
+            Self::UnsupportedVersion { version } => write!(
+                f,
+                "unsupported bytecode version {} (expected {})",
+                version, BYTECODE_VERSION
+            ),
+            Self::UnexpectedEnd => f.write_str("unexpected end of the serialized script"),
+            Self::TrailingBytes => f.write_str("trailing bytes after the serialized script"),
+            Self::InvalidField { field } => write!(f, "invalid {} in the serialized script", field),
+            Self::InvalidCode {
+                function,
+                pc,
+                message,
+            } => write!(
+                f,
+                "invalid bytecode in function '{}' at {:04}: {}",
+                function, pc, message
+            ),
+        }
+    }
+}
+
+impl Error for BytecodeError {}
+
+impl CodeBlock {
+    /// Serializes this code block and all the functions nested in it.
+    ///
+    /// The bytes can be loaded again with [`CodeBlock::from_bytes`] by the same version of the vm.
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::{vm::CodeBlock, Context};
+    /// let mut context = Context::new();
+    ///
+    /// let bytes = context.compile("1 + 3").unwrap().to_bytes();
+    /// let code = CodeBlock::from_bytes(&bytes).unwrap();
+    ///
+    /// assert_eq!(context.execute(code).unwrap().as_number(), Some(4.0));
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = Writer::default();
+        writer.bytes.extend_from_slice(MAGIC);
+        writer.u32(BYTECODE_VERSION);
+        writer.u8(OPCODE_COUNT);
+        writer.code_block(self);
+        writer.bytes
+    }
+
+    /// Loads a code block that was serialized with [`CodeBlock::to_bytes`].
+    ///
+    /// The bytes are validated, so that malformed or truncated input is reported as an error
+    /// instead of making the vm panic.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BytecodeError> {
+        // Interned names are never freed, so the names of the code are only looked up until it is
+        // known to be valid. If some of them were not interned yet, the code is read again.
+        let mut reader = Reader {
+            bytes,
+            intern: false,
+            unknown_names: false,
+        };
+        let code_block = reader.script()?;
+        // The script runs in the global environment, whose bindings are not in slots.
+        code_block.validate(&[])?;
+        if !reader.unknown_names {
+            return Ok(code_block);
+        }
+        Reader {
+            bytes,
+            intern: true,
+            unknown_names: false,
+        }
+        .script()
+    }
+
+    /// Checks that the code only contains valid instructions, whose operands point into the
+    /// tables of this code block and at the start of instructions, and that the functions nested
+    /// in it are valid in the environments they are created in.
+    ///
+    /// `environments` are the number of bindings of the declarative environments that the code
+    /// starts in, from the outermost one.
+    fn validate(&self, environments: &[usize]) -> Result<(), BytecodeError> {
+        let error = |pc: usize, message| BytecodeError::InvalidCode {
+            function: self.name.to_string(),
+            pc,
+            message,
+        };
+
+        let mut boundaries = vec![false; self.code.len() + 1];
+        let mut targets = Vec::new();
+        let mut pc = 0;
+        while pc < self.code.len() {
+            boundaries[pc] = true;
+            let opcode =
+                Opcode::try_from(self.code[pc]).map_err(|_| error(pc, "invalid opcode"))?;
+            let operands = operands(opcode);
+            let size = operands.iter().map(|operand| operand.size()).sum::<usize>();
+            if pc + 1 + size > self.code.len() {
+                return Err(error(pc, "truncated operands"));
+            }
+
+            let mut offset = pc + 1;
+            for operand in operands {
+                let value = || self.read::<u32>(offset) as usize;
+                let valid = match operand {
+                    Operand::Literal => value() < self.literals.len(),
+                    Operand::Name => value() < self.variables.len(),
+                    Operand::Function => value() < self.functions.len(),
+                    Operand::Scope => value() < self.scopes.len(),
+                    Operand::Cache => value() < self.inline_caches.len(),
+                    Operand::Address => {
+                        targets.push((pc, value()));
+                        true
+                    }
+                    // Depths and slots depend on the environments at the instruction, so they
+                    // are checked when following the control flow.
+                    Operand::Byte
+                    | Operand::Short
+                    | Operand::Integer
+                    | Operand::Double
+                    | Operand::Depth
+                    | Operand::Slot => true,
+                };
+                if !valid {
+                    return Err(error(pc, "operand out of range"));
+                }
+                offset += operand.size();
+            }
+            pc = offset;
+        }
+        boundaries[self.code.len()] = true;
+
+        let is_boundary = |address: usize| boundaries.get(address).copied().unwrap_or(false);
+        for (pc, target) in targets {
+            if !is_boundary(target) {
+                return Err(error(pc, "jump target is not an instruction"));
+            }
+        }
+        for handler in &self.handlers {
+            let (start, end) = (handler.start as usize, handler.end as usize);
+            if start > end
+                || !is_boundary(start)
+                || !is_boundary(end)
+                || !is_boundary(handler.address as usize)
+            {
+                return Err(error(start, "invalid exception handler"));
+            }
+        }
+        let mut previous = 0;
+        for &(start, _) in &self.line_table {
+            if start < previous || !is_boundary(start as usize) {
+                return Err(error(start as usize, "invalid line table"));
+            }
+            previous = start;
+        }
+
+        let slots = self.bindings.len() as u32;
+        if matches!(self.arguments_binding, Some(slot) if slot >= slots)
+            || matches!(self.function_binding, Some(slot) if slot >= slots)
+        {
+            return Err(BytecodeError::InvalidField {
+                field: "binding slot",
+            });
+        }
+
+        let closures = self.validate_flow(environments)?;
+        for (function, closure) in self.functions.iter().zip(closures) {
+            // A function that is never created can't run, so it is checked on its own.
+            let mut environments = closure.unwrap_or_default();
+            environments.push(function.bindings.len());
+            function.validate(&environments)?;
+        }
+        Ok(())
+    }
+
+    /// Follows the control flow of the code from its start and from its exception handlers, and
+    /// checks that every instruction finds the values that it pops on the stack and the bindings
+    /// that it uses in its environments, whichever path leads to it.
+    ///
+    /// Values that are left above the ones an instruction uses are harmless, as the frame drops
+    /// them when it returns.
+    ///
+    /// Returns the environments that each function is created in, if it is created at all.
+    fn validate_flow(
+        &self,
+        environments: &[usize],
+    ) -> Result<Vec<Option<Vec<usize>>>, BytecodeError> {
+        let error = |pc: usize, message| BytecodeError::InvalidCode {
+            function: self.name.to_string(),
+            pc,
+            message,
+        };
+
+        let mut states = vec![None; self.code.len() + 1];
+        let mut pending = Vec::new();
+        let state = FlowState {
+            stack: 0,
+            environments: environments.to_vec(),
+        };
+        state
+            .enter(&mut states, &mut pending, 0)
+            .map_err(|message| error(0, message))?;
+
+        let base = environments.len();
+        let mut closures = vec![None; self.functions.len()];
+        while let Some(pc) = pending.pop() {
+            if pc == self.code.len() {
+                continue;
+            }
+            let state = states[pc]
+                .clone()
+                .expect("pending instructions have a state");
+            let opcode = Opcode::try_from(self.code[pc]).expect("opcodes are checked first");
+
+            let mut next = pc + 1;
+            let mut values = Vec::new();
+            for operand in operands(opcode) {
+                values.push(match operand {
+                    Operand::Byte => self.read::<u8>(next) as usize,
+                    Operand::Short | Operand::Double => 0,
+                    _ => self.read::<u32>(next) as usize,
+                });
+                next += operand.size();
+            }
+
+            let mut environments = state.environments.clone();
+            // Slots without a depth are in the current environment.
+            let mut bindings = environments.last().copied();
+            for (operand, &value) in operands(opcode).iter().zip(&values) {
+                match operand {
+                    Operand::Depth => {
+                        bindings = environments
+                            .len()
+                            .checked_sub(1)
+                            .and_then(|top| top.checked_sub(value))
+                            .map(|index| environments[index]);
+                        if bindings.is_none() {
+                            return Err(error(pc, "environment out of range"));
+                        }
+                    }
+                    Operand::Slot if !matches!(bindings, Some(len) if value < len) => {
+                        return Err(error(pc, "binding slot out of range"));
+                    }
+                    _ => {}
+                }
+            }
+            match opcode {
+                Opcode::PushDeclarativeEnvironment => {
+                    environments.push(self.scopes[values[0]].len());
+                }
+                Opcode::CopyDeclarativeEnvironment => {
+                    let len = self.scopes[values[0]].len();
+                    let blocks = environments.len() - base;
+                    match environments.last_mut() {
+                        Some(bindings) if blocks > 0 && *bindings >= len => *bindings = len,
+                        _ => return Err(error(pc, "no environment to copy")),
+                    }
+                }
+                Opcode::PopEnvironment => {
+                    if environments.len() == base {
+                        return Err(error(pc, "no environment to pop"));
+                    }
+                    environments.pop();
+                }
+                Opcode::GetFunction => match &closures[values[0]] {
+                    None => closures[values[0]] = Some(environments.clone()),
+                    Some(closure) if *closure == environments => {}
+                    Some(_) => {
+                        return Err(error(pc, "function is created in different environments"))
+                    }
+                },
+                Opcode::RotateLeft | Opcode::RotateRight if values[0] == 0 => {
+                    return Err(error(pc, "operand out of range"));
+                }
+                _ => {}
+            }
+
+            let (pops, pushes) = stack_effect(opcode, &values);
+            let stack = state
+                .stack
+                .checked_sub(pops)
+                .ok_or_else(|| error(pc, "stack underflow"))?;
+
+            // The exception handler drops the values and the environments above the ones the
+            // protected code was entered with, and pushes the exception.
+            if let Some(handler) = self.handler(pc) {
+                let stack_depth = handler.stack_depth as usize;
+                let depth = base.saturating_add(handler.environment_depth as usize);
+                if stack_depth > stack || depth > state.environments.len() {
+                    return Err(error(pc, "invalid exception handler"));
+                }
+                let state = FlowState {
+                    stack: stack_depth + 1,
+                    environments: state.environments[..depth].to_vec(),
+                };
+                state
+                    .enter(&mut states, &mut pending, handler.address as usize)
+                    .map_err(|message| error(pc, message))?;
+            }
+
+            let mut successors = Vec::new();
+            match opcode {
+                Opcode::Jump | Opcode::Default => successors.push((values[0], stack)),
+                Opcode::Return | Opcode::Throw => {}
+                Opcode::JumpIfFalse
+                | Opcode::JumpIfTrue
+                | Opcode::JumpIfNotLessThan
+                | Opcode::JumpIfNotLessThanOrEq
+                | Opcode::Case
+                | Opcode::ForInLoopInitIterator => {
+                    successors.push((values[0], stack));
+                    successors.push((next, stack + pushes));
+                }
+                Opcode::JumpIfNotUndefined
+                | Opcode::LogicalAnd
+                | Opcode::LogicalOr
+                | Opcode::Coalesce => {
+                    successors.push((values[0], stack + 1));
+                    successors.push((next, stack + pushes));
+                }
+                Opcode::IteratorStep => {
+                    successors.push((values[0], stack + 2));
+                    successors.push((next, stack + pushes));
+                }
+                Opcode::GeneratorNext | Opcode::GeneratorDelegateNext => {
+                    // A return drops the values above the depth of the operand, below the
+                    // iterator of a delegating generator.
+                    let (address, stack_depth) =
+                        (values[values.len() - 2], values[values.len() - 1]);
+                    let kept = if opcode == Opcode::GeneratorNext {
+                        stack
+                    } else {
+                        stack + 2
+                    };
+                    if stack_depth > kept {
+                        return Err(error(pc, "stack underflow"));
+                    }
+                    successors.push((address, stack_depth + 1));
+                    if opcode == Opcode::GeneratorDelegateNext {
+                        successors.push((values[0], stack + pushes));
+                    }
+                    successors.push((next, stack + pushes));
+                }
+                _ => successors.push((next, stack + pushes)),
+            }
+            for (target, stack) in successors {
+                let state = FlowState {
+                    stack,
+                    environments: environments.clone(),
+                };
+                state
+                    .enter(&mut states, &mut pending, target)
+                    .map_err(|message| error(pc, message))?;
+            }
+        }
+        Ok(closures)
+    }
+}
+
+/// The values on the stack and the environments when an instruction is run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FlowState {
+    /// The number of values on the stack of the frame.
+    stack: usize,
+    /// The number of bindings of each known environment, from the outermost one.
+    environments: Vec<usize>,
+}
+
+impl FlowState {
+    /// Enters the instruction at `target` with this state, whose environments must be the same
+    /// as those of the other paths that lead to it.
+    ///
+    /// The paths may leave different numbers of values on the stack, like the completion value
+    /// of a statement, so the instruction is checked again with the fewest of them.
+    fn enter(
+        self,
+        states: &mut [Option<Self>],
+        pending: &mut Vec<usize>,
+        target: usize,
+    ) -> Result<(), &'static str> {
+        match &mut states[target] {
+            Some(state) if state.environments != self.environments => {
+                Err("environments differ between paths")
+            }
+            Some(state) if state.stack <= self.stack => Ok(()),
+            state => {
+                *state = Some(self);
+                pending.push(target);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The kind of an operand of an instruction.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum Operand {
+    Byte,
+    Short,
+    Integer,
+    Double,
+    Literal,
+    Name,
+    Function,
+    Scope,
+    Cache,
+    Address,
+    /// The number of environments between the current one and the one of a binding.
+    Depth,
+    /// The index of a binding in its environment.
+    Slot,
+}
+
+impl Operand {
+    /// The size of the operand in the code.
+    pub(super) fn size(self) -> usize {
+        match self {
+            Self::Byte => size_of::<u8>(),
+            Self::Short => size_of::<u16>(),
+            Self::Double => size_of::<f64>(),
+            Self::Integer
+            | Self::Literal
+            | Self::Name
+            | Self::Function
+            | Self::Scope
+            | Self::Cache
+            | Self::Address
+            | Self::Depth
+            | Self::Slot => size_of::<u32>(),
+        }
+    }
+}
+
+/// Gets the operands of `opcode`, in the order they follow it in the code.
+pub(super) fn operands(opcode: Opcode) -> &'static [Operand] {
+    match opcode {
+        Opcode::PushInt8 | Opcode::RotateLeft | Opcode::RotateRight => &[Operand::Byte],
+        Opcode::PushInt16 => &[Operand::Short],
+        Opcode::PushInt32
+        | Opcode::PushNewArray
+        | Opcode::Call
+        | Opcode::New
+        | Opcode::CopyDataProperties
+        | Opcode::ConcatToString => &[Operand::Integer],
+        Opcode::PushRational => &[Operand::Double],
+        Opcode::PushLiteral => &[Operand::Literal],
+        Opcode::GetLocal | Opcode::SetLocal | Opcode::IncLocal | Opcode::DecLocal => {
+            &[Operand::Depth, Operand::Slot]
+        }
+        Opcode::InitLocal => &[Operand::Slot],
+        Opcode::PushDeclarativeEnvironment | Opcode::CopyDeclarativeEnvironment => {
+            &[Operand::Scope]
+        }
+        Opcode::GetFunction => &[Operand::Function],
+        Opcode::DefVar
+        | Opcode::DefLet
+        | Opcode::DefConst
+        | Opcode::InitLexical
+        | Opcode::GetName
+        | Opcode::GetNameOrUndefined
+        | Opcode::SetName
+        | Opcode::DefineOwnPropertyByName
+        | Opcode::SetPropertyGetterByName
+        | Opcode::SetPropertySetterByName
+        | Opcode::DeletePropertyByName => &[Operand::Name],
+        Opcode::GetPropertyByName | Opcode::SetPropertyByName => &[Operand::Name, Operand::Cache],
+        Opcode::Jump
+        | Opcode::JumpIfFalse
+        | Opcode::JumpIfTrue
+        | Opcode::JumpIfNotUndefined
+        | Opcode::JumpIfNotLessThan
+        | Opcode::JumpIfNotLessThanOrEq
+        | Opcode::LogicalAnd
+        | Opcode::LogicalOr
+        | Opcode::Coalesce
+        | Opcode::Case
+        | Opcode::Default
+        | Opcode::ForInLoopInitIterator
+        | Opcode::IteratorStep => &[Operand::Address],
+        Opcode::GeneratorNext => &[Operand::Address, Operand::Integer],
+        Opcode::GeneratorDelegateNext => &[Operand::Address, Operand::Address, Operand::Integer],
+        _ => &[],
+    }
+}
+
+/// Gets the number of values that `opcode` pops from the stack or reads below its top, and the
+/// number of values that it leaves in their place when it continues with the next instruction.
+///
+/// `operands` are the values of the operands of the instruction.
+fn stack_effect(opcode: Opcode, operands: &[usize]) -> (usize, usize) {
+    match opcode {
+        Opcode::Nop
+        | Opcode::DefVar
+        | Opcode::DefLet
+        | Opcode::DefConst
+        | Opcode::PushDeclarativeEnvironment
+        | Opcode::CopyDeclarativeEnvironment
+        | Opcode::PopEnvironment
+        | Opcode::IncLocal
+        | Opcode::DecLocal
+        | Opcode::Jump => (0, 0),
+        Opcode::PushZero
+        | Opcode::PushOne
+        | Opcode::PushInt8
+        | Opcode::PushInt16
+        | Opcode::PushInt32
+        | Opcode::PushRational
+        | Opcode::PushNaN
+        | Opcode::PushPositiveInfinity
+        | Opcode::PushNegativeInfinity
+        | Opcode::PushNull
+        | Opcode::PushTrue
+        | Opcode::PushFalse
+        | Opcode::PushUndefined
+        | Opcode::PushLiteral
+        | Opcode::PushEmptyObject
+        | Opcode::GetName
+        | Opcode::GetNameOrUndefined
+        | Opcode::GetLocal
+        | Opcode::This
+        | Opcode::GetFunction => (0, 1),
+        Opcode::Pop
+        | Opcode::InitLexical
+        | Opcode::SetName
+        | Opcode::SetLocal
+        | Opcode::InitLocal
+        | Opcode::JumpIfFalse
+        | Opcode::JumpIfTrue
+        | Opcode::LogicalAnd
+        | Opcode::LogicalOr
+        | Opcode::Coalesce
+        | Opcode::Default
+        | Opcode::Throw
+        | Opcode::Return => (1, 0),
+        Opcode::BitNot
+        | Opcode::Inc
+        | Opcode::Dec
+        | Opcode::ToNumeric
+        | Opcode::TypeOf
+        | Opcode::Void
+        | Opcode::LogicalNot
+        | Opcode::Pos
+        | Opcode::Neg
+        | Opcode::ToBoolean
+        | Opcode::ValueNotNullOrUndefined
+        | Opcode::JumpIfNotUndefined
+        | Opcode::GetPropertyByName
+        | Opcode::DeletePropertyByName => (1, 1),
+        Opcode::Dup
+        | Opcode::InitIterator
+        | Opcode::ForInLoopInitIterator
+        | Opcode::Yield
+        | Opcode::Await => (1, 2),
+        Opcode::SetPropertyByName
+        | Opcode::DefineOwnPropertyByName
+        | Opcode::SetPropertyGetterByName
+        | Opcode::SetPropertySetterByName
+        | Opcode::JumpIfNotLessThan
+        | Opcode::JumpIfNotLessThanOrEq
+        | Opcode::IteratorClose => (2, 0),
+        Opcode::PushValueToArray
+        | Opcode::PushIteratorToArray
+        | Opcode::Add
+        | Opcode::Sub
+        | Opcode::Div
+        | Opcode::Mul
+        | Opcode::Mod
+        | Opcode::Pow
+        | Opcode::ShiftRight
+        | Opcode::ShiftLeft
+        | Opcode::UnsignedShiftRight
+        | Opcode::BitOr
+        | Opcode::BitAnd
+        | Opcode::BitXor
+        | Opcode::In
+        | Opcode::Eq
+        | Opcode::StrictEq
+        | Opcode::NotEq
+        | Opcode::StrictNotEq
+        | Opcode::GreaterThan
+        | Opcode::GreaterThanOrEq
+        | Opcode::LessThan
+        | Opcode::LessThanOrEq
+        | Opcode::InstanceOf
+        | Opcode::GetPropertyByValue
+        | Opcode::DeletePropertyByValue
+        | Opcode::Case
+        | Opcode::NewSpread
+        | Opcode::GeneratorNext => (2, 1),
+        Opcode::Swap => (2, 2),
+        Opcode::IteratorNext | Opcode::IteratorStep | Opcode::IteratorToArray => (2, 3),
+        Opcode::Dup2 => (2, 4),
+        Opcode::SetPropertyByValue
+        | Opcode::DefineOwnPropertyByValue
+        | Opcode::SetPropertyGetterByValue
+        | Opcode::SetPropertySetterByValue => (3, 0),
+        Opcode::CallSpread => (3, 1),
+        Opcode::GeneratorDelegateNext => (4, 3),
+        Opcode::RotateLeft | Opcode::RotateRight => (operands[0], operands[0]),
+        Opcode::PushNewArray | Opcode::ConcatToString => (operands[0], 1),
+        Opcode::New => (operands[0].saturating_add(1), 1),
+        Opcode::Call | Opcode::CopyDataProperties => (operands[0].saturating_add(2), 1),
+    }
+}
+
+/// Writes the serialized form of code blocks.
+#[derive(Debug, Default)]
+struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn f64(&mut self, value: f64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn len(&mut self, len: usize) {
+        self.u32(u32::try_from(len).expect("too many items to serialize"));
+    }
+
+    fn str(&mut self, value: &str) {
+        self.len(value.len());
+        self.bytes.extend_from_slice(value.as_bytes());
+    }
+
+    fn js_string(&mut self, value: &JsString) {
+        self.len(value.len());
+        for unit in value.as_slice() {
+            self.bytes.extend_from_slice(&unit.to_le_bytes());
+        }
+    }
+
+    fn slot(&mut self, slot: Option<u32>) {
+        // Slots are indices in a vector, so `u32::MAX` is never a valid slot.
+        self.u32(slot.unwrap_or(u32::MAX));
+    }
+
+    fn bindings(&mut self, bindings: &[(Sym, BindingKind)]) {
+        self.len(bindings.len());
+        for &(name, kind) in bindings {
+            self.str(&name);
+            self.u8(match kind {
+                BindingKind::Var => 0,
+                BindingKind::Let => 1,
+                BindingKind::Const => 2,
+            });
+        }
+    }
+
+    fn literal(&mut self, value: &JsValue) {
+        match value {
+            JsValue::Undefined => self.u8(LITERAL_UNDEFINED),
+            JsValue::Null => self.u8(LITERAL_NULL),
+            JsValue::Boolean(false) => self.u8(LITERAL_FALSE),
+            JsValue::Boolean(true) => self.u8(LITERAL_TRUE),
+            JsValue::Integer(integer) => {
+                self.u8(LITERAL_INTEGER);
+                self.u32(*integer as u32);
+            }
+            JsValue::Rational(rational) => {
+                self.u8(LITERAL_RATIONAL);
+                self.f64(*rational);
+            }
+            JsValue::String(string) => {
+                self.u8(LITERAL_STRING);
+                self.js_string(string);
+            }
+            JsValue::BigInt(bigint) => {
+                self.u8(LITERAL_BIGINT);
+                self.str(&bigint.to_string_radix(16));
+            }
+            JsValue::Object(_) | JsValue::Symbol(_) => {
+                unreachable!("the literals of a code block are primitive values")
+            }
+        }
+    }
+
+    fn code_block(&mut self, code_block: &CodeBlock) {
+        self.js_string(&code_block.name);
+        self.u32(code_block.length);
+        let mut flags = 0;
+        for (set, flag) in [
+            (code_block.strict, FLAG_STRICT),
+            (code_block.constructor, FLAG_CONSTRUCTOR),
+            (code_block.generator, FLAG_GENERATOR),
+            (code_block.asynchronous, FLAG_ASYNC),
+        ] {
+            if set {
+                flags |= flag;
+            }
+        }
+        self.u8(flags);
+        self.u8(match code_block.this_mode {
+            ThisMode::Lexical => 0,
+            ThisMode::Strict => 1,
+            ThisMode::Global => 2,
+        });
+
+        self.len(code_block.params.len());
+        for param in code_block.params.iter() {
+            self.str(&param.name());
+            let mut flags = 0;
+            if param.is_rest_param() {
+                flags |= PARAMETER_REST;
+            }
+            if param.init().is_some() {
+                flags |= PARAMETER_INIT;
+            }
+            self.u8(flags);
+        }
+
+        self.len(code_block.code.len());
+        self.bytes.extend_from_slice(&code_block.code);
+
+        self.len(code_block.literals.len());
+        for literal in &code_block.literals {
+            self.literal(literal);
+        }
+
+        self.len(code_block.variables.len());
+        for name in &code_block.variables {
+            self.str(name);
+        }
+
+        self.bindings(&code_block.bindings);
+        self.slot(code_block.arguments_binding);
+        self.slot(code_block.function_binding);
+        self.len(code_block.scopes.len());
+        for scope in &code_block.scopes {
+            self.bindings(scope);
+        }
+
+        self.len(code_block.line_table.len());
+        for &(pc, position) in &code_block.line_table {
+            self.u32(pc);
+            self.u32(position.line_number());
+            self.u32(position.column_number());
+        }
+
+        self.len(code_block.handlers.len());
+        for handler in &code_block.handlers {
+            self.u32(handler.start);
+            self.u32(handler.end);
+            self.u32(handler.address);
+            self.u32(handler.stack_depth);
+            self.u32(handler.environment_depth);
+        }
+
+        self.len(code_block.functions.len());
+        for function in &code_block.functions {
+            self.code_block(function);
+        }
+
+        // The caches are filled at runtime, so only their number is written.
+        self.len(code_block.inline_caches.len());
+    }
+}
+
+/// Reads the serialized form of code blocks.
+#[derive(Debug)]
+struct Reader<'a> {
+    bytes: &'a [u8],
+    /// Whether the names that are not interned yet are interned, or replaced by the empty name.
+    intern: bool,
+    /// Whether a name that is not interned yet was replaced.
+    unknown_names: bool,
+}
+
+impl<'a> Reader<'a> {
+    /// Reads the header and the code block of a script.
+    fn script(&mut self) -> Result<CodeBlock, BytecodeError> {
+        if self.take(MAGIC.len())? != MAGIC {
+            return Err(BytecodeError::InvalidMagic);
+        }
+        let version = self.u32()?;
+        if version != BYTECODE_VERSION || self.u8()? != OPCODE_COUNT {
+            return Err(BytecodeError::UnsupportedVersion { version });
+        }
+        let code_block = self.code_block()?;
+        if !self.bytes.is_empty() {
+            return Err(BytecodeError::TrailingBytes);
+        }
+        Ok(code_block)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], BytecodeError> {
+        if len > self.bytes.len() {
+            return Err(BytecodeError::UnexpectedEnd);
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8, BytecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, BytecodeError> {
+        let bytes = self.take(size_of::<u32>())?;
+        Ok(u32::from_le_bytes(
+            bytes.try_into().expect("took the size of a u32"),
+        ))
+    }
+
+    fn f64(&mut self) -> Result<f64, BytecodeError> {
+        let bytes = self.take(size_of::<f64>())?;
+        Ok(f64::from_le_bytes(
+            bytes.try_into().expect("took the size of a f64"),
+        ))
+    }
+
+    fn len(&mut self) -> Result<usize, BytecodeError> {
+        Ok(self.u32()? as usize)
+    }
+
+    fn str(&mut self) -> Result<&'a str, BytecodeError> {
+        let len = self.len()?;
+        std::str::from_utf8(self.take(len)?)
+            .map_err(|_| BytecodeError::InvalidField { field: "string" })
+    }
+
+    fn name(&mut self) -> Result<Sym, BytecodeError> {
+        let name = self.str()?;
+        if self.intern {
+            return Ok(Sym::intern(name));
+        }
+        Ok(Sym::get(name).unwrap_or_else(|| {
+            self.unknown_names = true;
+            Sym::intern("")
+        }))
+    }
+
+    fn js_string(&mut self) -> Result<JsString, BytecodeError> {
+        let len = self.len()?;
+        let bytes = self.take(len.saturating_mul(size_of::<u16>()))?;
+        let units: Vec<u16> = bytes
+            .chunks_exact(size_of::<u16>())
+            .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+            .collect();
+        Ok(JsString::from(units))
+    }
+
+    fn slot(&mut self) -> Result<Option<u32>, BytecodeError> {
+        Ok(Some(self.u32()?).filter(|&slot| slot != u32::MAX))
+    }
+
+    fn bindings(&mut self) -> Result<Vec<(Sym, BindingKind)>, BytecodeError> {
+        (0..self.len()?)
+            .map(|_| {
+                let name = self.name()?;
+                let kind = match self.u8()? {
+                    0 => BindingKind::Var,
+                    1 => BindingKind::Let,
+                    2 => BindingKind::Const,
+                    _ => {
+                        return Err(BytecodeError::InvalidField {
+                            field: "binding kind",
+                        })
+                    }
+                };
+                Ok((name, kind))
+            })
+            .collect()
+    }
+
+    fn literal(&mut self) -> Result<JsValue, BytecodeError> {
+        Ok(match self.u8()? {
+            LITERAL_UNDEFINED => JsValue::undefined(),
+            LITERAL_NULL => JsValue::null(),
+            LITERAL_FALSE => JsValue::new(false),
+            LITERAL_TRUE => JsValue::new(true),
+            LITERAL_INTEGER => JsValue::new(self.u32()? as i32),
+            LITERAL_RATIONAL => JsValue::new(self.f64()?),
+            LITERAL_STRING => JsValue::new(self.js_string()?),
+            LITERAL_BIGINT => JsBigInt::from_string_radix(self.str()?, 16)
+                .map(JsValue::new)
+                .ok_or(BytecodeError::InvalidField { field: "bigint" })?,
+            _ => return Err(BytecodeError::InvalidField { field: "literal" }),
+        })
+    }
+
+    fn code_block(&mut self) -> Result<CodeBlock, BytecodeError> {
+        let name = self.js_string()?;
+        let length = self.u32()?;
+        let flags = self.u8()?;
+        let mut code_block = CodeBlock::new(
+            name,
+            length,
+            flags & FLAG_STRICT != 0,
+            flags & FLAG_CONSTRUCTOR != 0,
+        );
+        code_block.generator = flags & FLAG_GENERATOR != 0;
+        code_block.asynchronous = flags & FLAG_ASYNC != 0;
+        code_block.this_mode = match self.u8()? {
+            0 => ThisMode::Lexical,
+            1 => ThisMode::Strict,
+            2 => ThisMode::Global,
+            _ => return Err(BytecodeError::InvalidField { field: "this mode" }),
+        };
+
+        // The initializers of the parameters are compiled into the bytecode, so the vm only
+        // needs to know whether a parameter has one.
+        code_block.params = (0..self.len()?)
+            .map(|_| {
+                let name = self.name()?;
+                let flags = self.u8()?;
+                let init = (flags & PARAMETER_INIT != 0).then(|| Node::Empty);
+                Ok(FormalParameter::new(
+                    name,
+                    init,
+                    flags & PARAMETER_REST != 0,
+                ))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let len = self.len()?;
+        code_block.code = self.take(len)?.to_vec();
+
+        code_block.literals = (0..self.len()?)
+            .map(|_| self.literal())
+            .collect::<Result<_, _>>()?;
+
+        code_block.variables = (0..self.len()?)
+            .map(|_| self.name())
+            .collect::<Result<_, _>>()?;
+
+        code_block.bindings = self.bindings()?;
+        code_block.arguments_binding = self.slot()?;
+        code_block.function_binding = self.slot()?;
+        code_block.scopes = (0..self.len()?)
+            .map(|_| self.bindings().map(Vec::into_boxed_slice))
+            .collect::<Result<_, _>>()?;
+
+        code_block.line_table = (0..self.len()?)
+            .map(|_| {
+                let pc = self.u32()?;
+                let (line, column) = (self.u32()?, self.u32()?);
+                if line == 0 || column == 0 {
+                    return Err(BytecodeError::InvalidField { field: "position" });
+                }
+                Ok((pc, Position::new(line, column)))
+            })
+            .collect::<Result<_, _>>()?;
+
+        code_block.handlers = (0..self.len()?)
+            .map(|_| {
+                Ok(Handler {
+                    start: self.u32()?,
+                    end: self.u32()?,
+                    address: self.u32()?,
+                    stack_depth: self.u32()?,
+                    environment_depth: self.u32()?,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        code_block.functions = (0..self.len()?)
+            .map(|_| self.code_block().map(Gc::new))
+            .collect::<Result<_, _>>()?;
+
+        // Every cache is used by an instruction, which bounds their number by the size of the
+        // code.
+        let caches = self.len()?;
+        if caches > code_block.code.len() {
+            return Err(BytecodeError::InvalidField {
+                field: "inline caches",
+            });
+        }
+        code_block.inline_caches = vec![InlineCache::default(); caches];
+        Ok(code_block)
+    }
+}
@@ -1,4 +1,7 @@
-use crate::exec;
+use super::{serialization::operands, BytecodeError, CodeBlock, Opcode, OptimizationLevel};
+use crate::{exec, interner::Sym, Context, JsString, JsValue};
+use gc::Gc;
+use std::convert::{TryFrom, TryInto};
 
 #[test]
 fn typeof_string() {
@@ -27,3 +30,1138 @@ fn basic_op() {
     "#;
     assert_eq!(&exec(basic_op), "3");
 }
+
+#[test]
+fn line_table() {
+    use crate::{bytecompiler::ByteCompiler, syntax::ast::Position, syntax::Parser, JsString};
+
+    let statement_list = Parser::new("let a = 1;\n\n  a + 2;".as_bytes(), false)
+        .parse_all()
+        .expect("failed to parse");
+    let mut compiler = ByteCompiler::new(JsString::new("<main>"), false);
+    compiler.compile_statement_list(&statement_list, true);
+    let code = compiler.finish();
+
+    // The code of a node belongs to the node, except for the code of its children.
+    let positions: Vec<_> = code
+        .line_table
+        .iter()
+        .map(|&(_, position)| position)
+        .collect();
+    assert_eq!(
+        positions,
+        [
+            Position::new(1, 9),
+            Position::new(1, 1),
+            Position::new(3, 3),
+            Position::new(3, 7),
+            Position::new(3, 3),
+        ]
+    );
+    assert_eq!(code.position(0), Some(Position::new(1, 9)));
+    let (second, _) = code.line_table[2];
+    assert_eq!(
+        code.position(second as usize - 1),
+        Some(Position::new(1, 1))
+    );
+    assert_eq!(code.position(code.code.len()), Some(Position::new(3, 3)));
+}
+
+#[test]
+fn function_bindings_are_resolved_to_slots() {
+    let scenario = r#"
+        function add(a, b) {
+            var sum = a + b;
+            let twice = sum * 2;
+            return twice;
+        }
+        add(1, 2);
+    "#;
+    assert_eq!(&exec(scenario), "6");
+}
+
+#[test]
+fn closures_access_outer_scopes_by_depth() {
+    let scenario = r#"
+        function counter() {
+            let count = 0;
+            return () => {
+                count = count + 1;
+                return count;
+            };
+        }
+        const next = counter();
+        next();
+        next();
+        next();
+    "#;
+    assert_eq!(&exec(scenario), "3");
+}
+
+#[test]
+fn block_scopes_shadow_outer_bindings() {
+    let scenario = r#"
+        function f() {
+            let x = 1;
+            {
+                let x = 2;
+                x = x + 1;
+            }
+            return x;
+        }
+        f();
+    "#;
+    assert_eq!(&exec(scenario), "1");
+}
+
+#[test]
+fn block_scopes_are_fresh_for_each_iteration() {
+    let scenario = r#"
+        function f() {
+            var closures = [];
+            var i = 0;
+            while (i < 3) {
+                let captured = i;
+                closures[i] = () => captured;
+                i = i + 1;
+            }
+            return closures[0]() + closures[1]() + closures[2]();
+        }
+        f();
+    "#;
+    assert_eq!(&exec(scenario), "3");
+}
+
+#[test]
+fn break_and_continue_leave_block_scopes() {
+    let scenario = r#"
+        function f() {
+            var result = 0;
+            var i = 0;
+            while (true) {
+                let j = i;
+                i = i + 1;
+                if (j == 1) {
+                    continue;
+                }
+                if (j == 3) {
+                    break;
+                }
+                result = result + j;
+            }
+            return result;
+        }
+        f();
+    "#;
+    assert_eq!(&exec(scenario), "2");
+}
+
+#[test]
+fn lexical_bindings_have_a_temporal_dead_zone() {
+    let scenario = r#"
+        {
+            let before = x;
+            let x = 1;
+        }
+    "#;
+    assert!(exec(scenario).contains("ReferenceError"));
+}
+
+#[test]
+fn unresolved_names_fall_back_to_the_global_object() {
+    let scenario = r#"
+        var global = 40;
+        function f() {
+            {
+                let local = 2;
+                return global + local;
+            }
+        }
+        f();
+    "#;
+    assert_eq!(&exec(scenario), "42");
+}
+
+#[test]
+fn scope_analysis_emits_slot_accesses() {
+    use crate::{bytecompiler::ByteCompiler, syntax::Parser, JsString};
+
+    fn opcodes(code: &CodeBlock) -> Vec<Opcode> {
+        let mut opcodes = Vec::new();
+        let mut pc = 0;
+        while pc < code.code.len() {
+            opcodes.push(code.code[pc].try_into().unwrap());
+            code.instruction_operands(&mut pc);
+        }
+        opcodes
+    }
+
+    let source = "function f(a) { var b = a; { let c = b; } return g(c); }";
+    let statement_list = Parser::new(source.as_bytes(), false)
+        .parse_all()
+        .expect("failed to parse");
+    let mut compiler = ByteCompiler::new(JsString::new("<main>"), false);
+    compiler.compile_statement_list(&statement_list, true);
+    let code = compiler.finish();
+    let function = &code.functions[0];
+
+    assert_eq!(function.bindings.len(), 2);
+    assert_eq!(function.scopes.len(), 1);
+    let opcodes = opcodes(function);
+    assert!(opcodes.contains(&Opcode::GetLocal));
+    assert!(opcodes.contains(&Opcode::InitLocal));
+    assert!(opcodes.contains(&Opcode::PushDeclarativeEnvironment));
+    // `g` and the `c` outside of the block are not declared in any scope.
+    assert_eq!(
+        opcodes
+            .iter()
+            .filter(|&&opcode| opcode == Opcode::GetName)
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn direct_eval_disables_slot_resolution_through_its_scope() {
+    use crate::{bytecompiler::ByteCompiler, syntax::Parser, JsString};
+
+    let source = "function f(a) { eval('var b'); return () => a + b; }";
+    let statement_list = Parser::new(source.as_bytes(), false)
+        .parse_all()
+        .expect("failed to parse");
+    let mut compiler = ByteCompiler::new(JsString::new("<main>"), false);
+    compiler.compile_statement_list(&statement_list, true);
+    let code = compiler.finish();
+    let arrow = &code.functions[0].functions[0];
+
+    let mut pc = 0;
+    let mut names = Vec::new();
+    while pc < arrow.code.len() {
+        let opcode: Opcode = arrow.code[pc].try_into().unwrap();
+        let operands = arrow.instruction_operands(&mut pc);
+        if opcode == Opcode::GetName {
+            names.push(operands);
+        }
+    }
+    // `a` is declared in the scope of `f`, but `b` could be added to it by the `eval`.
+    assert_eq!(names.len(), 1);
+    assert!(names[0].ends_with("'b'"));
+}
+
+#[test]
+fn update_expressions() {
+    let scenario = r#"
+        let a = 1;
+        const o = { x: 1 };
+        const arr = [1];
+        let r = a++ + ++a;
+        r = r + o.x++ + --o["x"] + arr[0]--;
+        [r, a, o.x, arr[0]].join();
+    "#;
+    assert_eq!(&exec(scenario), "\"7,3,1,0\"");
+}
+
+#[test]
+fn object_literals() {
+    let scenario = r#"
+        const key = "b";
+        const o = {
+            a: 1,
+            [key]: 2,
+            c() { return this.a + this.b; },
+            get d() { return 4; },
+            set e(v) { this.a = v; },
+            ...{ f: 6 },
+        };
+        o.e = 10;
+        [o.c(), o.d, o.f].join();
+    "#;
+    assert_eq!(&exec(scenario), "\"12,4,6\"");
+}
+
+#[test]
+fn spread_in_arrays_and_calls() {
+    let scenario = r#"
+        function sum(a, b, c, d) { return a + b + c + d; }
+        const parts = [2, 3];
+        const all = [1, ...parts, 4];
+        sum(...all) + all.length;
+    "#;
+    assert_eq!(&exec(scenario), "14");
+}
+
+#[test]
+fn destructuring_patterns() {
+    let scenario = r#"
+        const { a, b: { c = 3 }, ...rest } = { a: 1, b: {}, d: 4, e: 5 };
+        let [x, , y = 7, ...ys] = [10, 20, undefined, 30, 40];
+        [a, c, rest.d + rest.e, x, y, ys.join("-")].join();
+    "#;
+    assert_eq!(&exec(scenario), "\"1,3,9,10,7,30-40\"");
+}
+
+#[test]
+fn for_loops() {
+    let scenario = r#"
+        const closures = [];
+        for (let i = 0; i < 3; i++) {
+            closures.push(() => i);
+        }
+        let keys = "";
+        for (const key in { a: 1, b: 2 }) {
+            keys += key;
+        }
+        let sum = 0;
+        for (const [value] of [[1], [2], [3]]) {
+            if (value == 2) continue;
+            sum += value;
+        }
+        closures.map(f => f()).join() + keys + sum;
+    "#;
+    assert_eq!(&exec(scenario), "\"0,1,2ab4\"");
+}
+
+#[test]
+fn try_catch_finally() {
+    let scenario = r#"
+        const log = [];
+        function f() {
+            try {
+                log.push("try");
+                throw new Error("boom");
+            } catch ({ message }) {
+                log.push(message);
+            } finally {
+                log.push("finally");
+            }
+            try {
+                try {
+                    throw 1;
+                } finally {
+                    log.push("inner");
+                }
+            } catch (e) {
+                log.push(e);
+            }
+        }
+        f();
+        log.join();
+    "#;
+    assert_eq!(&exec(scenario), "\"try,boom,finally,inner,1\"");
+}
+
+#[test]
+fn template_literals() {
+    let scenario = r#"
+        function tag(strings, ...values) {
+            return strings.raw.join("|") + values.join();
+        }
+        const name = "world";
+        `hello ${name}!` + tag`a${1}b${2}c`;
+    "#;
+    assert_eq!(&exec(scenario), "\"hello world!a|b|c1,2\"");
+}
+
+#[test]
+fn new_expressions() {
+    let scenario = r#"
+        function Point(x, y) {
+            this.x = x;
+            this.y = y;
+        }
+        Point.prototype.sum = function () { return this.x + this.y; };
+        const p = new Point(...[1, 2]);
+        [p.sum(), p instanceof Point].join();
+    "#;
+    assert_eq!(&exec(scenario), "\"3,true\"");
+}
+
+#[test]
+fn default_rest_parameters_and_arguments() {
+    let scenario = r#"
+        function f(a, b = a + 1, ...rest) {
+            return [a, b, rest.length, arguments.length].join();
+        }
+        f(1) + ";" + f(1, 5, 6, 7);
+    "#;
+    assert_eq!(&exec(scenario), "\"1,2,0,1;1,5,2,4\"");
+}
+
+#[test]
+fn natives_call_bytecode_functions() {
+    let scenario = r#"
+        const scale = { factor: 10 };
+        const mapped = [1, 2, 3].map(function (x, ...rest) {
+            return x * this.factor + rest.length;
+        }, scale);
+        const nested = [[1, 2], [3]].map(inner => inner.reduce((a, b) => a + b));
+        mapped.join() + ";" + nested.join();
+    "#;
+    assert_eq!(&exec(scenario), "\"12,22,32;3,3\"");
+}
+
+#[test]
+fn natives_construct_bytecode_functions() {
+    let scenario = r#"
+        function Base() { this.kind = "base"; }
+        function Other() {}
+        Other.prototype.tag = "other";
+        const made = Reflect.construct(Base, [], Other);
+        [made.kind, made.tag, made instanceof Other].join();
+    "#;
+    assert_eq!(&exec(scenario), "\"base,other,true\"");
+}
+
+#[test]
+fn exceptions_propagate_through_native_frames() {
+    let scenario = r#"
+        let caught;
+        try {
+            [1, 2, 3].forEach(x => {
+                if (x == 2) throw new RangeError("stop at " + x);
+            });
+        } catch (e) {
+            caught = e instanceof RangeError ? e.message : "wrong error";
+        }
+        caught;
+    "#;
+    assert_eq!(&exec(scenario), "\"stop at 2\"");
+}
+
+#[test]
+fn native_constructors_called_as_functions() {
+    let scenario = r#"
+        [typeof Number("1"), typeof new Number(1), String(12), typeof Symbol()].join();
+    "#;
+    assert_eq!(&exec(scenario), "\"number,object,12,symbol\"");
+}
+
+#[test]
+fn finally_runs_when_leaving_try_blocks() {
+    let scenario = r#"
+        const log = [];
+        function early() {
+            try {
+                try {
+                    return "returned";
+                } finally {
+                    log.push("inner");
+                }
+            } finally {
+                log.push("outer");
+            }
+        }
+        function loops() {
+            for (const i of [0, 1, 2]) {
+                try {
+                    if (i == 0) continue;
+                    if (i == 2) break;
+                    log.push("body" + i);
+                } finally {
+                    log.push("finally" + i);
+                }
+            }
+        }
+        log.push(early());
+        loops();
+        log.join();
+    "#;
+    assert_eq!(
+        &exec(scenario),
+        "\"inner,outer,returned,finally0,body1,finally1,finally2\""
+    );
+}
+
+#[test]
+fn finally_completions_override_the_try_block() {
+    let scenario = r#"
+        let runs = 0;
+        function overrides() {
+            try {
+                return 1;
+            } finally {
+                return 2;
+            }
+        }
+        function throws() {
+            try {
+                return 1;
+            } finally {
+                runs++;
+                throw "from finally";
+            }
+        }
+        let caught;
+        try {
+            throws();
+        } catch (e) {
+            caught = e;
+        }
+        [overrides(), caught, runs].join();
+    "#;
+    assert_eq!(&exec(scenario), "\"2,from finally,1\"");
+}
+
+#[test]
+fn exceptions_restore_the_stack_and_environments() {
+    let scenario = r#"
+        function f() {
+            let result = "";
+            for (const key in { a: 1, b: 2 }) {
+                try {
+                    let inner = key;
+                    {
+                        let deeper = inner + "!";
+                        throw deeper;
+                    }
+                } catch (e) {
+                    result += e;
+                }
+            }
+            return result;
+        }
+        f();
+    "#;
+    assert_eq!(&exec(scenario), "\"a!b!\"");
+}
+
+#[test]
+fn generators_suspend_and_resume() {
+    let scenario = r#"
+        function* counter(start) {
+            const step = yield start;
+            try {
+                yield start + step;
+            } finally {
+                yield "cleanup";
+            }
+            return "end";
+        }
+        const log = [];
+        let it = counter(1);
+        log.push(it.next(5).value, it.next(2).value, it.next().value);
+        let last = it.next();
+        log.push(last.value, last.done, it.next().done);
+
+        it = counter(1);
+        it.next();
+        it.next(1);
+        log.push(it.return("early").value, it.next().value);
+
+        it = counter(1);
+        it.next();
+        it.next(1);
+        try {
+            it.throw("error");
+            it.next();
+        } catch (e) {
+            log.push(e);
+        }
+        log.join();
+    "#;
+    assert_eq!(
+        &exec(scenario),
+        "\"1,3,cleanup,end,true,true,cleanup,early,error\""
+    );
+}
+
+#[test]
+fn generators_are_iterable() {
+    let scenario = r#"
+        function* inner() {
+            yield 1;
+            yield 2;
+            return 3;
+        }
+        function* outer() {
+            const result = yield* inner();
+            yield result;
+            yield* [4, 5];
+        }
+        const object = {
+            *method() {
+                yield this.value;
+            },
+            value: 6,
+        };
+        const closed = [];
+        function* loop() {
+            for (const x of [7, 8]) {
+                try {
+                    yield x;
+                } finally {
+                    closed.push(x);
+                }
+            }
+        }
+        for (const x of loop()) {
+            break;
+        }
+        [...outer(), ...object.method(), closed].join();
+    "#;
+    assert_eq!(&exec(scenario), "\"1,2,3,4,5,6,7\"");
+}
+
+#[test]
+fn generator_objects() {
+    let scenario = r#"
+        function* g() {}
+        const it = g();
+        let constructed = false;
+        try {
+            new g();
+            constructed = true;
+        } catch (e) {}
+        [
+            Object.getPrototypeOf(it) === g.prototype,
+            Object.prototype.toString.call(it),
+            it[Symbol.iterator]() === it,
+            constructed,
+        ].join();
+    "#;
+    assert_eq!(&exec(scenario), "\"true,[object Generator],true,false\"");
+}
+
+#[test]
+fn generator_methods_reject_other_receivers() {
+    let scenario = r#"
+        function* g() {}
+        var n = g().next;
+        try {
+            n();
+        } catch (e) {
+            e.name
+        }
+    "#;
+    assert_eq!(&exec(scenario), "\"TypeError\"");
+
+    let scenario = r#"
+        function* g() {}
+        try {
+            g().next.call(TypeError.prototype);
+        } catch (e) {
+            e.name
+        }
+    "#;
+    assert_eq!(&exec(scenario), "\"TypeError\"");
+}
+
+#[test]
+fn async_functions_resume_after_the_script() {
+    let mut context = Context::default();
+    let script = r#"
+        var log = [];
+        async function f() {
+            log.push("start");
+            await 1;
+            log.push("resumed");
+            await {
+                then(resolve) {
+                    log.push("then");
+                    resolve();
+                },
+            };
+            try {
+                await {
+                    then(resolve, reject) {
+                        reject("rejected");
+                    },
+                };
+            } catch (e) {
+                log.push(e);
+            }
+        }
+        f();
+        log.push("script");
+        log.join();
+    "#;
+    let log = context.eval(script).unwrap();
+    assert_eq!(&log.display().to_string(), "\"start,script\"");
+    let log = context.eval("log.join()").unwrap();
+    assert_eq!(
+        &log.display().to_string(),
+        "\"start,script,resumed,then,rejected\""
+    );
+}
+
+#[test]
+fn disassemble() {
+    let mut context = Context::new();
+    let code = context
+        .compile(
+            r#"
+            function f(a) {
+                return a || "none";
+            }
+            f(1);
+        "#,
+        )
+        .unwrap();
+    let disassembly = code.disassemble();
+
+    assert!(disassembly.starts_with("function <main> '<main>' (length: 0)\n"));
+    assert!(disassembly.contains("\n  Names:\n    0000: f\n"));
+    assert!(disassembly.contains("\n  Functions:\n    0000: <main>/0 'f'\n"));
+    assert!(disassembly.contains("\nfunction <main>/0 'f' (length: 1, constructor)\n"));
+    assert!(disassembly.contains("\n    ; 3:17\n"));
+    assert!(disassembly.contains("LogicalOr                L0\n"));
+    assert!(disassembly.contains("\n  L0:\n"));
+    assert!(disassembly.contains("\n  Literals:\n    0000: <string> \"none\"\n"));
+}
+
+#[test]
+fn serialized_scripts_run_without_the_parser() {
+    let script = r#"
+        function* range(n = 3) {
+            for (let i = 0; i < n; i++) {
+                yield i;
+            }
+        }
+        function f(a, ...rest) {
+            try {
+                throw arguments.length + rest.length;
+            } catch (e) {
+                return `${a}:${e}:${10n ** 20n}`;
+            }
+        }
+        let values = [];
+        for (const value of range()) {
+            values.push(value);
+        }
+        f("\ud800", 1, 2) + " " + values.join();
+    "#;
+    let code = Context::new().compile(script).unwrap();
+    let bytes = code.to_bytes();
+    let loaded = CodeBlock::from_bytes(&bytes).unwrap();
+
+    assert_eq!(loaded.disassemble(), code.disassemble());
+    assert_eq!(loaded.to_bytes(), bytes);
+    let value = Context::new().execute(loaded).unwrap();
+    assert_eq!(
+        value.as_string().unwrap().as_slice(),
+        Context::new()
+            .eval(script)
+            .unwrap()
+            .as_string()
+            .unwrap()
+            .as_slice()
+    );
+}
+
+#[test]
+fn serialized_names_are_interned_once_valid() {
+    let code = Context::new()
+        .compile("var serializedName0 = 1; serializedName0;")
+        .unwrap();
+    let bytes = code.to_bytes();
+    let renamed = |name: &str| {
+        let mut renamed = bytes.clone();
+        let old = b"serializedName0";
+        let mut i = 0;
+        while i + old.len() <= renamed.len() {
+            if &renamed[i..i + old.len()] == old {
+                renamed[i..i + old.len()].copy_from_slice(name.as_bytes());
+            }
+            i += 1;
+        }
+        renamed
+    };
+
+    let mut invalid = renamed("serializedName1");
+    invalid.push(0);
+    assert_eq!(
+        CodeBlock::from_bytes(&invalid).unwrap_err(),
+        BytecodeError::TrailingBytes
+    );
+    assert_eq!(Sym::get("serializedName1"), None);
+
+    let loaded = CodeBlock::from_bytes(&renamed("serializedName2")).unwrap();
+    assert!(Sym::get("serializedName2").is_some());
+    let value = Context::new().execute(loaded).unwrap();
+    assert_eq!(value.as_number(), Some(1.0));
+}
+
+#[test]
+fn invalid_serialized_scripts() {
+    let code = Context::new()
+        .compile("function f(a) { return a ? 1 : 2; } f(true);")
+        .unwrap();
+    let bytes = code.to_bytes();
+
+    assert_eq!(
+        CodeBlock::from_bytes(b"not bytecode").unwrap_err(),
+        BytecodeError::InvalidMagic
+    );
+    let mut other_version = bytes.clone();
+    other_version[4] ^= 0xff;
+    assert!(matches!(
+        CodeBlock::from_bytes(&other_version),
+        Err(BytecodeError::UnsupportedVersion { .. })
+    ));
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(
+        CodeBlock::from_bytes(&trailing).unwrap_err(),
+        BytecodeError::TrailingBytes
+    );
+    for len in 0..bytes.len() {
+        assert!(CodeBlock::from_bytes(&bytes[..len]).is_err());
+    }
+
+    // Make the conditional jump of `f` target the middle of an instruction.
+    let mut function = CodeBlock::new(JsString::new("f"), 1, false, true);
+    function.code = code.functions[0].code.clone();
+    let jump = function
+        .code
+        .iter()
+        .position(|&byte| byte == Opcode::JumpIfFalse as u8)
+        .unwrap();
+    function.code[jump + 1] += 1;
+    let mut script = CodeBlock::new(JsString::new("<main>"), 0, false, false);
+    script.functions.push(Gc::new(function));
+    assert!(matches!(
+        CodeBlock::from_bytes(&script.to_bytes()),
+        Err(BytecodeError::InvalidCode {
+            message: "jump target is not an instruction",
+            ..
+        })
+    ));
+
+    let error = |code: &CodeBlock| match CodeBlock::from_bytes(&code.to_bytes()) {
+        Err(BytecodeError::InvalidCode { message, .. }) => message,
+        result => panic!("expected invalid code, got {:?}", result.map(|_| ())),
+    };
+
+    // Make `f` read a slot and an environment that it doesn't have.
+    let get = code.functions[0]
+        .code
+        .iter()
+        .position(|&byte| byte == Opcode::GetLocal as u8)
+        .unwrap();
+    for (offset, value, message) in [
+        (5, 50, "binding slot out of range"),
+        (1, 1, "environment out of range"),
+    ] {
+        let mut function = (*code.functions[0]).clone();
+        function.code[get + offset..get + offset + 4].copy_from_slice(&u32::to_le_bytes(value));
+        let mut script = code.clone();
+        script.functions[0] = Gc::new(function);
+        assert_eq!(error(&script), message);
+    }
+
+    // Call `f` without pushing the `this` value first.
+    let mut script = code.clone();
+    let this = script
+        .code
+        .iter()
+        .position(|&byte| byte == Opcode::This as u8)
+        .unwrap();
+    script.code[this] = Opcode::Pop as u8;
+    assert_eq!(error(&script), "stack underflow");
+
+    // Make the handler keep values and environments that the protected code doesn't have.
+    let code = Context::new()
+        .compile("try { throw 1; } catch (e) { e; }")
+        .unwrap();
+    let mut script = code.clone();
+    script.handlers[0].stack_depth = 1;
+    assert_eq!(error(&script), "invalid exception handler");
+    let mut script = code;
+    script.handlers[0].environment_depth = 1;
+    assert_eq!(error(&script), "invalid exception handler");
+}
+
+#[test]
+fn validated_operands_match_the_vm() {
+    for byte in 0..=Opcode::Nop as u8 {
+        let opcode = Opcode::try_from(byte).unwrap();
+        let mut code = CodeBlock::new(JsString::new("test"), 0, false, false);
+        code.code = vec![0; 16];
+        code.code[0] = byte;
+        code.literals.push(JsValue::undefined());
+        code.variables.push(Sym::intern("test"));
+        code.functions
+            .push(Gc::new(CodeBlock::new(JsString::new("f"), 0, false, false)));
+
+        let mut pc = 0;
+        code.instruction_operands(&mut pc);
+        let size = operands(opcode)
+            .iter()
+            .map(|operand| operand.size())
+            .sum::<usize>();
+        assert_eq!(pc, 1 + size, "operands of {:?}", opcode);
+    }
+}
+
+#[test]
+fn optimizer_folds_constants() {
+    let mut context = Context::new();
+    let code = context
+        .compile(r#"(1 + 2 * 3) + " apples " + typeof -(4)"#)
+        .unwrap();
+    let disassembly = code.disassemble();
+
+    for opcode in ["Add", "Mul", "Neg", "TypeOf"] {
+        assert!(!disassembly.contains(opcode), "{}", disassembly);
+    }
+    assert!(disassembly.ends_with("\n  Literals:\n    0000: <string> \"7 apples number\"\n"));
+    assert_eq!(
+        &context.execute(code).unwrap().display().to_string(),
+        "\"7 apples number\""
+    );
+
+    // Mixing bigints and numbers throws, so it is left to the vm.
+    let code = context.compile("1n + 1").unwrap();
+    assert!(code.disassemble().contains("Add"));
+    assert!(context.execute(code).is_err());
+}
+
+#[test]
+fn optimizer_threads_jumps_and_removes_dead_code() {
+    let mut context = Context::new();
+    let code = context
+        .compile(
+            r#"
+            function f(x) {
+                while (true) {
+                    if (x) {
+                        break;
+                    }
+                    x = 1;
+                }
+                return x;
+                x = 2;
+            }
+            f(0);
+        "#,
+        )
+        .unwrap();
+    let function = code.disassemble();
+    let function = &function[function.find("function <main>/0").unwrap()..];
+
+    assert!(!function.contains("PushTrue"), "{}", function);
+    assert!(!function.contains("PushInt8"), "{}", function);
+    assert!(!function.contains("PushUndefined"), "{}", function);
+    assert_eq!(function.matches("Jump").count(), 3, "{}", function);
+    assert_eq!(context.execute(code).unwrap(), JsValue::new(1));
+}
+
+#[test]
+fn optimizer_removes_jumps_that_become_jumps_to_next() {
+    let mut context = Context::new();
+    let code = context
+        .compile("var a = 3; for (let i = 0; i < a; i++) { if (i) continue; } a")
+        .unwrap();
+    let disassembly = code.disassemble();
+
+    let lines: Vec<_> = disassembly.lines().map(str::trim).collect();
+    for pair in lines.windows(2) {
+        if let Some(label) = pair[0].split("Jump").nth(1) {
+            let label = label.split_whitespace().last().unwrap();
+            assert_ne!(pair[1], format!("{}:", label), "{}", disassembly);
+        }
+    }
+    assert_eq!(context.execute(code).unwrap(), JsValue::new(3));
+}
+
+#[test]
+fn optimizer_emits_superinstructions() {
+    let script = r#"
+        function count(n) {
+            let total = 0;
+            for (let i = 0; i < n; i++) {
+                total += i;
+            }
+            for (let i = n; 0 <= i; i--) {
+                total += i;
+            }
+            return total;
+        }
+        count(10);
+    "#;
+    let mut context = Context::new();
+    let code = context.compile(script).unwrap();
+    let disassembly = code.disassemble();
+
+    for opcode in [
+        "IncLocal",
+        "DecLocal",
+        "JumpIfNotLessThan ",
+        "JumpIfNotLessThanOrEq",
+    ] {
+        assert!(disassembly.contains(opcode), "{}", disassembly);
+    }
+    assert_eq!(context.execute(code).unwrap(), JsValue::new(100));
+
+    context.set_optimization_level(OptimizationLevel::Basic);
+    let code = context.compile(script).unwrap();
+    assert!(!code.disassemble().contains("IncLocal"));
+    assert_eq!(context.execute(code).unwrap(), JsValue::new(100));
+}
+
+#[test]
+fn optimization_level_none_keeps_the_compiled_code() {
+    use crate::{bytecompiler::ByteCompiler, syntax::Parser};
+
+    let script = "let a = 1 + 2; if (true) { a; }";
+    let mut context = Context::new();
+    context.set_optimization_level(OptimizationLevel::None);
+    assert_eq!(context.optimization_level(), OptimizationLevel::None);
+
+    let statement_list = Parser::new(script.as_bytes(), false).parse_all().unwrap();
+    let mut compiler = ByteCompiler::new(JsString::new("<main>"), false);
+    compiler.compile_statement_list(&statement_list, true);
+
+    assert_eq!(
+        context.compile(script).unwrap().code,
+        compiler.finish().code
+    );
+}
+
+#[test]
+fn inline_caches_follow_shape_changes() {
+    let scenario = r#"
+        "use strict";
+        function get(o) { return o.x; }
+        function set(o, v) { o.x = v; }
+        const results = [];
+
+        const proto = { x: "proto" };
+        const o = Object.create(proto);
+        results.push(get(o), get(o));
+        proto.x = "changed";
+        results.push(get(o));
+        set(o, "own");
+        results.push(get(o), get(proto));
+        set(o, "again");
+        results.push(get(o));
+        delete o.x;
+        results.push(get(o));
+        Object.setPrototypeOf(o, { x: "other" });
+        results.push(get(o));
+
+        const p = { x: 1 };
+        set(p, 2);
+        results.push(get(p));
+        Object.defineProperty(p, "x", { get() { return "getter"; } });
+        results.push(get(p));
+        Object.defineProperty(p, "x", { value: 3, writable: false });
+        results.push(get(p));
+        try {
+            set(p, 4);
+        } catch (e) {
+            results.push(e.name);
+        }
+
+        for (let i = 0; i < 20; i++) {
+            const q = { ["k" + i]: i, x: i };
+            set(q, get(q) + 1);
+            if (get(q) !== i + 1) {
+                results.push("wrong");
+            }
+        }
+        results.join(",");
+    "#;
+    assert_eq!(
+        &exec(scenario),
+        "\"proto,proto,changed,own,changed,again,changed,other,2,getter,3,TypeError\""
+    );
+}
+
+#[test]
+fn inline_cache_states() {
+    use super::InlineCache;
+
+    let mut context = Context::new();
+    let objects = context
+        .eval(
+            "[{ x: 0 }, { x: 1 }, { y: 0, x: 2 }, { z: 0, x: 3 }, { w: 0, x: 4 }, { v: 0, x: 5 }]",
+        )
+        .unwrap();
+    let objects: Vec<_> = (0..6)
+        .map(|i| {
+            objects
+                .get_field(i, &mut context)
+                .unwrap()
+                .as_object()
+                .unwrap()
+                .clone()
+        })
+        .collect();
+    let x = Sym::intern("x");
+
+    let cache = InlineCache::default();
+    assert_eq!(cache.get(&objects[0], x), Some(JsValue::new(0)));
+    assert_eq!(cache.len(), 1);
+    // Objects with the same layout share an entry.
+    assert_eq!(cache.get(&objects[1], x), Some(JsValue::new(1)));
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.get(&objects[2], x), Some(JsValue::new(2)));
+    assert_eq!(cache.len(), 2);
+    for (i, object) in objects.iter().enumerate().take(5) {
+        assert_eq!(cache.get(object, x), Some(JsValue::new(i)));
+    }
+    assert_eq!(cache.len(), 4);
+    assert_eq!(cache.get(&objects[5], x), Some(JsValue::new(5)));
+    assert!(cache.is_megamorphic());
+    assert_eq!(cache.get(&objects[0], x), None);
+
+    // Properties that cannot be cached are left to `[[Get]]`.
+    let cache = InlineCache::default();
+    assert_eq!(cache.get(&objects[0], Sym::intern("missing")), None);
+    assert_eq!(cache.len(), 0);
+
+    let cache = InlineCache::default();
+    assert!(cache.set(&objects[1], x, &JsValue::new(10)));
+    assert!(cache.set(&objects[1], x, &JsValue::new(11)));
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.get(&objects[1], x), Some(JsValue::new(11)));
+
+    // Adding a property is left to `[[Set]]`.
+    let cache = InlineCache::default();
+    assert!(!cache.set(&objects[1], Sym::intern("z"), &JsValue::new(0)));
+    assert_eq!(cache.len(), 0);
+}
+
+#[test]
+fn labelled_block_breaks_at_every_optimization_level() {
+    let scripts = [
+        ("label: { break label; } 'after'", JsValue::new("after")),
+        (
+            "function f(){ a: { break a; } return 1 } f()",
+            JsValue::new(1),
+        ),
+        ("var x = 0; a: { x = 1; break a; } x", JsValue::new(1)),
+    ];
+    for level in [
+        OptimizationLevel::None,
+        OptimizationLevel::Basic,
+        OptimizationLevel::Full,
+    ] {
+        for (script, expected) in &scripts {
+            let mut context = Context::new();
+            context.set_optimization_level(level);
+            assert_eq!(
+                &context.eval(script).unwrap(),
+                expected,
+                "{} at {:?}",
+                script,
+                level
+            );
+        }
+    }
+}
+
+#[test]
+fn optimizer_leaves_jumps_out_of_the_code() {
+    let mut context = Context::new();
+    let mut code = CodeBlock::new(JsString::new("<main>"), 0, false, false);
+    code.code.push(Opcode::Jump as u8);
+    code.code.extend(&u32::MAX.to_ne_bytes());
+    code.code.push(Opcode::PushUndefined as u8);
+    code.code.push(Opcode::Return as u8);
+    let original = code.code.clone();
+
+    code.optimize(OptimizationLevel::Full, &mut context);
+    assert_eq!(code.code, original);
+}
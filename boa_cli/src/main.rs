@@ -25,7 +25,10 @@
     clippy::as_conversions
 )]
 
-use boa::{syntax::ast::node::StatementList, Context};
+use boa::{
+    syntax::{ast::node::StatementList, estree::to_estree},
+    Context,
+};
 use colored::*;
 use rustyline::{config::Config, error::ReadlineError, EditMode, Editor};
 use std::{fs::read, path::PathBuf};
@@ -71,6 +74,11 @@ struct Opt {
     #[structopt(long = "trace", short = "t")]
     trace: bool,
 
+    /// Dump the bytecode that the scripts compile to, instead of running them.
+    #[cfg(feature = "vm")]
+    #[structopt(long = "dump-bytecode", short = "b")]
+    dump_bytecode: bool,
+
     /// Use vi mode in the REPL
     #[structopt(long = "vi")]
     vi_mode: bool,
@@ -79,6 +87,10 @@ struct Opt {
 impl Opt {
     /// Returns whether a dump flag has been used.
     fn has_dump_flag(&self) -> bool {
+        #[cfg(feature = "vm")]
+        if self.dump_bytecode {
+            return true;
+        }
         self.dump_ast.is_some()
     }
 }
@@ -102,6 +114,12 @@ arg_enum! {
 
         // This is a pretty printed json format.
         JsonPretty,
+
+        // This is a minified json format following the ESTree specification.
+        Estree,
+
+        // This is a pretty printed json format following the ESTree specification.
+        EstreePretty,
     }
 }
 
@@ -118,11 +136,11 @@ fn parse_tokens<T: AsRef<[u8]>>(src: T) -> Result<StatementList, String> {
         .map_err(|e| format!("ParsingError: {}", e))
 }
 
-/// Dumps the AST to stdout with format controlled by the given arguments.
+/// Dumps the AST or the bytecode to stdout with format controlled by the given arguments.
 ///
 /// Returns a error of type String with a error message,
 /// if the source has a syntax or parsing error.
-fn dump<T: AsRef<[u8]>>(src: T, args: &Opt) -> Result<(), String> {
+fn dump<T: AsRef<[u8]>>(src: T, args: &Opt, context: &mut Context) -> Result<(), String> {
     let src_bytes: &[u8] = src.as_ref();
     if let Some(ref arg) = args.dump_ast {
         let ast = parse_tokens(src_bytes)?;
@@ -134,12 +152,30 @@ fn dump<T: AsRef<[u8]>>(src: T, args: &Opt) -> Result<(), String> {
                 DumpFormat::JsonPretty => {
                     println!("{}", serde_json::to_string_pretty(&ast).unwrap())
                 }
+                DumpFormat::Estree => {
+                    let estree = to_estree(&ast, &String::from_utf8_lossy(src_bytes));
+                    println!("{}", serde_json::to_string(&estree).unwrap())
+                }
+                DumpFormat::EstreePretty => {
+                    let estree = to_estree(&ast, &String::from_utf8_lossy(src_bytes));
+                    println!("{}", serde_json::to_string_pretty(&estree).unwrap())
+                }
             },
             // Default ast dumping format.
             None => println!("{:#?}", ast),
         }
     }
 
+    #[cfg(feature = "vm")]
+    if args.dump_bytecode {
+        let code = context
+            .compile(src_bytes)
+            .map_err(|e| format!("Uncaught {}", e.display()))?;
+        print!("{}", code.disassemble());
+    }
+    #[cfg(not(feature = "vm"))]
+    let _ = context;
+
     Ok(())
 }
 
@@ -156,7 +192,7 @@ pub fn main() -> Result<(), std::io::Error> {
         let buffer = read(file)?;
 
         if args.has_dump_flag() {
-            if let Err(e) = dump(&buffer, &args) {
+            if let Err(e) = dump(&buffer, &args, &mut context) {
                 eprintln!("{}", e);
             }
         } else {
@@ -192,7 +228,7 @@ pub fn main() -> Result<(), std::io::Error> {
                     editor.add_history_entry(&line);
 
                     if args.has_dump_flag() {
-                        if let Err(e) = dump(&line, &args) {
+                        if let Err(e) = dump(&line, &args, &mut context) {
                             eprintln!("{}", e);
                         }
                     } else {
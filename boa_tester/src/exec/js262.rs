@@ -87,7 +87,7 @@ fn eval_script(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsRe
     // eprintln!("called $262.evalScript()");
 
     if let Some(source_text) = args.get(0).and_then(|val| val.as_string()) {
-        match boa::parse(source_text.as_str(), false) {
+        match boa::parse(source_text.to_std_string_lossy(), false) {
             // TODO: check strict
             Err(e) => context.throw_type_error(format!("Uncaught Syntax Error: {}", e)),
             Ok(script) => script.run(context),